@@ -0,0 +1,1416 @@
+// Port of am_map.c. The automap draws the lines the player has seen, and
+// everything with the IDDT cheat, over the whole framebuffer instead of
+// the player's view. It can follow the player or be panned and zoomed
+use crate::fixed::{fixed_div, fixed_mul, Angle, Fixed, FRACBITS, FRACUNIT};
+use crate::game::event::{
+    Event, Responder, KEY_DOWNARROW, KEY_LEFTARROW, KEY_RIGHTARROW, KEY_TAB, KEY_UPARROW,
+};
+use crate::graphics::Picture;
+use crate::m_cheat::CheatSequence;
+use crate::play::blockmap::MAPBLOCKUNITS;
+use crate::play::level::{Level, Line, ML_DONTDRAW, ML_MAPPED, ML_SECRET};
+use crate::play::mobj::Mobj;
+use crate::play::player::{Player, PW_ALLMAP, PW_INVISIBILITY};
+use crate::play::world::{Deathmatch, World};
+use crate::play::MAXPLAYERS;
+use crate::render::{Framebuffer, SCREENHEIGHT, SCREENWIDTH};
+use crate::wad::{self, DoomFile};
+
+pub mod export;
+
+// For use if I do walls with outsides/insides
+const REDS: u8 = (256 - 5 * 16) as u8;
+const REDRANGE: u8 = 16;
+const GREENS: u8 = 7 * 16;
+const GRAYS: u8 = 6 * 16;
+const GRAYSRANGE: u8 = 16;
+const BROWNS: u8 = 4 * 16;
+const YELLOWS: u8 = (256 - 32 + 7) as u8;
+const BLACK: u8 = 0;
+const WHITE: u8 = (256 - 47) as u8;
+
+// Automap colors
+const BACKGROUND: u8 = BLACK;
+const YOURCOLORS: u8 = WHITE;
+const WALLCOLORS: u8 = REDS;
+const WALLRANGE: u8 = REDRANGE;
+const TSWALLCOLORS: u8 = GRAYS;
+const FDWALLCOLORS: u8 = BROWNS;
+const CDWALLCOLORS: u8 = YELLOWS;
+const THINGCOLORS: u8 = GREENS;
+const SECRETWALLCOLORS: u8 = WALLCOLORS;
+const GRIDCOLORS: u8 = GRAYS + GRAYSRANGE / 2;
+const XHAIRCOLORS: u8 = GRAYS;
+// *close* to black, for players with invisibility
+const INVISIBLECOLOR: u8 = 246;
+const THEIRCOLORS: [u8; MAXPLAYERS] = [GREENS, GRAYS, BROWNS, REDS];
+
+// Drawing stuff
+const AM_PANDOWNKEY: i32 = KEY_DOWNARROW;
+const AM_PANUPKEY: i32 = KEY_UPARROW;
+const AM_PANRIGHTKEY: i32 = KEY_RIGHTARROW;
+const AM_PANLEFTKEY: i32 = KEY_LEFTARROW;
+const AM_ZOOMINKEY: i32 = b'=' as i32;
+const AM_ZOOMOUTKEY: i32 = b'-' as i32;
+const AM_STARTKEY: i32 = KEY_TAB;
+const AM_ENDKEY: i32 = KEY_TAB;
+const AM_GOBIGKEY: i32 = b'0' as i32;
+const AM_FOLLOWKEY: i32 = b'f' as i32;
+const AM_GRIDKEY: i32 = b'g' as i32;
+const AM_MARKKEY: i32 = b'm' as i32;
+const AM_CLEARMARKKEY: i32 = b'c' as i32;
+
+pub const AM_NUMMARKPOINTS: usize = 10;
+
+// How much the automap moves window per tic in frame-buffer coordinates.
+// Moves 140 pixels in 1 second
+const F_PANINC: i32 = 4;
+// How much zoom-in per tic. Goes to 2x in 1 second
+const M_ZOOMIN: Fixed = Fixed(66846);
+// How much zoom-out per tic. Pulls out to 0.5x in 1 second
+const M_ZOOMOUT: Fixed = Fixed(64250);
+// 0.7, how far out a new level starts
+const INITSCALE: Fixed = Fixed(45875);
+
+const PLAYERRADIUS: Fixed = Fixed(16 * FRACUNIT);
+
+// Messages for the player
+pub const AMSTR_FOLLOWON: &str = "Follow Mode ON";
+pub const AMSTR_FOLLOWOFF: &str = "Follow Mode OFF";
+pub const AMSTR_GRIDON: &str = "Grid ON";
+pub const AMSTR_GRIDOFF: &str = "Grid OFF";
+pub const AMSTR_MARKEDSPOT: &str = "Marked Spot";
+pub const AMSTR_MARKSCLEARED: &str = "All Marks Cleared";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct MPoint {
+    x: Fixed,
+    y: Fixed,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct MLine {
+    a: MPoint,
+    b: MPoint,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct FPoint {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct FLine {
+    a: FPoint,
+    b: FPoint,
+}
+
+// The vector graphics for the automap. A line drawing of the player
+// pointing right, starting from the middle
+type LineCharacter = [((i32, i32), (i32, i32))];
+
+const R: i32 = 8 * PLAYERRADIUS.0 / 7;
+
+const PLAYER_ARROW: [((i32, i32), (i32, i32)); 7] = [
+    // -----
+    ((-R + R / 8, 0), (R, 0)),
+    // ----->
+    ((R, 0), (R - R / 2, R / 4)),
+    ((R, 0), (R - R / 2, -R / 4)),
+    // >---->
+    ((-R + R / 8, 0), (-R - R / 8, R / 4)),
+    ((-R + R / 8, 0), (-R - R / 8, -R / 4)),
+    // >>--->
+    ((-R + 3 * R / 8, 0), (-R + R / 8, R / 4)),
+    ((-R + 3 * R / 8, 0), (-R + R / 8, -R / 4)),
+];
+
+const CHEAT_PLAYER_ARROW: [((i32, i32), (i32, i32)); 16] = [
+    // -----
+    ((-R + R / 8, 0), (R, 0)),
+    // ----->
+    ((R, 0), (R - R / 2, R / 6)),
+    ((R, 0), (R - R / 2, -R / 6)),
+    // >----->
+    ((-R + R / 8, 0), (-R - R / 8, R / 6)),
+    ((-R + R / 8, 0), (-R - R / 8, -R / 6)),
+    // >>----->
+    ((-R + 3 * R / 8, 0), (-R + R / 8, R / 6)),
+    ((-R + 3 * R / 8, 0), (-R + R / 8, -R / 6)),
+    // >>-d--->
+    ((-R / 2, 0), (-R / 2, -R / 6)),
+    ((-R / 2, -R / 6), (-R / 2 + R / 6, -R / 6)),
+    ((-R / 2 + R / 6, -R / 6), (-R / 2 + R / 6, R / 4)),
+    // >>-dd-->
+    ((-R / 6, 0), (-R / 6, -R / 6)),
+    ((-R / 6, -R / 6), (0, -R / 6)),
+    ((0, -R / 6), (0, R / 4)),
+    // >>-ddt->
+    ((R / 6, R / 4), (R / 6, -R / 7)),
+    ((R / 6, -R / 7), (R / 6 + R / 32, -R / 7 - R / 32)),
+    ((R / 6 + R / 32, -R / 7 - R / 32), (R / 6 + R / 10, -R / 7)),
+];
+
+// -.5*R, .7*R and R with R as FRACUNIT
+const THINTRIANGLE_GUY: [((i32, i32), (i32, i32)); 3] = [
+    ((-32768, -45875), (FRACUNIT, 0)),
+    ((FRACUNIT, 0), (-32768, 45875)),
+    ((-32768, 45875), (-32768, -45875)),
+];
+
+// The colour a line is drawn in, if it's drawn at all. IDDT shows every
+// line and the computer area map the ones that weren't seen in gray
+fn line_color(level: &Level, line: &Line, cheating: u8, all_map: bool) -> Option<u8> {
+    if cheating != 0 || line.flags & ML_MAPPED != 0 {
+        if line.flags & ML_DONTDRAW != 0 && cheating == 0 {
+            return None;
+        }
+        let (Some(front_sector), Some(back_sector)) = (line.front_sector, line.back_sector) else {
+            return Some(WALLCOLORS);
+        };
+        let front_sector = &level.sectors[front_sector];
+        let back_sector = &level.sectors[back_sector];
+
+        if line.special == 39 {
+            // Teleporters
+            Some(WALLCOLORS + WALLRANGE / 2)
+        } else if line.flags & ML_SECRET != 0 {
+            // Secret door
+            if cheating != 0 {
+                Some(SECRETWALLCOLORS)
+            } else {
+                Some(WALLCOLORS)
+            }
+        } else if back_sector.floor_height != front_sector.floor_height {
+            // Floor level change
+            Some(FDWALLCOLORS)
+        } else if back_sector.ceiling_height != front_sector.ceiling_height {
+            // Ceiling level change
+            Some(CDWALLCOLORS)
+        } else if cheating != 0 {
+            Some(TSWALLCOLORS)
+        } else {
+            None
+        }
+    } else if all_map && line.flags & ML_DONTDRAW == 0 {
+        Some(GRAYS + 3)
+    } else {
+        None
+    }
+}
+
+// AM_rotate. Rotation in 2D, used to rotate player arrow line character
+fn rotate(x: Fixed, y: Fixed, angle: Angle) -> (Fixed, Fixed) {
+    (
+        fixed_mul(x, angle.cos()) - fixed_mul(y, angle.sin()),
+        fixed_mul(x, angle.sin()) + fixed_mul(y, angle.cos()),
+    )
+}
+
+pub struct Automap {
+    pub active: bool,
+    // IDDT. 1 shows every line and 2 every thing as well
+    pub cheating: u8,
+    pub grid: bool,
+    // Specifies whether to follow the player around
+    pub follow_player: bool,
+    // plr->message, until there's a HUD to show it
+    pub message: Option<String>,
+    // Episode and map the automap was set up for
+    level: Option<(i32, i32)>,
+    big_state: bool,
+    // The player being followed
+    player: usize,
+
+    // Size of the framebuffer scale times the original's
+    scale: usize,
+    // Location of window on screen
+    f_x: i32,
+    f_y: i32,
+    // Size of window on screen
+    f_w: i32,
+    f_h: i32,
+
+    // How far the window pans each tic (map coords)
+    m_paninc: MPoint,
+    // How far the window zooms in each tic (map coords)
+    mtof_zoommul: Fixed,
+    // How far the window zooms in each tic (fb coords)
+    ftom_zoommul: Fixed,
+
+    // LL x,y where the window is on the map (map coords)
+    m_x: Fixed,
+    m_y: Fixed,
+    // UR x,y where the window is on the map (map coords)
+    m_x2: Fixed,
+    m_y2: Fixed,
+    // Width/height of window on map (map coords)
+    m_w: Fixed,
+    m_h: Fixed,
+
+    // Based on level size
+    min_x: Fixed,
+    min_y: Fixed,
+    max_x: Fixed,
+    max_y: Fixed,
+
+    // Based on player size
+    min_scale_mtof: Fixed,
+    max_scale_mtof: Fixed,
+
+    // Old stuff for recovery later
+    old_m_w: Fixed,
+    old_m_h: Fixed,
+    old_m_x: Fixed,
+    old_m_y: Fixed,
+
+    // Old location used by the Follower routine
+    f_oldloc: Option<MPoint>,
+
+    // Used by MTOF to scale from map-to-frame-buffer coords
+    scale_mtof: Fixed,
+    // Used by FTOM to scale from frame-buffer-to-map coords (=1/scale_mtof)
+    scale_ftom: Fixed,
+
+    // Numbers used for marking by the automap
+    mark_nums: Vec<Picture>,
+    // Where the points are
+    mark_points: [Option<MPoint>; AM_NUMMARKPOINTS],
+    // Next point to be assigned
+    mark_point_num: usize,
+
+    cheat_amap: CheatSequence,
+}
+
+impl Automap {
+    // AM_loadPics as well. The marks are numbered with the AMMNUM patches
+    pub fn new(doom_files: &mut [DoomFile], scale: usize) -> Self {
+        let mark_nums: Vec<Picture> = (0..AM_NUMMARKPOINTS)
+            .map(|index| {
+                let name: String = format!("AMMNUM{}", index);
+                Picture::from_lump(&wad::get_lump_data(doom_files, &name))
+                    .unwrap_or_else(|error| panic!("{} {}", name, error))
+            })
+            .collect();
+        Automap::with_frame(
+            (SCREENWIDTH * scale) as i32,
+            (SCREENHEIGHT * scale) as i32,
+            scale,
+            mark_nums,
+        )
+    }
+
+    // There's no status bar yet, so the map has the whole frame
+    fn with_frame(f_w: i32, f_h: i32, scale: usize, mark_nums: Vec<Picture>) -> Self {
+        Automap {
+            active: false,
+            cheating: 0,
+            grid: false,
+            follow_player: true,
+            message: None,
+            level: None,
+            big_state: false,
+            player: 0,
+            scale,
+            f_x: 0,
+            f_y: 0,
+            f_w,
+            f_h,
+            m_paninc: MPoint::default(),
+            mtof_zoommul: Fixed::ONE,
+            ftom_zoommul: Fixed::ONE,
+            m_x: Fixed::ZERO,
+            m_y: Fixed::ZERO,
+            m_x2: Fixed::ZERO,
+            m_y2: Fixed::ZERO,
+            m_w: Fixed::ZERO,
+            m_h: Fixed::ZERO,
+            min_x: Fixed::ZERO,
+            min_y: Fixed::ZERO,
+            max_x: Fixed::ZERO,
+            max_y: Fixed::ZERO,
+            min_scale_mtof: Fixed::ZERO,
+            max_scale_mtof: Fixed::ZERO,
+            old_m_w: Fixed::ZERO,
+            old_m_h: Fixed::ZERO,
+            old_m_x: Fixed::ZERO,
+            old_m_y: Fixed::ZERO,
+            f_oldloc: None,
+            // INITSCALEMTOF
+            scale_mtof: Fixed(FRACUNIT / 5),
+            scale_ftom: Fixed::ZERO,
+            mark_nums,
+            mark_points: [None; AM_NUMMARKPOINTS],
+            mark_point_num: 0,
+            cheat_amap: CheatSequence::new(b"iddt"),
+        }
+    }
+
+    // Translates between frame-buffer and map distances
+    fn ftom(&self, x: i32) -> Fixed {
+        fixed_mul(Fixed(x << FRACBITS), self.scale_ftom)
+    }
+
+    fn mtof(&self, x: Fixed) -> i32 {
+        fixed_mul(x, self.scale_mtof).0 >> FRACBITS
+    }
+
+    // Translates between frame-buffer and map coordinates
+    fn cxmtof(&self, x: Fixed) -> i32 {
+        self.f_x + self.mtof(x - self.m_x)
+    }
+
+    fn cymtof(&self, y: Fixed) -> i32 {
+        self.f_y + (self.f_h - self.mtof(y - self.m_y))
+    }
+
+    fn activate_new_scale(&mut self) {
+        self.m_x += Fixed(self.m_w.0 / 2);
+        self.m_y += Fixed(self.m_h.0 / 2);
+        self.m_w = self.ftom(self.f_w);
+        self.m_h = self.ftom(self.f_h);
+        self.m_x -= Fixed(self.m_w.0 / 2);
+        self.m_y -= Fixed(self.m_h.0 / 2);
+        self.m_x2 = self.m_x + self.m_w;
+        self.m_y2 = self.m_y + self.m_h;
+    }
+
+    fn save_scale_and_loc(&mut self) {
+        self.old_m_x = self.m_x;
+        self.old_m_y = self.m_y;
+        self.old_m_w = self.m_w;
+        self.old_m_h = self.m_h;
+    }
+
+    fn restore_scale_and_loc(&mut self, world: &World) {
+        self.m_w = self.old_m_w;
+        self.m_h = self.old_m_h;
+        match self.follow_mobj(world) {
+            Some(mobj) if self.follow_player => {
+                self.m_x = mobj.x - Fixed(self.m_w.0 / 2);
+                self.m_y = mobj.y - Fixed(self.m_h.0 / 2);
+            }
+            _ => {
+                self.m_x = self.old_m_x;
+                self.m_y = self.old_m_y;
+            }
+        }
+        self.m_x2 = self.m_x + self.m_w;
+        self.m_y2 = self.m_y + self.m_h;
+
+        // Change the scaling multipliers
+        self.scale_mtof = fixed_div(Fixed(self.f_w << FRACBITS), self.m_w);
+        self.scale_ftom = fixed_div(Fixed::ONE, self.scale_mtof);
+    }
+
+    // Adds a marker at the current location
+    fn add_mark(&mut self) {
+        self.mark_points[self.mark_point_num] = Some(MPoint {
+            x: self.m_x + Fixed(self.m_w.0 / 2),
+            y: self.m_y + Fixed(self.m_h.0 / 2),
+        });
+        self.mark_point_num = (self.mark_point_num + 1) % AM_NUMMARKPOINTS;
+    }
+
+    fn clear_marks(&mut self) {
+        self.mark_points = [None; AM_NUMMARKPOINTS];
+        self.mark_point_num = 0;
+    }
+
+    // Determines bounding box of all vertices, sets global variables
+    // controlling zoom range
+    fn find_min_max_boundaries(&mut self, level: &Level) {
+        self.min_x = Fixed::MAX;
+        self.min_y = Fixed::MAX;
+        self.max_x = -Fixed::MAX;
+        self.max_y = -Fixed::MAX;
+
+        for vertex in &level.vertexes {
+            self.min_x = self.min_x.min(vertex.x);
+            self.max_x = self.max_x.max(vertex.x);
+            self.min_y = self.min_y.min(vertex.y);
+            self.max_y = self.max_y.max(vertex.y);
+        }
+
+        let max_w: Fixed = self.max_x - self.min_x;
+        let max_h: Fixed = self.max_y - self.min_y;
+
+        let a: Fixed = fixed_div(Fixed(self.f_w << FRACBITS), max_w);
+        let b: Fixed = fixed_div(Fixed(self.f_h << FRACBITS), max_h);
+
+        self.min_scale_mtof = a.min(b);
+        self.max_scale_mtof = fixed_div(Fixed(self.f_h << FRACBITS), Fixed(2 * PLAYERRADIUS.0));
+    }
+
+    fn change_window_loc(&mut self) {
+        if self.m_paninc.x != Fixed::ZERO || self.m_paninc.y != Fixed::ZERO {
+            self.follow_player = false;
+            self.f_oldloc = None;
+        }
+
+        self.m_x += self.m_paninc.x;
+        self.m_y += self.m_paninc.y;
+
+        if self.m_x + Fixed(self.m_w.0 / 2) > self.max_x {
+            self.m_x = self.max_x - Fixed(self.m_w.0 / 2);
+        } else if self.m_x + Fixed(self.m_w.0 / 2) < self.min_x {
+            self.m_x = self.min_x - Fixed(self.m_w.0 / 2);
+        }
+
+        if self.m_y + Fixed(self.m_h.0 / 2) > self.max_y {
+            self.m_y = self.max_y - Fixed(self.m_h.0 / 2);
+        } else if self.m_y + Fixed(self.m_h.0 / 2) < self.min_y {
+            self.m_y = self.min_y - Fixed(self.m_h.0 / 2);
+        }
+
+        self.m_x2 = self.m_x + self.m_w;
+        self.m_y2 = self.m_y + self.m_h;
+    }
+
+    fn follow_mobj<'a>(&self, world: &'a World) -> Option<&'a Mobj> {
+        world.players[self.player]
+            .mo
+            .map(|mo| world.thinkers.mobj(mo))
+    }
+
+    fn init_variables(&mut self, world: &World) {
+        self.active = true;
+        self.f_oldloc = None;
+
+        self.m_paninc = MPoint::default();
+        self.ftom_zoommul = Fixed::ONE;
+        self.mtof_zoommul = Fixed::ONE;
+
+        self.m_w = self.ftom(self.f_w);
+        self.m_h = self.ftom(self.f_h);
+
+        // Find player to center on initially
+        let players_in_game: [bool; MAXPLAYERS] = world.settings.players_in_game;
+        self.player = if players_in_game[world.settings.console_player] {
+            world.settings.console_player
+        } else {
+            (0..MAXPLAYERS)
+                .find(|&player| players_in_game[player])
+                .unwrap_or(0)
+        };
+
+        if let Some(mobj) = self.follow_mobj(world) {
+            self.m_x = mobj.x - Fixed(self.m_w.0 / 2);
+            self.m_y = mobj.y - Fixed(self.m_h.0 / 2);
+        }
+        self.change_window_loc();
+
+        // For saving & restoring
+        self.save_scale_and_loc();
+    }
+
+    fn level_init(&mut self, world: &World) {
+        self.level = Some((world.settings.episode, world.settings.map));
+        self.f_x = 0;
+        self.f_y = 0;
+
+        self.clear_marks();
+
+        self.find_min_max_boundaries(&world.level);
+        self.scale_mtof = fixed_div(self.min_scale_mtof, INITSCALE);
+        if self.scale_mtof > self.max_scale_mtof {
+            self.scale_mtof = self.min_scale_mtof;
+        }
+        self.scale_ftom = fixed_div(Fixed::ONE, self.scale_mtof);
+    }
+
+    // AM_Start
+    pub fn start(&mut self, world: &World) {
+        if self.level != Some((world.settings.episode, world.settings.map)) {
+            self.level_init(world);
+        }
+        self.init_variables(world);
+    }
+
+    // AM_Stop
+    pub fn stop(&mut self) {
+        self.active = false;
+    }
+
+    // Set the window scale to the maximum size
+    fn min_out_window_scale(&mut self) {
+        self.scale_mtof = self.min_scale_mtof;
+        self.scale_ftom = fixed_div(Fixed::ONE, self.scale_mtof);
+        self.activate_new_scale();
+    }
+
+    // Set the window scale to the minimum size
+    fn max_out_window_scale(&mut self) {
+        self.scale_mtof = self.max_scale_mtof;
+        self.scale_ftom = fixed_div(Fixed::ONE, self.scale_mtof);
+        self.activate_new_scale();
+    }
+
+    // AM_Responder. Handle events (user inputs) in automap mode
+    pub fn responder(&mut self, event: &Event, world: &World) -> bool {
+        if !self.active {
+            if *event == Event::KeyDown(AM_STARTKEY) {
+                self.start(world);
+                return true;
+            }
+            return false;
+        }
+
+        match *event {
+            Event::KeyDown(key) => {
+                let mut rc: bool = true;
+                match key {
+                    AM_PANRIGHTKEY if !self.follow_player => self.m_paninc.x = self.ftom(F_PANINC),
+                    AM_PANLEFTKEY if !self.follow_player => self.m_paninc.x = -self.ftom(F_PANINC),
+                    AM_PANUPKEY if !self.follow_player => self.m_paninc.y = self.ftom(F_PANINC),
+                    AM_PANDOWNKEY if !self.follow_player => self.m_paninc.y = -self.ftom(F_PANINC),
+                    AM_PANRIGHTKEY | AM_PANLEFTKEY | AM_PANUPKEY | AM_PANDOWNKEY => rc = false,
+                    AM_ZOOMOUTKEY => {
+                        self.mtof_zoommul = M_ZOOMOUT;
+                        self.ftom_zoommul = M_ZOOMIN;
+                    }
+                    AM_ZOOMINKEY => {
+                        self.mtof_zoommul = M_ZOOMIN;
+                        self.ftom_zoommul = M_ZOOMOUT;
+                    }
+                    AM_ENDKEY => {
+                        self.big_state = false;
+                        self.stop();
+                    }
+                    AM_GOBIGKEY => {
+                        self.big_state = !self.big_state;
+                        if self.big_state {
+                            self.save_scale_and_loc();
+                            self.min_out_window_scale();
+                        } else {
+                            self.restore_scale_and_loc(world);
+                        }
+                    }
+                    AM_FOLLOWKEY => {
+                        self.follow_player = !self.follow_player;
+                        self.f_oldloc = None;
+                        self.message = Some(String::from(if self.follow_player {
+                            AMSTR_FOLLOWON
+                        } else {
+                            AMSTR_FOLLOWOFF
+                        }));
+                    }
+                    AM_GRIDKEY => {
+                        self.grid = !self.grid;
+                        self.message = Some(String::from(if self.grid {
+                            AMSTR_GRIDON
+                        } else {
+                            AMSTR_GRIDOFF
+                        }));
+                    }
+                    AM_MARKKEY => {
+                        self.message =
+                            Some(format!("{} {}", AMSTR_MARKEDSPOT, self.mark_point_num));
+                        self.add_mark();
+                    }
+                    AM_CLEARMARKKEY => {
+                        self.clear_marks();
+                        self.message = Some(String::from(AMSTR_MARKSCLEARED));
+                    }
+                    _ => rc = false,
+                }
+
+                if world.settings.deathmatch == Deathmatch::Off && self.cheat_amap.check(key) {
+                    rc = false;
+                    self.cheating = (self.cheating + 1) % 3;
+                }
+                rc
+            }
+            Event::KeyUp(key) => {
+                match key {
+                    AM_PANRIGHTKEY | AM_PANLEFTKEY if !self.follow_player => {
+                        self.m_paninc.x = Fixed::ZERO;
+                    }
+                    AM_PANUPKEY | AM_PANDOWNKEY if !self.follow_player => {
+                        self.m_paninc.y = Fixed::ZERO;
+                    }
+                    AM_ZOOMOUTKEY | AM_ZOOMINKEY => {
+                        self.mtof_zoommul = Fixed::ONE;
+                        self.ftom_zoommul = Fixed::ONE;
+                    }
+                    _ => {}
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    // Zooming
+    fn change_window_scale(&mut self) {
+        // Change the scaling multipliers
+        self.scale_mtof = fixed_mul(self.scale_mtof, self.mtof_zoommul);
+        self.scale_ftom = fixed_div(Fixed::ONE, self.scale_mtof);
+
+        if self.scale_mtof < self.min_scale_mtof {
+            self.min_out_window_scale();
+        } else if self.scale_mtof > self.max_scale_mtof {
+            self.max_out_window_scale();
+        } else {
+            self.activate_new_scale();
+        }
+    }
+
+    fn do_follow_player(&mut self, world: &World) {
+        let Some(mobj) = self.follow_mobj(world) else {
+            return;
+        };
+        let location: MPoint = MPoint {
+            x: mobj.x,
+            y: mobj.y,
+        };
+
+        if self.f_oldloc != Some(location) {
+            self.m_x = self.ftom(self.mtof(mobj.x)) - Fixed(self.m_w.0 / 2);
+            self.m_y = self.ftom(self.mtof(mobj.y)) - Fixed(self.m_h.0 / 2);
+            self.m_x2 = self.m_x + self.m_w;
+            self.m_y2 = self.m_y + self.m_h;
+            self.f_oldloc = Some(location);
+        }
+    }
+
+    // AM_Ticker. Updates on game tick
+    pub fn ticker(&mut self, world: &World) {
+        if !self.active {
+            return;
+        }
+
+        // G_DoCompleted stopped the automap when the level ended
+        if self.level != Some((world.settings.episode, world.settings.map)) {
+            self.stop();
+            return;
+        }
+
+        if self.follow_player {
+            self.do_follow_player(world);
+        }
+
+        // Change the zoom if necessary
+        if self.ftom_zoommul != Fixed::ONE {
+            self.change_window_scale();
+        }
+
+        // Change x,y location
+        if self.m_paninc.x != Fixed::ZERO || self.m_paninc.y != Fixed::ZERO {
+            self.change_window_loc();
+        }
+    }
+
+    // Automap clipping of lines.
+    //
+    // Based on Cohen-Sutherland clipping algorithm but with a slightly
+    // faster reject and precalculated slopes. If the speed is needed,
+    // use a hash algorithm to handle the common cases
+    fn clip_mline(&self, ml: &MLine) -> Option<FLine> {
+        const LEFT: i32 = 1;
+        const RIGHT: i32 = 2;
+        const BOTTOM: i32 = 4;
+        const TOP: i32 = 8;
+
+        let outcode = |point: FPoint| -> i32 {
+            let mut oc: i32 = 0;
+            if point.y < 0 {
+                oc |= TOP;
+            } else if point.y >= self.f_h {
+                oc |= BOTTOM;
+            }
+            if point.x < 0 {
+                oc |= LEFT;
+            } else if point.x >= self.f_w {
+                oc |= RIGHT;
+            }
+            oc
+        };
+
+        // Do trivial rejects and outcodes
+        let mut outcode1: i32 = if ml.a.y > self.m_y2 {
+            TOP
+        } else if ml.a.y < self.m_y {
+            BOTTOM
+        } else {
+            0
+        };
+        let mut outcode2: i32 = if ml.b.y > self.m_y2 {
+            TOP
+        } else if ml.b.y < self.m_y {
+            BOTTOM
+        } else {
+            0
+        };
+        if outcode1 & outcode2 != 0 {
+            // Trivially outside
+            return None;
+        }
+
+        if ml.a.x < self.m_x {
+            outcode1 |= LEFT;
+        } else if ml.a.x > self.m_x2 {
+            outcode1 |= RIGHT;
+        }
+        if ml.b.x < self.m_x {
+            outcode2 |= LEFT;
+        } else if ml.b.x > self.m_x2 {
+            outcode2 |= RIGHT;
+        }
+        if outcode1 & outcode2 != 0 {
+            // Trivially outside
+            return None;
+        }
+
+        // Transform to frame-buffer coordinates
+        let mut fl: FLine = FLine {
+            a: FPoint {
+                x: self.cxmtof(ml.a.x),
+                y: self.cymtof(ml.a.y),
+            },
+            b: FPoint {
+                x: self.cxmtof(ml.b.x),
+                y: self.cymtof(ml.b.y),
+            },
+        };
+
+        outcode1 = outcode(fl.a);
+        outcode2 = outcode(fl.b);
+        if outcode1 & outcode2 != 0 {
+            return None;
+        }
+
+        while outcode1 | outcode2 != 0 {
+            // May be partially inside box. Find an outside point
+            let outside: i32 = if outcode1 != 0 { outcode1 } else { outcode2 };
+            let (ax, ay, bx, by): (i64, i64, i64, i64) = (
+                i64::from(fl.a.x),
+                i64::from(fl.a.y),
+                i64::from(fl.b.x),
+                i64::from(fl.b.y),
+            );
+
+            // Clip to each side
+            let tmp: FPoint = if outside & TOP != 0 {
+                let dy: i64 = ay - by;
+                let dx: i64 = bx - ax;
+                FPoint {
+                    x: (ax + dx * ay / dy) as i32,
+                    y: 0,
+                }
+            } else if outside & BOTTOM != 0 {
+                let dy: i64 = ay - by;
+                let dx: i64 = bx - ax;
+                FPoint {
+                    x: (ax + dx * (ay - i64::from(self.f_h)) / dy) as i32,
+                    y: self.f_h - 1,
+                }
+            } else if outside & RIGHT != 0 {
+                let dy: i64 = by - ay;
+                let dx: i64 = bx - ax;
+                FPoint {
+                    x: self.f_w - 1,
+                    y: (ay + dy * (i64::from(self.f_w) - 1 - ax) / dx) as i32,
+                }
+            } else {
+                let dy: i64 = by - ay;
+                let dx: i64 = bx - ax;
+                FPoint {
+                    x: 0,
+                    y: (ay + dy * -ax / dx) as i32,
+                }
+            };
+
+            if outside == outcode1 {
+                fl.a = tmp;
+                outcode1 = outcode(fl.a);
+            } else {
+                fl.b = tmp;
+                outcode2 = outcode(fl.b);
+            }
+
+            if outcode1 & outcode2 != 0 {
+                // Trivially outside
+                return None;
+            }
+        }
+
+        Some(fl)
+    }
+
+    // Classic Bresenham w/ whatever optimizations needed for speed
+    fn draw_fline(&self, fl: &FLine, color: u8, framebuffer: &mut Framebuffer) {
+        // For debugging only
+        let in_frame = |point: FPoint| {
+            point.x >= 0 && point.x < self.f_w && point.y >= 0 && point.y < self.f_h
+        };
+        if !in_frame(fl.a) || !in_frame(fl.b) {
+            return;
+        }
+
+        let width: usize = framebuffer.width;
+        let mut put_dot = |x: i32, y: i32| {
+            framebuffer.pixels[y as usize * width + x as usize] = color;
+        };
+
+        let dx: i32 = fl.b.x - fl.a.x;
+        let ax: i32 = 2 * dx.abs();
+        let sx: i32 = if dx < 0 { -1 } else { 1 };
+
+        let dy: i32 = fl.b.y - fl.a.y;
+        let ay: i32 = 2 * dy.abs();
+        let sy: i32 = if dy < 0 { -1 } else { 1 };
+
+        let mut x: i32 = fl.a.x;
+        let mut y: i32 = fl.a.y;
+
+        if ax > ay {
+            let mut d: i32 = ay - ax / 2;
+            loop {
+                put_dot(x, y);
+                if x == fl.b.x {
+                    return;
+                }
+                if d >= 0 {
+                    y += sy;
+                    d -= ax;
+                }
+                x += sx;
+                d += ay;
+            }
+        } else {
+            let mut d: i32 = ax - ay / 2;
+            loop {
+                put_dot(x, y);
+                if y == fl.b.y {
+                    return;
+                }
+                if d >= 0 {
+                    x += sx;
+                    d -= ay;
+                }
+                y += sy;
+                d += ax;
+            }
+        }
+    }
+
+    // Clip lines, draw visible parts of lines
+    fn draw_mline(&self, ml: &MLine, color: u8, framebuffer: &mut Framebuffer) {
+        if let Some(fl) = self.clip_mline(ml) {
+            self.draw_fline(&fl, color, framebuffer);
+        }
+    }
+
+    // Draws flat (floor/ceiling tile) aligned grid lines
+    fn draw_grid(&self, level: &Level, color: u8, framebuffer: &mut Framebuffer) {
+        let block_size: i32 = MAPBLOCKUNITS << FRACBITS;
+
+        // Figure out start of vertical gridlines
+        let mut start: i32 = self.m_x.0;
+        if (start - level.blockmap.org_x.0) % block_size != 0 {
+            start += block_size - ((start - level.blockmap.org_x.0) % block_size);
+        }
+        let end: i32 = self.m_x.0 + self.m_w.0;
+
+        // Draw vertical gridlines
+        for x in (start..end).step_by(block_size as usize) {
+            let ml: MLine = MLine {
+                a: MPoint {
+                    x: Fixed(x),
+                    y: self.m_y,
+                },
+                b: MPoint {
+                    x: Fixed(x),
+                    y: self.m_y + self.m_h,
+                },
+            };
+            self.draw_mline(&ml, color, framebuffer);
+        }
+
+        // Figure out start of horizontal gridlines
+        let mut start: i32 = self.m_y.0;
+        if (start - level.blockmap.org_y.0) % block_size != 0 {
+            start += block_size - ((start - level.blockmap.org_y.0) % block_size);
+        }
+        let end: i32 = self.m_y.0 + self.m_h.0;
+
+        // Draw horizontal gridlines
+        for y in (start..end).step_by(block_size as usize) {
+            let ml: MLine = MLine {
+                a: MPoint {
+                    x: self.m_x,
+                    y: Fixed(y),
+                },
+                b: MPoint {
+                    x: self.m_x + self.m_w,
+                    y: Fixed(y),
+                },
+            };
+            self.draw_mline(&ml, color, framebuffer);
+        }
+    }
+
+    // Determines visible lines, draws them. This is LineDef based, not
+    // LineSeg based
+    fn draw_walls(&self, level: &Level, all_map: bool, framebuffer: &mut Framebuffer) {
+        for line in &level.lines {
+            if let Some(color) = line_color(level, line, self.cheating, all_map) {
+                let ml: MLine = MLine {
+                    a: MPoint {
+                        x: level.vertexes[line.v1].x,
+                        y: level.vertexes[line.v1].y,
+                    },
+                    b: MPoint {
+                        x: level.vertexes[line.v2].x,
+                        y: level.vertexes[line.v2].y,
+                    },
+                };
+                self.draw_mline(&ml, color, framebuffer);
+            }
+        }
+    }
+
+    fn draw_line_character(
+        &self,
+        line_guy: &LineCharacter,
+        scale: Fixed,
+        angle: Angle,
+        color: u8,
+        position: MPoint,
+        framebuffer: &mut Framebuffer,
+    ) {
+        let transform = |(px, py): (i32, i32)| -> MPoint {
+            let (mut px, mut py): (Fixed, Fixed) = (Fixed(px), Fixed(py));
+            if scale != Fixed::ZERO {
+                px = fixed_mul(scale, px);
+                py = fixed_mul(scale, py);
+            }
+            if angle != Angle(0) {
+                (px, py) = rotate(px, py, angle);
+            }
+            MPoint {
+                x: px + position.x,
+                y: py + position.y,
+            }
+        };
+
+        for &(a, b) in line_guy {
+            let ml: MLine = MLine {
+                a: transform(a),
+                b: transform(b),
+            };
+            self.draw_mline(&ml, color, framebuffer);
+        }
+    }
+
+    fn draw_players(&self, world: &World, framebuffer: &mut Framebuffer) {
+        if !world.settings.netgame {
+            if let Some(mobj) = self.follow_mobj(world) {
+                let arrow: &LineCharacter = if self.cheating != 0 {
+                    &CHEAT_PLAYER_ARROW
+                } else {
+                    &PLAYER_ARROW
+                };
+                self.draw_line_character(
+                    arrow,
+                    Fixed::ZERO,
+                    mobj.angle,
+                    YOURCOLORS,
+                    MPoint {
+                        x: mobj.x,
+                        y: mobj.y,
+                    },
+                    framebuffer,
+                );
+            }
+            return;
+        }
+
+        for (index, player) in world.players.iter().enumerate() {
+            if world.settings.deathmatch != Deathmatch::Off && index != self.player {
+                continue;
+            }
+            if !world.settings.players_in_game[index] {
+                continue;
+            }
+            let Some(mo) = player.mo else {
+                continue;
+            };
+            let mobj: &Mobj = world.thinkers.mobj(mo);
+
+            let color: u8 = if player.powers[PW_INVISIBILITY] != 0 {
+                INVISIBLECOLOR
+            } else {
+                THEIRCOLORS[index]
+            };
+
+            self.draw_line_character(
+                &PLAYER_ARROW,
+                Fixed::ZERO,
+                mobj.angle,
+                color,
+                MPoint {
+                    x: mobj.x,
+                    y: mobj.y,
+                },
+                framebuffer,
+            );
+        }
+    }
+
+    fn draw_things(
+        &self,
+        things: impl Iterator<Item = (Fixed, Fixed, Angle)>,
+        framebuffer: &mut Framebuffer,
+    ) {
+        for (x, y, angle) in things {
+            self.draw_line_character(
+                &THINTRIANGLE_GUY,
+                Fixed(16 << FRACBITS),
+                angle,
+                THINGCOLORS,
+                MPoint { x, y },
+                framebuffer,
+            );
+        }
+    }
+
+    fn draw_marks(&self, framebuffer: &mut Framebuffer) {
+        // Because something's wrong with the wad, i guess
+        let w: i32 = 5 * self.scale as i32;
+        let h: i32 = 6 * self.scale as i32;
+
+        for (mark_point, mark_num) in self.mark_points.iter().zip(&self.mark_nums) {
+            let Some(mark_point) = mark_point else {
+                continue;
+            };
+            let fx: i32 = self.cxmtof(mark_point.x);
+            let fy: i32 = self.cymtof(mark_point.y);
+            if fx >= self.f_x && fx <= self.f_w - w && fy >= self.f_y && fy <= self.f_h - h {
+                self.draw_patch(fx, fy, mark_num, framebuffer);
+            }
+        }
+    }
+
+    // V_DrawPatch at the frame's scale
+    fn draw_patch(&self, x: i32, y: i32, patch: &Picture, framebuffer: &mut Framebuffer) {
+        let scale: i32 = self.scale as i32;
+        let left: i32 = x - i32::from(patch.left_offset) * scale;
+        let top: i32 = y - i32::from(patch.top_offset) * scale;
+
+        for py in 0..patch.height as i32 * scale {
+            for px in 0..patch.width as i32 * scale {
+                let (fx, fy): (i32, i32) = (left + px, top + py);
+                if fx < 0
+                    || fy < 0
+                    || fx >= framebuffer.width as i32
+                    || fy >= framebuffer.height as i32
+                {
+                    continue;
+                }
+                let texel: usize = (py / scale) as usize * patch.width + (px / scale) as usize;
+                if let Some(color) = patch.pixels[texel] {
+                    framebuffer.pixels[fy as usize * framebuffer.width + fx as usize] = color;
+                }
+            }
+        }
+    }
+
+    fn draw_crosshair(&self, color: u8, framebuffer: &mut Framebuffer) {
+        // Single point for now
+        framebuffer.pixels[(self.f_w * (self.f_h + 1) / 2) as usize] = color;
+    }
+
+    // AM_Drawer
+    pub fn drawer(&self, world: &World, framebuffer: &mut Framebuffer) {
+        if !self.active {
+            return;
+        }
+        assert_eq!(
+            (framebuffer.width, framebuffer.height),
+            (self.f_w as usize, self.f_h as usize),
+            "The automap was made for a different size framebuffer"
+        );
+
+        framebuffer.pixels.fill(BACKGROUND);
+        if self.grid {
+            self.draw_grid(&world.level, GRIDCOLORS, framebuffer);
+        }
+        let player: &Player = &world.players[self.player];
+        self.draw_walls(&world.level, player.powers[PW_ALLMAP] != 0, framebuffer);
+        self.draw_players(world, framebuffer);
+        if self.cheating == 2 {
+            let things = world.level.sectors.iter().flat_map(|sector| {
+                std::iter::successors(sector.thing_list, |&id| world.thinkers.mobj(id).snext)
+                    .map(|id| world.thinkers.mobj(id))
+                    .map(|mobj| (mobj.x, mobj.y, mobj.angle))
+            });
+            self.draw_things(things, framebuffer);
+        }
+        self.draw_crosshair(XHAIRCOLORS, framebuffer);
+        self.draw_marks(framebuffer);
+    }
+}
+
+// The automap with the world it maps, for the responder chain
+pub struct AutomapResponder<'a> {
+    pub automap: &'a mut Automap,
+    pub world: &'a World,
+}
+
+impl Responder for AutomapResponder<'_> {
+    fn responder(&mut self, event: &Event) -> bool {
+        self.automap.responder(event, self.world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::automap::{
+        Automap, MLine, MPoint, AMSTR_FOLLOWOFF, AMSTR_GRIDON, AMSTR_MARKSCLEARED, BACKGROUND,
+        GRIDCOLORS, THINGCOLORS, TSWALLCOLORS, WALLCOLORS, YOURCOLORS,
+    };
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::game::event::{Event, KEY_RIGHTARROW, KEY_TAB};
+    use crate::game::Game;
+    use crate::graphics::Palette;
+    use crate::play::level::ML_MAPPED;
+    use crate::play::ticcmd::TicCmd;
+    use crate::play::world::Deathmatch;
+    use crate::render::test_wad::{check_golden, test_game, START};
+    use crate::render::Framebuffer;
+    use crate::wad;
+
+    // The scene a tic in, with the automap open on it
+    fn started_automap() -> (Game, Automap) {
+        let mut game: Game = test_game(START);
+        game.tick(&[TicCmd::default()]);
+        let mut automap: Automap = Automap::new(&mut game.doom_files, 1);
+        assert!(automap.responder(&Event::KeyDown(KEY_TAB), &game.world));
+        (game, automap)
+    }
+
+    fn press(automap: &mut Automap, game: &Game, key: u8) -> bool {
+        let eaten: bool = automap.responder(&Event::KeyDown(i32::from(key)), &game.world);
+        automap.responder(&Event::KeyUp(i32::from(key)), &game.world);
+        eaten
+    }
+
+    fn draw(automap: &Automap, game: &Game) -> Framebuffer {
+        let mut framebuffer: Framebuffer = Framebuffer::new(1);
+        automap.drawer(&game.world, &mut framebuffer);
+        framebuffer
+    }
+
+    #[test]
+    fn test_responder_tab_starts_and_ends_the_automap() {
+        let (game, mut automap) = started_automap();
+        assert!(automap.active);
+
+        // Centered on the player
+        let center: Fixed = automap.m_x + Fixed(automap.m_w.0 / 2);
+        assert!((center.0 - 64 * FRACUNIT).abs() <= FRACUNIT);
+
+        assert!(automap.responder(&Event::KeyDown(KEY_TAB), &game.world));
+        assert!(!automap.active);
+        assert!(!automap.responder(&Event::KeyDown(i32::from(b'f')), &game.world));
+    }
+
+    #[test]
+    fn test_responder_pans_only_without_follow() {
+        let (game, mut automap) = started_automap();
+        automap.ticker(&game.world);
+        let m_x: Fixed = automap.m_x;
+
+        // Following the player the arrows are left for the game
+        assert!(!automap.responder(&Event::KeyDown(KEY_RIGHTARROW), &game.world));
+        automap.ticker(&game.world);
+        assert_eq!(automap.m_x, m_x);
+
+        assert!(press(&mut automap, &game, b'f'));
+        assert_eq!(automap.message.as_deref(), Some(AMSTR_FOLLOWOFF));
+        assert!(automap.responder(&Event::KeyDown(KEY_RIGHTARROW), &game.world));
+        automap.ticker(&game.world);
+        automap.ticker(&game.world);
+        let panned: Fixed = m_x + automap.ftom(4) + automap.ftom(4);
+        assert_eq!(automap.m_x, panned);
+
+        automap.responder(&Event::KeyUp(KEY_RIGHTARROW), &game.world);
+        automap.ticker(&game.world);
+        assert_eq!(automap.m_x, panned);
+    }
+
+    #[test]
+    fn test_ticker_zooms_between_the_scale_limits() {
+        let (game, mut automap) = started_automap();
+        let scale_mtof: Fixed = automap.scale_mtof;
+
+        automap.responder(&Event::KeyDown(i32::from(b'=')), &game.world);
+        automap.ticker(&game.world);
+        assert!(automap.scale_mtof > scale_mtof);
+        for _ in 0..200 {
+            automap.ticker(&game.world);
+        }
+        assert_eq!(automap.scale_mtof, automap.max_scale_mtof);
+        automap.responder(&Event::KeyUp(i32::from(b'=')), &game.world);
+
+        automap.responder(&Event::KeyDown(i32::from(b'-')), &game.world);
+        for _ in 0..400 {
+            automap.ticker(&game.world);
+        }
+        assert_eq!(automap.scale_mtof, automap.min_scale_mtof);
+
+        // The whole map fits the frame zoomed all the way out
+        assert!(automap.m_w >= automap.max_x - automap.min_x);
+    }
+
+    #[test]
+    fn test_responder_gobig_saves_and_restores_the_view() {
+        let (game, mut automap) = started_automap();
+        let (m_w, scale_mtof): (Fixed, Fixed) = (automap.m_w, automap.scale_mtof);
+
+        assert!(press(&mut automap, &game, b'0'));
+        assert_eq!(automap.scale_mtof, automap.min_scale_mtof);
+        assert!(press(&mut automap, &game, b'0'));
+        assert_eq!(automap.m_w, m_w);
+        assert!((automap.scale_mtof.0 - scale_mtof.0).abs() <= 1);
+    }
+
+    #[test]
+    fn test_responder_iddt_cycles_the_cheat_levels() {
+        let (mut game, mut automap) = started_automap();
+
+        for cheating in [1, 2, 0] {
+            let eaten: Vec<bool> = b"iddt"
+                .iter()
+                .map(|&key| press(&mut automap, &game, key))
+                .collect();
+            assert_eq!(eaten, vec![false; 4]);
+            assert_eq!(automap.cheating, cheating);
+        }
+
+        game.world.settings.deathmatch = Deathmatch::Classic;
+        for &key in b"iddt" {
+            press(&mut automap, &game, key);
+        }
+        assert_eq!(automap.cheating, 0);
+    }
+
+    #[test]
+    fn test_responder_marks_and_clears_spots() {
+        let (game, mut automap) = started_automap();
+
+        assert!(press(&mut automap, &game, b'm'));
+        assert_eq!(automap.message.as_deref(), Some("Marked Spot 0"));
+        assert!(press(&mut automap, &game, b'm'));
+        assert_eq!(automap.mark_point_num, 2);
+        assert_eq!(
+            automap.mark_points[0],
+            Some(MPoint {
+                x: automap.m_x + Fixed(automap.m_w.0 / 2),
+                y: automap.m_y + Fixed(automap.m_h.0 / 2),
+            })
+        );
+
+        assert!(press(&mut automap, &game, b'c'));
+        assert_eq!(automap.message.as_deref(), Some(AMSTR_MARKSCLEARED));
+        assert_eq!(automap.mark_points, [None; 10]);
+        assert_eq!(automap.mark_point_num, 0);
+    }
+
+    #[test]
+    fn test_clip_mline_keeps_lines_in_the_frame() {
+        let (_, automap) = started_automap();
+        let point = |x: i32, y: i32| MPoint {
+            x: Fixed(x * FRACUNIT),
+            y: Fixed(y * FRACUNIT),
+        };
+
+        // Far off to the side
+        assert_eq!(
+            automap.clip_mline(&MLine {
+                a: point(10000, 0),
+                b: point(10000, 100),
+            }),
+            None
+        );
+
+        // Right across the whole map, clipped at both edges
+        let fl = automap
+            .clip_mline(&MLine {
+                a: point(-10000, 128),
+                b: point(10000, 128),
+            })
+            .unwrap();
+        assert_eq!((fl.a.x, fl.b.x), (0, 319));
+        assert_eq!(fl.a.y, fl.b.y);
+        assert_eq!(fl.a.y, automap.cymtof(Fixed(128 * FRACUNIT)));
+    }
+
+    #[test]
+    fn test_drawer_shows_only_seen_lines_without_the_cheat() {
+        let (mut game, mut automap) = started_automap();
+
+        // Nothing's been seen yet, just the player and the crosshair
+        let framebuffer: Framebuffer = draw(&automap, &game);
+        assert!(framebuffer.pixels.contains(&YOURCOLORS));
+        assert!(!framebuffer.pixels.contains(&WALLCOLORS));
+
+        for line in &mut game.world.level.lines {
+            line.flags |= ML_MAPPED;
+        }
+        assert!(draw(&automap, &game).pixels.contains(&WALLCOLORS));
+
+        // The computer area map shows the rest in gray
+        for line in &mut game.world.level.lines {
+            line.flags &= !ML_MAPPED;
+        }
+        game.world.players[0].powers[crate::play::player::PW_ALLMAP] = 1;
+        assert!(draw(&automap, &game).pixels.contains(&(TSWALLCOLORS + 3)));
+
+        automap.cheating = 1;
+        let framebuffer: Framebuffer = draw(&automap, &game);
+        assert!(framebuffer.pixels.contains(&TSWALLCOLORS));
+        assert!(!framebuffer.pixels.contains(&THINGCOLORS));
+        assert!(framebuffer.pixels.contains(&BACKGROUND));
+    }
+
+    #[test]
+    fn test_drawer_matches_golden_cheat_with_grid_and_mark() {
+        let (mut game, mut automap) = started_automap();
+        for &key in b"iddtiddtgm" {
+            press(&mut automap, &game, key);
+        }
+        assert_eq!(automap.message.as_deref(), Some("Marked Spot 0"));
+        press(&mut automap, &game, b'f');
+        press(&mut automap, &game, b'g');
+        assert!(!automap.grid);
+        press(&mut automap, &game, b'g');
+        assert_eq!(automap.message.as_deref(), Some(AMSTR_GRIDON));
+
+        // Pan so the mark isn't under the player and zoom out to the walls
+        automap.responder(&Event::KeyDown(KEY_RIGHTARROW), &game.world);
+        automap.responder(&Event::KeyDown(i32::from(b'-')), &game.world);
+        for _ in 0..10 {
+            automap.ticker(&game.world);
+        }
+
+        let framebuffer: Framebuffer = draw(&automap, &game);
+        assert!(framebuffer.pixels.contains(&GRIDCOLORS));
+        assert!(framebuffer.pixels.contains(&THINGCOLORS));
+        let palette: Palette =
+            Palette::from_playpal(&wad::get_lump_data(&mut game.doom_files, "PLAYPAL"), 0).unwrap();
+        check_golden("automap-cheat", &framebuffer.to_png(&palette));
+    }
+
+    #[test]
+    fn test_ticker_stops_on_a_new_level() {
+        let (mut game, mut automap) = started_automap();
+
+        game.world.settings.map += 1;
+        automap.ticker(&game.world);
+        assert!(!automap.active);
+    }
+}
@@ -0,0 +1,255 @@
+// Whole map automaps for level review. Every line is drawn the way IDDT
+// shows it, with the things at their spawn spots when asked for, either
+// into a picture through the automap's own line drawing or as SVG in map
+// units
+use std::fmt::Write;
+
+use crate::automap::{line_color, rotate, Automap, BACKGROUND, THINGCOLORS, THINTRIANGLE_GUY};
+use crate::fixed::{fixed_div, fixed_mul, Angle, Fixed, ANG45, FRACBITS, FRACUNIT};
+use crate::graphics::Palette;
+use crate::image::png::PngImage;
+use crate::play::level::{Level, MapThing};
+use crate::render::Framebuffer;
+
+// Widest picture that keeps the frame size a fixed point number
+pub const MAX_EXPORT_WIDTH: usize = 16384;
+
+// IDDT level for the export
+fn cheating(things: bool) -> u8 {
+    if things {
+        2
+    } else {
+        1
+    }
+}
+
+fn thing_angle(map_thing: &MapThing) -> Angle {
+    Angle(ANG45.wrapping_mul((i32::from(map_thing.angle) / 45) as u32))
+}
+
+// The map's bounding box with a border of a 32nd of its larger side
+fn map_bounds(level: &Level) -> (Fixed, Fixed, Fixed, Fixed) {
+    let mut automap: Automap = Automap::with_frame(1, 1, 1, Vec::new());
+    automap.find_min_max_boundaries(level);
+    let pad: Fixed = Fixed(
+        (automap.max_x - automap.min_x)
+            .max(automap.max_y - automap.min_y)
+            .0
+            / 32
+            + FRACUNIT,
+    );
+    (
+        automap.min_x - pad,
+        automap.min_y - pad,
+        automap.max_x + pad,
+        automap.max_y + pad,
+    )
+}
+
+pub fn export_png(level: &Level, palette: &Palette, width: usize, things: bool) -> PngImage {
+    assert!(
+        (1..=MAX_EXPORT_WIDTH).contains(&width),
+        "Export width must be between 1 and {}",
+        MAX_EXPORT_WIDTH
+    );
+    let (min_x, min_y, max_x, max_y): (Fixed, Fixed, Fixed, Fixed) = map_bounds(level);
+
+    let scale_mtof: Fixed = fixed_div(Fixed((width as i32) << FRACBITS), max_x - min_x);
+    let height: i32 = (fixed_mul(max_y - min_y, scale_mtof).0 >> FRACBITS).max(1);
+
+    let mut automap: Automap = Automap::with_frame(width as i32, height, 1, Vec::new());
+    automap.cheating = cheating(things);
+    automap.scale_mtof = scale_mtof;
+    automap.scale_ftom = fixed_div(Fixed::ONE, scale_mtof);
+    automap.m_x = min_x;
+    automap.m_y = min_y;
+    automap.m_w = automap.ftom(automap.f_w);
+    automap.m_h = automap.ftom(automap.f_h);
+    automap.m_x2 = automap.m_x + automap.m_w;
+    automap.m_y2 = automap.m_y + automap.m_h;
+
+    let mut framebuffer: Framebuffer = Framebuffer {
+        width,
+        height: height as usize,
+        pixels: vec![BACKGROUND; width * height as usize],
+    };
+    automap.draw_walls(level, false, &mut framebuffer);
+    if things {
+        let spawn_spots = level.things.iter().map(|map_thing| {
+            (
+                Fixed(i32::from(map_thing.x) << FRACBITS),
+                Fixed(i32::from(map_thing.y) << FRACBITS),
+                thing_angle(map_thing),
+            )
+        });
+        automap.draw_things(spawn_spots, &mut framebuffer);
+    }
+
+    framebuffer.to_png(palette)
+}
+
+fn svg_color(palette: &Palette, index: u8) -> String {
+    let [r, g, b]: [u8; 3] = palette.color(index);
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+// Map units with y going down like the picture, never -0
+fn svg_point(x: Fixed, y: Fixed) -> (f64, f64) {
+    (
+        f64::from(x.0) / f64::from(FRACUNIT),
+        0.0 - f64::from(y.0) / f64::from(FRACUNIT),
+    )
+}
+
+pub fn export_svg(level: &Level, palette: &Palette, things: bool) -> String {
+    let (min_x, min_y, max_x, max_y): (Fixed, Fixed, Fixed, Fixed) = map_bounds(level);
+    let (left, top): (f64, f64) = svg_point(min_x, max_y);
+    let (right, bottom): (f64, f64) = svg_point(max_x, min_y);
+    let (width, height): (f64, f64) = (right - left, bottom - top);
+
+    let mut svg: String = String::new();
+    // Writing to a String can't fail
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}\" height=\"{}\">",
+        left, top, width, height, width, height
+    );
+    let _ = writeln!(
+        svg,
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+        left,
+        top,
+        width,
+        height,
+        svg_color(palette, BACKGROUND)
+    );
+    // Lines about as thick as the picture's at its usual width
+    let _ = writeln!(
+        svg,
+        "<g stroke-width=\"{}\" stroke-linecap=\"round\">",
+        (width.max(height) / 400.0).max(1.0)
+    );
+
+    for line in &level.lines {
+        let Some(color) = line_color(level, line, cheating(things), false) else {
+            continue;
+        };
+        let (x1, y1): (f64, f64) = svg_point(level.vertexes[line.v1].x, level.vertexes[line.v1].y);
+        let (x2, y2): (f64, f64) = svg_point(level.vertexes[line.v2].x, level.vertexes[line.v2].y);
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\"/>",
+            x1,
+            y1,
+            x2,
+            y2,
+            svg_color(palette, color)
+        );
+    }
+
+    if things {
+        for map_thing in &level.things {
+            let angle: Angle = thing_angle(map_thing);
+            let points: Vec<String> = THINTRIANGLE_GUY
+                .iter()
+                .map(|&((x, y), _)| {
+                    let (x, y): (Fixed, Fixed) = rotate(
+                        fixed_mul(Fixed(16 << FRACBITS), Fixed(x)),
+                        fixed_mul(Fixed(16 << FRACBITS), Fixed(y)),
+                        angle,
+                    );
+                    let (x, y): (f64, f64) = svg_point(
+                        x + Fixed(i32::from(map_thing.x) << FRACBITS),
+                        y + Fixed(i32::from(map_thing.y) << FRACBITS),
+                    );
+                    format!("{:.1},{:.1}", x, y)
+                })
+                .collect();
+            let _ = writeln!(
+                svg,
+                "<polygon points=\"{}\" fill=\"none\" stroke=\"{}\"/>",
+                points.join(" "),
+                svg_color(palette, THINGCOLORS)
+            );
+        }
+    }
+
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::automap::export::{export_png, export_svg};
+    use crate::automap::{FDWALLCOLORS, THINGCOLORS, WALLCOLORS};
+    use crate::game::Game;
+    use crate::graphics::Palette;
+    use crate::image::png::{PngColor, PngImage};
+    use crate::render::test_wad::{check_golden, test_game, START};
+    use crate::wad;
+
+    fn palette(game: &mut Game) -> Palette {
+        Palette::from_playpal(&wad::get_lump_data(&mut game.doom_files, "PLAYPAL"), 0).unwrap()
+    }
+
+    fn indices(png_image: &PngImage) -> &[u8] {
+        let PngColor::Indexed { indices, .. } = &png_image.color else {
+            panic!("The export is not indexed");
+        };
+        indices
+    }
+
+    #[test]
+    fn test_export_png_fits_the_whole_map() {
+        let mut game: Game = test_game(START);
+        let palette: Palette = palette(&mut game);
+
+        let png_image: PngImage = export_png(&game.world.level, &palette, 400, false);
+
+        // The scene is 576 by 256 with a border
+        assert_eq!(png_image.width, 400);
+        assert!((190..=192).contains(&png_image.height));
+        assert!(indices(&png_image).contains(&WALLCOLORS));
+        assert!(indices(&png_image).contains(&FDWALLCOLORS));
+        assert!(!indices(&png_image).contains(&THINGCOLORS));
+
+        // Every wall is inside the border
+        let width: usize = png_image.width as usize;
+        for row in indices(&png_image).chunks(width) {
+            assert_eq!((row[0], row[width - 1]), (0, 0));
+        }
+    }
+
+    #[test]
+    fn test_export_png_matches_golden_with_things() {
+        let mut game: Game = test_game(START);
+        let palette: Palette = palette(&mut game);
+
+        let png_image: PngImage = export_png(&game.world.level, &palette, 320, true);
+
+        assert!(indices(&png_image).contains(&THINGCOLORS));
+        check_golden("automap-export", &png_image);
+    }
+
+    #[test]
+    fn test_export_svg_has_a_line_per_wall() {
+        let mut game: Game = test_game(START);
+        let palette: Palette = palette(&mut game);
+
+        let svg: String = export_svg(&game.world.level, &palette, true);
+
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-19 -275 614 294\""));
+        assert!(svg.ends_with("</svg>\n"));
+        // The scene's lines all show with IDDT, two sided ones in gray
+        assert_eq!(svg.matches("<line ").count(), game.world.level.lines.len());
+        assert_eq!(
+            svg.matches("<polygon ").count(),
+            game.world.level.things.len()
+        );
+        assert!(svg.contains("<line x1=\"0\" y1=\"0\""));
+
+        let without_things: String = export_svg(&game.world.level, &palette, false);
+        assert_eq!(without_things.matches("<polygon ").count(), 0);
+    }
+}
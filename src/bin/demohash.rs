@@ -0,0 +1,63 @@
+use std::{env, fs, path::PathBuf, process};
+
+use zz_doom::config::Config;
+use zz_doom::game::demo::{self, DemoPlayback};
+use zz_doom::game::desync;
+use zz_doom::game::Game;
+use zz_doom::option::DoomOptions;
+use zz_doom::play::world::WorldSettings;
+use zz_doom::wad::{self, DoomFile};
+
+const USAGE: &str = "Usage: demohash <demo> <hash file> <iwad> [pwads...] [options...]";
+
+// Writes the hash stream of a known good demo playback for -demoverify to
+// check later builds against. Options like -complevel are the game's own so
+// the settings match what -demoverify plays the demo with
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.len() < 3 {
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    }
+
+    let options_start: usize = args[2..]
+        .iter()
+        .position(|arg| arg.starts_with('-'))
+        .map_or(args.len(), |position| position + 2);
+    let wad_paths: Vec<PathBuf> = args[2..options_start].iter().map(PathBuf::from).collect();
+    let doom_options: DoomOptions = DoomOptions::new(args[options_start..].to_vec());
+    let mut config: Config = Config::new(&doom_options);
+    config.set_game_type_by_iwad_paths(&wad_paths);
+
+    let mut doom_files: Vec<DoomFile> = Vec::new();
+    for wad_path in &wad_paths {
+        match wad::process_file(wad_path) {
+            Ok(doom_file) => doom_files.push(doom_file),
+            Err(error) => {
+                eprintln!("Error processing file {}.\n {}", wad_path.display(), error);
+                process::exit(1);
+            }
+        }
+    }
+
+    let demo_playback: DemoPlayback = demo::load_demo(&mut doom_files, &args[0])
+        .and_then(DemoPlayback::from_lump_data)
+        .unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        });
+
+    let settings: WorldSettings = WorldSettings::new(&config, &doom_options);
+    let mut game: Game =
+        Game::play_demo(settings, doom_files, demo_playback).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        });
+
+    let hash_stream: String = desync::hash_demo(&mut game);
+    if let Err(error) = fs::write(&args[1], hash_stream) {
+        eprintln!("Unable to write {}. {}", args[1], error);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,88 @@
+use std::{env, fs, path::PathBuf, process};
+
+use zz_doom::automap::export;
+use zz_doom::graphics::Palette;
+use zz_doom::play::level::{Level, MapLumps};
+use zz_doom::wad::{self, DoomFile};
+
+const USAGE: &str =
+    "Usage: mapexport [-things] [-width <pixels>] <map> <output.png|output.svg> <iwad> [pwads...]";
+
+// Pictures are this wide unless -width says otherwise
+const DEFAULT_WIDTH: usize = 1600;
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let mut things: bool = false;
+    let mut width: usize = DEFAULT_WIDTH;
+    while let Some(arg) = args.first() {
+        match arg.as_str() {
+            "-things" => {
+                things = true;
+                args.remove(0);
+            }
+            "-width" if args.len() > 1 => {
+                width = match args[1].parse() {
+                    Ok(width) if (1..=export::MAX_EXPORT_WIDTH).contains(&width) => width,
+                    _ => {
+                        eprintln!("Width must be between 1 and {}", export::MAX_EXPORT_WIDTH);
+                        process::exit(1);
+                    }
+                };
+                args.drain(..2);
+            }
+            _ => break,
+        }
+    }
+
+    if args.len() < 3 {
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    }
+
+    let map_name: String = args[0].to_ascii_uppercase();
+    let output_path: PathBuf = PathBuf::from(&args[1]);
+
+    let mut doom_files: Vec<DoomFile> = Vec::new();
+    for wad_path in &args[2..] {
+        match wad::process_file(&PathBuf::from(wad_path)) {
+            Ok(doom_file) => doom_files.push(doom_file),
+            Err(error) => {
+                eprintln!("Error processing file {}.\n {}", wad_path, error);
+                process::exit(1);
+            }
+        }
+    }
+
+    let level: Level = match MapLumps::from_wad(&mut doom_files, &map_name)
+        .and_then(|map_lumps| Level::from_lumps(&map_lumps))
+    {
+        Ok(level) => level,
+        Err(error) => {
+            eprintln!("Error loading {}.\n {}", map_name, error);
+            process::exit(1);
+        }
+    };
+    let palette: Palette =
+        Palette::from_playpal(&wad::get_lump_data(&mut doom_files, "PLAYPAL"), 0).unwrap_or_else(
+            |error| {
+                eprintln!("{}", error);
+                process::exit(1);
+            },
+        );
+
+    let is_svg: bool = output_path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"));
+    let output: Vec<u8> = if is_svg {
+        export::export_svg(&level, &palette, things).into_bytes()
+    } else {
+        export::export_png(&level, &palette, width, things).encode()
+    };
+
+    if let Err(error) = fs::write(&output_path, output) {
+        eprintln!("Error writing {}.\n {}", output_path.display(), error);
+        process::exit(1);
+    }
+}
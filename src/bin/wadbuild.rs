@@ -0,0 +1,43 @@
+use std::{env, path::PathBuf, process};
+
+use zz_doom::graphics::Palette;
+use zz_doom::wad::{self, build, DoomFile};
+
+const USAGE: &str = "Usage: wadbuild <manifest> <output wad> [palette wad]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.len() < 2 || args.len() > 3 {
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    }
+
+    let manifest_path: PathBuf = PathBuf::from(&args[0]);
+    let output_path: PathBuf = PathBuf::from(&args[1]);
+
+    // Extracted PWADs usually have no PLAYPAL to convert PNGs with,
+    // so let the user point us at the IWAD for one
+    let palette: Option<Palette> = args.get(2).map(|palette_wad_path| {
+        let mut doom_files: Vec<DoomFile> =
+            match wad::process_file(&PathBuf::from(palette_wad_path)) {
+                Ok(doom_file) => vec![doom_file],
+                Err(error) => {
+                    eprintln!("Error processing file {}.\n {}", palette_wad_path, error);
+                    process::exit(1);
+                }
+            };
+
+        Palette::from_playpal(&wad::get_lump_data(&mut doom_files, "PLAYPAL"), 0).unwrap_or_else(
+            |error| {
+                eprintln!("{}", error);
+                process::exit(1);
+            },
+        )
+    });
+
+    if let Err(error) = build::build_wad(&manifest_path, &output_path, palette) {
+        eprintln!("Error building {}.\n {}", output_path.display(), error);
+        process::exit(1);
+    }
+}
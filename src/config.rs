@@ -1,7 +1,13 @@
 use std::env;
+use std::fs;
 use std::path::PathBuf;
 
+use crate::game::event::{
+    KEY_DOWNARROW, KEY_LEFTARROW, KEY_RALT, KEY_RCTRL, KEY_RIGHTARROW, KEY_RSHIFT, KEY_UPARROW,
+};
 use crate::option::{DoomOption, DoomOptions};
+use crate::play::CompatibilityLevel;
+use crate::sound::SfxDevice;
 use crate::util;
 use crate::wad::iwad;
 
@@ -27,7 +33,7 @@ impl Language {
 }
 
 // Equivalent to GameMode in original source
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum GameType {
     // DOOM 1 Shareware(Was enum Shareware)
     DoomIShareware,
@@ -49,12 +55,12 @@ impl GameType {
         iwad::VALID_IWADS
             .iter()
             .find(|iwad| wad_file_name.contains(iwad.name))
-            .map(|iwad| iwad.game_type.clone())
+            .map(|iwad| iwad.game_type)
             .unwrap_or(GameType::Unknown)
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum GameDifficulty {
     Baby,
     Easy,
@@ -63,6 +69,90 @@ pub enum GameDifficulty {
     Nightmare,
 }
 
+// The controls part of default.cfg. Keys are key codes, the mouse and
+// joystick buttons are bit numbers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyBindings {
+    pub key_right: i32,
+    pub key_left: i32,
+    pub key_up: i32,
+    pub key_down: i32,
+    pub key_strafe_left: i32,
+    pub key_strafe_right: i32,
+    pub key_fire: i32,
+    pub key_use: i32,
+    pub key_strafe: i32,
+    pub key_speed: i32,
+    pub use_mouse: bool,
+    pub mouse_fire: i32,
+    pub mouse_strafe: i32,
+    pub mouse_forward: i32,
+    pub use_joystick: bool,
+    pub joy_fire: i32,
+    pub joy_strafe: i32,
+    pub joy_use: i32,
+    pub joy_speed: i32,
+    pub mouse_sensitivity: i32,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            key_right: KEY_RIGHTARROW,
+            key_left: KEY_LEFTARROW,
+            key_up: KEY_UPARROW,
+            key_down: KEY_DOWNARROW,
+            key_strafe_left: i32::from(b','),
+            key_strafe_right: i32::from(b'.'),
+            key_fire: KEY_RCTRL,
+            key_use: i32::from(b' '),
+            key_strafe: KEY_RALT,
+            key_speed: KEY_RSHIFT,
+            use_mouse: true,
+            mouse_fire: 0,
+            mouse_strafe: 1,
+            mouse_forward: 2,
+            use_joystick: false,
+            joy_fire: 0,
+            joy_strafe: 1,
+            joy_use: 3,
+            joy_speed: 2,
+            mouse_sensitivity: 5,
+        }
+    }
+}
+
+impl KeyBindings {
+    // Sets the binding with the name default.cfg uses. Returns false for
+    // names that aren't bindings
+    fn set(&mut self, name: &str, value: i32) -> bool {
+        match name {
+            "key_right" => self.key_right = value,
+            "key_left" => self.key_left = value,
+            "key_up" => self.key_up = value,
+            "key_down" => self.key_down = value,
+            "key_strafeleft" => self.key_strafe_left = value,
+            "key_straferight" => self.key_strafe_right = value,
+            "key_fire" => self.key_fire = value,
+            "key_use" => self.key_use = value,
+            "key_strafe" => self.key_strafe = value,
+            "key_speed" => self.key_speed = value,
+            "use_mouse" => self.use_mouse = value != 0,
+            "mouseb_fire" => self.mouse_fire = value,
+            "mouseb_strafe" => self.mouse_strafe = value,
+            "mouseb_forward" => self.mouse_forward = value,
+            "use_joystick" => self.use_joystick = value != 0,
+            "joyb_fire" => self.joy_fire = value,
+            "joyb_strafe" => self.joy_strafe = value,
+            "joyb_use" => self.joy_use = value,
+            "joyb_speed" => self.joy_speed = value,
+            "mouse_sensitivity" => self.mouse_sensitivity = value,
+            _ => return false,
+        }
+        true
+    }
+}
+
 pub struct Config<'a> {
     pub config_file_path: PathBuf,
     pub wad_files_dir: PathBuf,
@@ -72,6 +162,13 @@ pub struct Config<'a> {
     pub language: Language,
     pub auto_start: bool,
     pub start_episode: u32,
+    pub sfx_device: SfxDevice,
+    // Print the ENDOOM screen when quitting
+    pub show_endoom: bool,
+    // Save screenshots as PNG instead of the original's PCX
+    pub png_screenshots: bool,
+    pub key_bindings: KeyBindings,
+    pub compatibility: CompatibilityLevel,
 }
 
 impl<'a> Config<'a> {
@@ -110,12 +207,38 @@ impl<'a> Config<'a> {
         self.language = Language::from_wad_file_name(iwad_name);
     }
 
+    // Config files are lines of "name value" like the original's default.cfg.
+    // Unknown names and values that don't parse are ignored like the original did
+    pub fn apply_config_text(&mut self, text: &str) {
+        for line in text.lines() {
+            let mut fields = line.split_whitespace();
+
+            if let (Some(name), Some(Ok(value))) =
+                (fields.next(), fields.next().map(str::parse::<i32>))
+            {
+                match name {
+                    "snd_sfxdevice" => self.sfx_device = SfxDevice::from_config_value(value),
+                    "show_endoom" => self.show_endoom = value != 0,
+                    "png_screenshots" => self.png_screenshots = value != 0,
+                    "compatibility_level" => {
+                        if let Some(compatibility) = CompatibilityLevel::from_config_value(value) {
+                            self.compatibility = compatibility;
+                        }
+                    }
+                    _ => {
+                        self.key_bindings.set(name, value);
+                    }
+                }
+            }
+        }
+    }
+
     pub fn new(doom_options: &DoomOptions) -> Self {
         let auto_start: bool = is_auto_start(doom_options);
 
         let game_difficulty: GameDifficulty = if doom_options.is_option_enabled("-skill") {
             let skill_option: &DoomOption = doom_options.get_option_by_name("-skill").unwrap();
-            match skill_option.values.get(0).unwrap().as_str() {
+            match skill_option.values.first().unwrap().as_str() {
                 "1" => GameDifficulty::Baby,
                 "2" => GameDifficulty::Easy,
                 "3" => GameDifficulty::Medium,
@@ -131,7 +254,7 @@ impl<'a> Config<'a> {
             let episode_option: &DoomOption = doom_options.get_option_by_name("-episode").unwrap();
             let value: u32 = episode_option
                 .values
-                .get(0)
+                .first()
                 .unwrap()
                 .parse::<u32>()
                 .unwrap_or_else(|_e| panic!("Unable to parse -episode value to number"));
@@ -181,14 +304,35 @@ impl<'a> Config<'a> {
 
         println!("Setting wad directory to {}", wad_files_dir.display());
 
-        Config {
+        let mut config: Config = Config {
             config_file_path,
             wad_files_dir,
             game_difficulty,
             auto_start,
             start_episode,
             ..Default::default()
+        };
+
+        // No config file yet just means we use the defaults
+        if let Ok(config_text) = fs::read_to_string(&config.config_file_path) {
+            config.apply_config_text(&config_text);
+        }
+
+        // The command line wins over the config file
+        if doom_options.is_option_enabled("-complevel") {
+            let complevel_option: &DoomOption =
+                doom_options.get_option_by_name("-complevel").unwrap();
+            config.compatibility = complevel_option
+                .values
+                .first()
+                .unwrap()
+                .parse::<i32>()
+                .ok()
+                .and_then(CompatibilityLevel::from_config_value)
+                .expect("Invalid value for -complevel option. Valid values are 0 and 1");
         }
+
+        config
     }
 }
 
@@ -203,6 +347,11 @@ impl<'a> Default for Config<'a> {
             language: Language::English,
             auto_start: false,
             start_episode: 1,
+            sfx_device: SfxDevice::Digital,
+            show_endoom: true,
+            png_screenshots: false,
+            key_bindings: Default::default(),
+            compatibility: CompatibilityLevel::Vanilla,
         }
     }
 }
@@ -224,6 +373,94 @@ mod tests {
         assert_eq!(config.game_difficulty, GameDifficulty::Medium);
         assert_eq!(config.auto_start, false);
         assert_eq!(config.start_episode, 1);
+        assert_eq!(config.sfx_device, SfxDevice::Digital);
+        assert!(config.show_endoom);
+    }
+
+    #[test]
+    fn test_config_apply_config_text_sets_sfx_device() {
+        let mut config: Config = Default::default();
+
+        config.apply_config_text("mouse_sensitivity\t\t5\nsnd_sfxdevice\t\t1\n");
+        assert_eq!(config.sfx_device, SfxDevice::PcSpeaker);
+
+        config.apply_config_text("snd_sfxdevice 0");
+        assert_eq!(config.sfx_device, SfxDevice::None);
+
+        // Bad values leave the current device alone
+        config.apply_config_text("snd_sfxdevice\nsnd_sfxdevice loud");
+        assert_eq!(config.sfx_device, SfxDevice::None);
+    }
+
+    #[test]
+    fn test_config_apply_config_text_sets_show_endoom() {
+        let mut config: Config = Default::default();
+
+        config.apply_config_text("show_endoom 0");
+        assert!(!config.show_endoom);
+
+        config.apply_config_text("show_endoom no");
+        assert!(!config.show_endoom);
+
+        config.apply_config_text("show_endoom\t1");
+        assert!(config.show_endoom);
+    }
+
+    #[test]
+    fn test_config_apply_config_text_sets_png_screenshots() {
+        let mut config: Config = Default::default();
+        assert!(!config.png_screenshots);
+
+        config.apply_config_text("png_screenshots 1");
+        assert!(config.png_screenshots);
+
+        config.apply_config_text("png_screenshots 0");
+        assert!(!config.png_screenshots);
+    }
+
+    #[test]
+    fn test_config_apply_config_text_sets_compatibility() {
+        let mut config: Config = Default::default();
+
+        config.apply_config_text("compatibility_level 1");
+        assert_eq!(config.compatibility, CompatibilityLevel::Fixed);
+
+        // Unknown levels leave it alone
+        config.apply_config_text("compatibility_level 7");
+        assert_eq!(config.compatibility, CompatibilityLevel::Fixed);
+
+        config.apply_config_text("compatibility_level 0");
+        assert_eq!(config.compatibility, CompatibilityLevel::Vanilla);
+    }
+
+    #[test]
+    fn test_config_new_compatibility_set_from_complevel_option() {
+        let cmd_args: Vec<String> = vec![String::from("-complevel"), String::from("1")];
+        let config: Config = Config::new(&DoomOptions::new(cmd_args));
+
+        assert_eq!(config.compatibility, CompatibilityLevel::Fixed);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_config_new_compatibility_with_bad_complevel_option_value() {
+        let cmd_args: Vec<String> = vec![String::from("-complevel"), String::from("fixed")];
+        Config::new(&DoomOptions::new(cmd_args));
+    }
+
+    #[test]
+    fn test_config_apply_config_text_sets_key_bindings() {
+        let mut config: Config = Default::default();
+        assert_eq!(config.key_bindings.key_fire, KEY_RCTRL);
+
+        config
+            .apply_config_text("key_fire 102\nkey_strafeleft 97\nuse_mouse 0\nmouse_sensitivity 9");
+
+        assert_eq!(config.key_bindings.key_fire, i32::from(b'f'));
+        assert_eq!(config.key_bindings.key_strafe_left, i32::from(b'a'));
+        assert!(!config.key_bindings.use_mouse);
+        assert_eq!(config.key_bindings.mouse_sensitivity, 9);
+        assert_eq!(config.key_bindings.key_up, KEY_UPARROW);
     }
 
     #[test]
@@ -0,0 +1,286 @@
+use std::fmt::{self, Display};
+
+use crate::endoom::font::{FONT_HEIGHT, FONT_WIDTH, VGA_FONT};
+use crate::image::png::{PngColor, PngImage};
+
+pub mod font;
+
+pub const ENDOOM_WIDTH: usize = 80;
+pub const ENDOOM_HEIGHT: usize = 25;
+pub const ENDOOM_SIZE: usize = ENDOOM_WIDTH * ENDOOM_HEIGHT * 2;
+
+// VGA text mode colour index to the ANSI colour index
+const VGA_TO_ANSI_COLOR: [u8; 8] = [0, 4, 2, 6, 1, 5, 3, 7];
+
+// Default VGA text mode palette
+pub const VGA_PALETTE: [[u8; 3]; 16] = [
+    [0x00, 0x00, 0x00],
+    [0x00, 0x00, 0xAA],
+    [0x00, 0xAA, 0x00],
+    [0x00, 0xAA, 0xAA],
+    [0xAA, 0x00, 0x00],
+    [0xAA, 0x00, 0xAA],
+    [0xAA, 0x55, 0x00],
+    [0xAA, 0xAA, 0xAA],
+    [0x55, 0x55, 0x55],
+    [0x55, 0x55, 0xFF],
+    [0x55, 0xFF, 0x55],
+    [0x55, 0xFF, 0xFF],
+    [0xFF, 0x55, 0x55],
+    [0xFF, 0x55, 0xFF],
+    [0xFF, 0xFF, 0x55],
+    [0xFF, 0xFF, 0xFF],
+];
+
+#[rustfmt::skip]
+const CP437_TO_UNICODE: [char; 256] = [
+    ' ', '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼',
+    '►', '◄', '↕', '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+    ' ', '!', '"', '#', '$', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ':', ';', '<', '=', '>', '?',
+    '@', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O',
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '[', '\\', ']', '^', '_',
+    '`', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o',
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '{', '|', '}', '~', '⌂',
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{A0}',
+];
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum EndoomError {
+    InvalidSize(usize),
+}
+
+impl Display for EndoomError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidSize(size) => write!(
+                f,
+                "Invalid ENDOOM size {}. Expected {} bytes",
+                size, ENDOOM_SIZE
+            ),
+        }
+    }
+}
+
+pub fn cp437_to_char(character: u8) -> char {
+    CP437_TO_UNICODE[usize::from(character)]
+}
+
+// Text mode screen shown when quitting. Every cell is a CP437
+// character followed by a VGA attribute byte
+// (bits 0-3 foreground, bits 4-6 background, bit 7 blink)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Endoom {
+    pub data: Vec<u8>,
+}
+
+impl Endoom {
+    pub fn from_lump(data: &[u8]) -> Result<Self, EndoomError> {
+        if data.len() != ENDOOM_SIZE {
+            return Err(EndoomError::InvalidSize(data.len()));
+        }
+
+        Ok(Endoom {
+            data: data.to_vec(),
+        })
+    }
+
+    pub fn cell(&self, x: usize, y: usize) -> (u8, u8) {
+        let index: usize = (y * ENDOOM_WIDTH + x) * 2;
+        (self.data[index], self.data[index + 1])
+    }
+
+    pub fn to_ansi(&self) -> String {
+        let mut ansi: String = String::new();
+
+        for y in 0..ENDOOM_HEIGHT {
+            let mut current_attribute: Option<u8> = None;
+
+            for x in 0..ENDOOM_WIDTH {
+                let (character, attribute): (u8, u8) = self.cell(x, y);
+
+                if current_attribute != Some(attribute) {
+                    ansi.push_str(&ansi_attribute(attribute));
+                    current_attribute = Some(attribute);
+                }
+
+                ansi.push(cp437_to_char(character));
+            }
+
+            ansi.push_str("\x1b[0m\n");
+        }
+
+        ansi
+    }
+
+    // Characters only, colours are dropped
+    pub fn to_plain_text(&self) -> String {
+        let mut text: String = String::new();
+
+        for y in 0..ENDOOM_HEIGHT {
+            let line: String = (0..ENDOOM_WIDTH)
+                .map(|x| cp437_to_char(self.cell(x, y).0))
+                .collect();
+
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+
+        text
+    }
+
+    // Draws the screen the way a VGA card would in 80x25 text mode(640x400).
+    // Blinking characters are drawn in their visible state
+    pub fn to_png(&self) -> PngImage {
+        let width: usize = ENDOOM_WIDTH * FONT_WIDTH;
+        let height: usize = ENDOOM_HEIGHT * FONT_HEIGHT;
+        let mut indices: Vec<u8> = vec![0; width * height];
+
+        for y in 0..ENDOOM_HEIGHT {
+            for x in 0..ENDOOM_WIDTH {
+                let (character, attribute): (u8, u8) = self.cell(x, y);
+                let foreground: u8 = attribute & 0x0F;
+                let background: u8 = (attribute >> 4) & 0x07;
+                let glyph: &[u8; FONT_HEIGHT] = &VGA_FONT[usize::from(character)];
+
+                for (row, bits) in glyph.iter().enumerate() {
+                    let start: usize = (y * FONT_HEIGHT + row) * width + x * FONT_WIDTH;
+
+                    for column in 0..FONT_WIDTH {
+                        indices[start + column] = if bits & (0x80 >> column) != 0 {
+                            foreground
+                        } else {
+                            background
+                        };
+                    }
+                }
+            }
+        }
+
+        PngImage {
+            width: width as u32,
+            height: height as u32,
+            color: PngColor::Indexed {
+                palette: VGA_PALETTE.to_vec(),
+                indices,
+            },
+            grab_offset: None,
+        }
+    }
+}
+
+fn ansi_attribute(attribute: u8) -> String {
+    let foreground: u8 = attribute & 0x0F;
+    let background: u8 = (attribute >> 4) & 0x07;
+    let blink: bool = attribute & 0x80 != 0;
+
+    let foreground_code: u8 = if foreground >= 8 {
+        90 + VGA_TO_ANSI_COLOR[usize::from(foreground - 8)]
+    } else {
+        30 + VGA_TO_ANSI_COLOR[usize::from(foreground)]
+    };
+    let background_code: u8 = 40 + VGA_TO_ANSI_COLOR[usize::from(background)];
+
+    if blink {
+        format!("\x1b[0;{};{};5m", foreground_code, background_code)
+    } else {
+        format!("\x1b[0;{};{}m", foreground_code, background_code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::endoom::{cp437_to_char, Endoom, EndoomError, ENDOOM_SIZE, VGA_PALETTE};
+    use crate::image::png::{PngColor, PngImage};
+
+    #[test]
+    fn test_endoom_error_implements_display_trait() {
+        println!("{}", EndoomError::InvalidSize(10));
+    }
+
+    #[test]
+    fn test_cp437_to_char_maps_ascii_and_extended_characters() {
+        assert_eq!(cp437_to_char(b'A'), 'A');
+        assert_eq!(cp437_to_char(0x01), '☺');
+        assert_eq!(cp437_to_char(0xB0), '░');
+        assert_eq!(cp437_to_char(0xDB), '█');
+        assert_eq!(cp437_to_char(0xE1), 'ß');
+    }
+
+    #[test]
+    fn test_endoom_from_lump_returns_error_for_invalid_size() {
+        assert_eq!(
+            Endoom::from_lump(&[0; 10]),
+            Err(EndoomError::InvalidSize(10))
+        );
+    }
+
+    #[test]
+    fn test_endoom_to_ansi_writes_colors_per_attribute_change() {
+        let mut data: Vec<u8> = [b' ', 0x07].repeat(ENDOOM_SIZE / 2);
+        // Bright red on blue 'D' followed by a blinking one
+        data[0] = b'D';
+        data[1] = 0x1C;
+        data[2] = b'D';
+        data[3] = 0x9C;
+
+        let ansi: String = Endoom::from_lump(&data).unwrap().to_ansi();
+        let first_line: &str = ansi.lines().next().unwrap();
+
+        assert!(first_line.starts_with("\x1b[0;91;44mD\x1b[0;91;44;5mD\x1b[0;37;40m "));
+        assert!(first_line.ends_with("\x1b[0m"));
+        assert_eq!(ansi.lines().count(), 25);
+    }
+
+    #[test]
+    fn test_endoom_to_plain_text_writes_characters_without_colors() {
+        let mut data: Vec<u8> = [b' ', 0x07].repeat(ENDOOM_SIZE / 2);
+        data[0] = b'D';
+        data[1] = 0x1C;
+        data[2] = 0xDB;
+        data[160] = b'!';
+
+        let text: String = Endoom::from_lump(&data).unwrap().to_plain_text();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 25);
+        assert_eq!(lines[0], "D█");
+        assert_eq!(lines[1], "!");
+        assert_eq!(lines[2], "");
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_endoom_to_png_draws_glyphs_with_vga_colors() {
+        let mut data: Vec<u8> = [b' ', 0x07].repeat(ENDOOM_SIZE / 2);
+        // Full block in yellow on blue, then a space with a red background
+        data[0] = 0xDB;
+        data[1] = 0x1E;
+        data[3] = 0x40;
+
+        let png: PngImage = Endoom::from_lump(&data).unwrap().to_png();
+        assert_eq!((png.width, png.height), (640, 400));
+
+        let decoded: PngImage = PngImage::decode(&png.encode()).unwrap();
+        let PngColor::Indexed { palette, indices } = decoded.color else {
+            panic!("Expected an indexed PNG");
+        };
+
+        assert_eq!(palette, VGA_PALETTE.to_vec());
+        assert_eq!(indices.len(), 640 * 400);
+        assert!((0..16).all(|row| indices[row * 640..row * 640 + 8]
+            .iter()
+            .all(|index| *index == 14)));
+        assert!((0..16).all(|row| indices[row * 640 + 8..row * 640 + 16]
+            .iter()
+            .all(|index| *index == 4)));
+        assert!(indices[640 * 16..].iter().all(|index| *index == 0));
+    }
+}
@@ -0,0 +1,434 @@
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::fixed::tables::{
+    finecosine, finesine, finetangent, tantoangle, ANGLETOFINESHIFT, FINEANGLES, FINEMASK,
+    SLOPERANGE,
+};
+
+pub mod tables;
+
+pub const FRACBITS: u32 = 16;
+pub const FRACUNIT: i32 = 1 << FRACBITS;
+
+pub const ANG45: u32 = 0x20000000;
+pub const ANG90: u32 = 0x40000000;
+pub const ANG180: u32 = 0x80000000;
+pub const ANG270: u32 = 0xc0000000;
+
+// 16.16 fixed point number(fixed_t). Adding and subtracting wrap the
+// same way the original's 32 bit ints did on overflow
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(pub i32);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(FRACUNIT);
+    pub const MAX: Fixed = Fixed(i32::MAX);
+    pub const MIN: Fixed = Fixed(i32::MIN);
+
+    pub fn from_int(value: i32) -> Self {
+        Fixed(value.wrapping_shl(FRACBITS))
+    }
+
+    // Rounds towards negative infinity like the original's >> FRACBITS
+    pub fn to_int(self) -> i32 {
+        self.0 >> FRACBITS
+    }
+
+    pub fn abs(self) -> Self {
+        Fixed(self.0.wrapping_abs())
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    fn add(self, other: Fixed) -> Fixed {
+        Fixed(self.0.wrapping_add(other.0))
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, other: Fixed) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    fn sub(self, other: Fixed) -> Fixed {
+        Fixed(self.0.wrapping_sub(other.0))
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, other: Fixed) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed(self.0.wrapping_neg())
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    fn mul(self, other: Fixed) -> Fixed {
+        fixed_mul(self, other)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+
+    fn div(self, other: Fixed) -> Fixed {
+        fixed_div(self, other)
+    }
+}
+
+// FixedMul. The result is truncated back to 32 bits
+pub fn fixed_mul(a: Fixed, b: Fixed) -> Fixed {
+    Fixed(((i64::from(a.0) * i64::from(b.0)) >> FRACBITS) as i32)
+}
+
+// FixedDiv. Results that would overflow are clamped to the largest value
+// with the right sign instead of being worked out
+pub fn fixed_div(a: Fixed, b: Fixed) -> Fixed {
+    if (a.0.wrapping_abs() >> 14) >= b.0.wrapping_abs() {
+        if (a.0 ^ b.0) < 0 {
+            Fixed::MIN
+        } else {
+            Fixed::MAX
+        }
+    } else {
+        fixed_div2(a, b)
+    }
+}
+
+// FixedDiv2. The original did this with doubles and errored out with
+// "FixedDiv: divide by zero" when the result didn't fit
+fn fixed_div2(a: Fixed, b: Fixed) -> Fixed {
+    let result: i64 = (i64::from(a.0) << FRACBITS) / i64::from(b.0);
+
+    match i32::try_from(result) {
+        Ok(result) => Fixed(result),
+        Err(_) => panic!("FixedDiv: divide by zero"),
+    }
+}
+
+// Binary angle measurement(angle_t). The full u32 range is one turn
+// so angles wrap around on their own
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Angle(pub u32);
+
+impl Angle {
+    pub const ANG45: Angle = Angle(ANG45);
+    pub const ANG90: Angle = Angle(ANG90);
+    pub const ANG180: Angle = Angle(ANG180);
+    pub const ANG270: Angle = Angle(ANG270);
+
+    // Index into the fine sine/cosine tables
+    pub fn fine_index(self) -> usize {
+        (self.0 >> ANGLETOFINESHIFT) as usize & FINEMASK
+    }
+
+    pub fn sin(self) -> Fixed {
+        finesine(self.fine_index())
+    }
+
+    pub fn cos(self) -> Fixed {
+        finecosine(self.fine_index())
+    }
+
+    // Only valid for angles between -90 and 90 degrees, same as the
+    // original which offset the angle by 90 degrees before looking it up.
+    // Anything from 90 degrees on runs past the end of the table so it
+    // gets the steepest tangent there is instead
+    pub fn tan(self) -> Fixed {
+        let fine_angle: usize = self.0.wrapping_add(ANG90) as usize >> ANGLETOFINESHIFT;
+        finetangent(fine_angle.min(FINEANGLES / 2 - 1))
+    }
+}
+
+impl Add for Angle {
+    type Output = Angle;
+
+    fn add(self, other: Angle) -> Angle {
+        Angle(self.0.wrapping_add(other.0))
+    }
+}
+
+impl AddAssign for Angle {
+    fn add_assign(&mut self, other: Angle) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Angle {
+    type Output = Angle;
+
+    fn sub(self, other: Angle) -> Angle {
+        Angle(self.0.wrapping_sub(other.0))
+    }
+}
+
+impl SubAssign for Angle {
+    fn sub_assign(&mut self, other: Angle) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+
+    fn neg(self) -> Angle {
+        Angle(self.0.wrapping_neg())
+    }
+}
+
+// SlopeDiv. Turns num/den into an index for the TANTOANGLE table
+pub fn slope_div(num: u32, den: u32) -> u32 {
+    if den < 512 {
+        return SLOPERANGE;
+    }
+
+    let answer: u32 = (num << 3) / (den >> 8);
+
+    answer.min(SLOPERANGE)
+}
+
+// R_PointToAngle2. Angle of the line from (from_x, from_y) to (to_x, to_y)
+// worked out one octant at a time with the tangent table.
+// R_PointToAngle is this with the view position as the start point
+pub fn point_to_angle(from_x: Fixed, from_y: Fixed, to_x: Fixed, to_y: Fixed) -> Angle {
+    let x: i32 = to_x.0.wrapping_sub(from_x.0);
+    let y: i32 = to_y.0.wrapping_sub(from_y.0);
+
+    if x == 0 && y == 0 {
+        return Angle(0);
+    }
+
+    // The negated values are used as unsigned, i32::MIN stays as 2^31
+    let abs_x: u32 = x.wrapping_abs() as u32;
+    let abs_y: u32 = y.wrapping_abs() as u32;
+
+    let angle: u32 = if x >= 0 {
+        if y >= 0 {
+            if abs_x > abs_y {
+                // octant 0
+                tantoangle(slope_div(abs_y, abs_x)).0
+            } else {
+                // octant 1
+                (ANG90 - 1).wrapping_sub(tantoangle(slope_div(abs_x, abs_y)).0)
+            }
+        } else if abs_x > abs_y {
+            // octant 8
+            tantoangle(slope_div(abs_y, abs_x)).0.wrapping_neg()
+        } else {
+            // octant 7
+            ANG270.wrapping_add(tantoangle(slope_div(abs_x, abs_y)).0)
+        }
+    } else if y >= 0 {
+        if abs_x > abs_y {
+            // octant 3
+            (ANG180 - 1).wrapping_sub(tantoangle(slope_div(abs_y, abs_x)).0)
+        } else {
+            // octant 2
+            ANG90.wrapping_add(tantoangle(slope_div(abs_x, abs_y)).0)
+        }
+    } else if abs_x > abs_y {
+        // octant 4
+        ANG180.wrapping_add(tantoangle(slope_div(abs_y, abs_x)).0)
+    } else {
+        // octant 5
+        (ANG270 - 1).wrapping_sub(tantoangle(slope_div(abs_x, abs_y)).0)
+    };
+
+    Angle(angle)
+}
+
+// P_AproxDistance. Cheap distance that is off by up to ~12%
+pub fn aprox_distance(dx: Fixed, dy: Fixed) -> Fixed {
+    let dx: i32 = dx.0.wrapping_abs();
+    let dy: i32 = dy.0.wrapping_abs();
+
+    if dx < dy {
+        Fixed(dx.wrapping_add(dy).wrapping_sub(dx >> 1))
+    } else {
+        Fixed(dx.wrapping_add(dy).wrapping_sub(dy >> 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{
+        aprox_distance, fixed_div, fixed_mul, point_to_angle, slope_div, Angle, Fixed, ANG180,
+        ANG270, ANG45, ANG90, FRACUNIT,
+    };
+
+    #[test]
+    fn test_fixed_conversions_and_wrapping_ops() {
+        assert_eq!(Fixed::from_int(3), Fixed(3 * FRACUNIT));
+        assert_eq!(Fixed(-1).to_int(), -1);
+        assert_eq!(Fixed(FRACUNIT + FRACUNIT / 2).to_int(), 1);
+        assert_eq!(Fixed::MAX + Fixed(1), Fixed::MIN);
+        assert_eq!(Fixed::MIN - Fixed(1), Fixed::MAX);
+        assert_eq!(-Fixed::MIN, Fixed::MIN);
+        assert_eq!(Fixed::MIN.abs(), Fixed::MIN);
+
+        let mut value: Fixed = Fixed::ONE;
+        value += Fixed::ONE;
+        value -= Fixed(FRACUNIT / 2);
+        assert_eq!(value, Fixed(FRACUNIT * 3 / 2));
+    }
+
+    #[test]
+    fn test_fixed_mul_truncates_like_the_original() {
+        assert_eq!(
+            fixed_mul(Fixed::from_int(3), Fixed(FRACUNIT / 2)),
+            Fixed(3 * FRACUNIT / 2)
+        );
+        assert_eq!(
+            Fixed::from_int(-2) * Fixed::from_int(5),
+            Fixed::from_int(-10)
+        );
+        // Shifting rounds down, not towards zero
+        assert_eq!(fixed_mul(Fixed(-1), Fixed(1)), Fixed(-1));
+        assert_eq!(fixed_mul(Fixed(1), Fixed(1)), Fixed(0));
+        // Overflow keeps the bottom 32 bits
+        assert_eq!(
+            fixed_mul(Fixed::from_int(30000), Fixed::from_int(30000)),
+            Fixed((900000000_i64 << 16) as i32)
+        );
+    }
+
+    #[test]
+    fn test_fixed_div_clamps_overflow_like_the_original() {
+        assert_eq!(
+            fixed_div(Fixed::from_int(10), Fixed::from_int(4)),
+            Fixed(163840)
+        );
+        assert_eq!(Fixed::from_int(-1) / Fixed::from_int(3), Fixed(-21845));
+        assert_eq!(fixed_div(Fixed::ONE, Fixed(0)), Fixed::MAX);
+        assert_eq!(fixed_div(Fixed(-FRACUNIT), Fixed(0)), Fixed::MIN);
+        assert_eq!(fixed_div(Fixed::from_int(20000), Fixed(1)), Fixed::MAX);
+        assert_eq!(fixed_div(Fixed::from_int(20000), Fixed(-1)), Fixed::MIN);
+        // Just under the clamp check
+        assert_eq!(fixed_div(Fixed(0x3FFF), Fixed(1)), Fixed(0x3FFF << 16));
+    }
+
+    #[test]
+    #[should_panic(expected = "FixedDiv: divide by zero")]
+    fn test_fixed_div_panics_when_result_does_not_fit() {
+        // abs() of the smallest value is still negative so it skips the clamp
+        fixed_div(Fixed::MIN, Fixed(1));
+    }
+
+    #[test]
+    fn test_angle_wrapping_ops_and_lookups() {
+        assert_eq!(Angle::ANG270 + Angle::ANG180, Angle::ANG90);
+        assert_eq!(Angle(0) - Angle::ANG90, Angle::ANG270);
+        assert_eq!(-Angle::ANG45, Angle(ANG270 + ANG45));
+
+        let mut angle: Angle = Angle::ANG180;
+        angle += Angle::ANG180;
+        assert_eq!(angle, Angle(0));
+        angle -= Angle::ANG45;
+        assert_eq!(angle.fine_index(), 7168);
+
+        assert_eq!(Angle(0).sin(), Fixed(25));
+        assert_eq!(Angle(0).cos(), Fixed(65535));
+        assert_eq!(Angle::ANG90.sin(), Fixed(65535));
+        assert_eq!(Angle::ANG180.sin(), Fixed(-25));
+        assert_eq!(Angle::ANG270.cos(), Fixed(25));
+        assert_eq!(Angle(0).tan(), Fixed(25));
+        assert_eq!(Angle::ANG45.tan(), Fixed(65586));
+        assert_eq!((-Angle::ANG45).tan(), Fixed(-65485));
+    }
+
+    #[test]
+    fn test_angle_tan_clamps_at_90_degrees() {
+        assert_eq!(Angle(ANG90 - 1).tan(), Fixed(170910304));
+        assert_eq!(Angle::ANG90.tan(), Fixed(170910304));
+        assert_eq!(Angle::ANG180.tan(), Fixed(170910304));
+        assert_eq!(Angle::ANG270.tan(), Fixed(-170910304));
+    }
+
+    #[test]
+    fn test_slope_div() {
+        assert_eq!(slope_div(100, 511), 2048);
+        assert_eq!(slope_div(0, 512), 0);
+        assert_eq!(slope_div(1 << 16, 1 << 16), 2048);
+        assert_eq!(slope_div(1 << 15, 1 << 16), 1024);
+        assert_eq!(slope_div(1 << 20, 1 << 16), 2048);
+    }
+
+    #[test]
+    fn test_point_to_angle_in_every_octant() {
+        let point_to: fn(i32, i32) -> u32 = |x, y| {
+            point_to_angle(
+                Fixed::ZERO,
+                Fixed::ZERO,
+                Fixed::from_int(x),
+                Fixed::from_int(y),
+            )
+            .0
+        };
+
+        assert_eq!(point_to(0, 0), 0);
+        assert_eq!(point_to(10, 0), 0);
+        assert_eq!(point_to(0, 10), ANG90 - 1);
+        assert_eq!(point_to(-10, 0), ANG180 - 1);
+        assert_eq!(point_to(0, -10), ANG270);
+        assert_eq!(point_to(10, 10), ANG45 - 1);
+        assert_eq!(point_to(-10, 10), ANG90 + ANG45);
+        assert_eq!(point_to(-10, -10), ANG270 - 1 - ANG45);
+        assert_eq!(point_to(10, -10), ANG270 + ANG45);
+
+        // 1/2 slope in each octant
+        assert_eq!(point_to(20, 10), 316933405);
+        assert_eq!(point_to(10, 20), ANG90 - 1 - 316933405);
+        assert_eq!(point_to(-10, 20), ANG90 + 316933405);
+        assert_eq!(point_to(-20, 10), ANG180 - 1 - 316933405);
+        assert_eq!(point_to(-20, -10), ANG180 + 316933405);
+        assert_eq!(point_to(-10, -20), ANG270 - 1 - 316933405);
+        assert_eq!(point_to(10, -20), ANG270 + 316933405);
+        assert_eq!(point_to(20, -10), 316933405_u32.wrapping_neg());
+
+        assert_eq!(
+            point_to_angle(
+                Fixed::from_int(5),
+                Fixed::from_int(5),
+                Fixed::from_int(25),
+                Fixed::from_int(15)
+            ),
+            Angle(316933405)
+        );
+    }
+
+    #[test]
+    fn test_aprox_distance() {
+        assert_eq!(
+            aprox_distance(Fixed::from_int(3), Fixed::from_int(4)),
+            Fixed(5 * FRACUNIT + FRACUNIT / 2)
+        );
+        assert_eq!(
+            aprox_distance(Fixed::from_int(-4), Fixed::from_int(3)),
+            Fixed(5 * FRACUNIT + FRACUNIT / 2)
+        );
+        assert_eq!(
+            aprox_distance(Fixed::from_int(10), Fixed::ZERO),
+            Fixed::from_int(10)
+        );
+        assert_eq!(
+            aprox_distance(Fixed::ZERO, Fixed::from_int(-10)),
+            Fixed::from_int(10)
+        );
+    }
+}
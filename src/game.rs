@@ -0,0 +1,697 @@
+// Port of the game flow parts of g_game.c. A Game owns the loaded files and
+// the current level and moves from level to level, advancing exactly one
+// tic for every set of ticcmds it's given no matter how fast it's called
+use std::path::PathBuf;
+
+use crate::config::{Config, GameType};
+use crate::game::demo::{DemoHeader, DemoPlayback, DemoRecorder};
+use crate::game::savegame::save_game_dir;
+use crate::m_random::Random;
+use crate::option::DoomOptions;
+use crate::play::level::{LevelError, MapLumps};
+use crate::play::mobj::MF_SHADOW;
+use crate::play::player::{Player, PlayerState, NUMCARDS, NUMPOWERS};
+use crate::play::saveg::PicNumbers;
+use crate::play::ticcmd::{
+    TicCmd, BTS_PAUSE, BTS_SAVEGAME, BTS_SAVEMASK, BTS_SAVESHIFT, BT_SPECIAL, BT_SPECIALMASK,
+};
+use crate::play::world::{LevelExit, World, WorldSettings};
+use crate::play::MAXPLAYERS;
+use crate::wad::DoomFile;
+
+pub mod demo;
+pub mod desync;
+pub mod event;
+pub mod input;
+pub mod realtime;
+pub mod savegame;
+pub mod timedemo;
+
+// What the game has to do before the next tic runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameAction {
+    Nothing,
+    LoadLevel,
+    LoadGame,
+    SaveGame,
+    Completed,
+    Victory,
+    WorldDone,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameState {
+    Level,
+    // The game has been finished, nothing is simulated any more
+    Finale,
+}
+
+pub struct Game {
+    pub doom_files: Vec<DoomFile>,
+    pub world: World,
+    pub game_state: GameState,
+    pub game_action: GameAction,
+    pub paused: bool,
+    // Which exit the last level was left through
+    pub secret_exit: bool,
+    // The map the intermission goes on to
+    next_map: i32,
+    pub demo_playback: Option<DemoPlayback>,
+    pub demo_recorder: Option<DemoRecorder>,
+    // Where the save slots are
+    pub save_dir: PathBuf,
+    // Flat and texture numbers for savegames
+    pic_numbers: PicNumbers,
+    save_game_slot: usize,
+    save_description: String,
+    // Set by G_SaveGame, the save goes out with the next ticcmd
+    send_save: bool,
+    load_game_path: Option<PathBuf>,
+}
+
+impl Game {
+    pub fn new(
+        config: &Config,
+        doom_options: &DoomOptions,
+        doom_files: Vec<DoomFile>,
+    ) -> Result<Self, LevelError> {
+        let mut game: Game =
+            Game::with_settings(WorldSettings::new(config, doom_options), doom_files)?;
+        game.save_dir = save_game_dir(config);
+        Ok(game)
+    }
+
+    // G_InitNew. Starts a new game on the settings' episode and map with
+    // every player reborn and the random number index cleared
+    pub fn with_settings(
+        settings: WorldSettings,
+        mut doom_files: Vec<DoomFile>,
+    ) -> Result<Self, LevelError> {
+        let settings: WorldSettings = init_new_settings(settings);
+
+        // Force players to be initialized upon first level load
+        let players: [Player; MAXPLAYERS] = Default::default();
+        let world: World = load_world(&mut doom_files, settings, players, Random::new(), 0)?;
+        let pic_numbers: PicNumbers = PicNumbers::from_wad(&mut doom_files);
+
+        Ok(Game {
+            doom_files,
+            world,
+            game_state: GameState::Level,
+            game_action: GameAction::Nothing,
+            paused: false,
+            secret_exit: false,
+            next_map: settings.map,
+            demo_playback: None,
+            demo_recorder: None,
+            save_dir: PathBuf::new(),
+            pic_numbers,
+            save_game_slot: 0,
+            save_description: String::new(),
+            send_save: false,
+            load_game_path: None,
+        })
+    }
+
+    // G_DoPlayDemo. The demo's header replaces the skill, map and the rest
+    // of the settings it recorded
+    pub fn play_demo(
+        mut settings: WorldSettings,
+        doom_files: Vec<DoomFile>,
+        demo_playback: DemoPlayback,
+    ) -> Result<Self, LevelError> {
+        demo_playback.header.apply(&mut settings);
+
+        let mut game: Game = Game::with_settings(settings, doom_files)?;
+        game.demo_playback = Some(demo_playback);
+        Ok(game)
+    }
+
+    // G_BeginRecording. Every tic run from here on goes into the demo
+    pub fn start_recording(&mut self) {
+        let header: DemoHeader = DemoHeader::from_settings(&self.world.settings);
+        self.demo_recorder = Some(DemoRecorder::new(&header));
+    }
+
+    // The recorded demo with its end marker, for G_CheckDemoStatus to write
+    // out
+    pub fn finish_recording(&mut self) -> Option<Vec<u8>> {
+        self.demo_recorder.take().map(DemoRecorder::finish)
+    }
+
+    // Nothing runs any more once the demo being played back has ended
+    pub fn demo_finished(&self) -> bool {
+        self.demo_playback
+            .as_ref()
+            .is_some_and(DemoPlayback::is_finished)
+    }
+
+    // G_Ticker. Make ticcmd_ts for the players, one per player in the game.
+    // Players without a ticcmd stand still and a demo being played back
+    // replaces them
+    pub fn tick(&mut self, cmds: &[TicCmd]) {
+        if self.demo_finished() {
+            return;
+        }
+
+        // Do player reborns if needed
+        for player in 0..MAXPLAYERS {
+            if self.world.settings.players_in_game[player]
+                && self.world.players[player].player_state == PlayerState::Reborn
+            {
+                self.do_reborn(player);
+            }
+        }
+
+        // Do things to change the game state
+        while self.game_action != GameAction::Nothing {
+            match self.game_action {
+                GameAction::LoadLevel => self.do_load_level(),
+                GameAction::LoadGame => self.do_load_game(),
+                GameAction::SaveGame => self.do_save_game(),
+                GameAction::Completed => self.do_completed(),
+                GameAction::Victory => self.do_victory(),
+                GameAction::WorldDone => self.do_world_done(),
+                GameAction::Nothing => {}
+            }
+        }
+
+        // Get commands, check consistancy, and build new consistancy check
+        for player in 0..MAXPLAYERS {
+            if !self.world.settings.players_in_game[player] {
+                continue;
+            }
+
+            let mut cmd: TicCmd = cmds.get(player).copied().unwrap_or_default();
+            // G_BuildTiccmd's part of saving
+            if player == self.world.settings.console_player && self.send_save {
+                self.send_save = false;
+                cmd.buttons =
+                    BT_SPECIAL | BTS_SAVEGAME | ((self.save_game_slot as u8) << BTS_SAVESHIFT);
+            }
+            if let Some(demo_playback) = &mut self.demo_playback {
+                match demo_playback.read_cmd() {
+                    Some(demo_cmd) => cmd = demo_cmd,
+                    // The demo is over, the rest of the tic never runs
+                    None => return,
+                }
+            }
+            if let Some(demo_recorder) = &mut self.demo_recorder {
+                cmd = demo_recorder.write_cmd(&cmd);
+            }
+            self.world.players[player].cmd = cmd;
+
+            // Check for special buttons
+            if cmd.buttons & BT_SPECIAL != 0 {
+                match cmd.buttons & BT_SPECIALMASK {
+                    BTS_PAUSE => self.paused = !self.paused,
+                    BTS_SAVEGAME => {
+                        if self.save_description.is_empty() {
+                            self.save_description = String::from("NET GAME");
+                        }
+                        self.save_game_slot =
+                            usize::from((cmd.buttons & BTS_SAVEMASK) >> BTS_SAVESHIFT);
+                        self.game_action = GameAction::SaveGame;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Do main actions
+        if self.game_state == GameState::Level && !self.paused {
+            self.world.tick();
+
+            if let Some(exit) = self.world.level_exit.take() {
+                self.exit_level(exit);
+            }
+        } else {
+            // The game tic keeps counting while the world is stopped
+            self.world.game_tic += 1;
+        }
+    }
+
+    // G_ExitLevel and G_SecretExitLevel
+    fn exit_level(&mut self, exit: LevelExit) {
+        // IF NO WOLF3D LEVELS, NO SECRET EXIT!
+        self.secret_exit = exit == LevelExit::Secret
+            && (self.world.settings.game_type != GameType::DoomII
+                || self.doom_files.iter().any(|doom_file| {
+                    doom_file
+                        .lumps
+                        .iter()
+                        .any(|lump| lump.name.trim_end_matches('\0') == "MAP31")
+                }));
+        self.game_action = GameAction::Completed;
+    }
+
+    // G_DoReborn
+    fn do_reborn(&mut self, player: usize) {
+        if !self.world.settings.netgame {
+            // Reload the level from scratch
+            self.game_action = GameAction::LoadLevel;
+        } else {
+            // Respawn at the start
+            self.world.respawn_player(player);
+        }
+    }
+
+    // G_DoLoadLevel. Players carry over from the last level, the dead ones
+    // start again from scratch
+    fn do_load_level(&mut self) {
+        self.game_state = GameState::Level;
+
+        let mut players: [Player; MAXPLAYERS] = std::mem::take(&mut self.world.players);
+        for (player_num, player) in players.iter_mut().enumerate() {
+            if self.world.settings.players_in_game[player_num]
+                && player.player_state == PlayerState::Dead
+            {
+                player.player_state = PlayerState::Reborn;
+            }
+            player.frags = [0; MAXPLAYERS];
+        }
+
+        let random: Random = self.world.random.clone();
+        self.world = load_world(
+            &mut self.doom_files,
+            self.world.settings,
+            players,
+            random,
+            self.world.game_tic,
+        )
+        .unwrap_or_else(|error| panic!("G_DoLoadLevel: {}", error));
+        self.game_action = GameAction::Nothing;
+    }
+
+    // G_DoCompleted
+    fn do_completed(&mut self) {
+        self.game_action = GameAction::Nothing;
+
+        for player in 0..MAXPLAYERS {
+            if self.world.settings.players_in_game[player] {
+                // Take away cards and stuff
+                self.player_finish_level(player);
+            }
+        }
+
+        let settings: WorldSettings = self.world.settings;
+        if settings.game_type != GameType::DoomII && settings.map == 8 {
+            self.game_action = GameAction::Victory;
+            return;
+        }
+
+        self.next_map = next_map(
+            settings.game_type,
+            settings.episode,
+            settings.map,
+            self.secret_exit,
+        );
+
+        // There's no intermission screen yet, go straight on like it was
+        // skipped
+        self.world_done();
+    }
+
+    // G_PlayerFinishLevel. Call when a player completes a level
+    fn player_finish_level(&mut self, player_num: usize) {
+        let player: &mut Player = &mut self.world.players[player_num];
+        player.powers = [0; NUMPOWERS];
+        player.cards = [false; NUMCARDS];
+        player.extra_light = 0;
+        player.fixed_colormap = 0;
+        player.damage_count = 0;
+        player.bonus_count = 0;
+
+        if let Some(mo) = player.mo {
+            // Cancel invisibility
+            self.world.thinkers.mobj_mut(mo).flags &= !MF_SHADOW;
+        }
+    }
+
+    // G_WorldDone
+    fn world_done(&mut self) {
+        self.game_action = GameAction::WorldDone;
+
+        // The text screens between DOOM II's chapters come with the finale,
+        // only the end of the game stops here for now
+        if self.world.settings.game_type == GameType::DoomII && self.world.settings.map == 30 {
+            self.game_state = GameState::Finale;
+            self.game_action = GameAction::Nothing;
+        }
+    }
+
+    // G_DoWorldDone
+    fn do_world_done(&mut self) {
+        self.world.settings.map = self.next_map;
+        self.do_load_level();
+    }
+
+    // The end of a DOOM episode. The text and the end screens come with
+    // the finale, the game stops here for now
+    fn do_victory(&mut self) {
+        self.game_state = GameState::Finale;
+        self.game_action = GameAction::Nothing;
+    }
+}
+
+// The lump name of a map, ExMy for DOOM and MAPxx for DOOM II
+pub fn map_name(game_type: GameType, episode: i32, map: i32) -> String {
+    if game_type == GameType::DoomII {
+        format!("MAP{:02}", map)
+    } else {
+        format!("E{}M{}", episode, map)
+    }
+}
+
+// The map the intermission goes to from the one just finished. The end of
+// a DOOM episode isn't handled here, that's the finale
+pub fn next_map(game_type: GameType, episode: i32, map: i32, secret_exit: bool) -> i32 {
+    if game_type == GameType::DoomII {
+        if secret_exit {
+            match map {
+                15 => 31,
+                31 => 32,
+                _ => map + 1,
+            }
+        } else {
+            match map {
+                31 | 32 => 16,
+                _ => map + 1,
+            }
+        }
+    } else if secret_exit {
+        // Go to secret level
+        9
+    } else if map == 9 {
+        // Returning from secret level
+        match episode {
+            1 => 4,
+            2 => 6,
+            3 => 7,
+            4 => 3,
+            _ => map + 1,
+        }
+    } else {
+        map + 1
+    }
+}
+
+// G_InitNew's checks on the episode and map
+fn init_new_settings(mut settings: WorldSettings) -> WorldSettings {
+    if settings.episode < 1 {
+        settings.episode = 1;
+    }
+
+    settings.episode = match settings.game_type {
+        GameType::UltimateDoom => settings.episode.min(4),
+        // Only start episode 1 on shareware
+        GameType::DoomIShareware => 1,
+        _ => settings.episode.min(3),
+    };
+
+    if settings.map < 1 {
+        settings.map = 1;
+    }
+
+    if settings.map > 9 && settings.game_type != GameType::DoomII {
+        settings.map = 9;
+    }
+
+    settings
+}
+
+// P_SetupLevel for the settings' episode and map
+fn load_world(
+    doom_files: &mut [DoomFile],
+    settings: WorldSettings,
+    players: [Player; MAXPLAYERS],
+    random: Random,
+    game_tic: i32,
+) -> Result<World, LevelError> {
+    let map_lumps: MapLumps = MapLumps::from_wad(
+        doom_files,
+        &map_name(settings.game_type, settings.episode, settings.map),
+    )?;
+    World::setup_level(settings, &map_lumps, players, random, game_tic)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::config::GameType;
+    use crate::fixed::Fixed;
+    use crate::game::demo::DemoPlayback;
+    use crate::game::{next_map, Game, GameAction, GameState};
+    use crate::play::mobj::MobjId;
+    use crate::play::player::{PlayerState, IT_BLUECARD, PW_STRENGTH, WP_SHOTGUN};
+    use crate::play::test_map::{map_wad_lumps, TestMap};
+    use crate::play::ticcmd::{TicCmd, BTS_PAUSE, BT_ATTACK, BT_SPECIAL};
+    use crate::play::world::{LevelExit, WorldSettings};
+    use crate::test_util::{create_test_dir, write_test_wad};
+    use crate::wad::{self, DoomFile};
+
+    fn create_test_wad(name: &str, maps: &[i32]) -> DoomFile {
+        let mut dir: PathBuf = create_test_dir(name);
+
+        let test_map: TestMap = TestMap::new()
+            .room(512, 0, 128)
+            .thing(64, 128, 0, 1, 7)
+            .thing(192, 128, 0, 2, 7);
+        let lumps: Vec<(String, Vec<u8>)> = maps
+            .iter()
+            .flat_map(|map| map_wad_lumps(&format!("MAP{:02}", map), &test_map.to_map_lumps()))
+            .collect();
+
+        dir.push("test.wad");
+        write_test_wad(&dir, &lumps);
+        wad::process_file(&dir).unwrap()
+    }
+
+    pub fn create_game(name: &str, maps: &[i32]) -> Game {
+        create_game_with(name, maps, WorldSettings::default())
+    }
+
+    fn create_game_with(name: &str, maps: &[i32], settings: WorldSettings) -> Game {
+        Game::with_settings(settings, vec![create_test_wad(name, maps)]).unwrap()
+    }
+
+    fn forward() -> TicCmd {
+        TicCmd {
+            forward_move: 50,
+            angle_turn: 100,
+            buttons: BT_ATTACK,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_game_tick_advances_one_tic_per_call() {
+        let mut game: Game = create_game("game-tick", &[1]);
+        let mo: MobjId = game.world.players[0].mo.unwrap();
+        let start_x: Fixed = game.world.thinkers.mobj(mo).x;
+
+        // Long enough for the pistol to come up and fire
+        for _ in 0..20 {
+            game.tick(&[forward()]);
+        }
+
+        assert_eq!(game.world.level_time, 20);
+        assert_eq!(game.world.game_tic, 20);
+        assert!(game.world.thinkers.mobj(mo).x > start_x);
+        assert!(game.world.players[0].ammo[0] < 50);
+    }
+
+    #[test]
+    fn test_game_tick_is_deterministic() {
+        let mut first: Game = create_game("game-deterministic-1", &[1]);
+        let mut second: Game = create_game("game-deterministic-2", &[1]);
+
+        for _ in 0..50 {
+            first.tick(&[forward()]);
+            second.tick(&[forward()]);
+        }
+
+        let first_mo: MobjId = first.world.players[0].mo.unwrap();
+        let second_mo: MobjId = second.world.players[0].mo.unwrap();
+        let first_pos = {
+            let mobj = first.world.thinkers.mobj(first_mo);
+            (mobj.x, mobj.y, mobj.angle)
+        };
+        let second_pos = {
+            let mobj = second.world.thinkers.mobj(second_mo);
+            (mobj.x, mobj.y, mobj.angle)
+        };
+        assert_eq!(first_pos, second_pos);
+        assert_eq!(
+            format!("{:?}", first.world.random),
+            format!("{:?}", second.world.random)
+        );
+    }
+
+    #[test]
+    fn test_game_goes_on_to_the_next_map_after_the_exit() {
+        let mut game: Game = create_game("game-exit", &[1, 2]);
+        game.world.players[0].weapon_owned[WP_SHOTGUN] = true;
+        game.world.players[0].cards[IT_BLUECARD] = true;
+        game.world.players[0].powers[PW_STRENGTH] = 1;
+        game.tick(&[]);
+
+        game.world.exit_level(LevelExit::Normal);
+        game.tick(&[]);
+        assert_eq!(game.world.settings.map, 1);
+        assert_eq!(game.game_action, GameAction::Completed);
+
+        game.tick(&[]);
+        assert_eq!(game.world.settings.map, 2);
+        assert_eq!(game.world.level_time, 1);
+        assert_eq!(game.world.game_tic, 3);
+        assert!(game.world.players[0].weapon_owned[WP_SHOTGUN]);
+        assert!(!game.world.players[0].cards[IT_BLUECARD]);
+        assert_eq!(game.world.players[0].powers[PW_STRENGTH], 0);
+    }
+
+    #[test]
+    fn test_game_secret_exit_needs_map31() {
+        let mut game: Game = create_game("game-secret-exit", &[1, 2]);
+
+        game.world.exit_level(LevelExit::Secret);
+        game.tick(&[]);
+        game.tick(&[]);
+
+        assert!(!game.secret_exit);
+        assert_eq!(game.world.settings.map, 2);
+    }
+
+    #[test]
+    fn test_game_reloads_the_level_when_the_player_is_reborn() {
+        let mut game: Game = create_game("game-reborn", &[1]);
+        for _ in 0..5 {
+            game.tick(&[forward()]);
+        }
+
+        game.world.players[0].player_state = PlayerState::Reborn;
+        game.tick(&[]);
+
+        assert_eq!(game.world.level_time, 1);
+        assert_eq!(game.world.game_tic, 6);
+        assert_eq!(game.world.players[0].player_state, PlayerState::Live);
+        assert_eq!(game.world.players[0].ammo[0], 50);
+    }
+
+    #[test]
+    fn test_game_respawns_players_at_their_start_in_netgames() {
+        let mut game: Game = create_game_with(
+            "game-netgame-reborn",
+            &[1],
+            WorldSettings {
+                netgame: true,
+                players_in_game: [true, true, false, false],
+                ..Default::default()
+            },
+        );
+        let corpse: MobjId = game.world.players[1].mo.unwrap();
+
+        game.world.players[1].player_state = PlayerState::Reborn;
+        game.tick(&[]);
+
+        let mo: MobjId = game.world.players[1].mo.unwrap();
+        assert_ne!(mo, corpse);
+        assert_eq!(game.world.thinkers.mobj(corpse).player, None);
+        assert_eq!(game.world.body_queue[0], Some(corpse));
+        assert_eq!(game.world.level_time, 1);
+    }
+
+    #[test]
+    fn test_game_pause_button_stops_the_world() {
+        let mut game: Game = create_game("game-pause", &[1]);
+        let pause: TicCmd = TicCmd {
+            buttons: BT_SPECIAL | BTS_PAUSE,
+            ..Default::default()
+        };
+
+        game.tick(&[pause]);
+        game.tick(&[]);
+        assert!(game.paused);
+        assert_eq!(game.world.level_time, 0);
+        assert_eq!(game.world.game_tic, 2);
+
+        game.tick(&[pause]);
+        assert!(!game.paused);
+        assert_eq!(game.world.level_time, 1);
+    }
+
+    #[test]
+    fn test_game_stops_after_the_last_map() {
+        let mut game: Game = create_game_with(
+            "game-last-map",
+            &[30],
+            WorldSettings {
+                map: 30,
+                ..Default::default()
+            },
+        );
+
+        game.world.exit_level(LevelExit::Normal);
+        game.tick(&[]);
+        game.tick(&[]);
+        game.tick(&[]);
+
+        assert_eq!(game.game_state, GameState::Finale);
+        assert_eq!(game.world.level_time, 1);
+    }
+
+    #[test]
+    fn test_game_plays_back_what_it_recorded() {
+        let mut recording: Game = create_game("game-demo-record", &[1]);
+        recording.start_recording();
+        for tic in 0..40 {
+            let cmd: TicCmd = TicCmd {
+                angle_turn: if tic < 20 { 300 } else { -500 },
+                ..forward()
+            };
+            recording.tick(&[cmd]);
+        }
+        let demo_data: Vec<u8> = recording.finish_recording().unwrap();
+        assert_eq!(demo_data.len(), 13 + 40 * 4 + 1);
+
+        let mut playback: Game = Game::play_demo(
+            WorldSettings::default(),
+            vec![create_test_wad("game-demo-playback", &[1])],
+            DemoPlayback::from_lump_data(demo_data).unwrap(),
+        )
+        .unwrap();
+        for _ in 0..40 {
+            playback.tick(&[]);
+        }
+        assert!(!playback.demo_finished());
+        playback.tick(&[]);
+        assert!(playback.demo_finished());
+        // Nothing more runs once the demo is over
+        playback.tick(&[]);
+
+        let position = |game: &Game| {
+            let mobj = game.world.thinkers.mobj(game.world.players[0].mo.unwrap());
+            (mobj.x, mobj.y, mobj.angle, game.world.level_time)
+        };
+        assert_eq!(position(&playback), position(&recording));
+        assert_eq!(
+            format!("{:?}", playback.world.random),
+            format!("{:?}", recording.world.random)
+        );
+    }
+
+    #[test]
+    fn test_next_map_follows_secret_exits() {
+        assert_eq!(next_map(GameType::DoomII, 1, 1, false), 2);
+        assert_eq!(next_map(GameType::DoomII, 1, 15, true), 31);
+        assert_eq!(next_map(GameType::DoomII, 1, 31, true), 32);
+        assert_eq!(next_map(GameType::DoomII, 1, 31, false), 16);
+        assert_eq!(next_map(GameType::DoomII, 1, 32, false), 16);
+        assert_eq!(next_map(GameType::DoomIRegistered, 1, 3, true), 9);
+        assert_eq!(next_map(GameType::DoomIRegistered, 1, 9, false), 4);
+        assert_eq!(next_map(GameType::DoomIRegistered, 2, 9, false), 6);
+        assert_eq!(next_map(GameType::DoomIRegistered, 3, 9, false), 7);
+        assert_eq!(next_map(GameType::UltimateDoom, 4, 9, false), 3);
+    }
+}
@@ -0,0 +1,400 @@
+// The demo parts of g_game.c. A demo is a header with the game settings
+// followed by every player's ticcmd for every tic, 4 bytes each, and ends
+// with DEMOMARKER. Versions before 1.4 had a shorter header without a
+// version number
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::Path;
+
+use crate::config::GameDifficulty;
+use crate::play::ticcmd::TicCmd;
+use crate::play::world::{Deathmatch, WorldSettings};
+use crate::play::MAXPLAYERS;
+use crate::wad::{self, DoomFile};
+
+// The version byte written to new demos, 1.9
+pub const VERSION: u8 = 109;
+// The first version with the long header
+pub const LONG_HEADER_VERSION: u8 = 104;
+pub const DEMOMARKER: u8 = 0x80;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum DemoError {
+    NotFound(String),
+    DifferentVersion(u8),
+    Truncated,
+    IoError(String),
+}
+
+impl Display for DemoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound(name) => write!(f, "Unable to find demo {}", name),
+            Self::DifferentVersion(version) => write!(
+                f,
+                "Demo is from a different game version! Version byte is {}",
+                version
+            ),
+            Self::Truncated => write!(f, "Demo header is cut short"),
+            Self::IoError(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DemoHeader {
+    // None for the 1.0 to 1.2 headers
+    pub version: Option<u8>,
+    pub skill: GameDifficulty,
+    pub episode: i32,
+    pub map: i32,
+    pub deathmatch: Deathmatch,
+    pub respawn_parm: bool,
+    pub fast_parm: bool,
+    pub no_monsters: bool,
+    pub console_player: usize,
+    pub players_in_game: [bool; MAXPLAYERS],
+}
+
+impl DemoHeader {
+    // G_BeginRecording
+    pub fn from_settings(settings: &WorldSettings) -> Self {
+        DemoHeader {
+            version: Some(VERSION),
+            skill: settings.skill,
+            episode: settings.episode,
+            map: settings.map,
+            deathmatch: settings.deathmatch,
+            respawn_parm: settings.respawn_parm,
+            fast_parm: settings.fast_parm,
+            no_monsters: settings.no_monsters,
+            console_player: settings.console_player,
+            players_in_game: settings.players_in_game,
+        }
+    }
+
+    // Returns the header and its size in bytes
+    pub fn from_bytes(data: &[u8]) -> Result<(Self, usize), DemoError> {
+        let version: u8 = *data.first().ok_or(DemoError::Truncated)?;
+
+        // 1.0 to 1.2 demos start straight off with the skill
+        if version <= GameDifficulty::Nightmare as u8 {
+            let bytes: &[u8] = data.get(..7).ok_or(DemoError::Truncated)?;
+            let header: DemoHeader = DemoHeader {
+                version: None,
+                skill: skill_from_byte(bytes[0]),
+                episode: i32::from(bytes[1]),
+                map: i32::from(bytes[2]),
+                deathmatch: Deathmatch::Off,
+                respawn_parm: false,
+                fast_parm: false,
+                no_monsters: false,
+                console_player: 0,
+                players_in_game: players_in_game_from_bytes(&bytes[3..7]),
+            };
+            return Ok((header, 7));
+        }
+
+        if !(LONG_HEADER_VERSION..=VERSION).contains(&version) {
+            return Err(DemoError::DifferentVersion(version));
+        }
+
+        let bytes: &[u8] = data.get(..13).ok_or(DemoError::Truncated)?;
+        let header: DemoHeader = DemoHeader {
+            version: Some(version),
+            skill: skill_from_byte(bytes[1]),
+            episode: i32::from(bytes[2]),
+            map: i32::from(bytes[3]),
+            deathmatch: match bytes[4] {
+                0 => Deathmatch::Off,
+                1 => Deathmatch::Classic,
+                _ => Deathmatch::AltDeath,
+            },
+            respawn_parm: bytes[5] != 0,
+            fast_parm: bytes[6] != 0,
+            no_monsters: bytes[7] != 0,
+            console_player: usize::from(bytes[8]) % MAXPLAYERS,
+            players_in_game: players_in_game_from_bytes(&bytes[9..13]),
+        };
+        Ok((header, 13))
+    }
+
+    // Always written as a 1.9 header
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![
+            VERSION,
+            self.skill as u8,
+            self.episode as u8,
+            self.map as u8,
+            match self.deathmatch {
+                Deathmatch::Off => 0,
+                Deathmatch::Classic => 1,
+                Deathmatch::AltDeath => 2,
+            },
+            u8::from(self.respawn_parm),
+            u8::from(self.fast_parm),
+            u8::from(self.no_monsters),
+            self.console_player as u8,
+        ];
+        data.extend(
+            self.players_in_game
+                .iter()
+                .map(|in_game| u8::from(*in_game)),
+        );
+        data
+    }
+
+    // G_DoPlayDemo. The settings the demo was recorded with, on top of the
+    // ones that don't come from the demo like the game type
+    pub fn apply(&self, settings: &mut WorldSettings) {
+        settings.skill = self.skill;
+        settings.episode = self.episode;
+        settings.map = self.map;
+        settings.deathmatch = self.deathmatch;
+        settings.respawn_parm = self.respawn_parm;
+        settings.fast_parm = self.fast_parm;
+        settings.no_monsters = self.no_monsters;
+        settings.console_player = self.console_player;
+        settings.players_in_game = self.players_in_game;
+        settings.netgame = self.players_in_game[1];
+    }
+}
+
+// A demo being played back, read one ticcmd at a time
+#[derive(Clone, Debug)]
+pub struct DemoPlayback {
+    pub header: DemoHeader,
+    data: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl DemoPlayback {
+    pub fn from_lump_data(data: Vec<u8>) -> Result<Self, DemoError> {
+        let (header, position): (DemoHeader, usize) = DemoHeader::from_bytes(&data)?;
+
+        Ok(DemoPlayback {
+            header,
+            data,
+            position,
+            finished: false,
+        })
+    }
+
+    // G_ReadDemoTiccmd. None once the end marker is reached. Demos cut
+    // short without a marker end where the data runs out
+    pub fn read_cmd(&mut self) -> Option<TicCmd> {
+        let bytes: Option<&[u8]> = self.data.get(self.position..self.position + 4);
+        let Some(bytes) = bytes.filter(|bytes| bytes[0] != DEMOMARKER) else {
+            // End of demo data stream
+            self.finished = true;
+            return None;
+        };
+
+        self.position += 4;
+        Some(TicCmd {
+            forward_move: bytes[0] as i8,
+            side_move: bytes[1] as i8,
+            angle_turn: (u16::from(bytes[2]) << 8) as i16,
+            buttons: bytes[3],
+            ..Default::default()
+        })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+// A demo being recorded from the ticcmds the game runs
+#[derive(Clone, Debug)]
+pub struct DemoRecorder {
+    data: Vec<u8>,
+}
+
+impl DemoRecorder {
+    // G_BeginRecording
+    pub fn new(header: &DemoHeader) -> Self {
+        DemoRecorder {
+            data: header.to_bytes(),
+        }
+    }
+
+    // G_WriteDemoTiccmd. Returns the ticcmd as it will be read back so the
+    // game runs exactly what playback will
+    pub fn write_cmd(&mut self, cmd: &TicCmd) -> TicCmd {
+        let angle_turn: u8 = ((i32::from(cmd.angle_turn) + 128) >> 8) as u8;
+        self.data.extend_from_slice(&[
+            cmd.forward_move as u8,
+            cmd.side_move as u8,
+            angle_turn,
+            cmd.buttons,
+        ]);
+
+        // Make SURE it is exactly the same
+        TicCmd {
+            angle_turn: (u16::from(angle_turn) << 8) as i16,
+            ..*cmd
+        }
+    }
+
+    // The finished demo with its end marker
+    pub fn finish(mut self) -> Vec<u8> {
+        self.data.push(DEMOMARKER);
+        self.data
+    }
+}
+
+// -playdemo takes a file name with or without .lmp, or the name of a lump
+// like DEMO1 in the loaded files
+pub fn load_demo(doom_files: &mut [DoomFile], name: &str) -> Result<Vec<u8>, DemoError> {
+    for path in [name.to_owned(), format!("{}.lmp", name)] {
+        let path: &Path = Path::new(&path);
+        if path.is_file() {
+            return fs::read(path).map_err(|error| DemoError::IoError(error.to_string()));
+        }
+    }
+
+    wad::find_lump_data(doom_files, &name.to_uppercase())
+        .ok_or_else(|| DemoError::NotFound(name.to_owned()))
+}
+
+pub(crate) fn skill_from_byte(value: u8) -> GameDifficulty {
+    match value {
+        0 => GameDifficulty::Baby,
+        1 => GameDifficulty::Easy,
+        2 => GameDifficulty::Medium,
+        3 => GameDifficulty::Hard,
+        _ => GameDifficulty::Nightmare,
+    }
+}
+
+pub(crate) fn players_in_game_from_bytes(bytes: &[u8]) -> [bool; MAXPLAYERS] {
+    let mut players_in_game: [bool; MAXPLAYERS] = [false; MAXPLAYERS];
+    for (in_game, byte) in players_in_game.iter_mut().zip(bytes) {
+        *in_game = *byte != 0;
+    }
+    players_in_game
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::config::GameDifficulty;
+    use crate::game::demo::{
+        load_demo, DemoError, DemoHeader, DemoPlayback, DemoRecorder, DEMOMARKER, VERSION,
+    };
+    use crate::play::ticcmd::{TicCmd, BT_ATTACK};
+    use crate::play::world::{Deathmatch, WorldSettings};
+    use crate::test_util::{create_test_dir, write_test_wad};
+    use crate::wad::{self, DoomFile};
+
+    #[test]
+    fn test_demo_header_bytes_round_trip() {
+        let header: DemoHeader = DemoHeader::from_settings(&WorldSettings {
+            skill: GameDifficulty::Hard,
+            episode: 2,
+            map: 7,
+            deathmatch: Deathmatch::AltDeath,
+            fast_parm: true,
+            console_player: 1,
+            players_in_game: [true, true, false, false],
+            ..Default::default()
+        });
+        let data: Vec<u8> = header.to_bytes();
+
+        assert_eq!(data, vec![VERSION, 3, 2, 7, 2, 0, 1, 0, 1, 1, 1, 0, 0]);
+        assert_eq!(DemoHeader::from_bytes(&data), Ok((header, 13)));
+    }
+
+    #[test]
+    fn test_demo_header_reads_old_versions() {
+        let (header, size): (DemoHeader, usize) =
+            DemoHeader::from_bytes(&[2, 1, 3, 1, 0, 0, 0]).unwrap();
+
+        assert_eq!(size, 7);
+        assert_eq!(header.version, None);
+        assert_eq!(header.skill, GameDifficulty::Medium);
+        assert_eq!((header.episode, header.map), (1, 3));
+        assert_eq!(header.deathmatch, Deathmatch::Off);
+        assert_eq!(header.players_in_game, [true, false, false, false]);
+    }
+
+    #[test]
+    fn test_demo_header_rejects_unknown_versions() {
+        assert_eq!(
+            DemoHeader::from_bytes(&[110, 2, 1, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0]),
+            Err(DemoError::DifferentVersion(110))
+        );
+        assert_eq!(
+            DemoHeader::from_bytes(&[VERSION, 2, 1]),
+            Err(DemoError::Truncated)
+        );
+    }
+
+    #[test]
+    fn test_demo_recorder_writes_what_playback_reads() {
+        let header: DemoHeader = DemoHeader::from_settings(&WorldSettings::default());
+        let mut demo_recorder: DemoRecorder = DemoRecorder::new(&header);
+        let cmd: TicCmd = TicCmd {
+            forward_move: -25,
+            side_move: 40,
+            angle_turn: -200,
+            buttons: BT_ATTACK,
+            ..Default::default()
+        };
+
+        let written: TicCmd = demo_recorder.write_cmd(&cmd);
+        assert_eq!(written.angle_turn, -256);
+
+        let data: Vec<u8> = demo_recorder.finish();
+        assert_eq!(data.last(), Some(&DEMOMARKER));
+
+        let mut demo_playback: DemoPlayback = DemoPlayback::from_lump_data(data).unwrap();
+        assert_eq!(demo_playback.header, header);
+        assert_eq!(demo_playback.read_cmd(), Some(written));
+        assert!(!demo_playback.is_finished());
+        assert_eq!(demo_playback.read_cmd(), None);
+        assert!(demo_playback.is_finished());
+    }
+
+    #[test]
+    fn test_demo_playback_ends_where_data_runs_out() {
+        let mut demo_playback: DemoPlayback =
+            DemoPlayback::from_lump_data(vec![2, 1, 1, 1, 0, 0, 0, 50, 0]).unwrap();
+
+        assert_eq!(demo_playback.read_cmd(), None);
+        assert!(demo_playback.is_finished());
+    }
+
+    #[test]
+    fn test_load_demo_reads_files_and_lumps() {
+        let dir: PathBuf = create_test_dir("demo-load");
+        let mut wad_path: PathBuf = dir.clone();
+        wad_path.push("demos.wad");
+        write_test_wad(&wad_path, &[(String::from("DEMO1"), vec![1, 2, 3])]);
+        let mut doom_files: Vec<DoomFile> = vec![wad::process_file(&wad_path).unwrap()];
+
+        let mut demo_path: PathBuf = dir.clone();
+        demo_path.push("mydemo.lmp");
+        fs::write(&demo_path, [4, 5, 6]).unwrap();
+        let mut name_path: PathBuf = dir;
+        name_path.push("mydemo");
+
+        assert_eq!(load_demo(&mut doom_files, "demo1"), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            load_demo(&mut doom_files, name_path.to_str().unwrap()),
+            Ok(vec![4, 5, 6])
+        );
+        assert_eq!(
+            load_demo(&mut doom_files, demo_path.to_str().unwrap()),
+            Ok(vec![4, 5, 6])
+        );
+        assert_eq!(
+            load_demo(&mut doom_files, "DEMO4"),
+            Err(DemoError::NotFound(String::from("DEMO4")))
+        );
+    }
+}
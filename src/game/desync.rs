@@ -0,0 +1,350 @@
+// Hash streams for checking demos still play back the same. A hash stream
+// is a text file with a line for every tic of "<tic> <total hash>" followed
+// by the objects whose hashes changed since the line before as
+// "<key>=<hash>", and "-<key>" for objects that went away
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Write};
+use std::iter::Enumerate;
+use std::str::Lines;
+
+use crate::game::Game;
+use crate::play::hash::{StateHashes, StateKey};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum DesyncError {
+    InvalidLine(usize, String),
+    Desynced { tic: i32, differences: Vec<String> },
+}
+
+impl Display for DesyncError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLine(line_number, line) => {
+                write!(f, "Invalid hash stream line {}: {}", line_number, line)
+            }
+            Self::Desynced { tic, differences } => {
+                write!(f, "Demo desynced at tic {}", tic)?;
+                for difference in differences {
+                    write!(f, "\n  {}", difference)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TicHashes {
+    pub tic: i32,
+    pub hashes: StateHashes,
+}
+
+pub struct HashStreamWriter {
+    previous: BTreeMap<StateKey, u32>,
+    text: String,
+}
+
+impl HashStreamWriter {
+    pub fn new() -> Self {
+        HashStreamWriter {
+            previous: BTreeMap::new(),
+            text: String::from("# zz-doom hash stream\n"),
+        }
+    }
+
+    pub fn write_tic(&mut self, tic: i32, hashes: &StateHashes) {
+        let _ = write!(self.text, "{} {:016x}", tic, hashes.total);
+
+        for (key, hash) in hashes.objects.iter() {
+            if self.previous.get(key) != Some(hash) {
+                let _ = write!(self.text, " {}={:08x}", key, hash);
+            }
+        }
+        for key in self.previous.keys() {
+            if !hashes.objects.contains_key(key) {
+                let _ = write!(self.text, " -{}", key);
+            }
+        }
+
+        self.text.push('\n');
+        self.previous = hashes.objects.clone();
+    }
+
+    pub fn finish(self) -> String {
+        self.text
+    }
+}
+
+impl Default for HashStreamWriter {
+    fn default() -> Self {
+        HashStreamWriter::new()
+    }
+}
+
+// Reads a hash stream back a tic at a time, filling in the objects that
+// didn't change
+pub struct HashStreamReader<'a> {
+    lines: Enumerate<Lines<'a>>,
+    current: BTreeMap<StateKey, u32>,
+}
+
+impl<'a> HashStreamReader<'a> {
+    pub fn new(text: &'a str) -> Self {
+        HashStreamReader {
+            lines: text.lines().enumerate(),
+            current: BTreeMap::new(),
+        }
+    }
+
+    fn read_line(&mut self, line: &str) -> Option<TicHashes> {
+        let mut fields = line.split_whitespace();
+        let tic: i32 = fields.next()?.parse().ok()?;
+        let total: u64 = u64::from_str_radix(fields.next()?, 16).ok()?;
+
+        for field in fields {
+            if let Some(key) = field.strip_prefix('-') {
+                self.current.remove(&key.parse().ok()?);
+            } else {
+                let (key, hash) = field.split_once('=')?;
+                self.current
+                    .insert(key.parse().ok()?, u32::from_str_radix(hash, 16).ok()?);
+            }
+        }
+
+        Some(TicHashes {
+            tic,
+            hashes: StateHashes {
+                total,
+                objects: self.current.clone(),
+            },
+        })
+    }
+}
+
+impl Iterator for HashStreamReader<'_> {
+    type Item = Result<TicHashes, DesyncError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, line): (usize, &str) = self
+            .lines
+            .find(|(_index, line)| !line.trim().is_empty() && !line.starts_with('#'))?;
+
+        Some(
+            self.read_line(line)
+                .ok_or_else(|| DesyncError::InvalidLine(index + 1, line.to_owned())),
+        )
+    }
+}
+
+// Plays the game's demo through to the end as fast as it goes without
+// sound, hashing the state after every tic
+pub fn hash_demo(game: &mut Game) -> String {
+    let mut writer: HashStreamWriter = HashStreamWriter::new();
+
+    loop {
+        game.tick(&[]);
+        game.world.sound_events.clear();
+        if game.demo_finished() {
+            break;
+        }
+        writer.write_tic(game.world.game_tic, &game.world.state_hashes());
+    }
+
+    writer.finish()
+}
+
+// -demoverify. Plays the game's demo through without sound and checks every
+// tic against the hash stream, stopping at the first one that doesn't match.
+// Returns how many tics were checked
+pub fn verify_demo(game: &mut Game, hash_stream: &str) -> Result<i32, DesyncError> {
+    let mut reader: HashStreamReader = HashStreamReader::new(hash_stream);
+    let mut tics: i32 = 0;
+
+    loop {
+        game.tick(&[]);
+        game.world.sound_events.clear();
+        let expected: Option<TicHashes> = reader.next().transpose()?;
+
+        let Some(expected) = expected else {
+            if game.demo_finished() {
+                return Ok(tics);
+            }
+            return Err(DesyncError::Desynced {
+                tic: game.world.game_tic,
+                differences: vec![String::from("The hash stream ends but the demo carries on")],
+            });
+        };
+
+        if game.demo_finished() {
+            return Err(DesyncError::Desynced {
+                tic: expected.tic,
+                differences: vec![String::from("The demo ends but the hash stream carries on")],
+            });
+        }
+
+        let actual: StateHashes = game.world.state_hashes();
+        if expected.tic != game.world.game_tic || expected.hashes.total != actual.total {
+            return Err(DesyncError::Desynced {
+                tic: game.world.game_tic,
+                differences: differences(game, &expected, &actual),
+            });
+        }
+        tics += 1;
+    }
+}
+
+fn differences(game: &Game, expected: &TicHashes, actual: &StateHashes) -> Vec<String> {
+    let mut differences: Vec<String> = Vec::new();
+    if expected.tic != game.world.game_tic {
+        differences.push(format!("Expected tic {}", expected.tic));
+    }
+
+    let describe = |key: StateKey| game.world.describe_state(key).unwrap_or_default();
+    for (key, expected_hash) in expected.hashes.objects.iter() {
+        match actual.objects.get(key) {
+            None => differences.push(format!(
+                "{} is missing, expected hash {:08x}",
+                key, expected_hash
+            )),
+            Some(hash) if hash != expected_hash => differences.push(format!(
+                "{} hash {:08x}, expected {:08x}: {}",
+                key,
+                hash,
+                expected_hash,
+                describe(*key)
+            )),
+            Some(_) => {}
+        }
+    }
+    for key in actual.objects.keys() {
+        if !expected.hashes.objects.contains_key(key) {
+            differences.push(format!("{} wasn't expected: {}", key, describe(*key)));
+        }
+    }
+
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::game::demo::{DemoHeader, DemoPlayback, DemoRecorder};
+    use crate::game::desync::{
+        hash_demo, verify_demo, DesyncError, HashStreamReader, HashStreamWriter, TicHashes,
+    };
+    use crate::game::tests::create_game;
+    use crate::game::Game;
+    use crate::play::hash::{StateHashes, StateKey};
+    use crate::play::ticcmd::{TicCmd, BT_ATTACK};
+    use crate::play::world::WorldSettings;
+
+    fn create_demo_game(name: &str, tics: i32) -> Game {
+        let mut demo_recorder: DemoRecorder =
+            DemoRecorder::new(&DemoHeader::from_settings(&WorldSettings::default()));
+        for tic in 0..tics {
+            demo_recorder.write_cmd(&TicCmd {
+                forward_move: 50,
+                angle_turn: if tic < 15 { 512 } else { -512 },
+                buttons: BT_ATTACK,
+                ..Default::default()
+            });
+        }
+
+        let mut game: Game = create_game(name, &[1]);
+        game.demo_playback = Some(DemoPlayback::from_lump_data(demo_recorder.finish()).unwrap());
+        game
+    }
+
+    fn hashes(total: u64, objects: &[(StateKey, u32)]) -> StateHashes {
+        StateHashes {
+            total,
+            objects: objects.iter().copied().collect::<BTreeMap<StateKey, u32>>(),
+        }
+    }
+
+    #[test]
+    fn test_hash_stream_writes_only_changes() {
+        let mut writer: HashStreamWriter = HashStreamWriter::new();
+        writer.write_tic(
+            1,
+            &hashes(0xab, &[(StateKey::Random, 1), (StateKey::Mobj(0), 2)]),
+        );
+        writer.write_tic(
+            2,
+            &hashes(0xcd, &[(StateKey::Random, 3), (StateKey::Sector(0), 4)]),
+        );
+        let text: String = writer.finish();
+
+        assert_eq!(
+            text.lines().skip(1).collect::<Vec<&str>>(),
+            vec![
+                "1 00000000000000ab rng=00000001 m0=00000002",
+                "2 00000000000000cd rng=00000003 s0=00000004 -m0",
+            ]
+        );
+
+        let read: Vec<TicHashes> = HashStreamReader::new(&text)
+            .collect::<Result<Vec<TicHashes>, DesyncError>>()
+            .unwrap();
+        assert_eq!(
+            read[1],
+            TicHashes {
+                tic: 2,
+                hashes: hashes(0xcd, &[(StateKey::Random, 3), (StateKey::Sector(0), 4)]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_hash_stream_reader_reports_bad_lines() {
+        let mut reader: HashStreamReader = HashStreamReader::new("# header\n\n1 zz\n");
+
+        assert_eq!(
+            reader.next(),
+            Some(Err(DesyncError::InvalidLine(3, String::from("1 zz"))))
+        );
+    }
+
+    #[test]
+    fn test_verify_demo_passes_the_same_playback() {
+        let hash_stream: String = hash_demo(&mut create_demo_game("desync-hash", 30));
+        assert_eq!(hash_stream.lines().count(), 31);
+
+        assert_eq!(
+            verify_demo(&mut create_demo_game("desync-verify", 30), &hash_stream),
+            Ok(30)
+        );
+    }
+
+    #[test]
+    fn test_verify_demo_stops_at_the_first_difference() {
+        let hash_stream: String = hash_demo(&mut create_demo_game("desync-good", 30));
+
+        // A different random index from the start desyncs the game once
+        // something uses it
+        let mut game: Game = create_demo_game("desync-bad", 30);
+        game.world.random.set_prndindex(100);
+
+        let Err(DesyncError::Desynced { tic, differences }) = verify_demo(&mut game, &hash_stream)
+        else {
+            panic!("Expected a desync");
+        };
+        assert_eq!(tic, 1);
+        assert!(differences[0].starts_with("rng hash "));
+        assert!(differences[0].ends_with(": prndindex 100"));
+    }
+
+    #[test]
+    fn test_verify_demo_catches_a_demo_of_a_different_length() {
+        let hash_stream: String = hash_demo(&mut create_demo_game("desync-long", 30));
+
+        assert_eq!(
+            verify_demo(&mut create_demo_game("desync-short", 20), &hash_stream),
+            Err(DesyncError::Desynced {
+                tic: 21,
+                differences: vec![String::from("The demo ends but the hash stream carries on")],
+            })
+        );
+    }
+}
@@ -0,0 +1,177 @@
+// d_event.h, doomkeys.h and the event handling of d_main.c. Input from a
+// backend reaches the game as events, queued until the next tic offers
+// them to each responder in turn. Keys are the original's key codes,
+// lowercase ASCII for the printable ones
+pub const KEY_RIGHTARROW: i32 = 0xae;
+pub const KEY_LEFTARROW: i32 = 0xac;
+pub const KEY_UPARROW: i32 = 0xad;
+pub const KEY_DOWNARROW: i32 = 0xaf;
+pub const KEY_ESCAPE: i32 = 27;
+pub const KEY_ENTER: i32 = 13;
+pub const KEY_TAB: i32 = 9;
+pub const KEY_F1: i32 = 0x80 + 0x3b;
+pub const KEY_F2: i32 = 0x80 + 0x3c;
+pub const KEY_F3: i32 = 0x80 + 0x3d;
+pub const KEY_F4: i32 = 0x80 + 0x3e;
+pub const KEY_F5: i32 = 0x80 + 0x3f;
+pub const KEY_F6: i32 = 0x80 + 0x40;
+pub const KEY_F7: i32 = 0x80 + 0x41;
+pub const KEY_F8: i32 = 0x80 + 0x42;
+pub const KEY_F9: i32 = 0x80 + 0x43;
+pub const KEY_F10: i32 = 0x80 + 0x44;
+pub const KEY_F11: i32 = 0x80 + 0x57;
+pub const KEY_F12: i32 = 0x80 + 0x58;
+
+pub const KEY_BACKSPACE: i32 = 127;
+pub const KEY_PAUSE: i32 = 0xff;
+
+pub const KEY_EQUALS: i32 = 0x3d;
+pub const KEY_MINUS: i32 = 0x2d;
+
+pub const KEY_RSHIFT: i32 = 0x80 + 0x36;
+pub const KEY_RCTRL: i32 = 0x80 + 0x1d;
+pub const KEY_RALT: i32 = 0x80 + 0x38;
+
+pub const KEY_LALT: i32 = KEY_RALT;
+
+// Input event types
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    KeyDown(i32),
+    KeyUp(i32),
+    // Buttons as bits and how far the mouse moved since the last event
+    Mouse { buttons: i32, x: i32, y: i32 },
+    // Buttons as bits and the stick position, -1, 0 or 1 on each axis
+    Joystick { buttons: i32, x: i32, y: i32 },
+}
+
+pub const MAXEVENTS: usize = 64;
+
+// M_Responder, AM_Responder, ST_Responder and G_Responder. Returns true if
+// the event was eaten so no responder after it sees it
+pub trait Responder {
+    fn responder(&mut self, event: &Event) -> bool;
+}
+
+// The ring buffer the backends post into
+pub struct EventQueue {
+    events: [Event; MAXEVENTS],
+    head: usize,
+    tail: usize,
+}
+
+impl EventQueue {
+    pub fn new() -> Self {
+        EventQueue {
+            events: [Event::KeyUp(0); MAXEVENTS],
+            head: 0,
+            tail: 0,
+        }
+    }
+
+    // D_PostEvent. The original overran the whole queue when it filled up,
+    // this loses the oldest event instead
+    pub fn post(&mut self, event: Event) {
+        self.events[self.head] = event;
+        self.head = (self.head + 1) & (MAXEVENTS - 1);
+        if self.head == self.tail {
+            self.tail = (self.tail + 1) & (MAXEVENTS - 1);
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<Event> {
+        if self.tail == self.head {
+            return None;
+        }
+        let event: Event = self.events[self.tail];
+        self.tail = (self.tail + 1) & (MAXEVENTS - 1);
+        Some(event)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tail == self.head
+    }
+
+    // D_ProcessEvents. Send all the events of the given timestamp down the
+    // responder chain
+    pub fn process(&mut self, responders: &mut [&mut dyn Responder]) {
+        while let Some(event) = self.pop() {
+            for responder in responders.iter_mut() {
+                if responder.responder(&event) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl Default for EventQueue {
+    fn default() -> Self {
+        EventQueue::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::event::{Event, EventQueue, Responder, KEY_ESCAPE, MAXEVENTS};
+
+    struct TestResponder {
+        seen: Vec<Event>,
+        eats: fn(&Event) -> bool,
+    }
+
+    impl Responder for TestResponder {
+        fn responder(&mut self, event: &Event) -> bool {
+            self.seen.push(*event);
+            (self.eats)(event)
+        }
+    }
+
+    #[test]
+    fn test_event_queue_pops_events_in_order() {
+        let mut events: EventQueue = EventQueue::new();
+        assert!(events.is_empty());
+
+        events.post(Event::KeyDown(KEY_ESCAPE));
+        events.post(Event::KeyUp(KEY_ESCAPE));
+
+        assert_eq!(events.pop(), Some(Event::KeyDown(KEY_ESCAPE)));
+        assert_eq!(events.pop(), Some(Event::KeyUp(KEY_ESCAPE)));
+        assert_eq!(events.pop(), None);
+    }
+
+    #[test]
+    fn test_event_queue_drops_oldest_events_when_full() {
+        let mut events: EventQueue = EventQueue::new();
+
+        for key in 0..MAXEVENTS as i32 + 10 {
+            events.post(Event::KeyDown(key));
+        }
+
+        let keys: Vec<Event> = std::iter::from_fn(|| events.pop()).collect();
+        assert_eq!(keys.len(), MAXEVENTS - 1);
+        assert_eq!(keys[0], Event::KeyDown(11));
+        assert_eq!(keys[MAXEVENTS - 2], Event::KeyDown(MAXEVENTS as i32 + 9));
+    }
+
+    #[test]
+    fn test_event_queue_process_stops_at_the_responder_that_eats_the_event() {
+        let mut menu: TestResponder = TestResponder {
+            seen: Vec::new(),
+            eats: |event| *event == Event::KeyDown(KEY_ESCAPE),
+        };
+        let mut game: TestResponder = TestResponder {
+            seen: Vec::new(),
+            eats: |_| true,
+        };
+        let mut events: EventQueue = EventQueue::new();
+        events.post(Event::KeyDown(KEY_ESCAPE));
+        events.post(Event::KeyDown(i32::from(b'w')));
+
+        events.process(&mut [&mut menu, &mut game]);
+
+        assert_eq!(menu.seen.len(), 2);
+        assert_eq!(game.seen, vec![Event::KeyDown(i32::from(b'w'))]);
+        assert!(events.is_empty());
+    }
+}
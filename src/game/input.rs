@@ -0,0 +1,523 @@
+// The input side of g_game.c. G_Responder keeps track of which keys and
+// buttons are held and G_BuildTiccmd turns them into the console player's
+// ticcmd every tic
+use crate::config::KeyBindings;
+use crate::game::event::{Event, Responder, KEY_F1, KEY_PAUSE};
+use crate::option::DoomOptions;
+use crate::play::player::NUMWEAPONS;
+use crate::play::ticcmd::{
+    TicCmd, BTS_PAUSE, BT_ATTACK, BT_CHANGE, BT_SPECIAL, BT_USE, BT_WEAPONSHIFT,
+};
+
+pub const NUMKEYS: usize = 256;
+
+const FORWARDMOVE: [i32; 2] = [0x19, 0x32];
+const SIDEMOVE: [i32; 2] = [0x18, 0x28];
+// + slow turn
+const ANGLETURN: [i32; 3] = [640, 1280, 320];
+const SLOWTURNTICS: i32 = 6;
+
+// No network game splits the ticcmds up, every tic is built
+const TICDUP: i32 = 1;
+
+// -turbo with no value is 200%, anything else is kept between 10% and 400%
+pub fn turbo_scale(doom_options: &DoomOptions) -> Option<i32> {
+    doom_options
+        .get_option_by_name("-turbo")
+        .and_then(|option| {
+            if !option.enabled {
+                return None;
+            }
+            let scale: i32 = option
+                .values
+                .first()
+                .map_or(200, |value| value.parse::<i32>().unwrap_or(0));
+            Some(scale.clamp(10, 400))
+        })
+}
+
+pub struct GameInput {
+    key_bindings: KeyBindings,
+    // Screenshots on F1 like the menu did in development mode
+    devparm: bool,
+    forward_move: [i32; 2],
+    side_move: [i32; 2],
+
+    game_key_down: [bool; NUMKEYS],
+    // Number of tics the turn keys have been held
+    turn_held: i32,
+    mouse_buttons: [bool; 3],
+    mouse_x: i32,
+    mouse_y: i32,
+    joy_buttons: [bool; 4],
+    joy_x_move: i32,
+    joy_y_move: i32,
+
+    // Mouse forward and strafe button double clicks to use
+    dclick_time: i32,
+    dclick_state: bool,
+    dclicks: i32,
+    dclick_time2: i32,
+    dclick_state2: bool,
+    dclicks2: i32,
+
+    send_pause: bool,
+    // Set by G_ScreenShot, for whatever draws the frames to save the next one
+    pub screenshot_requested: bool,
+}
+
+impl GameInput {
+    pub fn new(key_bindings: KeyBindings, turbo_scale: Option<i32>, devparm: bool) -> Self {
+        let mut forward_move: [i32; 2] = FORWARDMOVE;
+        let mut side_move: [i32; 2] = SIDEMOVE;
+        if let Some(scale) = turbo_scale {
+            println!("turbo scale: {}%", scale);
+            forward_move = forward_move.map(|speed| speed * scale / 100);
+            side_move = side_move.map(|speed| speed * scale / 100);
+        }
+
+        GameInput {
+            key_bindings,
+            devparm,
+            forward_move,
+            side_move,
+            game_key_down: [false; NUMKEYS],
+            turn_held: 0,
+            mouse_buttons: [false; 3],
+            mouse_x: 0,
+            mouse_y: 0,
+            joy_buttons: [false; 4],
+            joy_x_move: 0,
+            joy_y_move: 0,
+            dclick_time: 0,
+            dclick_state: false,
+            dclicks: 0,
+            dclick_time2: 0,
+            dclick_state2: false,
+            dclicks2: 0,
+            send_pause: false,
+            screenshot_requested: false,
+        }
+    }
+
+    fn key_down(&self, key: i32) -> bool {
+        usize::try_from(key)
+            .ok()
+            .and_then(|key| self.game_key_down.get(key))
+            .is_some_and(|&down| down)
+    }
+
+    // Buttons out of range are never held
+    fn button(buttons: &[bool], button: i32) -> bool {
+        usize::try_from(button)
+            .ok()
+            .and_then(|button| buttons.get(button))
+            .is_some_and(|&down| down)
+    }
+
+    // G_BuildTiccmd. Builds a ticcmd from all of the available inputs
+    pub fn build_ticcmd(&mut self) -> TicCmd {
+        let bindings: KeyBindings = self.key_bindings;
+        let mut cmd: TicCmd = TicCmd::default();
+        let mut angle_turn: i32 = 0;
+
+        let strafe: bool = self.key_down(bindings.key_strafe)
+            || Self::button(&self.mouse_buttons, bindings.mouse_strafe)
+            || Self::button(&self.joy_buttons, bindings.joy_strafe);
+        let speed: usize = usize::from(
+            self.key_down(bindings.key_speed)
+                || Self::button(&self.joy_buttons, bindings.joy_speed),
+        );
+
+        let mut forward: i32 = 0;
+        let mut side: i32 = 0;
+
+        // Use two stage accelerative turning on the keyboard and joystick
+        if self.joy_x_move != 0
+            || self.key_down(bindings.key_right)
+            || self.key_down(bindings.key_left)
+        {
+            self.turn_held += TICDUP;
+        } else {
+            self.turn_held = 0;
+        }
+
+        let turn_speed: usize = if self.turn_held < SLOWTURNTICS {
+            // Slow turn
+            2
+        } else {
+            speed
+        };
+
+        // Let movement keys cancel each other out
+        if strafe {
+            if self.key_down(bindings.key_right) {
+                side += self.side_move[speed];
+            }
+            if self.key_down(bindings.key_left) {
+                side -= self.side_move[speed];
+            }
+            if self.joy_x_move > 0 {
+                side += self.side_move[speed];
+            }
+            if self.joy_x_move < 0 {
+                side -= self.side_move[speed];
+            }
+        } else {
+            if self.key_down(bindings.key_right) {
+                angle_turn -= ANGLETURN[turn_speed];
+            }
+            if self.key_down(bindings.key_left) {
+                angle_turn += ANGLETURN[turn_speed];
+            }
+            if self.joy_x_move > 0 {
+                angle_turn -= ANGLETURN[turn_speed];
+            }
+            if self.joy_x_move < 0 {
+                angle_turn += ANGLETURN[turn_speed];
+            }
+        }
+
+        if self.key_down(bindings.key_up) {
+            forward += self.forward_move[speed];
+        }
+        if self.key_down(bindings.key_down) {
+            forward -= self.forward_move[speed];
+        }
+        if self.joy_y_move < 0 {
+            forward += self.forward_move[speed];
+        }
+        if self.joy_y_move > 0 {
+            forward -= self.forward_move[speed];
+        }
+        if self.key_down(bindings.key_strafe_right) {
+            side += self.side_move[speed];
+        }
+        if self.key_down(bindings.key_strafe_left) {
+            side -= self.side_move[speed];
+        }
+
+        // Buttons
+        if self.key_down(bindings.key_fire)
+            || Self::button(&self.mouse_buttons, bindings.mouse_fire)
+            || Self::button(&self.joy_buttons, bindings.joy_fire)
+        {
+            cmd.buttons |= BT_ATTACK;
+        }
+
+        if self.key_down(bindings.key_use) || Self::button(&self.joy_buttons, bindings.joy_use) {
+            cmd.buttons |= BT_USE;
+            // Clear double clicks if hit use button
+            self.dclicks = 0;
+        }
+
+        // Chainsaw overrides
+        if let Some(weapon) =
+            (0..NUMWEAPONS as u8 - 1).find(|&weapon| self.key_down(i32::from(b'1' + weapon)))
+        {
+            cmd.buttons |= BT_CHANGE;
+            cmd.buttons |= weapon << BT_WEAPONSHIFT;
+        }
+
+        // Mouse
+        let mouse_forward: bool = Self::button(&self.mouse_buttons, bindings.mouse_forward);
+        if mouse_forward {
+            forward += self.forward_move[speed];
+        }
+
+        // Forward double click
+        if mouse_forward != self.dclick_state && self.dclick_time > 1 {
+            self.dclick_state = mouse_forward;
+            if self.dclick_state {
+                self.dclicks += 1;
+            }
+            if self.dclicks == 2 {
+                cmd.buttons |= BT_USE;
+                self.dclicks = 0;
+            } else {
+                self.dclick_time = 0;
+            }
+        } else {
+            self.dclick_time += TICDUP;
+            if self.dclick_time > 20 {
+                self.dclicks = 0;
+                self.dclick_state = false;
+            }
+        }
+
+        // Strafe double click
+        let bstrafe: bool = Self::button(&self.mouse_buttons, bindings.mouse_strafe)
+            || Self::button(&self.joy_buttons, bindings.joy_strafe);
+        if bstrafe != self.dclick_state2 && self.dclick_time2 > 1 {
+            self.dclick_state2 = bstrafe;
+            if self.dclick_state2 {
+                self.dclicks2 += 1;
+            }
+            if self.dclicks2 == 2 {
+                cmd.buttons |= BT_USE;
+                self.dclicks2 = 0;
+            } else {
+                self.dclick_time2 = 0;
+            }
+        } else {
+            self.dclick_time2 += TICDUP;
+            if self.dclick_time2 > 20 {
+                self.dclicks2 = 0;
+                self.dclick_state2 = false;
+            }
+        }
+
+        forward += self.mouse_y;
+        if strafe {
+            side += self.mouse_x * 2;
+        } else {
+            angle_turn -= self.mouse_x * 0x8;
+        }
+        self.mouse_x = 0;
+        self.mouse_y = 0;
+
+        let max_move: i32 = self.forward_move[1];
+        forward = forward.clamp(-max_move, max_move);
+        side = side.clamp(-max_move, max_move);
+
+        // Stored in chars and shorts like the original, so big -turbo scales
+        // wrap around
+        cmd.forward_move = forward as i8;
+        cmd.side_move = side as i8;
+        cmd.angle_turn = angle_turn as i16;
+
+        // Special buttons
+        if self.send_pause {
+            self.send_pause = false;
+            cmd.buttons = BT_SPECIAL | BTS_PAUSE;
+        }
+
+        cmd
+    }
+}
+
+impl Responder for GameInput {
+    // The level part of G_Responder, the last responder in the chain
+    fn responder(&mut self, event: &Event) -> bool {
+        match *event {
+            // M_Responder's screenshot key until there's a menu
+            Event::KeyDown(KEY_F1) if self.devparm => {
+                self.screenshot_requested = true;
+                true
+            }
+            Event::KeyDown(KEY_PAUSE) => {
+                self.send_pause = true;
+                true
+            }
+            Event::KeyDown(key) => {
+                if let Some(down) = usize::try_from(key)
+                    .ok()
+                    .and_then(|key| self.game_key_down.get_mut(key))
+                {
+                    *down = true;
+                }
+                // Eat key down events
+                true
+            }
+            Event::KeyUp(key) => {
+                if let Some(down) = usize::try_from(key)
+                    .ok()
+                    .and_then(|key| self.game_key_down.get_mut(key))
+                {
+                    *down = false;
+                }
+                // Always let key up events filter down
+                false
+            }
+            Event::Mouse { buttons, x, y } => {
+                if !self.key_bindings.use_mouse {
+                    return false;
+                }
+                for (index, button) in self.mouse_buttons.iter_mut().enumerate() {
+                    *button = buttons & (1 << index) != 0;
+                }
+                let sensitivity: i32 = self.key_bindings.mouse_sensitivity;
+                self.mouse_x = x * (sensitivity + 5) / 10;
+                self.mouse_y = y * (sensitivity + 5) / 10;
+                // Eat events
+                true
+            }
+            Event::Joystick { buttons, x, y } => {
+                if !self.key_bindings.use_joystick {
+                    return false;
+                }
+                for (index, button) in self.joy_buttons.iter_mut().enumerate() {
+                    *button = buttons & (1 << index) != 0;
+                }
+                self.joy_x_move = x;
+                self.joy_y_move = y;
+                // Eat events
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::KeyBindings;
+    use crate::game::event::{
+        Event, Responder, KEY_F1, KEY_LEFTARROW, KEY_PAUSE, KEY_RALT, KEY_RCTRL, KEY_RIGHTARROW,
+        KEY_RSHIFT, KEY_UPARROW,
+    };
+    use crate::game::input::{turbo_scale, GameInput};
+    use crate::option::DoomOptions;
+    use crate::play::ticcmd::{
+        TicCmd, BTS_PAUSE, BT_ATTACK, BT_CHANGE, BT_SPECIAL, BT_USE, BT_WEAPONSHIFT,
+    };
+
+    fn game_input(keys: &[i32]) -> GameInput {
+        let mut input: GameInput = GameInput::new(Default::default(), None, false);
+        for &key in keys {
+            input.responder(&Event::KeyDown(key));
+        }
+        input
+    }
+
+    #[test]
+    fn test_build_ticcmd_walks_and_runs_forward() {
+        assert_eq!(game_input(&[KEY_UPARROW]).build_ticcmd().forward_move, 0x19);
+        assert_eq!(
+            game_input(&[KEY_UPARROW, KEY_RSHIFT])
+                .build_ticcmd()
+                .forward_move,
+            0x32
+        );
+        // Movement keys cancel each other out
+        let mut input: GameInput = game_input(&[i32::from(b','), i32::from(b'.')]);
+        assert_eq!(input.build_ticcmd(), TicCmd::default());
+    }
+
+    #[test]
+    fn test_build_ticcmd_turns_slowly_before_full_speed() {
+        let mut input: GameInput = game_input(&[KEY_LEFTARROW]);
+        let turns: Vec<i16> = (0..7).map(|_| input.build_ticcmd().angle_turn).collect();
+
+        assert_eq!(turns, vec![320, 320, 320, 320, 320, 640, 640]);
+
+        input.responder(&Event::KeyDown(KEY_RSHIFT));
+        assert_eq!(input.build_ticcmd().angle_turn, 1280);
+
+        // Letting go starts the slow turn over
+        input.responder(&Event::KeyUp(KEY_LEFTARROW));
+        input.build_ticcmd();
+        input.responder(&Event::KeyDown(KEY_RIGHTARROW));
+        assert_eq!(input.build_ticcmd().angle_turn, -320);
+    }
+
+    #[test]
+    fn test_build_ticcmd_strafe_modifier_turns_turning_into_strafing() {
+        let mut input: GameInput = game_input(&[KEY_RALT, KEY_RIGHTARROW]);
+
+        let cmd: TicCmd = input.build_ticcmd();
+
+        assert_eq!(cmd.angle_turn, 0);
+        assert_eq!(cmd.side_move, 0x18);
+    }
+
+    #[test]
+    fn test_build_ticcmd_sets_buttons() {
+        let cmd: TicCmd = game_input(&[KEY_RCTRL, i32::from(b' '), i32::from(b'3')]).build_ticcmd();
+
+        assert_eq!(
+            cmd.buttons,
+            BT_ATTACK | BT_USE | BT_CHANGE | (2 << BT_WEAPONSHIFT)
+        );
+
+        let mut input: GameInput = game_input(&[KEY_PAUSE]);
+        assert_eq!(input.build_ticcmd().buttons, BT_SPECIAL | BTS_PAUSE);
+        assert_eq!(input.build_ticcmd().buttons, 0);
+    }
+
+    #[test]
+    fn test_build_ticcmd_uses_configured_bindings() {
+        let key_bindings: KeyBindings = KeyBindings {
+            key_up: i32::from(b'w'),
+            key_fire: i32::from(b'f'),
+            ..Default::default()
+        };
+        let mut input: GameInput = GameInput::new(key_bindings, None, false);
+        input.responder(&Event::KeyDown(i32::from(b'w')));
+        input.responder(&Event::KeyDown(i32::from(b'f')));
+        input.responder(&Event::KeyDown(KEY_UPARROW));
+
+        let cmd: TicCmd = input.build_ticcmd();
+
+        assert_eq!(cmd.forward_move, 0x19);
+        assert_eq!(cmd.buttons, BT_ATTACK);
+    }
+
+    #[test]
+    fn test_build_ticcmd_adds_mouse_movement_once() {
+        let mut input: GameInput = game_input(&[]);
+
+        assert!(input.responder(&Event::Mouse {
+            buttons: 1,
+            x: 10,
+            y: 20
+        }));
+        let cmd: TicCmd = input.build_ticcmd();
+
+        assert_eq!(cmd.buttons, BT_ATTACK);
+        assert_eq!(cmd.angle_turn, -80);
+        assert_eq!(cmd.forward_move, 20);
+        assert_eq!(input.build_ticcmd().angle_turn, 0);
+    }
+
+    #[test]
+    fn test_build_ticcmd_scales_moves_with_turbo() {
+        let mut input: GameInput = GameInput::new(Default::default(), Some(200), false);
+        input.responder(&Event::KeyDown(KEY_UPARROW));
+        input.responder(&Event::KeyDown(KEY_RSHIFT));
+
+        assert_eq!(input.build_ticcmd().forward_move, 100);
+
+        // Past 255% running forward no longer fits a char
+        let mut input: GameInput = GameInput::new(Default::default(), Some(400), false);
+        input.responder(&Event::KeyDown(KEY_UPARROW));
+        input.responder(&Event::KeyDown(KEY_RSHIFT));
+
+        assert_eq!(input.build_ticcmd().forward_move, -56);
+    }
+
+    #[test]
+    fn test_turbo_scale_defaults_and_clamps() {
+        let scale = |args: &[&str]| {
+            turbo_scale(&DoomOptions::new(
+                args.iter().map(|arg| arg.to_string()).collect(),
+            ))
+        };
+
+        assert_eq!(scale(&[]), None);
+        assert_eq!(scale(&["-turbo"]), Some(200));
+        assert_eq!(scale(&["-turbo", "150"]), Some(150));
+        assert_eq!(scale(&["-turbo", "1000"]), Some(400));
+        assert_eq!(scale(&["-turbo", "fast"]), Some(10));
+    }
+
+    #[test]
+    fn test_responder_eats_key_downs_but_not_key_ups() {
+        let mut input: GameInput = game_input(&[]);
+
+        assert!(input.responder(&Event::KeyDown(KEY_UPARROW)));
+        assert!(!input.responder(&Event::KeyUp(KEY_UPARROW)));
+        assert_eq!(input.build_ticcmd(), TicCmd::default());
+        // Codes outside the key table are eaten and ignored
+        assert!(input.responder(&Event::KeyDown(1000)));
+    }
+
+    #[test]
+    fn test_responder_requests_screenshots_in_development_mode() {
+        let input: GameInput = game_input(&[KEY_F1]);
+        assert!(!input.screenshot_requested);
+
+        let mut input: GameInput = GameInput::new(Default::default(), None, true);
+        input.responder(&Event::KeyDown(KEY_F1));
+        assert!(input.screenshot_requested);
+    }
+}
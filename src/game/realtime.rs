@@ -0,0 +1,105 @@
+// The wall clock side of d_main.c and d_net.c for interactive play. The
+// clock says how many tics should have run by now and the driver runs
+// however many are due, so a slow frame is caught up on the next one
+// instead of slowing the game down
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::game::Game;
+use crate::play::ticcmd::TicCmd;
+use crate::play::TICRATE;
+
+// I_GetTime. Counts tics from when the clock was made
+pub struct TicClock {
+    base_time: Instant,
+}
+
+impl TicClock {
+    pub fn new() -> Self {
+        TicClock {
+            base_time: Instant::now(),
+        }
+    }
+
+    pub fn time(&self) -> i32 {
+        let elapsed: Duration = self.base_time.elapsed();
+        elapsed.as_secs() as i32 * TICRATE + elapsed.subsec_micros() as i32 * TICRATE / 1_000_000
+    }
+}
+
+impl Default for TicClock {
+    fn default() -> Self {
+        TicClock::new()
+    }
+}
+
+#[derive(Default)]
+pub struct RealTimeDriver {
+    clock: TicClock,
+    // Tics run so far
+    game_time: i32,
+}
+
+impl RealTimeDriver {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // TryRunTics. Waits until at least one tic is due and runs every tic
+    // the clock says is due, asking for each tic's ticcmds right before it
+    // runs. Returns how many tics were run
+    pub fn run_tics(
+        &mut self,
+        game: &mut Game,
+        build_cmds: &mut impl FnMut(&Game) -> Vec<TicCmd>,
+    ) -> i32 {
+        let mut now: i32 = self.clock.time();
+
+        // Wait for new tics if needed
+        while now <= self.game_time {
+            thread::sleep(Duration::from_millis(1));
+            now = self.clock.time();
+        }
+
+        let counts: i32 = now - self.game_time;
+        for _ in 0..counts {
+            let cmds: Vec<TicCmd> = build_cmds(game);
+            game.tick(&cmds);
+        }
+        self.game_time = now;
+
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::realtime::{RealTimeDriver, TicClock};
+    use crate::game::tests::create_game;
+    use crate::game::Game;
+    use crate::play::ticcmd::TicCmd;
+
+    #[test]
+    fn test_tic_clock_starts_at_zero() {
+        let clock: TicClock = TicClock::new();
+
+        assert_eq!(clock.time(), 0);
+    }
+
+    #[test]
+    fn test_real_time_driver_runs_the_tics_that_are_due() {
+        let mut game: Game = create_game("realtime", &[1]);
+        let mut driver: RealTimeDriver = RealTimeDriver::new();
+        let mut cmds_built: i32 = 0;
+
+        while game.world.level_time < 3 {
+            driver.run_tics(&mut game, &mut |_| {
+                cmds_built += 1;
+                vec![TicCmd::default()]
+            });
+        }
+
+        assert!(game.world.level_time >= 3);
+        assert_eq!(cmds_built, game.world.level_time);
+    }
+}
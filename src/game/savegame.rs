@@ -0,0 +1,370 @@
+// The savegame parts of g_game.c, plus the random index after the end marker
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::game::demo::{players_in_game_from_bytes, skill_from_byte, VERSION};
+use crate::game::{init_new_settings, load_world, Game, GameAction, GameState};
+use crate::m_random::Random;
+use crate::play::saveg::{SaveGameError, SaveReader, SaveWriter};
+use crate::play::world::{World, WorldSettings};
+use crate::play::MAXPLAYERS;
+
+pub const SAVESTRINGSIZE: usize = 24;
+const VERSIONSIZE: usize = 16;
+const SAVEGAMENAME: &str = "doomsav";
+// Consistancy marker at the end of the archives
+const SAVE_END_MARKER: u8 = 0x1d;
+// How many slots the save and load menus have
+pub const SAVE_SLOTS: usize = 6;
+
+pub const GGSAVED: &str = "game saved.";
+
+// Saves go next to the config file
+pub fn save_game_dir(config: &Config) -> PathBuf {
+    config
+        .config_file_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+pub fn save_game_path(save_dir: &Path, slot: usize) -> PathBuf {
+    save_dir.join(format!("{}{}.dsg", SAVEGAMENAME, slot))
+}
+
+// M_ReadSaveStrings. None for the empty slots
+pub fn save_slot_descriptions(save_dir: &Path) -> [Option<String>; SAVE_SLOTS] {
+    std::array::from_fn(|slot| {
+        let data: Vec<u8> = fs::read(save_game_path(save_dir, slot)).ok()?;
+        let description: &[u8] = data.get(..SAVESTRINGSIZE)?;
+        let len: usize = description
+            .iter()
+            .position(|c| *c == 0)
+            .unwrap_or(SAVESTRINGSIZE);
+        Some(String::from_utf8_lossy(&description[..len]).into_owned())
+    })
+}
+
+fn version_bytes() -> [u8; VERSIONSIZE] {
+    let mut bytes: [u8; VERSIONSIZE] = [0; VERSIONSIZE];
+    let version: String = format!("version {}", VERSION);
+    bytes[..version.len()].copy_from_slice(version.as_bytes());
+    bytes
+}
+
+impl Game {
+    // G_SaveGame. Called by the menu task. The save goes out with the
+    // console player's next ticcmd so every node saves on the same tic
+    pub fn save_game(&mut self, slot: usize, description: &str) {
+        self.save_game_slot = slot;
+        self.save_description = description.to_owned();
+        self.send_save = true;
+    }
+
+    // G_LoadGame. Can be called by the startup code or the menu task
+    pub fn load_game(&mut self, path: PathBuf) {
+        self.load_game_path = Some(path);
+        self.game_action = GameAction::LoadGame;
+    }
+
+    // G_DoSaveGame's buffer. Descriptions longer than the field are cut
+    // short
+    pub fn save_game_data(&self, description: &str) -> Vec<u8> {
+        let world: &World = &self.world;
+        let mut writer: SaveWriter = SaveWriter::new();
+
+        let mut name: [u8; SAVESTRINGSIZE] = [0; SAVESTRINGSIZE];
+        for (byte, c) in name.iter_mut().zip(description.bytes()) {
+            *byte = c;
+        }
+        writer.write_bytes(&name);
+        writer.write_bytes(&version_bytes());
+
+        writer.write_u8(world.settings.skill as u8);
+        writer.write_u8(world.settings.episode as u8);
+        writer.write_u8(world.settings.map as u8);
+        for in_game in world.settings.players_in_game {
+            writer.write_u8(u8::from(in_game));
+        }
+        writer.write_u8((world.level_time >> 16) as u8);
+        writer.write_u8((world.level_time >> 8) as u8);
+        writer.write_u8(world.level_time as u8);
+
+        world.archive_players(&mut writer);
+        world.archive_world(&mut writer, &self.pic_numbers);
+        world.archive_thinkers(&mut writer);
+        world.archive_specials(&mut writer, &self.pic_numbers);
+
+        // Consistancy marker
+        writer.write_u8(SAVE_END_MARKER);
+        writer.write_u8(world.random.prndindex());
+
+        writer.finish()
+    }
+
+    // G_DoLoadGame once the file's been read. The level is loaded again
+    // like a new game and the archives are put over it. Nothing changes
+    // unless the whole savegame is good
+    pub fn read_save_game(&mut self, data: &[u8]) -> Result<(), SaveGameError> {
+        let mut reader: SaveReader = SaveReader::new(data);
+
+        // Skip the description field
+        reader.read_bytes(SAVESTRINGSIZE)?;
+        if reader.read_bytes(VERSIONSIZE)? != version_bytes() {
+            // Bad version
+            return Err(SaveGameError::DifferentVersion);
+        }
+
+        let mut settings: WorldSettings = self.world.settings;
+        settings.skill = skill_from_byte(reader.read_u8()?);
+        settings.episode = i32::from(reader.read_u8()?);
+        settings.map = i32::from(reader.read_u8()?);
+        settings.players_in_game = players_in_game_from_bytes(reader.read_bytes(MAXPLAYERS)?);
+
+        // Load a base level
+        let settings: WorldSettings = init_new_settings(settings);
+        let mut world: World = load_world(
+            &mut self.doom_files,
+            settings,
+            Default::default(),
+            Random::new(),
+            self.world.game_tic,
+        )
+        .map_err(|error| SaveGameError::LevelError(error.to_string()))?;
+
+        // Get the times
+        let level_time: &[u8] = reader.read_bytes(3)?;
+        world.level_time = (i32::from(level_time[0]) << 16)
+            + (i32::from(level_time[1]) << 8)
+            + i32::from(level_time[2]);
+
+        // Dearchive all the modifications
+        world.unarchive_players(&mut reader)?;
+        world.unarchive_world(&mut reader, &self.pic_numbers)?;
+        world.unarchive_thinkers(&mut reader)?;
+        world.unarchive_specials(&mut reader, &self.pic_numbers)?;
+
+        if reader.read_u8()? != SAVE_END_MARKER {
+            return Err(SaveGameError::BadSaveGame);
+        }
+        // Savegames from the original end at the marker
+        if !reader.is_at_end() {
+            world.random.set_prndindex(reader.read_u8()?);
+        }
+
+        self.world = world;
+        self.game_state = GameState::Level;
+        self.paused = false;
+        self.next_map = settings.map;
+        Ok(())
+    }
+
+    // G_DoSaveGame
+    pub(crate) fn do_save_game(&mut self) {
+        let path: PathBuf = save_game_path(&self.save_dir, self.save_game_slot);
+        let data: Vec<u8> = self.save_game_data(&self.save_description);
+
+        self.game_action = GameAction::Nothing;
+        self.save_description.clear();
+
+        match fs::write(&path, data) {
+            Ok(()) => {
+                let console_player: usize = self.world.settings.console_player;
+                self.world.players[console_player].message = Some(GGSAVED);
+            }
+            Err(error) => eprintln!("Unable to write savegame {}. {}", path.display(), error),
+        }
+    }
+
+    // G_DoLoadGame. A savegame that can't be loaded leaves the game as it
+    // was
+    pub(crate) fn do_load_game(&mut self) {
+        self.game_action = GameAction::Nothing;
+
+        let Some(path) = self.load_game_path.take() else {
+            return;
+        };
+        let result: Result<(), SaveGameError> = fs::read(&path)
+            .map_err(|error| {
+                SaveGameError::IoError(format!(
+                    "Unable to read savegame {}. {}",
+                    path.display(),
+                    error
+                ))
+            })
+            .and_then(|data| self.read_save_game(&data));
+
+        if let Err(error) = result {
+            eprintln!("{}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::config::{Config, GameDifficulty};
+    use crate::game::savegame::{
+        save_game_dir, save_game_path, save_slot_descriptions, GGSAVED, SAVE_SLOTS,
+    };
+    use crate::game::{Game, GameAction};
+    use crate::play::doors::DoorType;
+    use crate::play::hash::StateHashes;
+    use crate::play::saveg::SaveGameError;
+    use crate::play::test_map::{map_wad_lumps, TestMap, TestRoom};
+    use crate::play::ticcmd::TicCmd;
+    use crate::play::world::WorldSettings;
+    use crate::test_util::{create_test_dir, write_test_wad};
+    use crate::wad;
+
+    // A room with the player and a zombieman facing away from them, next
+    // to a glowing room with a door to close. Monsters forget their target
+    // when a game's loaded so this one can't be allowed to see the player
+    fn create_save_game(name: &str) -> Game {
+        let test_map: TestMap = TestMap::new()
+            .room(256, 0, 128)
+            .room_with(TestRoom {
+                width: 256,
+                floor_height: 0,
+                ceiling_height: 128,
+                light_level: 160,
+                special: 8,
+                tag: 1,
+            })
+            .thing(64, 128, 0, 1, 7)
+            .thing(224, 200, 0, 3004, 7)
+            .line_special(0, 0, 1);
+
+        let path: PathBuf = create_test_dir(name).join("test.wad");
+        write_test_wad(&path, &map_wad_lumps("MAP01", &test_map.to_map_lumps()));
+        let settings: WorldSettings = WorldSettings {
+            skill: GameDifficulty::Hard,
+            ..Default::default()
+        };
+        Game::with_settings(settings, vec![wad::process_file(&path).unwrap()]).unwrap()
+    }
+
+    fn forward() -> TicCmd {
+        TicCmd {
+            forward_move: 25,
+            angle_turn: 256,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_save_game_restores_the_world_mid_level() {
+        let mut game: Game = create_save_game("savegame-save");
+        for _ in 0..10 {
+            game.tick(&[forward()]);
+        }
+        let line: usize = game
+            .world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == 1)
+            .unwrap();
+        assert!(game.world.do_door(line, DoorType::Close));
+        for _ in 0..10 {
+            game.tick(&[forward()]);
+        }
+
+        let data: Vec<u8> = game.save_game_data("mid level");
+        let hashes: StateHashes = game.world.state_hashes();
+
+        let mut loaded: Game = create_save_game("savegame-load");
+        loaded.tick(&[]);
+        assert_eq!(loaded.read_save_game(&data), Ok(()));
+        assert_eq!(loaded.world.state_hashes(), hashes);
+        assert_eq!(loaded.world.level_time, game.world.level_time);
+        assert_eq!(loaded.world.settings.skill, GameDifficulty::Hard);
+        assert_eq!(loaded.world.players[0], game.world.players[0]);
+
+        // And keeps going the same way
+        for _ in 0..30 {
+            game.tick(&[forward()]);
+            loaded.tick(&[forward()]);
+            assert_eq!(loaded.world.state_hashes(), game.world.state_hashes());
+        }
+    }
+
+    #[test]
+    fn test_save_game_goes_out_with_a_ticcmd_to_its_slot() {
+        let dir: PathBuf = create_test_dir("savegame-slots");
+        let mut game: Game = create_save_game("savegame-slots-wad");
+        game.save_dir = dir.clone();
+        game.tick(&[forward()]);
+
+        game.save_game(3, "slot three");
+        game.tick(&[forward()]);
+        assert_eq!(game.game_action, GameAction::SaveGame);
+        let level_time: i32 = game.world.level_time;
+
+        // Saved before the next tic runs
+        game.tick(&[forward()]);
+        assert_eq!(game.world.players[0].message, Some(GGSAVED));
+        let mut descriptions: [Option<String>; SAVE_SLOTS] = Default::default();
+        descriptions[3] = Some(String::from("slot three"));
+        assert_eq!(save_slot_descriptions(&dir), descriptions);
+
+        game.tick(&[forward()]);
+        game.load_game(save_game_path(&dir, 3));
+        game.tick(&[]);
+        assert_eq!(game.world.level_time, level_time + 1);
+
+        // Loading a missing slot leaves the game alone
+        game.load_game(save_game_path(&dir, 5));
+        game.tick(&[]);
+        assert_eq!(game.world.level_time, level_time + 2);
+        assert_eq!(game.game_action, GameAction::Nothing);
+    }
+
+    #[test]
+    fn test_read_save_game_checks_the_savegame() {
+        let mut game: Game = create_save_game("savegame-bad");
+        game.tick(&[forward()]);
+        let data: Vec<u8> = game.save_game_data("");
+        let hashes: StateHashes = game.world.state_hashes();
+
+        let mut version: Vec<u8> = data.clone();
+        version[32] = b'0';
+        assert_eq!(
+            game.read_save_game(&version),
+            Err(SaveGameError::DifferentVersion)
+        );
+        assert_eq!(
+            game.read_save_game(&data[..data.len() - 10]),
+            Err(SaveGameError::Truncated)
+        );
+        let mut marker: Vec<u8> = data.clone();
+        let marker_index: usize = marker.len() - 2;
+        marker[marker_index] = 0;
+        assert_eq!(
+            game.read_save_game(&marker),
+            Err(SaveGameError::BadSaveGame)
+        );
+        assert_eq!(game.world.state_hashes(), hashes);
+
+        // The original's savegames end at the marker, the random index is
+        // left where the level setup put it
+        assert_eq!(game.read_save_game(&data[..data.len() - 1]), Ok(()));
+        assert_eq!(game.world.level_time, 1);
+    }
+
+    #[test]
+    fn test_save_game_dir_is_next_to_the_config_file() {
+        let config: Config = Config {
+            config_file_path: PathBuf::from("/home/doomguy/.doomrc"),
+            ..Default::default()
+        };
+
+        assert_eq!(save_game_dir(&config), PathBuf::from("/home/doomguy"));
+        assert_eq!(
+            save_game_path(&save_game_dir(&config), 2),
+            PathBuf::from("/home/doomguy/doomsav2.dsg")
+        );
+    }
+}
@@ -0,0 +1,251 @@
+// -timedemo from g_game.c. Plays a demo back as fast as it will go, one tic
+// and then one frame at a time like -singletics, timing both so the results
+// can be compared between builds
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::game::Game;
+use crate::play::TICRATE;
+
+pub struct TimeDemoResults {
+    pub game_tics: i32,
+    // Whole tics of wall clock time the demo took, what I_GetTime counted
+    pub real_tics: i32,
+    pub elapsed: Duration,
+    // Time spent in each G_Ticker
+    pub tic_times: Vec<Duration>,
+    // Time spent drawing each frame, empty with -nodraw
+    pub render_times: Vec<Duration>,
+}
+
+impl TimeDemoResults {
+    // A demo too short for the clock to see has no rate, 0 keeps the JSON
+    // valid where inf or NaN wouldn't be
+    pub fn fps(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            return 0.0;
+        }
+        f64::from(self.game_tics) / self.elapsed.as_secs_f64()
+    }
+
+    // What the original printed when the demo ended
+    pub fn summary(&self) -> String {
+        format!(
+            "timed {} gametics in {} realtics ({:.6} fps)",
+            self.game_tics,
+            self.real_tics,
+            self.fps()
+        )
+    }
+
+    // Timings in microseconds. The render fields are null with -nodraw
+    pub fn to_json(&self) -> String {
+        let mut json: String = String::from("{\n");
+        let _ = writeln!(json, "  \"gametics\": {},", self.game_tics);
+        let _ = writeln!(json, "  \"realtics\": {},", self.real_tics);
+        let _ = writeln!(json, "  \"elapsed_us\": {},", self.elapsed.as_micros());
+        let _ = writeln!(json, "  \"fps\": {:.6},", self.fps());
+        let _ = writeln!(json, "  \"nodraw\": {},", self.render_times.is_empty());
+        let _ = writeln!(json, "  \"tic\": {},", timing_stats_json(&self.tic_times));
+        let _ = writeln!(
+            json,
+            "  \"render\": {},",
+            timing_stats_json(&self.render_times)
+        );
+        let _ = writeln!(
+            json,
+            "  \"tic_times_us\": {},",
+            micros_json(&self.tic_times)
+        );
+        let _ = writeln!(
+            json,
+            "  \"render_times_us\": {}",
+            micros_json(&self.render_times)
+        );
+        json.push_str("}\n");
+        json
+    }
+}
+
+// G_TimeDemo and the -singletics loop of D_DoomLoop. The game has to be
+// playing back a demo, nothing else ends the loop. Frames are only drawn when
+// there's something to draw them with
+pub fn time_demo(game: &mut Game, mut draw: Option<&mut dyn FnMut(&Game)>) -> TimeDemoResults {
+    assert!(
+        game.demo_playback.is_some(),
+        "time_demo needs a demo being played back"
+    );
+
+    let mut tic_times: Vec<Duration> = Vec::new();
+    let mut render_times: Vec<Duration> = Vec::new();
+    let start_time: Instant = Instant::now();
+
+    loop {
+        let tic_start: Instant = Instant::now();
+        game.tick(&[]);
+        // Timed without sound
+        game.world.sound_events.clear();
+
+        // The tic that reaches the end of the demo doesn't run
+        if game.demo_finished() {
+            break;
+        }
+        tic_times.push(tic_start.elapsed());
+
+        if let Some(draw) = draw.as_mut() {
+            let render_start: Instant = Instant::now();
+            draw(game);
+            render_times.push(render_start.elapsed());
+        }
+    }
+
+    let elapsed: Duration = start_time.elapsed();
+    TimeDemoResults {
+        game_tics: tic_times.len() as i32,
+        real_tics: (elapsed.as_micros() * TICRATE as u128 / 1_000_000) as i32,
+        elapsed,
+        tic_times,
+        render_times,
+    }
+}
+
+// Nearest rank percentile of sorted timings
+fn percentile(sorted: &[u128], percent: usize) -> u128 {
+    let rank: usize = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn timing_stats_json(times: &[Duration]) -> String {
+    if times.is_empty() {
+        return String::from("null");
+    }
+
+    let mut micros: Vec<u128> = times.iter().map(Duration::as_micros).collect();
+    micros.sort_unstable();
+    let mean: u128 = micros.iter().sum::<u128>() / micros.len() as u128;
+
+    format!(
+        "{{\"mean_us\": {}, \"p50_us\": {}, \"p90_us\": {}, \"p99_us\": {}, \"max_us\": {}}}",
+        mean,
+        percentile(&micros, 50),
+        percentile(&micros, 90),
+        percentile(&micros, 99),
+        micros[micros.len() - 1]
+    )
+}
+
+fn micros_json(times: &[Duration]) -> String {
+    let micros: Vec<String> = times
+        .iter()
+        .map(|time| time.as_micros().to_string())
+        .collect();
+    format!("[{}]", micros.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::game::demo::{DemoHeader, DemoPlayback, DemoRecorder};
+    use crate::game::tests::create_game;
+    use crate::game::timedemo::{percentile, time_demo, TimeDemoResults};
+    use crate::game::Game;
+    use crate::play::ticcmd::TicCmd;
+    use crate::play::world::WorldSettings;
+
+    fn create_timedemo_game(name: &str, tics: usize) -> Game {
+        let mut demo_recorder: DemoRecorder =
+            DemoRecorder::new(&DemoHeader::from_settings(&WorldSettings::default()));
+        for _ in 0..tics {
+            demo_recorder.write_cmd(&TicCmd {
+                forward_move: 25,
+                ..Default::default()
+            });
+        }
+
+        let mut game: Game = create_game(name, &[1]);
+        game.demo_playback = Some(DemoPlayback::from_lump_data(demo_recorder.finish()).unwrap());
+        game
+    }
+
+    #[test]
+    fn test_time_demo_times_every_tic_and_frame() {
+        let mut game: Game = create_timedemo_game("timedemo-draw", 30);
+        let mut frames: i32 = 0;
+        let mut draw = |_: &Game| frames += 1;
+
+        let results: TimeDemoResults = time_demo(&mut game, Some(&mut draw));
+
+        assert_eq!(results.game_tics, 30);
+        assert_eq!(results.tic_times.len(), 30);
+        assert_eq!(results.render_times.len(), 30);
+        assert_eq!(frames, 30);
+        assert_eq!(game.world.level_time, 30);
+        assert!(results.summary().starts_with("timed 30 gametics in "));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_time_demo_without_a_demo() {
+        let mut game: Game = create_game("timedemo-nodemo", &[1]);
+
+        time_demo(&mut game, None);
+    }
+
+    #[test]
+    fn test_time_demo_nodraw_skips_rendering() {
+        let mut game: Game = create_timedemo_game("timedemo-nodraw", 10);
+
+        let results: TimeDemoResults = time_demo(&mut game, None);
+
+        assert_eq!(results.game_tics, 10);
+        assert!(results.render_times.is_empty());
+        let json: String = results.to_json();
+        assert!(json.contains("\"gametics\": 10,"));
+        assert!(json.contains("\"nodraw\": true,"));
+        assert!(json.contains("\"render\": null,"));
+        assert!(json.contains("\"render_times_us\": []"));
+    }
+
+    #[test]
+    fn test_time_demo_results_json_has_percentiles() {
+        let results: TimeDemoResults = TimeDemoResults {
+            game_tics: 4,
+            real_tics: 0,
+            elapsed: Duration::from_millis(10),
+            tic_times: [40, 10, 30, 20].map(Duration::from_micros).to_vec(),
+            render_times: vec![Duration::from_micros(5)],
+        };
+
+        let json: String = results.to_json();
+        assert!(json.contains(
+            "\"tic\": {\"mean_us\": 25, \"p50_us\": 20, \"p90_us\": 40, \"p99_us\": 40, \"max_us\": 40},"
+        ));
+        assert!(json.contains("\"fps\": 400.000000,"));
+        assert!(json.contains("\"tic_times_us\": [40, 10, 30, 20],"));
+    }
+
+    #[test]
+    fn test_time_demo_results_fps_is_zero_without_elapsed_time() {
+        let results: TimeDemoResults = TimeDemoResults {
+            game_tics: 0,
+            real_tics: 0,
+            elapsed: Duration::ZERO,
+            tic_times: Vec::new(),
+            render_times: Vec::new(),
+        };
+
+        assert_eq!(results.fps(), 0.0);
+        assert!(results.to_json().contains("\"fps\": 0.000000,"));
+        assert!(results.summary().ends_with("(0.000000 fps)"));
+    }
+
+    #[test]
+    fn test_percentile_uses_nearest_rank() {
+        let sorted: Vec<u128> = (1..=100).collect();
+
+        assert_eq!(percentile(&sorted, 50), 50);
+        assert_eq!(percentile(&sorted, 99), 99);
+        assert_eq!(percentile(&[7], 90), 7);
+    }
+}
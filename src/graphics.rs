@@ -0,0 +1,372 @@
+use std::fmt::{self, Display};
+
+pub const PALETTE_NUM_COLORS: usize = 256;
+pub const PALETTE_SIZE: usize = PALETTE_NUM_COLORS * 3;
+pub const FLAT_WIDTH: usize = 64;
+pub const FLAT_HEIGHT: usize = 64;
+
+// The original limited patch dimensions based on the column post top delta
+// being a byte with 0xFF reserved as the end of column marker
+const PICTURE_MAX_HEIGHT: usize = 255;
+const PICTURE_MAX_POST_LENGTH: usize = 128;
+const PICTURE_HEADER_SIZE: usize = 8;
+const POST_END_MARKER: u8 = 0xFF;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum GraphicsError {
+    InvalidPalette(usize),
+    InvalidPicture(String),
+    PictureTooTall(usize),
+}
+
+impl Display for GraphicsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidPalette(palette_index) => write!(
+                f,
+                "Palette {} does not exist in the supplied PLAYPAL data",
+                palette_index
+            ),
+            Self::InvalidPicture(reason) => write!(f, "Invalid picture data. {}", reason),
+            Self::PictureTooTall(height) => write!(
+                f,
+                "Picture height of {} exceeds max height of {}",
+                height, PICTURE_MAX_HEIGHT
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    pub colors: Vec<[u8; 3]>,
+}
+
+impl Palette {
+    // PLAYPAL holds 14 palettes, 0 is the normal one and the rest are
+    // used for the damage/pickup/radiation suit screen tints
+    pub fn from_playpal(playpal_data: &[u8], palette_index: usize) -> Result<Self, GraphicsError> {
+        let start: usize = palette_index * PALETTE_SIZE;
+
+        let palette_data: &[u8] = playpal_data
+            .get(start..start + PALETTE_SIZE)
+            .ok_or(GraphicsError::InvalidPalette(palette_index))?;
+
+        let colors: Vec<[u8; 3]> = palette_data
+            .chunks_exact(3)
+            .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+            .collect();
+
+        Ok(Palette { colors })
+    }
+
+    pub fn color(&self, index: u8) -> [u8; 3] {
+        self.colors[usize::from(index)]
+    }
+
+    // Exact matches always win so paletted images survive a round trip
+    pub fn nearest_index(&self, rgb: [u8; 3]) -> u8 {
+        let mut best_index: usize = 0;
+        let mut best_distance: u32 = u32::MAX;
+
+        for (index, color) in self.colors.iter().enumerate() {
+            let distance: u32 = color
+                .iter()
+                .zip(rgb.iter())
+                .map(|(a, b)| {
+                    let difference: i32 = i32::from(*a) - i32::from(*b);
+                    (difference * difference) as u32
+                })
+                .sum();
+
+            if distance < best_distance {
+                best_index = index;
+                best_distance = distance;
+
+                if distance == 0 {
+                    break;
+                }
+            }
+        }
+
+        best_index as u8
+    }
+}
+
+// Equivalent to patch_t in the original source. Used for
+// sprites, wall patches and all the menu/status bar graphics.
+// Pixels are stored row by row with None being transparent
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Picture {
+    pub width: usize,
+    pub height: usize,
+    pub left_offset: i16,
+    pub top_offset: i16,
+    pub pixels: Vec<Option<u8>>,
+}
+
+impl Picture {
+    pub fn from_lump(data: &[u8]) -> Result<Self, GraphicsError> {
+        if data.len() < PICTURE_HEADER_SIZE {
+            return Err(GraphicsError::InvalidPicture(String::from(
+                "Lump is smaller than the picture header",
+            )));
+        }
+
+        let width: usize = usize::from(u16::from_le_bytes([data[0], data[1]]));
+        let height: usize = usize::from(u16::from_le_bytes([data[2], data[3]]));
+        let left_offset: i16 = i16::from_le_bytes([data[4], data[5]]);
+        let top_offset: i16 = i16::from_le_bytes([data[6], data[7]]);
+
+        if width == 0 || height == 0 {
+            return Err(GraphicsError::InvalidPicture(format!(
+                "Invalid dimensions {}x{}",
+                width, height
+            )));
+        }
+
+        let column_offsets_end: usize = PICTURE_HEADER_SIZE + width * 4;
+        if data.len() < column_offsets_end {
+            return Err(GraphicsError::InvalidPicture(String::from(
+                "Lump is smaller than the column offset table",
+            )));
+        }
+
+        let mut pixels: Vec<Option<u8>> = vec![None; width * height];
+
+        for x in 0..width {
+            let offset_start: usize = PICTURE_HEADER_SIZE + x * 4;
+            let mut position: usize =
+                u32::from_le_bytes(data[offset_start..offset_start + 4].try_into().unwrap())
+                    as usize;
+
+            if position < column_offsets_end || position >= data.len() {
+                return Err(GraphicsError::InvalidPicture(format!(
+                    "Column {} has invalid offset {}",
+                    x, position
+                )));
+            }
+
+            loop {
+                let top_delta: u8 = *data.get(position).ok_or_else(|| {
+                    GraphicsError::InvalidPicture(format!("Column {} is not terminated", x))
+                })?;
+
+                if top_delta == POST_END_MARKER {
+                    break;
+                }
+
+                let length: usize = usize::from(*data.get(position + 1).ok_or_else(|| {
+                    GraphicsError::InvalidPicture(format!("Column {} is truncated", x))
+                })?);
+
+                // Skip top delta, length and the unused padding byte
+                let post_start: usize = position + 3;
+                let post_data: &[u8] =
+                    data.get(post_start..post_start + length).ok_or_else(|| {
+                        GraphicsError::InvalidPicture(format!("Column {} is truncated", x))
+                    })?;
+
+                for (row_offset, pixel) in post_data.iter().enumerate() {
+                    let y: usize = usize::from(top_delta) + row_offset;
+
+                    // The original just drew past the bottom, we clip instead
+                    if y < height {
+                        pixels[y * width + x] = Some(*pixel);
+                    }
+                }
+
+                // Skip post data and the trailing padding byte
+                position = post_start + length + 1;
+            }
+        }
+
+        Ok(Picture {
+            width,
+            height,
+            left_offset,
+            top_offset,
+            pixels,
+        })
+    }
+
+    pub fn to_lump(&self) -> Result<Vec<u8>, GraphicsError> {
+        if self.height > PICTURE_MAX_HEIGHT {
+            return Err(GraphicsError::PictureTooTall(self.height));
+        }
+
+        let mut columns_data: Vec<u8> = Vec::new();
+        let mut column_offsets: Vec<u32> = Vec::with_capacity(self.width);
+        let column_offsets_end: usize = PICTURE_HEADER_SIZE + self.width * 4;
+
+        for x in 0..self.width {
+            column_offsets.push((column_offsets_end + columns_data.len()) as u32);
+
+            let mut y: usize = 0;
+            while y < self.height {
+                if self.pixels[y * self.width + x].is_none() {
+                    y += 1;
+                    continue;
+                }
+
+                let post_start: usize = y;
+                let mut post_pixels: Vec<u8> = Vec::new();
+
+                while y < self.height && post_pixels.len() < PICTURE_MAX_POST_LENGTH {
+                    match self.pixels[y * self.width + x] {
+                        Some(pixel) => post_pixels.push(pixel),
+                        None => break,
+                    }
+                    y += 1;
+                }
+
+                columns_data.push(post_start as u8);
+                columns_data.push(post_pixels.len() as u8);
+                // Unused padding bytes, the original tools
+                // duplicated the first and last pixel
+                columns_data.push(post_pixels[0]);
+                columns_data.extend_from_slice(&post_pixels);
+                columns_data.push(post_pixels[post_pixels.len() - 1]);
+            }
+
+            columns_data.push(POST_END_MARKER);
+        }
+
+        let mut lump: Vec<u8> = Vec::with_capacity(column_offsets_end + columns_data.len());
+        lump.extend_from_slice(&(self.width as u16).to_le_bytes());
+        lump.extend_from_slice(&(self.height as u16).to_le_bytes());
+        lump.extend_from_slice(&self.left_offset.to_le_bytes());
+        lump.extend_from_slice(&self.top_offset.to_le_bytes());
+
+        for column_offset in column_offsets {
+            lump.extend_from_slice(&column_offset.to_le_bytes());
+        }
+
+        lump.extend_from_slice(&columns_data);
+
+        Ok(lump)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphics::{GraphicsError, Palette, Picture, PALETTE_SIZE};
+
+    fn create_test_playpal() -> Vec<u8> {
+        let mut playpal: Vec<u8> = Vec::new();
+
+        for palette_index in 0..2 {
+            for color_index in 0..256 {
+                playpal.push(color_index as u8);
+                playpal.push(palette_index as u8);
+                playpal.push(255 - color_index as u8);
+            }
+        }
+
+        playpal
+    }
+
+    #[test]
+    fn test_graphics_error_implements_display_trait() {
+        let graphics_errors: Vec<GraphicsError> = vec![
+            GraphicsError::InvalidPalette(1),
+            GraphicsError::InvalidPicture(String::from("test")),
+            GraphicsError::PictureTooTall(300),
+        ];
+
+        for graphics_error in graphics_errors {
+            println!("{}", graphics_error);
+        }
+    }
+
+    #[test]
+    fn test_palette_from_playpal_reads_requested_palette() {
+        let playpal: Vec<u8> = create_test_playpal();
+
+        let palette: Palette = Palette::from_playpal(&playpal, 1).unwrap();
+
+        assert_eq!(palette.colors.len(), 256);
+        assert_eq!(palette.color(0), [0, 1, 255]);
+        assert_eq!(palette.color(255), [255, 1, 0]);
+    }
+
+    #[test]
+    fn test_palette_from_playpal_returns_error_for_missing_palette() {
+        let playpal: Vec<u8> = vec![0; PALETTE_SIZE];
+
+        assert_eq!(
+            Palette::from_playpal(&playpal, 1),
+            Err(GraphicsError::InvalidPalette(1))
+        );
+    }
+
+    #[test]
+    fn test_palette_nearest_index_finds_exact_and_closest_colors() {
+        let palette: Palette = Palette::from_playpal(&create_test_playpal(), 0).unwrap();
+
+        assert_eq!(palette.nearest_index([10, 0, 245]), 10);
+        assert_eq!(palette.nearest_index([11, 3, 243]), 11);
+    }
+
+    #[test]
+    fn test_picture_lump_round_trip() {
+        let picture: Picture = Picture {
+            width: 3,
+            height: 4,
+            left_offset: -2,
+            top_offset: 7,
+            pixels: vec![
+                Some(1),
+                None,
+                Some(9),
+                Some(2),
+                None,
+                None,
+                None,
+                None,
+                Some(10),
+                Some(3),
+                Some(5),
+                Some(11),
+            ],
+        };
+
+        let lump: Vec<u8> = picture.to_lump().unwrap();
+
+        assert_eq!(Picture::from_lump(&lump).unwrap(), picture);
+    }
+
+    #[test]
+    fn test_picture_from_lump_reads_posts() {
+        // 1x3 picture with a single post of 2 pixels starting at row 1
+        let lump: Vec<u8> = vec![1, 0, 3, 0, 0, 0, 0, 0, 12, 0, 0, 0, 1, 2, 0, 7, 8, 0, 0xFF];
+
+        let picture: Picture = Picture::from_lump(&lump).unwrap();
+
+        assert_eq!(picture.pixels, vec![None, Some(7), Some(8)]);
+    }
+
+    #[test]
+    fn test_picture_from_lump_returns_error_for_invalid_data() {
+        assert!(Picture::from_lump(&[1, 0, 1, 0]).is_err());
+
+        // Column offset points past the end of the lump
+        assert!(Picture::from_lump(&[1, 0, 1, 0, 0, 0, 0, 0, 200, 0, 0, 0]).is_err());
+
+        // Post without an end of column marker
+        assert!(Picture::from_lump(&[1, 0, 1, 0, 0, 0, 0, 0, 12, 0, 0, 0, 0, 1, 0, 5]).is_err());
+    }
+
+    #[test]
+    fn test_picture_to_lump_returns_error_when_too_tall() {
+        let picture: Picture = Picture {
+            width: 1,
+            height: 300,
+            left_offset: 0,
+            top_offset: 0,
+            pixels: vec![Some(0); 300],
+        };
+
+        assert_eq!(picture.to_lump(), Err(GraphicsError::PictureTooTall(300)));
+    }
+}
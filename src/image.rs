@@ -0,0 +1,43 @@
+use std::fmt::{self, Display};
+
+pub mod pcx;
+pub mod png;
+pub mod zlib;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ImageError {
+    InvalidPcx(String),
+    InvalidPng(String),
+    InvalidZlib(String),
+    UnsupportedPng(String),
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidPcx(reason) => write!(f, "Invalid PCX data. {}", reason),
+            Self::InvalidPng(reason) => write!(f, "Invalid PNG data. {}", reason),
+            Self::InvalidZlib(reason) => write!(f, "Invalid zlib data. {}", reason),
+            Self::UnsupportedPng(reason) => write!(f, "Unsupported PNG. {}", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::ImageError;
+
+    #[test]
+    fn test_image_error_implements_display_trait() {
+        let image_errors: Vec<ImageError> = vec![
+            ImageError::InvalidPcx(String::from("test")),
+            ImageError::InvalidPng(String::from("test")),
+            ImageError::InvalidZlib(String::from("test")),
+            ImageError::UnsupportedPng(String::from("test")),
+        ];
+
+        for image_error in image_errors {
+            println!("{}", image_error);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+// 8 bit PCX files like WritePCXfile in m_misc.c writes for screenshots
+use crate::image::ImageError;
+
+const PCX_HEADER_SIZE: usize = 128;
+const PCX_MANUFACTURER: u8 = 0x0A;
+const PCX_VERSION: u8 = 5;
+const PCX_ENCODING_RLE: u8 = 1;
+// The 256 colour palette after the image data starts with this byte
+const PCX_PALETTE_MARKER: u8 = 0x0C;
+const PCX_PALETTE_SIZE: usize = 768;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PcxImage {
+    pub width: u32,
+    pub height: u32,
+    pub palette: Vec<[u8; 3]>,
+    pub indices: Vec<u8>,
+}
+
+impl PcxImage {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut pcx: Vec<u8> =
+            Vec::with_capacity(PCX_HEADER_SIZE + self.indices.len() * 2 + 1 + PCX_PALETTE_SIZE);
+
+        pcx.push(PCX_MANUFACTURER);
+        pcx.push(PCX_VERSION);
+        pcx.push(PCX_ENCODING_RLE);
+        // bits_per_pixel
+        pcx.push(8);
+        // xmin, ymin
+        pcx.extend_from_slice(&0u16.to_le_bytes());
+        pcx.extend_from_slice(&0u16.to_le_bytes());
+        // xmax, ymax
+        pcx.extend_from_slice(&(self.width as u16 - 1).to_le_bytes());
+        pcx.extend_from_slice(&(self.height as u16 - 1).to_le_bytes());
+        // hres, vres
+        pcx.extend_from_slice(&(self.width as u16).to_le_bytes());
+        pcx.extend_from_slice(&(self.height as u16).to_le_bytes());
+        // The 16 colour palette isn't used
+        pcx.extend_from_slice(&[0; 48]);
+        // reserved
+        pcx.push(0);
+        // color_planes
+        pcx.push(1);
+        pcx.extend_from_slice(&(self.width as u16).to_le_bytes());
+        // palette_type, colour
+        pcx.extend_from_slice(&2u16.to_le_bytes());
+        pcx.resize(PCX_HEADER_SIZE, 0);
+
+        // The original never wrote runs, only escaped the bytes that look
+        // like run counts
+        for &index in &self.indices {
+            if index & 0xC0 == 0xC0 {
+                pcx.push(0xC1);
+            }
+            pcx.push(index);
+        }
+
+        pcx.push(PCX_PALETTE_MARKER);
+        for index in 0..256 {
+            let color: [u8; 3] = self.palette.get(index).copied().unwrap_or_default();
+            pcx.extend_from_slice(&color);
+        }
+
+        pcx
+    }
+
+    pub fn decode(pcx: &[u8]) -> Result<Self, ImageError> {
+        if pcx.len() < PCX_HEADER_SIZE + 1 + PCX_PALETTE_SIZE {
+            return Err(ImageError::InvalidPcx(String::from("File is too short")));
+        }
+        if pcx[0] != PCX_MANUFACTURER || pcx[2] != PCX_ENCODING_RLE {
+            return Err(ImageError::InvalidPcx(String::from("Bad header")));
+        }
+        if pcx[3] != 8 || pcx[65] != 1 {
+            return Err(ImageError::InvalidPcx(String::from(
+                "Only 8 bit single plane images are supported",
+            )));
+        }
+
+        let read_u16 =
+            |offset: usize| -> u32 { u16::from_le_bytes([pcx[offset], pcx[offset + 1]]) as u32 };
+        let width: u32 = read_u16(8) - read_u16(4) + 1;
+        let height: u32 = read_u16(10) - read_u16(6) + 1;
+        let bytes_per_line: usize = read_u16(66) as usize;
+
+        let palette_start: usize = pcx.len() - PCX_PALETTE_SIZE;
+        if pcx[palette_start - 1] != PCX_PALETTE_MARKER {
+            return Err(ImageError::InvalidPcx(String::from("Missing palette")));
+        }
+        let palette: Vec<[u8; 3]> = pcx[palette_start..]
+            .chunks_exact(3)
+            .map(|color| [color[0], color[1], color[2]])
+            .collect();
+
+        let mut scanlines: Vec<u8> = Vec::with_capacity(bytes_per_line * height as usize);
+        let mut data = pcx[PCX_HEADER_SIZE..palette_start - 1].iter();
+        while scanlines.len() < bytes_per_line * height as usize {
+            let byte: u8 = *data
+                .next()
+                .ok_or_else(|| ImageError::InvalidPcx(String::from("Image data is too short")))?;
+            if byte & 0xC0 == 0xC0 {
+                let value: u8 = *data.next().ok_or_else(|| {
+                    ImageError::InvalidPcx(String::from("Image data is too short"))
+                })?;
+                let count: usize = (byte & 0x3F) as usize;
+                scanlines.extend(std::iter::repeat_n(value, count));
+            } else {
+                scanlines.push(byte);
+            }
+        }
+
+        // Scanlines can be padded past the width
+        let indices: Vec<u8> = scanlines
+            .chunks(bytes_per_line)
+            .take(height as usize)
+            .flat_map(|line| line[..width as usize].iter().copied())
+            .collect();
+
+        Ok(PcxImage {
+            width,
+            height,
+            palette,
+            indices,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::pcx::PcxImage;
+    use crate::image::ImageError;
+
+    fn test_image() -> PcxImage {
+        PcxImage {
+            width: 3,
+            height: 2,
+            palette: (0..=255).map(|index: u8| [index, 255 - index, 7]).collect(),
+            indices: vec![0, 0xC0, 0x3F, 0xFF, 1, 0xC1],
+        }
+    }
+
+    #[test]
+    fn test_pcx_image_encode_writes_vanilla_header() {
+        let pcx: Vec<u8> = test_image().encode();
+
+        assert_eq!(&pcx[0..4], &[0x0A, 5, 1, 8]);
+        // xmax, ymax
+        assert_eq!(&pcx[8..12], &[2, 0, 1, 0]);
+        assert_eq!(pcx[65], 1);
+        // bytes_per_line, palette_type
+        assert_eq!(&pcx[66..70], &[3, 0, 2, 0]);
+        // Only the bytes over 0xBF are escaped
+        assert_eq!(
+            &pcx[128..137],
+            &[0, 0xC1, 0xC0, 0x3F, 0xC1, 0xFF, 1, 0xC1, 0xC1]
+        );
+        assert_eq!(pcx[137], 0x0C);
+        assert_eq!(pcx.len(), 128 + 9 + 1 + 768);
+    }
+
+    #[test]
+    fn test_pcx_image_encode_decode_round_trip() {
+        let image: PcxImage = test_image();
+
+        assert_eq!(PcxImage::decode(&image.encode()), Ok(image));
+    }
+
+    #[test]
+    fn test_pcx_image_decode_expands_runs_and_skips_padding() {
+        let mut pcx: Vec<u8> = test_image().encode();
+        // Two 4 byte lines, the first a run of 4
+        pcx[66] = 4;
+        pcx.splice(128..137, [0xC4, 9, 1, 2, 3, 0]);
+
+        let image: PcxImage = PcxImage::decode(&pcx).unwrap();
+
+        assert_eq!(image.indices, vec![9, 9, 9, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_pcx_image_decode_returns_error_for_invalid_data() {
+        assert!(matches!(
+            PcxImage::decode(&[0x0A, 5, 1, 8]),
+            Err(ImageError::InvalidPcx(_))
+        ));
+
+        let mut pcx: Vec<u8> = test_image().encode();
+        pcx[0] = 0;
+        assert!(matches!(
+            PcxImage::decode(&pcx),
+            Err(ImageError::InvalidPcx(_))
+        ));
+    }
+}
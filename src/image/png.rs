@@ -0,0 +1,502 @@
+use crate::image::{zlib, ImageError};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+const COLOR_TYPE_GRAYSCALE: u8 = 0;
+const COLOR_TYPE_RGB: u8 = 2;
+const COLOR_TYPE_INDEXED: u8 = 3;
+const COLOR_TYPE_GRAYSCALE_ALPHA: u8 = 4;
+const COLOR_TYPE_RGBA: u8 = 6;
+const FILTER_TYPE_NONE: u8 = 0;
+const FILTER_TYPE_SUB: u8 = 1;
+const FILTER_TYPE_UP: u8 = 2;
+const FILTER_TYPE_AVERAGE: u8 = 3;
+const FILTER_TYPE_PAETH: u8 = 4;
+
+const CRC_TABLE: [u32; 256] = create_crc_table();
+
+const fn create_crc_table() -> [u32; 256] {
+    let mut table: [u32; 256] = [0; 256];
+    let mut index: usize = 0;
+
+    while index < 256 {
+        let mut crc: u32 = index as u32;
+        let mut bit: usize = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xEDB88320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[index] = crc;
+        index += 1;
+    }
+
+    table
+}
+
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(u32::MAX, |crc, byte| {
+        CRC_TABLE[((crc ^ u32::from(*byte)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PngColor {
+    Indexed {
+        palette: Vec<[u8; 3]>,
+        indices: Vec<u8>,
+    },
+    Rgba(Vec<[u8; 4]>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PngImage {
+    pub width: u32,
+    pub height: u32,
+    pub color: PngColor,
+    // Stored in a grAb chunk, the same way other Doom editing tools
+    // keep a pictures left/top offsets inside a PNG
+    pub grab_offset: Option<(i32, i32)>,
+}
+
+impl PngImage {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut png: Vec<u8> = Vec::from(PNG_SIGNATURE);
+
+        let color_type: u8 = match self.color {
+            PngColor::Indexed { .. } => COLOR_TYPE_INDEXED,
+            PngColor::Rgba(_) => COLOR_TYPE_RGBA,
+        };
+
+        let mut header: Vec<u8> = Vec::with_capacity(13);
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        // Bit depth, color type, compression method, filter method, interlace method
+        header.extend_from_slice(&[8, color_type, 0, 0, 0]);
+        write_chunk(&mut png, b"IHDR", &header);
+
+        if let Some((x_offset, y_offset)) = self.grab_offset {
+            let mut grab: Vec<u8> = Vec::with_capacity(8);
+            grab.extend_from_slice(&x_offset.to_be_bytes());
+            grab.extend_from_slice(&y_offset.to_be_bytes());
+            write_chunk(&mut png, b"grAb", &grab);
+        }
+
+        let width: usize = self.width as usize;
+        let mut scanlines: Vec<u8> = Vec::new();
+
+        match &self.color {
+            PngColor::Indexed { palette, indices } => {
+                let palette_data: Vec<u8> = palette.iter().flatten().copied().collect();
+                write_chunk(&mut png, b"PLTE", &palette_data);
+
+                for row in indices.chunks(width.max(1)) {
+                    scanlines.push(FILTER_TYPE_NONE);
+                    scanlines.extend_from_slice(row);
+                }
+            }
+            PngColor::Rgba(pixels) => {
+                for row in pixels.chunks(width.max(1)) {
+                    scanlines.push(FILTER_TYPE_NONE);
+                    scanlines.extend(row.iter().flatten());
+                }
+            }
+        }
+
+        write_chunk(&mut png, b"IDAT", &zlib::compress_stored(&scanlines));
+        write_chunk(&mut png, b"IEND", &[]);
+
+        png
+    }
+
+    // Only 8 bit non interlaced images are supported, which
+    // covers everything the common image editors write by default
+    pub fn decode(png: &[u8]) -> Result<Self, ImageError> {
+        if !png.starts_with(&PNG_SIGNATURE) {
+            return Err(ImageError::InvalidPng(String::from(
+                "Missing PNG signature",
+            )));
+        }
+
+        let mut position: usize = PNG_SIGNATURE.len();
+        let mut header: Option<&[u8]> = None;
+        let mut palette: Vec<[u8; 3]> = Vec::new();
+        let mut transparency: Vec<u8> = Vec::new();
+        let mut grab_offset: Option<(i32, i32)> = None;
+        let mut image_data: Vec<u8> = Vec::new();
+
+        loop {
+            let chunk_header: &[u8] = png
+                .get(position..position + 8)
+                .ok_or_else(|| ImageError::InvalidPng(String::from("Missing IEND chunk")))?;
+            let length: usize = u32::from_be_bytes(chunk_header[0..4].try_into().unwrap()) as usize;
+            let chunk_type: &[u8] = &chunk_header[4..8];
+
+            let chunk_end: usize = position + 8 + length;
+            let data: &[u8] = png.get(position + 8..chunk_end).ok_or_else(|| {
+                ImageError::InvalidPng(format!(
+                    "Chunk {} is truncated",
+                    String::from_utf8_lossy(chunk_type)
+                ))
+            })?;
+            let crc: [u8; 4] = png
+                .get(chunk_end..chunk_end + 4)
+                .ok_or_else(|| ImageError::InvalidPng(String::from("Missing chunk CRC")))?
+                .try_into()
+                .unwrap();
+
+            if crc32(&png[position + 4..chunk_end]) != u32::from_be_bytes(crc) {
+                return Err(ImageError::InvalidPng(format!(
+                    "CRC mismatch in chunk {}",
+                    String::from_utf8_lossy(chunk_type)
+                )));
+            }
+
+            match chunk_type {
+                b"IHDR" if length == 13 => header = Some(data),
+                b"PLTE" => {
+                    palette = data
+                        .chunks_exact(3)
+                        .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+                        .collect()
+                }
+                b"tRNS" => transparency = data.to_vec(),
+                b"grAb" if length == 8 => {
+                    grab_offset = Some((
+                        i32::from_be_bytes(data[0..4].try_into().unwrap()),
+                        i32::from_be_bytes(data[4..8].try_into().unwrap()),
+                    ))
+                }
+                b"IDAT" => image_data.extend_from_slice(data),
+                b"IEND" => break,
+                _ => (),
+            }
+
+            position = chunk_end + 4;
+        }
+
+        let header: &[u8] =
+            header.ok_or_else(|| ImageError::InvalidPng(String::from("Missing IHDR chunk")))?;
+        let width: u32 = u32::from_be_bytes(header[0..4].try_into().unwrap());
+        let height: u32 = u32::from_be_bytes(header[4..8].try_into().unwrap());
+        let bit_depth: u8 = header[8];
+        let color_type: u8 = header[9];
+        let interlace_method: u8 = header[12];
+
+        if bit_depth != 8 {
+            return Err(ImageError::UnsupportedPng(format!(
+                "Bit depth {} is not supported, only 8",
+                bit_depth
+            )));
+        }
+
+        if interlace_method != 0 {
+            return Err(ImageError::UnsupportedPng(String::from(
+                "Interlaced images are not supported",
+            )));
+        }
+
+        let channels: usize = match color_type {
+            COLOR_TYPE_GRAYSCALE | COLOR_TYPE_INDEXED => 1,
+            COLOR_TYPE_GRAYSCALE_ALPHA => 2,
+            COLOR_TYPE_RGB => 3,
+            COLOR_TYPE_RGBA => 4,
+            _ => {
+                return Err(ImageError::InvalidPng(format!(
+                    "Invalid color type {}",
+                    color_type
+                )))
+            }
+        };
+
+        let pixels: Vec<u8> = unfilter(
+            &zlib::decompress(&image_data)?,
+            width as usize,
+            height as usize,
+            channels,
+        )?;
+
+        let color: PngColor = match color_type {
+            COLOR_TYPE_INDEXED => {
+                if pixels
+                    .iter()
+                    .any(|index| usize::from(*index) >= palette.len())
+                {
+                    return Err(ImageError::InvalidPng(String::from(
+                        "Palette index out of range",
+                    )));
+                }
+
+                if transparency.is_empty() {
+                    PngColor::Indexed {
+                        palette,
+                        indices: pixels,
+                    }
+                } else {
+                    PngColor::Rgba(
+                        pixels
+                            .iter()
+                            .map(|index| {
+                                let [red, green, blue]: [u8; 3] = palette[usize::from(*index)];
+                                let alpha: u8 = transparency
+                                    .get(usize::from(*index))
+                                    .copied()
+                                    .unwrap_or(255);
+                                [red, green, blue, alpha]
+                            })
+                            .collect(),
+                    )
+                }
+            }
+            COLOR_TYPE_GRAYSCALE => PngColor::Rgba(
+                pixels
+                    .iter()
+                    .map(|gray| [*gray, *gray, *gray, 255])
+                    .collect(),
+            ),
+            COLOR_TYPE_GRAYSCALE_ALPHA => PngColor::Rgba(
+                pixels
+                    .chunks_exact(2)
+                    .map(|pixel| [pixel[0], pixel[0], pixel[0], pixel[1]])
+                    .collect(),
+            ),
+            COLOR_TYPE_RGB => PngColor::Rgba(
+                pixels
+                    .chunks_exact(3)
+                    .map(|pixel| [pixel[0], pixel[1], pixel[2], 255])
+                    .collect(),
+            ),
+            _ => PngColor::Rgba(
+                pixels
+                    .chunks_exact(4)
+                    .map(|pixel| [pixel[0], pixel[1], pixel[2], pixel[3]])
+                    .collect(),
+            ),
+        };
+
+        Ok(PngImage {
+            width,
+            height,
+            color,
+            grab_offset,
+        })
+    }
+
+    pub fn to_rgba(&self) -> Vec<[u8; 4]> {
+        match &self.color {
+            PngColor::Indexed { palette, indices } => indices
+                .iter()
+                .map(|index| {
+                    let [red, green, blue]: [u8; 3] = palette[usize::from(*index)];
+                    [red, green, blue, 255]
+                })
+                .collect(),
+            PngColor::Rgba(pixels) => pixels.clone(),
+        }
+    }
+}
+
+fn unfilter(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    bytes_per_pixel: usize,
+) -> Result<Vec<u8>, ImageError> {
+    let row_size: usize = width * bytes_per_pixel;
+
+    if data.len() < (row_size + 1) * height {
+        return Err(ImageError::InvalidPng(String::from(
+            "Not enough image data",
+        )));
+    }
+
+    let mut pixels: Vec<u8> = vec![0; row_size * height];
+
+    for y in 0..height {
+        let filter_type: u8 = data[y * (row_size + 1)];
+        let row_start: usize = y * (row_size + 1) + 1;
+
+        for x in 0..row_size {
+            let current: u8 = data[row_start + x];
+            let left: u8 = if x >= bytes_per_pixel {
+                pixels[y * row_size + x - bytes_per_pixel]
+            } else {
+                0
+            };
+            let up: u8 = if y > 0 {
+                pixels[(y - 1) * row_size + x]
+            } else {
+                0
+            };
+            let up_left: u8 = if y > 0 && x >= bytes_per_pixel {
+                pixels[(y - 1) * row_size + x - bytes_per_pixel]
+            } else {
+                0
+            };
+
+            pixels[y * row_size + x] = match filter_type {
+                FILTER_TYPE_NONE => current,
+                FILTER_TYPE_SUB => current.wrapping_add(left),
+                FILTER_TYPE_UP => current.wrapping_add(up),
+                FILTER_TYPE_AVERAGE => {
+                    current.wrapping_add(((u16::from(left) + u16::from(up)) / 2) as u8)
+                }
+                FILTER_TYPE_PAETH => current.wrapping_add(paeth_predictor(left, up, up_left)),
+                _ => {
+                    return Err(ImageError::InvalidPng(format!(
+                        "Invalid filter type {}",
+                        filter_type
+                    )))
+                }
+            };
+        }
+    }
+
+    Ok(pixels)
+}
+
+fn paeth_predictor(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate: i16 = i16::from(left) + i16::from(up) - i16::from(up_left);
+    let left_distance: i16 = (estimate - i16::from(left)).abs();
+    let up_distance: i16 = (estimate - i16::from(up)).abs();
+    let up_left_distance: i16 = (estimate - i16::from(up_left)).abs();
+
+    if left_distance <= up_distance && left_distance <= up_left_distance {
+        left
+    } else if up_distance <= up_left_distance {
+        up
+    } else {
+        up_left
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    let crc_start: usize = png.len();
+    png.extend_from_slice(chunk_type);
+    png.extend_from_slice(data);
+
+    let crc: u32 = crc32(&png[crc_start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::png::{crc32, PngColor, PngImage};
+    use crate::image::ImageError;
+
+    #[test]
+    fn test_crc32_matches_known_value() {
+        assert_eq!(crc32(b"IEND"), 0xAE426082);
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
+
+    #[test]
+    fn test_png_image_encode_writes_chunks() {
+        let png_image: PngImage = PngImage {
+            width: 2,
+            height: 1,
+            color: PngColor::Indexed {
+                palette: vec![[0, 0, 0], [255, 255, 255]],
+                indices: vec![0, 1],
+            },
+            grab_offset: Some((-3, 4)),
+        };
+
+        let png: Vec<u8> = png_image.encode();
+
+        assert_eq!(
+            &png[0..8],
+            &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]
+        );
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[37..41], b"grAb");
+        assert_eq!(&png[41..49], &[0xFF, 0xFF, 0xFF, 0xFD, 0, 0, 0, 4]);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn test_png_image_encode_decode_round_trip() {
+        let png_images: Vec<PngImage> = vec![
+            PngImage {
+                width: 3,
+                height: 2,
+                color: PngColor::Indexed {
+                    palette: vec![[0, 0, 0], [255, 0, 0], [0, 0, 255]],
+                    indices: vec![0, 1, 2, 2, 1, 0],
+                },
+                grab_offset: None,
+            },
+            PngImage {
+                width: 2,
+                height: 2,
+                color: PngColor::Rgba(vec![
+                    [1, 2, 3, 255],
+                    [0, 0, 0, 0],
+                    [4, 5, 6, 128],
+                    [7, 8, 9, 255],
+                ]),
+                grab_offset: Some((-12, 30)),
+            },
+        ];
+
+        for png_image in png_images {
+            assert_eq!(PngImage::decode(&png_image.encode()).unwrap(), png_image);
+        }
+    }
+
+    #[test]
+    fn test_png_image_decode_unfilters_compressed_rgb_image() {
+        // 3x2 RGB image written by zlib with a Sub and a Paeth filtered row
+        let png: [u8; 74] = [
+            137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 3, 0, 0, 0, 2,
+            8, 2, 0, 0, 0, 18, 22, 241, 77, 0, 0, 0, 17, 73, 68, 65, 84, 120, 218, 99, 228, 18,
+            145, 131, 0, 22, 86, 24, 0, 0, 14, 211, 1, 35, 215, 170, 33, 206, 0, 0, 0, 0, 73, 69,
+            78, 68, 174, 66, 96, 130,
+        ];
+
+        let png_image: PngImage = PngImage::decode(&png).unwrap();
+
+        assert_eq!(
+            png_image.to_rgba(),
+            vec![
+                [10, 20, 30, 255],
+                [40, 50, 60, 255],
+                [70, 80, 90, 255],
+                [15, 25, 35, 255],
+                [45, 55, 65, 255],
+                [75, 85, 95, 255],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_png_image_decode_returns_error_for_invalid_data() {
+        assert_eq!(
+            PngImage::decode(b"GIF89a"),
+            Err(ImageError::InvalidPng(String::from(
+                "Missing PNG signature"
+            )))
+        );
+
+        let mut png: Vec<u8> = PngImage {
+            width: 1,
+            height: 1,
+            color: PngColor::Rgba(vec![[0, 0, 0, 0]]),
+            grab_offset: None,
+        }
+        .encode();
+        png[20] ^= 0xFF;
+        assert_eq!(
+            PngImage::decode(&png),
+            Err(ImageError::InvalidPng(String::from(
+                "CRC mismatch in chunk IHDR"
+            )))
+        );
+    }
+}
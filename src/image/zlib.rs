@@ -0,0 +1,433 @@
+use crate::image::ImageError;
+
+// Max size of a stored(uncompressed) deflate block
+const DEFLATE_MAX_STORED_BLOCK_SIZE: usize = 65535;
+const DEFLATE_MAX_CODE_LENGTH: usize = 15;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+// Order the code length code lengths are stored in for dynamic blocks
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+pub fn adler32(data: &[u8]) -> u32 {
+    let (a, b): (u32, u32) = data.iter().fold((1, 0), |(a, b), byte| {
+        let a: u32 = (a + u32::from(*byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+
+    (b << 16) | a
+}
+
+// Wraps the data in a zlib stream made of uncompressed deflate blocks.
+// Our images are tiny so it isn't worth compressing them
+pub fn compress_stored(data: &[u8]) -> Vec<u8> {
+    // Deflate with a 32K window, no preset dictionary
+    let mut zlib: Vec<u8> = vec![0x78, 0x01];
+    let mut blocks = data.chunks(DEFLATE_MAX_STORED_BLOCK_SIZE).peekable();
+
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let is_final_block: u8 = u8::from(blocks.peek().is_none());
+        let length: u16 = block.len() as u16;
+
+        zlib.push(is_final_block);
+        zlib.extend_from_slice(&length.to_le_bytes());
+        zlib.extend_from_slice(&(!length).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+
+    zlib.extend_from_slice(&adler32(data).to_be_bytes());
+    zlib
+}
+
+pub fn decompress(zlib: &[u8]) -> Result<Vec<u8>, ImageError> {
+    if zlib.len() < 6 {
+        return Err(ImageError::InvalidZlib(String::from("Stream is too short")));
+    }
+
+    let compression_method: u8 = zlib[0] & 0x0F;
+    let header_check: u16 = u16::from_be_bytes([zlib[0], zlib[1]]);
+
+    if compression_method != 8 || !header_check.is_multiple_of(31) {
+        return Err(ImageError::InvalidZlib(String::from("Invalid header")));
+    }
+
+    if zlib[1] & 0x20 != 0 {
+        return Err(ImageError::InvalidZlib(String::from(
+            "Preset dictionaries are not supported",
+        )));
+    }
+
+    let mut bit_reader: BitReader = BitReader::new(&zlib[2..]);
+    let data: Vec<u8> = inflate(&mut bit_reader)?;
+
+    let checksum_start: usize = 2 + bit_reader.byte_position();
+    let checksum: [u8; 4] = zlib
+        .get(checksum_start..checksum_start + 4)
+        .ok_or_else(|| ImageError::InvalidZlib(String::from("Missing checksum")))?
+        .try_into()
+        .unwrap();
+
+    if u32::from_be_bytes(checksum) != adler32(&data) {
+        return Err(ImageError::InvalidZlib(String::from("Checksum mismatch")));
+    }
+
+    Ok(data)
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    bit_buffer: u32,
+    bit_count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        BitReader {
+            data,
+            position: 0,
+            bit_buffer: 0,
+            bit_count: 0,
+        }
+    }
+
+    fn bits(&mut self, count: u32) -> Result<u32, ImageError> {
+        while self.bit_count < count {
+            let byte: u8 = *self
+                .data
+                .get(self.position)
+                .ok_or_else(|| ImageError::InvalidZlib(String::from("Unexpected end of data")))?;
+            self.bit_buffer |= u32::from(byte) << self.bit_count;
+            self.bit_count += 8;
+            self.position += 1;
+        }
+
+        let value: u32 = self.bit_buffer & ((1u64 << count) - 1) as u32;
+        self.bit_buffer >>= count;
+        self.bit_count -= count;
+
+        Ok(value)
+    }
+
+    // Stored blocks start on a byte boundary
+    fn align_to_byte(&mut self) {
+        self.bit_buffer = 0;
+        self.bit_count = 0;
+    }
+
+    fn byte_position(&self) -> usize {
+        self.position - (self.bit_count / 8) as usize
+    }
+}
+
+// Canonical huffman table stored as the number of codes of each length
+// and the symbols ordered by code
+struct Huffman {
+    counts: [u16; DEFLATE_MAX_CODE_LENGTH + 1],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts: [u16; DEFLATE_MAX_CODE_LENGTH + 1] = [0; DEFLATE_MAX_CODE_LENGTH + 1];
+        for length in lengths {
+            counts[usize::from(*length)] += 1;
+        }
+        counts[0] = 0;
+
+        let mut offsets: [u16; DEFLATE_MAX_CODE_LENGTH + 2] = [0; DEFLATE_MAX_CODE_LENGTH + 2];
+        for length in 1..=DEFLATE_MAX_CODE_LENGTH {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+
+        let mut symbols: Vec<u16> = vec![0; lengths.len()];
+        for (symbol, length) in lengths.iter().enumerate() {
+            if *length != 0 {
+                let offset: &mut u16 = &mut offsets[usize::from(*length)];
+                symbols[usize::from(*offset)] = symbol as u16;
+                *offset += 1;
+            }
+        }
+
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, bit_reader: &mut BitReader) -> Result<u16, ImageError> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+
+        for length in 1..=DEFLATE_MAX_CODE_LENGTH {
+            code |= bit_reader.bits(1)? as i32;
+            let count: i32 = i32::from(self.counts[length]);
+
+            if code - count < first {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err(ImageError::InvalidZlib(String::from(
+            "Invalid huffman code",
+        )))
+    }
+}
+
+fn inflate(bit_reader: &mut BitReader) -> Result<Vec<u8>, ImageError> {
+    let mut output: Vec<u8> = Vec::new();
+
+    loop {
+        let is_final_block: bool = bit_reader.bits(1)? == 1;
+        let block_type: u32 = bit_reader.bits(2)?;
+
+        match block_type {
+            0 => inflate_stored_block(bit_reader, &mut output)?,
+            1 => {
+                let (literal_lengths, distance_lengths): (Huffman, Huffman) = fixed_huffman();
+                inflate_huffman_block(bit_reader, &mut output, &literal_lengths, &distance_lengths)?
+            }
+            2 => {
+                let (literal_lengths, distance_lengths): (Huffman, Huffman) =
+                    dynamic_huffman(bit_reader)?;
+                inflate_huffman_block(bit_reader, &mut output, &literal_lengths, &distance_lengths)?
+            }
+            _ => return Err(ImageError::InvalidZlib(String::from("Invalid block type"))),
+        }
+
+        if is_final_block {
+            return Ok(output);
+        }
+    }
+}
+
+fn inflate_stored_block(
+    bit_reader: &mut BitReader,
+    output: &mut Vec<u8>,
+) -> Result<(), ImageError> {
+    bit_reader.align_to_byte();
+
+    let length: u32 = bit_reader.bits(16)?;
+    let length_complement: u32 = bit_reader.bits(16)?;
+
+    if length != !length_complement & 0xFFFF {
+        return Err(ImageError::InvalidZlib(String::from(
+            "Stored block length mismatch",
+        )));
+    }
+
+    let start: usize = bit_reader.position;
+    let block: &[u8] = bit_reader
+        .data
+        .get(start..start + length as usize)
+        .ok_or_else(|| ImageError::InvalidZlib(String::from("Stored block is truncated")))?;
+
+    output.extend_from_slice(block);
+    bit_reader.position += length as usize;
+
+    Ok(())
+}
+
+fn fixed_huffman() -> (Huffman, Huffman) {
+    let mut literal_lengths: [u8; 288] = [8; 288];
+    literal_lengths[144..256].fill(9);
+    literal_lengths[256..280].fill(7);
+
+    (Huffman::new(&literal_lengths), Huffman::new(&[5; 30]))
+}
+
+fn dynamic_huffman(bit_reader: &mut BitReader) -> Result<(Huffman, Huffman), ImageError> {
+    let literal_count: usize = bit_reader.bits(5)? as usize + 257;
+    let distance_count: usize = bit_reader.bits(5)? as usize + 1;
+    let code_length_count: usize = bit_reader.bits(4)? as usize + 4;
+
+    let mut code_length_lengths: [u8; 19] = [0; 19];
+    for index in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+        code_length_lengths[*index] = bit_reader.bits(3)? as u8;
+    }
+    let code_length_huffman: Huffman = Huffman::new(&code_length_lengths);
+
+    let mut lengths: Vec<u8> = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let symbol: u16 = code_length_huffman.decode(bit_reader)?;
+
+        let (length, repeat): (u8, u32) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous: u8 = *lengths.last().ok_or_else(|| {
+                    ImageError::InvalidZlib(String::from("Repeat with no previous length"))
+                })?;
+                (previous, 3 + bit_reader.bits(2)?)
+            }
+            17 => (0, 3 + bit_reader.bits(3)?),
+            _ => (0, 11 + bit_reader.bits(7)?),
+        };
+
+        for _ in 0..repeat {
+            lengths.push(length);
+        }
+    }
+
+    if lengths.len() > literal_count + distance_count {
+        return Err(ImageError::InvalidZlib(String::from(
+            "Too many code lengths",
+        )));
+    }
+
+    Ok((
+        Huffman::new(&lengths[..literal_count]),
+        Huffman::new(&lengths[literal_count..]),
+    ))
+}
+
+fn inflate_huffman_block(
+    bit_reader: &mut BitReader,
+    output: &mut Vec<u8>,
+    literal_huffman: &Huffman,
+    distance_huffman: &Huffman,
+) -> Result<(), ImageError> {
+    loop {
+        let symbol: usize = usize::from(literal_huffman.decode(bit_reader)?);
+
+        if symbol < 256 {
+            output.push(symbol as u8);
+            continue;
+        }
+
+        if symbol == 256 {
+            return Ok(());
+        }
+
+        let length_index: usize = symbol - 257;
+        if length_index >= LENGTH_BASE.len() {
+            return Err(ImageError::InvalidZlib(String::from("Invalid length code")));
+        }
+
+        let length: usize = usize::from(LENGTH_BASE[length_index])
+            + bit_reader.bits(u32::from(LENGTH_EXTRA_BITS[length_index]))? as usize;
+
+        let distance_index: usize = usize::from(distance_huffman.decode(bit_reader)?);
+        if distance_index >= DISTANCE_BASE.len() {
+            return Err(ImageError::InvalidZlib(String::from(
+                "Invalid distance code",
+            )));
+        }
+
+        let distance: usize = usize::from(DISTANCE_BASE[distance_index])
+            + bit_reader.bits(u32::from(DISTANCE_EXTRA_BITS[distance_index]))? as usize;
+
+        if distance > output.len() {
+            return Err(ImageError::InvalidZlib(String::from(
+                "Distance goes past the start of the data",
+            )));
+        }
+
+        // Copies can overlap the bytes they are producing
+        let copy_start: usize = output.len() - distance;
+        for index in 0..length {
+            output.push(output[copy_start + index]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::zlib::{adler32, compress_stored, decompress};
+    use crate::image::ImageError;
+
+    #[test]
+    fn test_adler32_matches_known_value() {
+        assert_eq!(adler32(b"Wikipedia"), 0x11E60398);
+    }
+
+    #[test]
+    fn test_compress_stored_splits_large_data_into_blocks() {
+        let data: Vec<u8> = vec![7; 70000];
+        let zlib: Vec<u8> = compress_stored(&data);
+
+        // 2 byte header, 2 block headers of 5 bytes and a 4 byte checksum
+        assert_eq!(zlib.len(), data.len() + 2 + 10 + 4);
+        assert_eq!(zlib[2], 0);
+        assert_eq!(zlib[2 + 5 + 65535], 1);
+    }
+
+    #[test]
+    fn test_decompress_stored_round_trip() {
+        let data: Vec<u8> = (0..70000).map(|x| (x % 251) as u8).collect();
+
+        assert_eq!(decompress(&compress_stored(&data)).unwrap(), data);
+        assert_eq!(decompress(&compress_stored(&[])).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_decompress_fixed_huffman_block() {
+        let zlib: [u8; 16] = [
+            120, 218, 203, 72, 205, 201, 201, 87, 200, 64, 39, 1, 104, 3, 8, 177,
+        ];
+
+        assert_eq!(decompress(&zlib).unwrap(), b"hello hello hello hello");
+    }
+
+    #[test]
+    fn test_decompress_dynamic_huffman_block() {
+        let zlib: [u8; 160] = [
+            120, 218, 21, 143, 11, 10, 0, 48, 8, 66, 207, 106, 101, 31, 239, 127, 128, 57, 70, 48,
+            76, 94, 90, 186, 108, 173, 66, 88, 94, 213, 222, 237, 100, 229, 13, 34, 5, 130, 171,
+            153, 209, 102, 7, 114, 139, 195, 185, 81, 159, 70, 213, 160, 210, 95, 69, 137, 140,
+            216, 141, 142, 73, 112, 38, 32, 225, 251, 12, 53, 37, 66, 135, 26, 138, 137, 238, 206,
+            153, 53, 229, 172, 114, 62, 211, 182, 254, 118, 143, 236, 216, 110, 113, 33, 160, 185,
+            148, 58, 253, 44, 241, 115, 211, 72, 150, 25, 134, 118, 215, 102, 116, 105, 89, 129,
+            171, 246, 182, 45, 43, 63, 115, 219, 105, 53, 112, 33, 172, 110, 28, 101, 255, 165, 10,
+            49, 10, 246, 143, 79, 108, 228, 177, 112, 174, 172, 104, 211, 23, 93, 21, 23, 89, 78,
+            235, 250, 96, 81, 94, 63, 48, 123, 119, 55,
+        ];
+
+        let data: Vec<u8> = decompress(&zlib).unwrap();
+
+        assert_eq!(data.len(), 300);
+        assert!(data.starts_with(b"djicfjhjbjaheiddhiih"));
+        assert!(data.ends_with(b"ahjgaedejiig"));
+    }
+
+    #[test]
+    fn test_decompress_returns_error_for_invalid_data() {
+        assert_eq!(
+            decompress(&[0x78]),
+            Err(ImageError::InvalidZlib(String::from("Stream is too short")))
+        );
+
+        assert_eq!(
+            decompress(&[0x78, 0x02, 0, 0, 0, 0]),
+            Err(ImageError::InvalidZlib(String::from("Invalid header")))
+        );
+
+        let mut bad_checksum: Vec<u8> = compress_stored(b"test");
+        let length: usize = bad_checksum.len();
+        bad_checksum[length - 1] ^= 0xFF;
+        assert_eq!(
+            decompress(&bad_checksum),
+            Err(ImageError::InvalidZlib(String::from("Checksum mismatch")))
+        );
+    }
+}
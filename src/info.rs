@@ -0,0 +1,237 @@
+// Port of the original info.c/info.h. The tables are in the sub modules and
+// use the same names as the original, S_PLAY_RUN1, MT_TROOP, SPR_PISG, etc so
+// they can be compared against the original source and DeHackEd patches
+use crate::fixed::Fixed;
+use crate::sound::sfx::SfxNum;
+
+pub mod mobjinfo;
+pub mod sprites;
+pub mod states;
+pub mod weaponinfo;
+
+pub type SpriteNum = usize;
+pub type StateNum = usize;
+pub type MobjType = usize;
+
+// Frame numbers with this set are drawn at full brightness
+pub const FF_FULLBRIGHT: i32 = 0x8000;
+pub const FF_FRAMEMASK: i32 = 0x7fff;
+
+// The action functions states can call when they're entered. The weapon ones
+// are called on the player sprite instead of a mobj
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Light0,
+    WeaponReady,
+    Lower,
+    Raise,
+    Punch,
+    ReFire,
+    FirePistol,
+    Light1,
+    FireShotgun,
+    Light2,
+    FireShotgun2,
+    CheckReload,
+    OpenShotgun2,
+    LoadShotgun2,
+    CloseShotgun2,
+    FireCGun,
+    GunFlash,
+    FireMissile,
+    Saw,
+    FirePlasma,
+    BfgSound,
+    FireBfg,
+    BfgSpray,
+    Explode,
+    Pain,
+    PlayerScream,
+    Fall,
+    XScream,
+    Look,
+    Chase,
+    FaceTarget,
+    PosAttack,
+    Scream,
+    SPosAttack,
+    VileChase,
+    VileStart,
+    VileTarget,
+    VileAttack,
+    StartFire,
+    Fire,
+    FireCrackle,
+    Tracer,
+    SkelWhoosh,
+    SkelFist,
+    SkelMissile,
+    FatRaise,
+    FatAttack1,
+    FatAttack2,
+    FatAttack3,
+    BossDeath,
+    CPosAttack,
+    CPosRefire,
+    TroopAttack,
+    SargAttack,
+    HeadAttack,
+    BruisAttack,
+    SkullAttack,
+    Metal,
+    SpidRefire,
+    BabyMetal,
+    BspiAttack,
+    Hoof,
+    CyberAttack,
+    PainAttack,
+    PainDie,
+    KeenDie,
+    BrainPain,
+    BrainScream,
+    BrainDie,
+    BrainAwake,
+    BrainSpit,
+    SpawnSound,
+    SpawnFly,
+    BrainExplode,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct State {
+    pub sprite: SpriteNum,
+    // Frame letter, A is 0. Can have FF_FULLBRIGHT set
+    pub frame: i32,
+    // -1 stays in the state forever
+    pub tics: i32,
+    pub action: Action,
+    pub next_state: StateNum,
+    pub misc1: i32,
+    pub misc2: i32,
+}
+
+impl State {
+    const fn new(
+        sprite: SpriteNum,
+        frame: i32,
+        tics: i32,
+        action: Action,
+        next_state: StateNum,
+    ) -> Self {
+        State {
+            sprite,
+            frame,
+            tics,
+            action,
+            next_state,
+            misc1: 0,
+            misc2: 0,
+        }
+    }
+
+    pub fn is_full_bright(&self) -> bool {
+        self.frame & FF_FULLBRIGHT != 0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MobjInfo {
+    // Thing type number used in the map THINGS lump, -1 if it can't be placed
+    pub doomed_num: i32,
+    pub spawn_state: StateNum,
+    pub spawn_health: i32,
+    pub see_state: StateNum,
+    pub see_sound: SfxNum,
+    pub reaction_time: i32,
+    pub attack_sound: SfxNum,
+    pub pain_state: StateNum,
+    // Out of 256
+    pub pain_chance: i32,
+    pub pain_sound: SfxNum,
+    pub melee_state: StateNum,
+    pub missile_state: StateNum,
+    pub death_state: StateNum,
+    pub xdeath_state: StateNum,
+    pub death_sound: SfxNum,
+    // Map units per step for monsters, fixed point per tic for missiles
+    pub speed: i32,
+    pub radius: Fixed,
+    pub height: Fixed,
+    pub mass: i32,
+    pub damage: i32,
+    pub active_sound: SfxNum,
+    // MF_* flags from play::mobj
+    pub flags: u32,
+    pub raise_state: StateNum,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::info::mobjinfo::*;
+    use crate::info::sprites::*;
+    use crate::info::states::*;
+    use crate::info::{Action, MobjType, FF_FRAMEMASK};
+    use crate::play::mobj::{MF_COUNTKILL, MF_MISSILE, MF_SHOOTABLE, MF_SOLID};
+    use crate::sound::sfx::{SFX_BGSIT1, SFX_FIRSHT};
+
+    #[test]
+    fn test_tables_have_original_sizes() {
+        assert_eq!(NUMSPRITES, 138);
+        assert_eq!(NUMSTATES, 967);
+        assert_eq!(NUMMOBJTYPES, 137);
+        assert_eq!(SPRNAMES[SPR_TLP2], "TLP2");
+    }
+
+    #[test]
+    fn test_states_match_original() {
+        assert_eq!(S_PLAY, 149);
+        assert_eq!(S_TROO_STND, 442);
+        assert_eq!(STATES[S_TROO_STND].sprite, SPR_TROO);
+        assert_eq!(STATES[S_TROO_STND].action, Action::Look);
+        assert_eq!(STATES[S_TROO_STND].next_state, S_TROO_STND2);
+        assert_eq!(STATES[S_PISTOLFLASH].frame & FF_FRAMEMASK, 0);
+        assert!(STATES[S_PISTOLFLASH].is_full_bright());
+        assert!(!STATES[S_PISTOL].is_full_bright());
+        assert_eq!(STATES[S_NULL].tics, -1);
+    }
+
+    #[test]
+    fn test_states_only_point_at_valid_states_and_sprites() {
+        for state in STATES.iter() {
+            assert!(state.next_state < NUMSTATES);
+            assert!(state.sprite < NUMSPRITES);
+            // A state that lasts 0 tics and points back at itself would
+            // hang P_SetMobjState
+            assert!(state.tics != 0 || state.next_state != S_NULL || state.action != Action::None);
+        }
+    }
+
+    #[test]
+    fn test_mobjinfo_matches_original() {
+        let imp: MobjType = MT_TROOP;
+        assert_eq!(MOBJINFO[imp].doomed_num, 3001);
+        assert_eq!(MOBJINFO[imp].spawn_health, 60);
+        assert_eq!(MOBJINFO[imp].see_sound, SFX_BGSIT1);
+        assert_eq!(MOBJINFO[imp].radius, Fixed(20 * FRACUNIT));
+        assert_eq!(MOBJINFO[imp].flags, MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL);
+        assert_eq!(MOBJINFO[MT_TROOPSHOT].speed, 10 * FRACUNIT);
+        assert_eq!(MOBJINFO[MT_TROOPSHOT].see_sound, SFX_FIRSHT);
+        assert_ne!(MOBJINFO[MT_TROOPSHOT].flags & MF_MISSILE, 0);
+        assert_eq!(MOBJINFO[MT_MISC86].doomed_num, 81);
+    }
+
+    #[test]
+    fn test_mobjinfo_doomed_nums_are_unique() {
+        let mut doomed_nums: Vec<i32> = MOBJINFO
+            .iter()
+            .map(|info| info.doomed_num)
+            .filter(|doomed_num| *doomed_num != -1)
+            .collect();
+        let count: usize = doomed_nums.len();
+        doomed_nums.sort();
+        doomed_nums.dedup();
+        assert_eq!(doomed_nums.len(), count);
+    }
+}
@@ -0,0 +1,3710 @@
+use crate::fixed::{Fixed, FRACUNIT};
+use crate::info::states::*;
+use crate::info::{MobjInfo, MobjType};
+use crate::play::mobj::*;
+use crate::sound::sfx::*;
+
+pub const MT_PLAYER: MobjType = 0;
+pub const MT_POSSESSED: MobjType = 1;
+pub const MT_SHOTGUY: MobjType = 2;
+pub const MT_VILE: MobjType = 3;
+pub const MT_FIRE: MobjType = 4;
+pub const MT_UNDEAD: MobjType = 5;
+pub const MT_TRACER: MobjType = 6;
+pub const MT_SMOKE: MobjType = 7;
+pub const MT_FATSO: MobjType = 8;
+pub const MT_FATSHOT: MobjType = 9;
+pub const MT_CHAINGUY: MobjType = 10;
+pub const MT_TROOP: MobjType = 11;
+pub const MT_SERGEANT: MobjType = 12;
+pub const MT_SHADOWS: MobjType = 13;
+pub const MT_HEAD: MobjType = 14;
+pub const MT_BRUISER: MobjType = 15;
+pub const MT_BRUISERSHOT: MobjType = 16;
+pub const MT_KNIGHT: MobjType = 17;
+pub const MT_SKULL: MobjType = 18;
+pub const MT_SPIDER: MobjType = 19;
+pub const MT_BABY: MobjType = 20;
+pub const MT_CYBORG: MobjType = 21;
+pub const MT_PAIN: MobjType = 22;
+pub const MT_WOLFSS: MobjType = 23;
+pub const MT_KEEN: MobjType = 24;
+pub const MT_BOSSBRAIN: MobjType = 25;
+pub const MT_BOSSSPIT: MobjType = 26;
+pub const MT_BOSSTARGET: MobjType = 27;
+pub const MT_SPAWNSHOT: MobjType = 28;
+pub const MT_SPAWNFIRE: MobjType = 29;
+pub const MT_BARREL: MobjType = 30;
+pub const MT_TROOPSHOT: MobjType = 31;
+pub const MT_HEADSHOT: MobjType = 32;
+pub const MT_ROCKET: MobjType = 33;
+pub const MT_PLASMA: MobjType = 34;
+pub const MT_BFG: MobjType = 35;
+pub const MT_ARACHPLAZ: MobjType = 36;
+pub const MT_PUFF: MobjType = 37;
+pub const MT_BLOOD: MobjType = 38;
+pub const MT_TFOG: MobjType = 39;
+pub const MT_IFOG: MobjType = 40;
+pub const MT_TELEPORTMAN: MobjType = 41;
+pub const MT_EXTRABFG: MobjType = 42;
+pub const MT_MISC0: MobjType = 43;
+pub const MT_MISC1: MobjType = 44;
+pub const MT_MISC2: MobjType = 45;
+pub const MT_MISC3: MobjType = 46;
+pub const MT_MISC4: MobjType = 47;
+pub const MT_MISC5: MobjType = 48;
+pub const MT_MISC6: MobjType = 49;
+pub const MT_MISC7: MobjType = 50;
+pub const MT_MISC8: MobjType = 51;
+pub const MT_MISC9: MobjType = 52;
+pub const MT_MISC10: MobjType = 53;
+pub const MT_MISC11: MobjType = 54;
+pub const MT_MISC12: MobjType = 55;
+pub const MT_INV: MobjType = 56;
+pub const MT_MISC13: MobjType = 57;
+pub const MT_INS: MobjType = 58;
+pub const MT_MISC14: MobjType = 59;
+pub const MT_MISC15: MobjType = 60;
+pub const MT_MISC16: MobjType = 61;
+pub const MT_MEGA: MobjType = 62;
+pub const MT_CLIP: MobjType = 63;
+pub const MT_MISC17: MobjType = 64;
+pub const MT_MISC18: MobjType = 65;
+pub const MT_MISC19: MobjType = 66;
+pub const MT_MISC20: MobjType = 67;
+pub const MT_MISC21: MobjType = 68;
+pub const MT_MISC22: MobjType = 69;
+pub const MT_MISC23: MobjType = 70;
+pub const MT_MISC24: MobjType = 71;
+pub const MT_MISC25: MobjType = 72;
+pub const MT_CHAINGUN: MobjType = 73;
+pub const MT_MISC26: MobjType = 74;
+pub const MT_MISC27: MobjType = 75;
+pub const MT_MISC28: MobjType = 76;
+pub const MT_SHOTGUN: MobjType = 77;
+pub const MT_SUPERSHOTGUN: MobjType = 78;
+pub const MT_MISC29: MobjType = 79;
+pub const MT_MISC30: MobjType = 80;
+pub const MT_MISC31: MobjType = 81;
+pub const MT_MISC32: MobjType = 82;
+pub const MT_MISC33: MobjType = 83;
+pub const MT_MISC34: MobjType = 84;
+pub const MT_MISC35: MobjType = 85;
+pub const MT_MISC36: MobjType = 86;
+pub const MT_MISC37: MobjType = 87;
+pub const MT_MISC38: MobjType = 88;
+pub const MT_MISC39: MobjType = 89;
+pub const MT_MISC40: MobjType = 90;
+pub const MT_MISC41: MobjType = 91;
+pub const MT_MISC42: MobjType = 92;
+pub const MT_MISC43: MobjType = 93;
+pub const MT_MISC44: MobjType = 94;
+pub const MT_MISC45: MobjType = 95;
+pub const MT_MISC46: MobjType = 96;
+pub const MT_MISC47: MobjType = 97;
+pub const MT_MISC48: MobjType = 98;
+pub const MT_MISC49: MobjType = 99;
+pub const MT_MISC50: MobjType = 100;
+pub const MT_MISC51: MobjType = 101;
+pub const MT_MISC52: MobjType = 102;
+pub const MT_MISC53: MobjType = 103;
+pub const MT_MISC54: MobjType = 104;
+pub const MT_MISC55: MobjType = 105;
+pub const MT_MISC56: MobjType = 106;
+pub const MT_MISC57: MobjType = 107;
+pub const MT_MISC58: MobjType = 108;
+pub const MT_MISC59: MobjType = 109;
+pub const MT_MISC60: MobjType = 110;
+pub const MT_MISC61: MobjType = 111;
+pub const MT_MISC62: MobjType = 112;
+pub const MT_MISC63: MobjType = 113;
+pub const MT_MISC64: MobjType = 114;
+pub const MT_MISC65: MobjType = 115;
+pub const MT_MISC66: MobjType = 116;
+pub const MT_MISC67: MobjType = 117;
+pub const MT_MISC68: MobjType = 118;
+pub const MT_MISC69: MobjType = 119;
+pub const MT_MISC70: MobjType = 120;
+pub const MT_MISC71: MobjType = 121;
+pub const MT_MISC72: MobjType = 122;
+pub const MT_MISC73: MobjType = 123;
+pub const MT_MISC74: MobjType = 124;
+pub const MT_MISC75: MobjType = 125;
+pub const MT_MISC76: MobjType = 126;
+pub const MT_MISC77: MobjType = 127;
+pub const MT_MISC78: MobjType = 128;
+pub const MT_MISC79: MobjType = 129;
+pub const MT_MISC80: MobjType = 130;
+pub const MT_MISC81: MobjType = 131;
+pub const MT_MISC82: MobjType = 132;
+pub const MT_MISC83: MobjType = 133;
+pub const MT_MISC84: MobjType = 134;
+pub const MT_MISC85: MobjType = 135;
+pub const MT_MISC86: MobjType = 136;
+
+pub const NUMMOBJTYPES: usize = 137;
+
+pub static MOBJINFO: [MobjInfo; NUMMOBJTYPES] = [
+    // MT_PLAYER
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_PLAY,
+        spawn_health: 100,
+        see_state: S_PLAY_RUN1,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_PLAY_PAIN,
+        pain_chance: 255,
+        pain_sound: SFX_PLPAIN,
+        melee_state: S_NULL,
+        missile_state: S_PLAY_ATK1,
+        death_state: S_PLAY_DIE1,
+        xdeath_state: S_PLAY_XDIE1,
+        death_sound: SFX_PLDETH,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_DROPOFF | MF_PICKUP | MF_NOTDMATCH,
+        raise_state: S_NULL,
+    },
+    // MT_POSSESSED
+    MobjInfo {
+        doomed_num: 3004,
+        spawn_state: S_POSS_STND,
+        spawn_health: 20,
+        see_state: S_POSS_RUN1,
+        see_sound: SFX_POSIT1,
+        reaction_time: 8,
+        attack_sound: SFX_PISTOL,
+        pain_state: S_POSS_PAIN,
+        pain_chance: 200,
+        pain_sound: SFX_POPAIN,
+        melee_state: S_NULL,
+        missile_state: S_POSS_ATK1,
+        death_state: S_POSS_DIE1,
+        xdeath_state: S_POSS_XDIE1,
+        death_sound: SFX_PODTH1,
+        speed: 8,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_POSACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_POSS_RAISE1,
+    },
+    // MT_SHOTGUY
+    MobjInfo {
+        doomed_num: 9,
+        spawn_state: S_SPOS_STND,
+        spawn_health: 30,
+        see_state: S_SPOS_RUN1,
+        see_sound: SFX_POSIT2,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_SPOS_PAIN,
+        pain_chance: 170,
+        pain_sound: SFX_POPAIN,
+        melee_state: S_NULL,
+        missile_state: S_SPOS_ATK1,
+        death_state: S_SPOS_DIE1,
+        xdeath_state: S_SPOS_XDIE1,
+        death_sound: SFX_PODTH2,
+        speed: 8,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_POSACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_SPOS_RAISE1,
+    },
+    // MT_VILE
+    MobjInfo {
+        doomed_num: 64,
+        spawn_state: S_VILE_STND,
+        spawn_health: 700,
+        see_state: S_VILE_RUN1,
+        see_sound: SFX_VILSIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_VILE_PAIN,
+        pain_chance: 10,
+        pain_sound: SFX_VIPAIN,
+        melee_state: S_NULL,
+        missile_state: S_VILE_ATK1,
+        death_state: S_VILE_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_VILDTH,
+        speed: 15,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 500,
+        damage: 0,
+        active_sound: SFX_VILACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_NULL,
+    },
+    // MT_FIRE
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_FIRE1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_UNDEAD
+    MobjInfo {
+        doomed_num: 66,
+        spawn_state: S_SKEL_STND,
+        spawn_health: 300,
+        see_state: S_SKEL_RUN1,
+        see_sound: SFX_SKESIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_SKEL_PAIN,
+        pain_chance: 100,
+        pain_sound: SFX_POPAIN,
+        melee_state: S_SKEL_FIST1,
+        missile_state: S_SKEL_MISS1,
+        death_state: S_SKEL_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_SKEDTH,
+        speed: 10,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 500,
+        damage: 0,
+        active_sound: SFX_SKEACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_SKEL_RAISE1,
+    },
+    // MT_TRACER
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_TRACER,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_SKEATK,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_TRACEEXP1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_BAREXP,
+        speed: 10 * FRACUNIT,
+        radius: Fixed(11 * FRACUNIT),
+        height: Fixed(8 * FRACUNIT),
+        mass: 100,
+        damage: 10,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_MISSILE | MF_DROPOFF | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_SMOKE
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_SMOKE1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_FATSO
+    MobjInfo {
+        doomed_num: 67,
+        spawn_state: S_FATT_STND,
+        spawn_health: 600,
+        see_state: S_FATT_RUN1,
+        see_sound: SFX_MANSIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_FATT_PAIN,
+        pain_chance: 80,
+        pain_sound: SFX_MNPAIN,
+        melee_state: S_NULL,
+        missile_state: S_FATT_ATK1,
+        death_state: S_FATT_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_MANDTH,
+        speed: 8,
+        radius: Fixed(48 * FRACUNIT),
+        height: Fixed(64 * FRACUNIT),
+        mass: 1000,
+        damage: 0,
+        active_sound: SFX_POSACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_FATT_RAISE1,
+    },
+    // MT_FATSHOT
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_FATSHOT1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_FIRSHT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_FATSHOTX1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_FIRXPL,
+        speed: 20 * FRACUNIT,
+        radius: Fixed(6 * FRACUNIT),
+        height: Fixed(8 * FRACUNIT),
+        mass: 100,
+        damage: 8,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_MISSILE | MF_DROPOFF | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_CHAINGUY
+    MobjInfo {
+        doomed_num: 65,
+        spawn_state: S_CPOS_STND,
+        spawn_health: 70,
+        see_state: S_CPOS_RUN1,
+        see_sound: SFX_POSIT2,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_CPOS_PAIN,
+        pain_chance: 170,
+        pain_sound: SFX_POPAIN,
+        melee_state: S_NULL,
+        missile_state: S_CPOS_ATK1,
+        death_state: S_CPOS_DIE1,
+        xdeath_state: S_CPOS_XDIE1,
+        death_sound: SFX_PODTH2,
+        speed: 8,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_POSACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_CPOS_RAISE1,
+    },
+    // MT_TROOP
+    MobjInfo {
+        doomed_num: 3001,
+        spawn_state: S_TROO_STND,
+        spawn_health: 60,
+        see_state: S_TROO_RUN1,
+        see_sound: SFX_BGSIT1,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_TROO_PAIN,
+        pain_chance: 200,
+        pain_sound: SFX_POPAIN,
+        melee_state: S_TROO_ATK1,
+        missile_state: S_TROO_ATK1,
+        death_state: S_TROO_DIE1,
+        xdeath_state: S_TROO_XDIE1,
+        death_sound: SFX_BGDTH1,
+        speed: 8,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_BGACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_TROO_RAISE1,
+    },
+    // MT_SERGEANT
+    MobjInfo {
+        doomed_num: 3002,
+        spawn_state: S_SARG_STND,
+        spawn_health: 150,
+        see_state: S_SARG_RUN1,
+        see_sound: SFX_SGTSIT,
+        reaction_time: 8,
+        attack_sound: SFX_SGTATK,
+        pain_state: S_SARG_PAIN,
+        pain_chance: 180,
+        pain_sound: SFX_DMPAIN,
+        melee_state: S_SARG_ATK1,
+        missile_state: S_NULL,
+        death_state: S_SARG_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_SGTDTH,
+        speed: 10,
+        radius: Fixed(30 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 400,
+        damage: 0,
+        active_sound: SFX_DMACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_SARG_RAISE1,
+    },
+    // MT_SHADOWS
+    MobjInfo {
+        doomed_num: 58,
+        spawn_state: S_SARG_STND,
+        spawn_health: 150,
+        see_state: S_SARG_RUN1,
+        see_sound: SFX_SGTSIT,
+        reaction_time: 8,
+        attack_sound: SFX_SGTATK,
+        pain_state: S_SARG_PAIN,
+        pain_chance: 180,
+        pain_sound: SFX_DMPAIN,
+        melee_state: S_SARG_ATK1,
+        missile_state: S_NULL,
+        death_state: S_SARG_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_SGTDTH,
+        speed: 10,
+        radius: Fixed(30 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 400,
+        damage: 0,
+        active_sound: SFX_DMACT,
+        flags: MF_SOLID | MF_SHADOW | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_SARG_RAISE1,
+    },
+    // MT_HEAD
+    MobjInfo {
+        doomed_num: 3005,
+        spawn_state: S_HEAD_STND,
+        spawn_health: 400,
+        see_state: S_HEAD_RUN1,
+        see_sound: SFX_CACSIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_HEAD_PAIN,
+        pain_chance: 128,
+        pain_sound: SFX_DMPAIN,
+        melee_state: S_NULL,
+        missile_state: S_HEAD_ATK1,
+        death_state: S_HEAD_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_CACDTH,
+        speed: 8,
+        radius: Fixed(31 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 400,
+        damage: 0,
+        active_sound: SFX_DMACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_FLOAT | MF_NOGRAVITY | MF_COUNTKILL,
+        raise_state: S_HEAD_RAISE1,
+    },
+    // MT_BRUISER
+    MobjInfo {
+        doomed_num: 3003,
+        spawn_state: S_BOSS_STND,
+        spawn_health: 1000,
+        see_state: S_BOSS_RUN1,
+        see_sound: SFX_BRSSIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_BOSS_PAIN,
+        pain_chance: 50,
+        pain_sound: SFX_DMPAIN,
+        melee_state: S_BOSS_ATK1,
+        missile_state: S_BOSS_ATK1,
+        death_state: S_BOSS_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_BRSDTH,
+        speed: 8,
+        radius: Fixed(24 * FRACUNIT),
+        height: Fixed(64 * FRACUNIT),
+        mass: 1000,
+        damage: 0,
+        active_sound: SFX_DMACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_BOSS_RAISE1,
+    },
+    // MT_BRUISERSHOT
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_BRBALL1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_FIRSHT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_BRBALLX1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_FIRXPL,
+        speed: 15 * FRACUNIT,
+        radius: Fixed(6 * FRACUNIT),
+        height: Fixed(8 * FRACUNIT),
+        mass: 100,
+        damage: 8,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_MISSILE | MF_DROPOFF | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_KNIGHT
+    MobjInfo {
+        doomed_num: 69,
+        spawn_state: S_BOS2_STND,
+        spawn_health: 500,
+        see_state: S_BOS2_RUN1,
+        see_sound: SFX_KNTSIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_BOS2_PAIN,
+        pain_chance: 50,
+        pain_sound: SFX_DMPAIN,
+        melee_state: S_BOS2_ATK1,
+        missile_state: S_BOS2_ATK1,
+        death_state: S_BOS2_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_KNTDTH,
+        speed: 8,
+        radius: Fixed(24 * FRACUNIT),
+        height: Fixed(64 * FRACUNIT),
+        mass: 1000,
+        damage: 0,
+        active_sound: SFX_DMACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_BOS2_RAISE1,
+    },
+    // MT_SKULL
+    MobjInfo {
+        doomed_num: 3006,
+        spawn_state: S_SKULL_STND,
+        spawn_health: 100,
+        see_state: S_SKULL_RUN1,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_SKLATK,
+        pain_state: S_SKULL_PAIN,
+        pain_chance: 256,
+        pain_sound: SFX_DMPAIN,
+        melee_state: S_NULL,
+        missile_state: S_SKULL_ATK1,
+        death_state: S_SKULL_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_FIRXPL,
+        speed: 8,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 50,
+        damage: 3,
+        active_sound: SFX_DMACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_FLOAT | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_SPIDER
+    MobjInfo {
+        doomed_num: 7,
+        spawn_state: S_SPID_STND,
+        spawn_health: 3000,
+        see_state: S_SPID_RUN1,
+        see_sound: SFX_SPISIT,
+        reaction_time: 8,
+        attack_sound: SFX_SHOTGN,
+        pain_state: S_SPID_PAIN,
+        pain_chance: 40,
+        pain_sound: SFX_DMPAIN,
+        melee_state: S_NULL,
+        missile_state: S_SPID_ATK1,
+        death_state: S_SPID_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_SPIDTH,
+        speed: 12,
+        radius: Fixed(128 * FRACUNIT),
+        height: Fixed(100 * FRACUNIT),
+        mass: 1000,
+        damage: 0,
+        active_sound: SFX_DMACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_NULL,
+    },
+    // MT_BABY
+    MobjInfo {
+        doomed_num: 68,
+        spawn_state: S_BSPI_STND,
+        spawn_health: 500,
+        see_state: S_BSPI_SIGHT,
+        see_sound: SFX_BSPSIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_BSPI_PAIN,
+        pain_chance: 128,
+        pain_sound: SFX_DMPAIN,
+        melee_state: S_NULL,
+        missile_state: S_BSPI_ATK1,
+        death_state: S_BSPI_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_BSPDTH,
+        speed: 12,
+        radius: Fixed(64 * FRACUNIT),
+        height: Fixed(64 * FRACUNIT),
+        mass: 600,
+        damage: 0,
+        active_sound: SFX_BSPACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_BSPI_RAISE1,
+    },
+    // MT_CYBORG
+    MobjInfo {
+        doomed_num: 16,
+        spawn_state: S_CYBER_STND,
+        spawn_health: 4000,
+        see_state: S_CYBER_RUN1,
+        see_sound: SFX_CYBSIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_CYBER_PAIN,
+        pain_chance: 20,
+        pain_sound: SFX_DMPAIN,
+        melee_state: S_NULL,
+        missile_state: S_CYBER_ATK1,
+        death_state: S_CYBER_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_CYBDTH,
+        speed: 16,
+        radius: Fixed(40 * FRACUNIT),
+        height: Fixed(110 * FRACUNIT),
+        mass: 1000,
+        damage: 0,
+        active_sound: SFX_DMACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_NULL,
+    },
+    // MT_PAIN
+    MobjInfo {
+        doomed_num: 71,
+        spawn_state: S_PAIN_STND,
+        spawn_health: 400,
+        see_state: S_PAIN_RUN1,
+        see_sound: SFX_PESIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_PAIN_PAIN,
+        pain_chance: 128,
+        pain_sound: SFX_PEPAIN,
+        melee_state: S_NULL,
+        missile_state: S_PAIN_ATK1,
+        death_state: S_PAIN_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_PEDTH,
+        speed: 8,
+        radius: Fixed(31 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 400,
+        damage: 0,
+        active_sound: SFX_DMACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_FLOAT | MF_NOGRAVITY | MF_COUNTKILL,
+        raise_state: S_PAIN_RAISE1,
+    },
+    // MT_WOLFSS
+    MobjInfo {
+        doomed_num: 84,
+        spawn_state: S_SSWV_STND,
+        spawn_health: 50,
+        see_state: S_SSWV_RUN1,
+        see_sound: SFX_SSSIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_SSWV_PAIN,
+        pain_chance: 170,
+        pain_sound: SFX_POPAIN,
+        melee_state: S_NULL,
+        missile_state: S_SSWV_ATK1,
+        death_state: S_SSWV_DIE1,
+        xdeath_state: S_SSWV_XDIE1,
+        death_sound: SFX_SSDTH,
+        speed: 8,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(56 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_POSACT,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_SSWV_RAISE1,
+    },
+    // MT_KEEN
+    MobjInfo {
+        doomed_num: 72,
+        spawn_state: S_KEENSTND,
+        spawn_health: 100,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_KEENPAIN,
+        pain_chance: 256,
+        pain_sound: SFX_KEENPN,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_COMMKEEN,
+        xdeath_state: S_NULL,
+        death_sound: SFX_KEENDT,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(72 * FRACUNIT),
+        mass: 10000000,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY | MF_SHOOTABLE | MF_COUNTKILL,
+        raise_state: S_NULL,
+    },
+    // MT_BOSSBRAIN
+    MobjInfo {
+        doomed_num: 88,
+        spawn_state: S_BRAIN,
+        spawn_health: 250,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_BRAIN_PAIN,
+        pain_chance: 255,
+        pain_sound: SFX_BOSPN,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_BRAIN_DIE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_BOSDTH,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 10000000,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SHOOTABLE,
+        raise_state: S_NULL,
+    },
+    // MT_BOSSSPIT
+    MobjInfo {
+        doomed_num: 89,
+        spawn_state: S_BRAINEYE,
+        spawn_health: 1000,
+        see_state: S_BRAINEYESEE,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(32 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_NOSECTOR,
+        raise_state: S_NULL,
+    },
+    // MT_BOSSTARGET
+    MobjInfo {
+        doomed_num: 87,
+        spawn_state: S_NULL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(32 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_NOSECTOR,
+        raise_state: S_NULL,
+    },
+    // MT_SPAWNSHOT
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_SPAWN1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_BOSPIT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_FIRXPL,
+        speed: 10 * FRACUNIT,
+        radius: Fixed(6 * FRACUNIT),
+        height: Fixed(32 * FRACUNIT),
+        mass: 100,
+        damage: 3,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_MISSILE | MF_DROPOFF | MF_NOGRAVITY | MF_NOCLIP,
+        raise_state: S_NULL,
+    },
+    // MT_SPAWNFIRE
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_SPAWNFIRE1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_BARREL
+    MobjInfo {
+        doomed_num: 2035,
+        spawn_state: S_BAR1,
+        spawn_health: 20,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_BEXP,
+        xdeath_state: S_NULL,
+        death_sound: SFX_BAREXP,
+        speed: 0,
+        radius: Fixed(10 * FRACUNIT),
+        height: Fixed(42 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SHOOTABLE | MF_NOBLOOD,
+        raise_state: S_NULL,
+    },
+    // MT_TROOPSHOT
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_TBALL1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_FIRSHT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_TBALLX1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_FIRXPL,
+        speed: 10 * FRACUNIT,
+        radius: Fixed(6 * FRACUNIT),
+        height: Fixed(8 * FRACUNIT),
+        mass: 100,
+        damage: 3,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_MISSILE | MF_DROPOFF | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_HEADSHOT
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_RBALL1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_FIRSHT,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_RBALLX1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_FIRXPL,
+        speed: 10 * FRACUNIT,
+        radius: Fixed(6 * FRACUNIT),
+        height: Fixed(8 * FRACUNIT),
+        mass: 100,
+        damage: 5,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_MISSILE | MF_DROPOFF | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_ROCKET
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_ROCKET,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_RLAUNC,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_EXPLODE1,
+        xdeath_state: S_NULL,
+        death_sound: SFX_BAREXP,
+        speed: 20 * FRACUNIT,
+        radius: Fixed(11 * FRACUNIT),
+        height: Fixed(8 * FRACUNIT),
+        mass: 100,
+        damage: 20,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_MISSILE | MF_DROPOFF | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_PLASMA
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_PLASBALL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_PLASMA,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_PLASEXP,
+        xdeath_state: S_NULL,
+        death_sound: SFX_FIRXPL,
+        speed: 25 * FRACUNIT,
+        radius: Fixed(13 * FRACUNIT),
+        height: Fixed(8 * FRACUNIT),
+        mass: 100,
+        damage: 5,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_MISSILE | MF_DROPOFF | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_BFG
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_BFGSHOT,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_BFGLAND,
+        xdeath_state: S_NULL,
+        death_sound: SFX_RXPLOD,
+        speed: 25 * FRACUNIT,
+        radius: Fixed(13 * FRACUNIT),
+        height: Fixed(8 * FRACUNIT),
+        mass: 100,
+        damage: 100,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_MISSILE | MF_DROPOFF | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_ARACHPLAZ
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_ARACH_PLAZ,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_PLASMA,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_ARACH_PLEX,
+        xdeath_state: S_NULL,
+        death_sound: SFX_FIRXPL,
+        speed: 25 * FRACUNIT,
+        radius: Fixed(13 * FRACUNIT),
+        height: Fixed(8 * FRACUNIT),
+        mass: 100,
+        damage: 5,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_MISSILE | MF_DROPOFF | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_PUFF
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_PUFF1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_BLOOD
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_BLOOD1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP,
+        raise_state: S_NULL,
+    },
+    // MT_TFOG
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_TFOG,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_IFOG
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_IFOG,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_TELEPORTMAN
+    MobjInfo {
+        doomed_num: 14,
+        spawn_state: S_NULL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_NOSECTOR,
+        raise_state: S_NULL,
+    },
+    // MT_EXTRABFG
+    MobjInfo {
+        doomed_num: -1,
+        spawn_state: S_BFGEXP,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC0
+    MobjInfo {
+        doomed_num: 2018,
+        spawn_state: S_ARM1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC1
+    MobjInfo {
+        doomed_num: 2019,
+        spawn_state: S_ARM2,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC2
+    MobjInfo {
+        doomed_num: 2014,
+        spawn_state: S_BON1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_COUNTITEM,
+        raise_state: S_NULL,
+    },
+    // MT_MISC3
+    MobjInfo {
+        doomed_num: 2015,
+        spawn_state: S_BON2,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_COUNTITEM,
+        raise_state: S_NULL,
+    },
+    // MT_MISC4
+    MobjInfo {
+        doomed_num: 5,
+        spawn_state: S_BKEY,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_NOTDMATCH,
+        raise_state: S_NULL,
+    },
+    // MT_MISC5
+    MobjInfo {
+        doomed_num: 13,
+        spawn_state: S_RKEY,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_NOTDMATCH,
+        raise_state: S_NULL,
+    },
+    // MT_MISC6
+    MobjInfo {
+        doomed_num: 6,
+        spawn_state: S_YKEY,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_NOTDMATCH,
+        raise_state: S_NULL,
+    },
+    // MT_MISC7
+    MobjInfo {
+        doomed_num: 39,
+        spawn_state: S_YSKULL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_NOTDMATCH,
+        raise_state: S_NULL,
+    },
+    // MT_MISC8
+    MobjInfo {
+        doomed_num: 38,
+        spawn_state: S_RSKULL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_NOTDMATCH,
+        raise_state: S_NULL,
+    },
+    // MT_MISC9
+    MobjInfo {
+        doomed_num: 40,
+        spawn_state: S_BSKULL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_NOTDMATCH,
+        raise_state: S_NULL,
+    },
+    // MT_MISC10
+    MobjInfo {
+        doomed_num: 2011,
+        spawn_state: S_STIM,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC11
+    MobjInfo {
+        doomed_num: 2012,
+        spawn_state: S_MEDI,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC12
+    MobjInfo {
+        doomed_num: 2013,
+        spawn_state: S_SOUL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_COUNTITEM,
+        raise_state: S_NULL,
+    },
+    // MT_INV
+    MobjInfo {
+        doomed_num: 2022,
+        spawn_state: S_PINV,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_COUNTITEM,
+        raise_state: S_NULL,
+    },
+    // MT_MISC13
+    MobjInfo {
+        doomed_num: 2023,
+        spawn_state: S_PSTR,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_COUNTITEM,
+        raise_state: S_NULL,
+    },
+    // MT_INS
+    MobjInfo {
+        doomed_num: 2024,
+        spawn_state: S_PINS,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_COUNTITEM,
+        raise_state: S_NULL,
+    },
+    // MT_MISC14
+    MobjInfo {
+        doomed_num: 2025,
+        spawn_state: S_SUIT,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC15
+    MobjInfo {
+        doomed_num: 2026,
+        spawn_state: S_PMAP,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_COUNTITEM,
+        raise_state: S_NULL,
+    },
+    // MT_MISC16
+    MobjInfo {
+        doomed_num: 2045,
+        spawn_state: S_PVIS,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_COUNTITEM,
+        raise_state: S_NULL,
+    },
+    // MT_MEGA
+    MobjInfo {
+        doomed_num: 83,
+        spawn_state: S_MEGA,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL | MF_COUNTITEM,
+        raise_state: S_NULL,
+    },
+    // MT_CLIP
+    MobjInfo {
+        doomed_num: 2007,
+        spawn_state: S_CLIP,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC17
+    MobjInfo {
+        doomed_num: 2048,
+        spawn_state: S_AMMO,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC18
+    MobjInfo {
+        doomed_num: 2010,
+        spawn_state: S_ROCK,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC19
+    MobjInfo {
+        doomed_num: 2046,
+        spawn_state: S_BROK,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC20
+    MobjInfo {
+        doomed_num: 2047,
+        spawn_state: S_CELL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC21
+    MobjInfo {
+        doomed_num: 17,
+        spawn_state: S_CELP,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC22
+    MobjInfo {
+        doomed_num: 2008,
+        spawn_state: S_SHEL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC23
+    MobjInfo {
+        doomed_num: 2049,
+        spawn_state: S_SBOX,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC24
+    MobjInfo {
+        doomed_num: 8,
+        spawn_state: S_BPAK,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC25
+    MobjInfo {
+        doomed_num: 2006,
+        spawn_state: S_BFUG,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_CHAINGUN
+    MobjInfo {
+        doomed_num: 2002,
+        spawn_state: S_MGUN,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC26
+    MobjInfo {
+        doomed_num: 2005,
+        spawn_state: S_CSAW,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC27
+    MobjInfo {
+        doomed_num: 2003,
+        spawn_state: S_LAUN,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC28
+    MobjInfo {
+        doomed_num: 2004,
+        spawn_state: S_PLAS,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_SHOTGUN
+    MobjInfo {
+        doomed_num: 2001,
+        spawn_state: S_SHOT,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_SUPERSHOTGUN
+    MobjInfo {
+        doomed_num: 82,
+        spawn_state: S_SHOT2,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPECIAL,
+        raise_state: S_NULL,
+    },
+    // MT_MISC29
+    MobjInfo {
+        doomed_num: 85,
+        spawn_state: S_TECHLAMP,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC30
+    MobjInfo {
+        doomed_num: 86,
+        spawn_state: S_TECH2LAMP,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC31
+    MobjInfo {
+        doomed_num: 2028,
+        spawn_state: S_COLU,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC32
+    MobjInfo {
+        doomed_num: 30,
+        spawn_state: S_TALLGRNCOL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC33
+    MobjInfo {
+        doomed_num: 31,
+        spawn_state: S_SHRTGRNCOL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC34
+    MobjInfo {
+        doomed_num: 32,
+        spawn_state: S_TALLREDCOL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC35
+    MobjInfo {
+        doomed_num: 33,
+        spawn_state: S_SHRTREDCOL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC36
+    MobjInfo {
+        doomed_num: 37,
+        spawn_state: S_SKULLCOL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC37
+    MobjInfo {
+        doomed_num: 36,
+        spawn_state: S_HEARTCOL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC38
+    MobjInfo {
+        doomed_num: 41,
+        spawn_state: S_EVILEYE,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC39
+    MobjInfo {
+        doomed_num: 42,
+        spawn_state: S_FLOATSKULL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC40
+    MobjInfo {
+        doomed_num: 43,
+        spawn_state: S_TORCHTREE,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC41
+    MobjInfo {
+        doomed_num: 44,
+        spawn_state: S_BLUETORCH,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC42
+    MobjInfo {
+        doomed_num: 45,
+        spawn_state: S_GREENTORCH,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC43
+    MobjInfo {
+        doomed_num: 46,
+        spawn_state: S_REDTORCH,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC44
+    MobjInfo {
+        doomed_num: 55,
+        spawn_state: S_BTORCHSHRT,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC45
+    MobjInfo {
+        doomed_num: 56,
+        spawn_state: S_GTORCHSHRT,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC46
+    MobjInfo {
+        doomed_num: 57,
+        spawn_state: S_RTORCHSHRT,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC47
+    MobjInfo {
+        doomed_num: 47,
+        spawn_state: S_STALAGTITE,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC48
+    MobjInfo {
+        doomed_num: 48,
+        spawn_state: S_TECHPILLAR,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC49
+    MobjInfo {
+        doomed_num: 34,
+        spawn_state: S_CANDLESTIK,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC50
+    MobjInfo {
+        doomed_num: 35,
+        spawn_state: S_CANDELABRA,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC51
+    MobjInfo {
+        doomed_num: 49,
+        spawn_state: S_BLOODYTWITCH,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(68 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC52
+    MobjInfo {
+        doomed_num: 50,
+        spawn_state: S_MEAT2,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(84 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC53
+    MobjInfo {
+        doomed_num: 51,
+        spawn_state: S_MEAT3,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(84 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC54
+    MobjInfo {
+        doomed_num: 52,
+        spawn_state: S_MEAT4,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(68 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC55
+    MobjInfo {
+        doomed_num: 53,
+        spawn_state: S_MEAT5,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(52 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC56
+    MobjInfo {
+        doomed_num: 59,
+        spawn_state: S_MEAT2,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(84 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC57
+    MobjInfo {
+        doomed_num: 60,
+        spawn_state: S_MEAT4,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(68 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC58
+    MobjInfo {
+        doomed_num: 61,
+        spawn_state: S_MEAT3,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(52 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC59
+    MobjInfo {
+        doomed_num: 62,
+        spawn_state: S_MEAT5,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(52 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC60
+    MobjInfo {
+        doomed_num: 63,
+        spawn_state: S_BLOODYTWITCH,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(68 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC61
+    MobjInfo {
+        doomed_num: 22,
+        spawn_state: S_HEAD_DIE6,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC62
+    MobjInfo {
+        doomed_num: 15,
+        spawn_state: S_PLAY_DIE7,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC63
+    MobjInfo {
+        doomed_num: 18,
+        spawn_state: S_POSS_DIE5,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC64
+    MobjInfo {
+        doomed_num: 21,
+        spawn_state: S_SARG_DIE6,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC65
+    MobjInfo {
+        doomed_num: 23,
+        spawn_state: S_SKULL_DIE6,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC66
+    MobjInfo {
+        doomed_num: 20,
+        spawn_state: S_TROO_DIE5,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC67
+    MobjInfo {
+        doomed_num: 19,
+        spawn_state: S_SPOS_DIE5,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC68
+    MobjInfo {
+        doomed_num: 10,
+        spawn_state: S_PLAY_XDIE9,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC69
+    MobjInfo {
+        doomed_num: 12,
+        spawn_state: S_PLAY_XDIE9,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC70
+    MobjInfo {
+        doomed_num: 28,
+        spawn_state: S_HEADSONSTICK,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC71
+    MobjInfo {
+        doomed_num: 24,
+        spawn_state: S_GIBS,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: 0,
+        raise_state: S_NULL,
+    },
+    // MT_MISC72
+    MobjInfo {
+        doomed_num: 27,
+        spawn_state: S_HEADONASTICK,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC73
+    MobjInfo {
+        doomed_num: 29,
+        spawn_state: S_HEADCANDLES,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC74
+    MobjInfo {
+        doomed_num: 25,
+        spawn_state: S_DEADSTICK,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC75
+    MobjInfo {
+        doomed_num: 26,
+        spawn_state: S_LIVESTICK,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC76
+    MobjInfo {
+        doomed_num: 54,
+        spawn_state: S_BIGTREE,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(32 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC77
+    MobjInfo {
+        doomed_num: 70,
+        spawn_state: S_BBAR1,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID,
+        raise_state: S_NULL,
+    },
+    // MT_MISC78
+    MobjInfo {
+        doomed_num: 73,
+        spawn_state: S_HANGNOGUTS,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(88 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC79
+    MobjInfo {
+        doomed_num: 74,
+        spawn_state: S_HANGBNOBRAIN,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(88 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC80
+    MobjInfo {
+        doomed_num: 75,
+        spawn_state: S_HANGTLOOKDN,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(64 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC81
+    MobjInfo {
+        doomed_num: 76,
+        spawn_state: S_HANGTSKULL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(64 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC82
+    MobjInfo {
+        doomed_num: 77,
+        spawn_state: S_HANGTLOOKUP,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(64 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC83
+    MobjInfo {
+        doomed_num: 78,
+        spawn_state: S_HANGTNOBRAIN,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(16 * FRACUNIT),
+        height: Fixed(64 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_SOLID | MF_SPAWNCEILING | MF_NOGRAVITY,
+        raise_state: S_NULL,
+    },
+    // MT_MISC84
+    MobjInfo {
+        doomed_num: 79,
+        spawn_state: S_COLONGIBS,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP,
+        raise_state: S_NULL,
+    },
+    // MT_MISC85
+    MobjInfo {
+        doomed_num: 80,
+        spawn_state: S_SMALLPOOL,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP,
+        raise_state: S_NULL,
+    },
+    // MT_MISC86
+    MobjInfo {
+        doomed_num: 81,
+        spawn_state: S_BRAINSTEM,
+        spawn_health: 1000,
+        see_state: S_NULL,
+        see_sound: SFX_NONE,
+        reaction_time: 8,
+        attack_sound: SFX_NONE,
+        pain_state: S_NULL,
+        pain_chance: 0,
+        pain_sound: SFX_NONE,
+        melee_state: S_NULL,
+        missile_state: S_NULL,
+        death_state: S_NULL,
+        xdeath_state: S_NULL,
+        death_sound: SFX_NONE,
+        speed: 0,
+        radius: Fixed(20 * FRACUNIT),
+        height: Fixed(16 * FRACUNIT),
+        mass: 100,
+        damage: 0,
+        active_sound: SFX_NONE,
+        flags: MF_NOBLOCKMAP,
+        raise_state: S_NULL,
+    },
+];
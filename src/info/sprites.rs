@@ -0,0 +1,161 @@
+use crate::info::SpriteNum;
+
+pub const SPR_TROO: SpriteNum = 0;
+pub const SPR_SHTG: SpriteNum = 1;
+pub const SPR_PUNG: SpriteNum = 2;
+pub const SPR_PISG: SpriteNum = 3;
+pub const SPR_PISF: SpriteNum = 4;
+pub const SPR_SHTF: SpriteNum = 5;
+pub const SPR_SHT2: SpriteNum = 6;
+pub const SPR_CHGG: SpriteNum = 7;
+pub const SPR_CHGF: SpriteNum = 8;
+pub const SPR_MISG: SpriteNum = 9;
+pub const SPR_MISF: SpriteNum = 10;
+pub const SPR_SAWG: SpriteNum = 11;
+pub const SPR_PLSG: SpriteNum = 12;
+pub const SPR_PLSF: SpriteNum = 13;
+pub const SPR_BFGG: SpriteNum = 14;
+pub const SPR_BFGF: SpriteNum = 15;
+pub const SPR_BLUD: SpriteNum = 16;
+pub const SPR_PUFF: SpriteNum = 17;
+pub const SPR_BAL1: SpriteNum = 18;
+pub const SPR_BAL2: SpriteNum = 19;
+pub const SPR_PLSS: SpriteNum = 20;
+pub const SPR_PLSE: SpriteNum = 21;
+pub const SPR_MISL: SpriteNum = 22;
+pub const SPR_BFS1: SpriteNum = 23;
+pub const SPR_BFE1: SpriteNum = 24;
+pub const SPR_BFE2: SpriteNum = 25;
+pub const SPR_TFOG: SpriteNum = 26;
+pub const SPR_IFOG: SpriteNum = 27;
+pub const SPR_PLAY: SpriteNum = 28;
+pub const SPR_POSS: SpriteNum = 29;
+pub const SPR_SPOS: SpriteNum = 30;
+pub const SPR_VILE: SpriteNum = 31;
+pub const SPR_FIRE: SpriteNum = 32;
+pub const SPR_FATB: SpriteNum = 33;
+pub const SPR_FBXP: SpriteNum = 34;
+pub const SPR_SKEL: SpriteNum = 35;
+pub const SPR_MANF: SpriteNum = 36;
+pub const SPR_FATT: SpriteNum = 37;
+pub const SPR_CPOS: SpriteNum = 38;
+pub const SPR_SARG: SpriteNum = 39;
+pub const SPR_HEAD: SpriteNum = 40;
+pub const SPR_BAL7: SpriteNum = 41;
+pub const SPR_BOSS: SpriteNum = 42;
+pub const SPR_BOS2: SpriteNum = 43;
+pub const SPR_SKUL: SpriteNum = 44;
+pub const SPR_SPID: SpriteNum = 45;
+pub const SPR_BSPI: SpriteNum = 46;
+pub const SPR_APLS: SpriteNum = 47;
+pub const SPR_APBX: SpriteNum = 48;
+pub const SPR_CYBR: SpriteNum = 49;
+pub const SPR_PAIN: SpriteNum = 50;
+pub const SPR_SSWV: SpriteNum = 51;
+pub const SPR_KEEN: SpriteNum = 52;
+pub const SPR_BBRN: SpriteNum = 53;
+pub const SPR_BOSF: SpriteNum = 54;
+pub const SPR_ARM1: SpriteNum = 55;
+pub const SPR_ARM2: SpriteNum = 56;
+pub const SPR_BAR1: SpriteNum = 57;
+pub const SPR_BEXP: SpriteNum = 58;
+pub const SPR_FCAN: SpriteNum = 59;
+pub const SPR_BON1: SpriteNum = 60;
+pub const SPR_BON2: SpriteNum = 61;
+pub const SPR_BKEY: SpriteNum = 62;
+pub const SPR_RKEY: SpriteNum = 63;
+pub const SPR_YKEY: SpriteNum = 64;
+pub const SPR_BSKU: SpriteNum = 65;
+pub const SPR_RSKU: SpriteNum = 66;
+pub const SPR_YSKU: SpriteNum = 67;
+pub const SPR_STIM: SpriteNum = 68;
+pub const SPR_MEDI: SpriteNum = 69;
+pub const SPR_SOUL: SpriteNum = 70;
+pub const SPR_PINV: SpriteNum = 71;
+pub const SPR_PSTR: SpriteNum = 72;
+pub const SPR_PINS: SpriteNum = 73;
+pub const SPR_MEGA: SpriteNum = 74;
+pub const SPR_SUIT: SpriteNum = 75;
+pub const SPR_PMAP: SpriteNum = 76;
+pub const SPR_PVIS: SpriteNum = 77;
+pub const SPR_CLIP: SpriteNum = 78;
+pub const SPR_AMMO: SpriteNum = 79;
+pub const SPR_ROCK: SpriteNum = 80;
+pub const SPR_BROK: SpriteNum = 81;
+pub const SPR_CELL: SpriteNum = 82;
+pub const SPR_CELP: SpriteNum = 83;
+pub const SPR_SHEL: SpriteNum = 84;
+pub const SPR_SBOX: SpriteNum = 85;
+pub const SPR_BPAK: SpriteNum = 86;
+pub const SPR_BFUG: SpriteNum = 87;
+pub const SPR_MGUN: SpriteNum = 88;
+pub const SPR_CSAW: SpriteNum = 89;
+pub const SPR_LAUN: SpriteNum = 90;
+pub const SPR_PLAS: SpriteNum = 91;
+pub const SPR_SHOT: SpriteNum = 92;
+pub const SPR_SGN2: SpriteNum = 93;
+pub const SPR_COLU: SpriteNum = 94;
+pub const SPR_SMT2: SpriteNum = 95;
+pub const SPR_GOR1: SpriteNum = 96;
+pub const SPR_POL2: SpriteNum = 97;
+pub const SPR_POL5: SpriteNum = 98;
+pub const SPR_POL4: SpriteNum = 99;
+pub const SPR_POL3: SpriteNum = 100;
+pub const SPR_POL1: SpriteNum = 101;
+pub const SPR_POL6: SpriteNum = 102;
+pub const SPR_GOR2: SpriteNum = 103;
+pub const SPR_GOR3: SpriteNum = 104;
+pub const SPR_GOR4: SpriteNum = 105;
+pub const SPR_GOR5: SpriteNum = 106;
+pub const SPR_SMIT: SpriteNum = 107;
+pub const SPR_COL1: SpriteNum = 108;
+pub const SPR_COL2: SpriteNum = 109;
+pub const SPR_COL3: SpriteNum = 110;
+pub const SPR_COL4: SpriteNum = 111;
+pub const SPR_CAND: SpriteNum = 112;
+pub const SPR_CBRA: SpriteNum = 113;
+pub const SPR_COL6: SpriteNum = 114;
+pub const SPR_TRE1: SpriteNum = 115;
+pub const SPR_TRE2: SpriteNum = 116;
+pub const SPR_ELEC: SpriteNum = 117;
+pub const SPR_CEYE: SpriteNum = 118;
+pub const SPR_FSKU: SpriteNum = 119;
+pub const SPR_COL5: SpriteNum = 120;
+pub const SPR_TBLU: SpriteNum = 121;
+pub const SPR_TGRN: SpriteNum = 122;
+pub const SPR_TRED: SpriteNum = 123;
+pub const SPR_SMBT: SpriteNum = 124;
+pub const SPR_SMGT: SpriteNum = 125;
+pub const SPR_SMRT: SpriteNum = 126;
+pub const SPR_HDB1: SpriteNum = 127;
+pub const SPR_HDB2: SpriteNum = 128;
+pub const SPR_HDB3: SpriteNum = 129;
+pub const SPR_HDB4: SpriteNum = 130;
+pub const SPR_HDB5: SpriteNum = 131;
+pub const SPR_HDB6: SpriteNum = 132;
+pub const SPR_POB1: SpriteNum = 133;
+pub const SPR_POB2: SpriteNum = 134;
+pub const SPR_BRS1: SpriteNum = 135;
+pub const SPR_TLMP: SpriteNum = 136;
+pub const SPR_TLP2: SpriteNum = 137;
+
+pub const NUMSPRITES: usize = 138;
+
+// Sprite lump names are these followed by the frame letter and rotation, e.g. TROOA1
+#[rustfmt::skip]
+pub const SPRNAMES: [&str; NUMSPRITES] = [
+    "TROO", "SHTG", "PUNG", "PISG", "PISF", "SHTF", "SHT2", "CHGG", "CHGF", "MISG",
+    "MISF", "SAWG", "PLSG", "PLSF", "BFGG", "BFGF", "BLUD", "PUFF", "BAL1", "BAL2",
+    "PLSS", "PLSE", "MISL", "BFS1", "BFE1", "BFE2", "TFOG", "IFOG", "PLAY", "POSS",
+    "SPOS", "VILE", "FIRE", "FATB", "FBXP", "SKEL", "MANF", "FATT", "CPOS", "SARG",
+    "HEAD", "BAL7", "BOSS", "BOS2", "SKUL", "SPID", "BSPI", "APLS", "APBX", "CYBR",
+    "PAIN", "SSWV", "KEEN", "BBRN", "BOSF", "ARM1", "ARM2", "BAR1", "BEXP", "FCAN",
+    "BON1", "BON2", "BKEY", "RKEY", "YKEY", "BSKU", "RSKU", "YSKU", "STIM", "MEDI",
+    "SOUL", "PINV", "PSTR", "PINS", "MEGA", "SUIT", "PMAP", "PVIS", "CLIP", "AMMO",
+    "ROCK", "BROK", "CELL", "CELP", "SHEL", "SBOX", "BPAK", "BFUG", "MGUN", "CSAW",
+    "LAUN", "PLAS", "SHOT", "SGN2", "COLU", "SMT2", "GOR1", "POL2", "POL5", "POL4",
+    "POL3", "POL1", "POL6", "GOR2", "GOR3", "GOR4", "GOR5", "SMIT", "COL1", "COL2",
+    "COL3", "COL4", "CAND", "CBRA", "COL6", "TRE1", "TRE2", "ELEC", "CEYE", "FSKU",
+    "COL5", "TBLU", "TGRN", "TRED", "SMBT", "SMGT", "SMRT", "HDB1", "HDB2", "HDB3",
+    "HDB4", "HDB5", "HDB6", "POB1", "POB2", "BRS1", "TLMP", "TLP2",
+];
@@ -0,0 +1,1943 @@
+use crate::info::sprites::*;
+use crate::info::{Action, State, StateNum, FF_FULLBRIGHT};
+
+pub const S_NULL: StateNum = 0;
+pub const S_LIGHTDONE: StateNum = 1;
+pub const S_PUNCH: StateNum = 2;
+pub const S_PUNCHDOWN: StateNum = 3;
+pub const S_PUNCHUP: StateNum = 4;
+pub const S_PUNCH1: StateNum = 5;
+pub const S_PUNCH2: StateNum = 6;
+pub const S_PUNCH3: StateNum = 7;
+pub const S_PUNCH4: StateNum = 8;
+pub const S_PUNCH5: StateNum = 9;
+pub const S_PISTOL: StateNum = 10;
+pub const S_PISTOLDOWN: StateNum = 11;
+pub const S_PISTOLUP: StateNum = 12;
+pub const S_PISTOL1: StateNum = 13;
+pub const S_PISTOL2: StateNum = 14;
+pub const S_PISTOL3: StateNum = 15;
+pub const S_PISTOL4: StateNum = 16;
+pub const S_PISTOLFLASH: StateNum = 17;
+pub const S_SGUN: StateNum = 18;
+pub const S_SGUNDOWN: StateNum = 19;
+pub const S_SGUNUP: StateNum = 20;
+pub const S_SGUN1: StateNum = 21;
+pub const S_SGUN2: StateNum = 22;
+pub const S_SGUN3: StateNum = 23;
+pub const S_SGUN4: StateNum = 24;
+pub const S_SGUN5: StateNum = 25;
+pub const S_SGUN6: StateNum = 26;
+pub const S_SGUN7: StateNum = 27;
+pub const S_SGUN8: StateNum = 28;
+pub const S_SGUN9: StateNum = 29;
+pub const S_SGUNFLASH1: StateNum = 30;
+pub const S_SGUNFLASH2: StateNum = 31;
+pub const S_DSGUN: StateNum = 32;
+pub const S_DSGUNDOWN: StateNum = 33;
+pub const S_DSGUNUP: StateNum = 34;
+pub const S_DSGUN1: StateNum = 35;
+pub const S_DSGUN2: StateNum = 36;
+pub const S_DSGUN3: StateNum = 37;
+pub const S_DSGUN4: StateNum = 38;
+pub const S_DSGUN5: StateNum = 39;
+pub const S_DSGUN6: StateNum = 40;
+pub const S_DSGUN7: StateNum = 41;
+pub const S_DSGUN8: StateNum = 42;
+pub const S_DSGUN9: StateNum = 43;
+pub const S_DSGUN10: StateNum = 44;
+pub const S_DSNR1: StateNum = 45;
+pub const S_DSNR2: StateNum = 46;
+pub const S_DSGUNFLASH1: StateNum = 47;
+pub const S_DSGUNFLASH2: StateNum = 48;
+pub const S_CHAIN: StateNum = 49;
+pub const S_CHAINDOWN: StateNum = 50;
+pub const S_CHAINUP: StateNum = 51;
+pub const S_CHAIN1: StateNum = 52;
+pub const S_CHAIN2: StateNum = 53;
+pub const S_CHAIN3: StateNum = 54;
+pub const S_CHAINFLASH1: StateNum = 55;
+pub const S_CHAINFLASH2: StateNum = 56;
+pub const S_MISSILE: StateNum = 57;
+pub const S_MISSILEDOWN: StateNum = 58;
+pub const S_MISSILEUP: StateNum = 59;
+pub const S_MISSILE1: StateNum = 60;
+pub const S_MISSILE2: StateNum = 61;
+pub const S_MISSILE3: StateNum = 62;
+pub const S_MISSILEFLASH1: StateNum = 63;
+pub const S_MISSILEFLASH2: StateNum = 64;
+pub const S_MISSILEFLASH3: StateNum = 65;
+pub const S_MISSILEFLASH4: StateNum = 66;
+pub const S_SAW: StateNum = 67;
+pub const S_SAWB: StateNum = 68;
+pub const S_SAWDOWN: StateNum = 69;
+pub const S_SAWUP: StateNum = 70;
+pub const S_SAW1: StateNum = 71;
+pub const S_SAW2: StateNum = 72;
+pub const S_SAW3: StateNum = 73;
+pub const S_PLASMA: StateNum = 74;
+pub const S_PLASMADOWN: StateNum = 75;
+pub const S_PLASMAUP: StateNum = 76;
+pub const S_PLASMA1: StateNum = 77;
+pub const S_PLASMA2: StateNum = 78;
+pub const S_PLASMAFLASH1: StateNum = 79;
+pub const S_PLASMAFLASH2: StateNum = 80;
+pub const S_BFG: StateNum = 81;
+pub const S_BFGDOWN: StateNum = 82;
+pub const S_BFGUP: StateNum = 83;
+pub const S_BFG1: StateNum = 84;
+pub const S_BFG2: StateNum = 85;
+pub const S_BFG3: StateNum = 86;
+pub const S_BFG4: StateNum = 87;
+pub const S_BFGFLASH1: StateNum = 88;
+pub const S_BFGFLASH2: StateNum = 89;
+pub const S_BLOOD1: StateNum = 90;
+pub const S_BLOOD2: StateNum = 91;
+pub const S_BLOOD3: StateNum = 92;
+pub const S_PUFF1: StateNum = 93;
+pub const S_PUFF2: StateNum = 94;
+pub const S_PUFF3: StateNum = 95;
+pub const S_PUFF4: StateNum = 96;
+pub const S_TBALL1: StateNum = 97;
+pub const S_TBALL2: StateNum = 98;
+pub const S_TBALLX1: StateNum = 99;
+pub const S_TBALLX2: StateNum = 100;
+pub const S_TBALLX3: StateNum = 101;
+pub const S_RBALL1: StateNum = 102;
+pub const S_RBALL2: StateNum = 103;
+pub const S_RBALLX1: StateNum = 104;
+pub const S_RBALLX2: StateNum = 105;
+pub const S_RBALLX3: StateNum = 106;
+pub const S_PLASBALL: StateNum = 107;
+pub const S_PLASBALL2: StateNum = 108;
+pub const S_PLASEXP: StateNum = 109;
+pub const S_PLASEXP2: StateNum = 110;
+pub const S_PLASEXP3: StateNum = 111;
+pub const S_PLASEXP4: StateNum = 112;
+pub const S_PLASEXP5: StateNum = 113;
+pub const S_ROCKET: StateNum = 114;
+pub const S_BFGSHOT: StateNum = 115;
+pub const S_BFGSHOT2: StateNum = 116;
+pub const S_BFGLAND: StateNum = 117;
+pub const S_BFGLAND2: StateNum = 118;
+pub const S_BFGLAND3: StateNum = 119;
+pub const S_BFGLAND4: StateNum = 120;
+pub const S_BFGLAND5: StateNum = 121;
+pub const S_BFGLAND6: StateNum = 122;
+pub const S_BFGEXP: StateNum = 123;
+pub const S_BFGEXP2: StateNum = 124;
+pub const S_BFGEXP3: StateNum = 125;
+pub const S_BFGEXP4: StateNum = 126;
+pub const S_EXPLODE1: StateNum = 127;
+pub const S_EXPLODE2: StateNum = 128;
+pub const S_EXPLODE3: StateNum = 129;
+pub const S_TFOG: StateNum = 130;
+pub const S_TFOG01: StateNum = 131;
+pub const S_TFOG02: StateNum = 132;
+pub const S_TFOG2: StateNum = 133;
+pub const S_TFOG3: StateNum = 134;
+pub const S_TFOG4: StateNum = 135;
+pub const S_TFOG5: StateNum = 136;
+pub const S_TFOG6: StateNum = 137;
+pub const S_TFOG7: StateNum = 138;
+pub const S_TFOG8: StateNum = 139;
+pub const S_TFOG9: StateNum = 140;
+pub const S_TFOG10: StateNum = 141;
+pub const S_IFOG: StateNum = 142;
+pub const S_IFOG01: StateNum = 143;
+pub const S_IFOG02: StateNum = 144;
+pub const S_IFOG2: StateNum = 145;
+pub const S_IFOG3: StateNum = 146;
+pub const S_IFOG4: StateNum = 147;
+pub const S_IFOG5: StateNum = 148;
+pub const S_PLAY: StateNum = 149;
+pub const S_PLAY_RUN1: StateNum = 150;
+pub const S_PLAY_RUN2: StateNum = 151;
+pub const S_PLAY_RUN3: StateNum = 152;
+pub const S_PLAY_RUN4: StateNum = 153;
+pub const S_PLAY_ATK1: StateNum = 154;
+pub const S_PLAY_ATK2: StateNum = 155;
+pub const S_PLAY_PAIN: StateNum = 156;
+pub const S_PLAY_PAIN2: StateNum = 157;
+pub const S_PLAY_DIE1: StateNum = 158;
+pub const S_PLAY_DIE2: StateNum = 159;
+pub const S_PLAY_DIE3: StateNum = 160;
+pub const S_PLAY_DIE4: StateNum = 161;
+pub const S_PLAY_DIE5: StateNum = 162;
+pub const S_PLAY_DIE6: StateNum = 163;
+pub const S_PLAY_DIE7: StateNum = 164;
+pub const S_PLAY_XDIE1: StateNum = 165;
+pub const S_PLAY_XDIE2: StateNum = 166;
+pub const S_PLAY_XDIE3: StateNum = 167;
+pub const S_PLAY_XDIE4: StateNum = 168;
+pub const S_PLAY_XDIE5: StateNum = 169;
+pub const S_PLAY_XDIE6: StateNum = 170;
+pub const S_PLAY_XDIE7: StateNum = 171;
+pub const S_PLAY_XDIE8: StateNum = 172;
+pub const S_PLAY_XDIE9: StateNum = 173;
+pub const S_POSS_STND: StateNum = 174;
+pub const S_POSS_STND2: StateNum = 175;
+pub const S_POSS_RUN1: StateNum = 176;
+pub const S_POSS_RUN2: StateNum = 177;
+pub const S_POSS_RUN3: StateNum = 178;
+pub const S_POSS_RUN4: StateNum = 179;
+pub const S_POSS_RUN5: StateNum = 180;
+pub const S_POSS_RUN6: StateNum = 181;
+pub const S_POSS_RUN7: StateNum = 182;
+pub const S_POSS_RUN8: StateNum = 183;
+pub const S_POSS_ATK1: StateNum = 184;
+pub const S_POSS_ATK2: StateNum = 185;
+pub const S_POSS_ATK3: StateNum = 186;
+pub const S_POSS_PAIN: StateNum = 187;
+pub const S_POSS_PAIN2: StateNum = 188;
+pub const S_POSS_DIE1: StateNum = 189;
+pub const S_POSS_DIE2: StateNum = 190;
+pub const S_POSS_DIE3: StateNum = 191;
+pub const S_POSS_DIE4: StateNum = 192;
+pub const S_POSS_DIE5: StateNum = 193;
+pub const S_POSS_XDIE1: StateNum = 194;
+pub const S_POSS_XDIE2: StateNum = 195;
+pub const S_POSS_XDIE3: StateNum = 196;
+pub const S_POSS_XDIE4: StateNum = 197;
+pub const S_POSS_XDIE5: StateNum = 198;
+pub const S_POSS_XDIE6: StateNum = 199;
+pub const S_POSS_XDIE7: StateNum = 200;
+pub const S_POSS_XDIE8: StateNum = 201;
+pub const S_POSS_XDIE9: StateNum = 202;
+pub const S_POSS_RAISE1: StateNum = 203;
+pub const S_POSS_RAISE2: StateNum = 204;
+pub const S_POSS_RAISE3: StateNum = 205;
+pub const S_POSS_RAISE4: StateNum = 206;
+pub const S_SPOS_STND: StateNum = 207;
+pub const S_SPOS_STND2: StateNum = 208;
+pub const S_SPOS_RUN1: StateNum = 209;
+pub const S_SPOS_RUN2: StateNum = 210;
+pub const S_SPOS_RUN3: StateNum = 211;
+pub const S_SPOS_RUN4: StateNum = 212;
+pub const S_SPOS_RUN5: StateNum = 213;
+pub const S_SPOS_RUN6: StateNum = 214;
+pub const S_SPOS_RUN7: StateNum = 215;
+pub const S_SPOS_RUN8: StateNum = 216;
+pub const S_SPOS_ATK1: StateNum = 217;
+pub const S_SPOS_ATK2: StateNum = 218;
+pub const S_SPOS_ATK3: StateNum = 219;
+pub const S_SPOS_PAIN: StateNum = 220;
+pub const S_SPOS_PAIN2: StateNum = 221;
+pub const S_SPOS_DIE1: StateNum = 222;
+pub const S_SPOS_DIE2: StateNum = 223;
+pub const S_SPOS_DIE3: StateNum = 224;
+pub const S_SPOS_DIE4: StateNum = 225;
+pub const S_SPOS_DIE5: StateNum = 226;
+pub const S_SPOS_XDIE1: StateNum = 227;
+pub const S_SPOS_XDIE2: StateNum = 228;
+pub const S_SPOS_XDIE3: StateNum = 229;
+pub const S_SPOS_XDIE4: StateNum = 230;
+pub const S_SPOS_XDIE5: StateNum = 231;
+pub const S_SPOS_XDIE6: StateNum = 232;
+pub const S_SPOS_XDIE7: StateNum = 233;
+pub const S_SPOS_XDIE8: StateNum = 234;
+pub const S_SPOS_XDIE9: StateNum = 235;
+pub const S_SPOS_RAISE1: StateNum = 236;
+pub const S_SPOS_RAISE2: StateNum = 237;
+pub const S_SPOS_RAISE3: StateNum = 238;
+pub const S_SPOS_RAISE4: StateNum = 239;
+pub const S_SPOS_RAISE5: StateNum = 240;
+pub const S_VILE_STND: StateNum = 241;
+pub const S_VILE_STND2: StateNum = 242;
+pub const S_VILE_RUN1: StateNum = 243;
+pub const S_VILE_RUN2: StateNum = 244;
+pub const S_VILE_RUN3: StateNum = 245;
+pub const S_VILE_RUN4: StateNum = 246;
+pub const S_VILE_RUN5: StateNum = 247;
+pub const S_VILE_RUN6: StateNum = 248;
+pub const S_VILE_RUN7: StateNum = 249;
+pub const S_VILE_RUN8: StateNum = 250;
+pub const S_VILE_RUN9: StateNum = 251;
+pub const S_VILE_RUN10: StateNum = 252;
+pub const S_VILE_RUN11: StateNum = 253;
+pub const S_VILE_RUN12: StateNum = 254;
+pub const S_VILE_ATK1: StateNum = 255;
+pub const S_VILE_ATK2: StateNum = 256;
+pub const S_VILE_ATK3: StateNum = 257;
+pub const S_VILE_ATK4: StateNum = 258;
+pub const S_VILE_ATK5: StateNum = 259;
+pub const S_VILE_ATK6: StateNum = 260;
+pub const S_VILE_ATK7: StateNum = 261;
+pub const S_VILE_ATK8: StateNum = 262;
+pub const S_VILE_ATK9: StateNum = 263;
+pub const S_VILE_ATK10: StateNum = 264;
+pub const S_VILE_ATK11: StateNum = 265;
+pub const S_VILE_HEAL1: StateNum = 266;
+pub const S_VILE_HEAL2: StateNum = 267;
+pub const S_VILE_HEAL3: StateNum = 268;
+pub const S_VILE_PAIN: StateNum = 269;
+pub const S_VILE_PAIN2: StateNum = 270;
+pub const S_VILE_DIE1: StateNum = 271;
+pub const S_VILE_DIE2: StateNum = 272;
+pub const S_VILE_DIE3: StateNum = 273;
+pub const S_VILE_DIE4: StateNum = 274;
+pub const S_VILE_DIE5: StateNum = 275;
+pub const S_VILE_DIE6: StateNum = 276;
+pub const S_VILE_DIE7: StateNum = 277;
+pub const S_VILE_DIE8: StateNum = 278;
+pub const S_VILE_DIE9: StateNum = 279;
+pub const S_VILE_DIE10: StateNum = 280;
+pub const S_FIRE1: StateNum = 281;
+pub const S_FIRE2: StateNum = 282;
+pub const S_FIRE3: StateNum = 283;
+pub const S_FIRE4: StateNum = 284;
+pub const S_FIRE5: StateNum = 285;
+pub const S_FIRE6: StateNum = 286;
+pub const S_FIRE7: StateNum = 287;
+pub const S_FIRE8: StateNum = 288;
+pub const S_FIRE9: StateNum = 289;
+pub const S_FIRE10: StateNum = 290;
+pub const S_FIRE11: StateNum = 291;
+pub const S_FIRE12: StateNum = 292;
+pub const S_FIRE13: StateNum = 293;
+pub const S_FIRE14: StateNum = 294;
+pub const S_FIRE15: StateNum = 295;
+pub const S_FIRE16: StateNum = 296;
+pub const S_FIRE17: StateNum = 297;
+pub const S_FIRE18: StateNum = 298;
+pub const S_FIRE19: StateNum = 299;
+pub const S_FIRE20: StateNum = 300;
+pub const S_FIRE21: StateNum = 301;
+pub const S_FIRE22: StateNum = 302;
+pub const S_FIRE23: StateNum = 303;
+pub const S_FIRE24: StateNum = 304;
+pub const S_FIRE25: StateNum = 305;
+pub const S_FIRE26: StateNum = 306;
+pub const S_FIRE27: StateNum = 307;
+pub const S_FIRE28: StateNum = 308;
+pub const S_FIRE29: StateNum = 309;
+pub const S_FIRE30: StateNum = 310;
+pub const S_SMOKE1: StateNum = 311;
+pub const S_SMOKE2: StateNum = 312;
+pub const S_SMOKE3: StateNum = 313;
+pub const S_SMOKE4: StateNum = 314;
+pub const S_SMOKE5: StateNum = 315;
+pub const S_TRACER: StateNum = 316;
+pub const S_TRACER2: StateNum = 317;
+pub const S_TRACEEXP1: StateNum = 318;
+pub const S_TRACEEXP2: StateNum = 319;
+pub const S_TRACEEXP3: StateNum = 320;
+pub const S_SKEL_STND: StateNum = 321;
+pub const S_SKEL_STND2: StateNum = 322;
+pub const S_SKEL_RUN1: StateNum = 323;
+pub const S_SKEL_RUN2: StateNum = 324;
+pub const S_SKEL_RUN3: StateNum = 325;
+pub const S_SKEL_RUN4: StateNum = 326;
+pub const S_SKEL_RUN5: StateNum = 327;
+pub const S_SKEL_RUN6: StateNum = 328;
+pub const S_SKEL_RUN7: StateNum = 329;
+pub const S_SKEL_RUN8: StateNum = 330;
+pub const S_SKEL_RUN9: StateNum = 331;
+pub const S_SKEL_RUN10: StateNum = 332;
+pub const S_SKEL_RUN11: StateNum = 333;
+pub const S_SKEL_RUN12: StateNum = 334;
+pub const S_SKEL_FIST1: StateNum = 335;
+pub const S_SKEL_FIST2: StateNum = 336;
+pub const S_SKEL_FIST3: StateNum = 337;
+pub const S_SKEL_FIST4: StateNum = 338;
+pub const S_SKEL_MISS1: StateNum = 339;
+pub const S_SKEL_MISS2: StateNum = 340;
+pub const S_SKEL_MISS3: StateNum = 341;
+pub const S_SKEL_MISS4: StateNum = 342;
+pub const S_SKEL_PAIN: StateNum = 343;
+pub const S_SKEL_PAIN2: StateNum = 344;
+pub const S_SKEL_DIE1: StateNum = 345;
+pub const S_SKEL_DIE2: StateNum = 346;
+pub const S_SKEL_DIE3: StateNum = 347;
+pub const S_SKEL_DIE4: StateNum = 348;
+pub const S_SKEL_DIE5: StateNum = 349;
+pub const S_SKEL_DIE6: StateNum = 350;
+pub const S_SKEL_RAISE1: StateNum = 351;
+pub const S_SKEL_RAISE2: StateNum = 352;
+pub const S_SKEL_RAISE3: StateNum = 353;
+pub const S_SKEL_RAISE4: StateNum = 354;
+pub const S_SKEL_RAISE5: StateNum = 355;
+pub const S_SKEL_RAISE6: StateNum = 356;
+pub const S_FATSHOT1: StateNum = 357;
+pub const S_FATSHOT2: StateNum = 358;
+pub const S_FATSHOTX1: StateNum = 359;
+pub const S_FATSHOTX2: StateNum = 360;
+pub const S_FATSHOTX3: StateNum = 361;
+pub const S_FATT_STND: StateNum = 362;
+pub const S_FATT_STND2: StateNum = 363;
+pub const S_FATT_RUN1: StateNum = 364;
+pub const S_FATT_RUN2: StateNum = 365;
+pub const S_FATT_RUN3: StateNum = 366;
+pub const S_FATT_RUN4: StateNum = 367;
+pub const S_FATT_RUN5: StateNum = 368;
+pub const S_FATT_RUN6: StateNum = 369;
+pub const S_FATT_RUN7: StateNum = 370;
+pub const S_FATT_RUN8: StateNum = 371;
+pub const S_FATT_RUN9: StateNum = 372;
+pub const S_FATT_RUN10: StateNum = 373;
+pub const S_FATT_RUN11: StateNum = 374;
+pub const S_FATT_RUN12: StateNum = 375;
+pub const S_FATT_ATK1: StateNum = 376;
+pub const S_FATT_ATK2: StateNum = 377;
+pub const S_FATT_ATK3: StateNum = 378;
+pub const S_FATT_ATK4: StateNum = 379;
+pub const S_FATT_ATK5: StateNum = 380;
+pub const S_FATT_ATK6: StateNum = 381;
+pub const S_FATT_ATK7: StateNum = 382;
+pub const S_FATT_ATK8: StateNum = 383;
+pub const S_FATT_ATK9: StateNum = 384;
+pub const S_FATT_ATK10: StateNum = 385;
+pub const S_FATT_PAIN: StateNum = 386;
+pub const S_FATT_PAIN2: StateNum = 387;
+pub const S_FATT_DIE1: StateNum = 388;
+pub const S_FATT_DIE2: StateNum = 389;
+pub const S_FATT_DIE3: StateNum = 390;
+pub const S_FATT_DIE4: StateNum = 391;
+pub const S_FATT_DIE5: StateNum = 392;
+pub const S_FATT_DIE6: StateNum = 393;
+pub const S_FATT_DIE7: StateNum = 394;
+pub const S_FATT_DIE8: StateNum = 395;
+pub const S_FATT_DIE9: StateNum = 396;
+pub const S_FATT_DIE10: StateNum = 397;
+pub const S_FATT_RAISE1: StateNum = 398;
+pub const S_FATT_RAISE2: StateNum = 399;
+pub const S_FATT_RAISE3: StateNum = 400;
+pub const S_FATT_RAISE4: StateNum = 401;
+pub const S_FATT_RAISE5: StateNum = 402;
+pub const S_FATT_RAISE6: StateNum = 403;
+pub const S_FATT_RAISE7: StateNum = 404;
+pub const S_FATT_RAISE8: StateNum = 405;
+pub const S_CPOS_STND: StateNum = 406;
+pub const S_CPOS_STND2: StateNum = 407;
+pub const S_CPOS_RUN1: StateNum = 408;
+pub const S_CPOS_RUN2: StateNum = 409;
+pub const S_CPOS_RUN3: StateNum = 410;
+pub const S_CPOS_RUN4: StateNum = 411;
+pub const S_CPOS_RUN5: StateNum = 412;
+pub const S_CPOS_RUN6: StateNum = 413;
+pub const S_CPOS_RUN7: StateNum = 414;
+pub const S_CPOS_RUN8: StateNum = 415;
+pub const S_CPOS_ATK1: StateNum = 416;
+pub const S_CPOS_ATK2: StateNum = 417;
+pub const S_CPOS_ATK3: StateNum = 418;
+pub const S_CPOS_ATK4: StateNum = 419;
+pub const S_CPOS_PAIN: StateNum = 420;
+pub const S_CPOS_PAIN2: StateNum = 421;
+pub const S_CPOS_DIE1: StateNum = 422;
+pub const S_CPOS_DIE2: StateNum = 423;
+pub const S_CPOS_DIE3: StateNum = 424;
+pub const S_CPOS_DIE4: StateNum = 425;
+pub const S_CPOS_DIE5: StateNum = 426;
+pub const S_CPOS_DIE6: StateNum = 427;
+pub const S_CPOS_DIE7: StateNum = 428;
+pub const S_CPOS_XDIE1: StateNum = 429;
+pub const S_CPOS_XDIE2: StateNum = 430;
+pub const S_CPOS_XDIE3: StateNum = 431;
+pub const S_CPOS_XDIE4: StateNum = 432;
+pub const S_CPOS_XDIE5: StateNum = 433;
+pub const S_CPOS_XDIE6: StateNum = 434;
+pub const S_CPOS_RAISE1: StateNum = 435;
+pub const S_CPOS_RAISE2: StateNum = 436;
+pub const S_CPOS_RAISE3: StateNum = 437;
+pub const S_CPOS_RAISE4: StateNum = 438;
+pub const S_CPOS_RAISE5: StateNum = 439;
+pub const S_CPOS_RAISE6: StateNum = 440;
+pub const S_CPOS_RAISE7: StateNum = 441;
+pub const S_TROO_STND: StateNum = 442;
+pub const S_TROO_STND2: StateNum = 443;
+pub const S_TROO_RUN1: StateNum = 444;
+pub const S_TROO_RUN2: StateNum = 445;
+pub const S_TROO_RUN3: StateNum = 446;
+pub const S_TROO_RUN4: StateNum = 447;
+pub const S_TROO_RUN5: StateNum = 448;
+pub const S_TROO_RUN6: StateNum = 449;
+pub const S_TROO_RUN7: StateNum = 450;
+pub const S_TROO_RUN8: StateNum = 451;
+pub const S_TROO_ATK1: StateNum = 452;
+pub const S_TROO_ATK2: StateNum = 453;
+pub const S_TROO_ATK3: StateNum = 454;
+pub const S_TROO_PAIN: StateNum = 455;
+pub const S_TROO_PAIN2: StateNum = 456;
+pub const S_TROO_DIE1: StateNum = 457;
+pub const S_TROO_DIE2: StateNum = 458;
+pub const S_TROO_DIE3: StateNum = 459;
+pub const S_TROO_DIE4: StateNum = 460;
+pub const S_TROO_DIE5: StateNum = 461;
+pub const S_TROO_XDIE1: StateNum = 462;
+pub const S_TROO_XDIE2: StateNum = 463;
+pub const S_TROO_XDIE3: StateNum = 464;
+pub const S_TROO_XDIE4: StateNum = 465;
+pub const S_TROO_XDIE5: StateNum = 466;
+pub const S_TROO_XDIE6: StateNum = 467;
+pub const S_TROO_XDIE7: StateNum = 468;
+pub const S_TROO_XDIE8: StateNum = 469;
+pub const S_TROO_RAISE1: StateNum = 470;
+pub const S_TROO_RAISE2: StateNum = 471;
+pub const S_TROO_RAISE3: StateNum = 472;
+pub const S_TROO_RAISE4: StateNum = 473;
+pub const S_TROO_RAISE5: StateNum = 474;
+pub const S_SARG_STND: StateNum = 475;
+pub const S_SARG_STND2: StateNum = 476;
+pub const S_SARG_RUN1: StateNum = 477;
+pub const S_SARG_RUN2: StateNum = 478;
+pub const S_SARG_RUN3: StateNum = 479;
+pub const S_SARG_RUN4: StateNum = 480;
+pub const S_SARG_RUN5: StateNum = 481;
+pub const S_SARG_RUN6: StateNum = 482;
+pub const S_SARG_RUN7: StateNum = 483;
+pub const S_SARG_RUN8: StateNum = 484;
+pub const S_SARG_ATK1: StateNum = 485;
+pub const S_SARG_ATK2: StateNum = 486;
+pub const S_SARG_ATK3: StateNum = 487;
+pub const S_SARG_PAIN: StateNum = 488;
+pub const S_SARG_PAIN2: StateNum = 489;
+pub const S_SARG_DIE1: StateNum = 490;
+pub const S_SARG_DIE2: StateNum = 491;
+pub const S_SARG_DIE3: StateNum = 492;
+pub const S_SARG_DIE4: StateNum = 493;
+pub const S_SARG_DIE5: StateNum = 494;
+pub const S_SARG_DIE6: StateNum = 495;
+pub const S_SARG_RAISE1: StateNum = 496;
+pub const S_SARG_RAISE2: StateNum = 497;
+pub const S_SARG_RAISE3: StateNum = 498;
+pub const S_SARG_RAISE4: StateNum = 499;
+pub const S_SARG_RAISE5: StateNum = 500;
+pub const S_SARG_RAISE6: StateNum = 501;
+pub const S_HEAD_STND: StateNum = 502;
+pub const S_HEAD_RUN1: StateNum = 503;
+pub const S_HEAD_ATK1: StateNum = 504;
+pub const S_HEAD_ATK2: StateNum = 505;
+pub const S_HEAD_ATK3: StateNum = 506;
+pub const S_HEAD_PAIN: StateNum = 507;
+pub const S_HEAD_PAIN2: StateNum = 508;
+pub const S_HEAD_PAIN3: StateNum = 509;
+pub const S_HEAD_DIE1: StateNum = 510;
+pub const S_HEAD_DIE2: StateNum = 511;
+pub const S_HEAD_DIE3: StateNum = 512;
+pub const S_HEAD_DIE4: StateNum = 513;
+pub const S_HEAD_DIE5: StateNum = 514;
+pub const S_HEAD_DIE6: StateNum = 515;
+pub const S_HEAD_RAISE1: StateNum = 516;
+pub const S_HEAD_RAISE2: StateNum = 517;
+pub const S_HEAD_RAISE3: StateNum = 518;
+pub const S_HEAD_RAISE4: StateNum = 519;
+pub const S_HEAD_RAISE5: StateNum = 520;
+pub const S_HEAD_RAISE6: StateNum = 521;
+pub const S_BRBALL1: StateNum = 522;
+pub const S_BRBALL2: StateNum = 523;
+pub const S_BRBALLX1: StateNum = 524;
+pub const S_BRBALLX2: StateNum = 525;
+pub const S_BRBALLX3: StateNum = 526;
+pub const S_BOSS_STND: StateNum = 527;
+pub const S_BOSS_STND2: StateNum = 528;
+pub const S_BOSS_RUN1: StateNum = 529;
+pub const S_BOSS_RUN2: StateNum = 530;
+pub const S_BOSS_RUN3: StateNum = 531;
+pub const S_BOSS_RUN4: StateNum = 532;
+pub const S_BOSS_RUN5: StateNum = 533;
+pub const S_BOSS_RUN6: StateNum = 534;
+pub const S_BOSS_RUN7: StateNum = 535;
+pub const S_BOSS_RUN8: StateNum = 536;
+pub const S_BOSS_ATK1: StateNum = 537;
+pub const S_BOSS_ATK2: StateNum = 538;
+pub const S_BOSS_ATK3: StateNum = 539;
+pub const S_BOSS_PAIN: StateNum = 540;
+pub const S_BOSS_PAIN2: StateNum = 541;
+pub const S_BOSS_DIE1: StateNum = 542;
+pub const S_BOSS_DIE2: StateNum = 543;
+pub const S_BOSS_DIE3: StateNum = 544;
+pub const S_BOSS_DIE4: StateNum = 545;
+pub const S_BOSS_DIE5: StateNum = 546;
+pub const S_BOSS_DIE6: StateNum = 547;
+pub const S_BOSS_DIE7: StateNum = 548;
+pub const S_BOSS_RAISE1: StateNum = 549;
+pub const S_BOSS_RAISE2: StateNum = 550;
+pub const S_BOSS_RAISE3: StateNum = 551;
+pub const S_BOSS_RAISE4: StateNum = 552;
+pub const S_BOSS_RAISE5: StateNum = 553;
+pub const S_BOSS_RAISE6: StateNum = 554;
+pub const S_BOSS_RAISE7: StateNum = 555;
+pub const S_BOS2_STND: StateNum = 556;
+pub const S_BOS2_STND2: StateNum = 557;
+pub const S_BOS2_RUN1: StateNum = 558;
+pub const S_BOS2_RUN2: StateNum = 559;
+pub const S_BOS2_RUN3: StateNum = 560;
+pub const S_BOS2_RUN4: StateNum = 561;
+pub const S_BOS2_RUN5: StateNum = 562;
+pub const S_BOS2_RUN6: StateNum = 563;
+pub const S_BOS2_RUN7: StateNum = 564;
+pub const S_BOS2_RUN8: StateNum = 565;
+pub const S_BOS2_ATK1: StateNum = 566;
+pub const S_BOS2_ATK2: StateNum = 567;
+pub const S_BOS2_ATK3: StateNum = 568;
+pub const S_BOS2_PAIN: StateNum = 569;
+pub const S_BOS2_PAIN2: StateNum = 570;
+pub const S_BOS2_DIE1: StateNum = 571;
+pub const S_BOS2_DIE2: StateNum = 572;
+pub const S_BOS2_DIE3: StateNum = 573;
+pub const S_BOS2_DIE4: StateNum = 574;
+pub const S_BOS2_DIE5: StateNum = 575;
+pub const S_BOS2_DIE6: StateNum = 576;
+pub const S_BOS2_DIE7: StateNum = 577;
+pub const S_BOS2_RAISE1: StateNum = 578;
+pub const S_BOS2_RAISE2: StateNum = 579;
+pub const S_BOS2_RAISE3: StateNum = 580;
+pub const S_BOS2_RAISE4: StateNum = 581;
+pub const S_BOS2_RAISE5: StateNum = 582;
+pub const S_BOS2_RAISE6: StateNum = 583;
+pub const S_BOS2_RAISE7: StateNum = 584;
+pub const S_SKULL_STND: StateNum = 585;
+pub const S_SKULL_STND2: StateNum = 586;
+pub const S_SKULL_RUN1: StateNum = 587;
+pub const S_SKULL_RUN2: StateNum = 588;
+pub const S_SKULL_ATK1: StateNum = 589;
+pub const S_SKULL_ATK2: StateNum = 590;
+pub const S_SKULL_ATK3: StateNum = 591;
+pub const S_SKULL_ATK4: StateNum = 592;
+pub const S_SKULL_PAIN: StateNum = 593;
+pub const S_SKULL_PAIN2: StateNum = 594;
+pub const S_SKULL_DIE1: StateNum = 595;
+pub const S_SKULL_DIE2: StateNum = 596;
+pub const S_SKULL_DIE3: StateNum = 597;
+pub const S_SKULL_DIE4: StateNum = 598;
+pub const S_SKULL_DIE5: StateNum = 599;
+pub const S_SKULL_DIE6: StateNum = 600;
+pub const S_SPID_STND: StateNum = 601;
+pub const S_SPID_STND2: StateNum = 602;
+pub const S_SPID_RUN1: StateNum = 603;
+pub const S_SPID_RUN2: StateNum = 604;
+pub const S_SPID_RUN3: StateNum = 605;
+pub const S_SPID_RUN4: StateNum = 606;
+pub const S_SPID_RUN5: StateNum = 607;
+pub const S_SPID_RUN6: StateNum = 608;
+pub const S_SPID_RUN7: StateNum = 609;
+pub const S_SPID_RUN8: StateNum = 610;
+pub const S_SPID_RUN9: StateNum = 611;
+pub const S_SPID_RUN10: StateNum = 612;
+pub const S_SPID_RUN11: StateNum = 613;
+pub const S_SPID_RUN12: StateNum = 614;
+pub const S_SPID_ATK1: StateNum = 615;
+pub const S_SPID_ATK2: StateNum = 616;
+pub const S_SPID_ATK3: StateNum = 617;
+pub const S_SPID_ATK4: StateNum = 618;
+pub const S_SPID_PAIN: StateNum = 619;
+pub const S_SPID_PAIN2: StateNum = 620;
+pub const S_SPID_DIE1: StateNum = 621;
+pub const S_SPID_DIE2: StateNum = 622;
+pub const S_SPID_DIE3: StateNum = 623;
+pub const S_SPID_DIE4: StateNum = 624;
+pub const S_SPID_DIE5: StateNum = 625;
+pub const S_SPID_DIE6: StateNum = 626;
+pub const S_SPID_DIE7: StateNum = 627;
+pub const S_SPID_DIE8: StateNum = 628;
+pub const S_SPID_DIE9: StateNum = 629;
+pub const S_SPID_DIE10: StateNum = 630;
+pub const S_SPID_DIE11: StateNum = 631;
+pub const S_BSPI_STND: StateNum = 632;
+pub const S_BSPI_STND2: StateNum = 633;
+pub const S_BSPI_SIGHT: StateNum = 634;
+pub const S_BSPI_RUN1: StateNum = 635;
+pub const S_BSPI_RUN2: StateNum = 636;
+pub const S_BSPI_RUN3: StateNum = 637;
+pub const S_BSPI_RUN4: StateNum = 638;
+pub const S_BSPI_RUN5: StateNum = 639;
+pub const S_BSPI_RUN6: StateNum = 640;
+pub const S_BSPI_RUN7: StateNum = 641;
+pub const S_BSPI_RUN8: StateNum = 642;
+pub const S_BSPI_RUN9: StateNum = 643;
+pub const S_BSPI_RUN10: StateNum = 644;
+pub const S_BSPI_RUN11: StateNum = 645;
+pub const S_BSPI_RUN12: StateNum = 646;
+pub const S_BSPI_ATK1: StateNum = 647;
+pub const S_BSPI_ATK2: StateNum = 648;
+pub const S_BSPI_ATK3: StateNum = 649;
+pub const S_BSPI_ATK4: StateNum = 650;
+pub const S_BSPI_PAIN: StateNum = 651;
+pub const S_BSPI_PAIN2: StateNum = 652;
+pub const S_BSPI_DIE1: StateNum = 653;
+pub const S_BSPI_DIE2: StateNum = 654;
+pub const S_BSPI_DIE3: StateNum = 655;
+pub const S_BSPI_DIE4: StateNum = 656;
+pub const S_BSPI_DIE5: StateNum = 657;
+pub const S_BSPI_DIE6: StateNum = 658;
+pub const S_BSPI_DIE7: StateNum = 659;
+pub const S_BSPI_RAISE1: StateNum = 660;
+pub const S_BSPI_RAISE2: StateNum = 661;
+pub const S_BSPI_RAISE3: StateNum = 662;
+pub const S_BSPI_RAISE4: StateNum = 663;
+pub const S_BSPI_RAISE5: StateNum = 664;
+pub const S_BSPI_RAISE6: StateNum = 665;
+pub const S_BSPI_RAISE7: StateNum = 666;
+pub const S_ARACH_PLAZ: StateNum = 667;
+pub const S_ARACH_PLAZ2: StateNum = 668;
+pub const S_ARACH_PLEX: StateNum = 669;
+pub const S_ARACH_PLEX2: StateNum = 670;
+pub const S_ARACH_PLEX3: StateNum = 671;
+pub const S_ARACH_PLEX4: StateNum = 672;
+pub const S_ARACH_PLEX5: StateNum = 673;
+pub const S_CYBER_STND: StateNum = 674;
+pub const S_CYBER_STND2: StateNum = 675;
+pub const S_CYBER_RUN1: StateNum = 676;
+pub const S_CYBER_RUN2: StateNum = 677;
+pub const S_CYBER_RUN3: StateNum = 678;
+pub const S_CYBER_RUN4: StateNum = 679;
+pub const S_CYBER_RUN5: StateNum = 680;
+pub const S_CYBER_RUN6: StateNum = 681;
+pub const S_CYBER_RUN7: StateNum = 682;
+pub const S_CYBER_RUN8: StateNum = 683;
+pub const S_CYBER_ATK1: StateNum = 684;
+pub const S_CYBER_ATK2: StateNum = 685;
+pub const S_CYBER_ATK3: StateNum = 686;
+pub const S_CYBER_ATK4: StateNum = 687;
+pub const S_CYBER_ATK5: StateNum = 688;
+pub const S_CYBER_ATK6: StateNum = 689;
+pub const S_CYBER_PAIN: StateNum = 690;
+pub const S_CYBER_DIE1: StateNum = 691;
+pub const S_CYBER_DIE2: StateNum = 692;
+pub const S_CYBER_DIE3: StateNum = 693;
+pub const S_CYBER_DIE4: StateNum = 694;
+pub const S_CYBER_DIE5: StateNum = 695;
+pub const S_CYBER_DIE6: StateNum = 696;
+pub const S_CYBER_DIE7: StateNum = 697;
+pub const S_CYBER_DIE8: StateNum = 698;
+pub const S_CYBER_DIE9: StateNum = 699;
+pub const S_CYBER_DIE10: StateNum = 700;
+pub const S_PAIN_STND: StateNum = 701;
+pub const S_PAIN_RUN1: StateNum = 702;
+pub const S_PAIN_RUN2: StateNum = 703;
+pub const S_PAIN_RUN3: StateNum = 704;
+pub const S_PAIN_RUN4: StateNum = 705;
+pub const S_PAIN_RUN5: StateNum = 706;
+pub const S_PAIN_RUN6: StateNum = 707;
+pub const S_PAIN_ATK1: StateNum = 708;
+pub const S_PAIN_ATK2: StateNum = 709;
+pub const S_PAIN_ATK3: StateNum = 710;
+pub const S_PAIN_ATK4: StateNum = 711;
+pub const S_PAIN_PAIN: StateNum = 712;
+pub const S_PAIN_PAIN2: StateNum = 713;
+pub const S_PAIN_DIE1: StateNum = 714;
+pub const S_PAIN_DIE2: StateNum = 715;
+pub const S_PAIN_DIE3: StateNum = 716;
+pub const S_PAIN_DIE4: StateNum = 717;
+pub const S_PAIN_DIE5: StateNum = 718;
+pub const S_PAIN_DIE6: StateNum = 719;
+pub const S_PAIN_RAISE1: StateNum = 720;
+pub const S_PAIN_RAISE2: StateNum = 721;
+pub const S_PAIN_RAISE3: StateNum = 722;
+pub const S_PAIN_RAISE4: StateNum = 723;
+pub const S_PAIN_RAISE5: StateNum = 724;
+pub const S_PAIN_RAISE6: StateNum = 725;
+pub const S_SSWV_STND: StateNum = 726;
+pub const S_SSWV_STND2: StateNum = 727;
+pub const S_SSWV_RUN1: StateNum = 728;
+pub const S_SSWV_RUN2: StateNum = 729;
+pub const S_SSWV_RUN3: StateNum = 730;
+pub const S_SSWV_RUN4: StateNum = 731;
+pub const S_SSWV_RUN5: StateNum = 732;
+pub const S_SSWV_RUN6: StateNum = 733;
+pub const S_SSWV_RUN7: StateNum = 734;
+pub const S_SSWV_RUN8: StateNum = 735;
+pub const S_SSWV_ATK1: StateNum = 736;
+pub const S_SSWV_ATK2: StateNum = 737;
+pub const S_SSWV_ATK3: StateNum = 738;
+pub const S_SSWV_ATK4: StateNum = 739;
+pub const S_SSWV_ATK5: StateNum = 740;
+pub const S_SSWV_ATK6: StateNum = 741;
+pub const S_SSWV_PAIN: StateNum = 742;
+pub const S_SSWV_PAIN2: StateNum = 743;
+pub const S_SSWV_DIE1: StateNum = 744;
+pub const S_SSWV_DIE2: StateNum = 745;
+pub const S_SSWV_DIE3: StateNum = 746;
+pub const S_SSWV_DIE4: StateNum = 747;
+pub const S_SSWV_DIE5: StateNum = 748;
+pub const S_SSWV_XDIE1: StateNum = 749;
+pub const S_SSWV_XDIE2: StateNum = 750;
+pub const S_SSWV_XDIE3: StateNum = 751;
+pub const S_SSWV_XDIE4: StateNum = 752;
+pub const S_SSWV_XDIE5: StateNum = 753;
+pub const S_SSWV_XDIE6: StateNum = 754;
+pub const S_SSWV_XDIE7: StateNum = 755;
+pub const S_SSWV_XDIE8: StateNum = 756;
+pub const S_SSWV_XDIE9: StateNum = 757;
+pub const S_SSWV_RAISE1: StateNum = 758;
+pub const S_SSWV_RAISE2: StateNum = 759;
+pub const S_SSWV_RAISE3: StateNum = 760;
+pub const S_SSWV_RAISE4: StateNum = 761;
+pub const S_SSWV_RAISE5: StateNum = 762;
+pub const S_KEENSTND: StateNum = 763;
+pub const S_COMMKEEN: StateNum = 764;
+pub const S_COMMKEEN2: StateNum = 765;
+pub const S_COMMKEEN3: StateNum = 766;
+pub const S_COMMKEEN4: StateNum = 767;
+pub const S_COMMKEEN5: StateNum = 768;
+pub const S_COMMKEEN6: StateNum = 769;
+pub const S_COMMKEEN7: StateNum = 770;
+pub const S_COMMKEEN8: StateNum = 771;
+pub const S_COMMKEEN9: StateNum = 772;
+pub const S_COMMKEEN10: StateNum = 773;
+pub const S_COMMKEEN11: StateNum = 774;
+pub const S_COMMKEEN12: StateNum = 775;
+pub const S_KEENPAIN: StateNum = 776;
+pub const S_KEENPAIN2: StateNum = 777;
+pub const S_BRAIN: StateNum = 778;
+pub const S_BRAIN_PAIN: StateNum = 779;
+pub const S_BRAIN_DIE1: StateNum = 780;
+pub const S_BRAIN_DIE2: StateNum = 781;
+pub const S_BRAIN_DIE3: StateNum = 782;
+pub const S_BRAIN_DIE4: StateNum = 783;
+pub const S_BRAINEYE: StateNum = 784;
+pub const S_BRAINEYESEE: StateNum = 785;
+pub const S_BRAINEYE1: StateNum = 786;
+pub const S_SPAWN1: StateNum = 787;
+pub const S_SPAWN2: StateNum = 788;
+pub const S_SPAWN3: StateNum = 789;
+pub const S_SPAWN4: StateNum = 790;
+pub const S_SPAWNFIRE1: StateNum = 791;
+pub const S_SPAWNFIRE2: StateNum = 792;
+pub const S_SPAWNFIRE3: StateNum = 793;
+pub const S_SPAWNFIRE4: StateNum = 794;
+pub const S_SPAWNFIRE5: StateNum = 795;
+pub const S_SPAWNFIRE6: StateNum = 796;
+pub const S_SPAWNFIRE7: StateNum = 797;
+pub const S_SPAWNFIRE8: StateNum = 798;
+pub const S_BRAINEXPLODE1: StateNum = 799;
+pub const S_BRAINEXPLODE2: StateNum = 800;
+pub const S_BRAINEXPLODE3: StateNum = 801;
+pub const S_ARM1: StateNum = 802;
+pub const S_ARM1A: StateNum = 803;
+pub const S_ARM2: StateNum = 804;
+pub const S_ARM2A: StateNum = 805;
+pub const S_BAR1: StateNum = 806;
+pub const S_BAR2: StateNum = 807;
+pub const S_BEXP: StateNum = 808;
+pub const S_BEXP2: StateNum = 809;
+pub const S_BEXP3: StateNum = 810;
+pub const S_BEXP4: StateNum = 811;
+pub const S_BEXP5: StateNum = 812;
+pub const S_BBAR1: StateNum = 813;
+pub const S_BBAR2: StateNum = 814;
+pub const S_BBAR3: StateNum = 815;
+pub const S_BON1: StateNum = 816;
+pub const S_BON1A: StateNum = 817;
+pub const S_BON1B: StateNum = 818;
+pub const S_BON1C: StateNum = 819;
+pub const S_BON1D: StateNum = 820;
+pub const S_BON1E: StateNum = 821;
+pub const S_BON2: StateNum = 822;
+pub const S_BON2A: StateNum = 823;
+pub const S_BON2B: StateNum = 824;
+pub const S_BON2C: StateNum = 825;
+pub const S_BON2D: StateNum = 826;
+pub const S_BON2E: StateNum = 827;
+pub const S_BKEY: StateNum = 828;
+pub const S_BKEY2: StateNum = 829;
+pub const S_RKEY: StateNum = 830;
+pub const S_RKEY2: StateNum = 831;
+pub const S_YKEY: StateNum = 832;
+pub const S_YKEY2: StateNum = 833;
+pub const S_BSKULL: StateNum = 834;
+pub const S_BSKULL2: StateNum = 835;
+pub const S_RSKULL: StateNum = 836;
+pub const S_RSKULL2: StateNum = 837;
+pub const S_YSKULL: StateNum = 838;
+pub const S_YSKULL2: StateNum = 839;
+pub const S_STIM: StateNum = 840;
+pub const S_MEDI: StateNum = 841;
+pub const S_SOUL: StateNum = 842;
+pub const S_SOUL2: StateNum = 843;
+pub const S_SOUL3: StateNum = 844;
+pub const S_SOUL4: StateNum = 845;
+pub const S_SOUL5: StateNum = 846;
+pub const S_SOUL6: StateNum = 847;
+pub const S_PINV: StateNum = 848;
+pub const S_PINV2: StateNum = 849;
+pub const S_PINV3: StateNum = 850;
+pub const S_PINV4: StateNum = 851;
+pub const S_PSTR: StateNum = 852;
+pub const S_PINS: StateNum = 853;
+pub const S_PINS2: StateNum = 854;
+pub const S_PINS3: StateNum = 855;
+pub const S_PINS4: StateNum = 856;
+pub const S_MEGA: StateNum = 857;
+pub const S_MEGA2: StateNum = 858;
+pub const S_MEGA3: StateNum = 859;
+pub const S_MEGA4: StateNum = 860;
+pub const S_SUIT: StateNum = 861;
+pub const S_PMAP: StateNum = 862;
+pub const S_PMAP2: StateNum = 863;
+pub const S_PMAP3: StateNum = 864;
+pub const S_PMAP4: StateNum = 865;
+pub const S_PMAP5: StateNum = 866;
+pub const S_PMAP6: StateNum = 867;
+pub const S_PVIS: StateNum = 868;
+pub const S_PVIS2: StateNum = 869;
+pub const S_CLIP: StateNum = 870;
+pub const S_AMMO: StateNum = 871;
+pub const S_ROCK: StateNum = 872;
+pub const S_BROK: StateNum = 873;
+pub const S_CELL: StateNum = 874;
+pub const S_CELP: StateNum = 875;
+pub const S_SHEL: StateNum = 876;
+pub const S_SBOX: StateNum = 877;
+pub const S_BPAK: StateNum = 878;
+pub const S_BFUG: StateNum = 879;
+pub const S_MGUN: StateNum = 880;
+pub const S_CSAW: StateNum = 881;
+pub const S_LAUN: StateNum = 882;
+pub const S_PLAS: StateNum = 883;
+pub const S_SHOT: StateNum = 884;
+pub const S_SHOT2: StateNum = 885;
+pub const S_COLU: StateNum = 886;
+pub const S_STALAG: StateNum = 887;
+pub const S_BLOODYTWITCH: StateNum = 888;
+pub const S_BLOODYTWITCH2: StateNum = 889;
+pub const S_BLOODYTWITCH3: StateNum = 890;
+pub const S_BLOODYTWITCH4: StateNum = 891;
+pub const S_DEADTORSO: StateNum = 892;
+pub const S_DEADBOTTOM: StateNum = 893;
+pub const S_HEADSONSTICK: StateNum = 894;
+pub const S_GIBS: StateNum = 895;
+pub const S_HEADONASTICK: StateNum = 896;
+pub const S_HEADCANDLES: StateNum = 897;
+pub const S_HEADCANDLES2: StateNum = 898;
+pub const S_DEADSTICK: StateNum = 899;
+pub const S_LIVESTICK: StateNum = 900;
+pub const S_LIVESTICK2: StateNum = 901;
+pub const S_MEAT2: StateNum = 902;
+pub const S_MEAT3: StateNum = 903;
+pub const S_MEAT4: StateNum = 904;
+pub const S_MEAT5: StateNum = 905;
+pub const S_STALAGTITE: StateNum = 906;
+pub const S_TALLGRNCOL: StateNum = 907;
+pub const S_SHRTGRNCOL: StateNum = 908;
+pub const S_TALLREDCOL: StateNum = 909;
+pub const S_SHRTREDCOL: StateNum = 910;
+pub const S_CANDLESTIK: StateNum = 911;
+pub const S_CANDELABRA: StateNum = 912;
+pub const S_SKULLCOL: StateNum = 913;
+pub const S_TORCHTREE: StateNum = 914;
+pub const S_BIGTREE: StateNum = 915;
+pub const S_TECHPILLAR: StateNum = 916;
+pub const S_EVILEYE: StateNum = 917;
+pub const S_EVILEYE2: StateNum = 918;
+pub const S_EVILEYE3: StateNum = 919;
+pub const S_EVILEYE4: StateNum = 920;
+pub const S_FLOATSKULL: StateNum = 921;
+pub const S_FLOATSKULL2: StateNum = 922;
+pub const S_FLOATSKULL3: StateNum = 923;
+pub const S_HEARTCOL: StateNum = 924;
+pub const S_HEARTCOL2: StateNum = 925;
+pub const S_BLUETORCH: StateNum = 926;
+pub const S_BLUETORCH2: StateNum = 927;
+pub const S_BLUETORCH3: StateNum = 928;
+pub const S_BLUETORCH4: StateNum = 929;
+pub const S_GREENTORCH: StateNum = 930;
+pub const S_GREENTORCH2: StateNum = 931;
+pub const S_GREENTORCH3: StateNum = 932;
+pub const S_GREENTORCH4: StateNum = 933;
+pub const S_REDTORCH: StateNum = 934;
+pub const S_REDTORCH2: StateNum = 935;
+pub const S_REDTORCH3: StateNum = 936;
+pub const S_REDTORCH4: StateNum = 937;
+pub const S_BTORCHSHRT: StateNum = 938;
+pub const S_BTORCHSHRT2: StateNum = 939;
+pub const S_BTORCHSHRT3: StateNum = 940;
+pub const S_BTORCHSHRT4: StateNum = 941;
+pub const S_GTORCHSHRT: StateNum = 942;
+pub const S_GTORCHSHRT2: StateNum = 943;
+pub const S_GTORCHSHRT3: StateNum = 944;
+pub const S_GTORCHSHRT4: StateNum = 945;
+pub const S_RTORCHSHRT: StateNum = 946;
+pub const S_RTORCHSHRT2: StateNum = 947;
+pub const S_RTORCHSHRT3: StateNum = 948;
+pub const S_RTORCHSHRT4: StateNum = 949;
+pub const S_HANGNOGUTS: StateNum = 950;
+pub const S_HANGBNOBRAIN: StateNum = 951;
+pub const S_HANGTLOOKDN: StateNum = 952;
+pub const S_HANGTSKULL: StateNum = 953;
+pub const S_HANGTLOOKUP: StateNum = 954;
+pub const S_HANGTNOBRAIN: StateNum = 955;
+pub const S_COLONGIBS: StateNum = 956;
+pub const S_SMALLPOOL: StateNum = 957;
+pub const S_BRAINSTEM: StateNum = 958;
+pub const S_TECHLAMP: StateNum = 959;
+pub const S_TECHLAMP2: StateNum = 960;
+pub const S_TECHLAMP3: StateNum = 961;
+pub const S_TECHLAMP4: StateNum = 962;
+pub const S_TECH2LAMP: StateNum = 963;
+pub const S_TECH2LAMP2: StateNum = 964;
+pub const S_TECH2LAMP3: StateNum = 965;
+pub const S_TECH2LAMP4: StateNum = 966;
+
+pub const NUMSTATES: usize = 967;
+
+#[rustfmt::skip]
+pub static STATES: [State; NUMSTATES] = [
+    State::new(SPR_TROO, 0, -1, Action::None, S_NULL),
+    State::new(SPR_SHTG, 4, 0, Action::Light0, S_NULL),
+    State::new(SPR_PUNG, 0, 1, Action::WeaponReady, S_PUNCH),
+    State::new(SPR_PUNG, 0, 1, Action::Lower, S_PUNCHDOWN),
+    State::new(SPR_PUNG, 0, 1, Action::Raise, S_PUNCHUP),
+    State::new(SPR_PUNG, 1, 4, Action::None, S_PUNCH2),
+    State::new(SPR_PUNG, 2, 4, Action::Punch, S_PUNCH3),
+    State::new(SPR_PUNG, 3, 5, Action::None, S_PUNCH4),
+    State::new(SPR_PUNG, 2, 4, Action::None, S_PUNCH5),
+    State::new(SPR_PUNG, 1, 5, Action::ReFire, S_PUNCH),
+    State::new(SPR_PISG, 0, 1, Action::WeaponReady, S_PISTOL),
+    State::new(SPR_PISG, 0, 1, Action::Lower, S_PISTOLDOWN),
+    State::new(SPR_PISG, 0, 1, Action::Raise, S_PISTOLUP),
+    State::new(SPR_PISG, 0, 4, Action::None, S_PISTOL2),
+    State::new(SPR_PISG, 1, 6, Action::FirePistol, S_PISTOL3),
+    State::new(SPR_PISG, 2, 4, Action::None, S_PISTOL4),
+    State::new(SPR_PISG, 1, 5, Action::ReFire, S_PISTOL),
+    State::new(SPR_PISF, FF_FULLBRIGHT, 7, Action::Light1, S_LIGHTDONE),
+    State::new(SPR_SHTG, 0, 1, Action::WeaponReady, S_SGUN),
+    State::new(SPR_SHTG, 0, 1, Action::Lower, S_SGUNDOWN),
+    State::new(SPR_SHTG, 0, 1, Action::Raise, S_SGUNUP),
+    State::new(SPR_SHTG, 0, 3, Action::None, S_SGUN2),
+    State::new(SPR_SHTG, 0, 7, Action::FireShotgun, S_SGUN3),
+    State::new(SPR_SHTG, 1, 5, Action::None, S_SGUN4),
+    State::new(SPR_SHTG, 2, 5, Action::None, S_SGUN5),
+    State::new(SPR_SHTG, 3, 4, Action::None, S_SGUN6),
+    State::new(SPR_SHTG, 2, 5, Action::None, S_SGUN7),
+    State::new(SPR_SHTG, 1, 5, Action::None, S_SGUN8),
+    State::new(SPR_SHTG, 0, 3, Action::None, S_SGUN9),
+    State::new(SPR_SHTG, 0, 7, Action::ReFire, S_SGUN),
+    State::new(SPR_SHTF, FF_FULLBRIGHT, 4, Action::Light1, S_SGUNFLASH2),
+    State::new(SPR_SHTF, FF_FULLBRIGHT | 1, 3, Action::Light2, S_LIGHTDONE),
+    State::new(SPR_SHT2, 0, 1, Action::WeaponReady, S_DSGUN),
+    State::new(SPR_SHT2, 0, 1, Action::Lower, S_DSGUNDOWN),
+    State::new(SPR_SHT2, 0, 1, Action::Raise, S_DSGUNUP),
+    State::new(SPR_SHT2, 0, 3, Action::None, S_DSGUN2),
+    State::new(SPR_SHT2, 0, 7, Action::FireShotgun2, S_DSGUN3),
+    State::new(SPR_SHT2, 1, 7, Action::None, S_DSGUN4),
+    State::new(SPR_SHT2, 2, 7, Action::CheckReload, S_DSGUN5),
+    State::new(SPR_SHT2, 3, 7, Action::OpenShotgun2, S_DSGUN6),
+    State::new(SPR_SHT2, 4, 7, Action::None, S_DSGUN7),
+    State::new(SPR_SHT2, 5, 7, Action::LoadShotgun2, S_DSGUN8),
+    State::new(SPR_SHT2, 6, 6, Action::None, S_DSGUN9),
+    State::new(SPR_SHT2, 7, 6, Action::CloseShotgun2, S_DSGUN10),
+    State::new(SPR_SHT2, 0, 5, Action::ReFire, S_DSGUN),
+    State::new(SPR_SHT2, 1, 7, Action::None, S_DSNR2),
+    State::new(SPR_SHT2, 0, 3, Action::None, S_DSGUNDOWN),
+    State::new(SPR_SHT2, FF_FULLBRIGHT | 8, 5, Action::Light1, S_DSGUNFLASH2),
+    State::new(SPR_SHT2, FF_FULLBRIGHT | 9, 4, Action::Light2, S_LIGHTDONE),
+    State::new(SPR_CHGG, 0, 1, Action::WeaponReady, S_CHAIN),
+    State::new(SPR_CHGG, 0, 1, Action::Lower, S_CHAINDOWN),
+    State::new(SPR_CHGG, 0, 1, Action::Raise, S_CHAINUP),
+    State::new(SPR_CHGG, 0, 4, Action::FireCGun, S_CHAIN2),
+    State::new(SPR_CHGG, 1, 4, Action::FireCGun, S_CHAIN3),
+    State::new(SPR_CHGG, 1, 0, Action::ReFire, S_CHAIN),
+    State::new(SPR_CHGF, FF_FULLBRIGHT, 5, Action::Light1, S_LIGHTDONE),
+    State::new(SPR_CHGF, FF_FULLBRIGHT | 1, 5, Action::Light2, S_LIGHTDONE),
+    State::new(SPR_MISG, 0, 1, Action::WeaponReady, S_MISSILE),
+    State::new(SPR_MISG, 0, 1, Action::Lower, S_MISSILEDOWN),
+    State::new(SPR_MISG, 0, 1, Action::Raise, S_MISSILEUP),
+    State::new(SPR_MISG, 1, 8, Action::GunFlash, S_MISSILE2),
+    State::new(SPR_MISG, 1, 12, Action::FireMissile, S_MISSILE3),
+    State::new(SPR_MISG, 1, 0, Action::ReFire, S_MISSILE),
+    State::new(SPR_MISF, FF_FULLBRIGHT, 3, Action::Light1, S_MISSILEFLASH2),
+    State::new(SPR_MISF, FF_FULLBRIGHT | 1, 4, Action::None, S_MISSILEFLASH3),
+    State::new(SPR_MISF, FF_FULLBRIGHT | 2, 4, Action::Light2, S_MISSILEFLASH4),
+    State::new(SPR_MISF, FF_FULLBRIGHT | 3, 4, Action::Light2, S_LIGHTDONE),
+    State::new(SPR_SAWG, 2, 4, Action::WeaponReady, S_SAWB),
+    State::new(SPR_SAWG, 3, 4, Action::WeaponReady, S_SAW),
+    State::new(SPR_SAWG, 2, 1, Action::Lower, S_SAWDOWN),
+    State::new(SPR_SAWG, 2, 1, Action::Raise, S_SAWUP),
+    State::new(SPR_SAWG, 0, 4, Action::Saw, S_SAW2),
+    State::new(SPR_SAWG, 1, 4, Action::Saw, S_SAW3),
+    State::new(SPR_SAWG, 1, 0, Action::ReFire, S_SAW),
+    State::new(SPR_PLSG, 0, 1, Action::WeaponReady, S_PLASMA),
+    State::new(SPR_PLSG, 0, 1, Action::Lower, S_PLASMADOWN),
+    State::new(SPR_PLSG, 0, 1, Action::Raise, S_PLASMAUP),
+    State::new(SPR_PLSG, 0, 3, Action::FirePlasma, S_PLASMA2),
+    State::new(SPR_PLSG, 1, 20, Action::ReFire, S_PLASMA),
+    State::new(SPR_PLSF, FF_FULLBRIGHT, 4, Action::Light1, S_LIGHTDONE),
+    State::new(SPR_PLSF, FF_FULLBRIGHT | 1, 4, Action::Light1, S_LIGHTDONE),
+    State::new(SPR_BFGG, 0, 1, Action::WeaponReady, S_BFG),
+    State::new(SPR_BFGG, 0, 1, Action::Lower, S_BFGDOWN),
+    State::new(SPR_BFGG, 0, 1, Action::Raise, S_BFGUP),
+    State::new(SPR_BFGG, 0, 20, Action::BfgSound, S_BFG2),
+    State::new(SPR_BFGG, 1, 10, Action::GunFlash, S_BFG3),
+    State::new(SPR_BFGG, 1, 10, Action::FireBfg, S_BFG4),
+    State::new(SPR_BFGG, 1, 20, Action::ReFire, S_BFG),
+    State::new(SPR_BFGF, FF_FULLBRIGHT, 11, Action::Light1, S_BFGFLASH2),
+    State::new(SPR_BFGF, FF_FULLBRIGHT | 1, 6, Action::Light2, S_LIGHTDONE),
+    State::new(SPR_BLUD, 2, 8, Action::None, S_BLOOD2),
+    State::new(SPR_BLUD, 1, 8, Action::None, S_BLOOD3),
+    State::new(SPR_BLUD, 0, 8, Action::None, S_NULL),
+    State::new(SPR_PUFF, FF_FULLBRIGHT, 4, Action::None, S_PUFF2),
+    State::new(SPR_PUFF, 1, 4, Action::None, S_PUFF3),
+    State::new(SPR_PUFF, 2, 4, Action::None, S_PUFF4),
+    State::new(SPR_PUFF, 3, 4, Action::None, S_NULL),
+    State::new(SPR_BAL1, FF_FULLBRIGHT, 4, Action::None, S_TBALL2),
+    State::new(SPR_BAL1, FF_FULLBRIGHT | 1, 4, Action::None, S_TBALL1),
+    State::new(SPR_BAL1, FF_FULLBRIGHT | 2, 6, Action::None, S_TBALLX2),
+    State::new(SPR_BAL1, FF_FULLBRIGHT | 3, 6, Action::None, S_TBALLX3),
+    State::new(SPR_BAL1, FF_FULLBRIGHT | 4, 6, Action::None, S_NULL),
+    State::new(SPR_BAL2, FF_FULLBRIGHT, 4, Action::None, S_RBALL2),
+    State::new(SPR_BAL2, FF_FULLBRIGHT | 1, 4, Action::None, S_RBALL1),
+    State::new(SPR_BAL2, FF_FULLBRIGHT | 2, 6, Action::None, S_RBALLX2),
+    State::new(SPR_BAL2, FF_FULLBRIGHT | 3, 6, Action::None, S_RBALLX3),
+    State::new(SPR_BAL2, FF_FULLBRIGHT | 4, 6, Action::None, S_NULL),
+    State::new(SPR_PLSS, FF_FULLBRIGHT, 6, Action::None, S_PLASBALL2),
+    State::new(SPR_PLSS, FF_FULLBRIGHT | 1, 6, Action::None, S_PLASBALL),
+    State::new(SPR_PLSE, FF_FULLBRIGHT, 4, Action::None, S_PLASEXP2),
+    State::new(SPR_PLSE, FF_FULLBRIGHT | 1, 4, Action::None, S_PLASEXP3),
+    State::new(SPR_PLSE, FF_FULLBRIGHT | 2, 4, Action::None, S_PLASEXP4),
+    State::new(SPR_PLSE, FF_FULLBRIGHT | 3, 4, Action::None, S_PLASEXP5),
+    State::new(SPR_PLSE, FF_FULLBRIGHT | 4, 4, Action::None, S_NULL),
+    State::new(SPR_MISL, FF_FULLBRIGHT, 1, Action::None, S_ROCKET),
+    State::new(SPR_BFS1, FF_FULLBRIGHT, 4, Action::None, S_BFGSHOT2),
+    State::new(SPR_BFS1, FF_FULLBRIGHT | 1, 4, Action::None, S_BFGSHOT),
+    State::new(SPR_BFE1, FF_FULLBRIGHT, 8, Action::None, S_BFGLAND2),
+    State::new(SPR_BFE1, FF_FULLBRIGHT | 1, 8, Action::None, S_BFGLAND3),
+    State::new(SPR_BFE1, FF_FULLBRIGHT | 2, 8, Action::BfgSpray, S_BFGLAND4),
+    State::new(SPR_BFE1, FF_FULLBRIGHT | 3, 8, Action::None, S_BFGLAND5),
+    State::new(SPR_BFE1, FF_FULLBRIGHT | 4, 8, Action::None, S_BFGLAND6),
+    State::new(SPR_BFE1, FF_FULLBRIGHT | 5, 8, Action::None, S_NULL),
+    State::new(SPR_BFE2, FF_FULLBRIGHT, 8, Action::None, S_BFGEXP2),
+    State::new(SPR_BFE2, FF_FULLBRIGHT | 1, 8, Action::None, S_BFGEXP3),
+    State::new(SPR_BFE2, FF_FULLBRIGHT | 2, 8, Action::None, S_BFGEXP4),
+    State::new(SPR_BFE2, FF_FULLBRIGHT | 3, 8, Action::None, S_NULL),
+    State::new(SPR_MISL, FF_FULLBRIGHT | 1, 8, Action::Explode, S_EXPLODE2),
+    State::new(SPR_MISL, FF_FULLBRIGHT | 2, 6, Action::None, S_EXPLODE3),
+    State::new(SPR_MISL, FF_FULLBRIGHT | 3, 4, Action::None, S_NULL),
+    State::new(SPR_TFOG, FF_FULLBRIGHT, 6, Action::None, S_TFOG01),
+    State::new(SPR_TFOG, FF_FULLBRIGHT | 1, 6, Action::None, S_TFOG02),
+    State::new(SPR_TFOG, FF_FULLBRIGHT, 6, Action::None, S_TFOG2),
+    State::new(SPR_TFOG, FF_FULLBRIGHT | 1, 6, Action::None, S_TFOG3),
+    State::new(SPR_TFOG, FF_FULLBRIGHT | 2, 6, Action::None, S_TFOG4),
+    State::new(SPR_TFOG, FF_FULLBRIGHT | 3, 6, Action::None, S_TFOG5),
+    State::new(SPR_TFOG, FF_FULLBRIGHT | 4, 6, Action::None, S_TFOG6),
+    State::new(SPR_TFOG, FF_FULLBRIGHT | 5, 6, Action::None, S_TFOG7),
+    State::new(SPR_TFOG, FF_FULLBRIGHT | 6, 6, Action::None, S_TFOG8),
+    State::new(SPR_TFOG, FF_FULLBRIGHT | 7, 6, Action::None, S_TFOG9),
+    State::new(SPR_TFOG, FF_FULLBRIGHT | 8, 6, Action::None, S_TFOG10),
+    State::new(SPR_TFOG, FF_FULLBRIGHT | 9, 6, Action::None, S_NULL),
+    State::new(SPR_IFOG, FF_FULLBRIGHT, 6, Action::None, S_IFOG01),
+    State::new(SPR_IFOG, FF_FULLBRIGHT | 1, 6, Action::None, S_IFOG02),
+    State::new(SPR_IFOG, FF_FULLBRIGHT, 6, Action::None, S_IFOG2),
+    State::new(SPR_IFOG, FF_FULLBRIGHT | 1, 6, Action::None, S_IFOG3),
+    State::new(SPR_IFOG, FF_FULLBRIGHT | 2, 6, Action::None, S_IFOG4),
+    State::new(SPR_IFOG, FF_FULLBRIGHT | 3, 6, Action::None, S_IFOG5),
+    State::new(SPR_IFOG, FF_FULLBRIGHT | 4, 6, Action::None, S_NULL),
+    State::new(SPR_PLAY, 0, -1, Action::None, S_NULL),
+    State::new(SPR_PLAY, 0, 4, Action::None, S_PLAY_RUN2),
+    State::new(SPR_PLAY, 1, 4, Action::None, S_PLAY_RUN3),
+    State::new(SPR_PLAY, 2, 4, Action::None, S_PLAY_RUN4),
+    State::new(SPR_PLAY, 3, 4, Action::None, S_PLAY_RUN1),
+    State::new(SPR_PLAY, 4, 12, Action::None, S_PLAY),
+    State::new(SPR_PLAY, FF_FULLBRIGHT | 5, 6, Action::None, S_PLAY_ATK1),
+    State::new(SPR_PLAY, 6, 4, Action::None, S_PLAY_PAIN2),
+    State::new(SPR_PLAY, 6, 4, Action::Pain, S_PLAY),
+    State::new(SPR_PLAY, 7, 10, Action::None, S_PLAY_DIE2),
+    State::new(SPR_PLAY, 8, 10, Action::PlayerScream, S_PLAY_DIE3),
+    State::new(SPR_PLAY, 9, 10, Action::Fall, S_PLAY_DIE4),
+    State::new(SPR_PLAY, 10, 10, Action::None, S_PLAY_DIE5),
+    State::new(SPR_PLAY, 11, 10, Action::None, S_PLAY_DIE6),
+    State::new(SPR_PLAY, 12, 10, Action::None, S_PLAY_DIE7),
+    State::new(SPR_PLAY, 13, -1, Action::None, S_NULL),
+    State::new(SPR_PLAY, 14, 5, Action::None, S_PLAY_XDIE2),
+    State::new(SPR_PLAY, 15, 5, Action::XScream, S_PLAY_XDIE3),
+    State::new(SPR_PLAY, 16, 5, Action::Fall, S_PLAY_XDIE4),
+    State::new(SPR_PLAY, 17, 5, Action::None, S_PLAY_XDIE5),
+    State::new(SPR_PLAY, 18, 5, Action::None, S_PLAY_XDIE6),
+    State::new(SPR_PLAY, 19, 5, Action::None, S_PLAY_XDIE7),
+    State::new(SPR_PLAY, 20, 5, Action::None, S_PLAY_XDIE8),
+    State::new(SPR_PLAY, 21, 5, Action::None, S_PLAY_XDIE9),
+    State::new(SPR_PLAY, 22, -1, Action::None, S_NULL),
+    State::new(SPR_POSS, 0, 10, Action::Look, S_POSS_STND2),
+    State::new(SPR_POSS, 1, 10, Action::Look, S_POSS_STND),
+    State::new(SPR_POSS, 0, 4, Action::Chase, S_POSS_RUN2),
+    State::new(SPR_POSS, 0, 4, Action::Chase, S_POSS_RUN3),
+    State::new(SPR_POSS, 1, 4, Action::Chase, S_POSS_RUN4),
+    State::new(SPR_POSS, 1, 4, Action::Chase, S_POSS_RUN5),
+    State::new(SPR_POSS, 2, 4, Action::Chase, S_POSS_RUN6),
+    State::new(SPR_POSS, 2, 4, Action::Chase, S_POSS_RUN7),
+    State::new(SPR_POSS, 3, 4, Action::Chase, S_POSS_RUN8),
+    State::new(SPR_POSS, 3, 4, Action::Chase, S_POSS_RUN1),
+    State::new(SPR_POSS, 4, 10, Action::FaceTarget, S_POSS_ATK2),
+    State::new(SPR_POSS, 5, 8, Action::PosAttack, S_POSS_ATK3),
+    State::new(SPR_POSS, 4, 8, Action::None, S_POSS_RUN1),
+    State::new(SPR_POSS, 6, 3, Action::None, S_POSS_PAIN2),
+    State::new(SPR_POSS, 6, 3, Action::Pain, S_POSS_RUN1),
+    State::new(SPR_POSS, 7, 5, Action::None, S_POSS_DIE2),
+    State::new(SPR_POSS, 8, 5, Action::Scream, S_POSS_DIE3),
+    State::new(SPR_POSS, 9, 5, Action::Fall, S_POSS_DIE4),
+    State::new(SPR_POSS, 10, 5, Action::None, S_POSS_DIE5),
+    State::new(SPR_POSS, 11, -1, Action::None, S_NULL),
+    State::new(SPR_POSS, 12, 5, Action::None, S_POSS_XDIE2),
+    State::new(SPR_POSS, 13, 5, Action::XScream, S_POSS_XDIE3),
+    State::new(SPR_POSS, 14, 5, Action::Fall, S_POSS_XDIE4),
+    State::new(SPR_POSS, 15, 5, Action::None, S_POSS_XDIE5),
+    State::new(SPR_POSS, 16, 5, Action::None, S_POSS_XDIE6),
+    State::new(SPR_POSS, 17, 5, Action::None, S_POSS_XDIE7),
+    State::new(SPR_POSS, 18, 5, Action::None, S_POSS_XDIE8),
+    State::new(SPR_POSS, 19, 5, Action::None, S_POSS_XDIE9),
+    State::new(SPR_POSS, 20, -1, Action::None, S_NULL),
+    State::new(SPR_POSS, 10, 5, Action::None, S_POSS_RAISE2),
+    State::new(SPR_POSS, 9, 5, Action::None, S_POSS_RAISE3),
+    State::new(SPR_POSS, 8, 5, Action::None, S_POSS_RAISE4),
+    State::new(SPR_POSS, 7, 5, Action::None, S_POSS_RUN1),
+    State::new(SPR_SPOS, 0, 10, Action::Look, S_SPOS_STND2),
+    State::new(SPR_SPOS, 1, 10, Action::Look, S_SPOS_STND),
+    State::new(SPR_SPOS, 0, 3, Action::Chase, S_SPOS_RUN2),
+    State::new(SPR_SPOS, 0, 3, Action::Chase, S_SPOS_RUN3),
+    State::new(SPR_SPOS, 1, 3, Action::Chase, S_SPOS_RUN4),
+    State::new(SPR_SPOS, 1, 3, Action::Chase, S_SPOS_RUN5),
+    State::new(SPR_SPOS, 2, 3, Action::Chase, S_SPOS_RUN6),
+    State::new(SPR_SPOS, 2, 3, Action::Chase, S_SPOS_RUN7),
+    State::new(SPR_SPOS, 3, 3, Action::Chase, S_SPOS_RUN8),
+    State::new(SPR_SPOS, 3, 3, Action::Chase, S_SPOS_RUN1),
+    State::new(SPR_SPOS, 4, 10, Action::FaceTarget, S_SPOS_ATK2),
+    State::new(SPR_SPOS, FF_FULLBRIGHT | 5, 10, Action::SPosAttack, S_SPOS_ATK3),
+    State::new(SPR_SPOS, 4, 10, Action::None, S_SPOS_RUN1),
+    State::new(SPR_SPOS, 6, 3, Action::None, S_SPOS_PAIN2),
+    State::new(SPR_SPOS, 6, 3, Action::Pain, S_SPOS_RUN1),
+    State::new(SPR_SPOS, 7, 5, Action::None, S_SPOS_DIE2),
+    State::new(SPR_SPOS, 8, 5, Action::Scream, S_SPOS_DIE3),
+    State::new(SPR_SPOS, 9, 5, Action::Fall, S_SPOS_DIE4),
+    State::new(SPR_SPOS, 10, 5, Action::None, S_SPOS_DIE5),
+    State::new(SPR_SPOS, 11, -1, Action::None, S_NULL),
+    State::new(SPR_SPOS, 12, 5, Action::None, S_SPOS_XDIE2),
+    State::new(SPR_SPOS, 13, 5, Action::XScream, S_SPOS_XDIE3),
+    State::new(SPR_SPOS, 14, 5, Action::Fall, S_SPOS_XDIE4),
+    State::new(SPR_SPOS, 15, 5, Action::None, S_SPOS_XDIE5),
+    State::new(SPR_SPOS, 16, 5, Action::None, S_SPOS_XDIE6),
+    State::new(SPR_SPOS, 17, 5, Action::None, S_SPOS_XDIE7),
+    State::new(SPR_SPOS, 18, 5, Action::None, S_SPOS_XDIE8),
+    State::new(SPR_SPOS, 19, 5, Action::None, S_SPOS_XDIE9),
+    State::new(SPR_SPOS, 20, -1, Action::None, S_NULL),
+    State::new(SPR_SPOS, 11, 5, Action::None, S_SPOS_RAISE2),
+    State::new(SPR_SPOS, 10, 5, Action::None, S_SPOS_RAISE3),
+    State::new(SPR_SPOS, 9, 5, Action::None, S_SPOS_RAISE4),
+    State::new(SPR_SPOS, 8, 5, Action::None, S_SPOS_RAISE5),
+    State::new(SPR_SPOS, 7, 5, Action::None, S_SPOS_RUN1),
+    State::new(SPR_VILE, 0, 10, Action::Look, S_VILE_STND2),
+    State::new(SPR_VILE, 1, 10, Action::Look, S_VILE_STND),
+    State::new(SPR_VILE, 0, 2, Action::VileChase, S_VILE_RUN2),
+    State::new(SPR_VILE, 0, 2, Action::VileChase, S_VILE_RUN3),
+    State::new(SPR_VILE, 1, 2, Action::VileChase, S_VILE_RUN4),
+    State::new(SPR_VILE, 1, 2, Action::VileChase, S_VILE_RUN5),
+    State::new(SPR_VILE, 2, 2, Action::VileChase, S_VILE_RUN6),
+    State::new(SPR_VILE, 2, 2, Action::VileChase, S_VILE_RUN7),
+    State::new(SPR_VILE, 3, 2, Action::VileChase, S_VILE_RUN8),
+    State::new(SPR_VILE, 3, 2, Action::VileChase, S_VILE_RUN9),
+    State::new(SPR_VILE, 4, 2, Action::VileChase, S_VILE_RUN10),
+    State::new(SPR_VILE, 4, 2, Action::VileChase, S_VILE_RUN11),
+    State::new(SPR_VILE, 5, 2, Action::VileChase, S_VILE_RUN12),
+    State::new(SPR_VILE, 5, 2, Action::VileChase, S_VILE_RUN1),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 6, 0, Action::VileStart, S_VILE_ATK2),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 6, 10, Action::FaceTarget, S_VILE_ATK3),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 7, 8, Action::VileTarget, S_VILE_ATK4),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 8, 8, Action::FaceTarget, S_VILE_ATK5),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 9, 8, Action::FaceTarget, S_VILE_ATK6),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 10, 8, Action::FaceTarget, S_VILE_ATK7),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 11, 8, Action::FaceTarget, S_VILE_ATK8),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 12, 8, Action::FaceTarget, S_VILE_ATK9),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 13, 8, Action::FaceTarget, S_VILE_ATK10),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 14, 8, Action::VileAttack, S_VILE_ATK11),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 15, 20, Action::None, S_VILE_RUN1),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 26, 10, Action::None, S_VILE_HEAL2),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 27, 10, Action::None, S_VILE_HEAL3),
+    State::new(SPR_VILE, FF_FULLBRIGHT | 28, 10, Action::None, S_VILE_RUN1),
+    State::new(SPR_VILE, 16, 5, Action::None, S_VILE_PAIN2),
+    State::new(SPR_VILE, 16, 5, Action::Pain, S_VILE_RUN1),
+    State::new(SPR_VILE, 16, 7, Action::None, S_VILE_DIE2),
+    State::new(SPR_VILE, 17, 7, Action::Scream, S_VILE_DIE3),
+    State::new(SPR_VILE, 18, 7, Action::Fall, S_VILE_DIE4),
+    State::new(SPR_VILE, 19, 7, Action::None, S_VILE_DIE5),
+    State::new(SPR_VILE, 20, 7, Action::None, S_VILE_DIE6),
+    State::new(SPR_VILE, 21, 7, Action::None, S_VILE_DIE7),
+    State::new(SPR_VILE, 22, 7, Action::None, S_VILE_DIE8),
+    State::new(SPR_VILE, 23, 5, Action::None, S_VILE_DIE9),
+    State::new(SPR_VILE, 24, 5, Action::None, S_VILE_DIE10),
+    State::new(SPR_VILE, 25, -1, Action::None, S_NULL),
+    State::new(SPR_FIRE, FF_FULLBRIGHT, 2, Action::StartFire, S_FIRE2),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 1, 2, Action::Fire, S_FIRE3),
+    State::new(SPR_FIRE, FF_FULLBRIGHT, 2, Action::Fire, S_FIRE4),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 1, 2, Action::Fire, S_FIRE5),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 2, 2, Action::FireCrackle, S_FIRE6),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 1, 2, Action::Fire, S_FIRE7),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 2, 2, Action::Fire, S_FIRE8),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 1, 2, Action::Fire, S_FIRE9),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 2, 2, Action::Fire, S_FIRE10),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 3, 2, Action::Fire, S_FIRE11),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 2, 2, Action::Fire, S_FIRE12),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 3, 2, Action::Fire, S_FIRE13),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 2, 2, Action::Fire, S_FIRE14),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 3, 2, Action::Fire, S_FIRE15),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 4, 2, Action::Fire, S_FIRE16),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 3, 2, Action::Fire, S_FIRE17),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 4, 2, Action::Fire, S_FIRE18),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 3, 2, Action::Fire, S_FIRE19),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 4, 2, Action::FireCrackle, S_FIRE20),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 5, 2, Action::Fire, S_FIRE21),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 4, 2, Action::Fire, S_FIRE22),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 5, 2, Action::Fire, S_FIRE23),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 4, 2, Action::Fire, S_FIRE24),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 5, 2, Action::Fire, S_FIRE25),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 6, 2, Action::Fire, S_FIRE26),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 7, 2, Action::Fire, S_FIRE27),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 6, 2, Action::Fire, S_FIRE28),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 7, 2, Action::Fire, S_FIRE29),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 6, 2, Action::Fire, S_FIRE30),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 7, 2, Action::Fire, S_NULL),
+    State::new(SPR_PUFF, 1, 4, Action::None, S_SMOKE2),
+    State::new(SPR_PUFF, 2, 4, Action::None, S_SMOKE3),
+    State::new(SPR_PUFF, 1, 4, Action::None, S_SMOKE4),
+    State::new(SPR_PUFF, 2, 4, Action::None, S_SMOKE5),
+    State::new(SPR_PUFF, 3, 4, Action::None, S_NULL),
+    State::new(SPR_FATB, FF_FULLBRIGHT, 2, Action::Tracer, S_TRACER2),
+    State::new(SPR_FATB, FF_FULLBRIGHT | 1, 2, Action::Tracer, S_TRACER),
+    State::new(SPR_FBXP, FF_FULLBRIGHT, 8, Action::None, S_TRACEEXP2),
+    State::new(SPR_FBXP, FF_FULLBRIGHT | 1, 6, Action::None, S_TRACEEXP3),
+    State::new(SPR_FBXP, FF_FULLBRIGHT | 2, 4, Action::None, S_NULL),
+    State::new(SPR_SKEL, 0, 10, Action::Look, S_SKEL_STND2),
+    State::new(SPR_SKEL, 1, 10, Action::Look, S_SKEL_STND),
+    State::new(SPR_SKEL, 0, 2, Action::Chase, S_SKEL_RUN2),
+    State::new(SPR_SKEL, 0, 2, Action::Chase, S_SKEL_RUN3),
+    State::new(SPR_SKEL, 1, 2, Action::Chase, S_SKEL_RUN4),
+    State::new(SPR_SKEL, 1, 2, Action::Chase, S_SKEL_RUN5),
+    State::new(SPR_SKEL, 2, 2, Action::Chase, S_SKEL_RUN6),
+    State::new(SPR_SKEL, 2, 2, Action::Chase, S_SKEL_RUN7),
+    State::new(SPR_SKEL, 3, 2, Action::Chase, S_SKEL_RUN8),
+    State::new(SPR_SKEL, 3, 2, Action::Chase, S_SKEL_RUN9),
+    State::new(SPR_SKEL, 4, 2, Action::Chase, S_SKEL_RUN10),
+    State::new(SPR_SKEL, 4, 2, Action::Chase, S_SKEL_RUN11),
+    State::new(SPR_SKEL, 5, 2, Action::Chase, S_SKEL_RUN12),
+    State::new(SPR_SKEL, 5, 2, Action::Chase, S_SKEL_RUN1),
+    State::new(SPR_SKEL, 6, 0, Action::FaceTarget, S_SKEL_FIST2),
+    State::new(SPR_SKEL, 6, 6, Action::SkelWhoosh, S_SKEL_FIST3),
+    State::new(SPR_SKEL, 7, 6, Action::FaceTarget, S_SKEL_FIST4),
+    State::new(SPR_SKEL, 8, 6, Action::SkelFist, S_SKEL_RUN1),
+    State::new(SPR_SKEL, FF_FULLBRIGHT | 9, 0, Action::FaceTarget, S_SKEL_MISS2),
+    State::new(SPR_SKEL, FF_FULLBRIGHT | 9, 10, Action::FaceTarget, S_SKEL_MISS3),
+    State::new(SPR_SKEL, 10, 10, Action::SkelMissile, S_SKEL_MISS4),
+    State::new(SPR_SKEL, 10, 10, Action::FaceTarget, S_SKEL_RUN1),
+    State::new(SPR_SKEL, 11, 5, Action::None, S_SKEL_PAIN2),
+    State::new(SPR_SKEL, 11, 5, Action::Pain, S_SKEL_RUN1),
+    State::new(SPR_SKEL, 11, 7, Action::None, S_SKEL_DIE2),
+    State::new(SPR_SKEL, 12, 7, Action::None, S_SKEL_DIE3),
+    State::new(SPR_SKEL, 13, 7, Action::Scream, S_SKEL_DIE4),
+    State::new(SPR_SKEL, 14, 7, Action::Fall, S_SKEL_DIE5),
+    State::new(SPR_SKEL, 15, 7, Action::None, S_SKEL_DIE6),
+    State::new(SPR_SKEL, 16, -1, Action::None, S_NULL),
+    State::new(SPR_SKEL, 16, 5, Action::None, S_SKEL_RAISE2),
+    State::new(SPR_SKEL, 15, 5, Action::None, S_SKEL_RAISE3),
+    State::new(SPR_SKEL, 14, 5, Action::None, S_SKEL_RAISE4),
+    State::new(SPR_SKEL, 13, 5, Action::None, S_SKEL_RAISE5),
+    State::new(SPR_SKEL, 12, 5, Action::None, S_SKEL_RAISE6),
+    State::new(SPR_SKEL, 11, 5, Action::None, S_SKEL_RUN1),
+    State::new(SPR_MANF, FF_FULLBRIGHT, 4, Action::None, S_FATSHOT2),
+    State::new(SPR_MANF, FF_FULLBRIGHT | 1, 4, Action::None, S_FATSHOT1),
+    State::new(SPR_MISL, FF_FULLBRIGHT | 1, 8, Action::None, S_FATSHOTX2),
+    State::new(SPR_MISL, FF_FULLBRIGHT | 2, 6, Action::None, S_FATSHOTX3),
+    State::new(SPR_MISL, FF_FULLBRIGHT | 3, 4, Action::None, S_NULL),
+    State::new(SPR_FATT, 0, 15, Action::Look, S_FATT_STND2),
+    State::new(SPR_FATT, 1, 15, Action::Look, S_FATT_STND),
+    State::new(SPR_FATT, 0, 4, Action::Chase, S_FATT_RUN2),
+    State::new(SPR_FATT, 0, 4, Action::Chase, S_FATT_RUN3),
+    State::new(SPR_FATT, 1, 4, Action::Chase, S_FATT_RUN4),
+    State::new(SPR_FATT, 1, 4, Action::Chase, S_FATT_RUN5),
+    State::new(SPR_FATT, 2, 4, Action::Chase, S_FATT_RUN6),
+    State::new(SPR_FATT, 2, 4, Action::Chase, S_FATT_RUN7),
+    State::new(SPR_FATT, 3, 4, Action::Chase, S_FATT_RUN8),
+    State::new(SPR_FATT, 3, 4, Action::Chase, S_FATT_RUN9),
+    State::new(SPR_FATT, 4, 4, Action::Chase, S_FATT_RUN10),
+    State::new(SPR_FATT, 4, 4, Action::Chase, S_FATT_RUN11),
+    State::new(SPR_FATT, 5, 4, Action::Chase, S_FATT_RUN12),
+    State::new(SPR_FATT, 5, 4, Action::Chase, S_FATT_RUN1),
+    State::new(SPR_FATT, 6, 20, Action::FatRaise, S_FATT_ATK2),
+    State::new(SPR_FATT, FF_FULLBRIGHT | 7, 10, Action::FatAttack1, S_FATT_ATK3),
+    State::new(SPR_FATT, 6, 5, Action::FaceTarget, S_FATT_ATK4),
+    State::new(SPR_FATT, 6, 5, Action::FaceTarget, S_FATT_ATK5),
+    State::new(SPR_FATT, FF_FULLBRIGHT | 7, 10, Action::FatAttack2, S_FATT_ATK6),
+    State::new(SPR_FATT, 6, 5, Action::FaceTarget, S_FATT_ATK7),
+    State::new(SPR_FATT, 6, 5, Action::FaceTarget, S_FATT_ATK8),
+    State::new(SPR_FATT, FF_FULLBRIGHT | 7, 10, Action::FatAttack3, S_FATT_ATK9),
+    State::new(SPR_FATT, 6, 5, Action::FaceTarget, S_FATT_ATK10),
+    State::new(SPR_FATT, 6, 5, Action::FaceTarget, S_FATT_RUN1),
+    State::new(SPR_FATT, 9, 3, Action::None, S_FATT_PAIN2),
+    State::new(SPR_FATT, 9, 3, Action::Pain, S_FATT_RUN1),
+    State::new(SPR_FATT, 10, 6, Action::None, S_FATT_DIE2),
+    State::new(SPR_FATT, 11, 6, Action::Scream, S_FATT_DIE3),
+    State::new(SPR_FATT, 12, 6, Action::Fall, S_FATT_DIE4),
+    State::new(SPR_FATT, 13, 6, Action::None, S_FATT_DIE5),
+    State::new(SPR_FATT, 14, 6, Action::None, S_FATT_DIE6),
+    State::new(SPR_FATT, 15, 6, Action::None, S_FATT_DIE7),
+    State::new(SPR_FATT, 16, 6, Action::None, S_FATT_DIE8),
+    State::new(SPR_FATT, 17, 6, Action::None, S_FATT_DIE9),
+    State::new(SPR_FATT, 18, 6, Action::None, S_FATT_DIE10),
+    State::new(SPR_FATT, 19, -1, Action::BossDeath, S_NULL),
+    State::new(SPR_FATT, 17, 5, Action::None, S_FATT_RAISE2),
+    State::new(SPR_FATT, 16, 5, Action::None, S_FATT_RAISE3),
+    State::new(SPR_FATT, 15, 5, Action::None, S_FATT_RAISE4),
+    State::new(SPR_FATT, 14, 5, Action::None, S_FATT_RAISE5),
+    State::new(SPR_FATT, 13, 5, Action::None, S_FATT_RAISE6),
+    State::new(SPR_FATT, 12, 5, Action::None, S_FATT_RAISE7),
+    State::new(SPR_FATT, 11, 5, Action::None, S_FATT_RAISE8),
+    State::new(SPR_FATT, 10, 5, Action::None, S_FATT_RUN1),
+    State::new(SPR_CPOS, 0, 10, Action::Look, S_CPOS_STND2),
+    State::new(SPR_CPOS, 1, 10, Action::Look, S_CPOS_STND),
+    State::new(SPR_CPOS, 0, 3, Action::Chase, S_CPOS_RUN2),
+    State::new(SPR_CPOS, 0, 3, Action::Chase, S_CPOS_RUN3),
+    State::new(SPR_CPOS, 1, 3, Action::Chase, S_CPOS_RUN4),
+    State::new(SPR_CPOS, 1, 3, Action::Chase, S_CPOS_RUN5),
+    State::new(SPR_CPOS, 2, 3, Action::Chase, S_CPOS_RUN6),
+    State::new(SPR_CPOS, 2, 3, Action::Chase, S_CPOS_RUN7),
+    State::new(SPR_CPOS, 3, 3, Action::Chase, S_CPOS_RUN8),
+    State::new(SPR_CPOS, 3, 3, Action::Chase, S_CPOS_RUN1),
+    State::new(SPR_CPOS, 4, 10, Action::FaceTarget, S_CPOS_ATK2),
+    State::new(SPR_CPOS, FF_FULLBRIGHT | 5, 4, Action::CPosAttack, S_CPOS_ATK3),
+    State::new(SPR_CPOS, FF_FULLBRIGHT | 4, 4, Action::CPosAttack, S_CPOS_ATK4),
+    State::new(SPR_CPOS, 5, 1, Action::CPosRefire, S_CPOS_ATK2),
+    State::new(SPR_CPOS, 6, 3, Action::None, S_CPOS_PAIN2),
+    State::new(SPR_CPOS, 6, 3, Action::Pain, S_CPOS_RUN1),
+    State::new(SPR_CPOS, 7, 5, Action::None, S_CPOS_DIE2),
+    State::new(SPR_CPOS, 8, 5, Action::Scream, S_CPOS_DIE3),
+    State::new(SPR_CPOS, 9, 5, Action::Fall, S_CPOS_DIE4),
+    State::new(SPR_CPOS, 10, 5, Action::None, S_CPOS_DIE5),
+    State::new(SPR_CPOS, 11, 5, Action::None, S_CPOS_DIE6),
+    State::new(SPR_CPOS, 12, 5, Action::None, S_CPOS_DIE7),
+    State::new(SPR_CPOS, 13, -1, Action::None, S_NULL),
+    State::new(SPR_CPOS, 14, 5, Action::None, S_CPOS_XDIE2),
+    State::new(SPR_CPOS, 15, 5, Action::XScream, S_CPOS_XDIE3),
+    State::new(SPR_CPOS, 16, 5, Action::Fall, S_CPOS_XDIE4),
+    State::new(SPR_CPOS, 17, 5, Action::None, S_CPOS_XDIE5),
+    State::new(SPR_CPOS, 18, 5, Action::None, S_CPOS_XDIE6),
+    State::new(SPR_CPOS, 19, -1, Action::None, S_NULL),
+    State::new(SPR_CPOS, 13, 5, Action::None, S_CPOS_RAISE2),
+    State::new(SPR_CPOS, 12, 5, Action::None, S_CPOS_RAISE3),
+    State::new(SPR_CPOS, 11, 5, Action::None, S_CPOS_RAISE4),
+    State::new(SPR_CPOS, 10, 5, Action::None, S_CPOS_RAISE5),
+    State::new(SPR_CPOS, 9, 5, Action::None, S_CPOS_RAISE6),
+    State::new(SPR_CPOS, 8, 5, Action::None, S_CPOS_RAISE7),
+    State::new(SPR_CPOS, 7, 5, Action::None, S_CPOS_RUN1),
+    State::new(SPR_TROO, 0, 10, Action::Look, S_TROO_STND2),
+    State::new(SPR_TROO, 1, 10, Action::Look, S_TROO_STND),
+    State::new(SPR_TROO, 0, 3, Action::Chase, S_TROO_RUN2),
+    State::new(SPR_TROO, 0, 3, Action::Chase, S_TROO_RUN3),
+    State::new(SPR_TROO, 1, 3, Action::Chase, S_TROO_RUN4),
+    State::new(SPR_TROO, 1, 3, Action::Chase, S_TROO_RUN5),
+    State::new(SPR_TROO, 2, 3, Action::Chase, S_TROO_RUN6),
+    State::new(SPR_TROO, 2, 3, Action::Chase, S_TROO_RUN7),
+    State::new(SPR_TROO, 3, 3, Action::Chase, S_TROO_RUN8),
+    State::new(SPR_TROO, 3, 3, Action::Chase, S_TROO_RUN1),
+    State::new(SPR_TROO, 4, 8, Action::FaceTarget, S_TROO_ATK2),
+    State::new(SPR_TROO, 5, 8, Action::FaceTarget, S_TROO_ATK3),
+    State::new(SPR_TROO, 6, 6, Action::TroopAttack, S_TROO_RUN1),
+    State::new(SPR_TROO, 7, 2, Action::None, S_TROO_PAIN2),
+    State::new(SPR_TROO, 7, 2, Action::Pain, S_TROO_RUN1),
+    State::new(SPR_TROO, 8, 8, Action::None, S_TROO_DIE2),
+    State::new(SPR_TROO, 9, 8, Action::Scream, S_TROO_DIE3),
+    State::new(SPR_TROO, 10, 6, Action::None, S_TROO_DIE4),
+    State::new(SPR_TROO, 11, 6, Action::Fall, S_TROO_DIE5),
+    State::new(SPR_TROO, 12, -1, Action::None, S_NULL),
+    State::new(SPR_TROO, 13, 5, Action::None, S_TROO_XDIE2),
+    State::new(SPR_TROO, 14, 5, Action::XScream, S_TROO_XDIE3),
+    State::new(SPR_TROO, 15, 5, Action::None, S_TROO_XDIE4),
+    State::new(SPR_TROO, 16, 5, Action::Fall, S_TROO_XDIE5),
+    State::new(SPR_TROO, 17, 5, Action::None, S_TROO_XDIE6),
+    State::new(SPR_TROO, 18, 5, Action::None, S_TROO_XDIE7),
+    State::new(SPR_TROO, 19, 5, Action::None, S_TROO_XDIE8),
+    State::new(SPR_TROO, 20, -1, Action::None, S_NULL),
+    State::new(SPR_TROO, 12, 8, Action::None, S_TROO_RAISE2),
+    State::new(SPR_TROO, 11, 8, Action::None, S_TROO_RAISE3),
+    State::new(SPR_TROO, 10, 6, Action::None, S_TROO_RAISE4),
+    State::new(SPR_TROO, 9, 6, Action::None, S_TROO_RAISE5),
+    State::new(SPR_TROO, 8, 6, Action::None, S_TROO_RUN1),
+    State::new(SPR_SARG, 0, 10, Action::Look, S_SARG_STND2),
+    State::new(SPR_SARG, 1, 10, Action::Look, S_SARG_STND),
+    State::new(SPR_SARG, 0, 2, Action::Chase, S_SARG_RUN2),
+    State::new(SPR_SARG, 0, 2, Action::Chase, S_SARG_RUN3),
+    State::new(SPR_SARG, 1, 2, Action::Chase, S_SARG_RUN4),
+    State::new(SPR_SARG, 1, 2, Action::Chase, S_SARG_RUN5),
+    State::new(SPR_SARG, 2, 2, Action::Chase, S_SARG_RUN6),
+    State::new(SPR_SARG, 2, 2, Action::Chase, S_SARG_RUN7),
+    State::new(SPR_SARG, 3, 2, Action::Chase, S_SARG_RUN8),
+    State::new(SPR_SARG, 3, 2, Action::Chase, S_SARG_RUN1),
+    State::new(SPR_SARG, 4, 8, Action::FaceTarget, S_SARG_ATK2),
+    State::new(SPR_SARG, 5, 8, Action::FaceTarget, S_SARG_ATK3),
+    State::new(SPR_SARG, 6, 8, Action::SargAttack, S_SARG_RUN1),
+    State::new(SPR_SARG, 7, 2, Action::None, S_SARG_PAIN2),
+    State::new(SPR_SARG, 7, 2, Action::Pain, S_SARG_RUN1),
+    State::new(SPR_SARG, 8, 8, Action::None, S_SARG_DIE2),
+    State::new(SPR_SARG, 9, 8, Action::Scream, S_SARG_DIE3),
+    State::new(SPR_SARG, 10, 4, Action::None, S_SARG_DIE4),
+    State::new(SPR_SARG, 11, 4, Action::Fall, S_SARG_DIE5),
+    State::new(SPR_SARG, 12, 4, Action::None, S_SARG_DIE6),
+    State::new(SPR_SARG, 13, -1, Action::None, S_NULL),
+    State::new(SPR_SARG, 13, 5, Action::None, S_SARG_RAISE2),
+    State::new(SPR_SARG, 12, 5, Action::None, S_SARG_RAISE3),
+    State::new(SPR_SARG, 11, 5, Action::None, S_SARG_RAISE4),
+    State::new(SPR_SARG, 10, 5, Action::None, S_SARG_RAISE5),
+    State::new(SPR_SARG, 9, 5, Action::None, S_SARG_RAISE6),
+    State::new(SPR_SARG, 8, 5, Action::None, S_SARG_RUN1),
+    State::new(SPR_HEAD, 0, 10, Action::Look, S_HEAD_STND),
+    State::new(SPR_HEAD, 0, 3, Action::Chase, S_HEAD_RUN1),
+    State::new(SPR_HEAD, 1, 5, Action::FaceTarget, S_HEAD_ATK2),
+    State::new(SPR_HEAD, 2, 5, Action::FaceTarget, S_HEAD_ATK3),
+    State::new(SPR_HEAD, FF_FULLBRIGHT | 3, 5, Action::HeadAttack, S_HEAD_RUN1),
+    State::new(SPR_HEAD, 4, 3, Action::None, S_HEAD_PAIN2),
+    State::new(SPR_HEAD, 4, 3, Action::Pain, S_HEAD_PAIN3),
+    State::new(SPR_HEAD, 5, 6, Action::None, S_HEAD_RUN1),
+    State::new(SPR_HEAD, 6, 8, Action::None, S_HEAD_DIE2),
+    State::new(SPR_HEAD, 7, 8, Action::Scream, S_HEAD_DIE3),
+    State::new(SPR_HEAD, 8, 8, Action::None, S_HEAD_DIE4),
+    State::new(SPR_HEAD, 9, 8, Action::None, S_HEAD_DIE5),
+    State::new(SPR_HEAD, 10, 8, Action::Fall, S_HEAD_DIE6),
+    State::new(SPR_HEAD, 11, -1, Action::None, S_NULL),
+    State::new(SPR_HEAD, 11, 8, Action::None, S_HEAD_RAISE2),
+    State::new(SPR_HEAD, 10, 8, Action::None, S_HEAD_RAISE3),
+    State::new(SPR_HEAD, 9, 8, Action::None, S_HEAD_RAISE4),
+    State::new(SPR_HEAD, 8, 8, Action::None, S_HEAD_RAISE5),
+    State::new(SPR_HEAD, 7, 8, Action::None, S_HEAD_RAISE6),
+    State::new(SPR_HEAD, 6, 8, Action::None, S_HEAD_RUN1),
+    State::new(SPR_BAL7, FF_FULLBRIGHT, 4, Action::None, S_BRBALL2),
+    State::new(SPR_BAL7, FF_FULLBRIGHT | 1, 4, Action::None, S_BRBALL1),
+    State::new(SPR_BAL7, FF_FULLBRIGHT | 2, 6, Action::None, S_BRBALLX2),
+    State::new(SPR_BAL7, FF_FULLBRIGHT | 3, 6, Action::None, S_BRBALLX3),
+    State::new(SPR_BAL7, FF_FULLBRIGHT | 4, 6, Action::None, S_NULL),
+    State::new(SPR_BOSS, 0, 10, Action::Look, S_BOSS_STND2),
+    State::new(SPR_BOSS, 1, 10, Action::Look, S_BOSS_STND),
+    State::new(SPR_BOSS, 0, 3, Action::Chase, S_BOSS_RUN2),
+    State::new(SPR_BOSS, 0, 3, Action::Chase, S_BOSS_RUN3),
+    State::new(SPR_BOSS, 1, 3, Action::Chase, S_BOSS_RUN4),
+    State::new(SPR_BOSS, 1, 3, Action::Chase, S_BOSS_RUN5),
+    State::new(SPR_BOSS, 2, 3, Action::Chase, S_BOSS_RUN6),
+    State::new(SPR_BOSS, 2, 3, Action::Chase, S_BOSS_RUN7),
+    State::new(SPR_BOSS, 3, 3, Action::Chase, S_BOSS_RUN8),
+    State::new(SPR_BOSS, 3, 3, Action::Chase, S_BOSS_RUN1),
+    State::new(SPR_BOSS, 4, 8, Action::FaceTarget, S_BOSS_ATK2),
+    State::new(SPR_BOSS, 5, 8, Action::FaceTarget, S_BOSS_ATK3),
+    State::new(SPR_BOSS, 6, 8, Action::BruisAttack, S_BOSS_RUN1),
+    State::new(SPR_BOSS, 7, 2, Action::None, S_BOSS_PAIN2),
+    State::new(SPR_BOSS, 7, 2, Action::Pain, S_BOSS_RUN1),
+    State::new(SPR_BOSS, 8, 8, Action::None, S_BOSS_DIE2),
+    State::new(SPR_BOSS, 9, 8, Action::Scream, S_BOSS_DIE3),
+    State::new(SPR_BOSS, 10, 8, Action::None, S_BOSS_DIE4),
+    State::new(SPR_BOSS, 11, 8, Action::Fall, S_BOSS_DIE5),
+    State::new(SPR_BOSS, 12, 8, Action::None, S_BOSS_DIE6),
+    State::new(SPR_BOSS, 13, 8, Action::None, S_BOSS_DIE7),
+    State::new(SPR_BOSS, 14, -1, Action::BossDeath, S_NULL),
+    State::new(SPR_BOSS, 14, 8, Action::None, S_BOSS_RAISE2),
+    State::new(SPR_BOSS, 13, 8, Action::None, S_BOSS_RAISE3),
+    State::new(SPR_BOSS, 12, 8, Action::None, S_BOSS_RAISE4),
+    State::new(SPR_BOSS, 11, 8, Action::None, S_BOSS_RAISE5),
+    State::new(SPR_BOSS, 10, 8, Action::None, S_BOSS_RAISE6),
+    State::new(SPR_BOSS, 9, 8, Action::None, S_BOSS_RAISE7),
+    State::new(SPR_BOSS, 8, 8, Action::None, S_BOSS_RUN1),
+    State::new(SPR_BOS2, 0, 10, Action::Look, S_BOS2_STND2),
+    State::new(SPR_BOS2, 1, 10, Action::Look, S_BOS2_STND),
+    State::new(SPR_BOS2, 0, 3, Action::Chase, S_BOS2_RUN2),
+    State::new(SPR_BOS2, 0, 3, Action::Chase, S_BOS2_RUN3),
+    State::new(SPR_BOS2, 1, 3, Action::Chase, S_BOS2_RUN4),
+    State::new(SPR_BOS2, 1, 3, Action::Chase, S_BOS2_RUN5),
+    State::new(SPR_BOS2, 2, 3, Action::Chase, S_BOS2_RUN6),
+    State::new(SPR_BOS2, 2, 3, Action::Chase, S_BOS2_RUN7),
+    State::new(SPR_BOS2, 3, 3, Action::Chase, S_BOS2_RUN8),
+    State::new(SPR_BOS2, 3, 3, Action::Chase, S_BOS2_RUN1),
+    State::new(SPR_BOS2, 4, 8, Action::FaceTarget, S_BOS2_ATK2),
+    State::new(SPR_BOS2, 5, 8, Action::FaceTarget, S_BOS2_ATK3),
+    State::new(SPR_BOS2, 6, 8, Action::BruisAttack, S_BOS2_RUN1),
+    State::new(SPR_BOS2, 7, 2, Action::None, S_BOS2_PAIN2),
+    State::new(SPR_BOS2, 7, 2, Action::Pain, S_BOS2_RUN1),
+    State::new(SPR_BOS2, 8, 8, Action::None, S_BOS2_DIE2),
+    State::new(SPR_BOS2, 9, 8, Action::Scream, S_BOS2_DIE3),
+    State::new(SPR_BOS2, 10, 8, Action::None, S_BOS2_DIE4),
+    State::new(SPR_BOS2, 11, 8, Action::Fall, S_BOS2_DIE5),
+    State::new(SPR_BOS2, 12, 8, Action::None, S_BOS2_DIE6),
+    State::new(SPR_BOS2, 13, 8, Action::None, S_BOS2_DIE7),
+    State::new(SPR_BOS2, 14, -1, Action::None, S_NULL),
+    State::new(SPR_BOS2, 14, 8, Action::None, S_BOS2_RAISE2),
+    State::new(SPR_BOS2, 13, 8, Action::None, S_BOS2_RAISE3),
+    State::new(SPR_BOS2, 12, 8, Action::None, S_BOS2_RAISE4),
+    State::new(SPR_BOS2, 11, 8, Action::None, S_BOS2_RAISE5),
+    State::new(SPR_BOS2, 10, 8, Action::None, S_BOS2_RAISE6),
+    State::new(SPR_BOS2, 9, 8, Action::None, S_BOS2_RAISE7),
+    State::new(SPR_BOS2, 8, 8, Action::None, S_BOS2_RUN1),
+    State::new(SPR_SKUL, FF_FULLBRIGHT, 10, Action::Look, S_SKULL_STND2),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 1, 10, Action::Look, S_SKULL_STND),
+    State::new(SPR_SKUL, FF_FULLBRIGHT, 6, Action::Chase, S_SKULL_RUN2),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 1, 6, Action::Chase, S_SKULL_RUN1),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 2, 10, Action::FaceTarget, S_SKULL_ATK2),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 3, 4, Action::SkullAttack, S_SKULL_ATK3),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 2, 4, Action::None, S_SKULL_ATK4),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 3, 4, Action::None, S_SKULL_ATK3),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 4, 3, Action::None, S_SKULL_PAIN2),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 4, 3, Action::Pain, S_SKULL_RUN1),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 5, 6, Action::None, S_SKULL_DIE2),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 6, 6, Action::Scream, S_SKULL_DIE3),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 7, 6, Action::None, S_SKULL_DIE4),
+    State::new(SPR_SKUL, FF_FULLBRIGHT | 8, 6, Action::Fall, S_SKULL_DIE5),
+    State::new(SPR_SKUL, 9, 6, Action::None, S_SKULL_DIE6),
+    State::new(SPR_SKUL, 10, 6, Action::None, S_NULL),
+    State::new(SPR_SPID, 0, 10, Action::Look, S_SPID_STND2),
+    State::new(SPR_SPID, 1, 10, Action::Look, S_SPID_STND),
+    State::new(SPR_SPID, 0, 3, Action::Metal, S_SPID_RUN2),
+    State::new(SPR_SPID, 0, 3, Action::Chase, S_SPID_RUN3),
+    State::new(SPR_SPID, 1, 3, Action::Chase, S_SPID_RUN4),
+    State::new(SPR_SPID, 1, 3, Action::Chase, S_SPID_RUN5),
+    State::new(SPR_SPID, 2, 3, Action::Metal, S_SPID_RUN6),
+    State::new(SPR_SPID, 2, 3, Action::Chase, S_SPID_RUN7),
+    State::new(SPR_SPID, 3, 3, Action::Chase, S_SPID_RUN8),
+    State::new(SPR_SPID, 3, 3, Action::Chase, S_SPID_RUN9),
+    State::new(SPR_SPID, 4, 3, Action::Metal, S_SPID_RUN10),
+    State::new(SPR_SPID, 4, 3, Action::Chase, S_SPID_RUN11),
+    State::new(SPR_SPID, 5, 3, Action::Chase, S_SPID_RUN12),
+    State::new(SPR_SPID, 5, 3, Action::Chase, S_SPID_RUN1),
+    State::new(SPR_SPID, FF_FULLBRIGHT, 20, Action::FaceTarget, S_SPID_ATK2),
+    State::new(SPR_SPID, FF_FULLBRIGHT | 6, 4, Action::SPosAttack, S_SPID_ATK3),
+    State::new(SPR_SPID, FF_FULLBRIGHT | 7, 4, Action::SPosAttack, S_SPID_ATK4),
+    State::new(SPR_SPID, FF_FULLBRIGHT | 7, 1, Action::SpidRefire, S_SPID_ATK2),
+    State::new(SPR_SPID, 8, 3, Action::None, S_SPID_PAIN2),
+    State::new(SPR_SPID, 8, 3, Action::Pain, S_SPID_RUN1),
+    State::new(SPR_SPID, 9, 20, Action::Scream, S_SPID_DIE2),
+    State::new(SPR_SPID, 10, 10, Action::Fall, S_SPID_DIE3),
+    State::new(SPR_SPID, 11, 10, Action::None, S_SPID_DIE4),
+    State::new(SPR_SPID, 12, 10, Action::None, S_SPID_DIE5),
+    State::new(SPR_SPID, 13, 10, Action::None, S_SPID_DIE6),
+    State::new(SPR_SPID, 14, 10, Action::None, S_SPID_DIE7),
+    State::new(SPR_SPID, 15, 10, Action::None, S_SPID_DIE8),
+    State::new(SPR_SPID, 16, 10, Action::None, S_SPID_DIE9),
+    State::new(SPR_SPID, 17, 10, Action::None, S_SPID_DIE10),
+    State::new(SPR_SPID, 18, 30, Action::None, S_SPID_DIE11),
+    State::new(SPR_SPID, 18, -1, Action::BossDeath, S_NULL),
+    State::new(SPR_BSPI, 0, 10, Action::Look, S_BSPI_STND2),
+    State::new(SPR_BSPI, 1, 10, Action::Look, S_BSPI_STND),
+    State::new(SPR_BSPI, 0, 20, Action::None, S_BSPI_RUN1),
+    State::new(SPR_BSPI, 0, 3, Action::BabyMetal, S_BSPI_RUN2),
+    State::new(SPR_BSPI, 0, 3, Action::Chase, S_BSPI_RUN3),
+    State::new(SPR_BSPI, 1, 3, Action::Chase, S_BSPI_RUN4),
+    State::new(SPR_BSPI, 1, 3, Action::Chase, S_BSPI_RUN5),
+    State::new(SPR_BSPI, 2, 3, Action::Chase, S_BSPI_RUN6),
+    State::new(SPR_BSPI, 2, 3, Action::Chase, S_BSPI_RUN7),
+    State::new(SPR_BSPI, 3, 3, Action::BabyMetal, S_BSPI_RUN8),
+    State::new(SPR_BSPI, 3, 3, Action::Chase, S_BSPI_RUN9),
+    State::new(SPR_BSPI, 4, 3, Action::Chase, S_BSPI_RUN10),
+    State::new(SPR_BSPI, 4, 3, Action::Chase, S_BSPI_RUN11),
+    State::new(SPR_BSPI, 5, 3, Action::Chase, S_BSPI_RUN12),
+    State::new(SPR_BSPI, 5, 3, Action::Chase, S_BSPI_RUN1),
+    State::new(SPR_BSPI, FF_FULLBRIGHT, 20, Action::FaceTarget, S_BSPI_ATK2),
+    State::new(SPR_BSPI, FF_FULLBRIGHT | 6, 4, Action::BspiAttack, S_BSPI_ATK3),
+    State::new(SPR_BSPI, FF_FULLBRIGHT | 7, 4, Action::None, S_BSPI_ATK4),
+    State::new(SPR_BSPI, FF_FULLBRIGHT | 7, 20, Action::SpidRefire, S_BSPI_ATK2),
+    State::new(SPR_BSPI, 8, 3, Action::None, S_BSPI_PAIN2),
+    State::new(SPR_BSPI, 8, 3, Action::Pain, S_BSPI_RUN1),
+    State::new(SPR_BSPI, 9, 20, Action::Scream, S_BSPI_DIE2),
+    State::new(SPR_BSPI, 10, 7, Action::Fall, S_BSPI_DIE3),
+    State::new(SPR_BSPI, 11, 7, Action::None, S_BSPI_DIE4),
+    State::new(SPR_BSPI, 12, 7, Action::None, S_BSPI_DIE5),
+    State::new(SPR_BSPI, 13, 7, Action::None, S_BSPI_DIE6),
+    State::new(SPR_BSPI, 14, 7, Action::None, S_BSPI_DIE7),
+    State::new(SPR_BSPI, 15, -1, Action::BossDeath, S_NULL),
+    State::new(SPR_BSPI, 15, 5, Action::None, S_BSPI_RAISE2),
+    State::new(SPR_BSPI, 14, 5, Action::None, S_BSPI_RAISE3),
+    State::new(SPR_BSPI, 13, 5, Action::None, S_BSPI_RAISE4),
+    State::new(SPR_BSPI, 12, 5, Action::None, S_BSPI_RAISE5),
+    State::new(SPR_BSPI, 11, 5, Action::None, S_BSPI_RAISE6),
+    State::new(SPR_BSPI, 10, 5, Action::None, S_BSPI_RAISE7),
+    State::new(SPR_BSPI, 9, 5, Action::None, S_BSPI_RUN1),
+    State::new(SPR_APLS, FF_FULLBRIGHT, 5, Action::None, S_ARACH_PLAZ2),
+    State::new(SPR_APLS, FF_FULLBRIGHT | 1, 5, Action::None, S_ARACH_PLAZ),
+    State::new(SPR_APBX, FF_FULLBRIGHT, 5, Action::None, S_ARACH_PLEX2),
+    State::new(SPR_APBX, FF_FULLBRIGHT | 1, 5, Action::None, S_ARACH_PLEX3),
+    State::new(SPR_APBX, FF_FULLBRIGHT | 2, 5, Action::None, S_ARACH_PLEX4),
+    State::new(SPR_APBX, FF_FULLBRIGHT | 3, 5, Action::None, S_ARACH_PLEX5),
+    State::new(SPR_APBX, FF_FULLBRIGHT | 4, 5, Action::None, S_NULL),
+    State::new(SPR_CYBR, 0, 10, Action::Look, S_CYBER_STND2),
+    State::new(SPR_CYBR, 1, 10, Action::Look, S_CYBER_STND),
+    State::new(SPR_CYBR, 0, 3, Action::Hoof, S_CYBER_RUN2),
+    State::new(SPR_CYBR, 0, 3, Action::Chase, S_CYBER_RUN3),
+    State::new(SPR_CYBR, 1, 3, Action::Chase, S_CYBER_RUN4),
+    State::new(SPR_CYBR, 1, 3, Action::Chase, S_CYBER_RUN5),
+    State::new(SPR_CYBR, 2, 3, Action::Chase, S_CYBER_RUN6),
+    State::new(SPR_CYBR, 2, 3, Action::Chase, S_CYBER_RUN7),
+    State::new(SPR_CYBR, 3, 3, Action::Metal, S_CYBER_RUN8),
+    State::new(SPR_CYBR, 3, 3, Action::Chase, S_CYBER_RUN1),
+    State::new(SPR_CYBR, 4, 6, Action::FaceTarget, S_CYBER_ATK2),
+    State::new(SPR_CYBR, 5, 12, Action::CyberAttack, S_CYBER_ATK3),
+    State::new(SPR_CYBR, 4, 12, Action::FaceTarget, S_CYBER_ATK4),
+    State::new(SPR_CYBR, 5, 12, Action::CyberAttack, S_CYBER_ATK5),
+    State::new(SPR_CYBR, 4, 12, Action::FaceTarget, S_CYBER_ATK6),
+    State::new(SPR_CYBR, 5, 12, Action::CyberAttack, S_CYBER_RUN1),
+    State::new(SPR_CYBR, 6, 10, Action::Pain, S_CYBER_RUN1),
+    State::new(SPR_CYBR, 7, 10, Action::None, S_CYBER_DIE2),
+    State::new(SPR_CYBR, 8, 10, Action::Scream, S_CYBER_DIE3),
+    State::new(SPR_CYBR, 9, 10, Action::None, S_CYBER_DIE4),
+    State::new(SPR_CYBR, 10, 10, Action::None, S_CYBER_DIE5),
+    State::new(SPR_CYBR, 11, 10, Action::None, S_CYBER_DIE6),
+    State::new(SPR_CYBR, 12, 10, Action::Fall, S_CYBER_DIE7),
+    State::new(SPR_CYBR, 13, 10, Action::None, S_CYBER_DIE8),
+    State::new(SPR_CYBR, 14, 10, Action::None, S_CYBER_DIE9),
+    State::new(SPR_CYBR, 15, 30, Action::None, S_CYBER_DIE10),
+    State::new(SPR_CYBR, 15, -1, Action::BossDeath, S_NULL),
+    State::new(SPR_PAIN, 0, 10, Action::Look, S_PAIN_STND),
+    State::new(SPR_PAIN, 0, 3, Action::Chase, S_PAIN_RUN2),
+    State::new(SPR_PAIN, 0, 3, Action::Chase, S_PAIN_RUN3),
+    State::new(SPR_PAIN, 1, 3, Action::Chase, S_PAIN_RUN4),
+    State::new(SPR_PAIN, 1, 3, Action::Chase, S_PAIN_RUN5),
+    State::new(SPR_PAIN, 2, 3, Action::Chase, S_PAIN_RUN6),
+    State::new(SPR_PAIN, 2, 3, Action::Chase, S_PAIN_RUN1),
+    State::new(SPR_PAIN, 3, 5, Action::FaceTarget, S_PAIN_ATK2),
+    State::new(SPR_PAIN, 4, 5, Action::FaceTarget, S_PAIN_ATK3),
+    State::new(SPR_PAIN, FF_FULLBRIGHT | 5, 5, Action::FaceTarget, S_PAIN_ATK4),
+    State::new(SPR_PAIN, FF_FULLBRIGHT | 5, 0, Action::PainAttack, S_PAIN_RUN1),
+    State::new(SPR_PAIN, 6, 6, Action::None, S_PAIN_PAIN2),
+    State::new(SPR_PAIN, 6, 6, Action::Pain, S_PAIN_RUN1),
+    State::new(SPR_PAIN, FF_FULLBRIGHT | 7, 8, Action::None, S_PAIN_DIE2),
+    State::new(SPR_PAIN, FF_FULLBRIGHT | 8, 8, Action::Scream, S_PAIN_DIE3),
+    State::new(SPR_PAIN, FF_FULLBRIGHT | 9, 8, Action::None, S_PAIN_DIE4),
+    State::new(SPR_PAIN, FF_FULLBRIGHT | 10, 8, Action::None, S_PAIN_DIE5),
+    State::new(SPR_PAIN, FF_FULLBRIGHT | 11, 8, Action::PainDie, S_PAIN_DIE6),
+    State::new(SPR_PAIN, FF_FULLBRIGHT | 12, 8, Action::None, S_NULL),
+    State::new(SPR_PAIN, 12, 8, Action::None, S_PAIN_RAISE2),
+    State::new(SPR_PAIN, 11, 8, Action::None, S_PAIN_RAISE3),
+    State::new(SPR_PAIN, 10, 8, Action::None, S_PAIN_RAISE4),
+    State::new(SPR_PAIN, 9, 8, Action::None, S_PAIN_RAISE5),
+    State::new(SPR_PAIN, 8, 8, Action::None, S_PAIN_RAISE6),
+    State::new(SPR_PAIN, 7, 8, Action::None, S_PAIN_RUN1),
+    State::new(SPR_SSWV, 0, 10, Action::Look, S_SSWV_STND2),
+    State::new(SPR_SSWV, 1, 10, Action::Look, S_SSWV_STND),
+    State::new(SPR_SSWV, 0, 3, Action::Chase, S_SSWV_RUN2),
+    State::new(SPR_SSWV, 0, 3, Action::Chase, S_SSWV_RUN3),
+    State::new(SPR_SSWV, 1, 3, Action::Chase, S_SSWV_RUN4),
+    State::new(SPR_SSWV, 1, 3, Action::Chase, S_SSWV_RUN5),
+    State::new(SPR_SSWV, 2, 3, Action::Chase, S_SSWV_RUN6),
+    State::new(SPR_SSWV, 2, 3, Action::Chase, S_SSWV_RUN7),
+    State::new(SPR_SSWV, 3, 3, Action::Chase, S_SSWV_RUN8),
+    State::new(SPR_SSWV, 3, 3, Action::Chase, S_SSWV_RUN1),
+    State::new(SPR_SSWV, 4, 10, Action::FaceTarget, S_SSWV_ATK2),
+    State::new(SPR_SSWV, 5, 10, Action::FaceTarget, S_SSWV_ATK3),
+    State::new(SPR_SSWV, FF_FULLBRIGHT | 6, 4, Action::CPosAttack, S_SSWV_ATK4),
+    State::new(SPR_SSWV, 5, 6, Action::FaceTarget, S_SSWV_ATK5),
+    State::new(SPR_SSWV, FF_FULLBRIGHT | 6, 4, Action::CPosAttack, S_SSWV_ATK6),
+    State::new(SPR_SSWV, 5, 1, Action::CPosRefire, S_SSWV_ATK2),
+    State::new(SPR_SSWV, 7, 3, Action::None, S_SSWV_PAIN2),
+    State::new(SPR_SSWV, 7, 3, Action::Pain, S_SSWV_RUN1),
+    State::new(SPR_SSWV, 8, 5, Action::None, S_SSWV_DIE2),
+    State::new(SPR_SSWV, 9, 5, Action::Scream, S_SSWV_DIE3),
+    State::new(SPR_SSWV, 10, 5, Action::Fall, S_SSWV_DIE4),
+    State::new(SPR_SSWV, 11, 5, Action::None, S_SSWV_DIE5),
+    State::new(SPR_SSWV, 12, -1, Action::None, S_NULL),
+    State::new(SPR_SSWV, 13, 5, Action::None, S_SSWV_XDIE2),
+    State::new(SPR_SSWV, 14, 5, Action::XScream, S_SSWV_XDIE3),
+    State::new(SPR_SSWV, 15, 5, Action::Fall, S_SSWV_XDIE4),
+    State::new(SPR_SSWV, 16, 5, Action::None, S_SSWV_XDIE5),
+    State::new(SPR_SSWV, 17, 5, Action::None, S_SSWV_XDIE6),
+    State::new(SPR_SSWV, 18, 5, Action::None, S_SSWV_XDIE7),
+    State::new(SPR_SSWV, 19, 5, Action::None, S_SSWV_XDIE8),
+    State::new(SPR_SSWV, 20, 5, Action::None, S_SSWV_XDIE9),
+    State::new(SPR_SSWV, 21, -1, Action::None, S_NULL),
+    State::new(SPR_SSWV, 12, 5, Action::None, S_SSWV_RAISE2),
+    State::new(SPR_SSWV, 11, 5, Action::None, S_SSWV_RAISE3),
+    State::new(SPR_SSWV, 10, 5, Action::None, S_SSWV_RAISE4),
+    State::new(SPR_SSWV, 9, 5, Action::None, S_SSWV_RAISE5),
+    State::new(SPR_SSWV, 8, 5, Action::None, S_SSWV_RUN1),
+    State::new(SPR_KEEN, 0, -1, Action::None, S_KEENSTND),
+    State::new(SPR_KEEN, 0, 6, Action::None, S_COMMKEEN2),
+    State::new(SPR_KEEN, 1, 6, Action::None, S_COMMKEEN3),
+    State::new(SPR_KEEN, 2, 6, Action::Scream, S_COMMKEEN4),
+    State::new(SPR_KEEN, 3, 6, Action::None, S_COMMKEEN5),
+    State::new(SPR_KEEN, 4, 6, Action::None, S_COMMKEEN6),
+    State::new(SPR_KEEN, 5, 6, Action::None, S_COMMKEEN7),
+    State::new(SPR_KEEN, 6, 6, Action::None, S_COMMKEEN8),
+    State::new(SPR_KEEN, 7, 6, Action::None, S_COMMKEEN9),
+    State::new(SPR_KEEN, 8, 6, Action::None, S_COMMKEEN10),
+    State::new(SPR_KEEN, 9, 6, Action::None, S_COMMKEEN11),
+    State::new(SPR_KEEN, 10, 6, Action::KeenDie, S_COMMKEEN12),
+    State::new(SPR_KEEN, 11, -1, Action::None, S_NULL),
+    State::new(SPR_KEEN, 12, 4, Action::None, S_KEENPAIN2),
+    State::new(SPR_KEEN, 12, 8, Action::Pain, S_KEENSTND),
+    State::new(SPR_BBRN, 0, -1, Action::None, S_NULL),
+    State::new(SPR_BBRN, 1, 36, Action::BrainPain, S_BRAIN),
+    State::new(SPR_BBRN, 0, 100, Action::BrainScream, S_BRAIN_DIE2),
+    State::new(SPR_BBRN, 0, 10, Action::None, S_BRAIN_DIE3),
+    State::new(SPR_BBRN, 0, 10, Action::None, S_BRAIN_DIE4),
+    State::new(SPR_BBRN, 0, -1, Action::BrainDie, S_NULL),
+    State::new(SPR_SSWV, 0, 10, Action::Look, S_BRAINEYE),
+    State::new(SPR_SSWV, 0, 181, Action::BrainAwake, S_BRAINEYE1),
+    State::new(SPR_SSWV, 0, 150, Action::BrainSpit, S_BRAINEYE1),
+    State::new(SPR_BOSF, FF_FULLBRIGHT, 3, Action::SpawnSound, S_SPAWN2),
+    State::new(SPR_BOSF, FF_FULLBRIGHT | 1, 3, Action::SpawnFly, S_SPAWN3),
+    State::new(SPR_BOSF, FF_FULLBRIGHT | 2, 3, Action::SpawnFly, S_SPAWN4),
+    State::new(SPR_BOSF, FF_FULLBRIGHT | 3, 3, Action::SpawnFly, S_SPAWN1),
+    State::new(SPR_FIRE, FF_FULLBRIGHT, 4, Action::Fire, S_SPAWNFIRE2),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 1, 4, Action::Fire, S_SPAWNFIRE3),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 2, 4, Action::Fire, S_SPAWNFIRE4),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 3, 4, Action::Fire, S_SPAWNFIRE5),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 4, 4, Action::Fire, S_SPAWNFIRE6),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 5, 4, Action::Fire, S_SPAWNFIRE7),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 6, 4, Action::Fire, S_SPAWNFIRE8),
+    State::new(SPR_FIRE, FF_FULLBRIGHT | 7, 4, Action::Fire, S_NULL),
+    State::new(SPR_MISL, FF_FULLBRIGHT | 1, 10, Action::None, S_BRAINEXPLODE2),
+    State::new(SPR_MISL, FF_FULLBRIGHT | 2, 10, Action::None, S_BRAINEXPLODE3),
+    State::new(SPR_MISL, FF_FULLBRIGHT | 3, 10, Action::BrainExplode, S_NULL),
+    State::new(SPR_ARM1, 0, 6, Action::None, S_ARM1A),
+    State::new(SPR_ARM1, FF_FULLBRIGHT | 1, 7, Action::None, S_ARM1),
+    State::new(SPR_ARM2, 0, 6, Action::None, S_ARM2A),
+    State::new(SPR_ARM2, FF_FULLBRIGHT | 1, 6, Action::None, S_ARM2),
+    State::new(SPR_BAR1, 0, 6, Action::None, S_BAR2),
+    State::new(SPR_BAR1, 1, 6, Action::None, S_BAR1),
+    State::new(SPR_BEXP, FF_FULLBRIGHT, 5, Action::None, S_BEXP2),
+    State::new(SPR_BEXP, FF_FULLBRIGHT | 1, 5, Action::Scream, S_BEXP3),
+    State::new(SPR_BEXP, FF_FULLBRIGHT | 2, 5, Action::None, S_BEXP4),
+    State::new(SPR_BEXP, FF_FULLBRIGHT | 3, 10, Action::Explode, S_BEXP5),
+    State::new(SPR_BEXP, FF_FULLBRIGHT | 4, 10, Action::None, S_NULL),
+    State::new(SPR_FCAN, FF_FULLBRIGHT, 4, Action::None, S_BBAR2),
+    State::new(SPR_FCAN, FF_FULLBRIGHT | 1, 4, Action::None, S_BBAR3),
+    State::new(SPR_FCAN, FF_FULLBRIGHT | 2, 4, Action::None, S_BBAR1),
+    State::new(SPR_BON1, 0, 6, Action::None, S_BON1A),
+    State::new(SPR_BON1, 1, 6, Action::None, S_BON1B),
+    State::new(SPR_BON1, 2, 6, Action::None, S_BON1C),
+    State::new(SPR_BON1, 3, 6, Action::None, S_BON1D),
+    State::new(SPR_BON1, 2, 6, Action::None, S_BON1E),
+    State::new(SPR_BON1, 1, 6, Action::None, S_BON1),
+    State::new(SPR_BON2, 0, 6, Action::None, S_BON2A),
+    State::new(SPR_BON2, 1, 6, Action::None, S_BON2B),
+    State::new(SPR_BON2, 2, 6, Action::None, S_BON2C),
+    State::new(SPR_BON2, 3, 6, Action::None, S_BON2D),
+    State::new(SPR_BON2, 2, 6, Action::None, S_BON2E),
+    State::new(SPR_BON2, 1, 6, Action::None, S_BON2),
+    State::new(SPR_BKEY, 0, 10, Action::None, S_BKEY2),
+    State::new(SPR_BKEY, FF_FULLBRIGHT | 1, 10, Action::None, S_BKEY),
+    State::new(SPR_RKEY, 0, 10, Action::None, S_RKEY2),
+    State::new(SPR_RKEY, FF_FULLBRIGHT | 1, 10, Action::None, S_RKEY),
+    State::new(SPR_YKEY, 0, 10, Action::None, S_YKEY2),
+    State::new(SPR_YKEY, FF_FULLBRIGHT | 1, 10, Action::None, S_YKEY),
+    State::new(SPR_BSKU, 0, 10, Action::None, S_BSKULL2),
+    State::new(SPR_BSKU, FF_FULLBRIGHT | 1, 10, Action::None, S_BSKULL),
+    State::new(SPR_RSKU, 0, 10, Action::None, S_RSKULL2),
+    State::new(SPR_RSKU, FF_FULLBRIGHT | 1, 10, Action::None, S_RSKULL),
+    State::new(SPR_YSKU, 0, 10, Action::None, S_YSKULL2),
+    State::new(SPR_YSKU, FF_FULLBRIGHT | 1, 10, Action::None, S_YSKULL),
+    State::new(SPR_STIM, 0, -1, Action::None, S_NULL),
+    State::new(SPR_MEDI, 0, -1, Action::None, S_NULL),
+    State::new(SPR_SOUL, FF_FULLBRIGHT, 6, Action::None, S_SOUL2),
+    State::new(SPR_SOUL, FF_FULLBRIGHT | 1, 6, Action::None, S_SOUL3),
+    State::new(SPR_SOUL, FF_FULLBRIGHT | 2, 6, Action::None, S_SOUL4),
+    State::new(SPR_SOUL, FF_FULLBRIGHT | 3, 6, Action::None, S_SOUL5),
+    State::new(SPR_SOUL, FF_FULLBRIGHT | 2, 6, Action::None, S_SOUL6),
+    State::new(SPR_SOUL, FF_FULLBRIGHT | 1, 6, Action::None, S_SOUL),
+    State::new(SPR_PINV, FF_FULLBRIGHT, 6, Action::None, S_PINV2),
+    State::new(SPR_PINV, FF_FULLBRIGHT | 1, 6, Action::None, S_PINV3),
+    State::new(SPR_PINV, FF_FULLBRIGHT | 2, 6, Action::None, S_PINV4),
+    State::new(SPR_PINV, FF_FULLBRIGHT | 3, 6, Action::None, S_PINV),
+    State::new(SPR_PSTR, FF_FULLBRIGHT, -1, Action::None, S_NULL),
+    State::new(SPR_PINS, FF_FULLBRIGHT, 6, Action::None, S_PINS2),
+    State::new(SPR_PINS, FF_FULLBRIGHT | 1, 6, Action::None, S_PINS3),
+    State::new(SPR_PINS, FF_FULLBRIGHT | 2, 6, Action::None, S_PINS4),
+    State::new(SPR_PINS, FF_FULLBRIGHT | 3, 6, Action::None, S_PINS),
+    State::new(SPR_MEGA, FF_FULLBRIGHT, 6, Action::None, S_MEGA2),
+    State::new(SPR_MEGA, FF_FULLBRIGHT | 1, 6, Action::None, S_MEGA3),
+    State::new(SPR_MEGA, FF_FULLBRIGHT | 2, 6, Action::None, S_MEGA4),
+    State::new(SPR_MEGA, FF_FULLBRIGHT | 3, 6, Action::None, S_MEGA),
+    State::new(SPR_SUIT, FF_FULLBRIGHT, -1, Action::None, S_NULL),
+    State::new(SPR_PMAP, FF_FULLBRIGHT, 6, Action::None, S_PMAP2),
+    State::new(SPR_PMAP, FF_FULLBRIGHT | 1, 6, Action::None, S_PMAP3),
+    State::new(SPR_PMAP, FF_FULLBRIGHT | 2, 6, Action::None, S_PMAP4),
+    State::new(SPR_PMAP, FF_FULLBRIGHT | 3, 6, Action::None, S_PMAP5),
+    State::new(SPR_PMAP, FF_FULLBRIGHT | 2, 6, Action::None, S_PMAP6),
+    State::new(SPR_PMAP, FF_FULLBRIGHT | 1, 6, Action::None, S_PMAP),
+    State::new(SPR_PVIS, FF_FULLBRIGHT, 6, Action::None, S_PVIS2),
+    State::new(SPR_PVIS, 1, 6, Action::None, S_PVIS),
+    State::new(SPR_CLIP, 0, -1, Action::None, S_NULL),
+    State::new(SPR_AMMO, 0, -1, Action::None, S_NULL),
+    State::new(SPR_ROCK, 0, -1, Action::None, S_NULL),
+    State::new(SPR_BROK, 0, -1, Action::None, S_NULL),
+    State::new(SPR_CELL, 0, -1, Action::None, S_NULL),
+    State::new(SPR_CELP, 0, -1, Action::None, S_NULL),
+    State::new(SPR_SHEL, 0, -1, Action::None, S_NULL),
+    State::new(SPR_SBOX, 0, -1, Action::None, S_NULL),
+    State::new(SPR_BPAK, 0, -1, Action::None, S_NULL),
+    State::new(SPR_BFUG, 0, -1, Action::None, S_NULL),
+    State::new(SPR_MGUN, 0, -1, Action::None, S_NULL),
+    State::new(SPR_CSAW, 0, -1, Action::None, S_NULL),
+    State::new(SPR_LAUN, 0, -1, Action::None, S_NULL),
+    State::new(SPR_PLAS, 0, -1, Action::None, S_NULL),
+    State::new(SPR_SHOT, 0, -1, Action::None, S_NULL),
+    State::new(SPR_SGN2, 0, -1, Action::None, S_NULL),
+    State::new(SPR_COLU, FF_FULLBRIGHT, -1, Action::None, S_NULL),
+    State::new(SPR_SMT2, 0, -1, Action::None, S_NULL),
+    State::new(SPR_GOR1, 0, 10, Action::None, S_BLOODYTWITCH2),
+    State::new(SPR_GOR1, 1, 15, Action::None, S_BLOODYTWITCH3),
+    State::new(SPR_GOR1, 2, 8, Action::None, S_BLOODYTWITCH4),
+    State::new(SPR_GOR1, 1, 6, Action::None, S_BLOODYTWITCH),
+    State::new(SPR_PLAY, 13, -1, Action::None, S_NULL),
+    State::new(SPR_PLAY, 18, -1, Action::None, S_NULL),
+    State::new(SPR_POL2, 0, -1, Action::None, S_NULL),
+    State::new(SPR_POL5, 0, -1, Action::None, S_NULL),
+    State::new(SPR_POL4, 0, -1, Action::None, S_NULL),
+    State::new(SPR_POL3, FF_FULLBRIGHT, 6, Action::None, S_HEADCANDLES2),
+    State::new(SPR_POL3, FF_FULLBRIGHT | 1, 6, Action::None, S_HEADCANDLES),
+    State::new(SPR_POL1, 0, -1, Action::None, S_NULL),
+    State::new(SPR_POL6, 0, 6, Action::None, S_LIVESTICK2),
+    State::new(SPR_POL6, 1, 8, Action::None, S_LIVESTICK),
+    State::new(SPR_GOR2, 0, -1, Action::None, S_NULL),
+    State::new(SPR_GOR3, 0, -1, Action::None, S_NULL),
+    State::new(SPR_GOR4, 0, -1, Action::None, S_NULL),
+    State::new(SPR_GOR5, 0, -1, Action::None, S_NULL),
+    State::new(SPR_SMIT, 0, -1, Action::None, S_NULL),
+    State::new(SPR_COL1, 0, -1, Action::None, S_NULL),
+    State::new(SPR_COL2, 0, -1, Action::None, S_NULL),
+    State::new(SPR_COL3, 0, -1, Action::None, S_NULL),
+    State::new(SPR_COL4, 0, -1, Action::None, S_NULL),
+    State::new(SPR_CAND, FF_FULLBRIGHT, -1, Action::None, S_NULL),
+    State::new(SPR_CBRA, FF_FULLBRIGHT, -1, Action::None, S_NULL),
+    State::new(SPR_COL6, 0, -1, Action::None, S_NULL),
+    State::new(SPR_TRE1, 0, -1, Action::None, S_NULL),
+    State::new(SPR_TRE2, 0, -1, Action::None, S_NULL),
+    State::new(SPR_ELEC, 0, -1, Action::None, S_NULL),
+    State::new(SPR_CEYE, FF_FULLBRIGHT, 6, Action::None, S_EVILEYE2),
+    State::new(SPR_CEYE, FF_FULLBRIGHT | 1, 6, Action::None, S_EVILEYE3),
+    State::new(SPR_CEYE, FF_FULLBRIGHT | 2, 6, Action::None, S_EVILEYE4),
+    State::new(SPR_CEYE, FF_FULLBRIGHT | 1, 6, Action::None, S_EVILEYE),
+    State::new(SPR_FSKU, FF_FULLBRIGHT, 6, Action::None, S_FLOATSKULL2),
+    State::new(SPR_FSKU, FF_FULLBRIGHT | 1, 6, Action::None, S_FLOATSKULL3),
+    State::new(SPR_FSKU, FF_FULLBRIGHT | 2, 6, Action::None, S_FLOATSKULL),
+    State::new(SPR_COL5, 0, 14, Action::None, S_HEARTCOL2),
+    State::new(SPR_COL5, 1, 14, Action::None, S_HEARTCOL),
+    State::new(SPR_TBLU, FF_FULLBRIGHT, 4, Action::None, S_BLUETORCH2),
+    State::new(SPR_TBLU, FF_FULLBRIGHT | 1, 4, Action::None, S_BLUETORCH3),
+    State::new(SPR_TBLU, FF_FULLBRIGHT | 2, 4, Action::None, S_BLUETORCH4),
+    State::new(SPR_TBLU, FF_FULLBRIGHT | 3, 4, Action::None, S_BLUETORCH),
+    State::new(SPR_TGRN, FF_FULLBRIGHT, 4, Action::None, S_GREENTORCH2),
+    State::new(SPR_TGRN, FF_FULLBRIGHT | 1, 4, Action::None, S_GREENTORCH3),
+    State::new(SPR_TGRN, FF_FULLBRIGHT | 2, 4, Action::None, S_GREENTORCH4),
+    State::new(SPR_TGRN, FF_FULLBRIGHT | 3, 4, Action::None, S_GREENTORCH),
+    State::new(SPR_TRED, FF_FULLBRIGHT, 4, Action::None, S_REDTORCH2),
+    State::new(SPR_TRED, FF_FULLBRIGHT | 1, 4, Action::None, S_REDTORCH3),
+    State::new(SPR_TRED, FF_FULLBRIGHT | 2, 4, Action::None, S_REDTORCH4),
+    State::new(SPR_TRED, FF_FULLBRIGHT | 3, 4, Action::None, S_REDTORCH),
+    State::new(SPR_SMBT, FF_FULLBRIGHT, 4, Action::None, S_BTORCHSHRT2),
+    State::new(SPR_SMBT, FF_FULLBRIGHT | 1, 4, Action::None, S_BTORCHSHRT3),
+    State::new(SPR_SMBT, FF_FULLBRIGHT | 2, 4, Action::None, S_BTORCHSHRT4),
+    State::new(SPR_SMBT, FF_FULLBRIGHT | 3, 4, Action::None, S_BTORCHSHRT),
+    State::new(SPR_SMGT, FF_FULLBRIGHT, 4, Action::None, S_GTORCHSHRT2),
+    State::new(SPR_SMGT, FF_FULLBRIGHT | 1, 4, Action::None, S_GTORCHSHRT3),
+    State::new(SPR_SMGT, FF_FULLBRIGHT | 2, 4, Action::None, S_GTORCHSHRT4),
+    State::new(SPR_SMGT, FF_FULLBRIGHT | 3, 4, Action::None, S_GTORCHSHRT),
+    State::new(SPR_SMRT, FF_FULLBRIGHT, 4, Action::None, S_RTORCHSHRT2),
+    State::new(SPR_SMRT, FF_FULLBRIGHT | 1, 4, Action::None, S_RTORCHSHRT3),
+    State::new(SPR_SMRT, FF_FULLBRIGHT | 2, 4, Action::None, S_RTORCHSHRT4),
+    State::new(SPR_SMRT, FF_FULLBRIGHT | 3, 4, Action::None, S_RTORCHSHRT),
+    State::new(SPR_HDB1, 0, -1, Action::None, S_NULL),
+    State::new(SPR_HDB2, 0, -1, Action::None, S_NULL),
+    State::new(SPR_HDB3, 0, -1, Action::None, S_NULL),
+    State::new(SPR_HDB4, 0, -1, Action::None, S_NULL),
+    State::new(SPR_HDB5, 0, -1, Action::None, S_NULL),
+    State::new(SPR_HDB6, 0, -1, Action::None, S_NULL),
+    State::new(SPR_POB1, 0, -1, Action::None, S_NULL),
+    State::new(SPR_POB2, 0, -1, Action::None, S_NULL),
+    State::new(SPR_BRS1, 0, -1, Action::None, S_NULL),
+    State::new(SPR_TLMP, FF_FULLBRIGHT, 4, Action::None, S_TECHLAMP2),
+    State::new(SPR_TLMP, FF_FULLBRIGHT | 1, 4, Action::None, S_TECHLAMP3),
+    State::new(SPR_TLMP, FF_FULLBRIGHT | 2, 4, Action::None, S_TECHLAMP4),
+    State::new(SPR_TLMP, FF_FULLBRIGHT | 3, 4, Action::None, S_TECHLAMP),
+    State::new(SPR_TLP2, FF_FULLBRIGHT, 4, Action::None, S_TECH2LAMP2),
+    State::new(SPR_TLP2, FF_FULLBRIGHT | 1, 4, Action::None, S_TECH2LAMP3),
+    State::new(SPR_TLP2, FF_FULLBRIGHT | 2, 4, Action::None, S_TECH2LAMP4),
+    State::new(SPR_TLP2, FF_FULLBRIGHT | 3, 4, Action::None, S_TECH2LAMP),
+];
@@ -0,0 +1,98 @@
+// Port of d_items.c. The states each weapon's player sprite goes through
+use crate::info::states::*;
+use crate::info::StateNum;
+use crate::play::player::{AmmoType, AM_CELL, AM_CLIP, AM_MISL, AM_NOAMMO, AM_SHELL, NUMWEAPONS};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WeaponInfo {
+    pub ammo: AmmoType,
+    pub up_state: StateNum,
+    pub down_state: StateNum,
+    pub ready_state: StateNum,
+    pub attack_state: StateNum,
+    pub flash_state: StateNum,
+}
+
+pub static WEAPONINFO: [WeaponInfo; NUMWEAPONS] = [
+    // Fist
+    WeaponInfo {
+        ammo: AM_NOAMMO,
+        up_state: S_PUNCHUP,
+        down_state: S_PUNCHDOWN,
+        ready_state: S_PUNCH,
+        attack_state: S_PUNCH1,
+        flash_state: S_NULL,
+    },
+    // Pistol
+    WeaponInfo {
+        ammo: AM_CLIP,
+        up_state: S_PISTOLUP,
+        down_state: S_PISTOLDOWN,
+        ready_state: S_PISTOL,
+        attack_state: S_PISTOL1,
+        flash_state: S_PISTOLFLASH,
+    },
+    // Shotgun
+    WeaponInfo {
+        ammo: AM_SHELL,
+        up_state: S_SGUNUP,
+        down_state: S_SGUNDOWN,
+        ready_state: S_SGUN,
+        attack_state: S_SGUN1,
+        flash_state: S_SGUNFLASH1,
+    },
+    // Chaingun
+    WeaponInfo {
+        ammo: AM_CLIP,
+        up_state: S_CHAINUP,
+        down_state: S_CHAINDOWN,
+        ready_state: S_CHAIN,
+        attack_state: S_CHAIN1,
+        flash_state: S_CHAINFLASH1,
+    },
+    // Missile launcher
+    WeaponInfo {
+        ammo: AM_MISL,
+        up_state: S_MISSILEUP,
+        down_state: S_MISSILEDOWN,
+        ready_state: S_MISSILE,
+        attack_state: S_MISSILE1,
+        flash_state: S_MISSILEFLASH1,
+    },
+    // Plasma rifle
+    WeaponInfo {
+        ammo: AM_CELL,
+        up_state: S_PLASMAUP,
+        down_state: S_PLASMADOWN,
+        ready_state: S_PLASMA,
+        attack_state: S_PLASMA1,
+        flash_state: S_PLASMAFLASH1,
+    },
+    // Bfg 9000
+    WeaponInfo {
+        ammo: AM_CELL,
+        up_state: S_BFGUP,
+        down_state: S_BFGDOWN,
+        ready_state: S_BFG,
+        attack_state: S_BFG1,
+        flash_state: S_BFGFLASH1,
+    },
+    // Chainsaw
+    WeaponInfo {
+        ammo: AM_NOAMMO,
+        up_state: S_SAWUP,
+        down_state: S_SAWDOWN,
+        ready_state: S_SAW,
+        attack_state: S_SAW1,
+        flash_state: S_NULL,
+    },
+    // Super shotgun
+    WeaponInfo {
+        ammo: AM_SHELL,
+        up_state: S_DSGUNUP,
+        down_state: S_DSGUNDOWN,
+        ready_state: S_DSGUN,
+        attack_state: S_DSGUN1,
+        flash_state: S_DSGUNFLASH1,
+    },
+];
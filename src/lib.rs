@@ -0,0 +1,20 @@
+pub mod automap;
+pub mod config;
+pub mod endoom;
+pub mod fixed;
+pub mod game;
+pub mod graphics;
+pub mod image;
+pub mod info;
+pub mod m_cheat;
+pub mod m_random;
+pub mod music;
+pub mod option;
+pub mod play;
+pub mod render;
+pub mod sound;
+#[cfg(test)]
+pub(crate) mod test_util;
+pub mod util;
+pub mod video;
+pub mod wad;
@@ -0,0 +1,61 @@
+// Port of m_cheat.c. Cheat codes are typed a key at a time into a sequence
+// that goes back to the start on any wrong key
+pub struct CheatSequence {
+    sequence: &'static [u8],
+    position: usize,
+}
+
+impl CheatSequence {
+    pub const fn new(sequence: &'static [u8]) -> Self {
+        CheatSequence {
+            sequence,
+            position: 0,
+        }
+    }
+
+    // cht_CheckCheat. Returns true when the key finishes the sequence. A
+    // wrong key isn't checked against the start of the sequence again
+    pub fn check(&mut self, key: i32) -> bool {
+        if key == i32::from(self.sequence[self.position]) {
+            self.position += 1;
+        } else {
+            self.position = 0;
+        }
+
+        if self.position == self.sequence.len() {
+            self.position = 0;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::m_cheat::CheatSequence;
+
+    fn type_keys(cheat: &mut CheatSequence, keys: &[u8]) -> Vec<bool> {
+        keys.iter()
+            .map(|&key| cheat.check(i32::from(key)))
+            .collect()
+    }
+
+    #[test]
+    fn test_cheat_sequence_check_matches_the_whole_sequence() {
+        let mut cheat: CheatSequence = CheatSequence::new(b"iddt");
+
+        assert_eq!(
+            type_keys(&mut cheat, b"iddtiddt"),
+            vec![false, false, false, true, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_cheat_sequence_check_starts_over_on_a_wrong_key() {
+        let mut cheat: CheatSequence = CheatSequence::new(b"iddt");
+
+        // The second i is the wrong key, not the start of the cheat
+        assert_eq!(type_keys(&mut cheat, b"iidddt"), vec![false; 6]);
+        assert!(type_keys(&mut cheat, b"iddt")[3]);
+    }
+}
@@ -0,0 +1,210 @@
+use std::panic::Location;
+
+// Same table as the original, every "random" number comes from here
+#[rustfmt::skip]
+pub const RNDTABLE: [u8; 256] = [
+      0,   8, 109, 220, 222, 241, 149, 107,  75, 248, 254, 140,  16,  66,  74,  21,
+    211,  47,  80, 242, 154,  27, 205, 128, 161,  89,  77,  36,  95, 110,  85,  48,
+    212, 140, 211, 249,  22,  79, 200,  50,  28, 188,  52, 140, 202, 120,  68, 145,
+     62,  70, 184, 190,  91, 197, 152, 224, 149, 104,  25, 178, 252, 182, 202, 182,
+    141, 197,   4,  81, 181, 242, 145,  42,  39, 227, 156, 198, 225, 193, 219,  93,
+    122, 175, 249,   0, 175, 143,  70, 239,  46, 246, 163,  53, 163, 109, 168, 135,
+      2, 235,  25,  92,  20, 145, 138,  77,  69, 166,  78, 176, 173, 212, 166, 113,
+     94, 161,  41,  50, 239,  49, 111, 164,  70,  60,   2,  37, 171,  75, 136, 156,
+     11,  56,  42, 146, 138, 229,  73, 146,  77,  61,  98, 196, 135, 106,  63, 197,
+    195,  86,  96, 203, 113, 101, 170, 247, 181, 113,  80, 250, 108,   7, 255, 237,
+    129, 226,  79, 107, 112, 166, 103, 241,  24, 223, 239, 120, 198,  58,  60,  82,
+    128,   3, 184,  66, 143, 224, 145, 224,  81, 206, 163,  45,  63,  90, 168, 114,
+     59,  33, 159,  95,  28, 139, 123,  98, 125, 196,  15,  70, 194, 253,  54,  14,
+    109, 226,  71,  17, 161,  93, 186,  87, 244, 138,  20,  52, 123, 251,  26,  36,
+     17,  46,  52, 231, 232,  76,  31, 221,  84,  37, 216, 165, 212, 106, 197, 242,
+     98,  43,  39, 175, 254, 145, 190,  84, 118, 222, 187, 136, 120, 163, 236, 249,
+];
+
+// One P_Random call recorded while call logging is on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RandomCall {
+    // Index into RNDTABLE the value came from
+    pub index: u8,
+    pub value: i32,
+    pub caller: &'static Location<'static>,
+}
+
+// Port of m_random.c. The original kept two global indexes into the table,
+// one for the game simulation that has to be the same for every player
+// and demo playback(P_Random) and one for everything else(M_Random) so
+// menus, screen wipes, etc don't affect demo sync
+#[derive(Clone, Debug, Default)]
+pub struct Random {
+    rndindex: u8,
+    prndindex: u8,
+    call_log: Option<Vec<RandomCall>>,
+}
+
+impl Random {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Gameplay random number, 0 to 255
+    #[track_caller]
+    pub fn p_random(&mut self) -> i32 {
+        self.prndindex = self.prndindex.wrapping_add(1);
+        let value: i32 = i32::from(RNDTABLE[usize::from(self.prndindex)]);
+
+        if let Some(call_log) = &mut self.call_log {
+            call_log.push(RandomCall {
+                index: self.prndindex,
+                value,
+                caller: Location::caller(),
+            });
+        }
+
+        value
+    }
+
+    // P_Random() - P_Random(), with the first call always happening first
+    #[track_caller]
+    pub fn p_sub_random(&mut self) -> i32 {
+        let first: i32 = self.p_random();
+        first - self.p_random()
+    }
+
+    // Cosmetic random number, 0 to 255. Not part of the game state
+    pub fn m_random(&mut self) -> i32 {
+        self.rndindex = self.rndindex.wrapping_add(1);
+        i32::from(RNDTABLE[usize::from(self.rndindex)])
+    }
+
+    // M_ClearRandom. Done when a new level is started so demos and
+    // netgames all start from the same place
+    pub fn clear(&mut self) {
+        self.rndindex = 0;
+        self.prndindex = 0;
+    }
+
+    // Starts the gameplay index at a given position, for savegames and
+    // tools that need to resume a level part way through
+    pub fn set_prndindex(&mut self, index: u8) {
+        self.prndindex = index;
+    }
+
+    pub fn prndindex(&self) -> u8 {
+        self.prndindex
+    }
+
+    pub fn rndindex(&self) -> u8 {
+        self.rndindex
+    }
+
+    // Starts recording every P_Random call and where it was called from.
+    // Used to find the first call that differs when a demo desyncs
+    pub fn start_call_log(&mut self) {
+        self.call_log = Some(Vec::new());
+    }
+
+    // Returns the calls recorded since logging started or the last take,
+    // e.g. everything from one tic. Logging carries on afterwards
+    pub fn take_call_log(&mut self) -> Vec<RandomCall> {
+        self.call_log
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    pub fn stop_call_log(&mut self) -> Vec<RandomCall> {
+        self.call_log.take().unwrap_or_default()
+    }
+
+    pub fn is_logging_calls(&self) -> bool {
+        self.call_log.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::m_random::{Random, RandomCall, RNDTABLE};
+
+    #[test]
+    fn test_rndtable_matches_original() {
+        assert_eq!(RNDTABLE[..8], [0, 8, 109, 220, 222, 241, 149, 107]);
+        assert_eq!(RNDTABLE[248..], [118, 222, 187, 136, 120, 163, 236, 249]);
+        assert_eq!(
+            RNDTABLE.iter().map(|value| u32::from(*value)).sum::<u32>(),
+            32986
+        );
+    }
+
+    #[test]
+    fn test_p_random_and_m_random_use_separate_indexes() {
+        let mut random: Random = Random::new();
+
+        assert_eq!(random.p_random(), 8);
+        assert_eq!(random.p_random(), 109);
+        assert_eq!(random.m_random(), 8);
+        assert_eq!(random.p_random(), 220);
+        assert_eq!(random.prndindex(), 3);
+        assert_eq!(random.rndindex(), 1);
+    }
+
+    #[test]
+    fn test_p_random_wraps_around_the_table() {
+        let mut random: Random = Random::new();
+        random.set_prndindex(254);
+
+        assert_eq!(random.p_random(), 249);
+        assert_eq!(random.p_random(), 0);
+        assert_eq!(random.p_random(), 8);
+    }
+
+    #[test]
+    fn test_p_sub_random_calls_in_order() {
+        let mut random: Random = Random::new();
+
+        assert_eq!(random.p_sub_random(), 8 - 109);
+        assert_eq!(random.prndindex(), 2);
+    }
+
+    #[test]
+    fn test_clear_resets_both_indexes() {
+        let mut random: Random = Random::new();
+        random.p_random();
+        random.m_random();
+        random.m_random();
+
+        random.clear();
+
+        assert_eq!(random.prndindex(), 0);
+        assert_eq!(random.rndindex(), 0);
+        assert_eq!(random.p_random(), 8);
+    }
+
+    #[test]
+    fn test_call_log_records_p_random_call_sites() {
+        let mut random: Random = Random::new();
+        random.p_random();
+        assert!(!random.is_logging_calls());
+        assert_eq!(random.take_call_log(), Vec::new());
+
+        random.start_call_log();
+        let line: u32 = line!() + 1;
+        random.p_random();
+        random.m_random();
+        random.p_sub_random();
+
+        let calls: Vec<RandomCall> = random.take_call_log();
+        assert_eq!(calls.len(), 3);
+        assert_eq!((calls[0].index, calls[0].value), (2, 109));
+        assert_eq!(calls[0].caller.line(), line);
+        assert!(calls[0].caller.file().ends_with("m_random.rs"));
+        assert_eq!(calls[1].caller.line(), line + 2);
+        assert_eq!(calls[2].caller.line(), line + 2);
+
+        // Next tic starts with an empty log
+        random.p_random();
+        assert_eq!(random.take_call_log().len(), 1);
+        random.p_random();
+        assert_eq!(random.stop_call_log().len(), 1);
+        assert!(!random.is_logging_calls());
+    }
+}
@@ -1,14 +1,24 @@
-use std::{env, path::PathBuf};
+use std::{env, fs, path::PathBuf, process};
 
-mod config;
-mod option;
-mod util;
-mod wad;
-
-use config::Config;
-use option::DoomOptions;
-
-use crate::wad::DoomFile;
+use zz_doom::automap::AutomapResponder;
+use zz_doom::config::{Config, KeyBindings};
+use zz_doom::endoom::Endoom;
+use zz_doom::game::demo::{self, DemoPlayback};
+use zz_doom::game::desync;
+use zz_doom::game::event::EventQueue;
+use zz_doom::game::input::{self, GameInput};
+use zz_doom::game::realtime::RealTimeDriver;
+use zz_doom::game::savegame;
+use zz_doom::game::timedemo::{self, TimeDemoResults};
+use zz_doom::game::{Game, GameState};
+use zz_doom::option::{DoomOption, DoomOptions};
+use zz_doom::play::ticcmd::TicCmd;
+use zz_doom::play::world::WorldSettings;
+use zz_doom::play::MAXPLAYERS;
+use zz_doom::video::headless::{FrameDump, HeadlessVideo, ScreenshotFormat};
+use zz_doom::video::terminal::{self, TerminalVideo};
+use zz_doom::video::Screen;
+use zz_doom::wad::{self, DoomFile};
 
 fn main() {
     // Skipping the first arg as this is the executable name
@@ -50,4 +60,351 @@ fn main() {
             Err(error) => eprintln!("Error processing file {}.\n {}", wad_path.display(), error),
         }
     }
+
+    if let Launch::Demo(demo_option) = launch(&doom_options) {
+        let name: &str = option_value(&doom_options, demo_option);
+        println!("Playing demo {}.lmp.", name);
+
+        match demo::load_demo(&mut doom_files, name).and_then(DemoPlayback::from_lump_data) {
+            Ok(demo_playback) => {
+                let mut game: Game = Game::play_demo(
+                    WorldSettings::new(&config, &doom_options),
+                    doom_files,
+                    demo_playback,
+                )
+                .unwrap_or_else(|error| {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                });
+
+                if doom_options.is_option_enabled("-demoverify") {
+                    verify_demo(&mut game, &doom_options);
+                } else if demo_option == "-timedemo" {
+                    time_demo(&mut game, &config, &doom_options);
+                } else if let Err(error) = run_realtime(&mut game, &config, &doom_options, |game| {
+                    !game.demo_finished()
+                }) {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+                doom_files = game.doom_files;
+            }
+            // The original carried on without the demo
+            Err(error) => eprintln!("{}", error),
+        }
+    } else if realtime_video(&doom_options) == RealtimeVideo::None {
+        // Nothing would ever stop the game without somewhere to show it
+        eprintln!("No video backend to play on, use -terminal or -framedump");
+    } else {
+        let mut game: Game =
+            Game::new(&config, &doom_options, doom_files).unwrap_or_else(|error| {
+                eprintln!("{}", error);
+                process::exit(1);
+            });
+
+        if doom_options.is_option_enabled("-loadgame") {
+            // Only the first character is the slot, like the original
+            let slot: Option<usize> = option_value(&doom_options, "-loadgame")
+                .chars()
+                .next()
+                .and_then(|c| c.to_digit(10))
+                .map(|slot| slot as usize);
+            match slot {
+                Some(slot) => game.load_game(savegame::save_game_path(&game.save_dir, slot)),
+                None => eprintln!("Invalid -loadgame slot"),
+            }
+        }
+        if doom_options.is_option_enabled("-record") {
+            game.start_recording();
+        }
+
+        // D_DoomLoop. With only -framedump there's no input and the player
+        // stands still
+        let result: Result<(), String> = run_realtime(&mut game, &config, &doom_options, |game| {
+            game.game_state == GameState::Level
+        });
+
+        if doom_options.is_option_enabled("-record") {
+            let demo_name: String = format!("{}.lmp", option_value(&doom_options, "-record"));
+            if let Some(demo_data) = game.finish_recording() {
+                match fs::write(&demo_name, demo_data) {
+                    Ok(()) => println!("Demo {} recorded", demo_name),
+                    Err(error) => eprintln!("Unable to write demo {}. {}", demo_name, error),
+                }
+            }
+        }
+        if let Err(error) = result {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+        doom_files = game.doom_files;
+    }
+
+    // Original showed the ENDOOM text screen after shutting down
+    if config.show_endoom {
+        if let Some(lump_data) = wad::find_lump_data(&mut doom_files, "ENDOOM") {
+            match Endoom::from_lump(&lump_data) {
+                Ok(endoom) => print!("{}", endoom.to_ansi()),
+                Err(error) => eprintln!("{}", error),
+            }
+        }
+    }
+}
+
+// A demo with -playdemo or -timedemo, otherwise a new game that runs
+// until it's quit
+#[derive(Debug, PartialEq, Eq)]
+enum Launch {
+    Demo(&'static str),
+    Game,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RealtimeVideo {
+    Terminal,
+    Headless,
+    None,
+}
+
+fn launch(doom_options: &DoomOptions) -> Launch {
+    ["-playdemo", "-timedemo"]
+        .into_iter()
+        .find(|option_name| doom_options.is_option_enabled(option_name))
+        .map_or(Launch::Game, Launch::Demo)
+}
+
+fn realtime_video(doom_options: &DoomOptions) -> RealtimeVideo {
+    if doom_options.is_option_enabled("-terminal") {
+        RealtimeVideo::Terminal
+    } else if doom_options.is_option_enabled("-framedump") {
+        RealtimeVideo::Headless
+    } else {
+        RealtimeVideo::None
+    }
+}
+
+fn verify_demo(game: &mut Game, doom_options: &DoomOptions) {
+    let hash_path: &str = option_value(doom_options, "-demoverify");
+    let hash_stream: String = fs::read_to_string(hash_path).unwrap_or_else(|error| {
+        eprintln!("Unable to read {}. {}", hash_path, error);
+        process::exit(1);
+    });
+
+    match desync::verify_demo(game, &hash_stream) {
+        Ok(tics) => println!("Demo verified, {} tics match", tics),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+fn time_demo(game: &mut Game, config: &Config, doom_options: &DoomOptions) {
+    let no_draw: bool = doom_options.is_option_enabled("-nodraw");
+    let mut screen: Option<Screen> = (!no_draw).then(|| {
+        new_screen(game, config, doom_options).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        })
+    });
+    // Only the headless backend draws here so there's no terminal to restore
+    let mut draw = |game: &Game| {
+        if let Err(error) = draw_frame(screen.as_mut(), game) {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+    let draw: Option<&mut dyn FnMut(&Game)> = if no_draw { None } else { Some(&mut draw) };
+
+    let results: TimeDemoResults = timedemo::time_demo(game, draw);
+    println!("{}", results.summary());
+
+    if doom_options.is_option_enabled("-timedemojson") {
+        let json_path: &str = option_value(doom_options, "-timedemojson");
+        if let Err(error) = fs::write(json_path, results.to_json()) {
+            eprintln!("Unable to write {}. {}", json_path, error);
+        }
+    }
+}
+
+// D_DoomLoop. Input comes from the video backend, through the responders
+// into the console player's ticcmds. Errors come back once the backend has
+// been dropped so the terminal is usable again for them
+fn run_realtime(
+    game: &mut Game,
+    config: &Config,
+    doom_options: &DoomOptions,
+    keep_going: impl Fn(&Game) -> bool,
+) -> Result<(), String> {
+    let mut screen: Option<Screen> = realtime_screen(game, config, doom_options)?;
+    let mut event_queue: EventQueue = EventQueue::new();
+    let key_bindings: KeyBindings = if realtime_video(doom_options) == RealtimeVideo::Terminal {
+        terminal::terminal_key_bindings(config.key_bindings)
+    } else {
+        config.key_bindings
+    };
+    let mut game_input: GameInput = GameInput::new(
+        key_bindings,
+        input::turbo_scale(doom_options),
+        doom_options.is_option_enabled("-devparm"),
+    );
+    let mut driver: RealTimeDriver = RealTimeDriver::new();
+
+    loop {
+        driver.run_tics(game, &mut |game: &Game| {
+            // I_StartTic, D_ProcessEvents and G_BuildTiccmd. The automap
+            // only sees events during a level, like G_Responder gives them
+            match screen.as_mut() {
+                Some(screen) => {
+                    screen.video.start_tic(&mut event_queue);
+                    if game.game_state == GameState::Level {
+                        screen.automap.ticker(&game.world);
+                        let mut automap: AutomapResponder = AutomapResponder {
+                            automap: &mut screen.automap,
+                            world: &game.world,
+                        };
+                        event_queue.process(&mut [&mut automap, &mut game_input]);
+                    } else {
+                        event_queue.process(&mut [&mut game_input]);
+                    }
+                }
+                None => event_queue.process(&mut [&mut game_input]),
+            }
+
+            let mut cmds: Vec<TicCmd> = vec![TicCmd::default(); MAXPLAYERS];
+            cmds[game.world.settings.console_player] = game_input.build_ticcmd();
+            cmds
+        });
+        // S_UpdateSounds. Nothing plays them yet, so the sounds of every tic
+        // run for this frame are dropped here
+        game.world.sound_events.clear();
+
+        if let Some(screen) = screen.as_mut() {
+            if game_input.screenshot_requested {
+                game_input.screenshot_requested = false;
+                screen.video.request_screenshot();
+            }
+        }
+        if !draw_frame(screen.as_mut(), game)? || !keep_going(game) {
+            return Ok(());
+        }
+    }
+}
+
+// Screenshots go in the current directory like the original's and frames
+// are written to disk when there's a -framedump for them
+fn headless_video(config: &Config, doom_options: &DoomOptions) -> HeadlessVideo {
+    let screenshot_format: ScreenshotFormat = if config.png_screenshots {
+        ScreenshotFormat::Png
+    } else {
+        ScreenshotFormat::Pcx
+    };
+    let mut video: HeadlessVideo = HeadlessVideo::new(PathBuf::from("."), screenshot_format);
+
+    if doom_options.is_option_enabled("-framedump") {
+        let path: &str = option_value(doom_options, "-framedump");
+        match FrameDump::from_path(path) {
+            Ok(frame_dump) => video = video.with_frame_dump(frame_dump),
+            Err(error) => {
+                eprintln!("Unable to dump frames to {}. {}", path, error);
+                process::exit(1);
+            }
+        }
+    }
+
+    video
+}
+
+// Without -terminal there's no display, only the headless backend
+fn new_screen(
+    game: &mut Game,
+    config: &Config,
+    doom_options: &DoomOptions,
+) -> Result<Screen, String> {
+    let video: HeadlessVideo = headless_video(config, doom_options);
+    Screen::new(&mut game.doom_files, 1, Box::new(video)).map_err(|error| error.to_string())
+}
+
+// Drawing in real time is only worth it when the frames go somewhere, to
+// the terminal with -terminal or to disk with -framedump
+fn realtime_screen(
+    game: &mut Game,
+    config: &Config,
+    doom_options: &DoomOptions,
+) -> Result<Option<Screen>, String> {
+    match realtime_video(doom_options) {
+        RealtimeVideo::Terminal => {
+            let screenshots: HeadlessVideo = headless_video(config, doom_options);
+            let video: TerminalVideo = TerminalVideo::new(screenshots)
+                .map_err(|error| format!("Unable to use the terminal for video. {}", error))?;
+            // A failed Screen::new drops the video, putting the terminal back
+            let screen: Screen = Screen::new(&mut game.doom_files, 1, Box::new(video))
+                .map_err(|error| error.to_string())?;
+            Ok(Some(screen))
+        }
+        RealtimeVideo::Headless => new_screen(game, config, doom_options).map(Some),
+        RealtimeVideo::None => Ok(None),
+    }
+}
+
+// Returns false once the video backend wants to quit
+fn draw_frame(screen: Option<&mut Screen>, game: &Game) -> Result<bool, String> {
+    let Some(screen) = screen else {
+        return Ok(true);
+    };
+    screen
+        .draw(game)
+        .map_err(|error| format!("Unable to write frame. {}", error))?;
+    Ok(!screen.video.quit_requested())
+}
+
+fn option_value<'a>(doom_options: &'a DoomOptions, option_name: &str) -> &'a str {
+    let option: &DoomOption = doom_options.get_option_by_name(option_name).unwrap();
+    option.values.first().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use zz_doom::option::DoomOptions;
+
+    use crate::{launch, realtime_video, Launch, RealtimeVideo};
+
+    fn create_options(args: &[&str]) -> DoomOptions {
+        DoomOptions::new(args.iter().map(|arg| arg.to_string()).collect())
+    }
+
+    #[test]
+    fn test_launch_plays_the_game_without_a_demo() {
+        assert_eq!(launch(&create_options(&[])), Launch::Game);
+        assert_eq!(launch(&create_options(&["-terminal"])), Launch::Game);
+        assert_eq!(
+            launch(&create_options(&["-record", "demo", "-loadgame", "1"])),
+            Launch::Game
+        );
+        assert_eq!(
+            launch(&create_options(&["-playdemo", "demo1"])),
+            Launch::Demo("-playdemo")
+        );
+        assert_eq!(
+            launch(&create_options(&["-timedemo", "demo1", "-nodraw"])),
+            Launch::Demo("-timedemo")
+        );
+    }
+
+    #[test]
+    fn test_realtime_video_picks_the_terminal_with_terminal() {
+        assert_eq!(
+            realtime_video(&create_options(&["-terminal"])),
+            RealtimeVideo::Terminal
+        );
+        assert_eq!(
+            realtime_video(&create_options(&["-terminal", "-framedump", "frames"])),
+            RealtimeVideo::Terminal
+        );
+        assert_eq!(
+            realtime_video(&create_options(&["-framedump", "frames"])),
+            RealtimeVideo::Headless
+        );
+        assert_eq!(realtime_video(&create_options(&[])), RealtimeVideo::None);
+    }
 }
@@ -0,0 +1,40 @@
+use std::fmt::{self, Display};
+
+pub mod genmidi;
+pub mod midi;
+pub mod mus;
+pub mod opl;
+pub mod sequencer;
+pub mod synth;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum MusicError {
+    InvalidMus(String),
+    InvalidGenmidi(String),
+}
+
+impl Display for MusicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidMus(reason) => write!(f, "Invalid MUS data. {}", reason),
+            Self::InvalidGenmidi(reason) => write!(f, "Invalid GENMIDI data. {}", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::music::MusicError;
+
+    #[test]
+    fn test_music_error_implements_display_trait() {
+        let music_errors: Vec<MusicError> = vec![
+            MusicError::InvalidMus(String::from("test")),
+            MusicError::InvalidGenmidi(String::from("test")),
+        ];
+
+        for music_error in music_errors {
+            println!("{}", music_error);
+        }
+    }
+}
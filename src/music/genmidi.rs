@@ -0,0 +1,302 @@
+use crate::music::MusicError;
+
+pub const GENMIDI_HEADER: [u8; 8] = *b"#OPL_II#";
+pub const GENMIDI_NUM_MELODIC: usize = 128;
+pub const GENMIDI_NUM_PERCUSSION: usize = 47;
+pub const GENMIDI_NUM_INSTRUMENTS: usize = GENMIDI_NUM_MELODIC + GENMIDI_NUM_PERCUSSION;
+// Percussion instruments cover General MIDI notes 35(Acoustic Bass Drum) to 81(Open Triangle)
+pub const GENMIDI_FIRST_PERCUSSION_NOTE: u8 = 35;
+pub const GENMIDI_LAST_PERCUSSION_NOTE: u8 = 81;
+
+// Instrument always plays the same note, used by most of the percussion
+pub const GENMIDI_FLAG_FIXED: u16 = 0x0001;
+// Instrument plays both of its voices at once
+pub const GENMIDI_FLAG_DOUBLE_VOICE: u16 = 0x0004;
+
+const GENMIDI_OPERATOR_SIZE: usize = 6;
+const GENMIDI_VOICE_SIZE: usize = 16;
+const GENMIDI_INSTRUMENT_SIZE: usize = 36;
+const GENMIDI_NAME_SIZE: usize = 32;
+
+// Register values for one OPL operator
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GenMidiOperator {
+    // Tremolo, vibrato, sustain, KSR and frequency multiplier(0x20)
+    pub tremolo: u8,
+    // Attack and decay rates(0x60)
+    pub attack: u8,
+    // Sustain level and release rate(0x80)
+    pub sustain: u8,
+    // 0xE0
+    pub waveform: u8,
+    // Key scale level, the top two bits of 0x40
+    pub scale: u8,
+    // Total level, the bottom six bits of 0x40
+    pub level: u8,
+}
+
+impl GenMidiOperator {
+    fn from_bytes(data: &[u8]) -> Self {
+        GenMidiOperator {
+            tremolo: data[0],
+            attack: data[1],
+            sustain: data[2],
+            waveform: data[3],
+            scale: data[4],
+            level: data[5],
+        }
+    }
+
+    fn to_bytes(self) -> [u8; GENMIDI_OPERATOR_SIZE] {
+        [
+            self.tremolo,
+            self.attack,
+            self.sustain,
+            self.waveform,
+            self.scale,
+            self.level,
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GenMidiVoice {
+    pub modulator: GenMidiOperator,
+    // Feedback and connection(0xC0)
+    pub feedback: u8,
+    pub carrier: GenMidiOperator,
+    pub base_note_offset: i16,
+}
+
+impl GenMidiVoice {
+    fn from_bytes(data: &[u8]) -> Self {
+        GenMidiVoice {
+            modulator: GenMidiOperator::from_bytes(&data[0..6]),
+            feedback: data[6],
+            carrier: GenMidiOperator::from_bytes(&data[7..13]),
+            base_note_offset: i16::from_le_bytes([data[14], data[15]]),
+        }
+    }
+
+    fn to_bytes(self) -> [u8; GENMIDI_VOICE_SIZE] {
+        let mut data: [u8; GENMIDI_VOICE_SIZE] = [0; GENMIDI_VOICE_SIZE];
+        data[0..6].copy_from_slice(&self.modulator.to_bytes());
+        data[6] = self.feedback;
+        data[7..13].copy_from_slice(&self.carrier.to_bytes());
+        data[14..16].copy_from_slice(&self.base_note_offset.to_le_bytes());
+        data
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GenMidiInstrument {
+    pub flags: u16,
+    // Detune of the second voice, 128 is none
+    pub fine_tuning: u8,
+    pub fixed_note: u8,
+    pub voices: [GenMidiVoice; 2],
+    pub name: String,
+}
+
+impl GenMidiInstrument {
+    pub fn is_fixed(&self) -> bool {
+        self.flags & GENMIDI_FLAG_FIXED != 0
+    }
+
+    pub fn is_double_voice(&self) -> bool {
+        self.flags & GENMIDI_FLAG_DOUBLE_VOICE != 0
+    }
+}
+
+// The GENMIDI lump holds the OPL instrument bank used for music
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenMidi {
+    pub instruments: Vec<GenMidiInstrument>,
+}
+
+impl GenMidi {
+    pub fn from_lump(data: &[u8]) -> Result<Self, MusicError> {
+        if !data.starts_with(&GENMIDI_HEADER) {
+            return Err(MusicError::InvalidGenmidi(String::from(
+                "Missing #OPL_II# header",
+            )));
+        }
+
+        let instruments_start: usize = GENMIDI_HEADER.len();
+        let names_start: usize =
+            instruments_start + GENMIDI_NUM_INSTRUMENTS * GENMIDI_INSTRUMENT_SIZE;
+
+        if data.len() < names_start {
+            return Err(MusicError::InvalidGenmidi(format!(
+                "Lump is {} bytes, expected at least {}",
+                data.len(),
+                names_start
+            )));
+        }
+
+        let instruments: Vec<GenMidiInstrument> = (0..GENMIDI_NUM_INSTRUMENTS)
+            .map(|index| {
+                let start: usize = instruments_start + index * GENMIDI_INSTRUMENT_SIZE;
+                let instrument: &[u8] = &data[start..start + GENMIDI_INSTRUMENT_SIZE];
+
+                // Some PWAD GENMIDIs leave the names off
+                let name_start: usize = names_start + index * GENMIDI_NAME_SIZE;
+                let name: String = data
+                    .get(name_start..name_start + GENMIDI_NAME_SIZE)
+                    .map(|name| {
+                        String::from_utf8_lossy(name)
+                            .trim_end_matches('\0')
+                            .split('\0')
+                            .next()
+                            .unwrap_or_default()
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+
+                GenMidiInstrument {
+                    flags: u16::from_le_bytes([instrument[0], instrument[1]]),
+                    fine_tuning: instrument[2],
+                    fixed_note: instrument[3],
+                    voices: [
+                        GenMidiVoice::from_bytes(&instrument[4..20]),
+                        GenMidiVoice::from_bytes(&instrument[20..36]),
+                    ],
+                    name,
+                }
+            })
+            .collect();
+
+        Ok(GenMidi { instruments })
+    }
+
+    pub fn to_lump(&self) -> Vec<u8> {
+        let mut lump: Vec<u8> = GENMIDI_HEADER.to_vec();
+
+        for instrument in &self.instruments {
+            lump.extend_from_slice(&instrument.flags.to_le_bytes());
+            lump.push(instrument.fine_tuning);
+            lump.push(instrument.fixed_note);
+            lump.extend_from_slice(&instrument.voices[0].to_bytes());
+            lump.extend_from_slice(&instrument.voices[1].to_bytes());
+        }
+
+        for instrument in &self.instruments {
+            let mut name: [u8; GENMIDI_NAME_SIZE] = [0; GENMIDI_NAME_SIZE];
+            let name_length: usize = instrument.name.len().min(GENMIDI_NAME_SIZE - 1);
+            name[..name_length].copy_from_slice(&instrument.name.as_bytes()[..name_length]);
+            lump.extend_from_slice(&name);
+        }
+
+        lump
+    }
+
+    pub fn melodic_instrument(&self, program: u8) -> &GenMidiInstrument {
+        &self.instruments[usize::from(program.min(GENMIDI_NUM_MELODIC as u8 - 1))]
+    }
+
+    // None for notes outside of the percussion range
+    pub fn percussion_instrument(&self, note: u8) -> Option<&GenMidiInstrument> {
+        if (GENMIDI_FIRST_PERCUSSION_NOTE..=GENMIDI_LAST_PERCUSSION_NOTE).contains(&note) {
+            Some(
+                &self.instruments
+                    [GENMIDI_NUM_MELODIC + usize::from(note - GENMIDI_FIRST_PERCUSSION_NOTE)],
+            )
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::music::genmidi::{
+        GenMidi, GenMidiInstrument, GenMidiOperator, GenMidiVoice, GENMIDI_FLAG_DOUBLE_VOICE,
+        GENMIDI_FLAG_FIXED, GENMIDI_NUM_INSTRUMENTS,
+    };
+    use crate::music::MusicError;
+
+    fn create_genmidi() -> GenMidi {
+        GenMidi {
+            instruments: (0..GENMIDI_NUM_INSTRUMENTS)
+                .map(|index| GenMidiInstrument {
+                    flags: if index >= 128 { GENMIDI_FLAG_FIXED } else { 0 },
+                    fine_tuning: 128,
+                    fixed_note: index as u8 / 4,
+                    voices: [
+                        GenMidiVoice {
+                            modulator: GenMidiOperator {
+                                tremolo: 0x21,
+                                attack: 0xF2,
+                                sustain: 0x34,
+                                waveform: 1,
+                                scale: 0x40,
+                                level: 0x10,
+                            },
+                            feedback: 0x0E,
+                            carrier: GenMidiOperator {
+                                tremolo: 0x01,
+                                attack: 0xF1,
+                                sustain: 0x25,
+                                waveform: 0,
+                                scale: 0,
+                                level: 0,
+                            },
+                            base_note_offset: -12,
+                        },
+                        GenMidiVoice::default(),
+                    ],
+                    name: format!("Instrument {}", index),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_genmidi_lump_round_trip() {
+        let genmidi: GenMidi = create_genmidi();
+        let lump: Vec<u8> = genmidi.to_lump();
+
+        assert_eq!(lump.len(), 11908);
+        assert_eq!(GenMidi::from_lump(&lump).unwrap(), genmidi);
+    }
+
+    #[test]
+    fn test_genmidi_from_lump_allows_missing_names() {
+        let mut lump: Vec<u8> = create_genmidi().to_lump();
+        lump.truncate(8 + 175 * 36);
+
+        let genmidi: GenMidi = GenMidi::from_lump(&lump).unwrap();
+
+        assert_eq!(genmidi.instruments[5].name, "");
+        assert_eq!(genmidi.instruments[5].voices[0].base_note_offset, -12);
+    }
+
+    #[test]
+    fn test_genmidi_from_lump_returns_error_for_invalid_lumps() {
+        assert_eq!(
+            GenMidi::from_lump(b"#OPL_I#"),
+            Err(MusicError::InvalidGenmidi(String::from(
+                "Missing #OPL_II# header"
+            )))
+        );
+
+        assert!(GenMidi::from_lump(b"#OPL_II#\0\0\0").is_err());
+    }
+
+    #[test]
+    fn test_genmidi_instrument_lookup() {
+        let mut genmidi: GenMidi = create_genmidi();
+        genmidi.instruments[129].flags |= GENMIDI_FLAG_DOUBLE_VOICE;
+
+        assert_eq!(genmidi.melodic_instrument(3).name, "Instrument 3");
+        assert_eq!(genmidi.melodic_instrument(200).name, "Instrument 127");
+        assert_eq!(genmidi.percussion_instrument(34), None);
+        assert_eq!(genmidi.percussion_instrument(82), None);
+
+        let instrument: &GenMidiInstrument = genmidi.percussion_instrument(36).unwrap();
+        assert_eq!(instrument.name, "Instrument 129");
+        assert!(instrument.is_fixed());
+        assert!(instrument.is_double_voice());
+        assert!(!genmidi.melodic_instrument(0).is_fixed());
+    }
+}
@@ -0,0 +1,321 @@
+use crate::music::mus::{MusEventKind, MusFile, MUS_PERCUSSION_CHANNEL};
+
+pub const MIDI_PERCUSSION_CHANNEL: u8 = 9;
+// 70 ticks per quarter note at the default 120bpm tempo gives
+// us the 140Hz tick rate MUS uses
+const MIDI_TICKS_PER_QUARTER_NOTE: u16 = 70;
+const MIDI_TEMPO_MICROSECONDS_PER_QUARTER_NOTE: u32 = 500_000;
+const MUS_DEFAULT_VOLUME: u8 = 127;
+
+const MIDI_NOTE_OFF: u8 = 0x80;
+const MIDI_NOTE_ON: u8 = 0x90;
+const MIDI_CONTROLLER: u8 = 0xB0;
+const MIDI_PROGRAM_CHANGE: u8 = 0xC0;
+const MIDI_PITCH_BEND: u8 = 0xE0;
+
+// MUS controller number to MIDI controller number.
+// MUS controller 0 is a program change so it has no MIDI controller
+const MUS_CONTROLLER_TO_MIDI: [u8; 10] = [
+    0,  // Program change
+    0,  // Bank select
+    1,  // Modulation
+    7,  // Volume
+    10, // Pan
+    11, // Expression
+    91, // Reverb depth
+    93, // Chorus depth
+    64, // Sustain pedal
+    67, // Soft pedal
+];
+
+// MUS system events 10-14 to MIDI channel mode messages
+const MUS_SYSTEM_EVENT_TO_MIDI: [u8; 5] = [
+    120, // All sounds off
+    123, // All notes off
+    126, // Mono
+    127, // Poly
+    121, // Reset all controllers
+];
+
+// MUS uses channel 15 for percussion where MIDI uses channel 9,
+// so the channels between them get shifted up by one
+pub fn mus_channel_to_midi_channel(mus_channel: u8) -> u8 {
+    if mus_channel == MUS_PERCUSSION_CHANNEL {
+        MIDI_PERCUSSION_CHANNEL
+    } else if mus_channel >= MIDI_PERCUSSION_CHANNEL {
+        mus_channel + 1
+    } else {
+        mus_channel
+    }
+}
+
+pub fn mus_controller_to_midi_controller(mus_controller: u8) -> Option<u8> {
+    match mus_controller {
+        1..=9 => Some(MUS_CONTROLLER_TO_MIDI[usize::from(mus_controller)]),
+        10..=14 => Some(MUS_SYSTEM_EVENT_TO_MIDI[usize::from(mus_controller - 10)]),
+        _ => None,
+    }
+}
+
+// Converts to a type 0 standard MIDI file with a single track
+pub fn mus_to_midi(mus_file: &MusFile) -> Vec<u8> {
+    let mut track: Vec<u8> = Vec::new();
+    let mut channel_volumes: [u8; 16] = [MUS_DEFAULT_VOLUME; 16];
+    let mut delta_time: u32 = 0;
+
+    // Tempo meta event
+    write_variable_length(&mut track, 0);
+    track.extend_from_slice(&[0xFF, 0x51, 0x03]);
+    track.extend_from_slice(&MIDI_TEMPO_MICROSECONDS_PER_QUARTER_NOTE.to_be_bytes()[1..]);
+
+    for event in &mus_file.events {
+        let channel: u8 = mus_channel_to_midi_channel(event.channel);
+
+        let midi_event: Option<Vec<u8>> = match event.kind {
+            MusEventKind::ReleaseNote { note } => Some(vec![MIDI_NOTE_OFF | channel, note, 0]),
+            MusEventKind::PlayNote { note, volume } => {
+                if let Some(volume) = volume {
+                    channel_volumes[usize::from(event.channel)] = volume;
+                }
+
+                Some(vec![
+                    MIDI_NOTE_ON | channel,
+                    note,
+                    channel_volumes[usize::from(event.channel)],
+                ])
+            }
+            MusEventKind::PitchBend { bend } => {
+                let bend: u16 = u16::from(bend) << 6;
+                Some(vec![
+                    MIDI_PITCH_BEND | channel,
+                    (bend & 0x7F) as u8,
+                    (bend >> 7) as u8,
+                ])
+            }
+            MusEventKind::System { controller } => mus_controller_to_midi_controller(controller)
+                .filter(|_| controller >= 10)
+                .map(|midi_controller| vec![MIDI_CONTROLLER | channel, midi_controller, 0]),
+            MusEventKind::Controller { controller, value } => {
+                let value: u8 = value.min(0x7F);
+
+                if controller == 0 {
+                    Some(vec![MIDI_PROGRAM_CHANGE | channel, value])
+                } else {
+                    mus_controller_to_midi_controller(controller)
+                        .filter(|_| controller < 10)
+                        .map(|midi_controller| {
+                            vec![MIDI_CONTROLLER | channel, midi_controller, value]
+                        })
+                }
+            }
+            MusEventKind::MeasureEnd => None,
+            MusEventKind::ScoreEnd => break,
+        };
+
+        if let Some(midi_event) = midi_event {
+            write_variable_length(&mut track, delta_time);
+            track.extend_from_slice(&midi_event);
+            delta_time = 0;
+        }
+
+        delta_time += event.delay;
+    }
+
+    // End of track meta event
+    write_variable_length(&mut track, delta_time);
+    track.extend_from_slice(&[0xFF, 0x2F, 0x00]);
+
+    let mut midi: Vec<u8> = Vec::with_capacity(22 + track.len());
+    midi.extend_from_slice(b"MThd");
+    midi.extend_from_slice(&6u32.to_be_bytes());
+    // Format 0, 1 track
+    midi.extend_from_slice(&0u16.to_be_bytes());
+    midi.extend_from_slice(&1u16.to_be_bytes());
+    midi.extend_from_slice(&MIDI_TICKS_PER_QUARTER_NOTE.to_be_bytes());
+    midi.extend_from_slice(b"MTrk");
+    midi.extend_from_slice(&(track.len() as u32).to_be_bytes());
+    midi.extend_from_slice(&track);
+
+    midi
+}
+
+fn write_variable_length(data: &mut Vec<u8>, value: u32) {
+    let mut bytes: Vec<u8> = vec![(value & 0x7F) as u8];
+    let mut value: u32 = value >> 7;
+
+    while value > 0 {
+        bytes.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+
+    data.extend(bytes.iter().rev());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::music::midi::{
+        mus_channel_to_midi_channel, mus_controller_to_midi_controller, mus_to_midi,
+        write_variable_length,
+    };
+    use crate::music::mus::{MusEvent, MusEventKind, MusFile};
+
+    fn create_mus_file(events: Vec<MusEvent>) -> MusFile {
+        MusFile {
+            primary_channels: 1,
+            secondary_channels: 0,
+            instruments: Vec::new(),
+            events,
+        }
+    }
+
+    // Returns the track data after the tempo event
+    fn track_events(midi: &[u8]) -> &[u8] {
+        &midi[22 + 7..]
+    }
+
+    #[test]
+    fn test_write_variable_length_encodes_values() {
+        let mut data: Vec<u8> = Vec::new();
+        write_variable_length(&mut data, 0);
+        write_variable_length(&mut data, 0x7F);
+        write_variable_length(&mut data, 0x80);
+        write_variable_length(&mut data, 0x0FFFFFFF);
+
+        assert_eq!(data, vec![0x00, 0x7F, 0x81, 0x00, 0xFF, 0xFF, 0xFF, 0x7F]);
+    }
+
+    #[test]
+    fn test_mus_channel_to_midi_channel_swaps_percussion_channel() {
+        assert_eq!(mus_channel_to_midi_channel(0), 0);
+        assert_eq!(mus_channel_to_midi_channel(8), 8);
+        assert_eq!(mus_channel_to_midi_channel(9), 10);
+        assert_eq!(mus_channel_to_midi_channel(14), 15);
+        assert_eq!(mus_channel_to_midi_channel(15), 9);
+    }
+
+    #[test]
+    fn test_mus_to_midi_writes_header_and_events() {
+        let mus_file: MusFile = create_mus_file(vec![
+            MusEvent {
+                channel: 0,
+                kind: MusEventKind::PlayNote {
+                    note: 60,
+                    volume: Some(100),
+                },
+                delay: 140,
+            },
+            MusEvent {
+                channel: 0,
+                kind: MusEventKind::ReleaseNote { note: 60 },
+                delay: 0,
+            },
+            MusEvent {
+                channel: 0,
+                kind: MusEventKind::ScoreEnd,
+                delay: 0,
+            },
+        ]);
+
+        let midi: Vec<u8> = mus_to_midi(&mus_file);
+
+        assert_eq!(&midi[0..4], b"MThd");
+        assert_eq!(&midi[12..14], &[0, 70]);
+        assert_eq!(&midi[14..18], b"MTrk");
+        assert_eq!(
+            track_events(&midi),
+            &[0x00, 0x90, 60, 100, 0x81, 0x0C, 0x80, 60, 0, 0x00, 0xFF, 0x2F, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_mus_to_midi_reuses_last_channel_volume() {
+        let mus_file: MusFile = create_mus_file(vec![
+            MusEvent {
+                channel: 15,
+                kind: MusEventKind::PlayNote {
+                    note: 35,
+                    volume: Some(90),
+                },
+                delay: 0,
+            },
+            MusEvent {
+                channel: 15,
+                kind: MusEventKind::PlayNote {
+                    note: 36,
+                    volume: None,
+                },
+                delay: 0,
+            },
+        ]);
+
+        let midi: Vec<u8> = mus_to_midi(&mus_file);
+
+        assert_eq!(
+            &track_events(&midi)[0..8],
+            &[0x00, 0x99, 35, 90, 0x00, 0x99, 36, 90]
+        );
+    }
+
+    #[test]
+    fn test_mus_controller_to_midi_controller_maps_controllers() {
+        assert_eq!(mus_controller_to_midi_controller(0), None);
+        assert_eq!(mus_controller_to_midi_controller(3), Some(7));
+        assert_eq!(mus_controller_to_midi_controller(4), Some(10));
+        assert_eq!(mus_controller_to_midi_controller(9), Some(67));
+        assert_eq!(mus_controller_to_midi_controller(11), Some(123));
+        assert_eq!(mus_controller_to_midi_controller(14), Some(121));
+        assert_eq!(mus_controller_to_midi_controller(15), None);
+    }
+
+    #[test]
+    fn test_mus_to_midi_translates_controllers() {
+        let mus_file: MusFile = create_mus_file(vec![
+            MusEvent {
+                channel: 1,
+                kind: MusEventKind::Controller {
+                    controller: 0,
+                    value: 30,
+                },
+                delay: 0,
+            },
+            // Volume past the MIDI range gets clamped
+            MusEvent {
+                channel: 9,
+                kind: MusEventKind::Controller {
+                    controller: 3,
+                    value: 200,
+                },
+                delay: 0,
+            },
+            MusEvent {
+                channel: 15,
+                kind: MusEventKind::System { controller: 11 },
+                delay: 0,
+            },
+            MusEvent {
+                channel: 2,
+                kind: MusEventKind::PitchBend { bend: 128 },
+                delay: 0,
+            },
+            // Unknown controllers are dropped
+            MusEvent {
+                channel: 0,
+                kind: MusEventKind::Controller {
+                    controller: 20,
+                    value: 1,
+                },
+                delay: 0,
+            },
+        ]);
+
+        let midi: Vec<u8> = mus_to_midi(&mus_file);
+
+        assert_eq!(
+            track_events(&midi),
+            &[
+                0x00, 0xC1, 30, 0x00, 0xBA, 7, 127, 0x00, 0xB9, 123, 0, 0x00, 0xE2, 0x00, 0x40,
+                0x00, 0xFF, 0x2F, 0x00
+            ]
+        );
+    }
+}
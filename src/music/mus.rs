@@ -0,0 +1,306 @@
+use crate::music::MusicError;
+
+pub const MUS_ID: [u8; 4] = [b'M', b'U', b'S', 0x1A];
+pub const MUS_PERCUSSION_CHANNEL: u8 = 15;
+const MUS_HEADER_SIZE: usize = 16;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MusEventKind {
+    ReleaseNote { note: u8 },
+    // Volume is only present when it changes, otherwise the channels
+    // last volume is used
+    PlayNote { note: u8, volume: Option<u8> },
+    // 0-255 with 128 being no bend
+    PitchBend { bend: u8 },
+    // Controllers 10-14 which carry no value
+    System { controller: u8 },
+    // Controller 0 is a program(instrument) change
+    Controller { controller: u8, value: u8 },
+    MeasureEnd,
+    ScoreEnd,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MusEvent {
+    pub channel: u8,
+    pub kind: MusEventKind,
+    // Number of 140Hz ticks to wait after this event
+    pub delay: u32,
+}
+
+// Music lumps(D_*). MUS is a stripped down MIDI format created for DMX
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MusFile {
+    pub primary_channels: u16,
+    pub secondary_channels: u16,
+    pub instruments: Vec<u16>,
+    pub events: Vec<MusEvent>,
+}
+
+impl MusFile {
+    pub fn is_mus(data: &[u8]) -> bool {
+        data.starts_with(&MUS_ID)
+    }
+
+    pub fn from_lump(data: &[u8]) -> Result<Self, MusicError> {
+        if !MusFile::is_mus(data) {
+            return Err(MusicError::InvalidMus(String::from("Missing MUS id")));
+        }
+
+        if data.len() < MUS_HEADER_SIZE {
+            return Err(MusicError::InvalidMus(String::from(
+                "Lump is smaller than the MUS header",
+            )));
+        }
+
+        let read_u16 =
+            |offset: usize| -> u16 { u16::from_le_bytes([data[offset], data[offset + 1]]) };
+
+        let score_length: usize = usize::from(read_u16(4));
+        let score_start: usize = usize::from(read_u16(6));
+        let primary_channels: u16 = read_u16(8);
+        let secondary_channels: u16 = read_u16(10);
+        let instrument_count: usize = usize::from(read_u16(12));
+
+        let instruments_end: usize = MUS_HEADER_SIZE + instrument_count * 2;
+        if data.len() < instruments_end {
+            return Err(MusicError::InvalidMus(String::from(
+                "Lump is smaller than the instrument list",
+            )));
+        }
+
+        let instruments: Vec<u16> = (0..instrument_count)
+            .map(|index| read_u16(MUS_HEADER_SIZE + index * 2))
+            .collect();
+
+        if score_start > data.len() {
+            return Err(MusicError::InvalidMus(format!(
+                "Score start {} is past the end of the lump",
+                score_start
+            )));
+        }
+
+        // Some PWAD music has an incorrect score length, so
+        // we just read until the end of the lump in that case
+        let score_end: usize = (score_start + score_length).min(data.len());
+        let events: Vec<MusEvent> = parse_events(&data[score_start..score_end])?;
+
+        Ok(MusFile {
+            primary_channels,
+            secondary_channels,
+            instruments,
+            events,
+        })
+    }
+
+    // Length of the song in 140Hz ticks
+    pub fn duration(&self) -> u64 {
+        self.events.iter().map(|event| u64::from(event.delay)).sum()
+    }
+}
+
+fn parse_events(score: &[u8]) -> Result<Vec<MusEvent>, MusicError> {
+    let mut events: Vec<MusEvent> = Vec::new();
+    let mut position: usize = 0;
+
+    let next_byte = |position: &mut usize| -> Result<u8, MusicError> {
+        let byte: u8 = *score.get(*position).ok_or_else(|| {
+            MusicError::InvalidMus(String::from("Score ended in the middle of an event"))
+        })?;
+        *position += 1;
+        Ok(byte)
+    };
+
+    while position < score.len() {
+        let descriptor: u8 = next_byte(&mut position)?;
+        let has_delay: bool = descriptor & 0x80 != 0;
+        let event_type: u8 = (descriptor >> 4) & 0x07;
+        let channel: u8 = descriptor & 0x0F;
+
+        let kind: MusEventKind = match event_type {
+            0 => MusEventKind::ReleaseNote {
+                note: next_byte(&mut position)? & 0x7F,
+            },
+            1 => {
+                let note: u8 = next_byte(&mut position)?;
+                let volume: Option<u8> = if note & 0x80 != 0 {
+                    Some(next_byte(&mut position)? & 0x7F)
+                } else {
+                    None
+                };
+
+                MusEventKind::PlayNote {
+                    note: note & 0x7F,
+                    volume,
+                }
+            }
+            2 => MusEventKind::PitchBend {
+                bend: next_byte(&mut position)?,
+            },
+            3 => MusEventKind::System {
+                controller: next_byte(&mut position)? & 0x7F,
+            },
+            4 => MusEventKind::Controller {
+                controller: next_byte(&mut position)? & 0x7F,
+                value: next_byte(&mut position)?,
+            },
+            5 => MusEventKind::MeasureEnd,
+            6 => MusEventKind::ScoreEnd,
+            _ => {
+                return Err(MusicError::InvalidMus(format!(
+                    "Unknown event type {}",
+                    event_type
+                )))
+            }
+        };
+
+        // Delays are stored 7 bits at a time with the high
+        // bit set on every byte except the last one
+        let mut delay: u32 = 0;
+        if has_delay {
+            loop {
+                let byte: u8 = next_byte(&mut position)?;
+                delay = (delay << 7) | u32::from(byte & 0x7F);
+
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+        }
+
+        let is_score_end: bool = kind == MusEventKind::ScoreEnd;
+
+        events.push(MusEvent {
+            channel,
+            kind,
+            delay,
+        });
+
+        if is_score_end {
+            break;
+        }
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::music::mus::{MusEvent, MusEventKind, MusFile};
+    use crate::music::MusicError;
+
+    fn create_mus_lump(score: &[u8], instruments: &[u16]) -> Vec<u8> {
+        let score_start: u16 = (16 + instruments.len() * 2) as u16;
+
+        let mut lump: Vec<u8> = vec![b'M', b'U', b'S', 0x1A];
+        lump.extend_from_slice(&(score.len() as u16).to_le_bytes());
+        lump.extend_from_slice(&score_start.to_le_bytes());
+        lump.extend_from_slice(&1u16.to_le_bytes());
+        lump.extend_from_slice(&0u16.to_le_bytes());
+        lump.extend_from_slice(&(instruments.len() as u16).to_le_bytes());
+        lump.extend_from_slice(&0u16.to_le_bytes());
+
+        for instrument in instruments {
+            lump.extend_from_slice(&instrument.to_le_bytes());
+        }
+
+        lump.extend_from_slice(score);
+        lump
+    }
+
+    #[test]
+    fn test_mus_file_from_lump_parses_header_and_events() {
+        let score: Vec<u8> = vec![
+            // Change channel 0 to instrument 30
+            0x40,
+            0x00,
+            30,
+            // Play note 60 with volume 100 on channel 0, delay 2 bytes(130 ticks)
+            0x90,
+            0x80 | 60,
+            100,
+            0x81,
+            0x02,
+            // Release note 60
+            0x00,
+            60,
+            // Pitch bend and all notes off on channel 1
+            0x21,
+            200,
+            0x31,
+            11,
+            // Measure end followed by score end
+            0x50,
+            0x60,
+        ];
+        let lump: Vec<u8> = create_mus_lump(&score, &[30, 135]);
+
+        let mus_file: MusFile = MusFile::from_lump(&lump).unwrap();
+
+        assert_eq!(mus_file.primary_channels, 1);
+        assert_eq!(mus_file.instruments, vec![30, 135]);
+        assert_eq!(
+            mus_file.events,
+            vec![
+                MusEvent {
+                    channel: 0,
+                    kind: MusEventKind::Controller {
+                        controller: 0,
+                        value: 30
+                    },
+                    delay: 0
+                },
+                MusEvent {
+                    channel: 0,
+                    kind: MusEventKind::PlayNote {
+                        note: 60,
+                        volume: Some(100)
+                    },
+                    delay: 130
+                },
+                MusEvent {
+                    channel: 0,
+                    kind: MusEventKind::ReleaseNote { note: 60 },
+                    delay: 0
+                },
+                MusEvent {
+                    channel: 1,
+                    kind: MusEventKind::PitchBend { bend: 200 },
+                    delay: 0
+                },
+                MusEvent {
+                    channel: 1,
+                    kind: MusEventKind::System { controller: 11 },
+                    delay: 0
+                },
+                MusEvent {
+                    channel: 0,
+                    kind: MusEventKind::MeasureEnd,
+                    delay: 0
+                },
+                MusEvent {
+                    channel: 0,
+                    kind: MusEventKind::ScoreEnd,
+                    delay: 0
+                },
+            ]
+        );
+        assert_eq!(mus_file.duration(), 130);
+    }
+
+    #[test]
+    fn test_mus_file_from_lump_returns_error_for_invalid_data() {
+        assert_eq!(
+            MusFile::from_lump(b"MThd"),
+            Err(MusicError::InvalidMus(String::from("Missing MUS id")))
+        );
+
+        assert!(MusFile::from_lump(&[b'M', b'U', b'S', 0x1A, 0, 0]).is_err());
+
+        // Play note missing its note byte
+        assert!(MusFile::from_lump(&create_mus_lump(&[0x10], &[])).is_err());
+
+        // Event type 7 is unused
+        assert!(MusFile::from_lump(&create_mus_lump(&[0x70, 0x00], &[])).is_err());
+    }
+}
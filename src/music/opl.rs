@@ -0,0 +1,650 @@
+use std::sync::OnceLock;
+
+// The OPL chips run off a 14.318MHz clock divided by 288
+pub const OPL_SAMPLE_RATE: u32 = 49716;
+pub const OPL2_NUM_CHANNELS: usize = 9;
+pub const OPL3_NUM_CHANNELS: usize = 18;
+
+const NUM_OPERATORS_PER_BANK: usize = 18;
+// Register offset of each channels modulator, the carrier is 3 after it
+pub const CHANNEL_OPERATOR_OFFSETS: [u16; OPL2_NUM_CHANNELS] = [0, 1, 2, 8, 9, 10, 16, 17, 18];
+
+const ENVELOPE_SILENT: u16 = 0x1FF;
+const WAVEFORM_SILENT: u16 = 0x1000;
+// Frequency multipliers doubled so 0.5 fits in an integer
+const MULTIPLIERS_X2: [u32; 16] = [1, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 20, 24, 24, 30, 30];
+const KEY_SCALE_LEVELS: [i32; 16] = [
+    0, 32, 40, 45, 48, 51, 53, 55, 56, 58, 59, 60, 61, 62, 63, 64,
+];
+// Off, 3dB, 1.5dB and 6dB per octave
+const KEY_SCALE_LEVEL_SHIFTS: [u32; 4] = [8, 1, 2, 0];
+// How much the envelope moves on each of the 8 steps for the bottom 2 bits of the rate
+const ENVELOPE_INCREMENTS: [[u16; 8]; 4] = [
+    [0, 1, 0, 1, 0, 1, 0, 1],
+    [0, 1, 0, 1, 1, 1, 0, 1],
+    [0, 1, 1, 1, 0, 1, 1, 1],
+    [0, 1, 1, 1, 1, 1, 1, 1],
+];
+const TREMOLO_STEPS: u32 = 210;
+const TREMOLO_STEP_SAMPLES: u32 = 64;
+const VIBRATO_STEP_SAMPLES: u32 = 1024;
+
+// The chip stores sine and exponent tables in ROM and works with
+// attenuation in the log domain, we build the same tables once
+struct OplTables {
+    log_sine: [u16; 256],
+    exponent: [u16; 256],
+}
+
+fn tables() -> &'static OplTables {
+    static TABLES: OnceLock<OplTables> = OnceLock::new();
+
+    TABLES.get_or_init(|| {
+        let mut log_sine: [u16; 256] = [0; 256];
+        let mut exponent: [u16; 256] = [0; 256];
+
+        for index in 0..256 {
+            let sine: f64 = ((index as f64 + 0.5) * std::f64::consts::PI / 512.0).sin();
+            log_sine[index] = (-sine.log2() * 256.0).round() as u16;
+            exponent[index] = (2f64.powf((255 - index) as f64 / 256.0) * 1024.0).round() as u16;
+        }
+
+        OplTables { log_sine, exponent }
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EnvelopeState {
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+#[derive(Clone, Debug)]
+struct OplOperator {
+    tremolo: bool,
+    vibrato: bool,
+    sustain: bool,
+    key_scale_rate: bool,
+    multiplier: u8,
+    key_scale_level: u8,
+    total_level: u8,
+    attack_rate: u8,
+    decay_rate: u8,
+    sustain_level: u8,
+    release_rate: u8,
+    waveform: u8,
+    phase: u32,
+    envelope: u16,
+    envelope_state: EnvelopeState,
+    output: i32,
+    previous_output: i32,
+}
+
+impl OplOperator {
+    fn new() -> Self {
+        OplOperator {
+            tremolo: false,
+            vibrato: false,
+            sustain: false,
+            key_scale_rate: false,
+            multiplier: 0,
+            key_scale_level: 0,
+            total_level: 0,
+            attack_rate: 0,
+            decay_rate: 0,
+            sustain_level: 0,
+            release_rate: 0,
+            waveform: 0,
+            phase: 0,
+            envelope: ENVELOPE_SILENT,
+            envelope_state: EnvelopeState::Release,
+            output: 0,
+            previous_output: 0,
+        }
+    }
+
+    fn key_on(&mut self) {
+        self.phase = 0;
+        self.envelope_state = EnvelopeState::Attack;
+    }
+
+    fn key_off(&mut self) {
+        self.envelope_state = EnvelopeState::Release;
+    }
+
+    fn effective_rate(&self, rate: u8, key_scale: u32) -> u32 {
+        if rate == 0 {
+            0
+        } else {
+            (u32::from(rate) * 4 + key_scale).min(63)
+        }
+    }
+
+    fn update_envelope(&mut self, counter: u32, key_scale: u32) {
+        let key_scale: u32 = if self.key_scale_rate {
+            key_scale
+        } else {
+            key_scale >> 2
+        };
+
+        let rate: u8 = match self.envelope_state {
+            EnvelopeState::Attack => self.attack_rate,
+            EnvelopeState::Decay => self.decay_rate,
+            EnvelopeState::Sustain if self.sustain => 0,
+            EnvelopeState::Sustain | EnvelopeState::Release => self.release_rate,
+        };
+        let rate: u32 = self.effective_rate(rate, key_scale);
+
+        let increment: u16 = if rate == 0 {
+            0
+        } else {
+            let shift: u32 = rate >> 2;
+            let steps: &[u16; 8] = &ENVELOPE_INCREMENTS[(rate & 3) as usize];
+
+            if shift < 12 {
+                let period_mask: u32 = (1 << (12 - shift)) - 1;
+                if counter & period_mask == 0 {
+                    steps[((counter >> (12 - shift)) & 7) as usize]
+                } else {
+                    0
+                }
+            } else {
+                steps[(counter & 7) as usize] << (shift - 12)
+            }
+        };
+
+        match self.envelope_state {
+            EnvelopeState::Attack => {
+                if rate >= 60 {
+                    self.envelope = 0;
+                } else if increment > 0 {
+                    // Attack curves exponentially
+                    let step: u32 = ((u32::from(self.envelope) * u32::from(increment)) >> 3) + 1;
+                    self.envelope = self.envelope.saturating_sub(step as u16);
+                }
+
+                if self.envelope == 0 {
+                    self.envelope_state = EnvelopeState::Decay;
+                }
+            }
+            EnvelopeState::Decay => {
+                let sustain_level: u16 = if self.sustain_level == 15 {
+                    31 << 4
+                } else {
+                    u16::from(self.sustain_level) << 4
+                };
+
+                self.envelope = (self.envelope + increment).min(ENVELOPE_SILENT);
+
+                if self.envelope >= sustain_level {
+                    self.envelope_state = EnvelopeState::Sustain;
+                }
+            }
+            EnvelopeState::Sustain | EnvelopeState::Release => {
+                self.envelope = (self.envelope + increment).min(ENVELOPE_SILENT);
+            }
+        }
+    }
+
+    // Attenuation in 0.1875dB steps, 0x1FF is silent
+    fn attenuation(&self, fnum: u16, block: u8, tremolo: u16) -> u16 {
+        let key_scale_level: i32 =
+            (KEY_SCALE_LEVELS[usize::from(fnum >> 6)] << 2) - ((8 - i32::from(block)) << 5);
+        let key_scale_level: u16 = (key_scale_level.max(0) as u16)
+            >> KEY_SCALE_LEVEL_SHIFTS[usize::from(self.key_scale_level)];
+
+        let tremolo: u16 = if self.tremolo { tremolo } else { 0 };
+
+        (self.envelope + (u16::from(self.total_level) << 2) + key_scale_level + tremolo)
+            .min(ENVELOPE_SILENT)
+    }
+
+    // Output is roughly -4084 to 4084
+    fn calculate_output(&mut self, phase_offset: i32, attenuation: u16) -> i32 {
+        let phase: u16 = ((self.phase >> 9) as i32).wrapping_add(phase_offset) as u16 & 0x3FF;
+        let (log_value, negative): (u16, bool) = waveform_lookup(self.waveform, phase);
+
+        let level: u32 = u32::from(log_value) + (u32::from(attenuation) << 3);
+        let shift: u32 = level >> 8;
+        let value: i32 = if shift > 15 {
+            0
+        } else {
+            i32::from((tables().exponent[(level & 0xFF) as usize] << 1) >> shift)
+        };
+
+        self.previous_output = self.output;
+        self.output = if negative { -value } else { value };
+        self.output
+    }
+}
+
+// Returns the log attenuation of the waveform at a 10 bit phase and whether it's negative
+fn waveform_lookup(waveform: u8, phase: u16) -> (u16, bool) {
+    let log_sine: &[u16; 256] = &tables().log_sine;
+    let sine = |phase: u16| -> u16 {
+        if phase & 0x100 != 0 {
+            log_sine[usize::from((phase & 0xFF) ^ 0xFF)]
+        } else {
+            log_sine[usize::from(phase & 0xFF)]
+        }
+    };
+
+    match waveform {
+        // Sine
+        0 => (sine(phase), phase & 0x200 != 0),
+        // Half sine
+        1 if phase & 0x200 != 0 => (WAVEFORM_SILENT, false),
+        1 => (sine(phase), false),
+        // Absolute sine
+        2 => (sine(phase), false),
+        // Quarter sine
+        3 if phase & 0x100 != 0 => (WAVEFORM_SILENT, false),
+        3 => (log_sine[usize::from(phase & 0xFF)], false),
+        // Only on the OPL3 from here on.
+        // Double speed sine that is silent every other half
+        4 | 5 if phase & 0x200 != 0 => (WAVEFORM_SILENT, false),
+        4 => (sine(phase << 1), phase & 0x100 != 0),
+        5 => (sine(phase << 1), false),
+        // Square
+        6 => (0, phase & 0x200 != 0),
+        // Log sawtooth
+        _ => {
+            let negative: bool = phase & 0x200 != 0;
+            let phase: u16 = if negative {
+                (phase & 0x1FF) ^ 0x1FF
+            } else {
+                phase & 0x1FF
+            };
+
+            (phase << 3, negative)
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct OplChannel {
+    fnum: u16,
+    block: u8,
+    key_on: bool,
+    feedback: u8,
+    additive: bool,
+    left: bool,
+    right: bool,
+}
+
+impl OplChannel {
+    fn new() -> Self {
+        OplChannel {
+            fnum: 0,
+            block: 0,
+            key_on: false,
+            feedback: 0,
+            additive: false,
+            left: true,
+            right: true,
+        }
+    }
+}
+
+// Software emulation of the Yamaha YM3812(OPL2) and the two operator
+// mode of the YMF262(OPL3). Registers are written the same way as on
+// the real chip, OPL3 registers in the second bank are 0x100 and up.
+// Everything is integer maths so the output is the same on every machine
+pub struct OplChip {
+    opl3: bool,
+    operators: Vec<OplOperator>,
+    channels: Vec<OplChannel>,
+    waveform_select: bool,
+    deep_tremolo: bool,
+    deep_vibrato: bool,
+    counter: u32,
+    tremolo_position: u32,
+    vibrato_position: u32,
+}
+
+impl OplChip {
+    pub fn new(opl3: bool) -> Self {
+        let num_channels: usize = if opl3 {
+            OPL3_NUM_CHANNELS
+        } else {
+            OPL2_NUM_CHANNELS
+        };
+
+        OplChip {
+            opl3,
+            operators: vec![OplOperator::new(); num_channels * 2],
+            channels: vec![OplChannel::new(); num_channels],
+            // The OPL3 always allows waveform selection
+            waveform_select: opl3,
+            deep_tremolo: false,
+            deep_vibrato: false,
+            counter: 0,
+            tremolo_position: 0,
+            vibrato_position: 0,
+        }
+    }
+
+    pub fn is_opl3(&self) -> bool {
+        self.opl3
+    }
+
+    pub fn num_channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    // Register offsets 0-5, 8-13 and 16-21 are operators, the rest are unused
+    fn operator_index(&self, bank: usize, offset: u16) -> Option<usize> {
+        if offset >= 22 || offset % 8 >= 6 {
+            return None;
+        }
+
+        let index: usize = bank * NUM_OPERATORS_PER_BANK + usize::from(offset / 8 * 6 + offset % 8);
+
+        (index < self.operators.len()).then_some(index)
+    }
+
+    fn channel_operators(channel_index: usize) -> (usize, usize) {
+        let bank: usize = channel_index / OPL2_NUM_CHANNELS;
+        let offset: u16 = CHANNEL_OPERATOR_OFFSETS[channel_index % OPL2_NUM_CHANNELS];
+        let modulator: usize =
+            bank * NUM_OPERATORS_PER_BANK + usize::from(offset / 8 * 6 + offset % 8);
+
+        (modulator, modulator + 3)
+    }
+
+    pub fn write_register(&mut self, register: u16, value: u8) {
+        let bank: usize = usize::from(register >> 8);
+        let register: u16 = register & 0xFF;
+
+        if bank > 1 || (bank == 1 && !self.opl3) {
+            return;
+        }
+
+        match register {
+            0x01 if bank == 0 && !self.opl3 => self.waveform_select = value & 0x20 != 0,
+            0xBD if bank == 0 => {
+                self.deep_tremolo = value & 0x80 != 0;
+                self.deep_vibrato = value & 0x40 != 0;
+            }
+            0x20..=0x35 | 0x40..=0x55 | 0x60..=0x75 | 0x80..=0x95 | 0xE0..=0xF5 => {
+                let Some(operator_index) = self.operator_index(bank, register & 0x1F) else {
+                    return;
+                };
+                let waveform_mask: u8 = if self.opl3 { 0x07 } else { 0x03 };
+                let waveform_select: bool = self.waveform_select;
+                let operator: &mut OplOperator = &mut self.operators[operator_index];
+
+                match register & 0xE0 {
+                    0x20 => {
+                        operator.tremolo = value & 0x80 != 0;
+                        operator.vibrato = value & 0x40 != 0;
+                        operator.sustain = value & 0x20 != 0;
+                        operator.key_scale_rate = value & 0x10 != 0;
+                        operator.multiplier = value & 0x0F;
+                    }
+                    0x40 => {
+                        operator.key_scale_level = value >> 6;
+                        operator.total_level = value & 0x3F;
+                    }
+                    0x60 => {
+                        operator.attack_rate = value >> 4;
+                        operator.decay_rate = value & 0x0F;
+                    }
+                    0x80 => {
+                        operator.sustain_level = value >> 4;
+                        operator.release_rate = value & 0x0F;
+                    }
+                    _ if waveform_select => operator.waveform = value & waveform_mask,
+                    _ => (),
+                }
+            }
+            0xA0..=0xA8 | 0xB0..=0xB8 | 0xC0..=0xC8 => {
+                let channel_index: usize = bank * OPL2_NUM_CHANNELS + usize::from(register & 0x0F);
+                let opl3: bool = self.opl3;
+                let channel: &mut OplChannel = &mut self.channels[channel_index];
+
+                match register & 0xF0 {
+                    0xA0 => channel.fnum = (channel.fnum & 0x300) | u16::from(value),
+                    0xB0 => {
+                        channel.fnum = (channel.fnum & 0xFF) | (u16::from(value & 0x03) << 8);
+                        channel.block = (value >> 2) & 0x07;
+
+                        let key_on: bool = value & 0x20 != 0;
+                        let was_key_on: bool = channel.key_on;
+                        channel.key_on = key_on;
+
+                        let (modulator, carrier): (usize, usize) =
+                            OplChip::channel_operators(channel_index);
+
+                        if key_on && !was_key_on {
+                            self.operators[modulator].key_on();
+                            self.operators[carrier].key_on();
+                        } else if !key_on && was_key_on {
+                            self.operators[modulator].key_off();
+                            self.operators[carrier].key_off();
+                        }
+                    }
+                    _ => {
+                        channel.feedback = (value >> 1) & 0x07;
+                        channel.additive = value & 0x01 != 0;
+
+                        // The OPL2 has one output so panning doesn't apply
+                        if opl3 {
+                            channel.left = value & 0x10 != 0;
+                            channel.right = value & 0x20 != 0;
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // Generates one sample at OPL_SAMPLE_RATE, returned as left and right
+    pub fn generate_sample(&mut self) -> (i32, i32) {
+        let tremolo_step: u32 = if self.tremolo_position < TREMOLO_STEPS / 2 {
+            self.tremolo_position
+        } else {
+            TREMOLO_STEPS - self.tremolo_position
+        };
+        let tremolo: u16 = (tremolo_step >> if self.deep_tremolo { 2 } else { 4 }) as u16;
+
+        let mut left: i32 = 0;
+        let mut right: i32 = 0;
+
+        for channel_index in 0..self.channels.len() {
+            let channel: OplChannel = self.channels[channel_index].clone();
+            let (modulator_index, carrier_index): (usize, usize) =
+                OplChip::channel_operators(channel_index);
+
+            let key_scale: u32 =
+                (u32::from(channel.block) << 1) | u32::from((channel.fnum >> 9) & 1);
+
+            let feedback: i32 = {
+                let modulator: &OplOperator = &self.operators[modulator_index];
+                if channel.feedback == 0 {
+                    0
+                } else {
+                    (modulator.output + modulator.previous_output) >> (9 - channel.feedback)
+                }
+            };
+
+            let modulator_attenuation: u16 =
+                self.operators[modulator_index].attenuation(channel.fnum, channel.block, tremolo);
+            let modulator_output: i32 =
+                self.operators[modulator_index].calculate_output(feedback, modulator_attenuation);
+
+            let carrier_attenuation: u16 =
+                self.operators[carrier_index].attenuation(channel.fnum, channel.block, tremolo);
+            let carrier_input: i32 = if channel.additive {
+                0
+            } else {
+                modulator_output
+            };
+            let carrier_output: i32 =
+                self.operators[carrier_index].calculate_output(carrier_input, carrier_attenuation);
+
+            let output: i32 = if channel.additive {
+                modulator_output + carrier_output
+            } else {
+                carrier_output
+            };
+
+            if channel.left {
+                left += output;
+            }
+            if channel.right {
+                right += output;
+            }
+
+            for operator_index in [modulator_index, carrier_index] {
+                let fnum: u16 =
+                    self.vibrato_fnum(channel.fnum, self.operators[operator_index].vibrato);
+                let operator: &mut OplOperator = &mut self.operators[operator_index];
+
+                let increment: u32 = (((u32::from(fnum) << channel.block) >> 1)
+                    * MULTIPLIERS_X2[usize::from(operator.multiplier)])
+                    >> 1;
+                operator.phase = operator.phase.wrapping_add(increment);
+                operator.update_envelope(self.counter, key_scale);
+            }
+        }
+
+        self.counter = self.counter.wrapping_add(1);
+        if self.counter.is_multiple_of(TREMOLO_STEP_SAMPLES) {
+            self.tremolo_position = (self.tremolo_position + 1) % TREMOLO_STEPS;
+        }
+        if self.counter.is_multiple_of(VIBRATO_STEP_SAMPLES) {
+            self.vibrato_position = (self.vibrato_position + 1) & 7;
+        }
+
+        (left, right)
+    }
+
+    // Vibrato moves the frequency up and down by a fraction of fnum over 8 steps
+    fn vibrato_fnum(&self, fnum: u16, vibrato: bool) -> u16 {
+        if !vibrato || self.vibrato_position & 3 == 0 {
+            return fnum;
+        }
+
+        let mut range: u16 = (fnum >> 7) & 7;
+        if self.vibrato_position & 1 != 0 {
+            range >>= 1;
+        }
+        if !self.deep_vibrato {
+            range >>= 1;
+        }
+
+        if self.vibrato_position & 4 != 0 {
+            fnum.wrapping_sub(range) & 0x3FF
+        } else {
+            (fnum + range) & 0x3FF
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::music::opl::{waveform_lookup, OplChip, OPL_SAMPLE_RATE, WAVEFORM_SILENT};
+
+    // A plain sine wave on channel 0 with an instant attack
+    fn key_on_sine(chip: &mut OplChip, register_base: u16, channel: u16, fnum: u16, block: u8) {
+        let (modulator, carrier): (u16, u16) = (0x00, 0x03);
+
+        chip.write_register(register_base + 0x20 + modulator, 0x01);
+        chip.write_register(register_base + 0x40 + modulator, 0x3F);
+        chip.write_register(register_base + 0x60 + modulator, 0xF0);
+        chip.write_register(register_base + 0x80 + modulator, 0x0F);
+        chip.write_register(register_base + 0x20 + carrier, 0x21);
+        chip.write_register(register_base + 0x40 + carrier, 0x00);
+        chip.write_register(register_base + 0x60 + carrier, 0xF0);
+        chip.write_register(register_base + 0x80 + carrier, 0x0F);
+        chip.write_register(register_base + 0xC0 + channel, 0x30);
+        chip.write_register(register_base + 0xA0 + channel, (fnum & 0xFF) as u8);
+        chip.write_register(
+            register_base + 0xB0 + channel,
+            0x20 | (block << 2) | (fnum >> 8) as u8,
+        );
+    }
+
+    fn count_rising_zero_crossings(samples: &[i32]) -> usize {
+        samples
+            .windows(2)
+            .filter(|pair| pair[0] <= 0 && pair[1] > 0)
+            .count()
+    }
+
+    #[test]
+    fn test_waveform_lookup_matches_rom_values() {
+        // First and last entries of the OPL log sine ROM
+        assert_eq!(waveform_lookup(0, 0), (0x859, false));
+        assert_eq!(waveform_lookup(0, 0xFF), (0, false));
+        assert_eq!(waveform_lookup(0, 0x100), (0, false));
+        assert_eq!(waveform_lookup(0, 0x200), (0x859, true));
+        assert_eq!(waveform_lookup(1, 0x200), (WAVEFORM_SILENT, false));
+        assert_eq!(waveform_lookup(2, 0x200), (0x859, false));
+        assert_eq!(waveform_lookup(3, 0x100), (WAVEFORM_SILENT, false));
+    }
+
+    #[test]
+    fn test_opl_chip_is_silent_without_notes() {
+        let mut chip: OplChip = OplChip::new(false);
+
+        for _ in 0..1000 {
+            assert_eq!(chip.generate_sample(), (0, 0));
+        }
+    }
+
+    #[test]
+    fn test_opl_chip_plays_note_at_frequency() {
+        let mut chip: OplChip = OplChip::new(false);
+        // 440Hz is fnum 580 in block 4
+        key_on_sine(&mut chip, 0, 0, 580, 4);
+
+        let samples: Vec<i32> = (0..OPL_SAMPLE_RATE)
+            .map(|_| chip.generate_sample().0)
+            .collect();
+
+        let crossings: usize = count_rising_zero_crossings(&samples);
+        assert!((439..=441).contains(&crossings), "{} crossings", crossings);
+
+        let peak: i32 = samples.iter().map(|sample| sample.abs()).max().unwrap();
+        assert!(peak > 4000 && peak <= 4084, "peak {}", peak);
+    }
+
+    #[test]
+    fn test_opl_chip_releases_note_on_key_off() {
+        let mut chip: OplChip = OplChip::new(false);
+        key_on_sine(&mut chip, 0, 0, 580, 4);
+        for _ in 0..1000 {
+            chip.generate_sample();
+        }
+
+        chip.write_register(0xB0, 0x10 | 0x02);
+
+        // Release rate 15 goes silent almost straight away
+        for _ in 0..1000 {
+            chip.generate_sample();
+        }
+        assert_eq!(chip.generate_sample(), (0, 0));
+    }
+
+    #[test]
+    fn test_opl_chip_opl3_second_bank_and_panning() {
+        let mut opl2: OplChip = OplChip::new(false);
+        key_on_sine(&mut opl2, 0x100, 0, 580, 4);
+        assert_eq!(opl2.num_channels(), 9);
+        assert!((0..1000).all(|_| opl2.generate_sample() == (0, 0)));
+
+        let mut opl3: OplChip = OplChip::new(true);
+        key_on_sine(&mut opl3, 0x100, 0, 580, 4);
+        // Left only
+        opl3.write_register(0x1C0, 0x10);
+
+        let samples: Vec<(i32, i32)> = (0..1000).map(|_| opl3.generate_sample()).collect();
+        assert!(samples.iter().any(|(left, _)| *left != 0));
+        assert!(samples.iter().all(|(_, right)| *right == 0));
+    }
+}
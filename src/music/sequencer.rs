@@ -0,0 +1,212 @@
+use crate::music::mus::{MusEvent, MusEventKind, MusFile};
+
+// MUS delays are in 140Hz ticks, twice the game tic rate
+pub const MUS_TICK_RATE: u32 = 140;
+
+// Steps through a MUS score one tick at a time, handing back the events
+// that happen on each tick so a synth(or anything else) can play them
+#[derive(Clone, Debug)]
+pub struct MusSequencer {
+    pub mus_file: MusFile,
+    pub looping: bool,
+    event_index: usize,
+    ticks_until_next_event: u32,
+    current_tick: u64,
+    finished: bool,
+}
+
+impl MusSequencer {
+    pub fn new(mus_file: MusFile, looping: bool) -> Self {
+        MusSequencer {
+            mus_file,
+            looping,
+            event_index: 0,
+            ticks_until_next_event: 0,
+            current_tick: 0,
+            finished: false,
+        }
+    }
+
+    pub fn restart(&mut self) {
+        self.event_index = 0;
+        self.ticks_until_next_event = 0;
+        self.current_tick = 0;
+        self.finished = false;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    // Ticks since the start of the song, this keeps counting when looping
+    pub fn current_tick(&self) -> u64 {
+        self.current_tick
+    }
+
+    // Returns the events for the current tick and moves on to the next one.
+    // ScoreEnd is handed back too so players know when a song loops/ends
+    pub fn tick(&mut self) -> Vec<MusEvent> {
+        let mut events: Vec<MusEvent> = Vec::new();
+
+        if self.finished {
+            return events;
+        }
+
+        while self.ticks_until_next_event == 0 {
+            // Scores without a ScoreEnd event end when we run out of events
+            let event: MusEvent = match self.mus_file.events.get(self.event_index) {
+                Some(event) => event.clone(),
+                None => MusEvent {
+                    channel: 0,
+                    kind: MusEventKind::ScoreEnd,
+                    delay: 0,
+                },
+            };
+
+            self.event_index += 1;
+            self.ticks_until_next_event = event.delay;
+
+            if event.kind == MusEventKind::ScoreEnd {
+                events.push(event);
+
+                // A song with no delays would loop forever within one tick
+                if self.looping && self.mus_file.duration() > 0 {
+                    self.event_index = 0;
+                    self.ticks_until_next_event = 0;
+                } else {
+                    self.finished = true;
+                }
+
+                break;
+            }
+
+            events.push(event);
+        }
+
+        self.ticks_until_next_event = self.ticks_until_next_event.saturating_sub(1);
+        self.current_tick += 1;
+
+        events
+    }
+
+    // Runs the sequencer for the number of ticks that fit in the given
+    // time, for backends that render audio in blocks
+    pub fn advance_seconds(&mut self, seconds: f64) -> Vec<MusEvent> {
+        let ticks: u64 = (seconds * f64::from(MUS_TICK_RATE)) as u64;
+
+        (0..ticks).flat_map(|_| self.tick()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::music::mus::{MusEvent, MusEventKind, MusFile};
+    use crate::music::sequencer::MusSequencer;
+
+    fn create_event(kind: MusEventKind, delay: u32) -> MusEvent {
+        MusEvent {
+            channel: 0,
+            kind,
+            delay,
+        }
+    }
+
+    fn create_mus_file() -> MusFile {
+        MusFile {
+            primary_channels: 1,
+            secondary_channels: 0,
+            instruments: Vec::new(),
+            events: vec![
+                create_event(
+                    MusEventKind::PlayNote {
+                        note: 60,
+                        volume: Some(100),
+                    },
+                    0,
+                ),
+                create_event(
+                    MusEventKind::PlayNote {
+                        note: 64,
+                        volume: None,
+                    },
+                    2,
+                ),
+                create_event(MusEventKind::ReleaseNote { note: 60 }, 1),
+                create_event(MusEventKind::ScoreEnd, 0),
+            ],
+        }
+    }
+
+    fn event_kinds(events: Vec<MusEvent>) -> Vec<MusEventKind> {
+        events.into_iter().map(|event| event.kind).collect()
+    }
+
+    #[test]
+    fn test_mus_sequencer_tick_returns_events_at_their_time() {
+        let mut sequencer: MusSequencer = MusSequencer::new(create_mus_file(), false);
+
+        assert_eq!(
+            event_kinds(sequencer.tick()),
+            vec![
+                MusEventKind::PlayNote {
+                    note: 60,
+                    volume: Some(100)
+                },
+                MusEventKind::PlayNote {
+                    note: 64,
+                    volume: None
+                },
+            ]
+        );
+        assert_eq!(sequencer.tick(), Vec::new());
+        assert_eq!(
+            event_kinds(sequencer.tick()),
+            vec![MusEventKind::ReleaseNote { note: 60 }]
+        );
+        assert!(!sequencer.is_finished());
+        assert_eq!(event_kinds(sequencer.tick()), vec![MusEventKind::ScoreEnd]);
+        assert!(sequencer.is_finished());
+        assert_eq!(sequencer.tick(), Vec::new());
+        assert_eq!(sequencer.current_tick(), 4);
+    }
+
+    #[test]
+    fn test_mus_sequencer_tick_loops_songs() {
+        let mut sequencer: MusSequencer = MusSequencer::new(create_mus_file(), true);
+
+        let events: Vec<MusEvent> = sequencer.advance_seconds(4.0 / 140.0);
+        assert_eq!(events.len(), 4);
+        assert!(!sequencer.is_finished());
+
+        // Song starts again straight after the score end
+        assert_eq!(
+            event_kinds(sequencer.tick()),
+            vec![
+                MusEventKind::PlayNote {
+                    note: 60,
+                    volume: Some(100)
+                },
+                MusEventKind::PlayNote {
+                    note: 64,
+                    volume: None
+                },
+            ]
+        );
+
+        sequencer.restart();
+        assert_eq!(sequencer.current_tick(), 0);
+    }
+
+    #[test]
+    fn test_mus_sequencer_tick_finishes_scores_without_delays() {
+        let mut mus_file: MusFile = create_mus_file();
+        mus_file.events = vec![create_event(MusEventKind::MeasureEnd, 0)];
+        let mut sequencer: MusSequencer = MusSequencer::new(mus_file, true);
+
+        assert_eq!(
+            event_kinds(sequencer.tick()),
+            vec![MusEventKind::MeasureEnd, MusEventKind::ScoreEnd]
+        );
+        assert!(sequencer.is_finished());
+    }
+}
@@ -0,0 +1,796 @@
+use crate::music::genmidi::{GenMidi, GenMidiInstrument, GenMidiOperator, GenMidiVoice};
+use crate::music::mus::{MusEvent, MusEventKind, MusFile, MUS_PERCUSSION_CHANNEL};
+use crate::music::opl::{OplChip, CHANNEL_OPERATOR_OFFSETS, OPL2_NUM_CHANNELS, OPL_SAMPLE_RATE};
+use crate::music::sequencer::{MusSequencer, MUS_TICK_RATE};
+use crate::sound::wav::Wav;
+
+pub const MAX_MUSIC_VOLUME: u8 = 127;
+const MUS_NUM_CHANNELS: usize = 16;
+const DEFAULT_CHANNEL_VOLUME: u8 = 100;
+const DEFAULT_NOTE_VOLUME: u8 = 127;
+const DEFAULT_PAN: u8 = 64;
+// Pitch bends and second voice detuning are in 1/32nds of a semitone
+const FREQUENCY_STEPS_PER_SEMITONE: i32 = 32;
+// GENMIDI notes are kept to the range DMX could play
+const HIGHEST_NOTE: i32 = 95;
+
+#[derive(Clone, Copy, Debug)]
+struct SynthChannel {
+    instrument: u8,
+    volume: u8,
+    note_volume: u8,
+    pan: u8,
+    bend: i32,
+}
+
+impl SynthChannel {
+    fn new() -> Self {
+        SynthChannel {
+            instrument: 0,
+            volume: DEFAULT_CHANNEL_VOLUME,
+            note_volume: DEFAULT_NOTE_VOLUME,
+            pan: DEFAULT_PAN,
+            bend: 0,
+        }
+    }
+}
+
+// A note playing on one of the OPL channels
+#[derive(Clone, Debug)]
+struct SynthVoice {
+    mus_channel: u8,
+    // The note from the score, percussion and fixed instruments play something else
+    key: u8,
+    note: u8,
+    note_volume: u8,
+    instrument: GenMidiInstrument,
+    // Which of the instruments two voices this is
+    instrument_voice: usize,
+    age: u64,
+}
+
+// Plays MUS scores through the GENMIDI instruments on an emulated OPL chip,
+// the way the DMX library drove an Adlib/Sound Blaster. Rendering is fully
+// deterministic so it can be snapshot tested
+pub struct OplSynth {
+    pub sample_rate: u32,
+    chip: OplChip,
+    genmidi: GenMidi,
+    sequencer: Option<MusSequencer>,
+    channels: [SynthChannel; MUS_NUM_CHANNELS],
+    voices: Vec<Option<SynthVoice>>,
+    music_volume: u8,
+    voice_counter: u64,
+    frames_rendered: u64,
+    ticks_played: u64,
+    opl_samples_generated: u64,
+    previous_opl_sample: (i32, i32),
+    current_opl_sample: (i32, i32),
+}
+
+impl OplSynth {
+    pub fn new(genmidi: GenMidi, sample_rate: u32, opl3: bool) -> Self {
+        let chip: OplChip = OplChip::new(opl3);
+        let num_voices: usize = chip.num_channels();
+
+        let mut synth: OplSynth = OplSynth {
+            sample_rate: sample_rate.max(1),
+            chip,
+            genmidi,
+            sequencer: None,
+            channels: [SynthChannel::new(); MUS_NUM_CHANNELS],
+            voices: vec![None; num_voices],
+            music_volume: MAX_MUSIC_VOLUME,
+            voice_counter: 0,
+            frames_rendered: 0,
+            ticks_played: 0,
+            opl_samples_generated: 0,
+            previous_opl_sample: (0, 0),
+            current_opl_sample: (0, 0),
+        };
+        synth.reset_chip();
+
+        synth
+    }
+
+    fn reset_chip(&mut self) {
+        // Enable waveform selection on the OPL2
+        self.chip.write_register(0x01, 0x20);
+        // OPL3 mode
+        self.chip.write_register(0x105, 0x01);
+
+        for voice_index in 0..self.voices.len() {
+            self.write_channel_register(voice_index, 0xB0, 0);
+        }
+    }
+
+    pub fn play(&mut self, mus_file: MusFile, looping: bool) {
+        self.stop();
+        self.sequencer = Some(MusSequencer::new(mus_file, looping));
+    }
+
+    pub fn stop(&mut self) {
+        for voice_index in 0..self.voices.len() {
+            self.release_voice(voice_index);
+        }
+
+        self.sequencer = None;
+        self.channels = [SynthChannel::new(); MUS_NUM_CHANNELS];
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.sequencer
+            .as_ref()
+            .is_some_and(|sequencer| !sequencer.is_finished())
+    }
+
+    // 0 to 127, like snd_MusicVolume * 8
+    pub fn set_music_volume(&mut self, volume: u8) {
+        self.music_volume = volume.min(MAX_MUSIC_VOLUME);
+
+        for voice_index in 0..self.voices.len() {
+            self.update_voice_volume(voice_index);
+        }
+    }
+
+    // Renders interleaved 16 bit stereo samples at sample_rate
+    pub fn render(&mut self, frame_count: usize) -> Vec<i16> {
+        let mut output: Vec<i16> = Vec::with_capacity(frame_count * 2);
+
+        for _ in 0..frame_count {
+            // Play every tick that should have happened by this frame
+            while self.ticks_played * u64::from(self.sample_rate)
+                <= self.frames_rendered * u64::from(MUS_TICK_RATE)
+            {
+                self.play_tick();
+                self.ticks_played += 1;
+            }
+
+            // Linear interpolation between the two closest OPL samples
+            let position: u64 = ((self.frames_rendered * u64::from(OPL_SAMPLE_RATE)) << 16)
+                / u64::from(self.sample_rate);
+            let sample_index: u64 = position >> 16;
+            let fraction: i64 = (position & 0xFFFF) as i64;
+
+            while self.opl_samples_generated <= sample_index + 1 {
+                self.previous_opl_sample = self.current_opl_sample;
+                self.current_opl_sample = self.chip.generate_sample();
+                self.opl_samples_generated += 1;
+            }
+
+            let interpolate = |previous: i32, current: i32| -> i16 {
+                let sample: i64 =
+                    i64::from(previous) + ((i64::from(current - previous) * fraction) >> 16);
+                sample.clamp(i64::from(i16::MIN), i64::from(i16::MAX)) as i16
+            };
+
+            output.push(interpolate(
+                self.previous_opl_sample.0,
+                self.current_opl_sample.0,
+            ));
+            output.push(interpolate(
+                self.previous_opl_sample.1,
+                self.current_opl_sample.1,
+            ));
+
+            self.frames_rendered += 1;
+        }
+
+        output
+    }
+
+    pub fn render_wav(&mut self, frame_count: usize) -> Wav {
+        Wav {
+            sample_rate: self.sample_rate,
+            channels: 2,
+            bits_per_sample: 16,
+            data: self
+                .render(frame_count)
+                .iter()
+                .flat_map(|sample| sample.to_le_bytes())
+                .collect(),
+        }
+    }
+
+    fn play_tick(&mut self) {
+        let events: Vec<MusEvent> = match self.sequencer.as_mut() {
+            Some(sequencer) => sequencer.tick(),
+            None => return,
+        };
+
+        for event in events {
+            self.handle_event(&event);
+        }
+    }
+
+    fn handle_event(&mut self, event: &MusEvent) {
+        let mus_channel: u8 = event.channel & 0x0F;
+
+        match event.kind {
+            MusEventKind::ReleaseNote { note } => self.note_off(mus_channel, note),
+            MusEventKind::PlayNote { note, volume } => {
+                if let Some(volume) = volume {
+                    self.channels[usize::from(mus_channel)].note_volume = volume.min(127);
+                }
+                self.note_on(mus_channel, note);
+            }
+            MusEventKind::PitchBend { bend } => {
+                // 128 is centered and the full range is 2 semitones either way
+                self.channels[usize::from(mus_channel)].bend = (i32::from(bend) - 128) / 2;
+                self.update_channel_voices(mus_channel, OplSynth::update_voice_frequency);
+            }
+            MusEventKind::System { controller } => match controller {
+                // All sounds off and all notes off
+                10 | 11 => self.release_channel(mus_channel),
+                // Reset all controllers
+                14 => {
+                    let channel: &mut SynthChannel = &mut self.channels[usize::from(mus_channel)];
+                    channel.volume = DEFAULT_CHANNEL_VOLUME;
+                    channel.pan = DEFAULT_PAN;
+                    channel.bend = 0;
+                    self.update_channel_voices(mus_channel, OplSynth::update_voice_frequency);
+                    self.update_channel_voices(mus_channel, OplSynth::update_voice_volume);
+                }
+                _ => (),
+            },
+            MusEventKind::Controller { controller, value } => {
+                let value: u8 = value.min(127);
+
+                match controller {
+                    0 => self.channels[usize::from(mus_channel)].instrument = value,
+                    3 => {
+                        self.channels[usize::from(mus_channel)].volume = value;
+                        self.update_channel_voices(mus_channel, OplSynth::update_voice_volume);
+                    }
+                    4 => {
+                        self.channels[usize::from(mus_channel)].pan = value;
+                        self.update_channel_voices(mus_channel, OplSynth::update_voice_pan);
+                    }
+                    _ => (),
+                }
+            }
+            MusEventKind::MeasureEnd => (),
+            MusEventKind::ScoreEnd => {
+                for voice_index in 0..self.voices.len() {
+                    self.release_voice(voice_index);
+                }
+            }
+        }
+    }
+
+    fn update_channel_voices(&mut self, mus_channel: u8, update: fn(&mut OplSynth, usize)) {
+        for voice_index in 0..self.voices.len() {
+            if self.voices[voice_index]
+                .as_ref()
+                .is_some_and(|voice| voice.mus_channel == mus_channel)
+            {
+                update(self, voice_index);
+            }
+        }
+    }
+
+    fn note_on(&mut self, mus_channel: u8, key: u8) {
+        let instrument: GenMidiInstrument = if mus_channel == MUS_PERCUSSION_CHANNEL {
+            match self.genmidi.percussion_instrument(key) {
+                Some(instrument) => instrument.clone(),
+                None => return,
+            }
+        } else {
+            self.genmidi
+                .melodic_instrument(self.channels[usize::from(mus_channel)].instrument)
+                .clone()
+        };
+
+        let note: u8 = if instrument.is_fixed() {
+            instrument.fixed_note
+        } else {
+            key
+        };
+        let instrument_voices: usize = if instrument.is_double_voice() { 2 } else { 1 };
+
+        for instrument_voice in 0..instrument_voices {
+            let voice_index: usize = self.allocate_voice(mus_channel);
+
+            self.voice_counter += 1;
+            self.voices[voice_index] = Some(SynthVoice {
+                mus_channel,
+                key,
+                note,
+                note_volume: self.channels[usize::from(mus_channel)].note_volume,
+                instrument: instrument.clone(),
+                instrument_voice,
+                age: self.voice_counter,
+            });
+
+            self.load_voice_instrument(voice_index);
+            self.update_voice_volume(voice_index);
+            self.update_voice_pan(voice_index);
+            self.update_voice_frequency(voice_index);
+        }
+    }
+
+    fn note_off(&mut self, mus_channel: u8, key: u8) {
+        for voice_index in 0..self.voices.len() {
+            if self.voices[voice_index]
+                .as_ref()
+                .is_some_and(|voice| voice.mus_channel == mus_channel && voice.key == key)
+            {
+                self.release_voice(voice_index);
+            }
+        }
+    }
+
+    fn release_channel(&mut self, mus_channel: u8) {
+        for voice_index in 0..self.voices.len() {
+            if self.voices[voice_index]
+                .as_ref()
+                .is_some_and(|voice| voice.mus_channel == mus_channel)
+            {
+                self.release_voice(voice_index);
+            }
+        }
+    }
+
+    // Key off but keep the frequency so the release sounds right
+    fn release_voice(&mut self, voice_index: usize) {
+        if self.voices[voice_index].take().is_some() {
+            let frequency: u16 = self.voice_frequency_register(voice_index);
+            self.write_channel_register(voice_index, 0xB0, (frequency >> 8) as u8);
+        }
+    }
+
+    // Uses a free voice if there is one, otherwise the voice on the least
+    // important(highest) channel is cut off, oldest first
+    fn allocate_voice(&mut self, mus_channel: u8) -> usize {
+        if let Some(voice_index) = self.voices.iter().position(Option::is_none) {
+            return voice_index;
+        }
+
+        let voice_index: usize = self
+            .voices
+            .iter()
+            .enumerate()
+            .filter_map(|(voice_index, voice)| voice.as_ref().map(|voice| (voice_index, voice)))
+            .max_by(|(_, a), (_, b)| {
+                a.mus_channel
+                    .max(mus_channel)
+                    .cmp(&b.mus_channel.max(mus_channel))
+                    .then(b.age.cmp(&a.age))
+            })
+            .map(|(voice_index, _)| voice_index)
+            .unwrap_or(0);
+
+        self.release_voice(voice_index);
+        voice_index
+    }
+
+    fn voice_registers(&self, voice_index: usize) -> (u16, u16, u16) {
+        let bank: u16 = (voice_index / OPL2_NUM_CHANNELS) as u16 * 0x100;
+        let channel: usize = voice_index % OPL2_NUM_CHANNELS;
+        let modulator: u16 = CHANNEL_OPERATOR_OFFSETS[channel];
+
+        (
+            bank + channel as u16,
+            bank + modulator,
+            bank + modulator + 3,
+        )
+    }
+
+    fn write_channel_register(&mut self, voice_index: usize, register: u16, value: u8) {
+        let (channel, _, _): (u16, u16, u16) = self.voice_registers(voice_index);
+        self.chip.write_register(register + channel, value);
+    }
+
+    fn instrument_voice(&self, voice_index: usize) -> Option<GenMidiVoice> {
+        self.voices[voice_index]
+            .as_ref()
+            .map(|voice| voice.instrument.voices[voice.instrument_voice])
+    }
+
+    fn load_voice_instrument(&mut self, voice_index: usize) {
+        let Some(instrument_voice) = self.instrument_voice(voice_index) else {
+            return;
+        };
+        let (_, modulator, carrier): (u16, u16, u16) = self.voice_registers(voice_index);
+
+        for (operator_register, operator) in [
+            (modulator, instrument_voice.modulator),
+            (carrier, instrument_voice.carrier),
+        ] {
+            self.chip
+                .write_register(0x20 + operator_register, operator.tremolo);
+            self.chip
+                .write_register(0x60 + operator_register, operator.attack);
+            self.chip
+                .write_register(0x80 + operator_register, operator.sustain);
+            self.chip
+                .write_register(0xE0 + operator_register, operator.waveform);
+            self.chip.write_register(
+                0x40 + operator_register,
+                operator.scale | (operator.level & 0x3F),
+            );
+        }
+    }
+
+    fn update_voice_volume(&mut self, voice_index: usize) {
+        let Some(voice) = self.voices[voice_index].as_ref() else {
+            return;
+        };
+        let instrument_voice: GenMidiVoice = voice.instrument.voices[voice.instrument_voice];
+        let full_volume: u32 = u32::from(voice.note_volume)
+            * u32::from(self.channels[usize::from(voice.mus_channel)].volume)
+            * u32::from(self.music_volume)
+            / (127 * 127);
+        let (_, modulator, carrier): (u16, u16, u16) = self.voice_registers(voice_index);
+
+        let operator_level = |operator: GenMidiOperator| -> u8 {
+            let level: u32 = u32::from(operator.level & 0x3F);
+            let level: u32 = 0x3F - ((0x3F - level) * full_volume / 127);
+            operator.scale | level as u8
+        };
+
+        self.chip
+            .write_register(0x40 + carrier, operator_level(instrument_voice.carrier));
+
+        // In additive mode the modulator is heard directly so it's scaled too
+        if instrument_voice.feedback & 0x01 != 0 {
+            self.chip
+                .write_register(0x40 + modulator, operator_level(instrument_voice.modulator));
+        }
+    }
+
+    fn update_voice_pan(&mut self, voice_index: usize) {
+        let Some(voice) = self.voices[voice_index].as_ref() else {
+            return;
+        };
+        let feedback: u8 = voice.instrument.voices[voice.instrument_voice].feedback;
+        let pan: u8 = self.channels[usize::from(voice.mus_channel)].pan;
+
+        // Same thresholds as the DMX OPL3 driver
+        let pan_bits: u8 = if pan < 48 {
+            0x10
+        } else if pan > 96 {
+            0x20
+        } else {
+            0x30
+        };
+
+        self.write_channel_register(voice_index, 0xC0, feedback | pan_bits);
+    }
+
+    fn update_voice_frequency(&mut self, voice_index: usize) {
+        if self.voices[voice_index].is_none() {
+            return;
+        }
+
+        let frequency: u16 = self.voice_frequency_register(voice_index);
+        self.write_channel_register(voice_index, 0xA0, (frequency & 0xFF) as u8);
+        self.write_channel_register(voice_index, 0xB0, 0x20 | (frequency >> 8) as u8);
+    }
+
+    // fnum in the bottom 10 bits and the block above it
+    fn voice_frequency_register(&self, voice_index: usize) -> u16 {
+        let Some(voice) = self.voices[voice_index].as_ref() else {
+            return 0;
+        };
+        let instrument_voice: GenMidiVoice = voice.instrument.voices[voice.instrument_voice];
+
+        let mut note: i32 = i32::from(voice.note);
+        if !voice.instrument.is_fixed() {
+            note += i32::from(instrument_voice.base_note_offset);
+        }
+        while note < 0 {
+            note += 12;
+        }
+        while note > HIGHEST_NOTE {
+            note -= 12;
+        }
+
+        let mut frequency_index: i32 = note * FREQUENCY_STEPS_PER_SEMITONE
+            + self.channels[usize::from(voice.mus_channel)].bend;
+        if voice.instrument_voice == 1 {
+            frequency_index += i32::from(voice.instrument.fine_tuning) / 2 - 64;
+        }
+
+        note_frequency_register(frequency_index)
+    }
+}
+
+// Frequency index is a MIDI note number in 1/32nds of a semitone.
+// Picks the lowest block the frequency fits in for the best precision
+pub fn note_frequency_register(frequency_index: i32) -> u16 {
+    let note: f64 = f64::from(frequency_index) / f64::from(FREQUENCY_STEPS_PER_SEMITONE);
+    let hertz: f64 = 440.0 * 2f64.powf((note - 69.0) / 12.0);
+
+    for block in 0..8 {
+        let fnum: u32 =
+            (hertz * f64::from(1u32 << (20 - block)) / f64::from(OPL_SAMPLE_RATE)).round() as u32;
+
+        if fnum < 1024 {
+            return (block << 10) as u16 | fnum as u16;
+        }
+    }
+
+    (7 << 10) | 1023
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::music::genmidi::{
+        GenMidi, GenMidiInstrument, GenMidiOperator, GenMidiVoice, GENMIDI_FLAG_DOUBLE_VOICE,
+        GENMIDI_FLAG_FIXED, GENMIDI_NUM_INSTRUMENTS,
+    };
+    use crate::music::mus::{MusEvent, MusEventKind, MusFile};
+    use crate::music::synth::{note_frequency_register, OplSynth};
+    use crate::play::hash::fnv_bytes;
+    use crate::sound::wav::Wav;
+    use crate::test_util::{check_golden_hash, find_test_iwad, read_test_resource};
+    use crate::wad::{self, DoomFile};
+
+    fn create_instrument(index: usize) -> GenMidiInstrument {
+        let percussion: bool = index >= 128;
+
+        GenMidiInstrument {
+            flags: if percussion {
+                GENMIDI_FLAG_FIXED
+            } else if index == 1 {
+                GENMIDI_FLAG_DOUBLE_VOICE
+            } else {
+                0
+            },
+            fine_tuning: 132,
+            fixed_note: 48,
+            voices: [
+                GenMidiVoice {
+                    modulator: GenMidiOperator {
+                        tremolo: 0x21,
+                        attack: 0xF4,
+                        sustain: 0x56,
+                        waveform: (index % 4) as u8,
+                        scale: 0x40,
+                        level: 0x18,
+                    },
+                    feedback: 0x0A,
+                    carrier: GenMidiOperator {
+                        tremolo: 0xE1,
+                        attack: 0xF2,
+                        sustain: 0x38,
+                        waveform: 0,
+                        scale: 0,
+                        level: 0x00,
+                    },
+                    base_note_offset: if percussion { 0 } else { -12 },
+                },
+                GenMidiVoice {
+                    modulator: GenMidiOperator {
+                        tremolo: 0x02,
+                        attack: 0xF4,
+                        sustain: 0x56,
+                        waveform: 0,
+                        scale: 0,
+                        level: 0x20,
+                    },
+                    feedback: 0x01,
+                    carrier: GenMidiOperator {
+                        tremolo: 0x01,
+                        attack: 0xF2,
+                        sustain: 0x38,
+                        waveform: 2,
+                        scale: 0,
+                        level: 0x08,
+                    },
+                    base_note_offset: 0,
+                },
+            ],
+            name: String::new(),
+        }
+    }
+
+    fn create_genmidi() -> GenMidi {
+        GenMidi {
+            instruments: (0..GENMIDI_NUM_INSTRUMENTS)
+                .map(create_instrument)
+                .collect(),
+        }
+    }
+
+    fn create_event(channel: u8, kind: MusEventKind, delay: u32) -> MusEvent {
+        MusEvent {
+            channel,
+            kind,
+            delay,
+        }
+    }
+
+    fn play_note(channel: u8, note: u8, delay: u32) -> MusEvent {
+        create_event(
+            channel,
+            MusEventKind::PlayNote {
+                note,
+                volume: Some(110),
+            },
+            delay,
+        )
+    }
+
+    fn create_song() -> MusFile {
+        MusFile {
+            primary_channels: 3,
+            secondary_channels: 0,
+            instruments: vec![0, 1, 135],
+            events: vec![
+                create_event(
+                    1,
+                    MusEventKind::Controller {
+                        controller: 0,
+                        value: 1,
+                    },
+                    0,
+                ),
+                play_note(0, 60, 0),
+                play_note(1, 64, 0),
+                play_note(15, 36, 35),
+                create_event(15, MusEventKind::ReleaseNote { note: 36 }, 0),
+                create_event(0, MusEventKind::PitchBend { bend: 192 }, 35),
+                create_event(
+                    1,
+                    MusEventKind::Controller {
+                        controller: 3,
+                        value: 60,
+                    },
+                    0,
+                ),
+                create_event(0, MusEventKind::ReleaseNote { note: 60 }, 0),
+                play_note(0, 67, 70),
+                create_event(0, MusEventKind::System { controller: 11 }, 0),
+                create_event(1, MusEventKind::ReleaseNote { note: 64 }, 70),
+                create_event(0, MusEventKind::ScoreEnd, 0),
+            ],
+        }
+    }
+
+    fn hash_samples(samples: &[i16]) -> u64 {
+        let bytes: Vec<u8> = samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        fnv_bytes(&bytes)
+    }
+
+    #[test]
+    fn test_note_frequency_register_picks_block_and_fnum() {
+        // A440
+        assert_eq!(note_frequency_register(69 * 32), (4 << 10) | 580);
+        // An octave up is the same fnum in the next block
+        assert_eq!(note_frequency_register(81 * 32), (5 << 10) | 580);
+        assert_eq!(note_frequency_register(0), 172);
+        assert_eq!(note_frequency_register(200 * 32), (7 << 10) | 1023);
+    }
+
+    #[test]
+    fn test_opl_synth_is_silent_without_music() {
+        let mut synth: OplSynth = OplSynth::new(create_genmidi(), 11025, false);
+
+        assert!(synth.render(1000).iter().all(|sample| *sample == 0));
+        assert!(!synth.is_playing());
+    }
+
+    #[test]
+    fn test_opl_synth_plays_and_finishes_songs() {
+        let mut synth: OplSynth = OplSynth::new(create_genmidi(), 11025, false);
+        synth.play(create_song(), false);
+
+        // The song is 210 ticks, 1.5 seconds long
+        let samples: Vec<i16> = synth.render(11025);
+        assert!(samples.iter().any(|sample| *sample != 0));
+        assert!(synth.is_playing());
+
+        synth.render(11025 / 2 + 100);
+        assert!(!synth.is_playing());
+
+        // Releases die down to silence
+        synth.render(11025 * 2);
+        assert!(synth.render(100).iter().all(|sample| *sample == 0));
+    }
+
+    #[test]
+    fn test_opl_synth_music_volume_scales_output() {
+        let peak = |volume: u8| -> i32 {
+            let mut synth: OplSynth = OplSynth::new(create_genmidi(), 11025, false);
+            synth.set_music_volume(volume);
+            synth.play(create_song(), false);
+            synth
+                .render(2000)
+                .iter()
+                .map(|sample| i32::from(*sample).abs())
+                .max()
+                .unwrap()
+        };
+
+        // Like the real chip the quietest level is very quiet but not silent
+        assert!(peak(127) > peak(40));
+        assert!(peak(40) > peak(0));
+    }
+
+    #[test]
+    fn test_opl_synth_opl3_pans_channels() {
+        let mut synth: OplSynth = OplSynth::new(create_genmidi(), 11025, true);
+        let mut song: MusFile = create_song();
+        song.events.insert(
+            0,
+            create_event(
+                0,
+                MusEventKind::Controller {
+                    controller: 4,
+                    value: 0,
+                },
+                0,
+            ),
+        );
+        song.events
+            .retain(|event| event.channel != 1 && event.channel != 15);
+        synth.play(song, false);
+
+        let samples: Vec<i16> = synth.render(2000);
+        assert!(samples.iter().step_by(2).any(|left| *left != 0));
+        assert!(samples.iter().skip(1).step_by(2).all(|right| *right == 0));
+    }
+
+    #[test]
+    fn test_opl_synth_render_is_deterministic() {
+        let render = |sample_rate: u32, opl3: bool| -> Vec<i16> {
+            let mut synth: OplSynth = OplSynth::new(create_genmidi(), sample_rate, opl3);
+            synth.play(create_song(), true);
+            synth.render(sample_rate as usize * 2)
+        };
+
+        assert_eq!(render(22050, false), render(22050, false));
+
+        // Golden hashes of the rendered song, these should only change
+        // when the synth is deliberately changed
+        assert_eq!(hash_samples(&render(22050, false)), 3422493939340332801);
+        assert_eq!(hash_samples(&render(44100, true)), 5000088394062159861);
+    }
+
+    #[test]
+    fn test_opl_synth_renders_d_e1m1_from_the_iwad() {
+        let Some(doom_file) = find_test_iwad() else {
+            return;
+        };
+        let mut doom_files: Vec<DoomFile> = vec![doom_file];
+        // Doom II has no episode one music
+        let Some(mus_data) = wad::find_lump_data(&mut doom_files, "D_E1M1") else {
+            return;
+        };
+        let genmidi: GenMidi =
+            GenMidi::from_lump(&wad::get_lump_data(&mut doom_files, "GENMIDI")).unwrap();
+
+        let mut synth: OplSynth = OplSynth::new(genmidi, 11025, false);
+        synth.play(MusFile::from_lump(&mus_data).unwrap(), false);
+
+        // The first ten seconds, the same in every Doom 1.9 IWAD
+        check_golden_hash("d_e1m1-genmidi", hash_samples(&synth.render(11025 * 10)));
+    }
+
+    #[test]
+    fn test_opl_synth_renders_the_test_song_lumps() {
+        let mus_file: MusFile = MusFile::from_lump(&read_test_resource("test-song.mus")).unwrap();
+        let genmidi: GenMidi = GenMidi::from_lump(&read_test_resource("test-genmidi.lmp")).unwrap();
+        assert_eq!(mus_file, create_song());
+        assert_eq!(genmidi, create_genmidi());
+
+        let mut synth: OplSynth = OplSynth::new(genmidi, 22050, false);
+        synth.play(mus_file, false);
+        check_golden_hash("test-song-genmidi", hash_samples(&synth.render(22050 * 2)));
+    }
+
+    #[test]
+    fn test_opl_synth_render_wav_writes_16_bit_stereo() {
+        let mut synth: OplSynth = OplSynth::new(create_genmidi(), 22050, false);
+        let wav: Wav = synth.render_wav(100);
+
+        assert_eq!(wav.sample_rate, 22050);
+        assert_eq!(wav.channels, 2);
+        assert_eq!(wav.data.len(), 400);
+    }
+}
@@ -3,7 +3,7 @@ use std::{ops::RangeInclusive, path::PathBuf};
 use crate::util;
 
 // (option_name, min_num_values - max_num_values)
-const DEFAULT_OPTIONS: [(&str, RangeInclusive<u32>); 24] = [
+const DEFAULT_OPTIONS: [(&str, RangeInclusive<u32>); 30] = [
     ("-devparm", 0..=0),
     ("-nomonsters", 0..=0),
     ("-respawn", 0..=0),
@@ -15,8 +15,8 @@ const DEFAULT_OPTIONS: [(&str, RangeInclusive<u32>); 24] = [
     ("-altdeath", 0..=0),
     ("-deathmatch", 0..=0),
     ("-cdrom", 0..=0),
-    // int
-    ("-turbo", 1..=1),
+    // Optional int
+    ("-turbo", 0..=1),
     // 2 Strings, list
     ("-wart", 2..=2),
     ("-warp", 2..=2),
@@ -39,6 +39,19 @@ const DEFAULT_OPTIONS: [(&str, RangeInclusive<u32>); 24] = [
     ("-record", 1..=1),
     // char
     ("-loadgame", 1..=1),
+    // Skip drawing during -timedemo
+    ("-nodraw", 0..=0),
+    // Path to write the -timedemo results to as JSON
+    ("-timedemojson", 1..=1),
+    // Hash stream to check a demo being played back against
+    ("-demoverify", 1..=1),
+    // Directory for numbered PNGs of every frame, or a .y4m file or pipe to
+    // stream them to
+    ("-framedump", 1..=1),
+    // Play in the terminal with 24 bit colour half blocks
+    ("-terminal", 0..=0),
+    // 0 for doom2.exe's movement bugs, 1 to fix them
+    ("-complevel", 1..=1),
 ];
 
 pub struct DoomOption {
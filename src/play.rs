@@ -0,0 +1,57 @@
+// The game simulation, the p_*.c parts of the original. Everything is
+// headless so levels can be loaded, stepped and inspected without a screen
+pub mod blockmap;
+pub mod ceiling;
+pub mod doors;
+pub mod enemy;
+pub mod floor;
+pub mod hash;
+pub mod inter;
+pub mod level;
+pub mod lights;
+pub mod map;
+pub mod maputl;
+pub mod mobj;
+pub mod plats;
+pub mod player;
+pub mod pspr;
+pub mod saveg;
+pub mod sight;
+pub mod spec;
+pub mod switch;
+pub mod telept;
+#[cfg(test)]
+pub mod test_map;
+pub mod thinker;
+pub mod ticcmd;
+pub mod user;
+pub mod world;
+
+pub const MAXPLAYERS: usize = 4;
+// Game tics per second
+pub const TICRATE: i32 = 35;
+
+// Which engine to match where bugs in the original change how things move.
+// Demos only play back right with the level they were recorded with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompatibilityLevel {
+    // doom2.exe 1.9. Players can wallrun by sliding twice in one tic along
+    // walls north or east of them, fast moves south or west aren't split so
+    // they can skip through thin walls, and sliding into a wall at a steep
+    // angle bounces the player back off it
+    #[default]
+    Vanilla,
+    // Those movement bugs fixed
+    Fixed,
+}
+
+impl CompatibilityLevel {
+    // compatibility_level in the config file and -complevel
+    pub fn from_config_value(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(CompatibilityLevel::Vanilla),
+            1 => Some(CompatibilityLevel::Fixed),
+            _ => None,
+        }
+    }
+}
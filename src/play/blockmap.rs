@@ -0,0 +1,299 @@
+// The BLOCKMAP lump from p_setup.c. The map is split into 128x128 unit
+// blocks that each list the lines touching them, and mobjs are linked into
+// the block their origin is in, so collision checks only look at what's
+// nearby
+use crate::fixed::{Fixed, FRACBITS};
+use crate::play::level::{LevelError, Line, Vertex};
+use crate::play::mobj::MobjId;
+
+pub const MAPBLOCKUNITS: i32 = 128;
+pub const MAPBLOCKSIZE: i32 = MAPBLOCKUNITS << FRACBITS;
+pub const MAPBLOCKSHIFT: u32 = FRACBITS + 7;
+pub const MAPBTOFRAC: u32 = MAPBLOCKSHIFT - FRACBITS;
+
+// Ends each block's line list
+pub const BLOCKLIST_END: u16 = 0xFFFF;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Blockmap {
+    pub org_x: Fixed,
+    pub org_y: Fixed,
+    pub width: i32,
+    pub height: i32,
+    // The whole lump as shorts. The header and block offsets are at the
+    // start, the offsets index into the lump itself
+    pub lump: Vec<u16>,
+    // Head of each block's mobj list, linked through Mobj::bnext
+    pub links: Vec<Option<MobjId>>,
+}
+
+impl Blockmap {
+    // P_LoadBlockMap. The offsets and line numbers are read unsigned so
+    // big maps work, the original read them as signed shorts
+    pub fn from_lump(data: &[u8], num_lines: usize) -> Result<Self, LevelError> {
+        if data.len() < 8 || !data.len().is_multiple_of(2) {
+            return Err(LevelError::InvalidLump(format!(
+                "BLOCKMAP size of {} is invalid",
+                data.len()
+            )));
+        }
+
+        let lump: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|short| u16::from_le_bytes([short[0], short[1]]))
+            .collect();
+
+        let width: i32 = i32::from(lump[2] as i16);
+        let height: i32 = i32::from(lump[3] as i16);
+        let num_blocks: usize = usize::try_from(width * height).unwrap_or(0);
+
+        if width <= 0 || height <= 0 || lump.len() < 4 + num_blocks {
+            return Err(LevelError::InvalidLump(format!(
+                "BLOCKMAP with {}x{} blocks doesn't fit in {} bytes",
+                width,
+                height,
+                data.len()
+            )));
+        }
+
+        // Every list has to end before the lump does and only use lines
+        // that exist
+        for offset in lump[4..4 + num_blocks].iter() {
+            let start: usize = usize::from(*offset);
+            let list: &[u16] = match lump.get(start..).and_then(|rest| {
+                rest.iter()
+                    .position(|line| *line == BLOCKLIST_END)
+                    .map(|len| &rest[..len])
+            }) {
+                Some(list) => list,
+                None => {
+                    return Err(LevelError::InvalidLump(format!(
+                        "BLOCKMAP list at offset {} is not terminated",
+                        start
+                    )))
+                }
+            };
+
+            if let Some(line) = list.iter().find(|line| usize::from(**line) >= num_lines) {
+                return Err(LevelError::InvalidLump(format!(
+                    "BLOCKMAP line {} does not exist",
+                    line
+                )));
+            }
+        }
+
+        Ok(Blockmap {
+            org_x: Fixed(i32::from(lump[0] as i16) << FRACBITS),
+            org_y: Fixed(i32::from(lump[1] as i16) << FRACBITS),
+            width,
+            height,
+            lump,
+            links: vec![None; num_blocks],
+        })
+    }
+
+    // For maps without a BLOCKMAP lump. Lists are laid out like the node
+    // builders do, starting with a 0 that the original engine checks as a
+    // line in every block
+    pub fn build(vertexes: &[Vertex], lines: &[Line]) -> Self {
+        let to_int = |value: Fixed| value.0 >> FRACBITS;
+        let min_x: i32 = vertexes.iter().map(|v| to_int(v.x)).min().unwrap_or(0);
+        let min_y: i32 = vertexes.iter().map(|v| to_int(v.y)).min().unwrap_or(0);
+        let max_x: i32 = vertexes.iter().map(|v| to_int(v.x)).max().unwrap_or(0);
+        let max_y: i32 = vertexes.iter().map(|v| to_int(v.y)).max().unwrap_or(0);
+
+        let org_x: i32 = min_x - 8;
+        let org_y: i32 = min_y - 8;
+        let width: i32 = (max_x - org_x) / MAPBLOCKUNITS + 1;
+        let height: i32 = (max_y - org_y) / MAPBLOCKUNITS + 1;
+
+        let mut block_lines: Vec<Vec<u16>> = vec![Vec::new(); (width * height) as usize];
+        for (line_index, line) in lines.iter().enumerate() {
+            let (x1, y1) = (to_int(vertexes[line.v1].x), to_int(vertexes[line.v1].y));
+            let (x2, y2) = (to_int(vertexes[line.v2].x), to_int(vertexes[line.v2].y));
+
+            let block_left: i32 = (x1.min(x2) - org_x) / MAPBLOCKUNITS;
+            let block_right: i32 = (x1.max(x2) - org_x) / MAPBLOCKUNITS;
+            let block_bottom: i32 = (y1.min(y2) - org_y) / MAPBLOCKUNITS;
+            let block_top: i32 = (y1.max(y2) - org_y) / MAPBLOCKUNITS;
+
+            for block_y in block_bottom..=block_top {
+                for block_x in block_left..=block_right {
+                    let left: i64 = i64::from(org_x + block_x * MAPBLOCKUNITS);
+                    let bottom: i64 = i64::from(org_y + block_y * MAPBLOCKUNITS);
+                    let corners: [(i64, i64); 4] = [
+                        (left, bottom),
+                        (left + i64::from(MAPBLOCKUNITS), bottom),
+                        (left, bottom + i64::from(MAPBLOCKUNITS)),
+                        (
+                            left + i64::from(MAPBLOCKUNITS),
+                            bottom + i64::from(MAPBLOCKUNITS),
+                        ),
+                    ];
+
+                    // The line touches the block unless every corner is
+                    // strictly on the same side of it
+                    let sides: Vec<i64> = corners
+                        .iter()
+                        .map(|(x, y)| {
+                            (i64::from(x2 - x1) * (y - i64::from(y1))
+                                - i64::from(y2 - y1) * (x - i64::from(x1)))
+                            .signum()
+                        })
+                        .collect();
+                    if sides.iter().all(|side| *side > 0) || sides.iter().all(|side| *side < 0) {
+                        continue;
+                    }
+
+                    block_lines[(block_y * width + block_x) as usize].push(line_index as u16);
+                }
+            }
+        }
+
+        let mut lump: Vec<u16> = vec![org_x as u16, org_y as u16, width as u16, height as u16];
+        lump.resize(4 + block_lines.len(), 0);
+        for (block, list) in block_lines.iter().enumerate() {
+            lump[4 + block] = u16::try_from(lump.len()).unwrap();
+            lump.push(0);
+            lump.extend_from_slice(list);
+            lump.push(BLOCKLIST_END);
+        }
+
+        Blockmap {
+            org_x: Fixed(org_x << FRACBITS),
+            org_y: Fixed(org_y << FRACBITS),
+            width,
+            height,
+            lump,
+            links: vec![None; block_lines.len()],
+        }
+    }
+
+    pub fn to_lump_data(&self) -> Vec<u8> {
+        self.lump
+            .iter()
+            .flat_map(|short| short.to_le_bytes())
+            .collect()
+    }
+
+    // Index of a block in the offsets and links, None when off the map
+    pub fn block_index(&self, block_x: i32, block_y: i32) -> Option<usize> {
+        if block_x < 0 || block_y < 0 || block_x >= self.width || block_y >= self.height {
+            return None;
+        }
+        Some((block_y * self.width + block_x) as usize)
+    }
+
+    pub fn block_x(&self, x: Fixed) -> i32 {
+        (x - self.org_x).0 >> MAPBLOCKSHIFT
+    }
+
+    pub fn block_y(&self, y: Fixed) -> i32 {
+        (y - self.org_y).0 >> MAPBLOCKSHIFT
+    }
+
+    // Lines listed for a block, including the leading 0 the node builders
+    // put on every list
+    pub fn block_lines(&self, block_x: i32, block_y: i32) -> &[u16] {
+        match self.block_index(block_x, block_y) {
+            Some(index) => {
+                let start: usize = usize::from(self.lump[4 + index]);
+                let len: usize = self.lump[start..]
+                    .iter()
+                    .position(|line| *line == BLOCKLIST_END)
+                    .unwrap();
+                &self.lump[start..start + len]
+            }
+            None => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::play::blockmap::Blockmap;
+    use crate::play::level::{Level, LevelError, MapLumps};
+    use crate::play::test_map::TestMap;
+
+    fn create_level() -> Level {
+        Level::from_lumps(
+            &TestMap::new()
+                .room(256, 0, 128)
+                .room(128, 0, 128)
+                .to_map_lumps(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_blockmap_build_lists_lines_touching_blocks() {
+        let level: Level = create_level();
+        let blockmap: &Blockmap = &level.blockmap;
+
+        assert_eq!(blockmap.org_x, Fixed(-8 * FRACUNIT));
+        assert_eq!(blockmap.org_y, Fixed(-8 * FRACUNIT));
+        assert_eq!((blockmap.width, blockmap.height), (4, 3));
+
+        // Bottom left block has the bottom wall of room 0 and the left wall
+        let lines: &[u16] = blockmap.block_lines(0, 0);
+        assert_eq!(lines[0], 0);
+        for line in &lines[1..] {
+            let line = &level.lines[usize::from(*line)];
+            assert!(line.bbox[2] <= Fixed(120 * FRACUNIT));
+        }
+        assert_eq!(lines.len(), 3);
+
+        // Off the map
+        assert!(blockmap.block_lines(-1, 0).is_empty());
+        assert!(blockmap.block_lines(4, 0).is_empty());
+    }
+
+    #[test]
+    fn test_blockmap_lump_data_round_trip() {
+        let level: Level = create_level();
+        let mut map_lumps: MapLumps = TestMap::new()
+            .room(256, 0, 128)
+            .room(128, 0, 128)
+            .to_map_lumps();
+        map_lumps.blockmap = level.blockmap.to_lump_data();
+
+        assert_eq!(
+            Level::from_lumps(&map_lumps).unwrap().blockmap,
+            level.blockmap
+        );
+        assert_eq!(
+            Blockmap::from_lump(&level.blockmap.to_lump_data(), level.lines.len()).unwrap(),
+            level.blockmap
+        );
+    }
+
+    #[test]
+    fn test_blockmap_from_lump_returns_error_for_bad_data() {
+        assert!(matches!(
+            Blockmap::from_lump(&[0, 0, 0, 0, 1, 0], 1),
+            Err(LevelError::InvalidLump(_))
+        ));
+
+        // 1x1 blocks with a list that never ends
+        let data: Vec<u8> = [0u16, 0, 1, 1, 5, 0, 1]
+            .iter()
+            .flat_map(|short| short.to_le_bytes())
+            .collect();
+        assert!(matches!(
+            Blockmap::from_lump(&data, 2),
+            Err(LevelError::InvalidLump(_))
+        ));
+
+        // A list using line 1 when there's only one line
+        let data: Vec<u8> = [0u16, 0, 1, 1, 5, 0, 1, 0xFFFF]
+            .iter()
+            .flat_map(|short| short.to_le_bytes())
+            .collect();
+        assert!(Blockmap::from_lump(&data, 2).is_ok());
+        assert!(matches!(
+            Blockmap::from_lump(&data, 1),
+            Err(LevelError::InvalidLump(_))
+        ));
+    }
+}
@@ -0,0 +1,394 @@
+// Port of p_ceilng.c. Moving ceilings and crushers, with the active list
+// used to stop and restart crushers by tag
+use crate::fixed::{Fixed, FRACUNIT};
+use crate::play::floor::{MoveResult, Plane};
+use crate::play::thinker::{Thinker, ThinkerId};
+use crate::play::world::World;
+use crate::sound::sfx::{SFX_PSTOP, SFX_STNMOV};
+
+pub const MAXCEILINGS: usize = 30;
+pub const CEILSPEED: Fixed = Fixed(FRACUNIT);
+pub const CEILWAIT: i32 = 150;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CeilingType {
+    LowerToFloor,
+    RaiseToHighest,
+    LowerAndCrush,
+    CrushAndRaise,
+    FastCrushAndRaise,
+    SilentCrushAndRaise,
+}
+
+// ceiling_t
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ceiling {
+    pub ceiling_type: CeilingType,
+    pub sector: usize,
+    pub bottom_height: Fixed,
+    pub top_height: Fixed,
+    pub speed: Fixed,
+    pub crush: bool,
+    // 1 = up, 0 = waiting, -1 = down
+    pub direction: i32,
+    // ID
+    pub tag: i32,
+    pub old_direction: i32,
+}
+
+impl World {
+    // T_MoveCeiling
+    pub fn move_ceiling_thinker(&mut self, id: ThinkerId) {
+        let mut ceiling: Ceiling = match self.thinkers.get(id) {
+            Some(Thinker::Ceiling(ceiling)) => *ceiling,
+            _ => return,
+        };
+
+        match ceiling.direction {
+            // UP
+            1 => {
+                let res: MoveResult = self.move_plane(
+                    ceiling.sector,
+                    ceiling.speed,
+                    ceiling.top_height,
+                    false,
+                    Plane::Ceiling,
+                    ceiling.direction,
+                );
+                self.ceiling_move_sound(&ceiling);
+
+                if res == MoveResult::PastDest {
+                    match ceiling.ceiling_type {
+                        CeilingType::RaiseToHighest => {
+                            self.remove_active_ceiling(id);
+                            return;
+                        }
+                        CeilingType::SilentCrushAndRaise => {
+                            self.start_sector_sound(ceiling.sector, SFX_PSTOP);
+                            ceiling.direction = -1;
+                        }
+                        CeilingType::FastCrushAndRaise | CeilingType::CrushAndRaise => {
+                            ceiling.direction = -1;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // DOWN
+            -1 => {
+                let res: MoveResult = self.move_plane(
+                    ceiling.sector,
+                    ceiling.speed,
+                    ceiling.bottom_height,
+                    ceiling.crush,
+                    Plane::Ceiling,
+                    ceiling.direction,
+                );
+                self.ceiling_move_sound(&ceiling);
+
+                if res == MoveResult::PastDest {
+                    match ceiling.ceiling_type {
+                        CeilingType::SilentCrushAndRaise => {
+                            self.start_sector_sound(ceiling.sector, SFX_PSTOP);
+                            ceiling.speed = CEILSPEED;
+                            ceiling.direction = 1;
+                        }
+                        CeilingType::CrushAndRaise => {
+                            ceiling.speed = CEILSPEED;
+                            ceiling.direction = 1;
+                        }
+                        CeilingType::FastCrushAndRaise => {
+                            ceiling.direction = 1;
+                        }
+                        CeilingType::LowerAndCrush | CeilingType::LowerToFloor => {
+                            self.remove_active_ceiling(id);
+                            return;
+                        }
+                        _ => {}
+                    }
+                } else if res == MoveResult::Crushed {
+                    match ceiling.ceiling_type {
+                        CeilingType::SilentCrushAndRaise
+                        | CeilingType::CrushAndRaise
+                        | CeilingType::LowerAndCrush => {
+                            ceiling.speed = Fixed(CEILSPEED.0 / 8);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // IN STASIS
+            _ => {}
+        }
+
+        if let Some(Thinker::Ceiling(stored)) = self.thinkers.get_mut(id) {
+            *stored = ceiling;
+        }
+    }
+
+    fn ceiling_move_sound(&mut self, ceiling: &Ceiling) {
+        if self.level_time & 7 == 0 && ceiling.ceiling_type != CeilingType::SilentCrushAndRaise {
+            self.start_sector_sound(ceiling.sector, SFX_STNMOV);
+        }
+    }
+
+    // EV_DoCeiling. Move a ceiling up/down and all around!
+    pub fn do_ceiling(&mut self, line: usize, ceiling_type: CeilingType) -> bool {
+        let mut rtn: bool = false;
+
+        // Reactivate in-stasis ceilings...for certain types.
+        match ceiling_type {
+            CeilingType::FastCrushAndRaise
+            | CeilingType::SilentCrushAndRaise
+            | CeilingType::CrushAndRaise => self.activate_in_stasis_ceiling(line),
+            _ => {}
+        }
+
+        for sector in self.level.tagged_sectors(line) {
+            if self.level.sectors[sector].special_data.is_some() {
+                continue;
+            }
+
+            // new door thinker
+            rtn = true;
+            let floor_height: Fixed = self.level.sectors[sector].floor_height;
+            let ceiling_height: Fixed = self.level.sectors[sector].ceiling_height;
+            let mut ceiling: Ceiling = Ceiling {
+                ceiling_type,
+                sector,
+                bottom_height: Fixed::ZERO,
+                top_height: Fixed::ZERO,
+                speed: CEILSPEED,
+                crush: false,
+                direction: 0,
+                tag: self.level.sectors[sector].tag,
+                old_direction: 0,
+            };
+
+            match ceiling_type {
+                CeilingType::FastCrushAndRaise => {
+                    ceiling.crush = true;
+                    ceiling.top_height = ceiling_height;
+                    ceiling.bottom_height = floor_height + Fixed(8 * FRACUNIT);
+                    ceiling.direction = -1;
+                    ceiling.speed = Fixed(CEILSPEED.0 * 2);
+                }
+                CeilingType::SilentCrushAndRaise
+                | CeilingType::CrushAndRaise
+                | CeilingType::LowerAndCrush
+                | CeilingType::LowerToFloor => {
+                    if ceiling_type == CeilingType::SilentCrushAndRaise
+                        || ceiling_type == CeilingType::CrushAndRaise
+                    {
+                        ceiling.crush = true;
+                        ceiling.top_height = ceiling_height;
+                    }
+                    ceiling.bottom_height = floor_height;
+                    if ceiling_type != CeilingType::LowerToFloor {
+                        ceiling.bottom_height += Fixed(8 * FRACUNIT);
+                    }
+                    ceiling.direction = -1;
+                    ceiling.speed = CEILSPEED;
+                }
+                CeilingType::RaiseToHighest => {
+                    ceiling.top_height = self.level.find_highest_ceiling_surrounding(sector);
+                    ceiling.direction = 1;
+                    ceiling.speed = CEILSPEED;
+                }
+            }
+
+            let id: ThinkerId = self.thinkers.add(Thinker::Ceiling(ceiling));
+            self.level.sectors[sector].special_data = Some(id);
+            self.add_active_ceiling(id);
+        }
+
+        rtn
+    }
+
+    // P_AddActiveCeiling. A full list drops the ceiling, it keeps moving
+    // but can't be stopped
+    pub fn add_active_ceiling(&mut self, id: ThinkerId) {
+        if let Some(slot) = self.active_ceilings.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(id);
+        }
+    }
+
+    // P_RemoveActiveCeiling. Ceilings missing from the list are left alone
+    pub fn remove_active_ceiling(&mut self, id: ThinkerId) {
+        let slot: &mut Option<ThinkerId> = match self
+            .active_ceilings
+            .iter_mut()
+            .find(|slot| **slot == Some(id))
+        {
+            Some(slot) => slot,
+            None => return,
+        };
+        *slot = None;
+
+        if let Some(Thinker::Ceiling(ceiling)) = self.thinkers.get(id) {
+            self.level.sectors[ceiling.sector].special_data = None;
+        }
+        self.thinkers.remove(id);
+    }
+
+    // P_ActivateInStasisCeiling. Restart a ceiling that's in-stasis
+    pub fn activate_in_stasis_ceiling(&mut self, line: usize) {
+        let tag: i32 = self.level.lines[line].tag;
+
+        for id in self.active_ceilings.iter().flatten() {
+            if let Some(Thinker::Ceiling(ceiling)) = self.thinkers.get_mut(*id) {
+                if ceiling.tag == tag && ceiling.direction == 0 {
+                    ceiling.direction = ceiling.old_direction;
+                }
+            }
+        }
+    }
+
+    // EV_CeilingCrushStop. Stop a ceiling from crushing!
+    pub fn ceiling_crush_stop(&mut self, line: usize) -> bool {
+        let tag: i32 = self.level.lines[line].tag;
+        let mut rtn: bool = false;
+
+        for id in self.active_ceilings.iter().flatten() {
+            if let Some(Thinker::Ceiling(ceiling)) = self.thinkers.get_mut(*id) {
+                if ceiling.tag == tag && ceiling.direction != 0 {
+                    ceiling.old_direction = ceiling.direction;
+                    ceiling.direction = 0;
+                    rtn = true;
+                }
+            }
+        }
+
+        rtn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::Fixed;
+    use crate::info::mobjinfo::{MT_BLOOD, MT_TROOP};
+    use crate::play::ceiling::{CeilingType, CEILSPEED};
+    use crate::play::mobj::{MobjId, ONFLOORZ};
+    use crate::play::test_map::{tick, TestMap, TestRoom};
+    use crate::play::thinker::Thinker;
+    use crate::play::world::World;
+
+    fn tagged(ceiling_height: i16) -> TestRoom {
+        TestRoom {
+            width: 128,
+            floor_height: 0,
+            ceiling_height,
+            light_level: 160,
+            tag: 1,
+            ..Default::default()
+        }
+    }
+
+    fn tagged_line(world: &World) -> usize {
+        world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == 1)
+            .unwrap()
+    }
+
+    fn ceiling_speed(world: &World, sector: usize) -> Fixed {
+        match world
+            .thinkers
+            .get(world.level.sectors[sector].special_data.unwrap())
+        {
+            Some(Thinker::Ceiling(ceiling)) => ceiling.speed,
+            _ => panic!("no ceiling"),
+        }
+    }
+
+    #[test]
+    fn test_do_ceiling_lower_and_crush_stops_above_floor() {
+        let mut world: World = TestMap::new()
+            .room(128, 0, 128)
+            .room_with(tagged(128))
+            .line_special(0, 44, 1)
+            .to_world();
+        let line: usize = tagged_line(&world);
+
+        assert!(world.do_ceiling(line, CeilingType::LowerAndCrush));
+        assert!(!world.do_ceiling(line, CeilingType::LowerAndCrush));
+
+        tick(&mut world, 120);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(8));
+        assert!(world.level.sectors[1].special_data.is_some());
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].special_data, None);
+        assert!(world
+            .active_ceilings
+            .iter()
+            .all(|ceiling| ceiling.is_none()));
+    }
+
+    #[test]
+    fn test_do_ceiling_raise_to_highest() {
+        let mut world: World = TestMap::new()
+            .room(128, 0, 128)
+            .room_with(tagged(64))
+            .room(128, 0, 96)
+            .line_special(0, 40, 1)
+            .to_world();
+        let line: usize = tagged_line(&world);
+
+        assert!(world.do_ceiling(line, CeilingType::RaiseToHighest));
+        tick(&mut world, 64);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(128));
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].special_data, None);
+    }
+
+    #[test]
+    fn test_crusher_slows_down_and_damages_things() {
+        let mut world: World = TestMap::new()
+            .room(128, 0, 128)
+            .room_with(tagged(72))
+            .line_special(0, 6, 1)
+            .to_world();
+        let line: usize = tagged_line(&world);
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(192),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+
+        assert!(world.do_ceiling(line, CeilingType::CrushAndRaise));
+
+        // Imps are 56 high
+        tick(&mut world, 16);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(56));
+        assert_eq!(world.thinkers.mobj(troop).health, 60);
+
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(55));
+        assert_eq!(ceiling_speed(&world, 1), Fixed(CEILSPEED.0 / 8));
+        assert_eq!(world.thinkers.mobj(troop).health, 50);
+        assert_eq!(
+            world
+                .thinkers
+                .mobjs()
+                .filter(|(_id, mobj)| mobj.mobj_type == MT_BLOOD)
+                .count(),
+            1
+        );
+
+        // Stopped in stasis until another crusher line restarts it
+        assert!(world.ceiling_crush_stop(line));
+        let height: Fixed = world.level.sectors[1].ceiling_height;
+        tick(&mut world, 10);
+        assert_eq!(world.level.sectors[1].ceiling_height, height);
+
+        assert!(!world.do_ceiling(line, CeilingType::CrushAndRaise));
+        tick(&mut world, 1);
+        assert_eq!(
+            world.level.sectors[1].ceiling_height,
+            height - Fixed(CEILSPEED.0 / 8)
+        );
+    }
+}
@@ -0,0 +1,629 @@
+// Port of p_doors.c. Vertical doors, opened by tagged lines or by using
+// the door's own line
+use crate::fixed::{Fixed, FRACUNIT};
+use crate::play::floor::{MoveResult, Plane};
+use crate::play::mobj::MobjId;
+use crate::play::player::{
+    Player, IT_BLUECARD, IT_BLUESKULL, IT_REDCARD, IT_REDSKULL, IT_YELLOWCARD, IT_YELLOWSKULL,
+};
+use crate::play::thinker::{Thinker, ThinkerId};
+use crate::play::world::World;
+use crate::play::TICRATE;
+use crate::sound::sfx::{SfxNum, SFX_BDCLS, SFX_BDOPN, SFX_DORCLS, SFX_DOROPN, SFX_OOF};
+
+pub const VDOORSPEED: Fixed = Fixed(FRACUNIT * 2);
+pub const VDOORWAIT: i32 = 150;
+
+pub const PD_BLUEO: &str = "You need a blue key to activate this object";
+pub const PD_REDO: &str = "You need a red key to activate this object";
+pub const PD_YELLOWO: &str = "You need a yellow key to activate this object";
+pub const PD_BLUEK: &str = "You need a blue key to open this door";
+pub const PD_REDK: &str = "You need a red key to open this door";
+pub const PD_YELLOWK: &str = "You need a yellow key to open this door";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DoorType {
+    Normal,
+    Close30ThenOpen,
+    Close,
+    Open,
+    RaiseIn5Mins,
+    BlazeRaise,
+    BlazeOpen,
+    BlazeClose,
+}
+
+// vldoor_t
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerticalDoor {
+    pub door_type: DoorType,
+    pub sector: usize,
+    pub top_height: Fixed,
+    pub speed: Fixed,
+    // 1 = up, 0 = waiting at top, -1 = down, 2 = initial wait
+    pub direction: i32,
+    // Tics to wait at the top
+    pub top_wait: i32,
+    // (keep in case a door going down is reset)
+    // when it reaches 0, start going down
+    pub top_countdown: i32,
+}
+
+impl World {
+    // T_VerticalDoor
+    pub fn vertical_door_thinker(&mut self, id: ThinkerId) {
+        let mut door: VerticalDoor = match self.thinkers.get(id) {
+            Some(Thinker::Door(door)) => *door,
+            _ => return,
+        };
+
+        match door.direction {
+            // WAITING
+            0 => {
+                door.top_countdown -= 1;
+                if door.top_countdown == 0 {
+                    match door.door_type {
+                        DoorType::BlazeRaise => {
+                            // Time to go back down
+                            door.direction = -1;
+                            self.start_sector_sound(door.sector, SFX_BDCLS);
+                        }
+                        DoorType::Normal => {
+                            // Time to go back down
+                            door.direction = -1;
+                            self.start_sector_sound(door.sector, SFX_DORCLS);
+                        }
+                        DoorType::Close30ThenOpen => {
+                            door.direction = 1;
+                            self.start_sector_sound(door.sector, SFX_DOROPN);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // INITIAL WAIT
+            2 => {
+                door.top_countdown -= 1;
+                if door.top_countdown == 0 && door.door_type == DoorType::RaiseIn5Mins {
+                    door.direction = 1;
+                    door.door_type = DoorType::Normal;
+                    self.start_sector_sound(door.sector, SFX_DOROPN);
+                }
+            }
+            // DOWN
+            -1 => {
+                let floor_height: Fixed = self.level.sectors[door.sector].floor_height;
+                let res: MoveResult = self.move_plane(
+                    door.sector,
+                    door.speed,
+                    floor_height,
+                    false,
+                    Plane::Ceiling,
+                    door.direction,
+                );
+
+                if res == MoveResult::PastDest {
+                    match door.door_type {
+                        DoorType::BlazeRaise | DoorType::BlazeClose => {
+                            self.remove_door(id, door.sector);
+                            self.start_sector_sound(door.sector, SFX_BDCLS);
+                            return;
+                        }
+                        DoorType::Normal | DoorType::Close => {
+                            self.remove_door(id, door.sector);
+                            return;
+                        }
+                        DoorType::Close30ThenOpen => {
+                            door.direction = 0;
+                            door.top_countdown = TICRATE * 30;
+                        }
+                        _ => {}
+                    }
+                } else if res == MoveResult::Crushed {
+                    match door.door_type {
+                        // DO NOT GO BACK UP!
+                        DoorType::BlazeClose | DoorType::Close => {}
+                        _ => {
+                            door.direction = 1;
+                            self.start_sector_sound(door.sector, SFX_DOROPN);
+                        }
+                    }
+                }
+            }
+            // UP
+            1 => {
+                let res: MoveResult = self.move_plane(
+                    door.sector,
+                    door.speed,
+                    door.top_height,
+                    false,
+                    Plane::Ceiling,
+                    door.direction,
+                );
+
+                if res == MoveResult::PastDest {
+                    match door.door_type {
+                        DoorType::BlazeRaise | DoorType::Normal => {
+                            // Wait at top
+                            door.direction = 0;
+                            door.top_countdown = door.top_wait;
+                        }
+                        DoorType::Close30ThenOpen | DoorType::BlazeOpen | DoorType::Open => {
+                            self.remove_door(id, door.sector);
+                            return;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(Thinker::Door(stored)) = self.thinkers.get_mut(id) {
+            *stored = door;
+        }
+    }
+
+    fn remove_door(&mut self, id: ThinkerId, sector: usize) {
+        self.level.sectors[sector].special_data = None;
+        // Unlink and free
+        self.thinkers.remove(id);
+    }
+
+    // Whether the player has either key of a colour, the card or the skull.
+    // Shows the message and grunts if they don't
+    fn check_key(
+        &mut self,
+        player: usize,
+        card: usize,
+        skull: usize,
+        message: &'static str,
+    ) -> bool {
+        let player: &mut Player = &mut self.players[player];
+        if player.cards[card] || player.cards[skull] {
+            return true;
+        }
+
+        player.message = Some(message);
+        self.start_sound(None, SFX_OOF);
+        false
+    }
+
+    // EV_DoLockedDoor. Move a locked door up/down
+    pub fn do_locked_door(
+        &mut self,
+        line: usize,
+        door_type: DoorType,
+        player: Option<usize>,
+    ) -> bool {
+        let player: usize = match player {
+            Some(player) => player,
+            None => return false,
+        };
+
+        let has_key: bool = match self.level.lines[line].special {
+            // Blue Lock
+            99 | 133 => self.check_key(player, IT_BLUECARD, IT_BLUESKULL, PD_BLUEO),
+            // Red Lock
+            134 | 135 => self.check_key(player, IT_REDCARD, IT_REDSKULL, PD_REDO),
+            // Yellow Lock
+            136 | 137 => self.check_key(player, IT_YELLOWCARD, IT_YELLOWSKULL, PD_YELLOWO),
+            _ => true,
+        };
+
+        has_key && self.do_door(line, door_type)
+    }
+
+    // EV_DoDoor
+    pub fn do_door(&mut self, line: usize, door_type: DoorType) -> bool {
+        self.do_tagged_door(self.level.lines[line].tag, door_type)
+    }
+
+    // EV_DoDoor on a made up line with only a tag, for A_KeenDie and
+    // A_BossDeath
+    pub fn do_tagged_door(&mut self, tag: i32, door_type: DoorType) -> bool {
+        let mut rtn: bool = false;
+
+        for sector in self.level.sectors_with_tag(tag) {
+            if self.level.sectors[sector].special_data.is_some() {
+                continue;
+            }
+
+            // New door thinker
+            rtn = true;
+            let ceiling_height: Fixed = self.level.sectors[sector].ceiling_height;
+            let lowest_ceiling: Fixed =
+                self.level.find_lowest_ceiling_surrounding(sector) - Fixed(4 * FRACUNIT);
+            let mut door: VerticalDoor = VerticalDoor {
+                door_type,
+                sector,
+                top_height: Fixed::ZERO,
+                speed: VDOORSPEED,
+                direction: 0,
+                top_wait: VDOORWAIT,
+                top_countdown: 0,
+            };
+
+            let mut sound: Option<SfxNum> = None;
+            match door_type {
+                DoorType::BlazeClose => {
+                    door.top_height = lowest_ceiling;
+                    door.direction = -1;
+                    door.speed = Fixed(VDOORSPEED.0 * 4);
+                    sound = Some(SFX_BDCLS);
+                }
+                DoorType::Close => {
+                    door.top_height = lowest_ceiling;
+                    door.direction = -1;
+                    sound = Some(SFX_DORCLS);
+                }
+                DoorType::Close30ThenOpen => {
+                    door.top_height = ceiling_height;
+                    door.direction = -1;
+                    sound = Some(SFX_DORCLS);
+                }
+                DoorType::BlazeRaise | DoorType::BlazeOpen => {
+                    door.direction = 1;
+                    door.top_height = lowest_ceiling;
+                    door.speed = Fixed(VDOORSPEED.0 * 4);
+                    if door.top_height != ceiling_height {
+                        sound = Some(SFX_BDOPN);
+                    }
+                }
+                DoorType::Normal | DoorType::Open => {
+                    door.direction = 1;
+                    door.top_height = lowest_ceiling;
+                    if door.top_height != ceiling_height {
+                        sound = Some(SFX_DOROPN);
+                    }
+                }
+                DoorType::RaiseIn5Mins => {}
+            }
+
+            self.add_door(door);
+            if let Some(sound) = sound {
+                self.start_sector_sound(sector, sound);
+            }
+        }
+
+        rtn
+    }
+
+    fn add_door(&mut self, door: VerticalDoor) {
+        let id: ThinkerId = self.thinkers.add(Thinker::Door(door));
+        self.level.sectors[door.sector].special_data = Some(id);
+    }
+
+    // EV_VerticalDoor. Open a door manually, no tag value
+    pub fn vertical_door(&mut self, line: usize, id: MobjId) {
+        // Check for locks
+        let player: Option<usize> = self.thinkers.mobj(id).player;
+        let special: i32 = self.level.lines[line].special;
+
+        let has_key: bool = match (special, player) {
+            (_, None) => true,
+            // Blue Lock
+            (26 | 32, Some(player)) => self.check_key(player, IT_BLUECARD, IT_BLUESKULL, PD_BLUEK),
+            // Yellow Lock
+            (27 | 34, Some(player)) => {
+                self.check_key(player, IT_YELLOWCARD, IT_YELLOWSKULL, PD_YELLOWK)
+            }
+            // Red Lock
+            (28 | 33, Some(player)) => self.check_key(player, IT_REDCARD, IT_REDSKULL, PD_REDK),
+            _ => true,
+        };
+        if !has_key {
+            return;
+        }
+
+        // if the sector has an active thinker, use it
+        let side: usize = match self.level.lines[line].side_num[1] {
+            Some(side) => side,
+            None => panic!("EV_VerticalDoor: DR special type on 1-sided linedef"),
+        };
+        let sector: usize = self.level.sides[side].sector;
+
+        if let Some(thinker) = self.level.sectors[sector].special_data {
+            if let 1 | 26 | 27 | 28 | 117 = special {
+                // Wrong thinker type, a lift or floor can own the sector's
+                // special data. The door direction overlays the plat's wait
+                let direction: Option<&mut i32> = match self.thinkers.get_mut(thinker) {
+                    Some(Thinker::Door(door)) => Some(&mut door.direction),
+                    Some(Thinker::Plat(plat)) => Some(&mut plat.wait),
+                    _ => None,
+                };
+                if let Some(direction) = direction {
+                    if *direction == -1 {
+                        // go back up
+                        *direction = 1;
+                    } else {
+                        if player.is_none() {
+                            // JDC: bad guys never close doors
+                            return;
+                        }
+                        // start going down immediately
+                        *direction = -1;
+                    }
+                }
+                return;
+            }
+        }
+
+        // for proper sound
+        match special {
+            // BLAZING DOOR RAISE | BLAZING DOOR OPEN
+            117 | 118 => self.start_sector_sound(sector, SFX_BDOPN),
+            // NORMAL DOOR SOUND
+            _ => self.start_sector_sound(sector, SFX_DOROPN),
+        }
+
+        // new door thinker
+        let mut door: VerticalDoor = VerticalDoor {
+            door_type: DoorType::Normal,
+            sector,
+            top_height: Fixed::ZERO,
+            speed: VDOORSPEED,
+            direction: 1,
+            top_wait: VDOORWAIT,
+            top_countdown: 0,
+        };
+
+        match special {
+            31..=34 => {
+                door.door_type = DoorType::Open;
+                self.level.lines[line].special = 0;
+            }
+            // blazing door raise
+            117 => {
+                door.door_type = DoorType::BlazeRaise;
+                door.speed = Fixed(VDOORSPEED.0 * 4);
+            }
+            // blazing door open
+            118 => {
+                door.door_type = DoorType::BlazeOpen;
+                self.level.lines[line].special = 0;
+                door.speed = Fixed(VDOORSPEED.0 * 4);
+            }
+            _ => {}
+        }
+
+        // find the top and bottom of the movement range
+        door.top_height = self.level.find_lowest_ceiling_surrounding(sector) - Fixed(4 * FRACUNIT);
+        self.add_door(door);
+    }
+
+    // P_SpawnDoorCloseIn30
+    pub fn spawn_door_close_in_30(&mut self, sector: usize) {
+        self.level.sectors[sector].special = 0;
+        self.add_door(VerticalDoor {
+            door_type: DoorType::Normal,
+            sector,
+            top_height: Fixed::ZERO,
+            speed: VDOORSPEED,
+            direction: 0,
+            top_wait: 0,
+            top_countdown: 30 * TICRATE,
+        });
+    }
+
+    // P_SpawnDoorRaiseIn5Mins
+    pub fn spawn_door_raise_in_5_mins(&mut self, sector: usize) {
+        self.level.sectors[sector].special = 0;
+        let top_height: Fixed =
+            self.level.find_lowest_ceiling_surrounding(sector) - Fixed(4 * FRACUNIT);
+        self.add_door(VerticalDoor {
+            door_type: DoorType::RaiseIn5Mins,
+            sector,
+            top_height,
+            speed: VDOORSPEED,
+            direction: 2,
+            top_wait: VDOORWAIT,
+            top_countdown: 5 * 60 * TICRATE,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::Fixed;
+    use crate::info::mobjinfo::{MT_PLAYER, MT_TROOP};
+    use crate::play::doors::{DoorType, PD_BLUEK};
+    use crate::play::mobj::{MobjId, ONFLOORZ};
+    use crate::play::player::IT_BLUESKULL;
+    use crate::play::test_map::{tick, TestMap, TestRoom};
+    use crate::play::thinker::Thinker;
+    use crate::play::world::World;
+    use crate::sound::sfx::{SFX_DORCLS, SFX_DOROPN, SFX_OOF};
+
+    // A closed door between two rooms, with the given special on the line
+    // from the door to the room on its right
+    fn door_map(special: i16, tag: i16) -> TestMap {
+        TestMap::new()
+            .room(256, 0, 128)
+            .room_with(TestRoom {
+                width: 64,
+                floor_height: 0,
+                ceiling_height: 0,
+                light_level: 160,
+                tag: 1,
+                ..Default::default()
+            })
+            .room(256, 0, 128)
+            .line_special(1, special, tag)
+    }
+
+    fn special_line(world: &World) -> usize {
+        world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.special != 0)
+            .unwrap()
+    }
+
+    fn spawn_player(world: &mut World) -> MobjId {
+        let id: MobjId = world.spawn_mobj(
+            Fixed::from_int(340),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_PLAYER,
+        );
+        world.thinkers.mobj_mut(id).player = Some(0);
+        world.players[0].mo = Some(id);
+        id
+    }
+
+    fn door_direction(world: &World) -> i32 {
+        match world
+            .thinkers
+            .get(world.level.sectors[1].special_data.unwrap())
+        {
+            Some(Thinker::Door(door)) => door.direction,
+            _ => panic!("no door"),
+        }
+    }
+
+    #[test]
+    fn test_do_door_opens_waits_and_closes() {
+        let mut world: World = door_map(29, 1).to_world();
+        let line: usize = special_line(&world);
+
+        assert!(world.do_door(line, DoorType::Normal));
+        // Already moving
+        assert!(!world.do_door(line, DoorType::Normal));
+        assert_eq!(world.sound_events.last().unwrap().sfx, SFX_DOROPN);
+
+        // Up to 4 below the lowest surrounding ceiling at 2 units a tic,
+        // reaching it exactly only counts as getting there on the next tic
+        tick(&mut world, 62);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(124));
+        assert_eq!(door_direction(&world), 1);
+        tick(&mut world, 1);
+        assert_eq!(door_direction(&world), 0);
+
+        // Waits 150 tics at the top
+        tick(&mut world, 150);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(124));
+        assert_eq!(door_direction(&world), -1);
+        assert_eq!(world.sound_events.last().unwrap().sfx, SFX_DORCLS);
+
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(122));
+        tick(&mut world, 61);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(0));
+        assert!(world.level.sectors[1].special_data.is_some());
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].special_data, None);
+        assert!(!world
+            .thinkers
+            .iter()
+            .any(|(_id, thinker)| matches!(thinker, Thinker::Door(_))));
+    }
+
+    #[test]
+    fn test_do_door_blaze_open_stays_open() {
+        let mut world: World = door_map(112, 1).to_world();
+        let line: usize = special_line(&world);
+
+        assert!(world.do_door(line, DoorType::BlazeOpen));
+        tick(&mut world, 15);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(120));
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(124));
+        assert_eq!(world.level.sectors[1].special_data, None);
+    }
+
+    #[test]
+    fn test_vertical_door_reverses_when_used_again() {
+        let mut world: World = door_map(1, 0).to_world();
+        let line: usize = special_line(&world);
+        let player: MobjId = spawn_player(&mut world);
+
+        world.vertical_door(line, player);
+        tick(&mut world, 10);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(20));
+
+        // Players can close it on the way up
+        world.vertical_door(line, player);
+        assert_eq!(door_direction(&world), -1);
+        tick(&mut world, 5);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(10));
+
+        // Anything sends it back up on the way down
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(400),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+        world.vertical_door(line, troop);
+        assert_eq!(door_direction(&world), 1);
+
+        // But monsters never close doors
+        world.vertical_door(line, troop);
+        assert_eq!(door_direction(&world), 1);
+    }
+
+    #[test]
+    fn test_vertical_door_needs_keys() {
+        let mut world: World = door_map(26, 0).to_world();
+        let line: usize = special_line(&world);
+        let player: MobjId = spawn_player(&mut world);
+
+        world.vertical_door(line, player);
+        assert_eq!(world.level.sectors[1].special_data, None);
+        assert_eq!(world.players[0].message, Some(PD_BLUEK));
+        assert_eq!(world.sound_events.last().unwrap().sfx, SFX_OOF);
+        assert_eq!(world.sound_events.last().unwrap().origin, None);
+
+        // Either the card or the skull opens it
+        world.players[0].cards[IT_BLUESKULL] = true;
+        world.vertical_door(line, player);
+        assert!(world.level.sectors[1].special_data.is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "EV_VerticalDoor: DR special type on 1-sided linedef")]
+    fn test_vertical_door_panics_on_one_sided_lines() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .line_special(0, 1, 0)
+            .to_world();
+        let line: usize = special_line(&world);
+        let player: MobjId = spawn_player(&mut world);
+
+        world.vertical_door(line, player);
+    }
+
+    #[test]
+    fn test_spawn_specials_spawns_timed_doors() {
+        let timed_door = |special: i16, ceiling_height: i16| TestRoom {
+            width: 64,
+            floor_height: 0,
+            ceiling_height,
+            light_level: 160,
+            special,
+            ..Default::default()
+        };
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .room_with(timed_door(10, 64))
+            .room_with(timed_door(14, 0))
+            .room(256, 0, 128)
+            .to_world();
+        assert_eq!(world.level.sectors[1].special, 0);
+        assert_eq!(world.level.sectors[2].special, 0);
+
+        // Closes after 30 seconds
+        tick(&mut world, 30 * 35);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(64));
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(62));
+
+        // Opens after 5 minutes
+        tick(&mut world, 5 * 60 * 35 - 30 * 35 - 1);
+        assert_eq!(world.level.sectors[2].ceiling_height, Fixed::from_int(0));
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[2].ceiling_height, Fixed::from_int(2));
+    }
+}
@@ -0,0 +1,1803 @@
+// Port of p_enemy.c. Monster AI, the action functions the monster states
+// call, and the boss brain that spits out monsters at the end of Doom II
+use crate::config::{GameDifficulty, GameType};
+use crate::fixed::{
+    aprox_distance, fixed_mul, point_to_angle, Angle, Fixed, ANG270, ANG90, FRACUNIT,
+};
+use crate::info::mobjinfo::{
+    MOBJINFO, MT_ARACHPLAZ, MT_BABY, MT_BOSSTARGET, MT_BRUISER, MT_BRUISERSHOT, MT_CYBORG,
+    MT_FATSHOT, MT_FATSO, MT_FIRE, MT_HEAD, MT_HEADSHOT, MT_KNIGHT, MT_PAIN, MT_ROCKET,
+    MT_SERGEANT, MT_SHADOWS, MT_SKULL, MT_SMOKE, MT_SPAWNFIRE, MT_SPAWNSHOT, MT_SPIDER, MT_TRACER,
+    MT_TROOP, MT_TROOPSHOT, MT_UNDEAD, MT_VILE,
+};
+use crate::info::states::{S_BRAINEXPLODE1, S_NULL, S_VILE_HEAL1};
+use crate::info::{MobjInfo, MobjType, StateNum};
+use crate::play::blockmap::MAPBLOCKSHIFT;
+use crate::play::doors::DoorType;
+use crate::play::floor::FloorType;
+use crate::play::level::{Line, ML_SOUNDBLOCK, ML_TWOSIDED};
+use crate::play::map::{MELEERANGE, MISSILERANGE};
+use crate::play::mobj::{
+    Mobj, MobjId, FLOATSPEED, MAXRADIUS, MF_AMBUSH, MF_CORPSE, MF_FLOAT, MF_INFLOAT,
+    MF_JUSTATTACKED, MF_JUSTHIT, MF_SHADOW, MF_SHOOTABLE, MF_SKULLFLY, MF_SOLID,
+};
+use crate::play::world::{LevelExit, World};
+use crate::play::MAXPLAYERS;
+use crate::sound::sfx::{
+    SfxNum, SFX_BAREXP, SFX_BGDTH1, SFX_BGDTH2, SFX_BGSIT1, SFX_BGSIT2, SFX_BOSCUB, SFX_BOSDTH,
+    SFX_BOSPIT, SFX_BOSPN, SFX_BOSSIT, SFX_BSPWLK, SFX_CLAW, SFX_FLAME, SFX_FLAMST, SFX_HOOF,
+    SFX_MANATK, SFX_METAL, SFX_NONE, SFX_PDIEHI, SFX_PISTOL, SFX_PLDETH, SFX_PODTH1, SFX_PODTH2,
+    SFX_PODTH3, SFX_POSIT1, SFX_POSIT2, SFX_POSIT3, SFX_SHOTGN, SFX_SKEPCH, SFX_SKESWG, SFX_SLOP,
+    SFX_TELEPT, SFX_VILATK,
+};
+
+// Mobj::move_dir values
+pub const DI_EAST: i32 = 0;
+pub const DI_NORTHEAST: i32 = 1;
+pub const DI_NORTH: i32 = 2;
+pub const DI_NORTHWEST: i32 = 3;
+pub const DI_WEST: i32 = 4;
+pub const DI_SOUTHWEST: i32 = 5;
+pub const DI_SOUTH: i32 = 6;
+pub const DI_SOUTHEAST: i32 = 7;
+pub const DI_NODIR: i32 = 8;
+
+const OPPOSITE: [i32; 9] = [
+    DI_WEST,
+    DI_SOUTHWEST,
+    DI_SOUTH,
+    DI_SOUTHEAST,
+    DI_EAST,
+    DI_NORTHEAST,
+    DI_NORTH,
+    DI_NORTHWEST,
+    DI_NODIR,
+];
+
+const DIAGS: [i32; 4] = [DI_NORTHWEST, DI_NORTHEAST, DI_SOUTHWEST, DI_SOUTHEAST];
+
+const XSPEED: [i32; 8] = [FRACUNIT, 47000, 0, -47000, -FRACUNIT, -47000, 0, 47000];
+const YSPEED: [i32; 8] = [0, 47000, FRACUNIT, 47000, 0, -47000, -FRACUNIT, -47000];
+
+const SKULLSPEED: Fixed = Fixed(20 * FRACUNIT);
+const TRACEANGLE: u32 = 0xc000000;
+const FATSPREAD: u32 = ANG90 / 8;
+
+// The braintargets globals, the spots the boss brain shoots cubes at. easy
+// was a static in A_BrainSpit that makes it skip every other shot on the
+// easy skills
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BossBrain {
+    pub targets: Vec<MobjId>,
+    pub target_on: usize,
+    pub easy: bool,
+}
+
+impl World {
+    // P_RecursiveSound. Called by P_NoiseAlert. Recursively traverse
+    // adjacent sectors, sound blocking lines cut off traversal
+    fn recursive_sound(&mut self, sector: usize, sound_blocks: i32, sound_target: MobjId) {
+        let valid_count: i32 = self.valid_count;
+        let sec = &mut self.level.sectors[sector];
+
+        // Wake up all monsters in this sector
+        if sec.valid_count == valid_count && sec.sound_traversed <= sound_blocks + 1 {
+            // Already flooded
+            return;
+        }
+
+        sec.valid_count = valid_count;
+        sec.sound_traversed = sound_blocks + 1;
+        sec.sound_target = Some(sound_target);
+
+        for index in 0..self.level.sectors[sector].lines.len() {
+            let line: usize = self.level.sectors[sector].lines[index];
+            let check: &Line = &self.level.lines[line];
+
+            if check.flags & ML_TWOSIDED == 0 {
+                continue;
+            }
+
+            let (flags, front, back): (i32, Option<usize>, Option<usize>) =
+                (check.flags, check.front_sector, check.back_sector);
+
+            if self.level.line_opening(line).range <= Fixed::ZERO {
+                // Closed door
+                continue;
+            }
+
+            let other: usize = if front == Some(sector) {
+                back.unwrap()
+            } else {
+                front.unwrap()
+            };
+
+            if flags & ML_SOUNDBLOCK != 0 {
+                if sound_blocks == 0 {
+                    self.recursive_sound(other, 1, sound_target);
+                }
+            } else {
+                self.recursive_sound(other, sound_blocks, sound_target);
+            }
+        }
+    }
+
+    // P_NoiseAlert. If a monster yells at a player, it will alert other
+    // monsters to the player
+    pub fn noise_alert(&mut self, target: MobjId, emitter: MobjId) {
+        self.valid_count += 1;
+        let sector: usize = self.level.subsectors[self.thinkers.mobj(emitter).subsector].sector;
+        self.recursive_sound(sector, 0, target);
+    }
+
+    // The mobj's target if it's still around. The original kept pointing
+    // at freed mobjs
+    fn mobj_target(&self, id: MobjId) -> Option<MobjId> {
+        self.thinkers
+            .mobj(id)
+            .target
+            .filter(|target| self.thinkers.get_mobj(*target).is_some())
+    }
+
+    // (P_Random() - P_Random()) << shift, used to spread out shots
+    fn random_spread(&mut self, shift: u32) -> Angle {
+        let random: i32 = self.random.p_random();
+        Angle(((random - self.random.p_random()) << shift) as u32)
+    }
+
+    // P_CheckMeleeRange
+    fn check_melee_range(&mut self, id: MobjId) -> bool {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return false,
+        };
+
+        let actor: &Mobj = self.thinkers.mobj(id);
+        let pl: &Mobj = self.thinkers.mobj(target);
+        let dist: Fixed = aprox_distance(pl.x - actor.x, pl.y - actor.y);
+
+        if dist >= MELEERANGE - Fixed(20 * FRACUNIT) + pl.info().radius {
+            return false;
+        }
+
+        self.check_sight(id, target)
+    }
+
+    // P_CheckMissileRange
+    fn check_missile_range(&mut self, id: MobjId) -> bool {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return false,
+        };
+
+        if !self.check_sight(id, target) {
+            return false;
+        }
+
+        let (target_x, target_y): (Fixed, Fixed) = {
+            let target: &Mobj = self.thinkers.mobj(target);
+            (target.x, target.y)
+        };
+        let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+
+        if actor.flags & MF_JUSTHIT != 0 {
+            // The target just hit the enemy, so fight back!
+            actor.flags &= !MF_JUSTHIT;
+            return true;
+        }
+
+        if actor.reaction_time != 0 {
+            // Do not attack yet
+            return false;
+        }
+
+        let mut dist: Fixed =
+            aprox_distance(actor.x - target_x, actor.y - target_y) - Fixed(64 * FRACUNIT);
+
+        if actor.info().melee_state == S_NULL {
+            // No melee attack, so fire more
+            dist -= Fixed(128 * FRACUNIT);
+        }
+
+        let mut dist: i32 = dist.0 >> 16;
+        let mobj_type: MobjType = actor.mobj_type;
+
+        if mobj_type == MT_VILE && dist > 14 * 64 {
+            // Too far away
+            return false;
+        }
+
+        if mobj_type == MT_UNDEAD {
+            if dist < 196 {
+                // Close for fist attack
+                return false;
+            }
+            dist >>= 1;
+        }
+
+        if mobj_type == MT_CYBORG || mobj_type == MT_SPIDER || mobj_type == MT_SKULL {
+            dist >>= 1;
+        }
+
+        dist = dist.min(200);
+
+        if mobj_type == MT_CYBORG && dist > 160 {
+            dist = 160;
+        }
+
+        self.random.p_random() >= dist
+    }
+
+    // P_Move. Move in the current direction, returns false if the move is
+    // blocked
+    fn monster_move(&mut self, id: MobjId) -> bool {
+        let actor: &Mobj = self.thinkers.mobj(id);
+
+        if actor.move_dir == DI_NODIR {
+            return false;
+        }
+
+        if !(0..8).contains(&actor.move_dir) {
+            panic!("Weird actor->movedir!");
+        }
+
+        let dir: usize = actor.move_dir as usize;
+        let speed: i32 = actor.info().speed;
+        let try_x: Fixed = actor.x + Fixed(speed.wrapping_mul(XSPEED[dir]));
+        let try_y: Fixed = actor.y + Fixed(speed.wrapping_mul(YSPEED[dir]));
+
+        if !self.try_move(id, try_x, try_y) {
+            // Open any specials
+            let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+            if actor.flags & MF_FLOAT != 0 && self.position_check.float_ok {
+                // Must adjust height
+                if actor.z < self.position_check.floor_z {
+                    actor.z += FLOATSPEED;
+                } else {
+                    actor.z -= FLOATSPEED;
+                }
+
+                actor.flags |= MF_INFLOAT;
+                return true;
+            }
+
+            if self.position_check.spec_hit.is_empty() {
+                return false;
+            }
+
+            actor.move_dir = DI_NODIR;
+            let mut good: bool = false;
+            let spec_hit: Vec<usize> = std::mem::take(&mut self.position_check.spec_hit);
+
+            for line in spec_hit.into_iter().rev() {
+                // If the special is not a door that can be opened, return
+                // false
+                if self.use_special_line(id, line, 0) {
+                    good = true;
+                }
+            }
+
+            return good;
+        }
+
+        let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+        actor.flags &= !MF_INFLOAT;
+
+        if actor.flags & MF_FLOAT == 0 {
+            actor.z = actor.floor_z;
+        }
+
+        true
+    }
+
+    // P_TryWalk. Attempts to move actor on in its current (ob->moveangle)
+    // direction. If blocked by either a wall or an actor returns false. If
+    // move is either clear or blocked only by a door, returns true and sets.
+    // If a door is in the way, an OpenDoor call is made to start it opening
+    fn try_walk(&mut self, id: MobjId) -> bool {
+        if !self.monster_move(id) {
+            return false;
+        }
+
+        let random: i32 = self.random.p_random();
+        self.thinkers.mobj_mut(id).move_count = random & 15;
+        true
+    }
+
+    fn try_walk_dir(&mut self, id: MobjId, dir: i32) -> bool {
+        self.thinkers.mobj_mut(id).move_dir = dir;
+        self.try_walk(id)
+    }
+
+    // P_NewChaseDir
+    pub fn new_chase_dir(&mut self, id: MobjId) {
+        let actor: &Mobj = self.thinkers.mobj(id);
+        let target: &Mobj = match actor
+            .target
+            .and_then(|target| self.thinkers.get_mobj(target))
+        {
+            Some(target) => target,
+            None => panic!("P_NewChaseDir: called with no target"),
+        };
+
+        let old_dir: i32 = actor.move_dir;
+        let turnaround: i32 = OPPOSITE[old_dir as usize];
+
+        let delta_x: Fixed = target.x - actor.x;
+        let delta_y: Fixed = target.y - actor.y;
+
+        let mut d1: i32 = if delta_x > Fixed(10 * FRACUNIT) {
+            DI_EAST
+        } else if delta_x < Fixed(-10 * FRACUNIT) {
+            DI_WEST
+        } else {
+            DI_NODIR
+        };
+
+        let mut d2: i32 = if delta_y < Fixed(-10 * FRACUNIT) {
+            DI_SOUTH
+        } else if delta_y > Fixed(10 * FRACUNIT) {
+            DI_NORTH
+        } else {
+            DI_NODIR
+        };
+
+        // Try direct route
+        if d1 != DI_NODIR && d2 != DI_NODIR {
+            let index: usize =
+                (usize::from(delta_y < Fixed::ZERO) << 1) + usize::from(delta_x > Fixed::ZERO);
+            self.thinkers.mobj_mut(id).move_dir = DIAGS[index];
+
+            if DIAGS[index] != turnaround && self.try_walk(id) {
+                return;
+            }
+        }
+
+        // Try other directions
+        if self.random.p_random() > 200 || delta_y.abs() > delta_x.abs() {
+            std::mem::swap(&mut d1, &mut d2);
+        }
+
+        if d1 == turnaround {
+            d1 = DI_NODIR;
+        }
+        if d2 == turnaround {
+            d2 = DI_NODIR;
+        }
+
+        if d1 != DI_NODIR && self.try_walk_dir(id, d1) {
+            return;
+        }
+
+        if d2 != DI_NODIR && self.try_walk_dir(id, d2) {
+            return;
+        }
+
+        // There is no direct path to the player, so pick another direction
+        if old_dir != DI_NODIR && self.try_walk_dir(id, old_dir) {
+            return;
+        }
+
+        // Randomly determine direction of search
+        if self.random.p_random() & 1 != 0 {
+            for dir in DI_EAST..=DI_SOUTHEAST {
+                if dir != turnaround && self.try_walk_dir(id, dir) {
+                    return;
+                }
+            }
+        } else {
+            for dir in (DI_EAST..=DI_SOUTHEAST).rev() {
+                if dir != turnaround && self.try_walk_dir(id, dir) {
+                    return;
+                }
+            }
+        }
+
+        if turnaround != DI_NODIR && self.try_walk_dir(id, turnaround) {
+            return;
+        }
+
+        // Can not move
+        self.thinkers.mobj_mut(id).move_dir = DI_NODIR;
+    }
+
+    // The checks in P_LookForPlayers for one player. Returns their mobj if
+    // they can be targeted
+    fn look_at_player(&mut self, id: MobjId, player: usize, all_around: bool) -> Option<MobjId> {
+        if self.players[player].health <= 0 {
+            // Dead
+            return None;
+        }
+
+        let mo: MobjId = self.players[player].mo?;
+        if !self.check_sight(id, mo) {
+            // Out of sight
+            return None;
+        }
+
+        if !all_around {
+            let actor: &Mobj = self.thinkers.mobj(id);
+            let player_mo: &Mobj = self.thinkers.mobj(mo);
+            let an: Angle =
+                point_to_angle(actor.x, actor.y, player_mo.x, player_mo.y) - actor.angle;
+
+            if an.0 > ANG90 && an.0 < ANG270 {
+                let dist: Fixed = aprox_distance(player_mo.x - actor.x, player_mo.y - actor.y);
+
+                // If real close, react anyway
+                if dist > MELEERANGE {
+                    // Behind back
+                    return None;
+                }
+            }
+        }
+
+        Some(mo)
+    }
+
+    // P_LookForPlayers. If allaround is false, only look 180 degrees in
+    // front. Returns true if a player is targeted
+    pub fn look_for_players(&mut self, id: MobjId, all_around: bool) -> bool {
+        let mut checked: i32 = 0;
+        let stop: i32 = (self.thinkers.mobj(id).last_look - 1) & 3;
+
+        loop {
+            let last_look: i32 = self.thinkers.mobj(id).last_look;
+            let player: usize = last_look as usize;
+
+            if self.settings.players_in_game[player] {
+                if checked == 2 || last_look == stop {
+                    // Done looking
+                    return false;
+                }
+                checked += 1;
+
+                if let Some(mo) = self.look_at_player(id, player, all_around) {
+                    self.thinkers.mobj_mut(id).target = Some(mo);
+                    return true;
+                }
+            }
+
+            self.thinkers.mobj_mut(id).last_look = (last_look + 1) & 3;
+        }
+    }
+
+    // A_KeenDie. DOOM II special, map 32. Uses special tag 666
+    pub fn a_keen_die(&mut self, id: MobjId) {
+        self.a_fall(id);
+
+        // Scan the remaining thinkers to see if all Keens are dead
+        let mobj_type: MobjType = self.thinkers.mobj(id).mobj_type;
+        if self.other_alive(id, mobj_type) {
+            // Other Keen not dead
+            return;
+        }
+
+        self.do_tagged_door(666, DoorType::Open);
+    }
+
+    fn other_alive(&self, id: MobjId, mobj_type: MobjType) -> bool {
+        self.thinkers
+            .mobjs()
+            .any(|(other, mo2)| other != id && mo2.mobj_type == mobj_type && mo2.health > 0)
+    }
+
+    // A_Look. Stay in state until a player is sighted
+    pub fn a_look(&mut self, id: MobjId) {
+        let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+        // Any shot will wake up
+        actor.threshold = 0;
+
+        let sector: usize = self.level.subsectors[actor.subsector].sector;
+        let targ: Option<MobjId> = self.level.sectors[sector].sound_target.filter(|targ| {
+            self.thinkers
+                .get_mobj(*targ)
+                .is_some_and(|targ| targ.flags & MF_SHOOTABLE != 0)
+        });
+
+        let see_you: bool = match targ {
+            Some(targ) => {
+                let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+                actor.target = Some(targ);
+                actor.flags & MF_AMBUSH == 0 || self.check_sight(id, targ)
+            }
+            None => false,
+        };
+
+        if !see_you && !self.look_for_players(id, false) {
+            return;
+        }
+
+        // Go into chase state
+        let actor: &Mobj = self.thinkers.mobj(id);
+        let info: &MobjInfo = actor.info();
+        let mobj_type: MobjType = actor.mobj_type;
+
+        if info.see_sound != SFX_NONE {
+            let sound: SfxNum = match info.see_sound {
+                SFX_POSIT1 | SFX_POSIT2 | SFX_POSIT3 => {
+                    SFX_POSIT1 + (self.random.p_random() % 3) as usize
+                }
+                SFX_BGSIT1 | SFX_BGSIT2 => SFX_BGSIT1 + (self.random.p_random() % 2) as usize,
+                sound => sound,
+            };
+
+            if mobj_type == MT_SPIDER || mobj_type == MT_CYBORG {
+                // Full volume
+                self.start_sound(None, sound);
+            } else {
+                self.start_sound(Some(id), sound);
+            }
+        }
+
+        self.set_mobj_state(id, info.see_state);
+    }
+
+    // A_Chase. Actor has a melee attack, so it tries to close as fast as
+    // possible
+    pub fn a_chase(&mut self, id: MobjId) {
+        let target: Option<(Fixed, i32, u32)> = self
+            .thinkers
+            .mobj(id)
+            .target
+            .and_then(|target| self.thinkers.get_mobj(target))
+            .map(|target| (target.x, target.health, target.flags));
+        let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+
+        if actor.reaction_time != 0 {
+            actor.reaction_time -= 1;
+        }
+
+        // Modify target threshold
+        if actor.threshold != 0 {
+            if target.is_none_or(|(_x, health, _flags)| health <= 0) {
+                actor.threshold = 0;
+            } else {
+                actor.threshold -= 1;
+            }
+        }
+
+        // Turn towards movement direction if not there yet
+        if actor.move_dir < 8 {
+            actor.angle = Angle(actor.angle.0 & (7 << 29));
+            let delta: i32 = actor.angle.0.wrapping_sub((actor.move_dir as u32) << 29) as i32;
+
+            if delta > 0 {
+                actor.angle -= Angle(ANG90 / 2);
+            } else if delta < 0 {
+                actor.angle += Angle(ANG90 / 2);
+            }
+        }
+
+        if target.is_none_or(|(_x, _health, flags)| flags & MF_SHOOTABLE == 0) {
+            // Look for a new target
+            if self.look_for_players(id, true) {
+                // Got a new target
+                return;
+            }
+
+            let spawn_state: StateNum = self.thinkers.mobj(id).info().spawn_state;
+            self.set_mobj_state(id, spawn_state);
+            return;
+        }
+
+        // Do not attack twice in a row
+        if actor.flags & MF_JUSTATTACKED != 0 {
+            actor.flags &= !MF_JUSTATTACKED;
+            if !self.settings.fast_monsters() {
+                self.new_chase_dir(id);
+            }
+            return;
+        }
+
+        let info: &MobjInfo = actor.info();
+
+        // Check for melee attack
+        if info.melee_state != S_NULL && self.check_melee_range(id) {
+            self.start_sound(Some(id), info.attack_sound);
+            self.set_mobj_state(id, info.melee_state);
+            return;
+        }
+
+        // Check for missile attack
+        if info.missile_state != S_NULL
+            && (self.settings.fast_monsters() || self.thinkers.mobj(id).move_count == 0)
+            && self.check_missile_range(id)
+        {
+            self.set_mobj_state(id, info.missile_state);
+            self.thinkers.mobj_mut(id).flags |= MF_JUSTATTACKED;
+            return;
+        }
+
+        // Possibly choose another target
+        let actor: &Mobj = self.thinkers.mobj(id);
+        if self.settings.netgame
+            && actor.threshold == 0
+            && !self.check_sight(id, actor.target.unwrap())
+            && self.look_for_players(id, true)
+        {
+            // Got a new target
+            return;
+        }
+
+        // Chase towards player
+        let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+        actor.move_count -= 1;
+        if actor.move_count < 0 || !self.monster_move(id) {
+            self.new_chase_dir(id);
+        }
+
+        // Make active sound
+        if info.active_sound != SFX_NONE && self.random.p_random() < 3 {
+            self.start_sound(Some(id), info.active_sound);
+        }
+    }
+
+    // A_FaceTarget
+    pub fn a_face_target(&mut self, id: MobjId) {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return,
+        };
+
+        let target: &Mobj = self.thinkers.mobj(target);
+        let (target_x, target_y, target_flags): (Fixed, Fixed, u32) =
+            (target.x, target.y, target.flags);
+
+        let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+        actor.flags &= !MF_AMBUSH;
+        actor.angle = point_to_angle(actor.x, actor.y, target_x, target_y);
+
+        if target_flags & MF_SHADOW != 0 {
+            let spread: Angle = self.random_spread(21);
+            self.thinkers.mobj_mut(id).angle += spread;
+        }
+    }
+
+    // A_PosAttack
+    pub fn a_pos_attack(&mut self, id: MobjId) {
+        if self.mobj_target(id).is_none() {
+            return;
+        }
+
+        self.a_face_target(id);
+        let angle: Angle = self.thinkers.mobj(id).angle;
+        let slope: Fixed = self.aim_line_attack(id, angle, MISSILERANGE);
+
+        self.start_sound(Some(id), SFX_PISTOL);
+        let angle: Angle = angle + self.random_spread(20);
+        let damage: i32 = (self.random.p_random() % 5 + 1) * 3;
+        self.line_attack(id, angle, MISSILERANGE, slope, damage);
+    }
+
+    // A_SPosAttack
+    pub fn a_spos_attack(&mut self, id: MobjId) {
+        if self.mobj_target(id).is_none() {
+            return;
+        }
+
+        self.start_sound(Some(id), SFX_SHOTGN);
+        self.a_face_target(id);
+        let bangle: Angle = self.thinkers.mobj(id).angle;
+        let slope: Fixed = self.aim_line_attack(id, bangle, MISSILERANGE);
+
+        for _ in 0..3 {
+            let angle: Angle = bangle + self.random_spread(20);
+            let damage: i32 = (self.random.p_random() % 5 + 1) * 3;
+            self.line_attack(id, angle, MISSILERANGE, slope, damage);
+        }
+    }
+
+    // A_CPosAttack
+    pub fn a_cpos_attack(&mut self, id: MobjId) {
+        if self.mobj_target(id).is_none() {
+            return;
+        }
+
+        self.start_sound(Some(id), SFX_SHOTGN);
+        self.a_face_target(id);
+        let bangle: Angle = self.thinkers.mobj(id).angle;
+        let slope: Fixed = self.aim_line_attack(id, bangle, MISSILERANGE);
+
+        let angle: Angle = bangle + self.random_spread(20);
+        let damage: i32 = (self.random.p_random() % 5 + 1) * 3;
+        self.line_attack(id, angle, MISSILERANGE, slope, damage);
+    }
+
+    // A_CPosRefire and A_SpidRefire. Keep firing unless target got out of
+    // sight
+    fn refire(&mut self, id: MobjId, chance: i32) {
+        self.a_face_target(id);
+
+        if self.random.p_random() < chance {
+            return;
+        }
+
+        let keep_firing: bool = match self.mobj_target(id) {
+            Some(target) => self.thinkers.mobj(target).health > 0 && self.check_sight(id, target),
+            None => false,
+        };
+
+        if !keep_firing {
+            let see_state: StateNum = self.thinkers.mobj(id).info().see_state;
+            self.set_mobj_state(id, see_state);
+        }
+    }
+
+    // A_CPosRefire
+    pub fn a_cpos_refire(&mut self, id: MobjId) {
+        self.refire(id, 40);
+    }
+
+    // A_SpidRefire
+    pub fn a_spid_refire(&mut self, id: MobjId) {
+        self.refire(id, 10);
+    }
+
+    // A_BspiAttack
+    pub fn a_bspi_attack(&mut self, id: MobjId) {
+        self.missile_attack(id, MT_ARACHPLAZ);
+    }
+
+    // Faces the target and launches a missile at it
+    fn missile_attack(&mut self, id: MobjId, mobj_type: MobjType) {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return,
+        };
+
+        self.a_face_target(id);
+        self.spawn_missile(id, target, mobj_type);
+    }
+
+    // Melee damage to the target if it's in range
+    fn melee_attack(&mut self, id: MobjId, sound: SfxNum, damage: impl Fn(i32) -> i32) -> bool {
+        if !self.check_melee_range(id) {
+            return false;
+        }
+
+        self.start_sound(Some(id), sound);
+        let random: i32 = self.random.p_random();
+        let target: MobjId = self.thinkers.mobj(id).target.unwrap();
+        self.damage_mobj(target, Some(id), Some(id), damage(random));
+        true
+    }
+
+    // A_TroopAttack
+    pub fn a_troop_attack(&mut self, id: MobjId) {
+        if self.mobj_target(id).is_none() {
+            return;
+        }
+
+        self.a_face_target(id);
+        if self.melee_attack(id, SFX_CLAW, |random| (random % 8 + 1) * 3) {
+            return;
+        }
+
+        // Launch a missile
+        self.missile_attack(id, MT_TROOPSHOT);
+    }
+
+    // A_SargAttack
+    pub fn a_sarg_attack(&mut self, id: MobjId) {
+        if self.mobj_target(id).is_none() {
+            return;
+        }
+
+        self.a_face_target(id);
+        self.melee_attack(id, SFX_NONE, |random| (random % 10 + 1) * 4);
+    }
+
+    // A_HeadAttack
+    pub fn a_head_attack(&mut self, id: MobjId) {
+        if self.mobj_target(id).is_none() {
+            return;
+        }
+
+        self.a_face_target(id);
+        if self.melee_attack(id, SFX_NONE, |random| (random % 6 + 1) * 10) {
+            return;
+        }
+
+        // Launch a missile
+        self.missile_attack(id, MT_HEADSHOT);
+    }
+
+    // A_CyberAttack
+    pub fn a_cyber_attack(&mut self, id: MobjId) {
+        self.missile_attack(id, MT_ROCKET);
+    }
+
+    // A_BruisAttack
+    pub fn a_bruis_attack(&mut self, id: MobjId) {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return,
+        };
+
+        if self.melee_attack(id, SFX_CLAW, |random| (random % 8 + 1) * 10) {
+            return;
+        }
+
+        // Launch a missile
+        self.spawn_missile(id, target, MT_BRUISERSHOT);
+    }
+
+    // A_SkelMissile
+    pub fn a_skel_missile(&mut self, id: MobjId) {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return,
+        };
+
+        self.a_face_target(id);
+        // So missile spawns higher
+        self.thinkers.mobj_mut(id).z += Fixed(16 * FRACUNIT);
+        let mo: MobjId = self.spawn_missile(id, target, MT_TRACER);
+        // Back to normal
+        self.thinkers.mobj_mut(id).z -= Fixed(16 * FRACUNIT);
+
+        // Moved on without relinking like the original
+        let missile: &mut Mobj = self.thinkers.mobj_mut(mo);
+        missile.x += missile.mom_x;
+        missile.y += missile.mom_y;
+        missile.tracer = Some(target);
+    }
+
+    // A_Tracer. Steers revenant missiles towards their tracer every 4 tics
+    pub fn a_tracer(&mut self, id: MobjId) {
+        if self.game_tic & 3 != 0 {
+            return;
+        }
+
+        // Spawn a puff of smoke behind the rocket
+        let actor: &Mobj = self.thinkers.mobj(id);
+        let (x, y, z): (Fixed, Fixed, Fixed) = (actor.x, actor.y, actor.z);
+        let (mom_x, mom_y): (Fixed, Fixed) = (actor.mom_x, actor.mom_y);
+        self.spawn_puff(x, y, z);
+
+        let th: MobjId = self.spawn_mobj(x - mom_x, y - mom_y, z, MT_SMOKE);
+        self.thinkers.mobj_mut(th).mom_z = Fixed::ONE;
+        self.randomize_tics(th);
+
+        // Adjust direction
+        let dest: (Fixed, Fixed, Fixed) = match self
+            .thinkers
+            .mobj(id)
+            .tracer
+            .and_then(|tracer| self.thinkers.get_mobj(tracer))
+            .filter(|dest| dest.health > 0)
+        {
+            Some(dest) => (dest.x, dest.y, dest.z),
+            None => return,
+        };
+        let (dest_x, dest_y, dest_z): (Fixed, Fixed, Fixed) = dest;
+
+        // Change angle
+        let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+        let exact: Angle = point_to_angle(actor.x, actor.y, dest_x, dest_y);
+
+        if exact != actor.angle {
+            if (exact - actor.angle).0 > 0x80000000 {
+                actor.angle -= Angle(TRACEANGLE);
+                if (exact - actor.angle).0 < 0x80000000 {
+                    actor.angle = exact;
+                }
+            } else {
+                actor.angle += Angle(TRACEANGLE);
+                if (exact - actor.angle).0 > 0x80000000 {
+                    actor.angle = exact;
+                }
+            }
+        }
+
+        let speed: i32 = actor.info().speed;
+        actor.mom_x = fixed_mul(Fixed(speed), actor.angle.cos());
+        actor.mom_y = fixed_mul(Fixed(speed), actor.angle.sin());
+
+        // Change slope
+        let dist: i32 = (aprox_distance(dest_x - actor.x, dest_y - actor.y).0 / speed).max(1);
+        let slope: Fixed = Fixed((dest_z + Fixed(40 * FRACUNIT) - actor.z).0 / dist);
+
+        if slope < actor.mom_z {
+            actor.mom_z -= Fixed(FRACUNIT / 8);
+        } else {
+            actor.mom_z += Fixed(FRACUNIT / 8);
+        }
+    }
+
+    // A_SkelWhoosh
+    pub fn a_skel_whoosh(&mut self, id: MobjId) {
+        if self.mobj_target(id).is_none() {
+            return;
+        }
+
+        self.a_face_target(id);
+        self.start_sound(Some(id), SFX_SKESWG);
+    }
+
+    // A_SkelFist
+    pub fn a_skel_fist(&mut self, id: MobjId) {
+        if self.mobj_target(id).is_none() {
+            return;
+        }
+
+        self.a_face_target(id);
+        self.melee_attack(id, SFX_SKEPCH, |random| (random % 10 + 1) * 6);
+    }
+
+    // PIT_VileCheck. Detect a corpse that could be raised
+    fn vile_check(
+        &mut self,
+        id: MobjId,
+        try_x: Fixed,
+        try_y: Fixed,
+        corpse_hit: &mut Option<MobjId>,
+    ) -> bool {
+        let thing: &Mobj = self.thinkers.mobj(id);
+
+        if thing.flags & MF_CORPSE == 0 {
+            // Not a monster
+            return true;
+        }
+
+        if thing.tics != -1 {
+            // Not lying still yet
+            return true;
+        }
+
+        if thing.info().raise_state == S_NULL {
+            // Monster doesn't have a raise state
+            return true;
+        }
+
+        let max_dist: Fixed = thing.info().radius + MOBJINFO[MT_VILE].radius;
+
+        if (thing.x - try_x).abs() > max_dist || (thing.y - try_y).abs() > max_dist {
+            // Not actually touching
+            return true;
+        }
+
+        *corpse_hit = Some(id);
+        let thing: &mut Mobj = self.thinkers.mobj_mut(id);
+        thing.mom_x = Fixed::ZERO;
+        thing.mom_y = Fixed::ZERO;
+        thing.height = Fixed(thing.height.0 << 2);
+        let (x, y): (Fixed, Fixed) = (thing.x, thing.y);
+
+        let check: bool = self.check_position(id, x, y);
+
+        let thing: &mut Mobj = self.thinkers.mobj_mut(id);
+        thing.height = Fixed(thing.height.0 >> 2);
+
+        // Got one if it fits here, so stop checking
+        !check
+    }
+
+    // A_VileChase. Check for ressurecting a body
+    pub fn a_vile_chase(&mut self, id: MobjId) {
+        let actor: &Mobj = self.thinkers.mobj(id);
+
+        if actor.move_dir != DI_NODIR {
+            // Check for corpses to raise
+            let dir: usize = actor.move_dir as usize;
+            let speed: i32 = actor.info().speed;
+            let try_x: Fixed = actor.x + Fixed(speed.wrapping_mul(XSPEED[dir]));
+            let try_y: Fixed = actor.y + Fixed(speed.wrapping_mul(YSPEED[dir]));
+
+            let org_x: Fixed = self.level.blockmap.org_x;
+            let org_y: Fixed = self.level.blockmap.org_y;
+            let reach: Fixed = Fixed(MAXRADIUS.0 * 2);
+            let xl: i32 = (try_x - org_x - reach).0 >> MAPBLOCKSHIFT;
+            let xh: i32 = (try_x - org_x + reach).0 >> MAPBLOCKSHIFT;
+            let yl: i32 = (try_y - org_y - reach).0 >> MAPBLOCKSHIFT;
+            let yh: i32 = (try_y - org_y + reach).0 >> MAPBLOCKSHIFT;
+
+            for block_x in xl..=xh {
+                for block_y in yl..=yh {
+                    // Call PIT_VileCheck to check whether object is a corpse
+                    // that can be raised
+                    let mut corpse_hit: Option<MobjId> = None;
+                    if !self.block_things_iterator(block_x, block_y, |world, thing| {
+                        world.vile_check(thing, try_x, try_y, &mut corpse_hit)
+                    }) {
+                        // Got one!
+                        self.raise_corpse(id, corpse_hit.unwrap());
+                        return;
+                    }
+                }
+            }
+        }
+
+        // Return to normal attack
+        self.a_chase(id);
+    }
+
+    // The end of A_VileChase once a corpse is found
+    fn raise_corpse(&mut self, id: MobjId, corpse: MobjId) {
+        let temp: Option<MobjId> = self.thinkers.mobj(id).target;
+        self.thinkers.mobj_mut(id).target = Some(corpse);
+        self.a_face_target(id);
+        self.thinkers.mobj_mut(id).target = temp;
+
+        self.set_mobj_state(id, S_VILE_HEAL1);
+        self.start_sound(Some(corpse), SFX_SLOP);
+
+        let info: &MobjInfo = self.thinkers.mobj(corpse).info();
+        self.set_mobj_state(corpse, info.raise_state);
+
+        let corpse: &mut Mobj = self.thinkers.mobj_mut(corpse);
+        corpse.height = Fixed(corpse.height.0 << 2);
+        corpse.flags = info.flags;
+        corpse.health = info.spawn_health;
+        corpse.target = None;
+    }
+
+    // A_VileStart
+    pub fn a_vile_start(&mut self, id: MobjId) {
+        self.start_sound(Some(id), SFX_VILATK);
+    }
+
+    // A_StartFire
+    pub fn a_start_fire(&mut self, id: MobjId) {
+        self.start_sound(Some(id), SFX_FLAMST);
+        self.a_fire(id);
+    }
+
+    // A_FireCrackle
+    pub fn a_fire_crackle(&mut self, id: MobjId) {
+        self.start_sound(Some(id), SFX_FLAME);
+        self.a_fire(id);
+    }
+
+    // A_Fire. Keep fire in front of player unless out of sight
+    pub fn a_fire(&mut self, id: MobjId) {
+        let actor: &Mobj = self.thinkers.mobj(id);
+        let exists = |mobj: &MobjId| self.thinkers.get_mobj(*mobj).is_some();
+        let (dest, vile): (MobjId, MobjId) =
+            match (actor.tracer.filter(exists), actor.target.filter(exists)) {
+                (Some(dest), Some(vile)) => (dest, vile),
+                _ => return,
+            };
+
+        // Don't move it if the vile lost sight
+        if !self.check_sight(vile, dest) {
+            return;
+        }
+
+        let dest: &Mobj = self.thinkers.mobj(dest);
+        let (dest_x, dest_y, dest_z, an): (Fixed, Fixed, Fixed, Angle) =
+            (dest.x, dest.y, dest.z, dest.angle);
+
+        self.unset_thing_position(id);
+        let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+        actor.x = dest_x + fixed_mul(Fixed(24 * FRACUNIT), an.cos());
+        actor.y = dest_y + fixed_mul(Fixed(24 * FRACUNIT), an.sin());
+        actor.z = dest_z;
+        self.set_thing_position(id);
+    }
+
+    // A_VileTarget. Spawn the hellfire
+    pub fn a_vile_target(&mut self, id: MobjId) {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return,
+        };
+
+        self.a_face_target(id);
+
+        // The fire starts at the target's x for both x and y like the
+        // original, A_Fire moves it to the right place
+        let target_mobj: &Mobj = self.thinkers.mobj(target);
+        let (x, z): (Fixed, Fixed) = (target_mobj.x, target_mobj.z);
+        let fog: MobjId = self.spawn_mobj(x, x, z, MT_FIRE);
+
+        self.thinkers.mobj_mut(id).tracer = Some(fog);
+        let fog_mobj: &mut Mobj = self.thinkers.mobj_mut(fog);
+        fog_mobj.target = Some(id);
+        fog_mobj.tracer = Some(target);
+        self.a_fire(fog);
+    }
+
+    // A_VileAttack
+    pub fn a_vile_attack(&mut self, id: MobjId) {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return,
+        };
+
+        self.a_face_target(id);
+
+        if !self.check_sight(id, target) {
+            return;
+        }
+
+        self.start_sound(Some(id), SFX_BAREXP);
+        self.damage_mobj(target, Some(id), Some(id), 20);
+        let target_mobj: &mut Mobj = self.thinkers.mobj_mut(target);
+        target_mobj.mom_z = Fixed(1000 * FRACUNIT / target_mobj.info().mass);
+        let (target_x, target_y): (Fixed, Fixed) = (target_mobj.x, target_mobj.y);
+
+        let actor: &Mobj = self.thinkers.mobj(id);
+        let an: Angle = actor.angle;
+        let fire: MobjId = match actor
+            .tracer
+            .filter(|fire| self.thinkers.get_mobj(*fire).is_some())
+        {
+            Some(fire) => fire,
+            None => return,
+        };
+
+        // Move the fire between the vile and the player
+        let fire_mobj: &mut Mobj = self.thinkers.mobj_mut(fire);
+        fire_mobj.x = target_x - fixed_mul(Fixed(24 * FRACUNIT), an.cos());
+        fire_mobj.y = target_y - fixed_mul(Fixed(24 * FRACUNIT), an.sin());
+        self.radius_attack(fire, Some(id), 70);
+    }
+
+    // Mancubus attack, firing three missiles (bruisers) in three different
+    // directions? Doesn't look like it
+    pub fn a_fat_raise(&mut self, id: MobjId) {
+        self.a_face_target(id);
+        self.start_sound(Some(id), SFX_MANATK);
+    }
+
+    // Turns a missile and sends it off in the new direction
+    fn turn_missile(&mut self, id: MobjId, turn: Angle) {
+        let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+        mo.angle += turn;
+        let speed: i32 = mo.info().speed;
+        mo.mom_x = fixed_mul(Fixed(speed), mo.angle.cos());
+        mo.mom_y = fixed_mul(Fixed(speed), mo.angle.sin());
+    }
+
+    // A_FatAttack1
+    pub fn a_fat_attack1(&mut self, id: MobjId) {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return,
+        };
+
+        self.a_face_target(id);
+        // Change direction to ...
+        self.thinkers.mobj_mut(id).angle += Angle(FATSPREAD);
+        self.spawn_missile(id, target, MT_FATSHOT);
+
+        let mo: MobjId = self.spawn_missile(id, target, MT_FATSHOT);
+        self.turn_missile(mo, Angle(FATSPREAD));
+    }
+
+    // A_FatAttack2
+    pub fn a_fat_attack2(&mut self, id: MobjId) {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return,
+        };
+
+        self.a_face_target(id);
+        // Now here choose opposite deviation
+        self.thinkers.mobj_mut(id).angle -= Angle(FATSPREAD);
+        self.spawn_missile(id, target, MT_FATSHOT);
+
+        let mo: MobjId = self.spawn_missile(id, target, MT_FATSHOT);
+        self.turn_missile(mo, -Angle(FATSPREAD * 2));
+    }
+
+    // A_FatAttack3
+    pub fn a_fat_attack3(&mut self, id: MobjId) {
+        let target: MobjId = match self.mobj_target(id) {
+            Some(target) => target,
+            None => return,
+        };
+
+        self.a_face_target(id);
+
+        let mo: MobjId = self.spawn_missile(id, target, MT_FATSHOT);
+        self.turn_missile(mo, -Angle(FATSPREAD / 2));
+
+        let mo: MobjId = self.spawn_missile(id, target, MT_FATSHOT);
+        self.turn_missile(mo, Angle(FATSPREAD / 2));
+    }
+
+    // A_SkullAttack. Fly at the player like a missile
+    pub fn a_skull_attack(&mut self, id: MobjId) {
+        let dest: MobjId = match self.mobj_target(id) {
+            Some(dest) => dest,
+            None => return,
+        };
+
+        let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+        actor.flags |= MF_SKULLFLY;
+        let attack_sound: SfxNum = actor.info().attack_sound;
+
+        self.start_sound(Some(id), attack_sound);
+        self.a_face_target(id);
+
+        let dest: &Mobj = self.thinkers.mobj(dest);
+        let (dest_x, dest_y, dest_z, dest_height): (Fixed, Fixed, Fixed, Fixed) =
+            (dest.x, dest.y, dest.z, dest.height);
+
+        let actor: &mut Mobj = self.thinkers.mobj_mut(id);
+        actor.mom_x = fixed_mul(SKULLSPEED, actor.angle.cos());
+        actor.mom_y = fixed_mul(SKULLSPEED, actor.angle.sin());
+
+        let dist: i32 =
+            (aprox_distance(dest_x - actor.x, dest_y - actor.y).0 / SKULLSPEED.0).max(1);
+        actor.mom_z = Fixed((dest_z + Fixed(dest_height.0 >> 1) - actor.z).0 / dist);
+    }
+
+    // A_PainShootSkull. Spawn a lost soul and launch it at the target
+    fn pain_shoot_skull(&mut self, id: MobjId, angle: Angle) {
+        // Count total number of skull currently on the level
+        let count: usize = self
+            .thinkers
+            .mobjs()
+            .filter(|(_id, mobj)| mobj.mobj_type == MT_SKULL)
+            .count();
+
+        // If there are allready 20 skulls on the level, don't spit another
+        // one
+        if count > 20 {
+            return;
+        }
+
+        // Okay, there's playe for another one
+        let actor: &Mobj = self.thinkers.mobj(id);
+        let prestep: Fixed =
+            Fixed(4 * FRACUNIT + 3 * (actor.info().radius.0 + MOBJINFO[MT_SKULL].radius.0) / 2);
+        let x: Fixed = actor.x + fixed_mul(prestep, angle.cos());
+        let y: Fixed = actor.y + fixed_mul(prestep, angle.sin());
+        let z: Fixed = actor.z + Fixed(8 * FRACUNIT);
+        let target: Option<MobjId> = actor.target;
+
+        let new_mobj: MobjId = self.spawn_mobj(x, y, z, MT_SKULL);
+
+        // Check for movements
+        if !self.try_move(new_mobj, x, y) {
+            // Kill it immediately
+            self.damage_mobj(new_mobj, Some(id), Some(id), 10000);
+            return;
+        }
+
+        self.thinkers.mobj_mut(new_mobj).target = target;
+        self.a_skull_attack(new_mobj);
+    }
+
+    // A_PainAttack. Spawn a lost soul and launch it at the target
+    pub fn a_pain_attack(&mut self, id: MobjId) {
+        if self.mobj_target(id).is_none() {
+            return;
+        }
+
+        self.a_face_target(id);
+        let angle: Angle = self.thinkers.mobj(id).angle;
+        self.pain_shoot_skull(id, angle);
+    }
+
+    // A_PainDie
+    pub fn a_pain_die(&mut self, id: MobjId) {
+        self.a_fall(id);
+
+        let angle: Angle = self.thinkers.mobj(id).angle;
+        self.pain_shoot_skull(id, angle + Angle::ANG90);
+        self.pain_shoot_skull(id, angle + Angle::ANG180);
+        self.pain_shoot_skull(id, angle + Angle::ANG270);
+    }
+
+    // A_Scream
+    pub fn a_scream(&mut self, id: MobjId) {
+        let actor: &Mobj = self.thinkers.mobj(id);
+        let mobj_type: MobjType = actor.mobj_type;
+
+        let sound: SfxNum = match actor.info().death_sound {
+            SFX_NONE => return,
+            SFX_PODTH1 | SFX_PODTH2 | SFX_PODTH3 => {
+                SFX_PODTH1 + (self.random.p_random() % 3) as usize
+            }
+            SFX_BGDTH1 | SFX_BGDTH2 => SFX_BGDTH1 + (self.random.p_random() % 2) as usize,
+            sound => sound,
+        };
+
+        // Check for bosses
+        if mobj_type == MT_SPIDER || mobj_type == MT_CYBORG {
+            // Full volume
+            self.start_sound(None, sound);
+        } else {
+            self.start_sound(Some(id), sound);
+        }
+    }
+
+    // A_XScream
+    pub fn a_xscream(&mut self, id: MobjId) {
+        self.start_sound(Some(id), SFX_SLOP);
+    }
+
+    // A_Pain
+    pub fn a_pain(&mut self, id: MobjId) {
+        let pain_sound: SfxNum = self.thinkers.mobj(id).info().pain_sound;
+        self.start_sound(Some(id), pain_sound);
+    }
+
+    // A_Fall
+    pub fn a_fall(&mut self, id: MobjId) {
+        // Actor is on ground, it can be walked over
+        self.thinkers.mobj_mut(id).flags &= !MF_SOLID;
+
+        // So change this if corpse objects are meant to be obstacles
+    }
+
+    // A_Explode
+    pub fn a_explode(&mut self, id: MobjId) {
+        let source: Option<MobjId> = self.mobj_target(id);
+        self.radius_attack(id, source, 128);
+    }
+
+    // A_BossDeath. Possibly trigger special effects if on first boss level
+    pub fn a_boss_death(&mut self, id: MobjId) {
+        let mobj_type: MobjType = self.thinkers.mobj(id).mobj_type;
+        let (episode, map): (i32, i32) = (self.settings.episode, self.settings.map);
+        let commercial: bool = self.settings.game_type == GameType::DoomII;
+
+        let boss: bool = if commercial {
+            map == 7 && (mobj_type == MT_FATSO || mobj_type == MT_BABY)
+        } else {
+            match episode {
+                1 => map == 8 && mobj_type == MT_BRUISER,
+                2 => map == 8 && mobj_type == MT_CYBORG,
+                3 => map == 8 && mobj_type == MT_SPIDER,
+                4 => (map == 6 && mobj_type == MT_CYBORG) || (map == 8 && mobj_type == MT_SPIDER),
+                _ => map == 8,
+            }
+        };
+
+        if !boss {
+            return;
+        }
+
+        // Make sure there is a player alive for victory
+        if !(0..MAXPLAYERS)
+            .any(|player| self.settings.players_in_game[player] && self.players[player].health > 0)
+        {
+            // No one left alive, so do not end game
+            return;
+        }
+
+        // Scan the remaining thinkers to see if all bosses are dead
+        if self.other_alive(id, mobj_type) {
+            // Other boss not dead
+            return;
+        }
+
+        // Victory!
+        if commercial {
+            if mobj_type == MT_FATSO {
+                self.do_tagged_floor(666, None, FloorType::LowerFloorToLowest);
+                return;
+            }
+
+            if mobj_type == MT_BABY {
+                self.do_tagged_floor(667, None, FloorType::RaiseToTexture);
+                return;
+            }
+        } else {
+            match (episode, map) {
+                (1, _) | (4, 8) => {
+                    self.do_tagged_floor(666, None, FloorType::LowerFloorToLowest);
+                    return;
+                }
+                (4, 6) => {
+                    self.do_tagged_door(666, DoorType::BlazeOpen);
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        self.exit_level(LevelExit::Normal);
+    }
+
+    // A_Hoof
+    pub fn a_hoof(&mut self, id: MobjId) {
+        self.start_sound(Some(id), SFX_HOOF);
+        self.a_chase(id);
+    }
+
+    // A_Metal
+    pub fn a_metal(&mut self, id: MobjId) {
+        self.start_sound(Some(id), SFX_METAL);
+        self.a_chase(id);
+    }
+
+    // A_BabyMetal
+    pub fn a_baby_metal(&mut self, id: MobjId) {
+        self.start_sound(Some(id), SFX_BSPWLK);
+        self.a_chase(id);
+    }
+
+    // A_BrainAwake. Find all the target spots
+    pub fn a_brain_awake(&mut self, _id: MobjId) {
+        self.brain.targets = self
+            .thinkers
+            .mobjs()
+            .filter(|(_id, mobj)| mobj.mobj_type == MT_BOSSTARGET)
+            .map(|(id, _mobj)| id)
+            .collect();
+        self.brain.target_on = 0;
+
+        self.start_sound(None, SFX_BOSSIT);
+    }
+
+    // A_BrainPain
+    pub fn a_brain_pain(&mut self, _id: MobjId) {
+        self.start_sound(None, SFX_BOSPN);
+    }
+
+    // The rockets that go off when the boss brain dies
+    fn brain_explosion(&mut self, x: Fixed, y: Fixed, z: Fixed) {
+        let th: MobjId = self.spawn_mobj(x, y, z, MT_ROCKET);
+        let random: i32 = self.random.p_random();
+        self.thinkers.mobj_mut(th).mom_z = Fixed(random * 512);
+
+        self.set_mobj_state(th, S_BRAINEXPLODE1);
+
+        let random: i32 = self.random.p_random();
+        let th: &mut Mobj = self.thinkers.mobj_mut(th);
+        th.tics -= random & 7;
+        if th.tics < 1 {
+            th.tics = 1;
+        }
+    }
+
+    // A_BrainScream
+    pub fn a_brain_scream(&mut self, id: MobjId) {
+        let mo: &Mobj = self.thinkers.mobj(id);
+        let (mo_x, mo_y): (Fixed, Fixed) = (mo.x, mo.y);
+
+        let mut x: Fixed = mo_x - Fixed(196 * FRACUNIT);
+        while x < mo_x + Fixed(320 * FRACUNIT) {
+            let y: Fixed = mo_y - Fixed(320 * FRACUNIT);
+            let z: Fixed = Fixed(128 + self.random.p_random() * 2 * FRACUNIT);
+            self.brain_explosion(x, y, z);
+            x += Fixed(FRACUNIT * 8);
+        }
+
+        self.start_sound(None, SFX_BOSDTH);
+    }
+
+    // A_BrainExplode
+    pub fn a_brain_explode(&mut self, id: MobjId) {
+        let mo: &Mobj = self.thinkers.mobj(id);
+        let (mo_x, mo_y): (Fixed, Fixed) = (mo.x, mo.y);
+
+        let random: i32 = self.random.p_random();
+        let x: Fixed = mo_x + Fixed((random - self.random.p_random()) * 2048);
+        let z: Fixed = Fixed(128 + self.random.p_random() * 2 * FRACUNIT);
+        self.brain_explosion(x, mo_y, z);
+    }
+
+    // A_BrainDie
+    pub fn a_brain_die(&mut self, _id: MobjId) {
+        self.exit_level(LevelExit::Normal);
+    }
+
+    // A_BrainSpit
+    pub fn a_brain_spit(&mut self, id: MobjId) {
+        self.brain.easy = !self.brain.easy;
+        if matches!(
+            self.settings.skill,
+            GameDifficulty::Baby | GameDifficulty::Easy
+        ) && !self.brain.easy
+        {
+            return;
+        }
+
+        if self.brain.targets.is_empty() {
+            panic!("A_BrainSpit: numbraintargets was 0 (vanilla crashes here)");
+        }
+
+        // Shoot a cube at current target
+        let targ: MobjId = self.brain.targets[self.brain.target_on];
+        self.brain.target_on = (self.brain.target_on + 1) % self.brain.targets.len();
+
+        // Spawn brain missile
+        let new_mobj: MobjId = self.spawn_missile(id, targ, MT_SPAWNSHOT);
+        let targ_y: Fixed = self.thinkers.mobj(targ).y;
+        let mo_y: Fixed = self.thinkers.mobj(id).y;
+
+        let new_mobj: &mut Mobj = self.thinkers.mobj_mut(new_mobj);
+        new_mobj.target = Some(targ);
+        new_mobj.reaction_time = ((targ_y - mo_y).0 / new_mobj.mom_y.0) / new_mobj.state().tics;
+
+        self.start_sound(None, SFX_BOSPIT);
+    }
+
+    // A_SpawnSound. Travelling cube sound
+    pub fn a_spawn_sound(&mut self, id: MobjId) {
+        self.start_sound(Some(id), SFX_BOSCUB);
+        self.a_spawn_fly(id);
+    }
+
+    // A_SpawnFly
+    pub fn a_spawn_fly(&mut self, id: MobjId) {
+        let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+        mo.reaction_time -= 1;
+        if mo.reaction_time != 0 {
+            // Still flying
+            return;
+        }
+
+        let targ: MobjId = mo.target.unwrap();
+        let targ: &Mobj = self.thinkers.mobj(targ);
+        let (x, y, z): (Fixed, Fixed, Fixed) = (targ.x, targ.y, targ.z);
+
+        // First spawn teleport fog
+        let fog: MobjId = self.spawn_mobj(x, y, z, MT_SPAWNFIRE);
+        self.start_sound(Some(fog), SFX_TELEPT);
+
+        // Randomly select monster to spawn. Probability distribution (kind
+        // of :), decreasing likelihood
+        let mobj_type: MobjType = match self.random.p_random() {
+            0..=49 => MT_TROOP,
+            50..=89 => MT_SERGEANT,
+            90..=119 => MT_SHADOWS,
+            120..=129 => MT_PAIN,
+            130..=159 => MT_HEAD,
+            160..=161 => MT_VILE,
+            162..=171 => MT_UNDEAD,
+            172..=191 => MT_BABY,
+            192..=221 => MT_FATSO,
+            222..=245 => MT_KNIGHT,
+            _ => MT_BRUISER,
+        };
+
+        let new_mobj: MobjId = self.spawn_mobj(x, y, z, mobj_type);
+        if self.look_for_players(new_mobj, true) {
+            let see_state: StateNum = self.thinkers.mobj(new_mobj).info().see_state;
+            self.set_mobj_state(new_mobj, see_state);
+        }
+
+        // Telefrag anything in this spot
+        self.teleport_move(new_mobj, x, y);
+
+        // Remove self (i.e., cube)
+        self.remove_mobj(id);
+    }
+
+    // A_PlayerScream
+    pub fn a_player_scream(&mut self, id: MobjId) {
+        // Default death sound
+        let sound: SfxNum =
+            if self.settings.game_type == GameType::DoomII && self.thinkers.mobj(id).health < -50 {
+                // If the player dies less than -50% without gibbing
+                SFX_PDIEHI
+            } else {
+                SFX_PLDETH
+            };
+
+        self.start_sound(Some(id), sound);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GameType;
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::info::mobjinfo::{
+        MT_BOSSBRAIN, MT_BOSSTARGET, MT_BRUISER, MT_CYBORG, MT_PAIN, MT_SKULL, MT_SPAWNSHOT,
+        MT_TROOP, MT_TROOPSHOT,
+    };
+    use crate::info::states::{S_TROO_RUN1, S_TROO_STND};
+    use crate::info::MobjType;
+    use crate::play::enemy::{DI_NODIR, DI_WEST};
+    use crate::play::mobj::{MobjId, MF_SKULLFLY, ONFLOORZ};
+    use crate::play::test_map::{TestMap, TestRoom};
+    use crate::play::world::{LevelExit, World, WorldSettings};
+
+    // One room with the player at the west end
+    fn single_room() -> TestMap {
+        TestMap::new().room(512, 0, 128).thing(64, 128, 0, 1, 7)
+    }
+
+    fn spawn(world: &mut World, x: i32, y: i32, mobj_type: MobjType) -> MobjId {
+        world.spawn_mobj(
+            Fixed(x * FRACUNIT),
+            Fixed(y * FRACUNIT),
+            ONFLOORZ,
+            mobj_type,
+        )
+    }
+
+    fn find(world: &World, mobj_type: MobjType) -> Vec<MobjId> {
+        world
+            .thinkers
+            .mobjs()
+            .filter(|(_id, mobj)| mobj.mobj_type == mobj_type)
+            .map(|(id, _mobj)| id)
+            .collect()
+    }
+
+    #[test]
+    fn test_noise_alert_wakes_monsters_in_adjacent_sectors() {
+        let mut world: World = TestMap::new()
+            .room(512, 0, 128)
+            .room(512, 0, 128)
+            .thing(64, 128, 0, 1, 7)
+            .thing(800, 128, 0, 3001, 7)
+            .to_world_with(WorldSettings::default());
+        let player: MobjId = world.players[0].mo.unwrap();
+        let troop: MobjId = find(&world, MT_TROOP)[0];
+
+        world.noise_alert(player, player);
+        assert_eq!(world.level.sectors[1].sound_target, Some(player));
+
+        // Facing away but it heard the shot
+        world.a_look(troop);
+        assert_eq!(world.thinkers.mobj(troop).target, Some(player));
+        assert_ne!(world.thinkers.mobj(troop).state, S_TROO_STND);
+    }
+
+    #[test]
+    fn test_look_only_sees_players_in_front() {
+        let mut world: World = single_room()
+            .thing(400, 100, 0, 3001, 7)
+            .thing(400, 160, 180, 3001, 7)
+            .to_world_with(WorldSettings::default());
+        let troops: Vec<MobjId> = find(&world, MT_TROOP);
+
+        world.a_look(troops[0]);
+        assert_eq!(world.thinkers.mobj(troops[0]).target, None);
+        assert_eq!(world.thinkers.mobj(troops[0]).state, S_TROO_STND);
+
+        world.a_look(troops[1]);
+        assert_eq!(world.thinkers.mobj(troops[1]).target, world.players[0].mo);
+        assert_eq!(world.thinkers.mobj(troops[1]).state, S_TROO_RUN1);
+    }
+
+    #[test]
+    fn test_new_chase_dir_walks_towards_target() {
+        let mut world: World = single_room().to_world_with(WorldSettings::default());
+        let troop: MobjId = spawn(&mut world, 400, 128, MT_TROOP);
+        world.thinkers.mobj_mut(troop).target = world.players[0].mo;
+        // West would be turning around from the spawn direction of east
+        world.thinkers.mobj_mut(troop).move_dir = DI_NODIR;
+
+        world.new_chase_dir(troop);
+
+        assert_eq!(world.thinkers.mobj(troop).move_dir, DI_WEST);
+        assert!(world.thinkers.mobj(troop).x < Fixed(400 * FRACUNIT));
+    }
+
+    #[test]
+    #[should_panic(expected = "P_NewChaseDir: called with no target")]
+    fn test_new_chase_dir_panics_without_target() {
+        let mut world: World = single_room().to_world_with(WorldSettings::default());
+        let troop: MobjId = spawn(&mut world, 400, 128, MT_TROOP);
+
+        world.new_chase_dir(troop);
+    }
+
+    #[test]
+    fn test_troop_attack_throws_fireball_from_afar() {
+        let mut world: World = single_room().to_world_with(WorldSettings::default());
+        let troop: MobjId = spawn(&mut world, 400, 128, MT_TROOP);
+        world.thinkers.mobj_mut(troop).target = world.players[0].mo;
+
+        world.a_troop_attack(troop);
+
+        let shots: Vec<MobjId> = find(&world, MT_TROOPSHOT);
+        assert_eq!(shots.len(), 1);
+        assert_eq!(world.thinkers.mobj(shots[0]).target, Some(troop));
+        assert!(world.thinkers.mobj(shots[0]).mom_x < Fixed::ZERO);
+    }
+
+    #[test]
+    fn test_troop_attack_claws_in_melee_range() {
+        let mut world: World = single_room().to_world_with(WorldSettings::default());
+        let player: MobjId = world.players[0].mo.unwrap();
+        let troop: MobjId = spawn(&mut world, 100, 128, MT_TROOP);
+        world.thinkers.mobj_mut(troop).target = Some(player);
+
+        world.a_troop_attack(troop);
+
+        assert!(find(&world, MT_TROOPSHOT).is_empty());
+        assert!(world.thinkers.mobj(player).health < 100);
+    }
+
+    #[test]
+    fn test_pain_die_spits_three_skulls() {
+        let mut world: World = single_room().to_world_with(WorldSettings::default());
+        let pain: MobjId = spawn(&mut world, 256, 128, MT_PAIN);
+        world.thinkers.mobj_mut(pain).target = world.players[0].mo;
+
+        world.a_pain_die(pain);
+
+        let skulls: Vec<MobjId> = find(&world, MT_SKULL);
+        assert_eq!(skulls.len(), 3);
+        for skull in skulls {
+            assert_ne!(world.thinkers.mobj(skull).flags & MF_SKULLFLY, 0);
+            assert_eq!(world.thinkers.mobj(skull).target, world.players[0].mo);
+        }
+    }
+
+    #[test]
+    fn test_boss_death_waits_for_all_bosses() {
+        let settings: WorldSettings = WorldSettings {
+            game_type: GameType::DoomIRegistered,
+            episode: 1,
+            map: 8,
+            ..Default::default()
+        };
+        let mut world: World = TestMap::new()
+            .room(512, 0, 128)
+            .room_with(TestRoom {
+                width: 128,
+                floor_height: 64,
+                ceiling_height: 128,
+                light_level: 160,
+                tag: 666,
+                ..Default::default()
+            })
+            .thing(64, 128, 0, 1, 7)
+            .to_world_with(settings);
+        let first: MobjId = spawn(&mut world, 200, 64, MT_BRUISER);
+        let second: MobjId = spawn(&mut world, 200, 192, MT_BRUISER);
+
+        world.thinkers.mobj_mut(first).health = 0;
+        world.a_boss_death(first);
+        assert_eq!(world.level.sectors[1].special_data, None);
+
+        world.thinkers.mobj_mut(second).health = 0;
+        world.a_boss_death(second);
+        assert!(world.level.sectors[1].special_data.is_some());
+        assert_eq!(world.level_exit, None);
+    }
+
+    #[test]
+    fn test_boss_death_exits_level_with_a_live_player() {
+        let settings: WorldSettings = WorldSettings {
+            game_type: GameType::DoomIRegistered,
+            episode: 2,
+            map: 8,
+            ..Default::default()
+        };
+        let mut world: World = single_room().to_world_with(settings);
+        let cyborg: MobjId = spawn(&mut world, 300, 128, MT_CYBORG);
+        world.thinkers.mobj_mut(cyborg).health = 0;
+
+        world.players[0].health = 0;
+        world.a_boss_death(cyborg);
+        assert_eq!(world.level_exit, None);
+
+        world.players[0].health = 1;
+        world.a_boss_death(cyborg);
+        assert_eq!(world.level_exit, Some(LevelExit::Normal));
+    }
+
+    #[test]
+    fn test_brain_spit_cycles_through_targets() {
+        let mut world: World = single_room().to_world_with(WorldSettings::default());
+        let brain: MobjId = spawn(&mut world, 256, 32, MT_BOSSBRAIN);
+        let first: MobjId = spawn(&mut world, 200, 200, MT_BOSSTARGET);
+        let second: MobjId = spawn(&mut world, 300, 200, MT_BOSSTARGET);
+
+        world.a_brain_awake(brain);
+        assert_eq!(world.brain.targets, vec![first, second]);
+
+        world.a_brain_spit(brain);
+        let cubes: Vec<MobjId> = find(&world, MT_SPAWNSHOT);
+        assert_eq!(cubes.len(), 1);
+        assert_eq!(world.thinkers.mobj(cubes[0]).target, Some(first));
+        assert!(world.thinkers.mobj(cubes[0]).reaction_time > 0);
+        assert_eq!(world.brain.target_on, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "A_BrainSpit: numbraintargets was 0")]
+    fn test_brain_spit_panics_without_targets() {
+        let mut world: World = single_room().to_world_with(WorldSettings::default());
+        let brain: MobjId = spawn(&mut world, 256, 32, MT_BOSSBRAIN);
+
+        world.a_brain_awake(brain);
+        world.a_brain_spit(brain);
+    }
+}
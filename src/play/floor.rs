@@ -0,0 +1,776 @@
+// Port of p_floor.c. Moving floors and stairs, and T_MovePlane which
+// everything that moves a floor or ceiling goes through
+use crate::fixed::{Fixed, FRACUNIT};
+use crate::play::level::{names_equal, Line, Sector, ML_TWOSIDED};
+use crate::play::thinker::{Thinker, ThinkerId};
+use crate::play::world::World;
+use crate::sound::sfx::{SFX_PSTOP, SFX_STNMOV};
+
+pub const FLOORSPEED: Fixed = Fixed(FRACUNIT);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveResult {
+    Ok,
+    Crushed,
+    PastDest,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Plane {
+    Floor,
+    Ceiling,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloorType {
+    // Lower floor to highest surrounding floor
+    LowerFloor,
+    // Lower floor to lowest surrounding floor
+    LowerFloorToLowest,
+    // Lower floor to highest surrounding floor VERY FAST
+    TurboLower,
+    // Raise floor to lowest surrounding CEILING
+    RaiseFloor,
+    // Raise floor to next highest surrounding floor
+    RaiseFloorToNearest,
+    // Raise floor to shortest height texture around it
+    RaiseToTexture,
+    // Lower floor to lowest surrounding floor and change floorpic
+    LowerAndChange,
+    RaiseFloor24,
+    RaiseFloor24AndChange,
+    RaiseFloorCrush,
+    // Raise to next highest floor, turbo-speed
+    RaiseFloorTurbo,
+    DonutRaise,
+    RaiseFloor512,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StairType {
+    // Slowly build by 8
+    Build8,
+    // Quickly build by 16
+    Turbo16,
+}
+
+// floormove_t
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FloorMove {
+    pub floor_type: FloorType,
+    pub crush: bool,
+    pub sector: usize,
+    pub direction: i32,
+    pub new_special: i32,
+    pub texture: [u8; 8],
+    pub floor_dest_height: Fixed,
+    pub speed: Fixed,
+}
+
+fn plane_height(sector: &mut Sector, plane: Plane) -> &mut Fixed {
+    match plane {
+        Plane::Floor => &mut sector.floor_height,
+        Plane::Ceiling => &mut sector.ceiling_height,
+    }
+}
+
+impl World {
+    // T_MovePlane. Move a plane (floor or ceiling) and check for crushing
+    pub fn move_plane(
+        &mut self,
+        sector: usize,
+        speed: Fixed,
+        dest: Fixed,
+        crush: bool,
+        plane: Plane,
+        direction: i32,
+    ) -> MoveResult {
+        let last_pos: Fixed = *plane_height(&mut self.level.sectors[sector], plane);
+
+        // Going past the destination stops at it. Getting stuck on the way
+        // puts the plane back, but still counts as having got there
+        let past_dest: bool = if direction == 1 {
+            last_pos + speed > dest
+        } else {
+            last_pos - speed < dest
+        };
+        if past_dest {
+            *plane_height(&mut self.level.sectors[sector], plane) = dest;
+            if self.change_sector(sector, crush) {
+                *plane_height(&mut self.level.sectors[sector], plane) = last_pos;
+                self.change_sector(sector, crush);
+            }
+            return MoveResult::PastDest;
+        }
+
+        *plane_height(&mut self.level.sectors[sector], plane) = if direction == 1 {
+            last_pos + speed
+        } else {
+            last_pos - speed
+        };
+
+        if !self.change_sector(sector, crush) {
+            return MoveResult::Ok;
+        }
+
+        match (plane, direction) {
+            // Ceilings going up don't care what's in the way
+            (Plane::Ceiling, 1) => MoveResult::Ok,
+            // Floors going down only get stuck on things wedged under the
+            // ceiling
+            (Plane::Floor, -1) => {
+                *plane_height(&mut self.level.sectors[sector], plane) = last_pos;
+                self.change_sector(sector, crush);
+                MoveResult::Crushed
+            }
+            // Could get crushed
+            _ => {
+                if !crush {
+                    *plane_height(&mut self.level.sectors[sector], plane) = last_pos;
+                    self.change_sector(sector, crush);
+                }
+                MoveResult::Crushed
+            }
+        }
+    }
+
+    // T_MoveFloor. Move a floor to its destination (up or down)
+    pub fn move_floor_thinker(&mut self, id: ThinkerId) {
+        let floor: FloorMove = match self.thinkers.get(id) {
+            Some(Thinker::Floor(floor)) => *floor,
+            _ => return,
+        };
+
+        let res: MoveResult = self.move_plane(
+            floor.sector,
+            floor.speed,
+            floor.floor_dest_height,
+            floor.crush,
+            Plane::Floor,
+            floor.direction,
+        );
+
+        if self.level_time & 7 == 0 {
+            self.start_sector_sound(floor.sector, SFX_STNMOV);
+        }
+
+        if res == MoveResult::PastDest {
+            let sector: &mut Sector = &mut self.level.sectors[floor.sector];
+            sector.special_data = None;
+
+            let change: bool = match floor.direction {
+                1 => floor.floor_type == FloorType::DonutRaise,
+                -1 => floor.floor_type == FloorType::LowerAndChange,
+                _ => false,
+            };
+            if change {
+                sector.special = floor.new_special;
+                sector.floor_pic = floor.texture;
+            }
+
+            self.thinkers.remove(id);
+            self.start_sector_sound(floor.sector, SFX_PSTOP);
+        }
+    }
+
+    // Adds a floor thinker and makes it the sector's special data
+    pub(crate) fn add_floor_move(&mut self, floor: FloorMove) {
+        let id: ThinkerId = self.thinkers.add(Thinker::Floor(floor));
+        self.level.sectors[floor.sector].special_data = Some(id);
+    }
+
+    // EV_DoFloor. Handle floor types
+    pub fn do_floor(&mut self, line: usize, floor_type: FloorType) -> bool {
+        let ld: &Line = &self.level.lines[line];
+        self.do_tagged_floor(ld.tag, ld.front_sector, floor_type)
+    }
+
+    // EV_DoFloor without a real line, the way A_BossDeath calls it with a
+    // made up line that only has a tag. The front sector is only looked at
+    // by RaiseFloor24AndChange
+    pub fn do_tagged_floor(
+        &mut self,
+        tag: i32,
+        front_sector: Option<usize>,
+        floor_type: FloorType,
+    ) -> bool {
+        let mut rtn: bool = false;
+
+        for sector in self.level.sectors_with_tag(tag) {
+            let sec: &Sector = &self.level.sectors[sector];
+
+            // ALREADY MOVING? IF SO, KEEP GOING...
+            if sec.special_data.is_some() {
+                continue;
+            }
+
+            // New floor thinker
+            rtn = true;
+            let mut floor: FloorMove = FloorMove {
+                floor_type,
+                crush: false,
+                sector,
+                direction: 1,
+                new_special: 0,
+                texture: [0; 8],
+                floor_dest_height: Fixed::ZERO,
+                speed: FLOORSPEED,
+            };
+
+            match floor_type {
+                FloorType::LowerFloor => {
+                    floor.direction = -1;
+                    floor.floor_dest_height = self.level.find_highest_floor_surrounding(sector);
+                }
+                FloorType::LowerFloorToLowest => {
+                    floor.direction = -1;
+                    floor.floor_dest_height = self.level.find_lowest_floor_surrounding(sector);
+                }
+                FloorType::TurboLower => {
+                    floor.direction = -1;
+                    floor.speed = Fixed(FLOORSPEED.0 * 4);
+                    floor.floor_dest_height = self.level.find_highest_floor_surrounding(sector);
+                    if floor.floor_dest_height != sec.floor_height {
+                        floor.floor_dest_height += Fixed(8 * FRACUNIT);
+                    }
+                }
+                FloorType::RaiseFloorCrush | FloorType::RaiseFloor => {
+                    floor.crush = floor_type == FloorType::RaiseFloorCrush;
+                    floor.floor_dest_height = self
+                        .level
+                        .find_lowest_ceiling_surrounding(sector)
+                        .min(sec.ceiling_height);
+                    if floor.crush {
+                        floor.floor_dest_height -= Fixed(8 * FRACUNIT);
+                    }
+                }
+                FloorType::RaiseFloorTurbo => {
+                    floor.speed = Fixed(FLOORSPEED.0 * 4);
+                    floor.floor_dest_height =
+                        self.level.find_next_highest_floor(sector, sec.floor_height);
+                }
+                FloorType::RaiseFloorToNearest => {
+                    floor.floor_dest_height =
+                        self.level.find_next_highest_floor(sector, sec.floor_height);
+                }
+                FloorType::RaiseFloor24 => {
+                    floor.floor_dest_height = sec.floor_height + Fixed(24 * FRACUNIT);
+                }
+                FloorType::RaiseFloor512 => {
+                    floor.floor_dest_height = sec.floor_height + Fixed(512 * FRACUNIT);
+                }
+                FloorType::RaiseFloor24AndChange => {
+                    floor.floor_dest_height = sec.floor_height + Fixed(24 * FRACUNIT);
+                    let front: &Sector = &self.level.sectors[front_sector.unwrap()];
+                    let (floor_pic, special): ([u8; 8], i32) = (front.floor_pic, front.special);
+                    let sec: &mut Sector = &mut self.level.sectors[sector];
+                    sec.floor_pic = floor_pic;
+                    sec.special = special;
+                }
+                FloorType::RaiseToTexture => {
+                    floor.floor_dest_height = Fixed(
+                        sec.floor_height
+                            .0
+                            .wrapping_add(self.shortest_lower_texture(sector)),
+                    );
+                }
+                FloorType::LowerAndChange => {
+                    floor.direction = -1;
+                    floor.floor_dest_height = self.level.find_lowest_floor_surrounding(sector);
+                    floor.texture = sec.floor_pic;
+                    self.find_floor_change(sector, &mut floor);
+                }
+                FloorType::DonutRaise => {}
+            }
+
+            self.add_floor_move(floor);
+        }
+
+        rtn
+    }
+
+    // The height of the shortest lower texture on the sector's two sided
+    // lines. Lines without one use texture 0 like the original, sectors
+    // without any give INT_MAX
+    fn shortest_lower_texture(&self, sector: usize) -> i32 {
+        let mut min_size: i32 = i32::MAX;
+
+        for line in self.level.sectors[sector].lines.iter() {
+            let line: &Line = &self.level.lines[*line];
+            if line.flags & ML_TWOSIDED == 0 {
+                continue;
+            }
+
+            for side in line.side_num.iter().flatten() {
+                let texture: &[u8; 8] = &self.level.sides[*side].bottom_texture;
+                let height: Option<Fixed> = if texture[0] == b'-' {
+                    self.level
+                        .texture_heights
+                        .first()
+                        .map(|(_name, height)| *height)
+                } else {
+                    self.level
+                        .texture_heights
+                        .iter()
+                        .find(|(name, _height)| names_equal(name, texture))
+                        .map(|(_name, height)| *height)
+                };
+
+                if let Some(height) = height {
+                    min_size = min_size.min(height.0);
+                }
+            }
+        }
+
+        min_size
+    }
+
+    // Picks the floor flat and special for lowerAndChange from a sector
+    // next to it at the destination height. The original reused its sector
+    // variable for the neighbour, so the loop runs up to the line count of
+    // the last neighbour checked while still indexing the first sector's
+    // lines
+    fn find_floor_change(&self, sector: usize, floor: &mut FloorMove) {
+        let lines: &[usize] = &self.level.sectors[sector].lines;
+        let mut line_count: usize = lines.len();
+        let mut index: usize = 0;
+
+        while index < line_count {
+            let line: &Line = match lines.get(index) {
+                Some(line) => &self.level.lines[*line],
+                None => break,
+            };
+
+            if line.flags & ML_TWOSIDED != 0 {
+                let front: usize = self.level.sides[line.side_num[0].unwrap()].sector;
+                let other: usize = if front == sector {
+                    self.level.sides[line.side_num[1].unwrap()].sector
+                } else {
+                    front
+                };
+                let other_sector: &Sector = &self.level.sectors[other];
+                line_count = other_sector.lines.len();
+
+                if other_sector.floor_height == floor.floor_dest_height {
+                    floor.texture = other_sector.floor_pic;
+                    floor.new_special = other_sector.special;
+                    return;
+                }
+            }
+
+            index += 1;
+        }
+    }
+
+    // EV_BuildStairs. Build a staircase!
+    pub fn build_stairs(&mut self, line: usize, stair_type: StairType) -> bool {
+        let mut rtn: bool = false;
+        let mut secnum: Option<usize> = None;
+
+        while let Some(found) = self.level.find_sector_from_line_tag(line, secnum) {
+            secnum = Some(found);
+            let mut sector: usize = found;
+
+            // ALREADY MOVING? IF SO, KEEP GOING...
+            if self.level.sectors[sector].special_data.is_some() {
+                continue;
+            }
+
+            // New floor thinker
+            rtn = true;
+            let (speed, stair_size): (Fixed, Fixed) = match stair_type {
+                StairType::Build8 => (Fixed(FLOORSPEED.0 / 4), Fixed(8 * FRACUNIT)),
+                StairType::Turbo16 => (Fixed(FLOORSPEED.0 * 4), Fixed(16 * FRACUNIT)),
+            };
+
+            // The original left crush uninitialized, stairs can't crush
+            // here
+            let stair = |sector: usize, height: Fixed| FloorMove {
+                floor_type: FloorType::LowerFloor,
+                crush: false,
+                sector,
+                direction: 1,
+                new_special: 0,
+                texture: [0; 8],
+                floor_dest_height: height,
+                speed,
+            };
+
+            let mut height: Fixed = self.level.sectors[sector].floor_height + stair_size;
+            self.add_floor_move(stair(sector, height));
+            let texture: [u8; 8] = self.level.sectors[sector].floor_pic;
+
+            // Find next sector to raise
+            // 1. Find 2-sided line with same sector side[0]
+            // 2. Other side is the next sector to raise
+            loop {
+                let mut ok: bool = false;
+
+                for line in self.level.sectors[sector].lines.clone() {
+                    let line: &Line = &self.level.lines[line];
+                    if line.flags & ML_TWOSIDED == 0 {
+                        continue;
+                    }
+
+                    if line.front_sector != Some(sector) {
+                        continue;
+                    }
+
+                    let next: usize = line.back_sector.unwrap();
+                    if !names_equal(&self.level.sectors[next].floor_pic, &texture) {
+                        continue;
+                    }
+
+                    height += stair_size;
+
+                    if self.level.sectors[next].special_data.is_some() {
+                        continue;
+                    }
+
+                    sector = next;
+                    secnum = Some(next);
+                    self.add_floor_move(stair(sector, height));
+                    ok = true;
+                    break;
+                }
+
+                if !ok {
+                    break;
+                }
+            }
+        }
+
+        rtn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::Fixed;
+    use crate::info::mobjinfo::MT_TROOP;
+    use crate::play::floor::{FloorType, MoveResult, Plane, StairType, FLOORSPEED};
+    use crate::play::mobj::ONFLOORZ;
+    use crate::play::test_map::{tick, TestMap, TestRoom};
+    use crate::play::world::{SoundOrigin, World};
+    use crate::sound::sfx::{SfxNum, SFX_PSTOP, SFX_STNMOV};
+
+    fn tagged(width: i16, floor_height: i16, ceiling_height: i16) -> TestRoom {
+        TestRoom {
+            width,
+            floor_height,
+            ceiling_height,
+            light_level: 160,
+            tag: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_move_plane_stops_at_destination() {
+        let mut world: World = TestMap::new().room(256, 0, 128).to_world();
+
+        let res: MoveResult = world.move_plane(
+            0,
+            Fixed::from_int(3),
+            Fixed::from_int(5),
+            false,
+            Plane::Floor,
+            1,
+        );
+        assert_eq!(res, MoveResult::Ok);
+        assert_eq!(world.level.sectors[0].floor_height, Fixed::from_int(3));
+
+        let res: MoveResult = world.move_plane(
+            0,
+            Fixed::from_int(3),
+            Fixed::from_int(5),
+            false,
+            Plane::Floor,
+            1,
+        );
+        assert_eq!(res, MoveResult::PastDest);
+        assert_eq!(world.level.sectors[0].floor_height, Fixed::from_int(5));
+
+        let res: MoveResult = world.move_plane(
+            0,
+            Fixed::from_int(100),
+            Fixed::from_int(64),
+            false,
+            Plane::Ceiling,
+            -1,
+        );
+        assert_eq!(res, MoveResult::PastDest);
+        assert_eq!(world.level.sectors[0].ceiling_height, Fixed::from_int(64));
+    }
+
+    #[test]
+    fn test_move_plane_is_blocked_by_things_unless_crushing() {
+        let mut world: World = TestMap::new().room(256, 0, 64).to_world();
+        world.spawn_mobj(
+            Fixed::from_int(128),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+
+        // Imps are 56 high, the ceiling goes back up
+        world.level.sectors[0].ceiling_height = Fixed::from_int(57);
+        let res: MoveResult = world.move_plane(
+            0,
+            Fixed::from_int(2),
+            Fixed::from_int(0),
+            false,
+            Plane::Ceiling,
+            -1,
+        );
+        assert_eq!(res, MoveResult::Crushed);
+        assert_eq!(world.level.sectors[0].ceiling_height, Fixed::from_int(57));
+
+        let res: MoveResult = world.move_plane(
+            0,
+            Fixed::from_int(2),
+            Fixed::from_int(0),
+            true,
+            Plane::Ceiling,
+            -1,
+        );
+        assert_eq!(res, MoveResult::Crushed);
+        assert_eq!(world.level.sectors[0].ceiling_height, Fixed::from_int(55));
+    }
+
+    #[test]
+    fn test_do_floor_lowers_to_highest_surrounding_floor() {
+        let mut world: World = TestMap::new()
+            .room(128, 0, 128)
+            .room_with(tagged(128, 64, 128))
+            .room(128, 16, 128)
+            .line_special(0, 19, 1)
+            .to_world();
+        let line: usize = world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == 1)
+            .unwrap();
+
+        assert!(world.do_floor(line, FloorType::LowerFloor));
+        // Already moving
+        assert!(!world.do_floor(line, FloorType::LowerFloor));
+
+        tick(&mut world, 47);
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(17));
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(16));
+        // Finishes on the tic after it gets there
+        assert!(world.level.sectors[1].special_data.is_some());
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].special_data, None);
+        assert_eq!(world.thinkers.len(), 0);
+    }
+
+    #[test]
+    fn test_do_floor_raise_types() {
+        let raise = |floor_type: FloorType, tics: i32| -> Fixed {
+            let mut world: World = TestMap::new()
+                .room(128, 32, 160)
+                .room_with(tagged(128, 0, 128))
+                .room(128, 96, 200)
+                .line_special(0, 5, 1)
+                .to_world();
+            let line: usize = world
+                .level
+                .lines
+                .iter()
+                .position(|line| line.tag == 1)
+                .unwrap();
+            assert!(world.do_floor(line, floor_type));
+            tick(&mut world, tics);
+            world.level.sectors[1].floor_height
+        };
+
+        // To the lowest ceiling around, which is higher than its own
+        assert_eq!(raise(FloorType::RaiseFloor, 200), Fixed::from_int(128));
+        assert_eq!(raise(FloorType::RaiseFloorCrush, 200), Fixed::from_int(120));
+        assert_eq!(
+            raise(FloorType::RaiseFloorToNearest, 10),
+            Fixed::from_int(10)
+        );
+        assert_eq!(
+            raise(FloorType::RaiseFloorToNearest, 200),
+            Fixed::from_int(32)
+        );
+        assert_eq!(raise(FloorType::RaiseFloorTurbo, 10), Fixed::from_int(32));
+        assert_eq!(raise(FloorType::RaiseFloor24, 200), Fixed::from_int(24));
+        assert_eq!(raise(FloorType::RaiseFloor512, 20), Fixed::from_int(20));
+        assert_eq!(raise(FloorType::LowerFloorToLowest, 10), Fixed::from_int(0));
+        // Lowering to a higher floor jumps straight up to it
+        assert_eq!(raise(FloorType::LowerFloor, 1), Fixed::from_int(96));
+        assert_eq!(raise(FloorType::TurboLower, 1), Fixed::from_int(104));
+    }
+
+    #[test]
+    fn test_do_floor_raise_to_texture_uses_shortest_lower_texture() {
+        let mut world: World = TestMap::new()
+            .room(128, 64, 128)
+            .room_with(tagged(128, 0, 128))
+            .line_special(0, 30, 1)
+            .to_world();
+        world.level.texture_heights = vec![
+            (*b"AASHITTY", Fixed::from_int(64)),
+            (*b"STEP1\0\0\0", Fixed::from_int(16)),
+            (*b"BIGDOOR1", Fixed::from_int(128)),
+        ];
+        let line: usize = world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == 1)
+            .unwrap();
+        let side: usize = world.level.lines[line].side_num[0].unwrap();
+        world.level.sides[side].bottom_texture = *b"step1\0\0\0";
+
+        assert!(world.do_floor(line, FloorType::RaiseToTexture));
+        tick(&mut world, 100);
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(16));
+
+        // "-" on the back is texture 0
+        world.level.sides[side].bottom_texture = *b"BIGDOOR1";
+        assert!(world.do_floor(line, FloorType::RaiseToTexture));
+        tick(&mut world, 100);
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(80));
+    }
+
+    #[test]
+    fn test_do_floor_lower_and_change_takes_neighbours_flat() {
+        let mut world: World = TestMap::new()
+            .room_with(TestRoom {
+                special: 7,
+                ..tagged(128, 0, 128)
+            })
+            .room_with(tagged(128, 48, 128))
+            .line_special(0, 37, 1)
+            .to_world();
+        world.level.sectors[0].tag = 0;
+        world.level.sectors[0].floor_pic = *b"NUKAGE1\0";
+        let line: usize = world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == 1)
+            .unwrap();
+
+        assert!(world.do_floor(line, FloorType::LowerAndChange));
+        tick(&mut world, 48);
+        // Only changes once it's done
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(0));
+        assert_eq!(world.level.sectors[1].special, 0);
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(0));
+        assert_eq!(world.level.sectors[1].floor_pic, *b"NUKAGE1\0");
+        assert_eq!(world.level.sectors[1].special, 7);
+    }
+
+    #[test]
+    fn test_do_floor_raise_24_and_change_copies_front_sector() {
+        let mut world: World = TestMap::new()
+            .room_with(tagged(128, 0, 128))
+            .room(128, 0, 128)
+            .line_special(0, 59, 1)
+            .to_world();
+        world.level.sectors[1].floor_pic = *b"FLAT1\0\0\0";
+        world.level.sectors[1].special = 5;
+        let line: usize = world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == 1)
+            .unwrap();
+
+        assert!(world.do_floor(line, FloorType::RaiseFloor24AndChange));
+        assert_eq!(world.level.sectors[0].floor_pic, *b"FLAT1\0\0\0");
+        assert_eq!(world.level.sectors[0].special, 5);
+        tick(&mut world, 25);
+        assert_eq!(world.level.sectors[0].floor_height, Fixed::from_int(24));
+    }
+
+    #[test]
+    fn test_build_stairs_raises_each_step_higher() {
+        let mut world: World = TestMap::new()
+            .room(64, 0, 256)
+            .room(64, 0, 256)
+            .room(64, 0, 256)
+            .room_with(tagged(64, 0, 256))
+            .room(128, 0, 256)
+            .line_special(0, 7, 1)
+            .to_world();
+        // Each step is the back sector of a line with the step before it in
+        // front, which in the test map is the room to the left
+        for sector in world.level.sectors.iter_mut() {
+            sector.floor_pic = *b"STEP\0\0\0\0";
+        }
+        world.level.sectors[0].floor_pic = *b"FLAT1\0\0\0";
+        let line: usize = world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == 1)
+            .unwrap();
+
+        assert!(world.build_stairs(line, StairType::Build8));
+        assert_eq!(world.thinkers.len(), 3);
+
+        // 8, 16 and 24 high at a quarter unit a tic
+        tick(&mut world, 95);
+        assert_eq!(
+            world.level.sectors[1].floor_height,
+            Fixed::from_int(24) - Fixed(FLOORSPEED.0 / 4)
+        );
+        tick(&mut world, 2);
+        let heights: Vec<Fixed> = world
+            .level
+            .sectors
+            .iter()
+            .map(|sector| sector.floor_height)
+            .collect();
+        assert_eq!(
+            heights,
+            vec![
+                Fixed::from_int(0),
+                Fixed::from_int(24),
+                Fixed::from_int(16),
+                Fixed::from_int(8),
+                Fixed::from_int(0)
+            ]
+        );
+        assert!(world.thinkers.is_empty());
+    }
+
+    #[test]
+    fn test_move_floor_thinker_plays_sounds() {
+        let mut world: World = TestMap::new()
+            .room(128, 0, 128)
+            .room_with(tagged(128, 32, 128))
+            .line_special(0, 19, 1)
+            .to_world();
+        let line: usize = world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == 1)
+            .unwrap();
+        world.do_floor(line, FloorType::LowerFloorToLowest);
+        world.sound_events.clear();
+
+        // Every 8 tics while moving, then the stop when it's done
+        tick(&mut world, 33);
+        let sounds: Vec<SfxNum> = world.sound_events.iter().map(|event| event.sfx).collect();
+        assert_eq!(
+            sounds,
+            vec![SFX_STNMOV, SFX_STNMOV, SFX_STNMOV, SFX_STNMOV, SFX_STNMOV, SFX_PSTOP]
+        );
+        assert!(world
+            .sound_events
+            .iter()
+            .all(|event| event.origin == Some(SoundOrigin::Sector(1))));
+    }
+}
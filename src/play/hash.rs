@@ -0,0 +1,243 @@
+// Hashes of the game state for finding where a demo or netgame went out of
+// sync. Each mobj, sector and the random number index gets its own hash so
+// a mismatch can be narrowed down to the objects that differ
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::fixed::Fixed;
+use crate::play::level::Sector;
+use crate::play::mobj::Mobj;
+use crate::play::world::World;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+// Something in the world that's hashed on its own. Mobjs are numbered in
+// thinker order so the same mobj gets the same number in every run that's
+// still in sync
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StateKey {
+    Random,
+    Mobj(usize),
+    Sector(usize),
+}
+
+impl Display for StateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Random => write!(f, "rng"),
+            Self::Mobj(index) => write!(f, "m{}", index),
+            Self::Sector(index) => write!(f, "s{}", index),
+        }
+    }
+}
+
+impl FromStr for StateKey {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let parse_index = |index: &str| {
+            index
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid state key {}", value))
+        };
+
+        if value == "rng" {
+            Ok(StateKey::Random)
+        } else if let Some(index) = value.strip_prefix('m') {
+            Ok(StateKey::Mobj(parse_index(index)?))
+        } else if let Some(index) = value.strip_prefix('s') {
+            Ok(StateKey::Sector(parse_index(index)?))
+        } else {
+            Err(format!("Invalid state key {}", value))
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateHashes {
+    // Everything together, checked first
+    pub total: u64,
+    pub objects: BTreeMap<StateKey, u32>,
+}
+
+impl World {
+    // Mobj positions, momenta and health, sector heights and the random
+    // number index
+    pub fn state_hashes(&self) -> StateHashes {
+        let mut objects: BTreeMap<StateKey, u32> = BTreeMap::new();
+        objects.insert(
+            StateKey::Random,
+            fold(fnv(&[i32::from(self.random.prndindex())])),
+        );
+
+        for (index, (_id, mobj)) in self.thinkers.mobjs().enumerate() {
+            objects.insert(StateKey::Mobj(index), fold(fnv(&mobj_values(mobj))));
+        }
+
+        for (index, sector) in self.level.sectors.iter().enumerate() {
+            objects.insert(StateKey::Sector(index), fold(fnv(&sector_values(sector))));
+        }
+
+        let mut total: u64 = FNV_OFFSET_BASIS;
+        for (key, hash) in objects.iter() {
+            total = fnv_continue(total, key.to_string().as_bytes());
+            total = fnv_continue(total, &hash.to_le_bytes());
+        }
+
+        StateHashes { total, objects }
+    }
+
+    // The values that went into an object's hash, for desync reports
+    pub fn describe_state(&self, key: StateKey) -> Option<String> {
+        match key {
+            StateKey::Random => Some(format!("prndindex {}", self.random.prndindex())),
+            StateKey::Mobj(index) => self.thinkers.mobjs().nth(index).map(|(_id, mobj)| {
+                format!(
+                    "type {} at ({}, {}, {}) momentum ({}, {}, {}) health {}",
+                    mobj.mobj_type,
+                    to_map_units(mobj.x),
+                    to_map_units(mobj.y),
+                    to_map_units(mobj.z),
+                    to_map_units(mobj.mom_x),
+                    to_map_units(mobj.mom_y),
+                    to_map_units(mobj.mom_z),
+                    mobj.health
+                )
+            }),
+            StateKey::Sector(index) => self.level.sectors.get(index).map(|sector| {
+                format!(
+                    "floor {} ceiling {}",
+                    to_map_units(sector.floor_height),
+                    to_map_units(sector.ceiling_height)
+                )
+            }),
+        }
+    }
+}
+
+fn mobj_values(mobj: &Mobj) -> [i32; 8] {
+    [
+        mobj.mobj_type as i32,
+        mobj.x.0,
+        mobj.y.0,
+        mobj.z.0,
+        mobj.mom_x.0,
+        mobj.mom_y.0,
+        mobj.mom_z.0,
+        mobj.health,
+    ]
+}
+
+fn sector_values(sector: &Sector) -> [i32; 2] {
+    [sector.floor_height.0, sector.ceiling_height.0]
+}
+
+fn to_map_units(value: Fixed) -> f64 {
+    f64::from(value.0) / 65536.0
+}
+
+// FNV-1a, picked since it's the same on every platform and Rust version
+fn fnv(values: &[i32]) -> u64 {
+    values.iter().fold(FNV_OFFSET_BASIS, |hash, value| {
+        fnv_continue(hash, &value.to_le_bytes())
+    })
+}
+
+// FNV-1a of raw bytes for anything else that needs a stable hash
+pub fn fnv_bytes(bytes: &[u8]) -> u64 {
+    fnv_continue(FNV_OFFSET_BASIS, bytes)
+}
+
+fn fnv_continue(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn fold(hash: u64) -> u32 {
+    (hash ^ (hash >> 32)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::play::hash::{StateHashes, StateKey};
+    use crate::play::mobj::MobjId;
+    use crate::play::test_map::TestMap;
+    use crate::play::world::{World, WorldSettings};
+
+    fn create_world() -> World {
+        World::new(
+            WorldSettings::default(),
+            &TestMap::new()
+                .room(512, 0, 128)
+                .thing(64, 128, 0, 1, 7)
+                .thing(256, 128, 0, 3004, 7)
+                .to_map_lumps(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_state_key_string_round_trip() {
+        for key in [StateKey::Random, StateKey::Mobj(12), StateKey::Sector(3)] {
+            assert_eq!(key.to_string().parse::<StateKey>(), Ok(key));
+        }
+        assert!("x1".parse::<StateKey>().is_err());
+        assert!("m".parse::<StateKey>().is_err());
+    }
+
+    #[test]
+    fn test_state_hashes_are_the_same_for_the_same_state() {
+        let world: World = create_world();
+        let hashes: StateHashes = world.state_hashes();
+
+        assert_eq!(hashes, create_world().state_hashes());
+        assert_eq!(hashes, world.clone().state_hashes());
+        // The random index, 2 mobjs and 1 sector
+        assert_eq!(hashes.objects.len(), 4);
+    }
+
+    #[test]
+    fn test_state_hashes_change_with_the_object_that_changed() {
+        let mut world: World = create_world();
+        let before: StateHashes = world.state_hashes();
+
+        let mo: MobjId = world.players[0].mo.unwrap();
+        world.thinkers.mobj_mut(mo).mom_x = Fixed(FRACUNIT);
+        let after: StateHashes = world.state_hashes();
+
+        assert_ne!(before.total, after.total);
+        let changed: Vec<StateKey> = before
+            .objects
+            .iter()
+            .filter(|(key, hash)| after.objects[key] != **hash)
+            .map(|(key, _hash)| *key)
+            .collect();
+        assert_eq!(changed, vec![StateKey::Mobj(0)]);
+
+        world.random.p_random();
+        assert_ne!(
+            world.state_hashes().objects[&StateKey::Random],
+            after.objects[&StateKey::Random]
+        );
+    }
+
+    #[test]
+    fn test_describe_state_shows_the_hashed_values() {
+        let world: World = create_world();
+
+        assert_eq!(
+            world.describe_state(StateKey::Sector(0)),
+            Some(String::from("floor 0 ceiling 128"))
+        );
+        // The imp's spawn randomizes its tics
+        assert_eq!(
+            world.describe_state(StateKey::Random),
+            Some(String::from("prndindex 3"))
+        );
+        assert_eq!(world.describe_state(StateKey::Mobj(5)), None);
+    }
+}
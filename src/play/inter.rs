@@ -0,0 +1,1084 @@
+// Port of p_inter.c
+use crate::config::{GameDifficulty, GameType};
+use crate::fixed::{fixed_mul, point_to_angle, Angle, Fixed, FRACUNIT};
+use crate::info::mobjinfo::{
+    MT_CHAINGUN, MT_CHAINGUY, MT_CLIP, MT_POSSESSED, MT_SHOTGUN, MT_SHOTGUY, MT_SKULL, MT_VILE,
+    MT_WOLFSS,
+};
+use crate::info::sprites::*;
+use crate::info::states::S_NULL;
+use crate::info::weaponinfo::WEAPONINFO;
+use crate::info::{MobjInfo, MobjType, SpriteNum, StateNum};
+use crate::play::mobj::{
+    Mobj, MobjId, MF_CORPSE, MF_COUNTITEM, MF_COUNTKILL, MF_DROPOFF, MF_DROPPED, MF_FLOAT,
+    MF_JUSTHIT, MF_NOCLIP, MF_NOGRAVITY, MF_SHADOW, MF_SHOOTABLE, MF_SKULLFLY, MF_SOLID, ONFLOORZ,
+};
+use crate::play::player::{
+    AmmoType, Player, PlayerState, WeaponType, AM_CELL, AM_CLIP, AM_MISL, AM_NOAMMO, AM_SHELL,
+    CF_GODMODE, IT_BLUECARD, IT_BLUESKULL, IT_REDCARD, IT_REDSKULL, IT_YELLOWCARD, IT_YELLOWSKULL,
+    MAXHEALTH, NUMAMMO, PW_ALLMAP, PW_INFRARED, PW_INVISIBILITY, PW_INVULNERABILITY, PW_IRONFEET,
+    PW_STRENGTH, WP_BFG, WP_CHAINGUN, WP_CHAINSAW, WP_FIST, WP_MISSILE, WP_PISTOL, WP_PLASMA,
+    WP_SHOTGUN, WP_SUPERSHOTGUN,
+};
+use crate::play::world::{Deathmatch, World};
+use crate::play::TICRATE;
+use crate::sound::sfx::{SfxNum, SFX_GETPOW, SFX_ITEMUP, SFX_WPNUP};
+
+// Follow a player exclusively for 3 seconds
+pub const BASETHRESHOLD: i32 = 100;
+
+pub const BONUSADD: i32 = 6;
+
+// A weapon is found with two clip loads, a big item has five clip loads
+pub const MAXAMMO: [i32; NUMAMMO] = [200, 50, 300, 50];
+pub const CLIPAMMO: [i32; NUMAMMO] = [10, 4, 20, 1];
+
+// Power up durations, how many seconds till expiration, assuming TICRATE
+// is 35 ticks/second
+pub const INVULNTICS: i32 = 30 * TICRATE;
+pub const INVISTICS: i32 = 60 * TICRATE;
+pub const INFRATICS: i32 = 120 * TICRATE;
+pub const IRONTICS: i32 = 60 * TICRATE;
+
+pub const GOTARMOR: &str = "Picked up the armor.";
+pub const GOTMEGA: &str = "Picked up the MegaArmor!";
+pub const GOTHTHBONUS: &str = "Picked up a health bonus.";
+pub const GOTARMBONUS: &str = "Picked up an armor bonus.";
+pub const GOTSTIM: &str = "Picked up a stimpack.";
+pub const GOTMEDINEED: &str = "Picked up a medikit that you REALLY need!";
+pub const GOTMEDIKIT: &str = "Picked up a medikit.";
+pub const GOTSUPER: &str = "Supercharge!";
+
+pub const GOTBLUECARD: &str = "Picked up a blue keycard.";
+pub const GOTYELWCARD: &str = "Picked up a yellow keycard.";
+pub const GOTREDCARD: &str = "Picked up a red keycard.";
+pub const GOTBLUESKUL: &str = "Picked up a blue skull key.";
+pub const GOTYELWSKUL: &str = "Picked up a yellow skull key.";
+pub const GOTREDSKULL: &str = "Picked up a red skull key.";
+
+pub const GOTINVUL: &str = "Invulnerability!";
+pub const GOTBERSERK: &str = "Berserk!";
+pub const GOTINVIS: &str = "Partial Invisibility";
+pub const GOTSUIT: &str = "Radiation Shielding Suit";
+pub const GOTMAP: &str = "Computer Area Map";
+pub const GOTVISOR: &str = "Light Amplification Visor";
+pub const GOTMSPHERE: &str = "MegaSphere!";
+
+pub const GOTCLIP: &str = "Picked up a clip.";
+pub const GOTCLIPBOX: &str = "Picked up a box of bullets.";
+pub const GOTROCKET: &str = "Picked up a rocket.";
+pub const GOTROCKBOX: &str = "Picked up a box of rockets.";
+pub const GOTCELL: &str = "Picked up an energy cell.";
+pub const GOTCELLBOX: &str = "Picked up an energy cell pack.";
+pub const GOTSHELLS: &str = "Picked up 4 shotgun shells.";
+pub const GOTSHELLBOX: &str = "Picked up a box of shotgun shells.";
+pub const GOTBACKPACK: &str = "Picked up a backpack full of ammo!";
+
+pub const GOTBFG9000: &str = "You got the BFG9000!  Oh, yes.";
+pub const GOTCHAINGUN: &str = "You got the chaingun!";
+pub const GOTCHAINSAW: &str = "A chainsaw!  Find some meat!";
+pub const GOTLAUNCHER: &str = "You got the rocket launcher!";
+pub const GOTPLASMA: &str = "You got the plasma gun!";
+pub const GOTSHOTGUN: &str = "You got the shotgun!";
+pub const GOTSHOTGUN2: &str = "You got the super shotgun!";
+
+impl World {
+    // P_GiveAmmo. Num is the number of clip loads, not the individual count
+    // (0 = 1/2 clip). Returns false if the ammo can't be picked up at all
+    pub fn give_ammo(&mut self, player: usize, ammo: AmmoType, num: i32) -> bool {
+        if ammo == AM_NOAMMO {
+            return false;
+        }
+
+        if ammo >= NUMAMMO {
+            panic!("P_GiveAmmo: bad type {}", ammo);
+        }
+
+        let skill: GameDifficulty = self.settings.skill;
+        let p: &mut Player = &mut self.players[player];
+
+        if p.ammo[ammo] == p.max_ammo[ammo] {
+            return false;
+        }
+
+        let mut num: i32 = if num != 0 {
+            num * CLIPAMMO[ammo]
+        } else {
+            CLIPAMMO[ammo] / 2
+        };
+
+        if skill == GameDifficulty::Baby || skill == GameDifficulty::Nightmare {
+            // Give double ammo in trainer mode, you'll need in nightmare
+            num <<= 1;
+        }
+
+        let old_ammo: i32 = p.ammo[ammo];
+        p.ammo[ammo] = (p.ammo[ammo] + num).min(p.max_ammo[ammo]);
+
+        // If non zero ammo, don't change up weapons, player was lower on
+        // purpose
+        if old_ammo != 0 {
+            return true;
+        }
+
+        // We were down to zero, so select a new weapon. Preferences are not
+        // user selectable
+        match ammo {
+            AM_CLIP if p.ready_weapon == WP_FIST => {
+                p.pending_weapon = if p.weapon_owned[WP_CHAINGUN] {
+                    WP_CHAINGUN
+                } else {
+                    WP_PISTOL
+                };
+            }
+            AM_SHELL
+                if (p.ready_weapon == WP_FIST || p.ready_weapon == WP_PISTOL)
+                    && p.weapon_owned[WP_SHOTGUN] =>
+            {
+                p.pending_weapon = WP_SHOTGUN;
+            }
+            AM_CELL
+                if (p.ready_weapon == WP_FIST || p.ready_weapon == WP_PISTOL)
+                    && p.weapon_owned[WP_PLASMA] =>
+            {
+                p.pending_weapon = WP_PLASMA;
+            }
+            AM_MISL if p.ready_weapon == WP_FIST && p.weapon_owned[WP_MISSILE] => {
+                p.pending_weapon = WP_MISSILE;
+            }
+            _ => {}
+        }
+
+        true
+    }
+
+    // P_GiveWeapon. The weapon name may have a MF_DROPPED flag ored in
+    pub fn give_weapon(&mut self, player: usize, weapon: WeaponType, dropped: bool) -> bool {
+        let ammo: AmmoType = WEAPONINFO[weapon].ammo;
+
+        if self.settings.netgame && self.settings.deathmatch != Deathmatch::AltDeath && !dropped {
+            // Leave placed weapons forever on net games
+            if self.players[player].weapon_owned[weapon] {
+                return false;
+            }
+
+            let p: &mut Player = &mut self.players[player];
+            p.bonus_count += BONUSADD;
+            p.weapon_owned[weapon] = true;
+
+            if self.settings.deathmatch != Deathmatch::Off {
+                self.give_ammo(player, ammo, 5);
+            } else {
+                self.give_ammo(player, ammo, 2);
+            }
+            self.players[player].pending_weapon = weapon;
+
+            if player == self.settings.console_player {
+                self.start_sound(None, SFX_WPNUP);
+            }
+            return false;
+        }
+
+        let gave_ammo: bool = if ammo != AM_NOAMMO {
+            // Give one clip with a dropped weapon, two clips with a found
+            // weapon
+            self.give_ammo(player, ammo, if dropped { 1 } else { 2 })
+        } else {
+            false
+        };
+
+        let p: &mut Player = &mut self.players[player];
+        let gave_weapon: bool = if p.weapon_owned[weapon] {
+            false
+        } else {
+            p.weapon_owned[weapon] = true;
+            p.pending_weapon = weapon;
+            true
+        };
+
+        gave_weapon || gave_ammo
+    }
+
+    // P_GiveBody. Returns false if the body isn't needed at all
+    pub fn give_body(&mut self, player: usize, num: i32) -> bool {
+        let p: &mut Player = &mut self.players[player];
+
+        if p.health >= MAXHEALTH {
+            return false;
+        }
+
+        p.health = (p.health + num).min(MAXHEALTH);
+        let health: i32 = p.health;
+        let mo: MobjId = p.mo.unwrap();
+        self.thinkers.mobj_mut(mo).health = health;
+
+        true
+    }
+
+    // P_GiveArmor. Returns false if the armor is worse than the current
+    // armor
+    pub fn give_armor(&mut self, player: usize, armor_type: i32) -> bool {
+        let p: &mut Player = &mut self.players[player];
+        let hits: i32 = armor_type * 100;
+
+        if p.armor_points >= hits {
+            // Don't pick up
+            return false;
+        }
+
+        p.armor_type = armor_type;
+        p.armor_points = hits;
+
+        true
+    }
+
+    // P_GiveCard
+    pub fn give_card(&mut self, player: usize, card: usize) {
+        let p: &mut Player = &mut self.players[player];
+
+        if p.cards[card] {
+            return;
+        }
+
+        p.bonus_count = BONUSADD;
+        p.cards[card] = true;
+    }
+
+    // P_GivePower
+    pub fn give_power(&mut self, player: usize, power: usize) -> bool {
+        let p: &mut Player = &mut self.players[player];
+
+        match power {
+            PW_INVULNERABILITY => p.powers[power] = INVULNTICS,
+            PW_INVISIBILITY => {
+                p.powers[power] = INVISTICS;
+                let mo: MobjId = p.mo.unwrap();
+                self.thinkers.mobj_mut(mo).flags |= MF_SHADOW;
+            }
+            PW_INFRARED => p.powers[power] = INFRATICS,
+            PW_IRONFEET => p.powers[power] = IRONTICS,
+            PW_STRENGTH => {
+                self.give_body(player, 100);
+                self.players[player].powers[power] = 1;
+            }
+            _ => {
+                if p.powers[power] != 0 {
+                    // Already got it
+                    return false;
+                }
+
+                p.powers[power] = 1;
+            }
+        }
+
+        true
+    }
+
+    // Gives a key, keys stay on the level in net games so everyone can
+    // pick them up. Returns false if the key should be left there
+    fn touch_card(&mut self, player: usize, card: usize, message: &'static str) -> bool {
+        if !self.players[player].cards[card] {
+            self.players[player].message = Some(message);
+        }
+
+        self.give_card(player, card);
+
+        !self.settings.netgame
+    }
+
+    // P_TouchSpecialThing
+    pub fn touch_special_thing(&mut self, special: MobjId, toucher: MobjId) {
+        let special_mobj: &Mobj = self.thinkers.mobj(special);
+        let toucher_mobj: &Mobj = self.thinkers.mobj(toucher);
+        let delta: Fixed = special_mobj.z - toucher_mobj.z;
+
+        if delta > toucher_mobj.height || delta < Fixed(-8 * FRACUNIT) {
+            // Out of reach
+            return;
+        }
+
+        // Dead thing touching. Can happen with a sliding player corpse
+        if toucher_mobj.health <= 0 {
+            return;
+        }
+
+        let player: usize = toucher_mobj.player.unwrap();
+        let sprite: SpriteNum = special_mobj.sprite;
+        let dropped: bool = special_mobj.flags & MF_DROPPED != 0;
+        let count_item: bool = special_mobj.flags & MF_COUNTITEM != 0;
+        let mut sound: SfxNum = SFX_ITEMUP;
+
+        // Identify by sprite
+        let message: &'static str = match sprite {
+            // Armor
+            SPR_ARM1 => {
+                if !self.give_armor(player, 1) {
+                    return;
+                }
+                GOTARMOR
+            }
+            SPR_ARM2 => {
+                if !self.give_armor(player, 2) {
+                    return;
+                }
+                GOTMEGA
+            }
+
+            // Bonus items
+            SPR_BON1 => {
+                // Can go over 100%
+                let p: &mut Player = &mut self.players[player];
+                p.health = (p.health + 1).min(200);
+                let health: i32 = p.health;
+                self.thinkers.mobj_mut(toucher).health = health;
+                GOTHTHBONUS
+            }
+            SPR_BON2 => {
+                // Can go over 100%
+                let p: &mut Player = &mut self.players[player];
+                p.armor_points = (p.armor_points + 1).min(200);
+                if p.armor_type == 0 {
+                    p.armor_type = 1;
+                }
+                GOTARMBONUS
+            }
+            SPR_SOUL => {
+                let p: &mut Player = &mut self.players[player];
+                p.health = (p.health + 100).min(200);
+                let health: i32 = p.health;
+                self.thinkers.mobj_mut(toucher).health = health;
+                sound = SFX_GETPOW;
+                GOTSUPER
+            }
+            SPR_MEGA => {
+                if self.settings.game_type != GameType::DoomII {
+                    return;
+                }
+                self.players[player].health = 200;
+                self.thinkers.mobj_mut(toucher).health = 200;
+                self.give_armor(player, 2);
+                sound = SFX_GETPOW;
+                GOTMSPHERE
+            }
+
+            // Cards, leave cards for everyone
+            SPR_BKEY | SPR_YKEY | SPR_RKEY | SPR_BSKU | SPR_YSKU | SPR_RSKU => {
+                let (card, message): (usize, &'static str) = match sprite {
+                    SPR_BKEY => (IT_BLUECARD, GOTBLUECARD),
+                    SPR_YKEY => (IT_YELLOWCARD, GOTYELWCARD),
+                    SPR_RKEY => (IT_REDCARD, GOTREDCARD),
+                    SPR_BSKU => (IT_BLUESKULL, GOTBLUESKUL),
+                    SPR_YSKU => (IT_YELLOWSKULL, GOTYELWSKUL),
+                    _ => (IT_REDSKULL, GOTREDSKULL),
+                };
+                if !self.touch_card(player, card, message) {
+                    return;
+                }
+                self.pick_up(special, player, count_item, sound);
+                return;
+            }
+
+            // Medikits, heals
+            SPR_STIM => {
+                if !self.give_body(player, 10) {
+                    return;
+                }
+                GOTSTIM
+            }
+            SPR_MEDI => {
+                if !self.give_body(player, 25) {
+                    return;
+                }
+                // The health has already been given so this is only shown
+                // when it was 0 or less to begin with
+                if self.players[player].health < 25 {
+                    GOTMEDINEED
+                } else {
+                    GOTMEDIKIT
+                }
+            }
+
+            // Power ups
+            SPR_PINV => {
+                if !self.give_power(player, PW_INVULNERABILITY) {
+                    return;
+                }
+                sound = SFX_GETPOW;
+                GOTINVUL
+            }
+            SPR_PSTR => {
+                if !self.give_power(player, PW_STRENGTH) {
+                    return;
+                }
+                let p: &mut Player = &mut self.players[player];
+                if p.ready_weapon != WP_FIST {
+                    p.pending_weapon = WP_FIST;
+                }
+                sound = SFX_GETPOW;
+                GOTBERSERK
+            }
+            SPR_PINS => {
+                if !self.give_power(player, PW_INVISIBILITY) {
+                    return;
+                }
+                sound = SFX_GETPOW;
+                GOTINVIS
+            }
+            SPR_SUIT => {
+                if !self.give_power(player, PW_IRONFEET) {
+                    return;
+                }
+                sound = SFX_GETPOW;
+                GOTSUIT
+            }
+            SPR_PMAP => {
+                if !self.give_power(player, PW_ALLMAP) {
+                    return;
+                }
+                sound = SFX_GETPOW;
+                GOTMAP
+            }
+            SPR_PVIS => {
+                if !self.give_power(player, PW_INFRARED) {
+                    return;
+                }
+                sound = SFX_GETPOW;
+                GOTVISOR
+            }
+
+            // Ammo
+            SPR_CLIP => {
+                // Dropped clips only have half a clip in them
+                if !self.give_ammo(player, AM_CLIP, if dropped { 0 } else { 1 }) {
+                    return;
+                }
+                GOTCLIP
+            }
+            SPR_AMMO => {
+                if !self.give_ammo(player, AM_CLIP, 5) {
+                    return;
+                }
+                GOTCLIPBOX
+            }
+            SPR_ROCK => {
+                if !self.give_ammo(player, AM_MISL, 1) {
+                    return;
+                }
+                GOTROCKET
+            }
+            SPR_BROK => {
+                if !self.give_ammo(player, AM_MISL, 5) {
+                    return;
+                }
+                GOTROCKBOX
+            }
+            SPR_CELL => {
+                if !self.give_ammo(player, AM_CELL, 1) {
+                    return;
+                }
+                GOTCELL
+            }
+            SPR_CELP => {
+                if !self.give_ammo(player, AM_CELL, 5) {
+                    return;
+                }
+                GOTCELLBOX
+            }
+            SPR_SHEL => {
+                if !self.give_ammo(player, AM_SHELL, 1) {
+                    return;
+                }
+                GOTSHELLS
+            }
+            SPR_SBOX => {
+                if !self.give_ammo(player, AM_SHELL, 5) {
+                    return;
+                }
+                GOTSHELLBOX
+            }
+            SPR_BPAK => {
+                let p: &mut Player = &mut self.players[player];
+                if !p.backpack {
+                    for max_ammo in p.max_ammo.iter_mut() {
+                        *max_ammo *= 2;
+                    }
+                    p.backpack = true;
+                }
+                for ammo in 0..NUMAMMO {
+                    self.give_ammo(player, ammo, 1);
+                }
+                GOTBACKPACK
+            }
+
+            // Weapons
+            SPR_BFUG | SPR_MGUN | SPR_CSAW | SPR_LAUN | SPR_PLAS | SPR_SHOT | SPR_SGN2 => {
+                // Only the weapons monsters drop can be dropped
+                let (weapon, dropped, message): (WeaponType, bool, &'static str) = match sprite {
+                    SPR_BFUG => (WP_BFG, false, GOTBFG9000),
+                    SPR_MGUN => (WP_CHAINGUN, dropped, GOTCHAINGUN),
+                    SPR_CSAW => (WP_CHAINSAW, false, GOTCHAINSAW),
+                    SPR_LAUN => (WP_MISSILE, false, GOTLAUNCHER),
+                    SPR_PLAS => (WP_PLASMA, false, GOTPLASMA),
+                    SPR_SHOT => (WP_SHOTGUN, dropped, GOTSHOTGUN),
+                    _ => (WP_SUPERSHOTGUN, dropped, GOTSHOTGUN2),
+                };
+                if !self.give_weapon(player, weapon, dropped) {
+                    return;
+                }
+                sound = SFX_WPNUP;
+                message
+            }
+
+            _ => panic!("P_SpecialThing: Unknown gettable thing"),
+        };
+
+        self.players[player].message = Some(message);
+        self.pick_up(special, player, count_item, sound);
+    }
+
+    // The end of P_TouchSpecialThing, once the item has been taken
+    fn pick_up(&mut self, special: MobjId, player: usize, count_item: bool, sound: SfxNum) {
+        if count_item {
+            self.players[player].item_count += 1;
+        }
+
+        self.remove_mobj(special);
+        self.players[player].bonus_count += BONUSADD;
+
+        if player == self.settings.console_player {
+            self.start_sound(None, sound);
+        }
+    }
+
+    // P_KillMobj
+    pub fn kill_mobj(&mut self, source: Option<MobjId>, target: MobjId) {
+        let target_mobj: &mut Mobj = self.thinkers.mobj_mut(target);
+        target_mobj.flags &= !(MF_SHOOTABLE | MF_FLOAT | MF_SKULLFLY);
+
+        if target_mobj.mobj_type != MT_SKULL {
+            target_mobj.flags &= !MF_NOGRAVITY;
+        }
+
+        target_mobj.flags |= MF_CORPSE | MF_DROPOFF;
+        target_mobj.height = Fixed(target_mobj.height.0 >> 2);
+
+        let target_flags: u32 = target_mobj.flags;
+        let target_player: Option<usize> = target_mobj.player;
+        let source_player: Option<usize> =
+            source.and_then(|source| self.thinkers.mobj(source).player);
+
+        if let Some(source_player) = source_player {
+            // Count for intermission
+            if target_flags & MF_COUNTKILL != 0 {
+                self.players[source_player].kill_count += 1;
+            }
+
+            if let Some(target_player) = target_player {
+                self.players[source_player].frags[target_player] += 1;
+            }
+        } else if !self.settings.netgame && target_flags & MF_COUNTKILL != 0 {
+            // Count all monster deaths, even those caused by other monsters
+            self.players[0].kill_count += 1;
+        }
+
+        if let Some(target_player) = target_player {
+            // Count environment kills against you
+            if source.is_none() {
+                self.players[target_player].frags[target_player] += 1;
+            }
+
+            self.thinkers.mobj_mut(target).flags &= !MF_SOLID;
+            self.players[target_player].player_state = PlayerState::Dead;
+            self.drop_weapon(target_player);
+        }
+
+        let target_mobj: &Mobj = self.thinkers.mobj(target);
+        let info: &MobjInfo = target_mobj.info();
+        let state: StateNum =
+            if target_mobj.health < -info.spawn_health && info.xdeath_state != S_NULL {
+                info.xdeath_state
+            } else {
+                info.death_state
+            };
+        self.set_mobj_state(target, state);
+        self.randomize_tics(target);
+
+        // Drop stuff. This determines the kind of object spawned during the
+        // death frame of a thing
+        let target_mobj: &Mobj = self.thinkers.mobj(target);
+        let item: MobjType = match target_mobj.mobj_type {
+            MT_WOLFSS | MT_POSSESSED => MT_CLIP,
+            MT_SHOTGUY => MT_SHOTGUN,
+            MT_CHAINGUY => MT_CHAINGUN,
+            _ => return,
+        };
+
+        let (x, y): (Fixed, Fixed) = (target_mobj.x, target_mobj.y);
+        let mo: MobjId = self.spawn_mobj(x, y, ONFLOORZ, item);
+        // Special versions of items
+        self.thinkers.mobj_mut(mo).flags |= MF_DROPPED;
+    }
+
+    // P_DamageMobj. Damages both enemies and players.
+    // "inflictor" is the thing that caused the damage, creature or missile,
+    // can be None (slime, etc).
+    // "source" is the thing to target after taking damage, creature or
+    // None. Source and inflictor are the same for melee attacks. Source can
+    // be None for slime, barrel explosions and other environmental stuff
+    pub fn damage_mobj(
+        &mut self,
+        target: MobjId,
+        inflictor: Option<MobjId>,
+        source: Option<MobjId>,
+        damage: i32,
+    ) {
+        let mut damage: i32 = damage;
+        let target_mobj: &mut Mobj = self.thinkers.mobj_mut(target);
+
+        if target_mobj.flags & MF_SHOOTABLE == 0 {
+            // Shouldn't happen...
+            return;
+        }
+
+        if target_mobj.health <= 0 {
+            return;
+        }
+
+        if target_mobj.flags & MF_SKULLFLY != 0 {
+            target_mobj.mom_x = Fixed::ZERO;
+            target_mobj.mom_y = Fixed::ZERO;
+            target_mobj.mom_z = Fixed::ZERO;
+        }
+
+        let player: Option<usize> = target_mobj.player;
+        if player.is_some() && self.settings.skill == GameDifficulty::Baby {
+            // Take half damage in trainer mode
+            damage >>= 1;
+        }
+
+        // Some close combat weapons should not inflict thrust and push the
+        // victim out of reach, thus kick away unless using the chainsaw
+        if let Some(inflictor) = inflictor {
+            if self.thinkers.mobj(target).flags & MF_NOCLIP == 0 && !self.is_chainsaw(source) {
+                self.thrust_mobj(target, inflictor, damage);
+            }
+        }
+
+        // Player specific
+        if let Some(player) = player {
+            let target_mobj: &Mobj = self.thinkers.mobj(target);
+            let sector: usize = self.level.subsectors[target_mobj.subsector].sector;
+
+            // End of game hell hack
+            if self.level.sectors[sector].special == 11 && damage >= target_mobj.health {
+                damage = target_mobj.health - 1;
+            }
+
+            let player: &mut Player = &mut self.players[player];
+
+            // Below certain threshold, ignore damage in GOD mode, or with
+            // INVUL power
+            if damage < 1000
+                && (player.cheats & CF_GODMODE != 0 || player.powers[PW_INVULNERABILITY] != 0)
+            {
+                return;
+            }
+
+            if player.armor_type != 0 {
+                let mut saved: i32 = if player.armor_type == 1 {
+                    damage / 3
+                } else {
+                    damage / 2
+                };
+
+                if player.armor_points <= saved {
+                    // Armor is used up
+                    saved = player.armor_points;
+                    player.armor_type = 0;
+                }
+                player.armor_points -= saved;
+                damage -= saved;
+            }
+
+            // Mirror mobj health here for Dave
+            player.health -= damage;
+            if player.health < 0 {
+                player.health = 0;
+            }
+
+            player.attacker = source;
+            // Add damage after armor / invuln
+            player.damage_count += damage;
+
+            if player.damage_count > 100 {
+                // Teleport stomp does 10k points...
+                player.damage_count = 100;
+            }
+        }
+
+        // Do the damage
+        let target_mobj: &mut Mobj = self.thinkers.mobj_mut(target);
+        target_mobj.health -= damage;
+        if target_mobj.health <= 0 {
+            self.kill_mobj(source, target);
+            return;
+        }
+
+        let random: i32 = self.random.p_random();
+        let target_mobj: &mut Mobj = self.thinkers.mobj_mut(target);
+        if random < target_mobj.info().pain_chance && target_mobj.flags & MF_SKULLFLY == 0 {
+            // Fight back!
+            target_mobj.flags |= MF_JUSTHIT;
+            let pain_state: StateNum = target_mobj.info().pain_state;
+            self.set_mobj_state(target, pain_state);
+        }
+
+        let target_mobj: &mut Mobj = self.thinkers.mobj_mut(target);
+        // We're awake now...
+        target_mobj.reaction_time = 0;
+
+        let source_type: Option<MobjType> =
+            source.map(|source| self.thinkers.mobj(source).mobj_type);
+        let target_mobj: &mut Mobj = self.thinkers.mobj_mut(target);
+
+        if (target_mobj.threshold == 0 || target_mobj.mobj_type == MT_VILE)
+            && source.is_some_and(|source| source != target)
+            && source_type != Some(MT_VILE)
+        {
+            // If not intent on another player, chase after this one
+            target_mobj.target = source;
+            target_mobj.threshold = BASETHRESHOLD;
+
+            let info: &MobjInfo = target_mobj.info();
+            if target_mobj.state == info.spawn_state && info.see_state != S_NULL {
+                let see_state: StateNum = info.see_state;
+                self.set_mobj_state(target, see_state);
+            }
+        }
+    }
+
+    // Pushes the target away from the inflictor
+    fn thrust_mobj(&mut self, target: MobjId, inflictor: MobjId, damage: i32) {
+        let inflictor_mobj: &Mobj = self.thinkers.mobj(inflictor);
+        let (inflictor_x, inflictor_y, inflictor_z): (Fixed, Fixed, Fixed) =
+            (inflictor_mobj.x, inflictor_mobj.y, inflictor_mobj.z);
+        let target_mobj: &Mobj = self.thinkers.mobj(target);
+
+        let mut angle: Angle =
+            point_to_angle(inflictor_x, inflictor_y, target_mobj.x, target_mobj.y);
+        // Overflows for big damage like telefrags, the same as the original
+        let mut thrust: i32 =
+            damage.wrapping_mul(FRACUNIT >> 3).wrapping_mul(100) / target_mobj.info().mass;
+
+        // Make fall forwards sometimes
+        if damage < 40
+            && damage > target_mobj.health
+            && target_mobj.z - inflictor_z > Fixed(64 * FRACUNIT)
+            && self.random.p_random() & 1 != 0
+        {
+            angle += Angle::ANG180;
+            thrust = thrust.wrapping_mul(4);
+        }
+
+        let target_mobj: &mut Mobj = self.thinkers.mobj_mut(target);
+        target_mobj.mom_x += fixed_mul(Fixed(thrust), angle.cos());
+        target_mobj.mom_y += fixed_mul(Fixed(thrust), angle.sin());
+    }
+
+    // Whether the source is a player holding the chainsaw
+    fn is_chainsaw(&self, source: Option<MobjId>) -> bool {
+        source
+            .and_then(|source| self.thinkers.mobj(source).player)
+            .is_some_and(|player| self.players[player].ready_weapon == WP_CHAINSAW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GameDifficulty;
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::info::mobjinfo::{
+        MT_BARREL, MT_CLIP, MT_MISC0, MT_MISC11, MT_MISC4, MT_POSSESSED, MT_SHOTGUN, MT_TROOP,
+        MT_VILE,
+    };
+    use crate::info::states::{S_POSS_ATK1, S_POSS_DIE1, S_POSS_PAIN, S_POSS_XDIE1};
+    use crate::play::inter::{BASETHRESHOLD, BONUSADD, GOTBLUECARD, GOTCLIP, GOTMEDIKIT, MAXAMMO};
+    use crate::play::level::{MapThing, MTF_EASY, MTF_HARD, MTF_NORMAL};
+    use crate::play::mobj::{
+        Mobj, MobjId, MF_CORPSE, MF_DROPPED, MF_JUSTHIT, MF_SHOOTABLE, MF_SOLID, ONFLOORZ,
+    };
+    use crate::play::player::{
+        PlayerState, AM_CLIP, AM_SHELL, CF_GODMODE, IT_BLUECARD, WP_FIST, WP_PISTOL, WP_SHOTGUN,
+    };
+    use crate::play::test_map::TestMap;
+    use crate::play::world::{World, WorldSettings};
+    use crate::sound::sfx::SFX_ITEMUP;
+
+    fn create_world(settings: WorldSettings) -> World {
+        TestMap::new().room(512, 0, 128).to_world_with(settings)
+    }
+
+    fn spawn(world: &mut World, x: i32, mobj_type: usize) -> MobjId {
+        world.spawn_mobj(
+            Fixed(x * FRACUNIT),
+            Fixed(128 * FRACUNIT),
+            ONFLOORZ,
+            mobj_type,
+        )
+    }
+
+    fn spawn_player(world: &mut World) -> MobjId {
+        world.spawn_player(&MapThing {
+            x: 64,
+            y: 128,
+            angle: 0,
+            thing_type: 1,
+            options: MTF_EASY | MTF_NORMAL | MTF_HARD,
+        });
+        world.players[0].mo.unwrap()
+    }
+
+    #[test]
+    fn test_damage_mobj_thrusts_and_targets_source() {
+        let mut world: World = create_world(WorldSettings::default());
+        let troop: MobjId = spawn(&mut world, 300, MT_TROOP);
+        let source: MobjId = spawn(&mut world, 100, MT_POSSESSED);
+
+        world.damage_mobj(troop, Some(source), Some(source), 10);
+
+        let mobj: &Mobj = world.thinkers.mobj(troop);
+        assert_eq!(mobj.health, 50);
+        // 10 * 8192 * 100 / 100 straight along x. The sine table has 65535
+        // for 1.0 and 25 for 0
+        assert_eq!(mobj.mom_x, Fixed(81918));
+        assert_eq!(mobj.mom_y, Fixed(31));
+        assert_eq!(mobj.target, Some(source));
+        // A_Chase in the see state already counted it down once
+        assert_eq!(mobj.threshold, BASETHRESHOLD - 1);
+        assert_eq!(mobj.reaction_time, 0);
+    }
+
+    #[test]
+    fn test_damage_mobj_ignores_arch_vile_as_source() {
+        let mut world: World = create_world(WorldSettings::default());
+        let troop: MobjId = spawn(&mut world, 300, MT_TROOP);
+        let vile: MobjId = spawn(&mut world, 100, MT_VILE);
+
+        world.damage_mobj(troop, None, Some(vile), 1);
+
+        assert_eq!(world.thinkers.mobj(troop).target, None);
+        assert_eq!(world.thinkers.mobj(troop).threshold, 0);
+    }
+
+    #[test]
+    fn test_damage_mobj_pain_and_see_states() {
+        let mut world: World = create_world(WorldSettings::default());
+        let first: MobjId = spawn(&mut world, 300, MT_POSSESSED);
+        let second: MobjId = spawn(&mut world, 400, MT_POSSESSED);
+        let source: MobjId = spawn(&mut world, 100, MT_TROOP);
+
+        // 8 is under the pain chance of 200
+        world.random.set_prndindex(0);
+        world.damage_mobj(first, None, Some(source), 1);
+
+        let mobj: &Mobj = world.thinkers.mobj(first);
+        assert_ne!(mobj.flags & MF_JUSTHIT, 0);
+        // Not in the spawn state anymore so it stays in pain
+        assert_eq!(mobj.state, S_POSS_PAIN);
+
+        // 220 isn't, so it wakes up and chases the source. Being hit zeroed
+        // its reaction time so A_Chase in the see state fires straight away
+        world.random.set_prndindex(2);
+        world.damage_mobj(second, None, Some(source), 1);
+
+        let mobj: &Mobj = world.thinkers.mobj(second);
+        assert_eq!(mobj.flags & MF_JUSTHIT, 0);
+        assert_eq!(mobj.state, S_POSS_ATK1);
+    }
+
+    #[test]
+    fn test_kill_mobj_drops_items_and_counts_kills() {
+        let mut world: World = create_world(WorldSettings::default());
+        let possessed: MobjId = spawn(&mut world, 300, MT_POSSESSED);
+        let troop: MobjId = spawn(&mut world, 100, MT_TROOP);
+
+        world.damage_mobj(possessed, None, Some(troop), 20);
+
+        let mobj: &Mobj = world.thinkers.mobj(possessed);
+        assert_eq!(mobj.state, S_POSS_DIE1);
+        assert_eq!(mobj.flags & (MF_SHOOTABLE | MF_CORPSE), MF_CORPSE);
+        assert_eq!(mobj.height, Fixed(14 * FRACUNIT));
+        // Monsters killing monsters count for player 1
+        assert_eq!(world.players[0].kill_count, 1);
+
+        let clip: (MobjId, &Mobj) = world
+            .thinkers
+            .mobjs()
+            .find(|(_id, mobj)| mobj.mobj_type == MT_CLIP)
+            .unwrap();
+        assert_ne!(clip.1.flags & MF_DROPPED, 0);
+
+        // Already dead
+        world.damage_mobj(possessed, None, Some(troop), 20);
+        assert_eq!(world.thinkers.mobj(possessed).health, 0);
+    }
+
+    #[test]
+    fn test_kill_mobj_gibs_with_enough_damage() {
+        let mut world: World = create_world(WorldSettings::default());
+        let possessed: MobjId = spawn(&mut world, 300, MT_POSSESSED);
+        let barrel: MobjId = spawn(&mut world, 100, MT_BARREL);
+
+        world.damage_mobj(possessed, None, None, 100);
+        assert_eq!(world.thinkers.mobj(possessed).state, S_POSS_XDIE1);
+
+        // Barrels don't count as kills
+        world.damage_mobj(barrel, None, None, 100);
+        assert_eq!(world.players[0].kill_count, 1);
+    }
+
+    #[test]
+    fn test_damage_mobj_player_armor_and_death() {
+        let mut world: World = create_world(WorldSettings::default());
+        let player: MobjId = spawn_player(&mut world);
+        let troop: MobjId = spawn(&mut world, 300, MT_TROOP);
+        world.players[0].armor_type = 1;
+        world.players[0].armor_points = 5;
+
+        // A third is saved by the armor
+        world.damage_mobj(player, Some(troop), Some(troop), 12);
+        assert_eq!(world.players[0].armor_points, 1);
+        assert_eq!(world.thinkers.mobj(player).health, 92);
+        assert_eq!(world.players[0].health, 92);
+        assert_eq!(world.players[0].damage_count, 8);
+        assert_eq!(world.players[0].attacker, Some(troop));
+
+        // Armor used up
+        world.damage_mobj(player, Some(troop), Some(troop), 12);
+        assert_eq!(world.players[0].armor_type, 0);
+        assert_eq!(world.thinkers.mobj(player).health, 81);
+
+        // God mode blocks damage unless it's a telefrag
+        world.players[0].cheats |= CF_GODMODE;
+        world.damage_mobj(player, Some(troop), Some(troop), 200);
+        assert_eq!(world.thinkers.mobj(player).health, 81);
+
+        world.damage_mobj(player, None, None, 10000);
+        assert_eq!(world.players[0].player_state, PlayerState::Dead);
+        assert_eq!(world.players[0].health, 0);
+        assert_eq!(world.players[0].damage_count, 100);
+        assert_eq!(world.players[0].frags[0], 1);
+        assert_eq!(world.thinkers.mobj(player).flags & MF_SOLID, 0);
+    }
+
+    #[test]
+    fn test_damage_mobj_halves_player_damage_on_baby() {
+        let mut world: World = create_world(WorldSettings {
+            skill: GameDifficulty::Baby,
+            ..Default::default()
+        });
+        let player: MobjId = spawn_player(&mut world);
+        let troop: MobjId = spawn(&mut world, 300, MT_TROOP);
+
+        world.damage_mobj(player, None, None, 11);
+        world.damage_mobj(troop, None, None, 11);
+
+        assert_eq!(world.thinkers.mobj(player).health, 95);
+        assert_eq!(world.thinkers.mobj(troop).health, 49);
+    }
+
+    #[test]
+    fn test_touch_special_thing_picks_up_ammo() {
+        let mut world: World = create_world(WorldSettings::default());
+        let player: MobjId = spawn_player(&mut world);
+        let clip: MobjId = spawn(&mut world, 80, MT_CLIP);
+        world.thinkers.mobj_mut(clip).flags |= MF_DROPPED;
+
+        world.touch_special_thing(clip, player);
+
+        // Dropped clips are half a clip
+        assert_eq!(world.players[0].ammo[AM_CLIP], 55);
+        assert_eq!(world.players[0].message, Some(GOTCLIP));
+        assert_eq!(world.players[0].bonus_count, BONUSADD);
+        assert!(world.thinkers.is_removed(clip));
+        assert_eq!(world.sound_events.last().unwrap().sfx, SFX_ITEMUP);
+    }
+
+    #[test]
+    fn test_touch_special_thing_leaves_what_isnt_needed() {
+        let mut world: World = create_world(WorldSettings::default());
+        let player: MobjId = spawn_player(&mut world);
+        let first: MobjId = spawn(&mut world, 80, MT_MISC0);
+        let second: MobjId = spawn(&mut world, 80, MT_MISC0);
+        let medikit: MobjId = spawn(&mut world, 80, MT_MISC11);
+
+        world.touch_special_thing(first, player);
+        world.touch_special_thing(second, player);
+        world.touch_special_thing(medikit, player);
+
+        assert_eq!(world.players[0].armor_points, 100);
+        assert_eq!(world.players[0].armor_type, 1);
+        assert!(world.thinkers.is_removed(first));
+        assert!(!world.thinkers.is_removed(second));
+        // Already at full health
+        assert!(!world.thinkers.is_removed(medikit));
+
+        world.thinkers.mobj_mut(player).health = 50;
+        world.players[0].health = 50;
+        world.touch_special_thing(medikit, player);
+        assert_eq!(world.thinkers.mobj(player).health, 75);
+        assert_eq!(world.players[0].message, Some(GOTMEDIKIT));
+    }
+
+    #[test]
+    fn test_touch_special_thing_leaves_keys_in_netgames() {
+        let mut world: World = create_world(WorldSettings {
+            netgame: true,
+            ..Default::default()
+        });
+        let player: MobjId = spawn_player(&mut world);
+        let key: MobjId = spawn(&mut world, 80, MT_MISC4);
+
+        world.touch_special_thing(key, player);
+
+        assert!(world.players[0].cards[IT_BLUECARD]);
+        assert_eq!(world.players[0].message, Some(GOTBLUECARD));
+        assert!(!world.thinkers.is_removed(key));
+    }
+
+    #[test]
+    fn test_give_ammo_switches_weapon_when_empty() {
+        let mut world: World = create_world(WorldSettings {
+            skill: GameDifficulty::Nightmare,
+            ..Default::default()
+        });
+        spawn_player(&mut world);
+        world.players[0].ready_weapon = WP_FIST;
+        world.players[0].ammo[AM_CLIP] = 0;
+
+        assert!(world.give_ammo(0, AM_CLIP, 1));
+        // Doubled on nightmare
+        assert_eq!(world.players[0].ammo[AM_CLIP], 20);
+        assert_eq!(world.players[0].pending_weapon, WP_PISTOL);
+
+        world.players[0].ammo[AM_CLIP] = MAXAMMO[AM_CLIP];
+        assert!(!world.give_ammo(0, AM_CLIP, 1));
+    }
+
+    #[test]
+    fn test_give_weapon_stays_in_cooperative() {
+        let mut world: World = create_world(WorldSettings {
+            netgame: true,
+            ..Default::default()
+        });
+        let player: MobjId = spawn_player(&mut world);
+        let shotgun: MobjId = spawn(&mut world, 80, MT_SHOTGUN);
+
+        world.touch_special_thing(shotgun, player);
+
+        assert!(world.players[0].weapon_owned[WP_SHOTGUN]);
+        assert_eq!(world.players[0].ammo[AM_SHELL], 8);
+        assert_eq!(world.players[0].pending_weapon, WP_SHOTGUN);
+        assert!(!world.thinkers.is_removed(shotgun));
+    }
+}
@@ -0,0 +1,800 @@
+use std::fmt::{self, Display};
+
+use crate::fixed::{fixed_div, fixed_mul, Angle, Fixed, FRACBITS};
+use crate::play::blockmap::{Blockmap, MAPBLOCKSHIFT};
+use crate::play::mobj::{MobjId, MAXRADIUS};
+use crate::play::thinker::ThinkerId;
+use crate::wad::format::MAP_LUMP_NAMES;
+use crate::wad::{read_lump_data, DoomFile};
+
+pub const MAPTHING_SIZE: usize = 10;
+pub const MAPLINEDEF_SIZE: usize = 14;
+pub const MAPSIDEDEF_SIZE: usize = 30;
+pub const MAPVERTEX_SIZE: usize = 4;
+pub const MAPSEG_SIZE: usize = 12;
+pub const MAPSUBSECTOR_SIZE: usize = 4;
+pub const MAPNODE_SIZE: usize = 28;
+pub const MAPSECTOR_SIZE: usize = 26;
+
+// Indexes into bounding boxes
+pub const BOXTOP: usize = 0;
+pub const BOXBOTTOM: usize = 1;
+pub const BOXLEFT: usize = 2;
+pub const BOXRIGHT: usize = 3;
+
+// Ceilings with this flat are sky
+pub const SKYFLATNAME: &[u8; 8] = b"F_SKY1\0\0";
+
+// Set on node children that are subsectors instead of other nodes
+pub const NF_SUBSECTOR: u16 = 0x8000;
+
+// Linedef flags
+pub const ML_BLOCKING: i32 = 1;
+pub const ML_BLOCKMONSTERS: i32 = 2;
+pub const ML_TWOSIDED: i32 = 4;
+pub const ML_DONTPEGTOP: i32 = 8;
+pub const ML_DONTPEGBOTTOM: i32 = 16;
+pub const ML_SECRET: i32 = 32;
+pub const ML_SOUNDBLOCK: i32 = 64;
+pub const ML_DONTDRAW: i32 = 128;
+pub const ML_MAPPED: i32 = 256;
+
+// Thing option flags
+pub const MTF_EASY: i16 = 1;
+pub const MTF_NORMAL: i16 = 2;
+pub const MTF_HARD: i16 = 4;
+pub const MTF_AMBUSH: i16 = 8;
+pub const MTF_NOTSINGLE: i16 = 16;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum LevelError {
+    MapNotFound(String),
+    MissingLump(String),
+    InvalidLump(String),
+}
+
+impl Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MapNotFound(map_name) => write!(f, "Unable to find map {}", map_name),
+            Self::MissingLump(lump_name) => write!(f, "Map is missing its {} lump", lump_name),
+            Self::InvalidLump(reason) => write!(f, "Invalid map lump data. {}", reason),
+        }
+    }
+}
+
+// The lumps that follow a map marker, in the same order as MAP_LUMP_NAMES
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MapLumps {
+    pub things: Vec<u8>,
+    pub linedefs: Vec<u8>,
+    pub sidedefs: Vec<u8>,
+    pub vertexes: Vec<u8>,
+    pub segs: Vec<u8>,
+    pub ssectors: Vec<u8>,
+    pub nodes: Vec<u8>,
+    pub sectors: Vec<u8>,
+    pub reject: Vec<u8>,
+    pub blockmap: Vec<u8>,
+}
+
+impl MapLumps {
+    // Like the original this uses the lumps by their position after the
+    // marker, the names aren't checked
+    pub fn from_wad(doom_files: &mut [DoomFile], map_name: &str) -> Result<Self, LevelError> {
+        let (doom_file, marker_index): (&mut DoomFile, usize) = doom_files
+            .iter_mut()
+            .rev()
+            .find_map(|doom_file| {
+                let marker_index = doom_file
+                    .lumps
+                    .iter()
+                    .rposition(|lump| lump.name.trim_end_matches('\0') == map_name);
+                marker_index.map(|index| (doom_file, index))
+            })
+            .ok_or_else(|| LevelError::MapNotFound(map_name.to_owned()))?;
+
+        let mut lumps: Vec<Vec<u8>> = Vec::new();
+        for (offset, lump_name) in MAP_LUMP_NAMES.iter().enumerate() {
+            let lump_index: usize = marker_index + 1 + offset;
+            if lump_index >= doom_file.lumps.len() {
+                return Err(LevelError::MissingLump(lump_name.to_string()));
+            }
+            lumps.push(read_lump_data(doom_file, lump_index));
+        }
+
+        let mut lumps = lumps.into_iter();
+        let mut next = || lumps.next().unwrap();
+        Ok(MapLumps {
+            things: next(),
+            linedefs: next(),
+            sidedefs: next(),
+            vertexes: next(),
+            segs: next(),
+            ssectors: next(),
+            nodes: next(),
+            sectors: next(),
+            reject: next(),
+            blockmap: next(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MapThing {
+    pub x: i16,
+    pub y: i16,
+    pub angle: i16,
+    pub thing_type: i16,
+    pub options: i16,
+}
+
+impl MapThing {
+    pub fn from_lump_data(data: &[u8]) -> Self {
+        MapThing {
+            x: read_i16(data, 0),
+            y: read_i16(data, 2),
+            angle: read_i16(data, 4),
+            thing_type: read_i16(data, 6),
+            options: read_i16(data, 8),
+        }
+    }
+
+    pub fn to_lump_data(&self) -> [u8; MAPTHING_SIZE] {
+        let mut data: [u8; MAPTHING_SIZE] = [0; MAPTHING_SIZE];
+        for (index, value) in [self.x, self.y, self.angle, self.thing_type, self.options]
+            .iter()
+            .enumerate()
+        {
+            data[index * 2..index * 2 + 2].copy_from_slice(&value.to_le_bytes());
+        }
+        data
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Vertex {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sector {
+    pub floor_height: Fixed,
+    pub ceiling_height: Fixed,
+    pub floor_pic: [u8; 8],
+    pub ceiling_pic: [u8; 8],
+    pub light_level: i32,
+    pub special: i32,
+    pub tag: i32,
+    // Mobjs in the sector, linked through Mobj::snext
+    pub thing_list: Option<MobjId>,
+    // Middle of the sector's bounding box, where its sounds come from
+    pub sound_org: Vertex,
+    // Blockmap cells the sector's things could be linked into, indexed by
+    // BOXTOP, etc
+    pub block_box: [i32; 4],
+    pub lines: Vec<usize>,
+    // The door, floor, ceiling or plat thinker moving the sector
+    pub special_data: Option<ThinkerId>,
+    // Thing that made the last noise heard in the sector, and how many
+    // sound blocking lines it went through plus one
+    pub sound_target: Option<MobjId>,
+    pub sound_traversed: i32,
+    // So the noise flood fill only visits a sector once per noise
+    pub valid_count: i32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Side {
+    pub texture_offset: Fixed,
+    pub row_offset: Fixed,
+    pub top_texture: [u8; 8],
+    pub bottom_texture: [u8; 8],
+    pub mid_texture: [u8; 8],
+    pub sector: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SlopeType {
+    #[default]
+    Horizontal,
+    Vertical,
+    Positive,
+    Negative,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Line {
+    pub v1: usize,
+    pub v2: usize,
+    pub dx: Fixed,
+    pub dy: Fixed,
+    pub flags: i32,
+    pub special: i32,
+    pub tag: i32,
+    // Front and back side, the back is None for one sided lines
+    pub side_num: [Option<usize>; 2],
+    pub bbox: [Fixed; 4],
+    pub slope_type: SlopeType,
+    pub front_sector: Option<usize>,
+    pub back_sector: Option<usize>,
+    // So the blockmap iterators only check a line once
+    pub valid_count: i32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Subsector {
+    pub sector: usize,
+    pub num_lines: usize,
+    pub first_line: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Seg {
+    pub v1: usize,
+    pub v2: usize,
+    pub offset: Fixed,
+    pub angle: Angle,
+    pub side_def: usize,
+    pub line_def: usize,
+    pub front_sector: usize,
+    pub back_sector: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Node {
+    // Partition line
+    pub x: Fixed,
+    pub y: Fixed,
+    pub dx: Fixed,
+    pub dy: Fixed,
+    // Bounding boxes of the right and left children
+    pub bbox: [[Fixed; 4]; 2],
+    // NF_SUBSECTOR is set when the child is a subsector
+    pub children: [u16; 2],
+}
+
+impl Node {
+    // R_PointOnSide, 0 is the front/right side and 1 the back/left
+    pub fn point_on_side(&self, x: Fixed, y: Fixed) -> usize {
+        if self.dx == Fixed::ZERO {
+            if x <= self.x {
+                return usize::from(self.dy > Fixed::ZERO);
+            }
+            return usize::from(self.dy < Fixed::ZERO);
+        }
+
+        if self.dy == Fixed::ZERO {
+            if y <= self.y {
+                return usize::from(self.dx < Fixed::ZERO);
+            }
+            return usize::from(self.dx > Fixed::ZERO);
+        }
+
+        let dx: Fixed = x - self.x;
+        let dy: Fixed = y - self.y;
+
+        // Try to quickly decide by looking at the sign bits
+        if (self.dy.0 ^ self.dx.0 ^ dx.0 ^ dy.0) < 0 {
+            if (self.dy.0 ^ dx.0) < 0 {
+                // Left is negative
+                return 1;
+            }
+            return 0;
+        }
+
+        let left: Fixed = fixed_mul(Fixed(self.dy.0 >> FRACBITS), dx);
+        let right: Fixed = fixed_mul(dy, Fixed(self.dx.0 >> FRACBITS));
+
+        if right < left {
+            // Front side
+            return 0;
+        }
+        // Back side
+        1
+    }
+}
+
+// Port of the map loading parts of p_setup.c
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Level {
+    pub vertexes: Vec<Vertex>,
+    pub sectors: Vec<Sector>,
+    pub sides: Vec<Side>,
+    pub lines: Vec<Line>,
+    pub subsectors: Vec<Subsector>,
+    pub segs: Vec<Seg>,
+    pub nodes: Vec<Node>,
+    pub things: Vec<MapThing>,
+    pub blockmap: Blockmap,
+    // Bit per sector pair, set when nothing in the first sector can see
+    // into the second
+    pub reject: Vec<u8>,
+    // Texture names and heights in TEXTURE1/TEXTURE2 order, for the floors
+    // that raise by the height of the lower textures. Filled in by whatever
+    // loads the textures, "-" is texture 0 like the original
+    pub texture_heights: Vec<([u8; 8], Fixed)>,
+}
+
+impl Level {
+    // P_SetupLevel minus spawning the things, which needs the rest of the world
+    pub fn from_lumps(map_lumps: &MapLumps) -> Result<Self, LevelError> {
+        let mut level: Level = Level {
+            vertexes: load_vertexes(&map_lumps.vertexes)?,
+            sectors: load_sectors(&map_lumps.sectors)?,
+            ..Default::default()
+        };
+        level.sides = load_sides(&map_lumps.sidedefs, level.sectors.len())?;
+        level.lines = load_lines(&map_lumps.linedefs, &level.vertexes, &level.sides)?;
+        level.blockmap = if map_lumps.blockmap.is_empty() {
+            Blockmap::build(&level.vertexes, &level.lines)
+        } else {
+            Blockmap::from_lump(&map_lumps.blockmap, level.lines.len())?
+        };
+        level.subsectors = load_subsectors(&map_lumps.ssectors)?;
+        level.nodes = load_nodes(&map_lumps.nodes)?;
+        level.segs = load_segs(&map_lumps.segs, &level.vertexes, &level.lines, &level.sides)?;
+        level.things = records(&map_lumps.things, MAPTHING_SIZE, "THINGS")?
+            .map(MapThing::from_lump_data)
+            .collect();
+        level.reject = map_lumps.reject.clone();
+        level.group_lines()?;
+
+        Ok(level)
+    }
+
+    // R_PointInSubsector
+    pub fn point_in_subsector(&self, x: Fixed, y: Fixed) -> usize {
+        // Single subsector is a special case
+        if self.nodes.is_empty() {
+            return 0;
+        }
+
+        let mut node_num: u16 = u16::try_from(self.nodes.len() - 1).unwrap();
+        while node_num & NF_SUBSECTOR == 0 {
+            let node: &Node = &self.nodes[usize::from(node_num)];
+            node_num = node.children[node.point_on_side(x, y)];
+        }
+
+        usize::from(node_num & !NF_SUBSECTOR)
+    }
+
+    pub fn point_in_sector(&self, x: Fixed, y: Fixed) -> usize {
+        self.subsectors[self.point_in_subsector(x, y)].sector
+    }
+
+    // P_GroupLines. Sets the subsector sectors and builds the line lists
+    // and sound origins for every sector
+    fn group_lines(&mut self) -> Result<(), LevelError> {
+        for subsector in self.subsectors.iter_mut() {
+            let seg: &Seg = self.segs.get(subsector.first_line).ok_or_else(|| {
+                LevelError::InvalidLump(format!(
+                    "Subsector first seg {} does not exist",
+                    subsector.first_line
+                ))
+            })?;
+            subsector.sector = seg.front_sector;
+        }
+
+        for (line_index, line) in self.lines.iter().enumerate() {
+            if let Some(front_sector) = line.front_sector {
+                self.sectors[front_sector].lines.push(line_index);
+            }
+            if let Some(back_sector) = line.back_sector {
+                if line.front_sector != Some(back_sector) {
+                    self.sectors[back_sector].lines.push(line_index);
+                }
+            }
+        }
+
+        for sector in self.sectors.iter_mut() {
+            let mut bbox: [Fixed; 4] = [Fixed::MIN, Fixed::MAX, Fixed::MAX, Fixed::MIN];
+            for line_index in sector.lines.iter() {
+                let line: &Line = &self.lines[*line_index];
+                for vertex_index in [line.v1, line.v2] {
+                    add_to_box(&mut bbox, self.vertexes[vertex_index]);
+                }
+            }
+
+            sector.sound_org = Vertex {
+                x: Fixed(bbox[BOXRIGHT].0.wrapping_add(bbox[BOXLEFT].0) / 2),
+                y: Fixed(bbox[BOXTOP].0.wrapping_add(bbox[BOXBOTTOM].0) / 2),
+            };
+
+            // Adjust bounding box to map blocks
+            let blockmap: &Blockmap = &self.blockmap;
+            let block = |value: Fixed, org: Fixed, adjust: Fixed| -> i32 {
+                (value - org + adjust).0 >> MAPBLOCKSHIFT
+            };
+            sector.block_box[BOXTOP] =
+                block(bbox[BOXTOP], blockmap.org_y, MAXRADIUS).min(blockmap.height - 1);
+            sector.block_box[BOXBOTTOM] = block(bbox[BOXBOTTOM], blockmap.org_y, -MAXRADIUS).max(0);
+            sector.block_box[BOXRIGHT] =
+                block(bbox[BOXRIGHT], blockmap.org_x, MAXRADIUS).min(blockmap.width - 1);
+            sector.block_box[BOXLEFT] = block(bbox[BOXLEFT], blockmap.org_x, -MAXRADIUS).max(0);
+        }
+
+        Ok(())
+    }
+}
+
+// Texture and flat names are compared ignoring case and anything after a
+// terminating NUL
+pub fn names_equal(first: &[u8; 8], second: &[u8; 8]) -> bool {
+    let trim = |name: &[u8; 8]| -> usize { name.iter().position(|c| *c == 0).unwrap_or(8) };
+    first[..trim(first)].eq_ignore_ascii_case(&second[..trim(second)])
+}
+
+// M_AddToBox. The else ifs are the original's, a box that has only been
+// given decreasing values never gets its right/top set
+pub fn add_to_box(bbox: &mut [Fixed; 4], vertex: Vertex) {
+    if vertex.x < bbox[BOXLEFT] {
+        bbox[BOXLEFT] = vertex.x;
+    } else if vertex.x > bbox[BOXRIGHT] {
+        bbox[BOXRIGHT] = vertex.x;
+    }
+    if vertex.y < bbox[BOXBOTTOM] {
+        bbox[BOXBOTTOM] = vertex.y;
+    } else if vertex.y > bbox[BOXTOP] {
+        bbox[BOXTOP] = vertex.y;
+    }
+}
+
+fn read_i16(data: &[u8], offset: usize) -> i16 {
+    i16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+// Indexes in the map lumps are unsigned shorts in practice, maps with more
+// than 32767 of something only work because of that
+fn read_index(data: &[u8], offset: usize) -> usize {
+    usize::from(u16::from_le_bytes([data[offset], data[offset + 1]]))
+}
+
+fn read_fixed(data: &[u8], offset: usize) -> Fixed {
+    Fixed(i32::from(read_i16(data, offset)) << FRACBITS)
+}
+
+fn read_name(data: &[u8], offset: usize) -> [u8; 8] {
+    data[offset..offset + 8].try_into().unwrap()
+}
+
+fn records<'a>(
+    data: &'a [u8],
+    record_size: usize,
+    lump_name: &str,
+) -> Result<std::slice::ChunksExact<'a, u8>, LevelError> {
+    if !data.len().is_multiple_of(record_size) {
+        return Err(LevelError::InvalidLump(format!(
+            "{} size of {} is not a multiple of {}",
+            lump_name,
+            data.len(),
+            record_size
+        )));
+    }
+
+    Ok(data.chunks_exact(record_size))
+}
+
+fn index_error(lump_name: &str, index: usize) -> LevelError {
+    LevelError::InvalidLump(format!("{} references missing index {}", lump_name, index))
+}
+
+fn load_vertexes(data: &[u8]) -> Result<Vec<Vertex>, LevelError> {
+    Ok(records(data, MAPVERTEX_SIZE, "VERTEXES")?
+        .map(|record| Vertex {
+            x: read_fixed(record, 0),
+            y: read_fixed(record, 2),
+        })
+        .collect())
+}
+
+fn load_sectors(data: &[u8]) -> Result<Vec<Sector>, LevelError> {
+    Ok(records(data, MAPSECTOR_SIZE, "SECTORS")?
+        .map(|record| Sector {
+            floor_height: read_fixed(record, 0),
+            ceiling_height: read_fixed(record, 2),
+            floor_pic: read_name(record, 4),
+            ceiling_pic: read_name(record, 12),
+            light_level: i32::from(read_i16(record, 20)),
+            special: i32::from(read_i16(record, 22)),
+            tag: i32::from(read_i16(record, 24)),
+            ..Default::default()
+        })
+        .collect())
+}
+
+fn load_sides(data: &[u8], num_sectors: usize) -> Result<Vec<Side>, LevelError> {
+    records(data, MAPSIDEDEF_SIZE, "SIDEDEFS")?
+        .map(|record| {
+            let sector: usize = read_index(record, 28);
+            if sector >= num_sectors {
+                return Err(index_error("SIDEDEFS", sector));
+            }
+
+            Ok(Side {
+                texture_offset: read_fixed(record, 0),
+                row_offset: read_fixed(record, 2),
+                top_texture: read_name(record, 4),
+                bottom_texture: read_name(record, 12),
+                mid_texture: read_name(record, 20),
+                sector,
+            })
+        })
+        .collect()
+}
+
+fn load_lines(data: &[u8], vertexes: &[Vertex], sides: &[Side]) -> Result<Vec<Line>, LevelError> {
+    records(data, MAPLINEDEF_SIZE, "LINEDEFS")?
+        .map(|record| {
+            let v1: usize = read_index(record, 0);
+            let v2: usize = read_index(record, 2);
+            let (vertex1, vertex2): (Vertex, Vertex) = match (vertexes.get(v1), vertexes.get(v2)) {
+                (Some(vertex1), Some(vertex2)) => (*vertex1, *vertex2),
+                _ => return Err(index_error("LINEDEFS", v1.max(v2))),
+            };
+
+            let mut side_num: [Option<usize>; 2] = [None; 2];
+            for (side, offset) in side_num.iter_mut().zip([10, 12]) {
+                // -1 is no side
+                if read_i16(record, offset) != -1 {
+                    let side_index: usize = read_index(record, offset);
+                    if side_index >= sides.len() {
+                        return Err(index_error("LINEDEFS", side_index));
+                    }
+                    *side = Some(side_index);
+                }
+            }
+
+            let dx: Fixed = vertex2.x - vertex1.x;
+            let dy: Fixed = vertex2.y - vertex1.y;
+            let slope_type: SlopeType = if dx == Fixed::ZERO {
+                SlopeType::Vertical
+            } else if dy == Fixed::ZERO {
+                SlopeType::Horizontal
+            } else if fixed_div(dy, dx) > Fixed::ZERO {
+                SlopeType::Positive
+            } else {
+                SlopeType::Negative
+            };
+
+            Ok(Line {
+                v1,
+                v2,
+                dx,
+                dy,
+                flags: i32::from(read_i16(record, 4)),
+                special: i32::from(read_i16(record, 6)),
+                tag: i32::from(read_i16(record, 8)),
+                side_num,
+                bbox: [
+                    vertex1.y.max(vertex2.y),
+                    vertex1.y.min(vertex2.y),
+                    vertex1.x.min(vertex2.x),
+                    vertex1.x.max(vertex2.x),
+                ],
+                slope_type,
+                front_sector: side_num[0].map(|side| sides[side].sector),
+                back_sector: side_num[1].map(|side| sides[side].sector),
+                valid_count: 0,
+            })
+        })
+        .collect()
+}
+
+fn load_subsectors(data: &[u8]) -> Result<Vec<Subsector>, LevelError> {
+    Ok(records(data, MAPSUBSECTOR_SIZE, "SSECTORS")?
+        .map(|record| Subsector {
+            sector: 0,
+            num_lines: read_index(record, 0),
+            first_line: read_index(record, 2),
+        })
+        .collect())
+}
+
+fn load_nodes(data: &[u8]) -> Result<Vec<Node>, LevelError> {
+    Ok(records(data, MAPNODE_SIZE, "NODES")?
+        .map(|record| {
+            let mut bbox: [[Fixed; 4]; 2] = [[Fixed::ZERO; 4]; 2];
+            for (child, child_bbox) in bbox.iter_mut().enumerate() {
+                for (index, coord) in child_bbox.iter_mut().enumerate() {
+                    *coord = read_fixed(record, 8 + child * 8 + index * 2);
+                }
+            }
+
+            Node {
+                x: read_fixed(record, 0),
+                y: read_fixed(record, 2),
+                dx: read_fixed(record, 4),
+                dy: read_fixed(record, 6),
+                bbox,
+                children: [
+                    u16::from_le_bytes([record[24], record[25]]),
+                    u16::from_le_bytes([record[26], record[27]]),
+                ],
+            }
+        })
+        .collect())
+}
+
+fn load_segs(
+    data: &[u8],
+    vertexes: &[Vertex],
+    lines: &[Line],
+    sides: &[Side],
+) -> Result<Vec<Seg>, LevelError> {
+    records(data, MAPSEG_SIZE, "SEGS")?
+        .map(|record| {
+            let v1: usize = read_index(record, 0);
+            let v2: usize = read_index(record, 2);
+            if v1 >= vertexes.len() || v2 >= vertexes.len() {
+                return Err(index_error("SEGS", v1.max(v2)));
+            }
+
+            let line_def: usize = read_index(record, 6);
+            let line: &Line = lines
+                .get(line_def)
+                .ok_or_else(|| index_error("SEGS", line_def))?;
+            let side: usize = read_index(record, 8) & 1;
+            let side_def: usize = line.side_num[side].ok_or_else(|| {
+                LevelError::InvalidLump(format!("Seg uses missing side of line {}", line_def))
+            })?;
+
+            let back_sector: Option<usize> = if line.flags & ML_TWOSIDED != 0 {
+                line.side_num[side ^ 1].map(|side| sides[side].sector)
+            } else {
+                None
+            };
+
+            Ok(Seg {
+                v1,
+                v2,
+                angle: Angle((i32::from(read_i16(record, 4)) << FRACBITS) as u32),
+                offset: read_fixed(record, 10),
+                side_def,
+                line_def,
+                front_sector: sides[side_def].sector,
+                back_sector,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::play::level::{
+        Level, LevelError, MapLumps, MapThing, Node, SlopeType, ML_BLOCKING, ML_TWOSIDED,
+    };
+    use crate::play::test_map::TestMap;
+
+    #[test]
+    fn test_level_error_implements_display_trait() {
+        let level_errors: Vec<LevelError> = vec![
+            LevelError::MapNotFound(String::from("E1M1")),
+            LevelError::MissingLump(String::from("BLOCKMAP")),
+            LevelError::InvalidLump(String::from("test")),
+        ];
+
+        for level_error in level_errors {
+            println!("{}", level_error);
+        }
+    }
+
+    #[test]
+    fn test_map_thing_lump_data_round_trip() {
+        let thing: MapThing = MapThing {
+            x: -128,
+            y: 64,
+            angle: 90,
+            thing_type: 3001,
+            options: 7,
+        };
+
+        assert_eq!(MapThing::from_lump_data(&thing.to_lump_data()), thing);
+    }
+
+    #[test]
+    fn test_node_point_on_side() {
+        // Vertical partition pointing up, the right side is front
+        let node: Node = Node {
+            x: Fixed(0),
+            dy: Fixed(64 * FRACUNIT),
+            ..Default::default()
+        };
+        assert_eq!(node.point_on_side(Fixed(FRACUNIT), Fixed(0)), 0);
+        assert_eq!(node.point_on_side(Fixed(-FRACUNIT), Fixed(0)), 1);
+        assert_eq!(node.point_on_side(Fixed(0), Fixed(0)), 1);
+
+        // Diagonal partition going up and right
+        let node: Node = Node {
+            dx: Fixed(64 * FRACUNIT),
+            dy: Fixed(64 * FRACUNIT),
+            ..Default::default()
+        };
+        assert_eq!(
+            node.point_on_side(Fixed(10 * FRACUNIT), Fixed(-10 * FRACUNIT)),
+            0
+        );
+        assert_eq!(
+            node.point_on_side(Fixed(-10 * FRACUNIT), Fixed(10 * FRACUNIT)),
+            1
+        );
+    }
+
+    #[test]
+    fn test_level_from_lumps_loads_test_map() {
+        let level: Level = Level::from_lumps(
+            &TestMap::new()
+                .room(128, 0, 128)
+                .room(128, 24, 100)
+                .to_map_lumps(),
+        )
+        .unwrap();
+
+        assert_eq!(level.sectors.len(), 2);
+        assert_eq!(level.sectors[1].floor_height, Fixed(24 * FRACUNIT));
+        assert_eq!(level.sectors[1].ceiling_height, Fixed(100 * FRACUNIT));
+        assert_eq!(level.subsectors.len(), 2);
+        assert_eq!(level.nodes.len(), 1);
+        // Blockmap starts 8 units off the map, blocks are extended by
+        // MAXRADIUS
+        assert_eq!(level.sectors[1].block_box, [2, 0, 0, 2]);
+
+        let shared_line = level
+            .lines
+            .iter()
+            .find(|line| line.flags & ML_TWOSIDED != 0)
+            .unwrap();
+        assert_eq!(shared_line.front_sector, Some(1));
+        assert_eq!(shared_line.back_sector, Some(0));
+        assert_eq!(shared_line.slope_type, SlopeType::Vertical);
+        assert!(level
+            .lines
+            .iter()
+            .filter(|line| line.flags & ML_BLOCKING != 0)
+            .all(|line| line.back_sector.is_none()));
+
+        // Sector 0 has 3 outside walls and the shared line
+        assert_eq!(level.sectors[0].lines.len(), 4);
+        assert_eq!(level.sectors[0].sound_org.x, Fixed(64 * FRACUNIT));
+        assert_eq!(level.sectors[1].sound_org.x, Fixed(192 * FRACUNIT));
+    }
+
+    #[test]
+    fn test_level_point_in_subsector() {
+        let level: Level = Level::from_lumps(
+            &TestMap::new()
+                .room(128, 0, 128)
+                .room(64, 0, 128)
+                .room(128, 0, 128)
+                .to_map_lumps(),
+        )
+        .unwrap();
+
+        let point = |x: i32| level.point_in_sector(Fixed(x * FRACUNIT), Fixed(32 * FRACUNIT));
+        assert_eq!(point(10), 0);
+        assert_eq!(point(128), 0);
+        assert_eq!(point(129), 1);
+        assert_eq!(point(190), 1);
+        assert_eq!(point(250), 2);
+    }
+
+    #[test]
+    fn test_level_from_lumps_returns_error_for_bad_lumps() {
+        let mut map_lumps: MapLumps = TestMap::new().room(128, 0, 128).to_map_lumps();
+        map_lumps.vertexes.pop();
+        assert!(matches!(
+            Level::from_lumps(&map_lumps),
+            Err(LevelError::InvalidLump(_))
+        ));
+
+        let mut map_lumps: MapLumps = TestMap::new().room(128, 0, 128).to_map_lumps();
+        // Point the first sidedef at a sector that doesn't exist
+        map_lumps.sidedefs[28] = 9;
+        assert!(matches!(
+            Level::from_lumps(&map_lumps),
+            Err(LevelError::InvalidLump(_))
+        ));
+    }
+}
@@ -0,0 +1,419 @@
+// Port of p_lights.c. Sector light effects and the line specials that
+// change light levels
+use crate::play::thinker::{Thinker, ThinkerId};
+use crate::play::world::World;
+
+pub const GLOWSPEED: i32 = 8;
+pub const STROBEBRIGHT: i32 = 5;
+pub const FASTDARK: i32 = 15;
+pub const SLOWDARK: i32 = 35;
+
+// fireflicker_t
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FireFlicker {
+    pub sector: usize,
+    pub count: i32,
+    pub max_light: i32,
+    pub min_light: i32,
+}
+
+// lightflash_t
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LightFlash {
+    pub sector: usize,
+    pub count: i32,
+    pub max_light: i32,
+    pub min_light: i32,
+    pub max_time: i32,
+    pub min_time: i32,
+}
+
+// strobe_t
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Strobe {
+    pub sector: usize,
+    pub count: i32,
+    pub min_light: i32,
+    pub max_light: i32,
+    pub dark_time: i32,
+    pub bright_time: i32,
+}
+
+// glow_t
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glow {
+    pub sector: usize,
+    pub min_light: i32,
+    pub max_light: i32,
+    pub direction: i32,
+}
+
+impl World {
+    // T_FireFlicker
+    pub fn fire_flicker_thinker(&mut self, id: ThinkerId) {
+        let mut flick: FireFlicker = match self.thinkers.get(id) {
+            Some(Thinker::FireFlicker(flick)) => *flick,
+            _ => return,
+        };
+
+        flick.count -= 1;
+        if flick.count == 0 {
+            let amount: i32 = (self.random.p_random() & 3) * 16;
+            let light_level: &mut i32 = &mut self.level.sectors[flick.sector].light_level;
+
+            if *light_level - amount < flick.min_light {
+                *light_level = flick.min_light;
+            } else {
+                *light_level = flick.max_light - amount;
+            }
+
+            flick.count = 4;
+        }
+
+        if let Some(Thinker::FireFlicker(stored)) = self.thinkers.get_mut(id) {
+            *stored = flick;
+        }
+    }
+
+    // P_SpawnFireFlicker
+    pub fn spawn_fire_flicker(&mut self, sector: usize) {
+        // Note that we are resetting sector attributes.
+        // Nothing special about it during gameplay.
+        self.level.sectors[sector].special = 0;
+
+        let light_level: i32 = self.level.sectors[sector].light_level;
+        self.thinkers.add(Thinker::FireFlicker(FireFlicker {
+            sector,
+            count: 4,
+            max_light: light_level,
+            min_light: self.level.find_min_surrounding_light(sector, light_level) + 16,
+        }));
+    }
+
+    // T_LightFlash. Do flashing lights
+    pub fn light_flash_thinker(&mut self, id: ThinkerId) {
+        let mut flash: LightFlash = match self.thinkers.get(id) {
+            Some(Thinker::LightFlash(flash)) => *flash,
+            _ => return,
+        };
+
+        flash.count -= 1;
+        if flash.count == 0 {
+            if self.level.sectors[flash.sector].light_level == flash.max_light {
+                self.level.sectors[flash.sector].light_level = flash.min_light;
+                flash.count = (self.random.p_random() & flash.min_time) + 1;
+            } else {
+                self.level.sectors[flash.sector].light_level = flash.max_light;
+                flash.count = (self.random.p_random() & flash.max_time) + 1;
+            }
+        }
+
+        if let Some(Thinker::LightFlash(stored)) = self.thinkers.get_mut(id) {
+            *stored = flash;
+        }
+    }
+
+    // P_SpawnLightFlash. After the map has been loaded, scan each sector
+    // for specials that spawn thinkers
+    pub fn spawn_light_flash(&mut self, sector: usize) {
+        // nothing special about it during gameplay
+        self.level.sectors[sector].special = 0;
+
+        let light_level: i32 = self.level.sectors[sector].light_level;
+        let max_time: i32 = 64;
+        let count: i32 = (self.random.p_random() & max_time) + 1;
+        self.thinkers.add(Thinker::LightFlash(LightFlash {
+            sector,
+            count,
+            max_light: light_level,
+            min_light: self.level.find_min_surrounding_light(sector, light_level),
+            max_time,
+            min_time: 7,
+        }));
+    }
+
+    // T_StrobeFlash
+    pub fn strobe_flash_thinker(&mut self, id: ThinkerId) {
+        let mut flash: Strobe = match self.thinkers.get(id) {
+            Some(Thinker::Strobe(flash)) => *flash,
+            _ => return,
+        };
+
+        flash.count -= 1;
+        if flash.count == 0 {
+            let light_level: &mut i32 = &mut self.level.sectors[flash.sector].light_level;
+            if *light_level == flash.min_light {
+                *light_level = flash.max_light;
+                flash.count = flash.bright_time;
+            } else {
+                *light_level = flash.min_light;
+                flash.count = flash.dark_time;
+            }
+        }
+
+        if let Some(Thinker::Strobe(stored)) = self.thinkers.get_mut(id) {
+            *stored = flash;
+        }
+    }
+
+    // P_SpawnStrobeFlash. After the map has been loaded, scan each sector
+    // for specials that spawn thinkers
+    pub fn spawn_strobe_flash(&mut self, sector: usize, fast_or_slow: i32, in_sync: bool) {
+        let max_light: i32 = self.level.sectors[sector].light_level;
+        let mut min_light: i32 = self.level.find_min_surrounding_light(sector, max_light);
+        if min_light == max_light {
+            min_light = 0;
+        }
+
+        // nothing special about it during gameplay
+        self.level.sectors[sector].special = 0;
+
+        let count: i32 = if !in_sync {
+            (self.random.p_random() & 7) + 1
+        } else {
+            1
+        };
+        self.thinkers.add(Thinker::Strobe(Strobe {
+            sector,
+            count,
+            min_light,
+            max_light,
+            dark_time: fast_or_slow,
+            bright_time: STROBEBRIGHT,
+        }));
+    }
+
+    // EV_StartLightStrobing. Start strobing lights (usually from a trigger)
+    pub fn start_light_strobing(&mut self, line: usize) {
+        for sector in self.level.tagged_sectors(line) {
+            if self.level.sectors[sector].special_data.is_some() {
+                continue;
+            }
+
+            self.spawn_strobe_flash(sector, SLOWDARK, false);
+        }
+    }
+
+    // EV_TurnTagLightsOff. TURN LINE'S TAG LIGHTS OFF
+    pub fn turn_tag_lights_off(&mut self, line: usize) {
+        for sector in self.level.tagged_sectors(line) {
+            let min: i32 = self
+                .level
+                .find_min_surrounding_light(sector, self.level.sectors[sector].light_level);
+            self.level.sectors[sector].light_level = min;
+        }
+    }
+
+    // EV_LightTurnOn. TURN LINE'S TAG LIGHTS ON. A bright of 0 uses the
+    // brightest neighbour, which the original then kept for every other
+    // tagged sector
+    pub fn light_turn_on(&mut self, line: usize, mut bright: i32) {
+        for sector in self.level.tagged_sectors(line) {
+            // bright = 0 means to search
+            // for highest light level
+            // surrounding sector
+            if bright == 0 {
+                bright = self
+                    .level
+                    .neighbours(sector)
+                    .map(|other| other.light_level)
+                    .fold(bright, i32::max);
+            }
+
+            self.level.sectors[sector].light_level = bright;
+        }
+    }
+
+    // T_Glow. Spawn glowing light
+    pub fn glow_thinker(&mut self, id: ThinkerId) {
+        let mut glow: Glow = match self.thinkers.get(id) {
+            Some(Thinker::Glow(glow)) => *glow,
+            _ => return,
+        };
+
+        let light_level: &mut i32 = &mut self.level.sectors[glow.sector].light_level;
+        match glow.direction {
+            // DOWN
+            -1 => {
+                *light_level -= GLOWSPEED;
+                if *light_level <= glow.min_light {
+                    *light_level += GLOWSPEED;
+                    glow.direction = 1;
+                }
+            }
+            // UP
+            1 => {
+                *light_level += GLOWSPEED;
+                if *light_level >= glow.max_light {
+                    *light_level -= GLOWSPEED;
+                    glow.direction = -1;
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(Thinker::Glow(stored)) = self.thinkers.get_mut(id) {
+            *stored = glow;
+        }
+    }
+
+    // P_SpawnGlowingLight
+    pub fn spawn_glowing_light(&mut self, sector: usize) {
+        let light_level: i32 = self.level.sectors[sector].light_level;
+        self.thinkers.add(Thinker::Glow(Glow {
+            sector,
+            min_light: self.level.find_min_surrounding_light(sector, light_level),
+            max_light: light_level,
+            direction: -1,
+        }));
+
+        self.level.sectors[sector].special = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::play::lights::SLOWDARK;
+    use crate::play::test_map::{TestMap, TestRoom};
+    use crate::play::thinker::Thinker;
+    use crate::play::world::World;
+
+    fn lit(light_level: i16, special: i16, tag: i16) -> TestRoom {
+        TestRoom {
+            width: 128,
+            floor_height: 0,
+            ceiling_height: 128,
+            light_level,
+            special,
+            tag,
+        }
+    }
+
+    // A room with the given sector special between a room lit at 100 and
+    // one lit at 200
+    fn light_map(light_level: i16, special: i16) -> TestMap {
+        TestMap::new()
+            .room_with(lit(100, 0, 0))
+            .room_with(lit(light_level, special, 1))
+            .room_with(lit(200, 0, 0))
+            .line_special(0, 12, 1)
+    }
+
+    fn lights_over(world: &mut World, tics: i32) -> Vec<i32> {
+        (0..tics)
+            .map(|_| {
+                world.tick();
+                world.level.sectors[1].light_level
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_glowing_light_bounces_between_levels() {
+        let mut world: World = TestMap::new()
+            .room_with(lit(100, 0, 0))
+            .room_with(lit(160, 8, 0))
+            .to_world();
+        assert_eq!(world.level.sectors[1].special, 0);
+
+        let lights: Vec<i32> = lights_over(&mut world, 16);
+        assert_eq!(
+            lights,
+            vec![152, 144, 136, 128, 120, 112, 104, 104, 112, 120, 128, 136, 144, 152, 152, 144]
+        );
+    }
+
+    #[test]
+    fn test_sync_strobe_flash() {
+        let mut world: World = TestMap::new()
+            .room_with(lit(100, 0, 0))
+            .room_with(lit(160, 12, 0))
+            .to_world();
+
+        let lights: Vec<i32> = lights_over(&mut world, 41);
+        assert_eq!(lights[0], 100);
+        assert_eq!(lights[34], 100);
+        assert_eq!(lights[35], 160);
+        assert_eq!(lights[39], 160);
+        assert_eq!(lights[40], 100);
+    }
+
+    #[test]
+    fn test_strobe_flash_goes_dark_without_darker_neighbours() {
+        let mut world: World = TestMap::new()
+            .room_with(lit(160, 0, 0))
+            .room_with(lit(160, 13, 0))
+            .to_world();
+
+        let lights: Vec<i32> = lights_over(&mut world, 1);
+        assert_eq!(lights, vec![0]);
+    }
+
+    #[test]
+    fn test_random_lights_stay_in_range() {
+        let mut world: World = TestMap::new()
+            .room_with(lit(160, 17, 0))
+            .room_with(lit(100, 0, 0))
+            .room_with(lit(160, 1, 0))
+            .to_world();
+        assert_eq!(world.level.sectors[0].special, 0);
+        assert_eq!(world.level.sectors[2].special, 0);
+
+        let mut flashes: Vec<i32> = Vec::new();
+        let mut flickers: Vec<i32> = Vec::new();
+        for _ in 0..200 {
+            world.tick();
+            flickers.push(world.level.sectors[0].light_level);
+            flashes.push(world.level.sectors[2].light_level);
+        }
+
+        // Flickering lights only ever use their two levels
+        assert!(flashes.iter().all(|light| *light == 100 || *light == 160));
+        assert!(flashes.contains(&100));
+
+        // Fire flickers go down in steps of 16 but never below the darkest
+        // neighbour + 16
+        assert!(flickers
+            .iter()
+            .all(|light| [116, 128, 144, 160].contains(light)));
+        assert!(flickers.contains(&116));
+    }
+
+    #[test]
+    fn test_line_light_specials() {
+        let mut world: World = light_map(160, 0).to_world();
+        let line: usize = world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == 1)
+            .unwrap();
+
+        // 0 means the brightest neighbour
+        world.light_turn_on(line, 0);
+        assert_eq!(world.level.sectors[1].light_level, 200);
+        world.light_turn_on(line, 35);
+        assert_eq!(world.level.sectors[1].light_level, 35);
+
+        world.level.sectors[1].light_level = 160;
+        world.turn_tag_lights_off(line);
+        assert_eq!(world.level.sectors[1].light_level, 100);
+
+        // Strobes don't claim the sector, so each trigger adds another
+        world.level.sectors[1].light_level = 160;
+        world.start_light_strobing(line);
+        world.start_light_strobing(line);
+        let strobes: Vec<_> = world
+            .thinkers
+            .iter()
+            .filter_map(|(_id, thinker)| match thinker {
+                Thinker::Strobe(strobe) => Some(*strobe),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(strobes.len(), 2);
+        assert_eq!(strobes[0].dark_time, SLOWDARK);
+        assert_eq!(strobes[0].min_light, 100);
+        assert_eq!(strobes[0].max_light, 160);
+    }
+}
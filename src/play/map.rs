@@ -0,0 +1,1520 @@
+// Port of p_map.c. Movement, collision, hitscans, using lines and the
+// height checks for moving sectors
+use crate::fixed::{
+    aprox_distance, fixed_div, fixed_mul, point_to_angle, Angle, Fixed, ANG180, FRACBITS, FRACUNIT,
+};
+use crate::info::mobjinfo::{MT_BLOOD, MT_BRUISER, MT_CYBORG, MT_KNIGHT, MT_PLAYER, MT_SPIDER};
+use crate::info::states::S_GIBS;
+use crate::info::{MobjType, StateNum};
+use crate::play::blockmap::MAPBLOCKSHIFT;
+use crate::play::level::{
+    Line, Sector, SlopeType, BOXBOTTOM, BOXLEFT, BOXRIGHT, BOXTOP, ML_BLOCKING, ML_BLOCKMONSTERS,
+    ML_TWOSIDED, SKYFLATNAME,
+};
+use crate::play::maputl::{Intercept, InterceptTarget, LineOpening, PT_ADDLINES, PT_ADDTHINGS};
+use crate::play::mobj::{
+    Mobj, MobjId, MAXRADIUS, MF_DROPOFF, MF_DROPPED, MF_FLOAT, MF_MISSILE, MF_NOBLOOD, MF_NOCLIP,
+    MF_PICKUP, MF_SHOOTABLE, MF_SKULLFLY, MF_SOLID, MF_SPECIAL, MF_TELEPORT,
+};
+use crate::play::world::World;
+use crate::play::CompatibilityLevel;
+use crate::sound::sfx::SFX_NOWAY;
+
+// The most a mobj can step up in one move
+pub const MAXSTEPHEIGHT: Fixed = Fixed(24 * FRACUNIT);
+
+pub const MELEERANGE: Fixed = Fixed(64 * FRACUNIT);
+pub const MISSILERANGE: Fixed = Fixed(32 * 64 * FRACUNIT);
+pub const USERANGE: Fixed = Fixed(64 * FRACUNIT);
+
+// The tm* globals from p_map.c. P_CheckPosition leaves its results here for
+// P_TryMove and the monster movement to look at
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PositionCheck {
+    pub thing: Option<MobjId>,
+    pub flags: u32,
+    pub x: Fixed,
+    pub y: Fixed,
+    pub bbox: [Fixed; 4],
+    pub floor_z: Fixed,
+    pub ceiling_z: Fixed,
+    pub dropoff_z: Fixed,
+    // If true, the move would be ok if within floor_z - ceiling_z
+    pub float_ok: bool,
+    // Keeps track of the line that lowers the ceiling, so missiles don't
+    // explode against sky hack walls
+    pub ceiling_line: Option<usize>,
+    // Special lines touched, they're checked for being crossed after the
+    // move. The original overflowed a fixed size array past 8 lines
+    pub spec_hit: Vec<usize>,
+}
+
+// The shootthing, aimslope, linetarget, etc globals from p_map.c shared by
+// P_AimLineAttack and P_LineAttack
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LineAttack {
+    pub shoot_thing: Option<MobjId>,
+    // Height the shot starts at
+    pub shoot_z: Fixed,
+    pub attack_range: Fixed,
+    pub aim_slope: Fixed,
+    // Slopes to the top and bottom of the gap the aim can go through
+    pub top_slope: Fixed,
+    pub bottom_slope: Fixed,
+    pub damage: i32,
+    // Who got hit (or None)
+    pub line_target: Option<MobjId>,
+}
+
+// Closest lines hit by the traces in P_SlideMove
+#[derive(Clone, Copy, Debug)]
+struct SlideHit {
+    best_frac: Fixed,
+    best_line: Option<usize>,
+}
+
+impl World {
+    // P_CheckPosition. This is purely informative, nothing is modified
+    // (except things picked up).
+    //
+    // In:
+    //  a mobj_t (can be valid or invalid)
+    //  a position to be checked
+    //   (doesn't need to be related to the mobj_t->x,y)
+    //
+    // During:
+    //  special things are touched if MF_PICKUP
+    //  early out on solid lines?
+    //
+    // Out:
+    //  newsubsec
+    //  floorz
+    //  ceilingz
+    //  tmdropoffz
+    //   the lowest point contacted
+    //   (monsters won't move to a dropoff)
+    //  speciallines[]
+    //  numspeciallines
+    pub fn check_position(&mut self, id: MobjId, x: Fixed, y: Fixed) -> bool {
+        let (flags, radius): (u32, Fixed) = {
+            let thing: &Mobj = self.thinkers.mobj(id);
+            (thing.flags, thing.radius)
+        };
+
+        let subsector: usize = self.level.point_in_subsector(x, y);
+        let sector: usize = self.level.subsectors[subsector].sector;
+
+        // The base floor / ceiling is from the subsector that contains the
+        // point. Any contacted lines the step closer together will adjust
+        // them
+        let check: &mut PositionCheck = &mut self.position_check;
+        check.thing = Some(id);
+        check.flags = flags;
+        check.x = x;
+        check.y = y;
+        check.bbox[BOXTOP] = y + radius;
+        check.bbox[BOXBOTTOM] = y - radius;
+        check.bbox[BOXRIGHT] = x + radius;
+        check.bbox[BOXLEFT] = x - radius;
+        check.ceiling_line = None;
+        check.floor_z = self.level.sectors[sector].floor_height;
+        check.dropoff_z = self.level.sectors[sector].floor_height;
+        check.ceiling_z = self.level.sectors[sector].ceiling_height;
+        check.spec_hit.clear();
+
+        self.valid_count += 1;
+
+        if flags & MF_NOCLIP != 0 {
+            return true;
+        }
+
+        // Check things first, possibly picking things up. The bounding box
+        // is extended by MAXRADIUS because mobj_ts are grouped into
+        // mapblocks based on their origin point, and can overlap into
+        // adjacent blocks by up to MAXRADIUS units
+        let bbox: [Fixed; 4] = self.position_check.bbox;
+        let org_x: Fixed = self.level.blockmap.org_x;
+        let org_y: Fixed = self.level.blockmap.org_y;
+        let block = |value: Fixed, org: Fixed, adjust: Fixed| -> i32 {
+            (value - org + adjust).0 >> MAPBLOCKSHIFT
+        };
+
+        let xl: i32 = block(bbox[BOXLEFT], org_x, -MAXRADIUS);
+        let xh: i32 = block(bbox[BOXRIGHT], org_x, MAXRADIUS);
+        let yl: i32 = block(bbox[BOXBOTTOM], org_y, -MAXRADIUS);
+        let yh: i32 = block(bbox[BOXTOP], org_y, MAXRADIUS);
+
+        for block_x in xl..=xh {
+            for block_y in yl..=yh {
+                if !self.block_things_iterator(block_x, block_y, World::check_thing) {
+                    return false;
+                }
+            }
+        }
+
+        // Check lines
+        let xl: i32 = block(bbox[BOXLEFT], org_x, Fixed::ZERO);
+        let xh: i32 = block(bbox[BOXRIGHT], org_x, Fixed::ZERO);
+        let yl: i32 = block(bbox[BOXBOTTOM], org_y, Fixed::ZERO);
+        let yh: i32 = block(bbox[BOXTOP], org_y, Fixed::ZERO);
+
+        for block_x in xl..=xh {
+            for block_y in yl..=yh {
+                if !self.block_lines_iterator(block_x, block_y, World::check_line) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    // PIT_CheckLine. Adjusts the floor and ceiling heights for the lines the
+    // mobj would be touching
+    fn check_line(&mut self, line: usize) -> bool {
+        let ld: &Line = &self.level.lines[line];
+        let bbox: [Fixed; 4] = self.position_check.bbox;
+
+        if bbox[BOXRIGHT] <= ld.bbox[BOXLEFT]
+            || bbox[BOXLEFT] >= ld.bbox[BOXRIGHT]
+            || bbox[BOXTOP] <= ld.bbox[BOXBOTTOM]
+            || bbox[BOXBOTTOM] >= ld.bbox[BOXTOP]
+        {
+            return true;
+        }
+
+        if self.level.box_on_line_side(&bbox, line).is_some() {
+            return true;
+        }
+
+        // A line has been hit
+
+        // The moving thing's destination position will cross the given
+        // line. If this should not be allowed, return false. If the line is
+        // special, keep track of it to process later if the move is proven
+        // ok. NOTE: specials are NOT sorted by order, so two special lines
+        // that are only 8 pixels apart could be crossed in either order
+        if ld.back_sector.is_none() {
+            // One sided line
+            return false;
+        }
+
+        let (line_flags, special): (i32, i32) = (ld.flags, ld.special);
+        let thing: &Mobj = self.thinkers.mobj(self.position_check.thing.unwrap());
+        if thing.flags & MF_MISSILE == 0 {
+            if line_flags & ML_BLOCKING != 0 {
+                // Explicitly blocking everything
+                return false;
+            }
+
+            if thing.player.is_none() && line_flags & ML_BLOCKMONSTERS != 0 {
+                // Block monsters only
+                return false;
+            }
+        }
+
+        let opening: LineOpening = self.level.line_opening(line);
+        let check: &mut PositionCheck = &mut self.position_check;
+
+        // Adjust floor / ceiling heights
+        if opening.top < check.ceiling_z {
+            check.ceiling_z = opening.top;
+            check.ceiling_line = Some(line);
+        }
+
+        if opening.bottom > check.floor_z {
+            check.floor_z = opening.bottom;
+        }
+
+        if opening.low_floor < check.dropoff_z {
+            check.dropoff_z = opening.low_floor;
+        }
+
+        // If contacted a special line, add it to the list
+        if special != 0 {
+            check.spec_hit.push(line);
+        }
+
+        true
+    }
+
+    // PIT_CheckThing
+    fn check_thing(&mut self, id: MobjId) -> bool {
+        let thing: &Mobj = self.thinkers.mobj(id);
+
+        if thing.flags & (MF_SOLID | MF_SPECIAL | MF_SHOOTABLE) == 0 {
+            return true;
+        }
+
+        let tm_id: MobjId = self.position_check.thing.unwrap();
+        let tm_thing: &Mobj = self.thinkers.mobj(tm_id);
+        let block_dist: Fixed = thing.radius + tm_thing.radius;
+
+        if (thing.x - self.position_check.x).abs() >= block_dist
+            || (thing.y - self.position_check.y).abs() >= block_dist
+        {
+            // Didn't hit it
+            return true;
+        }
+
+        // Don't clip against self
+        if id == tm_id {
+            return true;
+        }
+
+        // Check for skulls slamming into things
+        if tm_thing.flags & MF_SKULLFLY != 0 {
+            let random: i32 = self.random.p_random();
+            let damage: i32 = (random % 8 + 1) * tm_thing.info().damage;
+            self.damage_mobj(id, Some(tm_id), Some(tm_id), damage);
+
+            let tm_thing: &mut Mobj = self.thinkers.mobj_mut(tm_id);
+            tm_thing.flags &= !MF_SKULLFLY;
+            tm_thing.mom_x = Fixed::ZERO;
+            tm_thing.mom_y = Fixed::ZERO;
+            tm_thing.mom_z = Fixed::ZERO;
+            let spawn_state: StateNum = tm_thing.info().spawn_state;
+            self.set_mobj_state(tm_id, spawn_state);
+
+            // Stop moving
+            return false;
+        }
+
+        // Missiles can hit other things
+        if tm_thing.flags & MF_MISSILE != 0 {
+            // See if it went over / under
+            if tm_thing.z > thing.z + thing.height {
+                // Overhead
+                return true;
+            }
+            if tm_thing.z + tm_thing.height < thing.z {
+                // Underneath
+                return true;
+            }
+
+            let source: Option<MobjId> = tm_thing.target;
+            let source_type: Option<MobjType> = source
+                .and_then(|source| self.thinkers.get_mobj(source))
+                .map(|source| source.mobj_type);
+
+            if let Some(source_type) = source_type {
+                if source_type == thing.mobj_type
+                    || (source_type == MT_KNIGHT && thing.mobj_type == MT_BRUISER)
+                    || (source_type == MT_BRUISER && thing.mobj_type == MT_KNIGHT)
+                {
+                    // Don't hit same species as originator
+                    if source == Some(id) {
+                        return true;
+                    }
+
+                    if thing.mobj_type != MT_PLAYER {
+                        // Explode, but do no damage. Let players missile
+                        // other players
+                        return false;
+                    }
+                }
+            }
+
+            if thing.flags & MF_SHOOTABLE == 0 {
+                // Didn't do any damage
+                return thing.flags & MF_SOLID == 0;
+            }
+
+            // Damage / explode
+            let random: i32 = self.random.p_random();
+            let damage: i32 = (random % 8 + 1) * tm_thing.info().damage;
+            let source: Option<MobjId> = source.filter(|source| self.thinkers.contains(*source));
+            self.damage_mobj(id, Some(tm_id), source, damage);
+
+            // Don't traverse any more
+            return false;
+        }
+
+        // Check for special pickup
+        if thing.flags & MF_SPECIAL != 0 {
+            let solid: bool = thing.flags & MF_SOLID != 0;
+            if self.position_check.flags & MF_PICKUP != 0 {
+                // Can remove thing
+                self.touch_special_thing(id, tm_id);
+            }
+            return !solid;
+        }
+
+        thing.flags & MF_SOLID == 0
+    }
+
+    // P_TryMove. Attempt to move to a new position, crossing special lines
+    // unless MF_TELEPORT is set
+    pub fn try_move(&mut self, id: MobjId, x: Fixed, y: Fixed) -> bool {
+        self.position_check.float_ok = false;
+
+        if !self.check_position(id, x, y) {
+            // Solid wall or thing
+            return false;
+        }
+
+        let (floor_z, ceiling_z, dropoff_z): (Fixed, Fixed, Fixed) = (
+            self.position_check.floor_z,
+            self.position_check.ceiling_z,
+            self.position_check.dropoff_z,
+        );
+        let thing: &Mobj = self.thinkers.mobj(id);
+
+        if thing.flags & MF_NOCLIP == 0 {
+            if ceiling_z - floor_z < thing.height {
+                // Doesn't fit
+                return false;
+            }
+
+            self.position_check.float_ok = true;
+
+            if thing.flags & MF_TELEPORT == 0 && ceiling_z - thing.z < thing.height {
+                // Mobj must lower itself to fit
+                return false;
+            }
+
+            if thing.flags & MF_TELEPORT == 0 && floor_z - thing.z > MAXSTEPHEIGHT {
+                // Too big a step up
+                return false;
+            }
+
+            if thing.flags & (MF_DROPOFF | MF_FLOAT) == 0 && floor_z - dropoff_z > MAXSTEPHEIGHT {
+                // Don't stand over a dropoff
+                return false;
+            }
+        }
+
+        // The move is ok, so link the thing into its new position
+        self.unset_thing_position(id);
+
+        let thing: &mut Mobj = self.thinkers.mobj_mut(id);
+        let (old_x, old_y): (Fixed, Fixed) = (thing.x, thing.y);
+        thing.floor_z = floor_z;
+        thing.ceiling_z = ceiling_z;
+        thing.x = x;
+        thing.y = y;
+        let flags: u32 = thing.flags;
+
+        self.set_thing_position(id);
+
+        // If any special lines were hit, do the effect
+        if flags & (MF_TELEPORT | MF_NOCLIP) == 0 {
+            while let Some(line) = self.position_check.spec_hit.pop() {
+                // See if the line was crossed
+                let side: usize = self.level.point_on_line_side(x, y, line);
+                let old_side: usize = self.level.point_on_line_side(old_x, old_y, line);
+                if side != old_side && self.level.lines[line].special != 0 {
+                    self.cross_special_line(line, old_side, id);
+                }
+            }
+        }
+
+        true
+    }
+
+    // P_TeleportMove. Kills anything occupying the position, monsters only
+    // telefrag on MAP30
+    pub fn teleport_move(&mut self, id: MobjId, x: Fixed, y: Fixed) -> bool {
+        let (flags, radius): (u32, Fixed) = {
+            let thing: &Mobj = self.thinkers.mobj(id);
+            (thing.flags, thing.radius)
+        };
+
+        let subsector: usize = self.level.point_in_subsector(x, y);
+        let sector: usize = self.level.subsectors[subsector].sector;
+
+        // The base floor/ceiling is from the subsector that contains the
+        // point. Any contacted lines the step closer together will adjust
+        // them
+        let check: &mut PositionCheck = &mut self.position_check;
+        check.thing = Some(id);
+        check.flags = flags;
+        check.x = x;
+        check.y = y;
+        check.bbox[BOXTOP] = y + radius;
+        check.bbox[BOXBOTTOM] = y - radius;
+        check.bbox[BOXRIGHT] = x + radius;
+        check.bbox[BOXLEFT] = x - radius;
+        check.ceiling_line = None;
+        check.floor_z = self.level.sectors[sector].floor_height;
+        check.dropoff_z = self.level.sectors[sector].floor_height;
+        check.ceiling_z = self.level.sectors[sector].ceiling_height;
+        check.spec_hit.clear();
+
+        self.valid_count += 1;
+
+        // Stomp on any things contacted
+        let bbox: [Fixed; 4] = self.position_check.bbox;
+        let org_x: Fixed = self.level.blockmap.org_x;
+        let org_y: Fixed = self.level.blockmap.org_y;
+        let xl: i32 = (bbox[BOXLEFT] - org_x - MAXRADIUS).0 >> MAPBLOCKSHIFT;
+        let xh: i32 = (bbox[BOXRIGHT] - org_x + MAXRADIUS).0 >> MAPBLOCKSHIFT;
+        let yl: i32 = (bbox[BOXBOTTOM] - org_y - MAXRADIUS).0 >> MAPBLOCKSHIFT;
+        let yh: i32 = (bbox[BOXTOP] - org_y + MAXRADIUS).0 >> MAPBLOCKSHIFT;
+
+        for block_x in xl..=xh {
+            for block_y in yl..=yh {
+                if !self.block_things_iterator(block_x, block_y, World::stomp_thing) {
+                    return false;
+                }
+            }
+        }
+
+        // The move is ok, so link the thing into its new position
+        self.unset_thing_position(id);
+
+        let (floor_z, ceiling_z): (Fixed, Fixed) =
+            (self.position_check.floor_z, self.position_check.ceiling_z);
+        let thing: &mut Mobj = self.thinkers.mobj_mut(id);
+        thing.floor_z = floor_z;
+        thing.ceiling_z = ceiling_z;
+        thing.x = x;
+        thing.y = y;
+
+        self.set_thing_position(id);
+
+        true
+    }
+
+    // PIT_StompThing
+    fn stomp_thing(&mut self, id: MobjId) -> bool {
+        let thing: &Mobj = self.thinkers.mobj(id);
+
+        if thing.flags & MF_SHOOTABLE == 0 {
+            return true;
+        }
+
+        let tm_id: MobjId = self.position_check.thing.unwrap();
+        let tm_thing: &Mobj = self.thinkers.mobj(tm_id);
+        let block_dist: Fixed = thing.radius + tm_thing.radius;
+
+        if (thing.x - self.position_check.x).abs() >= block_dist
+            || (thing.y - self.position_check.y).abs() >= block_dist
+        {
+            // Didn't hit it
+            return true;
+        }
+
+        // Don't clip against self
+        if id == tm_id {
+            return true;
+        }
+
+        // Monsters don't stomp things except on boss level
+        if tm_thing.player.is_none() && self.settings.map != 30 {
+            return false;
+        }
+
+        self.damage_mobj(id, Some(tm_id), Some(tm_id), 10000);
+
+        true
+    }
+
+    // P_ThingHeightClip. Takes a valid thing and adjusts the thing->floorz,
+    // thing->ceilingz, and possibly thing->z. This is called for all nearby
+    // monsters whenever a sector changes height. If the thing doesn't fit,
+    // the z will be set to the lowest value and false will be returned
+    pub fn thing_height_clip(&mut self, id: MobjId) -> bool {
+        let (x, y, on_floor): (Fixed, Fixed, bool) = {
+            let thing: &Mobj = self.thinkers.mobj(id);
+            (thing.x, thing.y, thing.z == thing.floor_z)
+        };
+
+        self.check_position(id, x, y);
+
+        // What about stranding a monster partially off an edge?
+        let (floor_z, ceiling_z): (Fixed, Fixed) =
+            (self.position_check.floor_z, self.position_check.ceiling_z);
+        let thing: &mut Mobj = self.thinkers.mobj_mut(id);
+        thing.floor_z = floor_z;
+        thing.ceiling_z = ceiling_z;
+
+        if on_floor {
+            // Walking monsters rise and fall with the floor
+            thing.z = thing.floor_z;
+        } else if thing.z + thing.height > thing.ceiling_z {
+            // Don't adjust a floating monster unless forced to
+            thing.z = thing.ceiling_z - thing.height;
+        }
+
+        thing.ceiling_z - thing.floor_z >= thing.height
+    }
+
+    // P_HitSlideLine. Adjusts the move so it runs along the wall
+    fn hit_slide_line(&self, id: MobjId, line: usize, move_x: &mut Fixed, move_y: &mut Fixed) {
+        let ld: &Line = &self.level.lines[line];
+
+        if ld.slope_type == SlopeType::Horizontal {
+            *move_y = Fixed::ZERO;
+            return;
+        }
+
+        if ld.slope_type == SlopeType::Vertical {
+            *move_x = Fixed::ZERO;
+            return;
+        }
+
+        let slide_mo: &Mobj = self.thinkers.mobj(id);
+        let side: usize = self.level.point_on_line_side(slide_mo.x, slide_mo.y, line);
+
+        let mut line_angle: Angle = point_to_angle(Fixed::ZERO, Fixed::ZERO, ld.dx, ld.dy);
+        if side == 1 {
+            line_angle += Angle::ANG180;
+        }
+
+        let move_angle: Angle = point_to_angle(Fixed::ZERO, Fixed::ZERO, *move_x, *move_y);
+        let mut delta_angle: Angle = move_angle - line_angle;
+
+        // The original turned moves more than 180 degrees off the line
+        // around, which sends the mobj back the way it came
+        if delta_angle.0 > ANG180 && self.settings.compatibility == CompatibilityLevel::Vanilla {
+            delta_angle += Angle::ANG180;
+        }
+
+        let move_len: Fixed = aprox_distance(*move_x, *move_y);
+        let new_len: Fixed = fixed_mul(move_len, delta_angle.cos());
+
+        *move_x = fixed_mul(new_len, line_angle.cos());
+        *move_y = fixed_mul(new_len, line_angle.sin());
+    }
+
+    // PTR_SlideTraverse
+    fn slide_traverse(&mut self, id: MobjId, intercept: &Intercept, hit: &mut SlideHit) -> bool {
+        let line: usize = match intercept.target {
+            InterceptTarget::Line(line) => line,
+            InterceptTarget::Thing(_) => panic!("PTR_SlideTraverse: not a line?"),
+        };
+        let slide_mo: &Mobj = self.thinkers.mobj(id);
+
+        let blocking: bool = if self.level.lines[line].flags & ML_TWOSIDED == 0 {
+            if self.level.point_on_line_side(slide_mo.x, slide_mo.y, line) != 0 {
+                // Don't hit the back side
+                return true;
+            }
+            true
+        } else {
+            let opening: LineOpening = self.level.line_opening(line);
+
+            // Doesn't fit, mobj is too high or too big a step up
+            opening.range < slide_mo.height
+                || opening.top - slide_mo.z < slide_mo.height
+                || opening.bottom - slide_mo.z > MAXSTEPHEIGHT
+        };
+
+        if !blocking {
+            // This line doesn't block movement
+            return true;
+        }
+
+        // The line does block movement, see if it is closer than best so
+        // far
+        if intercept.frac < hit.best_frac {
+            hit.best_frac = intercept.frac;
+            hit.best_line = Some(line);
+        }
+
+        // Stop
+        false
+    }
+
+    // P_SlideMove. The momx / momy move is bad, so try to slide along a
+    // wall. Find the first line hit, move flush to it, and slide along it.
+    // This is a kludgy mess
+    pub fn slide_move(&mut self, id: MobjId) {
+        let mut hit_count: i32 = 0;
+
+        loop {
+            hit_count += 1;
+            if hit_count == 3 {
+                // Don't loop forever
+                self.stair_step(id);
+                return;
+            }
+
+            let mo: &Mobj = self.thinkers.mobj(id);
+            let (mom_x, mom_y): (Fixed, Fixed) = (mo.mom_x, mo.mom_y);
+
+            // Trace along the three leading corners
+            let (lead_x, trail_x): (Fixed, Fixed) = if mom_x > Fixed::ZERO {
+                (mo.x + mo.radius, mo.x - mo.radius)
+            } else {
+                (mo.x - mo.radius, mo.x + mo.radius)
+            };
+
+            let (lead_y, trail_y): (Fixed, Fixed) = if mom_y > Fixed::ZERO {
+                (mo.y + mo.radius, mo.y - mo.radius)
+            } else {
+                (mo.y - mo.radius, mo.y + mo.radius)
+            };
+
+            let mut hit: SlideHit = SlideHit {
+                best_frac: Fixed(FRACUNIT + 1),
+                best_line: None,
+            };
+
+            for (x, y) in [(lead_x, lead_y), (trail_x, lead_y), (lead_x, trail_y)] {
+                self.path_traverse(
+                    x,
+                    y,
+                    x + mom_x,
+                    y + mom_y,
+                    PT_ADDLINES,
+                    |world, intercept| world.slide_traverse(id, intercept, &mut hit),
+                );
+            }
+
+            // Move up to the wall
+            let best_line: usize = match hit.best_line {
+                Some(best_line) if hit.best_frac != Fixed(FRACUNIT + 1) => best_line,
+                _ => {
+                    // The move most have hit the middle, so stairstep
+                    self.stair_step(id);
+                    return;
+                }
+            };
+
+            // Fudge a bit to make sure it doesn't hit
+            let mut best_frac: Fixed = hit.best_frac - Fixed(0x800);
+            if best_frac > Fixed::ZERO {
+                let (x, y): (Fixed, Fixed) = (
+                    self.thinkers.mobj(id).x + fixed_mul(mom_x, best_frac),
+                    self.thinkers.mobj(id).y + fixed_mul(mom_y, best_frac),
+                );
+
+                if !self.try_move(id, x, y) {
+                    self.stair_step(id);
+                    return;
+                }
+            }
+
+            // Now continue along the wall. First calculate remainder
+            best_frac = Fixed(FRACUNIT) - (best_frac + Fixed(0x800));
+            if best_frac > Fixed::ONE {
+                best_frac = Fixed::ONE;
+            }
+            if best_frac <= Fixed::ZERO {
+                return;
+            }
+
+            let mut move_x: Fixed = fixed_mul(mom_x, best_frac);
+            let mut move_y: Fixed = fixed_mul(mom_y, best_frac);
+
+            // Clip the moves
+            self.hit_slide_line(id, best_line, &mut move_x, &mut move_y);
+
+            let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+            mo.mom_x = move_x;
+            mo.mom_y = move_y;
+            let (x, y): (Fixed, Fixed) = (mo.x + move_x, mo.y + move_y);
+
+            if self.try_move(id, x, y) {
+                return;
+            }
+        }
+    }
+
+    // Used when P_SlideMove can't find the wall, tries moving along each
+    // axis on its own
+    fn stair_step(&mut self, id: MobjId) {
+        let mo: &Mobj = self.thinkers.mobj(id);
+        let (x, y, mom_x, mom_y): (Fixed, Fixed, Fixed, Fixed) = (mo.x, mo.y, mo.mom_x, mo.mom_y);
+
+        if !self.try_move(id, x, y + mom_y) {
+            self.try_move(id, x + mom_x, y);
+        }
+    }
+
+    // Where a line attack would end, attack_range units away from t1
+    fn attack_end(&mut self, id: MobjId, angle: Angle, distance: Fixed) -> (Fixed, Fixed) {
+        let t1: &Mobj = self.thinkers.mobj(id);
+        let x2: Fixed = t1.x + Fixed((distance.0 >> FRACBITS).wrapping_mul(angle.cos().0));
+        let y2: Fixed = t1.y + Fixed((distance.0 >> FRACBITS).wrapping_mul(angle.sin().0));
+
+        self.line_attack.shoot_thing = Some(id);
+        self.line_attack.shoot_z = t1.z + Fixed(t1.height.0 >> 1) + Fixed(8 * FRACUNIT);
+        self.line_attack.attack_range = distance;
+
+        (x2, y2)
+    }
+
+    // PTR_AimTraverse. Sets line_target and aim_slope when a target is
+    // aimed at
+    fn aim_traverse(&mut self, intercept: &Intercept) -> bool {
+        let attack: LineAttack = self.line_attack;
+
+        match intercept.target {
+            InterceptTarget::Line(line) => {
+                if self.level.lines[line].flags & ML_TWOSIDED == 0 {
+                    // Stop
+                    return false;
+                }
+
+                // Crosses a two sided line. A two sided line will restrict
+                // the possible target ranges
+                let opening: LineOpening = self.level.line_opening(line);
+
+                if opening.bottom >= opening.top {
+                    // Stop
+                    return false;
+                }
+
+                let dist: Fixed = fixed_mul(attack.attack_range, intercept.frac);
+                let (floor_step, ceiling_step): (bool, bool) = self.line_steps(line);
+
+                if floor_step {
+                    let slope: Fixed = fixed_div(opening.bottom - attack.shoot_z, dist);
+                    if slope > attack.bottom_slope {
+                        self.line_attack.bottom_slope = slope;
+                    }
+                }
+
+                if ceiling_step {
+                    let slope: Fixed = fixed_div(opening.top - attack.shoot_z, dist);
+                    if slope < attack.top_slope {
+                        self.line_attack.top_slope = slope;
+                    }
+                }
+
+                // Shot continues unless the gap closed up
+                self.line_attack.top_slope > self.line_attack.bottom_slope
+            }
+            InterceptTarget::Thing(id) => {
+                if Some(id) == attack.shoot_thing {
+                    // Can't shoot self
+                    return true;
+                }
+
+                let th: &Mobj = self.thinkers.mobj(id);
+                if th.flags & MF_SHOOTABLE == 0 {
+                    // Corpse or something
+                    return true;
+                }
+
+                // Check angles to see if the thing can be aimed at
+                let dist: Fixed = fixed_mul(attack.attack_range, intercept.frac);
+                let mut thing_top_slope: Fixed = fixed_div(th.z + th.height - attack.shoot_z, dist);
+
+                if thing_top_slope < attack.bottom_slope {
+                    // Shot over the thing
+                    return true;
+                }
+
+                let mut thing_bottom_slope: Fixed = fixed_div(th.z - attack.shoot_z, dist);
+
+                if thing_bottom_slope > attack.top_slope {
+                    // Shot under the thing
+                    return true;
+                }
+
+                // This thing can be hit!
+                if thing_top_slope > attack.top_slope {
+                    thing_top_slope = attack.top_slope;
+                }
+                if thing_bottom_slope < attack.bottom_slope {
+                    thing_bottom_slope = attack.bottom_slope;
+                }
+
+                self.line_attack.aim_slope = Fixed((thing_top_slope.0 + thing_bottom_slope.0) / 2);
+                self.line_attack.line_target = Some(id);
+
+                // Don't go any farther
+                false
+            }
+        }
+    }
+
+    // PTR_ShootTraverse
+    fn shoot_traverse(&mut self, intercept: &Intercept) -> bool {
+        let attack: LineAttack = self.line_attack;
+
+        match intercept.target {
+            InterceptTarget::Line(line) => {
+                if self.level.lines[line].special != 0 {
+                    self.shoot_special_line(attack.shoot_thing.unwrap(), line);
+                }
+
+                if self.level.lines[line].flags & ML_TWOSIDED != 0 {
+                    // Crosses a two sided line
+                    let opening: LineOpening = self.level.line_opening(line);
+                    let dist: Fixed = fixed_mul(attack.attack_range, intercept.frac);
+                    let (floor_step, ceiling_step): (bool, bool) = self.line_steps(line);
+
+                    let hit_floor: bool = floor_step
+                        && fixed_div(opening.bottom - attack.shoot_z, dist) > attack.aim_slope;
+                    let hit_ceiling: bool = ceiling_step
+                        && fixed_div(opening.top - attack.shoot_z, dist) < attack.aim_slope;
+
+                    if !hit_floor && !hit_ceiling {
+                        // Shot continues
+                        return true;
+                    }
+                }
+
+                // Hit line, position a bit closer
+                let frac: Fixed =
+                    intercept.frac - fixed_div(Fixed(4 * FRACUNIT), attack.attack_range);
+                let (x, y, z): (Fixed, Fixed, Fixed) = self.shot_position(frac);
+
+                let line: &Line = &self.level.lines[line];
+                let front: &Sector = &self.level.sectors[line.front_sector.unwrap()];
+                if &front.ceiling_pic == SKYFLATNAME {
+                    // Don't shoot the sky!
+                    if z > front.ceiling_height {
+                        return false;
+                    }
+
+                    // It's a sky hack wall
+                    if line
+                        .back_sector
+                        .is_some_and(|back| &self.level.sectors[back].ceiling_pic == SKYFLATNAME)
+                    {
+                        return false;
+                    }
+                }
+
+                // Spawn bullet puffs
+                self.spawn_puff(x, y, z);
+
+                // Don't go any farther
+                false
+            }
+            InterceptTarget::Thing(id) => {
+                if Some(id) == attack.shoot_thing {
+                    // Can't shoot self
+                    return true;
+                }
+
+                let th: &Mobj = self.thinkers.mobj(id);
+                if th.flags & MF_SHOOTABLE == 0 {
+                    // Corpse or something
+                    return true;
+                }
+
+                // Check angles to see if the thing can be aimed at
+                let dist: Fixed = fixed_mul(attack.attack_range, intercept.frac);
+                let thing_top_slope: Fixed = fixed_div(th.z + th.height - attack.shoot_z, dist);
+
+                if thing_top_slope < attack.aim_slope {
+                    // Shot over the thing
+                    return true;
+                }
+
+                let thing_bottom_slope: Fixed = fixed_div(th.z - attack.shoot_z, dist);
+
+                if thing_bottom_slope > attack.aim_slope {
+                    // Shot under the thing
+                    return true;
+                }
+
+                let no_blood: bool = th.flags & MF_NOBLOOD != 0;
+
+                // Hit thing, position a bit closer
+                let frac: Fixed =
+                    intercept.frac - fixed_div(Fixed(10 * FRACUNIT), attack.attack_range);
+                let (x, y, z): (Fixed, Fixed, Fixed) = self.shot_position(frac);
+
+                // Spawn bullet puffs or blood spots, depending on target
+                // type
+                if no_blood {
+                    self.spawn_puff(x, y, z);
+                } else {
+                    self.spawn_blood(x, y, z, attack.damage);
+                }
+
+                if attack.damage != 0 {
+                    self.damage_mobj(id, attack.shoot_thing, attack.shoot_thing, attack.damage);
+                }
+
+                // Don't go any farther
+                false
+            }
+        }
+    }
+
+    // Point on the shot's trace at frac, at the height of the aim
+    fn shot_position(&self, frac: Fixed) -> (Fixed, Fixed, Fixed) {
+        let attack: &LineAttack = &self.line_attack;
+        (
+            self.trace.x + fixed_mul(self.trace.dx, frac),
+            self.trace.y + fixed_mul(self.trace.dy, frac),
+            attack.shoot_z + fixed_mul(attack.aim_slope, fixed_mul(frac, attack.attack_range)),
+        )
+    }
+
+    // Whether the floor and ceiling heights change across a two sided line
+    fn line_steps(&self, line: usize) -> (bool, bool) {
+        let line: &Line = &self.level.lines[line];
+        let front: &Sector = &self.level.sectors[line.front_sector.unwrap()];
+        let back: &Sector = &self.level.sectors[line.back_sector.unwrap()];
+        (
+            front.floor_height != back.floor_height,
+            front.ceiling_height != back.ceiling_height,
+        )
+    }
+
+    // P_AimLineAttack. Returns the slope to aim at the first shootable
+    // thing along the line, and leaves the thing in line_attack.line_target
+    pub fn aim_line_attack(&mut self, id: MobjId, angle: Angle, distance: Fixed) -> Fixed {
+        let (x2, y2): (Fixed, Fixed) = self.attack_end(id, angle, distance);
+
+        // Can't shoot outside view angles
+        self.line_attack.top_slope = Fixed(100 * FRACUNIT / 160);
+        self.line_attack.bottom_slope = Fixed(-100 * FRACUNIT / 160);
+        self.line_attack.line_target = None;
+
+        let t1: &Mobj = self.thinkers.mobj(id);
+        self.path_traverse(
+            t1.x,
+            t1.y,
+            x2,
+            y2,
+            PT_ADDLINES | PT_ADDTHINGS,
+            |world, intercept| world.aim_traverse(intercept),
+        );
+
+        if self.line_attack.line_target.is_some() {
+            return self.line_attack.aim_slope;
+        }
+
+        Fixed::ZERO
+    }
+
+    // P_LineAttack. If damage is zero, it's just a test trace that will
+    // leave line_target set
+    pub fn line_attack(
+        &mut self,
+        id: MobjId,
+        angle: Angle,
+        distance: Fixed,
+        slope: Fixed,
+        damage: i32,
+    ) {
+        let (x2, y2): (Fixed, Fixed) = self.attack_end(id, angle, distance);
+        self.line_attack.damage = damage;
+        self.line_attack.aim_slope = slope;
+
+        let t1: &Mobj = self.thinkers.mobj(id);
+        self.path_traverse(
+            t1.x,
+            t1.y,
+            x2,
+            y2,
+            PT_ADDLINES | PT_ADDTHINGS,
+            |world, intercept| world.shoot_traverse(intercept),
+        );
+    }
+
+    // PTR_UseTraverse. Uses the first special line in front of the player,
+    // stopping at walls
+    fn use_traverse(&mut self, id: MobjId, intercept: &Intercept) -> bool {
+        let line: usize = match intercept.target {
+            InterceptTarget::Line(line) => line,
+            InterceptTarget::Thing(_) => return true,
+        };
+
+        if self.level.lines[line].special == 0 {
+            if self.level.line_opening(line).range <= Fixed::ZERO {
+                self.start_sound(Some(id), SFX_NOWAY);
+
+                // Can't use through a wall
+                return false;
+            }
+
+            // Not a special line, but keep checking
+            return true;
+        }
+
+        let use_thing: &Mobj = self.thinkers.mobj(id);
+        let side: usize = self
+            .level
+            .point_on_line_side(use_thing.x, use_thing.y, line);
+        self.use_special_line(id, line, side);
+
+        // Can't use for than one special line in a row
+        false
+    }
+
+    // P_UseLines. Looks for special lines in front of the player to
+    // activate
+    pub fn use_lines(&mut self, player: usize) {
+        let id: MobjId = self.players[player].mo.unwrap();
+        let mo: &Mobj = self.thinkers.mobj(id);
+        let (x1, y1): (Fixed, Fixed) = (mo.x, mo.y);
+        let x2: Fixed = x1 + Fixed((USERANGE.0 >> FRACBITS) * mo.angle.cos().0);
+        let y2: Fixed = y1 + Fixed((USERANGE.0 >> FRACBITS) * mo.angle.sin().0);
+
+        self.path_traverse(x1, y1, x2, y2, PT_ADDLINES, |world, intercept| {
+            world.use_traverse(id, intercept)
+        });
+    }
+
+    // PIT_RadiusAttack. "bomb_source" is the creature that caused the
+    // explosion at "bomb_spot"
+    fn radius_attack_thing(
+        &mut self,
+        id: MobjId,
+        bomb_spot: MobjId,
+        bomb_source: Option<MobjId>,
+        bomb_damage: i32,
+    ) -> bool {
+        let thing: &Mobj = self.thinkers.mobj(id);
+
+        if thing.flags & MF_SHOOTABLE == 0 {
+            return true;
+        }
+
+        // Boss spider and cyborg take no damage from concussion
+        if thing.mobj_type == MT_CYBORG || thing.mobj_type == MT_SPIDER {
+            return true;
+        }
+
+        let spot: &Mobj = self.thinkers.mobj(bomb_spot);
+        let dx: Fixed = (thing.x - spot.x).abs();
+        let dy: Fixed = (thing.y - spot.y).abs();
+        let dist: i32 = ((dx.max(dy) - thing.radius).0 >> FRACBITS).max(0);
+
+        if dist >= bomb_damage {
+            // Out of range
+            return true;
+        }
+
+        if self.check_sight(id, bomb_spot) {
+            // Must be in direct path
+            self.damage_mobj(id, Some(bomb_spot), bomb_source, bomb_damage - dist);
+        }
+
+        true
+    }
+
+    // P_RadiusAttack. Source is the creature that caused the explosion at
+    // spot
+    pub fn radius_attack(&mut self, spot: MobjId, source: Option<MobjId>, damage: i32) {
+        // MAXRADIUS shifted up overflows out of the distance, so only the
+        // damage counts like in the original
+        let dist: Fixed = Fixed((damage + MAXRADIUS.0).wrapping_shl(FRACBITS));
+        let spot_mobj: &Mobj = self.thinkers.mobj(spot);
+        let org_x: Fixed = self.level.blockmap.org_x;
+        let org_y: Fixed = self.level.blockmap.org_y;
+
+        let yh: i32 = (spot_mobj.y + dist - org_y).0 >> MAPBLOCKSHIFT;
+        let yl: i32 = (spot_mobj.y - dist - org_y).0 >> MAPBLOCKSHIFT;
+        let xh: i32 = (spot_mobj.x + dist - org_x).0 >> MAPBLOCKSHIFT;
+        let xl: i32 = (spot_mobj.x - dist - org_x).0 >> MAPBLOCKSHIFT;
+
+        for y in yl..=yh {
+            for x in xl..=xh {
+                self.block_things_iterator(x, y, |world, id| {
+                    world.radius_attack_thing(id, spot, source, damage)
+                });
+            }
+        }
+    }
+
+    // PIT_ChangeSector. Crushes bodies to gibs, removes dropped items and
+    // damages things that no longer fit
+    fn change_sector_thing(&mut self, id: MobjId, crunch: bool, no_fit: &mut bool) -> bool {
+        if self.thing_height_clip(id) {
+            // Keep checking
+            return true;
+        }
+
+        let thing: &Mobj = self.thinkers.mobj(id);
+
+        // Crunch bodies to giblets
+        if thing.health <= 0 {
+            self.set_mobj_state(id, S_GIBS);
+
+            let thing: &mut Mobj = self.thinkers.mobj_mut(id);
+            thing.flags &= !MF_SOLID;
+            thing.height = Fixed::ZERO;
+            thing.radius = Fixed::ZERO;
+
+            // Keep checking
+            return true;
+        }
+
+        // Crunch dropped items
+        if thing.flags & MF_DROPPED != 0 {
+            self.remove_mobj(id);
+
+            // Keep checking
+            return true;
+        }
+
+        if thing.flags & MF_SHOOTABLE == 0 {
+            // Assume it is bloody gibs or something
+            return true;
+        }
+
+        *no_fit = true;
+
+        if crunch && self.level_time & 3 == 0 {
+            self.damage_mobj(id, None, None, 10);
+
+            // Spray blood in a random direction
+            let thing: &Mobj = self.thinkers.mobj(id);
+            let (x, y, z): (Fixed, Fixed, Fixed) =
+                (thing.x, thing.y, thing.z + Fixed(thing.height.0 / 2));
+            let mo: MobjId = self.spawn_mobj(x, y, z, MT_BLOOD);
+            let mom_x: Fixed = Fixed(self.random.p_sub_random() << 12);
+            let mom_y: Fixed = Fixed(self.random.p_sub_random() << 12);
+            let mo: &mut Mobj = self.thinkers.mobj_mut(mo);
+            mo.mom_x = mom_x;
+            mo.mom_y = mom_y;
+        }
+
+        // Keep checking (crush other things)
+        true
+    }
+
+    // P_ChangeSector. Re-checks the heights of all things near a sector
+    // that moved. Returns true if something doesn't fit any more
+    pub fn change_sector(&mut self, sector: usize, crunch: bool) -> bool {
+        let mut no_fit: bool = false;
+        let block_box: [i32; 4] = self.level.sectors[sector].block_box;
+
+        // Re-check heights for all things near the moving sector
+        for block_x in block_box[BOXLEFT]..=block_box[BOXRIGHT] {
+            for block_y in block_box[BOXBOTTOM]..=block_box[BOXTOP] {
+                self.block_things_iterator(block_x, block_y, |world, id| {
+                    world.change_sector_thing(id, crunch, &mut no_fit)
+                });
+            }
+        }
+
+        no_fit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Angle, Fixed, FRACUNIT};
+    use crate::info::mobjinfo::{
+        MT_BARREL, MT_BLOOD, MT_CLIP, MT_MISC2, MT_PLAYER, MT_POSSESSED, MT_PUFF, MT_TROOP,
+    };
+    use crate::info::states::{S_BLOOD2, S_GIBS, S_PUFF3};
+    use crate::play::level::SKYFLATNAME;
+    use crate::play::map::{MELEERANGE, MISSILERANGE};
+    use crate::play::mobj::{Mobj, MobjId, MF_DROPPED, MF_NOCLIP, MF_SOLID, ONFLOORZ};
+    use crate::play::test_map::{TestMap, TestRoom};
+    use crate::play::world::{World, WorldSettings};
+    use crate::play::CompatibilityLevel;
+
+    fn spawn(world: &mut World, x: i32, y: i32, mobj_type: usize) -> MobjId {
+        world.spawn_mobj(Fixed::from_int(x), Fixed::from_int(y), ONFLOORZ, mobj_type)
+    }
+
+    fn position(world: &World, id: MobjId) -> (Fixed, Fixed) {
+        let mobj: &Mobj = world.thinkers.mobj(id);
+        (mobj.x, mobj.y)
+    }
+
+    #[test]
+    fn test_check_position_is_blocked_by_walls_and_solid_things() {
+        let mut world: World = TestMap::new().room(512, 0, 128).to_world();
+        let troop: MobjId = spawn(&mut world, 128, 128, MT_TROOP);
+        spawn(&mut world, 300, 128, MT_BARREL);
+        spawn(&mut world, 128, 200, MT_MISC2);
+
+        assert!(world.check_position(troop, Fixed::from_int(200), Fixed::from_int(128)));
+        assert_eq!(world.position_check.floor_z, Fixed::from_int(0));
+        assert_eq!(world.position_check.ceiling_z, Fixed::from_int(128));
+
+        // Walls
+        assert!(!world.check_position(troop, Fixed::from_int(10), Fixed::from_int(128)));
+        assert!(!world.check_position(troop, Fixed::from_int(128), Fixed::from_int(250)));
+        // Barrel
+        assert!(!world.check_position(troop, Fixed::from_int(280), Fixed::from_int(128)));
+        // Health bonuses aren't solid
+        assert!(world.check_position(troop, Fixed::from_int(128), Fixed::from_int(200)));
+
+        world.thinkers.mobj_mut(troop).flags |= MF_NOCLIP;
+        assert!(world.check_position(troop, Fixed::from_int(10), Fixed::from_int(128)));
+    }
+
+    #[test]
+    fn test_try_move_steps_up_to_24_units() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .room(128, 24, 128)
+            .room(128, 48, 128)
+            .to_world();
+        let troop: MobjId = spawn(&mut world, 200, 128, MT_TROOP);
+
+        assert!(world.try_move(troop, Fixed::from_int(260), Fixed::from_int(128)));
+        assert_eq!(
+            position(&world, troop),
+            (Fixed::from_int(260), Fixed::from_int(128))
+        );
+        assert_eq!(world.thinkers.mobj(troop).floor_z, Fixed::from_int(24));
+        assert_eq!(world.thinkers.mobj(troop).subsector, 1);
+
+        // The mobj is still at z 0 so the next step is too high
+        assert!(!world.try_move(troop, Fixed::from_int(380), Fixed::from_int(128)));
+        assert!(world.position_check.float_ok);
+
+        world.thinkers.mobj_mut(troop).z = Fixed::from_int(24);
+        assert!(world.try_move(troop, Fixed::from_int(380), Fixed::from_int(128)));
+        assert_eq!(world.thinkers.mobj(troop).floor_z, Fixed::from_int(48));
+    }
+
+    #[test]
+    fn test_try_move_keeps_monsters_off_dropoffs() {
+        let test_map = || TestMap::new().room(256, 64, 192).room(256, 0, 192);
+        let mut world: World = test_map().to_world();
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(200),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+
+        // Standing over the edge
+        assert!(!world.try_move(troop, Fixed::from_int(250), Fixed::from_int(128)));
+        assert!(world.try_move(troop, Fixed::from_int(300), Fixed::from_int(128)));
+
+        // Players can drop off
+        let mut world: World = test_map().to_world();
+        let player: MobjId = spawn(&mut world, 200, 128, MT_PLAYER);
+        assert!(world.try_move(player, Fixed::from_int(250), Fixed::from_int(128)));
+    }
+
+    #[test]
+    fn test_try_move_checks_ceiling_height() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .room(128, 0, 60)
+            .room(128, 0, 40)
+            .to_world();
+        let troop: MobjId = spawn(&mut world, 200, 128, MT_TROOP);
+
+        // Imps are 56 high
+        assert!(world.try_move(troop, Fixed::from_int(280), Fixed::from_int(128)));
+        assert!(!world.try_move(troop, Fixed::from_int(420), Fixed::from_int(128)));
+        assert!(!world.position_check.float_ok);
+    }
+
+    #[test]
+    fn test_thing_height_clip_follows_floor() {
+        let mut world: World = TestMap::new().room(256, 0, 128).to_world();
+        let troop: MobjId = spawn(&mut world, 128, 128, MT_TROOP);
+
+        world.level.sectors[0].floor_height = Fixed::from_int(16);
+        assert!(world.thing_height_clip(troop));
+        assert_eq!(world.thinkers.mobj(troop).z, Fixed::from_int(16));
+
+        world.level.sectors[0].ceiling_height = Fixed::from_int(40);
+        assert!(!world.thing_height_clip(troop));
+    }
+
+    #[test]
+    fn test_slide_move_slides_along_walls() {
+        let mut world: World = TestMap::new().room(512, 0, 128).to_world();
+        let player: MobjId = spawn(&mut world, 256, 200, MT_PLAYER);
+
+        // Moving up and right into the top wall at y 256
+        let mobj: &mut Mobj = world.thinkers.mobj_mut(player);
+        mobj.mom_x = Fixed::from_int(10);
+        mobj.mom_y = Fixed::from_int(50);
+        world.slide_move(player);
+
+        let mobj: &Mobj = world.thinkers.mobj(player);
+        assert!(mobj.x > Fixed::from_int(256));
+        assert!(mobj.y <= Fixed::from_int(256 - 16) && mobj.y > Fixed::from_int(200));
+        assert_eq!(mobj.mom_y, Fixed::ZERO);
+    }
+
+    #[test]
+    fn test_hit_slide_line_bounces_back_in_vanilla() {
+        let test_map = || {
+            TestMap::new()
+                .room_with(TestRoom {
+                    width: 256,
+                    ceiling_height: 128,
+                    ..Default::default()
+                })
+                .slope_right_wall(128)
+        };
+
+        let slide = |compatibility: CompatibilityLevel| -> (Fixed, Fixed) {
+            let mut world: World = World::new(
+                WorldSettings {
+                    compatibility,
+                    ..Default::default()
+                },
+                &test_map().to_map_lumps(),
+            )
+            .unwrap();
+            let player: MobjId = spawn(&mut world, 128, 128, MT_PLAYER);
+            let line: usize = world.level.lines.len() - 1;
+            let mut move_x: Fixed = Fixed::from_int(1);
+            let mut move_y: Fixed = Fixed::from_int(8);
+            world.hit_slide_line(player, line, &mut move_x, &mut move_y);
+            (move_x, move_y)
+        };
+
+        // The right wall leans right as it goes up. Heading mostly up into
+        // it should carry on up along it, the original sends it back down
+        let (move_x, move_y): (Fixed, Fixed) = slide(CompatibilityLevel::Fixed);
+        assert!(move_x > Fixed::ZERO && move_y > Fixed::ZERO);
+
+        let (move_x, move_y): (Fixed, Fixed) = slide(CompatibilityLevel::Vanilla);
+        assert!(move_x < Fixed::ZERO && move_y < Fixed::ZERO);
+    }
+
+    fn mobjs_of_type(world: &World, mobj_type: usize) -> Vec<MobjId> {
+        world
+            .thinkers
+            .mobjs()
+            .filter(|(_id, mobj)| mobj.mobj_type == mobj_type)
+            .map(|(id, _mobj)| id)
+            .collect()
+    }
+
+    #[test]
+    fn test_aim_line_attack_finds_first_shootable_thing() {
+        let mut world: World = TestMap::new()
+            .room(512, 0, 128)
+            .room(64, 80, 128)
+            .room(256, 0, 128)
+            .to_world();
+        let shooter: MobjId = spawn(&mut world, 64, 128, MT_POSSESSED);
+        let near: MobjId = spawn(&mut world, 300, 128, MT_TROOP);
+        let behind_step: MobjId = spawn(&mut world, 700, 128, MT_TROOP);
+
+        let slope: Fixed = world.aim_line_attack(shooter, Angle(0), MISSILERANGE);
+        assert_eq!(world.line_attack.line_target, Some(near));
+        // Aims between the top and bottom of the target, slightly down
+        assert!(slope < Fixed::ZERO && slope > Fixed(-FRACUNIT / 10));
+
+        // Nothing shootable to the left
+        let slope: Fixed = world.aim_line_attack(shooter, Angle::ANG180, MISSILERANGE);
+        assert_eq!(world.line_attack.line_target, None);
+        assert_eq!(slope, Fixed::ZERO);
+
+        // Out of range
+        world.aim_line_attack(shooter, Angle(0), MELEERANGE);
+        assert_eq!(world.line_attack.line_target, None);
+
+        // The step is higher than the target, aiming from the other side
+        world.remove_mobj(near);
+        world.aim_line_attack(shooter, Angle(0), MISSILERANGE);
+        assert_eq!(world.line_attack.line_target, None);
+        assert!(world.thinkers.contains(behind_step));
+    }
+
+    #[test]
+    fn test_line_attack_spawns_blood_and_damages_target() {
+        let mut world: World = TestMap::new().room(512, 0, 128).to_world();
+        let shooter: MobjId = spawn(&mut world, 64, 128, MT_POSSESSED);
+        let target: MobjId = spawn(&mut world, 300, 128, MT_TROOP);
+
+        let slope: Fixed = world.aim_line_attack(shooter, Angle(0), MISSILERANGE);
+        world.line_attack(shooter, Angle(0), MISSILERANGE, slope, 10);
+
+        assert_eq!(world.thinkers.mobj(target).health, 50);
+        assert_eq!(world.thinkers.mobj(target).target, Some(shooter));
+        let blood: Vec<MobjId> = mobjs_of_type(&world, MT_BLOOD);
+        assert_eq!(blood.len(), 1);
+        // 10 damage makes the smaller splat, 10 units in front of the
+        // target's centre
+        let blood: &Mobj = world.thinkers.mobj(blood[0]);
+        assert_eq!(blood.state, S_BLOOD2);
+        assert!(blood.x > Fixed::from_int(285) && blood.x < Fixed::from_int(295));
+        assert!(mobjs_of_type(&world, MT_PUFF).is_empty());
+    }
+
+    #[test]
+    fn test_line_attack_spawns_puffs_on_walls_and_bloodless_things() {
+        let mut world: World = TestMap::new().room(512, 0, 128).to_world();
+        let shooter: MobjId = spawn(&mut world, 64, 128, MT_POSSESSED);
+        let barrel: MobjId = spawn(&mut world, 300, 128, MT_BARREL);
+
+        world.line_attack(shooter, Angle(0), MISSILERANGE, Fixed::ZERO, 5);
+        assert_eq!(world.thinkers.mobj(barrel).health, 15);
+        assert_eq!(mobjs_of_type(&world, MT_PUFF).len(), 1);
+        assert!(mobjs_of_type(&world, MT_BLOOD).is_empty());
+
+        // The left wall, 4 units out from it
+        world.line_attack(shooter, Angle::ANG180, MISSILERANGE, Fixed::ZERO, 5);
+        let puffs: Vec<MobjId> = mobjs_of_type(&world, MT_PUFF);
+        assert_eq!(puffs.len(), 2);
+        let puff: &Mobj = world.thinkers.mobj(puffs[1]);
+        assert!(puff.x > Fixed::from_int(3) && puff.x < Fixed::from_int(5));
+        assert_eq!(puff.mom_z, Fixed(FRACUNIT));
+
+        // Punches don't spark
+        world.remove_mobj(shooter);
+        let shooter: MobjId = spawn(&mut world, 32, 128, MT_POSSESSED);
+        world.line_attack(shooter, Angle::ANG180, MELEERANGE, Fixed::ZERO, 5);
+        let puffs: Vec<MobjId> = mobjs_of_type(&world, MT_PUFF);
+        assert_eq!(world.thinkers.mobj(puffs[2]).state, S_PUFF3);
+    }
+
+    #[test]
+    fn test_line_attack_does_not_puff_on_sky() {
+        let mut world: World = TestMap::new().room(512, 0, 128).to_world();
+        world.level.sectors[0].ceiling_pic = *SKYFLATNAME;
+        let shooter: MobjId = spawn(&mut world, 64, 128, MT_POSSESSED);
+
+        // Aimed up over the wall
+        world.line_attack(shooter, Angle(0), MISSILERANGE, Fixed(FRACUNIT / 2), 5);
+        assert!(mobjs_of_type(&world, MT_PUFF).is_empty());
+
+        world.line_attack(shooter, Angle(0), MISSILERANGE, Fixed::ZERO, 5);
+        assert_eq!(mobjs_of_type(&world, MT_PUFF).len(), 1);
+    }
+
+    #[test]
+    fn test_radius_attack_falls_off_with_distance() {
+        let mut world: World = TestMap::new().room(512, 0, 128).to_world();
+        let spot: MobjId = spawn(&mut world, 100, 128, MT_PUFF);
+        let near: MobjId = spawn(&mut world, 150, 128, MT_TROOP);
+        let far: MobjId = spawn(&mut world, 400, 128, MT_TROOP);
+
+        world.radius_attack(spot, None, 40);
+
+        // 50 units away less the troop's radius of 20
+        assert_eq!(world.thinkers.mobj(near).health, 60 - 10);
+        assert_eq!(world.thinkers.mobj(far).health, 60);
+    }
+
+    #[test]
+    fn test_change_sector_gibs_corpses_and_removes_dropped_items() {
+        let mut world: World = TestMap::new().room(256, 0, 128).to_world();
+        let corpse: MobjId = spawn(&mut world, 64, 128, MT_TROOP);
+        world.thinkers.mobj_mut(corpse).health = 0;
+        let clip: MobjId = spawn(&mut world, 192, 128, MT_CLIP);
+        world.thinkers.mobj_mut(clip).flags |= MF_DROPPED;
+
+        world.level.sectors[0].ceiling_height = Fixed::from_int(4);
+        assert!(!world.change_sector(0, false));
+
+        let corpse: &Mobj = world.thinkers.mobj(corpse);
+        assert_eq!(corpse.state, S_GIBS);
+        assert_eq!(corpse.height, Fixed::ZERO);
+        assert_eq!(corpse.flags & MF_SOLID, 0);
+        assert!(mobjs_of_type(&world, MT_CLIP).is_empty());
+    }
+
+    #[test]
+    fn test_change_sector_reports_things_that_do_not_fit() {
+        let mut world: World = TestMap::new().room(256, 0, 128).to_world();
+        let troop: MobjId = spawn(&mut world, 64, 128, MT_TROOP);
+
+        world.level.sectors[0].ceiling_height = Fixed::from_int(56);
+        assert!(!world.change_sector(0, true));
+
+        // Only crushing hurts, every 4 tics
+        world.level.sectors[0].ceiling_height = Fixed::from_int(40);
+        assert!(world.change_sector(0, false));
+        assert_eq!(world.thinkers.mobj(troop).health, 60);
+        assert!(world.change_sector(0, true));
+        assert_eq!(world.thinkers.mobj(troop).health, 50);
+        assert_eq!(mobjs_of_type(&world, MT_BLOOD).len(), 1);
+
+        world.level_time = 1;
+        assert!(world.change_sector(0, true));
+        assert_eq!(world.thinkers.mobj(troop).health, 50);
+    }
+}
@@ -0,0 +1,793 @@
+// Port of p_maputl.c. Line side tests, openings between sectors and the
+// blockmap iterators that the movement, sight and attack code search with
+use crate::fixed::{fixed_div, fixed_mul, Fixed, FRACBITS, FRACUNIT};
+use crate::play::blockmap::{BLOCKLIST_END, MAPBLOCKSHIFT, MAPBLOCKSIZE, MAPBTOFRAC};
+use crate::play::level::{Level, Line, SlopeType, BOXBOTTOM, BOXLEFT, BOXRIGHT, BOXTOP};
+use crate::play::mobj::{Mobj, MobjId};
+use crate::play::world::World;
+
+// P_PathTraverse flags
+pub const PT_ADDLINES: i32 = 1;
+pub const PT_ADDTHINGS: i32 = 2;
+pub const PT_EARLYOUT: i32 = 4;
+
+// A line as an origin and a direction, divline_t
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DivLine {
+    pub x: Fixed,
+    pub y: Fixed,
+    pub dx: Fixed,
+    pub dy: Fixed,
+}
+
+impl DivLine {
+    // P_MakeDivline
+    pub fn from_line(level: &Level, line: &Line) -> Self {
+        DivLine {
+            x: level.vertexes[line.v1].x,
+            y: level.vertexes[line.v1].y,
+            dx: line.dx,
+            dy: line.dy,
+        }
+    }
+
+    // P_PointOnDivlineSide, 0 is the front and 1 the back
+    pub fn point_on_side(&self, x: Fixed, y: Fixed) -> usize {
+        if self.dx == Fixed::ZERO {
+            if x <= self.x {
+                return usize::from(self.dy > Fixed::ZERO);
+            }
+            return usize::from(self.dy < Fixed::ZERO);
+        }
+
+        if self.dy == Fixed::ZERO {
+            if y <= self.y {
+                return usize::from(self.dx < Fixed::ZERO);
+            }
+            return usize::from(self.dx > Fixed::ZERO);
+        }
+
+        let dx: Fixed = x - self.x;
+        let dy: Fixed = y - self.y;
+
+        // Try to quickly decide by looking at the sign bits
+        if (self.dy.0 ^ self.dx.0 ^ dx.0 ^ dy.0) < 0 {
+            if (self.dy.0 ^ dx.0) < 0 {
+                // Left is negative
+                return 1;
+            }
+            return 0;
+        }
+
+        let left: Fixed = fixed_mul(Fixed(self.dy.0 >> 8), Fixed(dx.0 >> 8));
+        let right: Fixed = fixed_mul(Fixed(dy.0 >> 8), Fixed(self.dx.0 >> 8));
+
+        if right < left {
+            // Front side
+            return 0;
+        }
+        // Back side
+        1
+    }
+}
+
+// P_InterceptVector. Returns the fractional intercept point along the first
+// divline. This is only called by the addthings and addlines traversers
+pub fn intercept_vector(v2: &DivLine, v1: &DivLine) -> Fixed {
+    let den: Fixed = fixed_mul(Fixed(v1.dy.0 >> 8), v2.dx) - fixed_mul(Fixed(v1.dx.0 >> 8), v2.dy);
+
+    if den == Fixed::ZERO {
+        return Fixed::ZERO;
+    }
+
+    let num: Fixed = fixed_mul(Fixed((v1.x - v2.x).0 >> 8), v1.dy)
+        + fixed_mul(Fixed((v2.y - v1.y).0 >> 8), v1.dx);
+
+    fixed_div(num, den)
+}
+
+// The gap between the sectors on either side of a line
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LineOpening {
+    pub top: Fixed,
+    pub bottom: Fixed,
+    pub range: Fixed,
+    pub low_floor: Fixed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterceptTarget {
+    Line(usize),
+    Thing(MobjId),
+}
+
+// A line or thing crossed by P_PathTraverse, frac is how far along the
+// trace it was hit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Intercept {
+    pub frac: Fixed,
+    pub target: InterceptTarget,
+}
+
+impl Level {
+    // P_PointOnLineSide, 0 is the front and 1 the back
+    pub fn point_on_line_side(&self, x: Fixed, y: Fixed, line: usize) -> usize {
+        let line: &Line = &self.lines[line];
+        let v1_x: Fixed = self.vertexes[line.v1].x;
+        let v1_y: Fixed = self.vertexes[line.v1].y;
+
+        if line.dx == Fixed::ZERO {
+            if x <= v1_x {
+                return usize::from(line.dy > Fixed::ZERO);
+            }
+            return usize::from(line.dy < Fixed::ZERO);
+        }
+
+        if line.dy == Fixed::ZERO {
+            if y <= v1_y {
+                return usize::from(line.dx < Fixed::ZERO);
+            }
+            return usize::from(line.dx > Fixed::ZERO);
+        }
+
+        let dx: Fixed = x - v1_x;
+        let dy: Fixed = y - v1_y;
+
+        let left: Fixed = fixed_mul(Fixed(line.dy.0 >> FRACBITS), dx);
+        let right: Fixed = fixed_mul(dy, Fixed(line.dx.0 >> FRACBITS));
+
+        if right < left {
+            // Front side
+            return 0;
+        }
+        // Back side
+        1
+    }
+
+    // P_BoxOnLineSide. Returns the side the box is on, or None if it
+    // crosses the line
+    pub fn box_on_line_side(&self, bbox: &[Fixed; 4], line: usize) -> Option<usize> {
+        let ld: &Line = &self.lines[line];
+        let v1_x: Fixed = self.vertexes[ld.v1].x;
+        let v1_y: Fixed = self.vertexes[ld.v1].y;
+
+        let (p1, p2): (usize, usize) = match ld.slope_type {
+            SlopeType::Horizontal => {
+                let p1: usize = usize::from(bbox[BOXTOP] > v1_y);
+                let p2: usize = usize::from(bbox[BOXBOTTOM] > v1_y);
+                if ld.dx < Fixed::ZERO {
+                    (p1 ^ 1, p2 ^ 1)
+                } else {
+                    (p1, p2)
+                }
+            }
+            SlopeType::Vertical => {
+                let p1: usize = usize::from(bbox[BOXRIGHT] < v1_x);
+                let p2: usize = usize::from(bbox[BOXLEFT] < v1_x);
+                if ld.dy < Fixed::ZERO {
+                    (p1 ^ 1, p2 ^ 1)
+                } else {
+                    (p1, p2)
+                }
+            }
+            SlopeType::Positive => (
+                self.point_on_line_side(bbox[BOXLEFT], bbox[BOXTOP], line),
+                self.point_on_line_side(bbox[BOXRIGHT], bbox[BOXBOTTOM], line),
+            ),
+            SlopeType::Negative => (
+                self.point_on_line_side(bbox[BOXRIGHT], bbox[BOXTOP], line),
+                self.point_on_line_side(bbox[BOXLEFT], bbox[BOXBOTTOM], line),
+            ),
+        };
+
+        if p1 == p2 {
+            return Some(p1);
+        }
+        None
+    }
+
+    // P_LineOpening. One sided lines have no opening
+    pub fn line_opening(&self, line: usize) -> LineOpening {
+        let line: &Line = &self.lines[line];
+
+        let (front, back) = match (line.side_num[1], line.front_sector, line.back_sector) {
+            (Some(_), Some(front), Some(back)) => (&self.sectors[front], &self.sectors[back]),
+            _ => return LineOpening::default(),
+        };
+
+        let top: Fixed = front.ceiling_height.min(back.ceiling_height);
+        let (bottom, low_floor): (Fixed, Fixed) = if front.floor_height > back.floor_height {
+            (front.floor_height, back.floor_height)
+        } else {
+            (back.floor_height, front.floor_height)
+        };
+
+        LineOpening {
+            top,
+            bottom,
+            range: top - bottom,
+            low_floor,
+        }
+    }
+}
+
+impl World {
+    // P_BlockLinesIterator. The valid_count flags are used to avoid checking
+    // lines that are in multiple blocks. Returns false if func returned
+    // false
+    pub fn block_lines_iterator(
+        &mut self,
+        block_x: i32,
+        block_y: i32,
+        mut func: impl FnMut(&mut World, usize) -> bool,
+    ) -> bool {
+        let index: usize = match self.level.blockmap.block_index(block_x, block_y) {
+            Some(index) => index,
+            None => return true,
+        };
+
+        let mut position: usize = usize::from(self.level.blockmap.lump[4 + index]);
+        loop {
+            let line: u16 = self.level.blockmap.lump[position];
+            if line == BLOCKLIST_END {
+                break;
+            }
+            position += 1;
+
+            let line: usize = usize::from(line);
+            if self.level.lines[line].valid_count == self.valid_count {
+                // Line has already been checked
+                continue;
+            }
+            self.level.lines[line].valid_count = self.valid_count;
+
+            if !func(self, line) {
+                return false;
+            }
+        }
+
+        // Everything was checked
+        true
+    }
+
+    // P_BlockThingsIterator. The next mobj is read after func runs like the
+    // original, so func can unlink the mobj it was given
+    pub fn block_things_iterator(
+        &mut self,
+        block_x: i32,
+        block_y: i32,
+        mut func: impl FnMut(&mut World, MobjId) -> bool,
+    ) -> bool {
+        let index: usize = match self.level.blockmap.block_index(block_x, block_y) {
+            Some(index) => index,
+            None => return true,
+        };
+
+        let mut next: Option<MobjId> = self.level.blockmap.links[index];
+        while let Some(id) = next {
+            if !func(self, id) {
+                return false;
+            }
+            next = self.thinkers.get_mobj(id).and_then(|mobj| mobj.bnext);
+        }
+
+        true
+    }
+
+    // Mobjs linked into a blockmap cell, most recently linked first
+    pub fn block_things(&self, block_x: i32, block_y: i32) -> Vec<MobjId> {
+        let mut things: Vec<MobjId> = Vec::new();
+        let mut next: Option<MobjId> = self
+            .level
+            .blockmap
+            .block_index(block_x, block_y)
+            .and_then(|index| self.level.blockmap.links[index]);
+
+        while let Some(id) = next {
+            things.push(id);
+            next = self.thinkers.mobj(id).bnext;
+        }
+
+        things
+    }
+
+    // P_PathTraverse. Traces a line from x1,y1 to x2,y2, calling the
+    // traverser function for each line and/or thing crossed in order of
+    // distance. Returns true if the traverser function returns true for
+    // all of them
+    pub fn path_traverse(
+        &mut self,
+        x1: Fixed,
+        y1: Fixed,
+        x2: Fixed,
+        y2: Fixed,
+        flags: i32,
+        trav: impl FnMut(&mut World, &Intercept) -> bool,
+    ) -> bool {
+        let early_out: bool = flags & PT_EARLYOUT != 0;
+
+        self.valid_count += 1;
+        let mut intercepts: Vec<Intercept> = Vec::new();
+
+        let org_x: Fixed = self.level.blockmap.org_x;
+        let org_y: Fixed = self.level.blockmap.org_y;
+        let mut x1: Fixed = x1;
+        let mut y1: Fixed = y1;
+
+        if (x1 - org_x).0 & (MAPBLOCKSIZE - 1) == 0 {
+            // Don't side exactly on a line
+            x1 += Fixed::ONE;
+        }
+        if (y1 - org_y).0 & (MAPBLOCKSIZE - 1) == 0 {
+            // Don't side exactly on a line
+            y1 += Fixed::ONE;
+        }
+
+        self.trace = DivLine {
+            x: x1,
+            y: y1,
+            dx: x2 - x1,
+            dy: y2 - y1,
+        };
+
+        let x1: i32 = (x1 - org_x).0;
+        let y1: i32 = (y1 - org_y).0;
+        let x2: i32 = (x2 - org_x).0;
+        let y2: i32 = (y2 - org_y).0;
+        let xt1: i32 = x1 >> MAPBLOCKSHIFT;
+        let yt1: i32 = y1 >> MAPBLOCKSHIFT;
+        let xt2: i32 = x2 >> MAPBLOCKSHIFT;
+        let yt2: i32 = y2 >> MAPBLOCKSHIFT;
+
+        let (map_x_step, partial, y_step): (i32, i32, Fixed) = if xt2 > xt1 {
+            (
+                1,
+                FRACUNIT - ((x1 >> MAPBTOFRAC) & (FRACUNIT - 1)),
+                fixed_div(
+                    Fixed(y2.wrapping_sub(y1)),
+                    Fixed(x2.wrapping_sub(x1).wrapping_abs()),
+                ),
+            )
+        } else if xt2 < xt1 {
+            (
+                -1,
+                (x1 >> MAPBTOFRAC) & (FRACUNIT - 1),
+                fixed_div(
+                    Fixed(y2.wrapping_sub(y1)),
+                    Fixed(x2.wrapping_sub(x1).wrapping_abs()),
+                ),
+            )
+        } else {
+            (0, FRACUNIT, Fixed(256 * FRACUNIT))
+        };
+        let mut y_intercept: Fixed = Fixed(y1 >> MAPBTOFRAC) + fixed_mul(Fixed(partial), y_step);
+
+        let (map_y_step, partial, x_step): (i32, i32, Fixed) = if yt2 > yt1 {
+            (
+                1,
+                FRACUNIT - ((y1 >> MAPBTOFRAC) & (FRACUNIT - 1)),
+                fixed_div(
+                    Fixed(x2.wrapping_sub(x1)),
+                    Fixed(y2.wrapping_sub(y1).wrapping_abs()),
+                ),
+            )
+        } else if yt2 < yt1 {
+            (
+                -1,
+                (y1 >> MAPBTOFRAC) & (FRACUNIT - 1),
+                fixed_div(
+                    Fixed(x2.wrapping_sub(x1)),
+                    Fixed(y2.wrapping_sub(y1).wrapping_abs()),
+                ),
+            )
+        } else {
+            (0, FRACUNIT, Fixed(256 * FRACUNIT))
+        };
+        let mut x_intercept: Fixed = Fixed(x1 >> MAPBTOFRAC) + fixed_mul(Fixed(partial), x_step);
+
+        // Step through map blocks. Count is present to prevent a round off
+        // error from skipping the break
+        let mut map_x: i32 = xt1;
+        let mut map_y: i32 = yt1;
+
+        for _ in 0..64 {
+            if flags & PT_ADDLINES != 0
+                && !self.block_lines_iterator(map_x, map_y, |world, line| {
+                    world.add_line_intercept(line, early_out, &mut intercepts)
+                })
+            {
+                // Early out
+                return false;
+            }
+
+            if flags & PT_ADDTHINGS != 0
+                && !self.block_things_iterator(map_x, map_y, |world, id| {
+                    world.add_thing_intercept(id, &mut intercepts)
+                })
+            {
+                // Early out
+                return false;
+            }
+
+            if map_x == xt2 && map_y == yt2 {
+                break;
+            }
+
+            if y_intercept.0 >> FRACBITS == map_y {
+                y_intercept += y_step;
+                map_x += map_x_step;
+            } else if x_intercept.0 >> FRACBITS == map_x {
+                x_intercept += x_step;
+                map_y += map_y_step;
+            }
+        }
+
+        // Go through the sorted list
+        self.traverse_intercepts(&mut intercepts, Fixed::ONE, trav)
+    }
+
+    // PIT_AddLineIntercepts. Looks for lines in the given block that
+    // intercept the given trace to add to the intercepts list. A line is
+    // crossed if its endpoints are on opposite sides of the trace. Returns
+    // true if early_out and a solid line hit
+    fn add_line_intercept(
+        &mut self,
+        line: usize,
+        early_out: bool,
+        intercepts: &mut Vec<Intercept>,
+    ) -> bool {
+        let trace: DivLine = self.trace;
+        let ld: &Line = &self.level.lines[line];
+        let big: Fixed = Fixed(16 * FRACUNIT);
+
+        // Avoid precision problems with two routines
+        let (s1, s2): (usize, usize) =
+            if trace.dx > big || trace.dy > big || trace.dx < -big || trace.dy < -big {
+                (
+                    trace.point_on_side(self.level.vertexes[ld.v1].x, self.level.vertexes[ld.v1].y),
+                    trace.point_on_side(self.level.vertexes[ld.v2].x, self.level.vertexes[ld.v2].y),
+                )
+            } else {
+                (
+                    self.level.point_on_line_side(trace.x, trace.y, line),
+                    self.level
+                        .point_on_line_side(trace.x + trace.dx, trace.y + trace.dy, line),
+                )
+            };
+
+        if s1 == s2 {
+            // Line isn't crossed
+            return true;
+        }
+
+        // Hit the line
+        let dl: DivLine = DivLine::from_line(&self.level, ld);
+        let frac: Fixed = intercept_vector(&trace, &dl);
+
+        if frac < Fixed::ZERO {
+            // Behind source
+            return true;
+        }
+
+        // Try to early out the check
+        if early_out && frac < Fixed::ONE && ld.back_sector.is_none() {
+            // Stop checking
+            return false;
+        }
+
+        intercepts.push(Intercept {
+            frac,
+            target: InterceptTarget::Line(line),
+        });
+
+        // Continue
+        true
+    }
+
+    // PIT_AddThingIntercepts
+    fn add_thing_intercept(&mut self, id: MobjId, intercepts: &mut Vec<Intercept>) -> bool {
+        let trace: DivLine = self.trace;
+        let thing: &Mobj = self.thinkers.mobj(id);
+        let trace_positive: bool = (trace.dx.0 ^ trace.dy.0) > 0;
+
+        // Check a corner to corner crossection for hit
+        let (x1, y1, x2, y2): (Fixed, Fixed, Fixed, Fixed) = if trace_positive {
+            (
+                thing.x - thing.radius,
+                thing.y + thing.radius,
+                thing.x + thing.radius,
+                thing.y - thing.radius,
+            )
+        } else {
+            (
+                thing.x - thing.radius,
+                thing.y - thing.radius,
+                thing.x + thing.radius,
+                thing.y + thing.radius,
+            )
+        };
+
+        let s1: usize = trace.point_on_side(x1, y1);
+        let s2: usize = trace.point_on_side(x2, y2);
+
+        if s1 == s2 {
+            // Line isn't crossed
+            return true;
+        }
+
+        let dl: DivLine = DivLine {
+            x: x1,
+            y: y1,
+            dx: x2 - x1,
+            dy: y2 - y1,
+        };
+        let frac: Fixed = intercept_vector(&trace, &dl);
+
+        if frac < Fixed::ZERO {
+            // Behind source
+            return true;
+        }
+
+        intercepts.push(Intercept {
+            frac,
+            target: InterceptTarget::Thing(id),
+        });
+
+        // Keep going
+        true
+    }
+
+    // P_TraverseIntercepts. Returns true if the traverser function returns
+    // true for all lines
+    fn traverse_intercepts(
+        &mut self,
+        intercepts: &mut [Intercept],
+        max_frac: Fixed,
+        mut func: impl FnMut(&mut World, &Intercept) -> bool,
+    ) -> bool {
+        let mut closest: usize = 0;
+
+        for _ in 0..intercepts.len() {
+            let mut dist: Fixed = Fixed::MAX;
+            for (index, intercept) in intercepts.iter().enumerate() {
+                if intercept.frac < dist {
+                    dist = intercept.frac;
+                    closest = index;
+                }
+            }
+
+            if dist > max_frac {
+                // Checked everything in range
+                return true;
+            }
+
+            if !func(self, &intercepts[closest]) {
+                // Don't bother going farther
+                return false;
+            }
+
+            intercepts[closest].frac = Fixed::MAX;
+        }
+
+        // Everything was traversed
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::info::mobjinfo::MT_TROOP;
+    use crate::play::level::{Level, ML_TWOSIDED};
+    use crate::play::maputl::{
+        intercept_vector, DivLine, Intercept, InterceptTarget, LineOpening, PT_ADDLINES,
+        PT_ADDTHINGS, PT_EARLYOUT,
+    };
+    use crate::play::mobj::{MobjId, ONFLOORZ};
+    use crate::play::test_map::TestMap;
+    use crate::play::world::World;
+
+    fn create_world() -> World {
+        TestMap::new()
+            .room(256, 0, 128)
+            .room(256, 32, 96)
+            .to_world()
+    }
+
+    fn shared_line(level: &Level) -> usize {
+        level
+            .lines
+            .iter()
+            .position(|line| line.flags & ML_TWOSIDED != 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_div_line_point_on_side() {
+        let div_line: DivLine = DivLine {
+            x: Fixed::from_int(0),
+            y: Fixed::from_int(0),
+            dx: Fixed::from_int(64),
+            dy: Fixed::from_int(64),
+        };
+
+        assert_eq!(
+            div_line.point_on_side(Fixed::from_int(10), Fixed::from_int(-10)),
+            0
+        );
+        assert_eq!(
+            div_line.point_on_side(Fixed::from_int(-10), Fixed::from_int(10)),
+            1
+        );
+        assert_eq!(
+            div_line.point_on_side(Fixed::from_int(20), Fixed::from_int(10)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_intercept_vector_returns_fraction_along_first_line() {
+        let trace: DivLine = DivLine {
+            x: Fixed::from_int(0),
+            y: Fixed::from_int(0),
+            dx: Fixed::from_int(100),
+            dy: Fixed::from_int(0),
+        };
+        let line: DivLine = DivLine {
+            x: Fixed::from_int(25),
+            y: Fixed::from_int(-50),
+            dx: Fixed::from_int(0),
+            dy: Fixed::from_int(100),
+        };
+
+        assert_eq!(intercept_vector(&trace, &line), Fixed(FRACUNIT / 4));
+
+        // Parallel lines
+        let parallel: DivLine = DivLine {
+            y: Fixed::from_int(10),
+            ..trace
+        };
+        assert_eq!(intercept_vector(&trace, &parallel), Fixed::ZERO);
+    }
+
+    #[test]
+    fn test_level_point_and_box_on_line_side() {
+        let world: World = create_world();
+        let line: usize = shared_line(&world.level);
+
+        // The shared line at x 256 faces right into room 1
+        assert_eq!(
+            world
+                .level
+                .point_on_line_side(Fixed::from_int(300), Fixed::from_int(64), line),
+            0
+        );
+        assert_eq!(
+            world
+                .level
+                .point_on_line_side(Fixed::from_int(200), Fixed::from_int(64), line),
+            1
+        );
+
+        let bbox = |left: i32, right: i32| {
+            [
+                Fixed::from_int(80),
+                Fixed::from_int(40),
+                Fixed::from_int(left),
+                Fixed::from_int(right),
+            ]
+        };
+        assert_eq!(world.level.box_on_line_side(&bbox(270, 300), line), Some(0));
+        assert_eq!(world.level.box_on_line_side(&bbox(200, 240), line), Some(1));
+        assert_eq!(world.level.box_on_line_side(&bbox(240, 270), line), None);
+    }
+
+    #[test]
+    fn test_level_line_opening() {
+        let world: World = create_world();
+
+        assert_eq!(
+            world.level.line_opening(shared_line(&world.level)),
+            LineOpening {
+                top: Fixed::from_int(96),
+                bottom: Fixed::from_int(32),
+                range: Fixed::from_int(64),
+                low_floor: Fixed::from_int(0),
+            }
+        );
+        assert_eq!(world.level.line_opening(0), LineOpening::default());
+    }
+
+    #[test]
+    fn test_block_lines_iterator_checks_lines_once_per_valid_count() {
+        let mut world: World = create_world();
+        world.valid_count += 1;
+
+        let mut lines: Vec<usize> = Vec::new();
+        world.block_lines_iterator(0, 0, |_world, line| {
+            lines.push(line);
+            true
+        });
+        world.block_lines_iterator(1, 0, |_world, line| {
+            lines.push(line);
+            true
+        });
+
+        let mut unique: Vec<usize> = lines.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(lines.len(), unique.len());
+        assert!(lines.contains(&0));
+
+        // Stops when func returns false
+        world.valid_count += 1;
+        let mut count: usize = 0;
+        assert!(!world.block_lines_iterator(0, 0, |_world, _line| {
+            count += 1;
+            false
+        }));
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_block_things_iterator_allows_unlinking_current_thing() {
+        let mut world: World = create_world();
+        let first: MobjId =
+            world.spawn_mobj(Fixed::from_int(64), Fixed::from_int(64), ONFLOORZ, MT_TROOP);
+        let second: MobjId =
+            world.spawn_mobj(Fixed::from_int(70), Fixed::from_int(70), ONFLOORZ, MT_TROOP);
+        let block_x: i32 = world.level.blockmap.block_x(Fixed::from_int(64));
+        let block_y: i32 = world.level.blockmap.block_y(Fixed::from_int(64));
+
+        let mut visited: Vec<MobjId> = Vec::new();
+        world.block_things_iterator(block_x, block_y, |world, id| {
+            visited.push(id);
+            world.remove_mobj(id);
+            true
+        });
+
+        assert_eq!(visited, vec![second, first]);
+        assert!(world.block_things(block_x, block_y).is_empty());
+    }
+
+    #[test]
+    fn test_path_traverse_visits_intercepts_in_order() {
+        let mut world: World = create_world();
+        let thing: MobjId = world.spawn_mobj(
+            Fixed::from_int(128),
+            Fixed::from_int(64),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+        let line: usize = shared_line(&world.level);
+
+        let mut targets: Vec<InterceptTarget> = Vec::new();
+        let traversed: bool = world.path_traverse(
+            Fixed::from_int(32),
+            Fixed::from_int(64),
+            Fixed::from_int(480),
+            Fixed::from_int(64),
+            PT_ADDLINES | PT_ADDTHINGS,
+            |_world, intercept: &Intercept| {
+                targets.push(intercept.target);
+                true
+            },
+        );
+
+        assert!(traversed);
+        assert_eq!(
+            targets,
+            vec![InterceptTarget::Thing(thing), InterceptTarget::Line(line)]
+        );
+
+        // The right wall is one sided so it stops an early out trace
+        assert!(!world.path_traverse(
+            Fixed::from_int(300),
+            Fixed::from_int(64),
+            Fixed::from_int(600),
+            Fixed::from_int(64),
+            PT_ADDLINES | PT_EARLYOUT,
+            |_world, _intercept| true,
+        ));
+    }
+}
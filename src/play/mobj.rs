@@ -0,0 +1,1836 @@
+// Port of the spawning, state and movement parts of p_mobj.c, the thing
+// position linking from p_maputl.c and the player respawning from g_game.c
+use crate::config::GameDifficulty;
+use crate::fixed::{
+    aprox_distance, fixed_mul, point_to_angle, Angle, Fixed, ANG45, FRACBITS, FRACUNIT,
+};
+use crate::info::mobjinfo::{
+    MOBJINFO, MT_BLOOD, MT_BRUISERSHOT, MT_HEADSHOT, MT_IFOG, MT_INS, MT_INV, MT_PLAYER, MT_PUFF,
+    MT_SKULL, MT_TFOG, MT_TROOPSHOT, NUMMOBJTYPES,
+};
+use crate::info::states::{
+    STATES, S_BLOOD2, S_BLOOD3, S_NULL, S_PLAY, S_PLAY_RUN1, S_PLAY_RUN4, S_PUFF3, S_SARG_PAIN2,
+    S_SARG_RUN1,
+};
+use crate::info::{Action, MobjInfo, MobjType, SpriteNum, State, StateNum};
+use crate::play::level::{
+    MapThing, MTF_AMBUSH, MTF_EASY, MTF_HARD, MTF_NORMAL, MTF_NOTSINGLE, SKYFLATNAME,
+};
+use crate::play::map::MELEERANGE;
+use crate::play::player::{Player, PlayerState, CF_NOMOMENTUM, VIEWHEIGHT};
+use crate::play::thinker::{Thinker, ThinkerId};
+use crate::play::world::{Deathmatch, World};
+use crate::play::{CompatibilityLevel, MAXPLAYERS};
+use crate::sound::sfx::{SfxNum, SFX_ITMBK, SFX_OOF, SFX_TELEPT};
+
+pub type MobjId = ThinkerId;
+
+// Call P_SpecialThing when touched
+pub const MF_SPECIAL: u32 = 0x1;
+// Blocks
+pub const MF_SOLID: u32 = 0x2;
+// Can be hit
+pub const MF_SHOOTABLE: u32 = 0x4;
+// Don't use the sector links(invisible but touchable)
+pub const MF_NOSECTOR: u32 = 0x8;
+// Don't use the blocklinks(inert but displayable)
+pub const MF_NOBLOCKMAP: u32 = 0x10;
+// Not to be activated by sound, deaf monster
+pub const MF_AMBUSH: u32 = 0x20;
+// Will try to attack right back
+pub const MF_JUSTHIT: u32 = 0x40;
+// Will take at least one step before attacking
+pub const MF_JUSTATTACKED: u32 = 0x80;
+// On level spawning hang from the ceiling instead of standing on the floor
+pub const MF_SPAWNCEILING: u32 = 0x100;
+// Don't apply gravity every tic
+pub const MF_NOGRAVITY: u32 = 0x200;
+// Allow jumps from high places
+pub const MF_DROPOFF: u32 = 0x400;
+// For players, will pick up items
+pub const MF_PICKUP: u32 = 0x800;
+// Player cheat
+pub const MF_NOCLIP: u32 = 0x1000;
+// Player: keep info about sliding along walls
+pub const MF_SLIDE: u32 = 0x2000;
+// Allow moves to any height, no gravity
+pub const MF_FLOAT: u32 = 0x4000;
+// Don't cross lines or look at heights on teleport
+pub const MF_TELEPORT: u32 = 0x8000;
+// Don't hit same species, explode on block
+pub const MF_MISSILE: u32 = 0x10000;
+// Dropped by a demon, not level spawned
+pub const MF_DROPPED: u32 = 0x20000;
+// Use fuzzy draw(shadow demons or spectres)
+pub const MF_SHADOW: u32 = 0x40000;
+// Don't bleed when shot(use puff)
+pub const MF_NOBLOOD: u32 = 0x80000;
+// Don't stop moving halfway off a step
+pub const MF_CORPSE: u32 = 0x100000;
+// Floating to a height for a move, don't auto float to target's height
+pub const MF_INFLOAT: u32 = 0x200000;
+// Count towards intermission kill total
+pub const MF_COUNTKILL: u32 = 0x400000;
+// Count towards intermission item total
+pub const MF_COUNTITEM: u32 = 0x800000;
+// Special handling: skull in flight
+pub const MF_SKULLFLY: u32 = 0x1000000;
+// Don't spawn this object in death match mode(e.g. key cards)
+pub const MF_NOTDMATCH: u32 = 0x2000000;
+// Player sprites in multiplayer modes are remapped to green, gray, brown
+// or red with these bits
+pub const MF_TRANSLATION: u32 = 0xc000000;
+pub const MF_TRANSSHIFT: u32 = 26;
+
+// z values for P_SpawnMobj that put the mobj on the floor or ceiling of
+// wherever it spawns
+pub const ONFLOORZ: Fixed = Fixed::MIN;
+pub const ONCEILINGZ: Fixed = Fixed::MAX;
+
+pub const GRAVITY: Fixed = Fixed(FRACUNIT);
+pub const MAXMOVE: Fixed = Fixed(30 * FRACUNIT);
+pub const FLOATSPEED: Fixed = Fixed(4 * FRACUNIT);
+pub const STOPSPEED: Fixed = Fixed(0x1000);
+pub const FRICTION: Fixed = Fixed(0xe800);
+
+// Largest radius of any mobj, things can overlap into neighbouring blocks
+// by this much
+pub const MAXRADIUS: Fixed = Fixed(32 * FRACUNIT);
+
+// Items removed in -altdeath wait here to respawn
+pub const ITEMQUESIZE: usize = 128;
+
+// Player corpses kept around in network games
+pub const BODYQUESIZE: usize = 32;
+
+// Dropped items and the invulnerability and invisibility spheres never come
+// back
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemRespawnQueue {
+    pub spawn_points: [MapThing; ITEMQUESIZE],
+    pub times: [i32; ITEMQUESIZE],
+    pub head: usize,
+    pub tail: usize,
+}
+
+impl Default for ItemRespawnQueue {
+    fn default() -> Self {
+        ItemRespawnQueue {
+            spawn_points: [MapThing::default(); ITEMQUESIZE],
+            times: [0; ITEMQUESIZE],
+            head: 0,
+            tail: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mobj {
+    pub x: Fixed,
+    pub y: Fixed,
+    pub z: Fixed,
+    // Links in the sector's thing list
+    pub snext: Option<MobjId>,
+    pub sprev: Option<MobjId>,
+    pub angle: Angle,
+    pub sprite: SpriteNum,
+    pub frame: i32,
+    // Links in the blockmap cell's thing list
+    pub bnext: Option<MobjId>,
+    pub bprev: Option<MobjId>,
+    pub subsector: usize,
+    // Highest floor and lowest ceiling the mobj is touching
+    pub floor_z: Fixed,
+    pub ceiling_z: Fixed,
+    pub radius: Fixed,
+    pub height: Fixed,
+    pub mom_x: Fixed,
+    pub mom_y: Fixed,
+    pub mom_z: Fixed,
+    pub valid_count: i32,
+    pub mobj_type: MobjType,
+    pub tics: i32,
+    pub state: StateNum,
+    pub flags: u32,
+    pub health: i32,
+    // Movement direction, movement count and reaction time are for the
+    // monster AI
+    pub move_dir: i32,
+    pub move_count: i32,
+    // Thing being chased/attacked, or the mobj that shot a missile
+    pub target: Option<MobjId>,
+    pub reaction_time: i32,
+    // If non zero don't target anything other than the current target
+    pub threshold: i32,
+    // Index into World::players for player mobjs
+    pub player: Option<usize>,
+    // Player number last looked for
+    pub last_look: i32,
+    // For nightmare respawn
+    pub spawn_point: MapThing,
+    // Thing being chased by revenant missiles
+    pub tracer: Option<MobjId>,
+}
+
+impl Mobj {
+    pub fn info(&self) -> &'static MobjInfo {
+        &MOBJINFO[self.mobj_type]
+    }
+
+    pub fn state(&self) -> &'static State {
+        &STATES[self.state]
+    }
+}
+
+impl World {
+    // P_SpawnMobj
+    pub fn spawn_mobj(&mut self, x: Fixed, y: Fixed, z: Fixed, mobj_type: MobjType) -> MobjId {
+        let info: &MobjInfo = &MOBJINFO[mobj_type];
+        let state: &State = &STATES[info.spawn_state];
+
+        let mut mobj: Mobj = Mobj {
+            mobj_type,
+            x,
+            y,
+            radius: info.radius,
+            height: info.height,
+            flags: info.flags,
+            health: info.spawn_health,
+            state: info.spawn_state,
+            tics: state.tics,
+            sprite: state.sprite,
+            frame: state.frame,
+            ..Default::default()
+        };
+
+        if self.settings.skill != GameDifficulty::Nightmare {
+            mobj.reaction_time = info.reaction_time;
+        }
+
+        mobj.last_look = self.random.p_random() % MAXPLAYERS as i32;
+
+        let id: MobjId = self.thinkers.add(Thinker::Mobj(mobj));
+
+        // Set subsector and/or block links
+        self.set_thing_position(id);
+
+        let sector: usize = self.level.subsectors[self.thinkers.mobj(id).subsector].sector;
+        let floor_height: Fixed = self.level.sectors[sector].floor_height;
+        let ceiling_height: Fixed = self.level.sectors[sector].ceiling_height;
+
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+        mobj.floor_z = floor_height;
+        mobj.ceiling_z = ceiling_height;
+        mobj.z = if z == ONFLOORZ {
+            floor_height
+        } else if z == ONCEILINGZ {
+            ceiling_height - info.height
+        } else {
+            z
+        };
+
+        id
+    }
+
+    // P_RemoveMobj
+    pub fn remove_mobj(&mut self, id: MobjId) {
+        let mobj: &Mobj = self.thinkers.mobj(id);
+        if mobj.flags & MF_SPECIAL != 0
+            && mobj.flags & MF_DROPPED == 0
+            && mobj.mobj_type != MT_INV
+            && mobj.mobj_type != MT_INS
+        {
+            let queue: &mut ItemRespawnQueue = &mut self.item_respawn_queue;
+            queue.spawn_points[queue.head] = mobj.spawn_point;
+            queue.times[queue.head] = self.level_time;
+            queue.head = (queue.head + 1) & (ITEMQUESIZE - 1);
+
+            // Lose one off the end?
+            if queue.head == queue.tail {
+                queue.tail = (queue.tail + 1) & (ITEMQUESIZE - 1);
+            }
+        }
+
+        // Unlink from sector and block lists
+        self.unset_thing_position(id);
+        // Free block
+        self.thinkers.remove(id);
+    }
+
+    // P_SetMobjState. Returns false if the mobj was removed because the
+    // state was S_NULL
+    pub fn set_mobj_state(&mut self, id: MobjId, state: StateNum) -> bool {
+        let mut state: StateNum = state;
+
+        loop {
+            if state == S_NULL {
+                self.thinkers.mobj_mut(id).state = S_NULL;
+                self.remove_mobj(id);
+                return false;
+            }
+
+            let st: &State = &STATES[state];
+            let tics: i32 = self.state_tics(state);
+            let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+            mobj.state = state;
+            mobj.tics = tics;
+            mobj.sprite = st.sprite;
+            mobj.frame = st.frame;
+
+            // Modified handling. Call action functions when the state is set
+            self.call_mobj_action(id, st.action);
+
+            state = st.next_state;
+
+            if self.thinkers.mobj(id).tics != 0 {
+                return true;
+            }
+        }
+    }
+
+    // The original halved the demon's tics in the state table for -fast
+    // and nightmare
+    fn state_tics(&self, state: StateNum) -> i32 {
+        let tics: i32 = STATES[state].tics;
+
+        if self.settings.fast_monsters() && (S_SARG_RUN1..=S_SARG_PAIN2).contains(&state) {
+            tics >> 1
+        } else {
+            tics
+        }
+    }
+
+    // The original changed the speed in the mobj info table for -fast and
+    // nightmare
+    pub fn missile_speed(&self, mobj_type: MobjType) -> Fixed {
+        let speed: i32 = MOBJINFO[mobj_type].speed;
+
+        match mobj_type {
+            MT_BRUISERSHOT | MT_HEADSHOT | MT_TROOPSHOT if self.settings.fast_monsters() => {
+                Fixed(20 * FRACUNIT)
+            }
+            _ => Fixed(speed),
+        }
+    }
+
+    fn call_mobj_action(&mut self, id: MobjId, action: Action) {
+        match action {
+            Action::Explode => self.a_explode(id),
+            Action::Pain => self.a_pain(id),
+            Action::PlayerScream => self.a_player_scream(id),
+            Action::Fall => self.a_fall(id),
+            Action::XScream => self.a_xscream(id),
+            Action::Look => self.a_look(id),
+            Action::Chase => self.a_chase(id),
+            Action::FaceTarget => self.a_face_target(id),
+            Action::PosAttack => self.a_pos_attack(id),
+            Action::Scream => self.a_scream(id),
+            Action::SPosAttack => self.a_spos_attack(id),
+            Action::VileChase => self.a_vile_chase(id),
+            Action::VileStart => self.a_vile_start(id),
+            Action::VileTarget => self.a_vile_target(id),
+            Action::VileAttack => self.a_vile_attack(id),
+            Action::StartFire => self.a_start_fire(id),
+            Action::Fire => self.a_fire(id),
+            Action::FireCrackle => self.a_fire_crackle(id),
+            Action::Tracer => self.a_tracer(id),
+            Action::SkelWhoosh => self.a_skel_whoosh(id),
+            Action::SkelFist => self.a_skel_fist(id),
+            Action::SkelMissile => self.a_skel_missile(id),
+            Action::FatRaise => self.a_fat_raise(id),
+            Action::FatAttack1 => self.a_fat_attack1(id),
+            Action::FatAttack2 => self.a_fat_attack2(id),
+            Action::FatAttack3 => self.a_fat_attack3(id),
+            Action::BossDeath => self.a_boss_death(id),
+            Action::CPosAttack => self.a_cpos_attack(id),
+            Action::CPosRefire => self.a_cpos_refire(id),
+            Action::TroopAttack => self.a_troop_attack(id),
+            Action::SargAttack => self.a_sarg_attack(id),
+            Action::HeadAttack => self.a_head_attack(id),
+            Action::BruisAttack => self.a_bruis_attack(id),
+            Action::SkullAttack => self.a_skull_attack(id),
+            Action::Metal => self.a_metal(id),
+            Action::SpidRefire => self.a_spid_refire(id),
+            Action::BabyMetal => self.a_baby_metal(id),
+            Action::BspiAttack => self.a_bspi_attack(id),
+            Action::Hoof => self.a_hoof(id),
+            Action::CyberAttack => self.a_cyber_attack(id),
+            Action::PainAttack => self.a_pain_attack(id),
+            Action::PainDie => self.a_pain_die(id),
+            Action::KeenDie => self.a_keen_die(id),
+            Action::BrainPain => self.a_brain_pain(id),
+            Action::BrainScream => self.a_brain_scream(id),
+            Action::BrainDie => self.a_brain_die(id),
+            Action::BrainAwake => self.a_brain_awake(id),
+            Action::BrainSpit => self.a_brain_spit(id),
+            Action::SpawnSound => self.a_spawn_sound(id),
+            Action::SpawnFly => self.a_spawn_fly(id),
+            Action::BrainExplode => self.a_brain_explode(id),
+            Action::BfgSpray => self.a_bfg_spray(id),
+            // The rest are weapon actions called on psprites
+            _ => {}
+        }
+    }
+
+    // P_ExplodeMissile
+    pub fn explode_missile(&mut self, id: MobjId) {
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+        mobj.mom_x = Fixed::ZERO;
+        mobj.mom_y = Fixed::ZERO;
+        mobj.mom_z = Fixed::ZERO;
+        let death_state: StateNum = mobj.info().death_state;
+
+        self.set_mobj_state(id, death_state);
+        self.randomize_tics(id);
+
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+        mobj.flags &= !MF_MISSILE;
+
+        let death_sound: SfxNum = mobj.info().death_sound;
+        self.start_sound(Some(id), death_sound);
+    }
+
+    // P_SpawnPuff
+    pub fn spawn_puff(&mut self, x: Fixed, y: Fixed, z: Fixed) {
+        let random: i32 = self.random.p_random();
+        let z: Fixed = z + Fixed((random - self.random.p_random()) << 10);
+
+        let id: MobjId = self.spawn_mobj(x, y, z, MT_PUFF);
+        self.thinkers.mobj_mut(id).mom_z = Fixed::ONE;
+        self.randomize_tics(id);
+
+        // Don't make punches spark on the wall
+        if self.line_attack.attack_range == MELEERANGE {
+            self.set_mobj_state(id, S_PUFF3);
+        }
+    }
+
+    // P_SpawnBlood
+    pub fn spawn_blood(&mut self, x: Fixed, y: Fixed, z: Fixed, damage: i32) {
+        let random: i32 = self.random.p_random();
+        let z: Fixed = z + Fixed((random - self.random.p_random()) << 10);
+
+        let id: MobjId = self.spawn_mobj(x, y, z, MT_BLOOD);
+        self.thinkers.mobj_mut(id).mom_z = Fixed(2 * FRACUNIT);
+        self.randomize_tics(id);
+
+        if (9..=12).contains(&damage) {
+            self.set_mobj_state(id, S_BLOOD2);
+        } else if damage < 9 {
+            self.set_mobj_state(id, S_BLOOD3);
+        }
+    }
+
+    // P_CheckMissileSpawn. Moves a little forward so an angle can be
+    // computed if it immediately explodes
+    pub fn check_missile_spawn(&mut self, id: MobjId) {
+        self.randomize_tics(id);
+
+        // The position is changed without relinking like the original
+        let th: &mut Mobj = self.thinkers.mobj_mut(id);
+        th.x += Fixed(th.mom_x.0 >> 1);
+        th.y += Fixed(th.mom_y.0 >> 1);
+        th.z += Fixed(th.mom_z.0 >> 1);
+        let (x, y): (Fixed, Fixed) = (th.x, th.y);
+
+        if !self.try_move(id, x, y) {
+            self.explode_missile(id);
+        }
+    }
+
+    // P_SpawnMissile. Fires a missile from source at dest
+    pub fn spawn_missile(&mut self, source: MobjId, dest: MobjId, mobj_type: MobjType) -> MobjId {
+        let src: &Mobj = self.thinkers.mobj(source);
+        let (x, y, z): (Fixed, Fixed, Fixed) = (src.x, src.y, src.z);
+        let id: MobjId = self.spawn_mobj(x, y, z + Fixed(4 * 8 * FRACUNIT), mobj_type);
+
+        let see_sound: SfxNum = self.thinkers.mobj(id).info().see_sound;
+        self.start_sound(Some(id), see_sound);
+
+        let dest_mobj: &Mobj = self.thinkers.mobj(dest);
+        let (dest_x, dest_y, dest_z, dest_flags): (Fixed, Fixed, Fixed, u32) =
+            (dest_mobj.x, dest_mobj.y, dest_mobj.z, dest_mobj.flags);
+        let mut an: Angle = point_to_angle(x, y, dest_x, dest_y);
+
+        // Fuzzy player
+        if dest_flags & MF_SHADOW != 0 {
+            let random: i32 = self.random.p_random();
+            an += Angle(((random - self.random.p_random()) << 20) as u32);
+        }
+
+        let speed: Fixed = self.missile_speed(mobj_type);
+        let th: &mut Mobj = self.thinkers.mobj_mut(id);
+        // Where it came from
+        th.target = Some(source);
+        th.angle = an;
+        th.mom_x = fixed_mul(speed, an.cos());
+        th.mom_y = fixed_mul(speed, an.sin());
+
+        let dist: i32 = (aprox_distance(dest_x - x, dest_y - y).0 / speed.0).max(1);
+        th.mom_z = Fixed((dest_z - z).0 / dist);
+
+        self.check_missile_spawn(id);
+
+        id
+    }
+
+    // P_SpawnPlayerMissile. Tries to aim at a nearby monster
+    pub fn spawn_player_missile(&mut self, source: MobjId, mobj_type: MobjType) {
+        let range: Fixed = Fixed(16 * 64 * FRACUNIT);
+
+        // See which target is to be aimed at
+        let source_angle: Angle = self.thinkers.mobj(source).angle;
+        let mut an: Angle = source_angle;
+        let mut slope: Fixed = self.aim_line_attack(source, an, range);
+
+        if self.line_attack.line_target.is_none() {
+            an += Angle(1 << 26);
+            slope = self.aim_line_attack(source, an, range);
+
+            if self.line_attack.line_target.is_none() {
+                an -= Angle(2 << 26);
+                slope = self.aim_line_attack(source, an, range);
+            }
+
+            if self.line_attack.line_target.is_none() {
+                an = source_angle;
+                slope = Fixed::ZERO;
+            }
+        }
+
+        let src: &Mobj = self.thinkers.mobj(source);
+        let (x, y, z): (Fixed, Fixed, Fixed) = (src.x, src.y, src.z + Fixed(4 * 8 * FRACUNIT));
+        let id: MobjId = self.spawn_mobj(x, y, z, mobj_type);
+
+        let see_sound: SfxNum = self.thinkers.mobj(id).info().see_sound;
+        self.start_sound(Some(id), see_sound);
+
+        let th: &mut Mobj = self.thinkers.mobj_mut(id);
+        th.target = Some(source);
+        th.angle = an;
+        let speed: Fixed = Fixed(th.info().speed);
+        th.mom_x = fixed_mul(speed, an.cos());
+        th.mom_y = fixed_mul(speed, an.sin());
+        th.mom_z = fixed_mul(speed, slope);
+
+        self.check_missile_spawn(id);
+    }
+
+    // Takes up to 3 tics off the current state, but leaves at least one
+    pub fn randomize_tics(&mut self, id: MobjId) {
+        let random: i32 = self.random.p_random();
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+        mobj.tics -= random & 3;
+        if mobj.tics < 1 {
+            mobj.tics = 1;
+        }
+    }
+
+    // P_XYMovement
+    pub fn xy_movement(&mut self, id: MobjId) {
+        let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+
+        if mo.mom_x == Fixed::ZERO && mo.mom_y == Fixed::ZERO {
+            if mo.flags & MF_SKULLFLY != 0 {
+                // The skull slammed into something
+                mo.flags &= !MF_SKULLFLY;
+                mo.mom_x = Fixed::ZERO;
+                mo.mom_y = Fixed::ZERO;
+                mo.mom_z = Fixed::ZERO;
+                let spawn_state: StateNum = mo.info().spawn_state;
+                self.set_mobj_state(id, spawn_state);
+            }
+            return;
+        }
+
+        let player: Option<usize> = mo.player;
+        mo.mom_x = mo.mom_x.clamp(-MAXMOVE, MAXMOVE);
+        mo.mom_y = mo.mom_y.clamp(-MAXMOVE, MAXMOVE);
+
+        let mut x_move: Fixed = mo.mom_x;
+        let mut y_move: Fixed = mo.mom_y;
+        let half_move: Fixed = Fixed(MAXMOVE.0 / 2);
+        let vanilla: bool = self.settings.compatibility == CompatibilityLevel::Vanilla;
+
+        loop {
+            let mo: &Mobj = self.thinkers.mobj(id);
+            // The original only split up big moves in the positive
+            // directions
+            let split: bool = x_move > half_move
+                || y_move > half_move
+                || (!vanilla && (x_move < -half_move || y_move < -half_move));
+
+            let (try_x, try_y): (Fixed, Fixed) = if split {
+                let try_move: (Fixed, Fixed) =
+                    (mo.x + Fixed(x_move.0 / 2), mo.y + Fixed(y_move.0 / 2));
+                x_move = Fixed(x_move.0 >> 1);
+                y_move = Fixed(y_move.0 >> 1);
+                try_move
+            } else {
+                let try_move: (Fixed, Fixed) = (mo.x + x_move, mo.y + y_move);
+                x_move = Fixed::ZERO;
+                y_move = Fixed::ZERO;
+                try_move
+            };
+
+            if !self.try_move(id, try_x, try_y) {
+                // Blocked move
+                let flags: u32 = self.thinkers.mobj(id).flags;
+                if player.is_some() {
+                    // Try to slide along it
+                    self.slide_move(id);
+
+                    // The original went on to slide with the full
+                    // momentum again for the second half of a split move,
+                    // which is what makes wallrunning work
+                    if !vanilla {
+                        x_move = Fixed::ZERO;
+                        y_move = Fixed::ZERO;
+                    }
+                } else if flags & MF_MISSILE != 0 {
+                    // Explode a missile
+                    let sky_hack: bool = self
+                        .position_check
+                        .ceiling_line
+                        .and_then(|line| self.level.lines[line].back_sector)
+                        .is_some_and(|sector| {
+                            &self.level.sectors[sector].ceiling_pic == SKYFLATNAME
+                        });
+
+                    if sky_hack {
+                        // Hack to prevent missiles exploding against the
+                        // sky. Does not handle sky floors
+                        self.remove_mobj(id);
+                        return;
+                    }
+                    self.explode_missile(id);
+                } else {
+                    let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+                    mo.mom_x = Fixed::ZERO;
+                    mo.mom_y = Fixed::ZERO;
+                }
+            }
+
+            if x_move == Fixed::ZERO && y_move == Fixed::ZERO {
+                break;
+            }
+        }
+
+        // Slow down
+        let (cheats, cmd_moving): (i32, bool) = match player {
+            Some(player) => {
+                let player: &Player = &self.players[player];
+                (
+                    player.cheats,
+                    player.cmd.forward_move != 0 || player.cmd.side_move != 0,
+                )
+            }
+            None => (0, false),
+        };
+
+        let sector_floor: Fixed = {
+            let mo: &Mobj = self.thinkers.mobj(id);
+            self.level.sectors[self.level.subsectors[mo.subsector].sector].floor_height
+        };
+        let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+
+        if cheats & CF_NOMOMENTUM != 0 {
+            // Debug option for no sliding at all
+            mo.mom_x = Fixed::ZERO;
+            mo.mom_y = Fixed::ZERO;
+            return;
+        }
+
+        if mo.flags & (MF_MISSILE | MF_SKULLFLY) != 0 {
+            // No friction for missiles ever
+            return;
+        }
+
+        if mo.z > mo.floor_z {
+            // No friction when airborne
+            return;
+        }
+
+        if mo.flags & MF_CORPSE != 0 {
+            // Do not stop sliding if halfway off a step with some momentum
+            let quarter: Fixed = Fixed(FRACUNIT / 4);
+            if (mo.mom_x > quarter
+                || mo.mom_x < -quarter
+                || mo.mom_y > quarter
+                || mo.mom_y < -quarter)
+                && mo.floor_z != sector_floor
+            {
+                return;
+            }
+        }
+
+        if mo.mom_x > -STOPSPEED
+            && mo.mom_x < STOPSPEED
+            && mo.mom_y > -STOPSPEED
+            && mo.mom_y < STOPSPEED
+            && !cmd_moving
+        {
+            // If in a walking frame, stop moving
+            if player.is_some() && (S_PLAY_RUN1..=S_PLAY_RUN4).contains(&mo.state) {
+                self.set_mobj_state(id, S_PLAY);
+            }
+
+            let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+            mo.mom_x = Fixed::ZERO;
+            mo.mom_y = Fixed::ZERO;
+        } else {
+            mo.mom_x = fixed_mul(mo.mom_x, FRICTION);
+            mo.mom_y = fixed_mul(mo.mom_y, FRICTION);
+        }
+    }
+
+    // P_ZMovement
+    pub fn z_movement(&mut self, id: MobjId) {
+        let mo: &Mobj = self.thinkers.mobj(id);
+
+        // Check for smooth step up
+        if let Some(player) = mo.player {
+            if mo.z < mo.floor_z {
+                let step: Fixed = mo.floor_z - mo.z;
+                let player: &mut Player = &mut self.players[player];
+                player.view_height -= step;
+                player.delta_view_height = Fixed((VIEWHEIGHT - player.view_height).0 >> 3);
+            }
+        }
+
+        let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+
+        // Adjust height
+        mo.z += mo.mom_z;
+
+        if mo.flags & MF_FLOAT != 0 && mo.flags & (MF_SKULLFLY | MF_INFLOAT) == 0 {
+            // Float down towards target if too close
+            let target: Option<(Fixed, Fixed, Fixed)> = mo
+                .target
+                .and_then(|target| self.thinkers.get_mobj(target))
+                .map(|target| (target.x, target.y, target.z));
+
+            if let Some((target_x, target_y, target_z)) = target {
+                let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+                let dist: Fixed = aprox_distance(mo.x - target_x, mo.y - target_y);
+                let delta: Fixed = target_z + Fixed(mo.height.0 >> 1) - mo.z;
+
+                if delta < Fixed::ZERO && dist < -Fixed(delta.0.wrapping_mul(3)) {
+                    mo.z -= FLOATSPEED;
+                } else if delta > Fixed::ZERO && dist < Fixed(delta.0.wrapping_mul(3)) {
+                    mo.z += FLOATSPEED;
+                }
+            }
+        }
+
+        let vanilla: bool = self.settings.compatibility == CompatibilityLevel::Vanilla;
+        let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+        let player: Option<usize> = mo.player;
+
+        // Clip movement
+        if mo.z <= mo.floor_z {
+            // Hit the floor. Lost souls bounce off it, the original turned
+            // the momentum around after it was zeroed so they didn't
+            if !vanilla && mo.flags & MF_SKULLFLY != 0 {
+                mo.mom_z = -mo.mom_z;
+            }
+
+            let mut squat: Option<Fixed> = None;
+            if mo.mom_z < Fixed::ZERO {
+                if player.is_some() && mo.mom_z < Fixed(-GRAVITY.0 * 8) {
+                    // Squat down. Decrease viewheight for a moment after
+                    // hitting the ground (hard), and utter appropriate
+                    // sound
+                    squat = Some(Fixed(mo.mom_z.0 >> 3));
+                }
+                mo.mom_z = Fixed::ZERO;
+            }
+            mo.z = mo.floor_z;
+
+            if vanilla && mo.flags & MF_SKULLFLY != 0 {
+                mo.mom_z = -mo.mom_z;
+            }
+
+            let explode: bool = mo.flags & MF_MISSILE != 0 && mo.flags & MF_NOCLIP == 0;
+
+            if let (Some(player), Some(squat)) = (player, squat) {
+                self.players[player].delta_view_height = squat;
+                self.start_sound(Some(id), SFX_OOF);
+            }
+
+            if explode {
+                self.explode_missile(id);
+                return;
+            }
+        } else if mo.flags & MF_NOGRAVITY == 0 {
+            if mo.mom_z == Fixed::ZERO {
+                mo.mom_z = Fixed(-GRAVITY.0 * 2);
+            } else {
+                mo.mom_z -= GRAVITY;
+            }
+        }
+
+        let mo: &mut Mobj = self.thinkers.mobj_mut(id);
+        if mo.z + mo.height > mo.ceiling_z {
+            // Hit the ceiling
+            if mo.mom_z > Fixed::ZERO {
+                mo.mom_z = Fixed::ZERO;
+            }
+            mo.z = mo.ceiling_z - mo.height;
+
+            if mo.flags & MF_SKULLFLY != 0 {
+                // The skull slammed into something
+                mo.mom_z = -mo.mom_z;
+            }
+
+            if mo.flags & MF_MISSILE != 0 && mo.flags & MF_NOCLIP == 0 {
+                self.explode_missile(id);
+            }
+        }
+    }
+
+    // P_MobjThinker
+    pub fn mobj_thinker(&mut self, id: MobjId) {
+        let mobj: &Mobj = self.thinkers.mobj(id);
+
+        // Momentum movement
+        if mobj.mom_x != Fixed::ZERO || mobj.mom_y != Fixed::ZERO || mobj.flags & MF_SKULLFLY != 0 {
+            self.xy_movement(id);
+
+            if self.thinkers.is_removed(id) {
+                // Mobj was removed
+                return;
+            }
+        }
+
+        let mobj: &Mobj = self.thinkers.mobj(id);
+        if mobj.z != mobj.floor_z || mobj.mom_z != Fixed::ZERO {
+            self.z_movement(id);
+
+            if self.thinkers.is_removed(id) {
+                // Mobj was removed
+                return;
+            }
+        }
+
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+
+        // Cycle through states, calling action functions at transitions
+        if mobj.tics != -1 {
+            mobj.tics -= 1;
+
+            // You can cycle through multiple states in a tic
+            if mobj.tics == 0 {
+                let next_state: StateNum = mobj.state().next_state;
+                self.set_mobj_state(id, next_state);
+            }
+        } else {
+            // Check for nightmare respawn
+            if mobj.flags & MF_COUNTKILL == 0 || !self.settings.respawn_monsters() {
+                return;
+            }
+
+            mobj.move_count += 1;
+            if mobj.move_count < 12 * 35 {
+                return;
+            }
+
+            if self.level_time & 31 != 0 {
+                return;
+            }
+
+            if self.random.p_random() > 4 {
+                return;
+            }
+
+            self.nightmare_respawn(id);
+        }
+    }
+
+    // P_NightmareRespawn
+    fn nightmare_respawn(&mut self, id: MobjId) {
+        let mobj: &Mobj = self.thinkers.mobj(id);
+        let spawn_point: MapThing = mobj.spawn_point;
+        let x: Fixed = Fixed(i32::from(spawn_point.x) << FRACBITS);
+        let y: Fixed = Fixed(i32::from(spawn_point.y) << FRACBITS);
+
+        // Somthing is occupying it's position?
+        if !self.check_position(id, x, y) {
+            // No respwan
+            return;
+        }
+
+        // Spawn a teleport fog at old spot because of removal of the body?
+        let mobj: &Mobj = self.thinkers.mobj(id);
+        let (old_x, old_y, mobj_type): (Fixed, Fixed, MobjType) = (mobj.x, mobj.y, mobj.mobj_type);
+        let sector: usize = self.level.subsectors[mobj.subsector].sector;
+        let floor_height: Fixed = self.level.sectors[sector].floor_height;
+        let mo: MobjId = self.spawn_mobj(old_x, old_y, floor_height, MT_TFOG);
+        self.start_sound(Some(mo), SFX_TELEPT);
+
+        // Spawn a teleport fog at the new spot
+        let subsector: usize = self.level.point_in_subsector(x, y);
+        let sector: usize = self.level.subsectors[subsector].sector;
+        let floor_height: Fixed = self.level.sectors[sector].floor_height;
+        let mo: MobjId = self.spawn_mobj(x, y, floor_height, MT_TFOG);
+        self.start_sound(Some(mo), SFX_TELEPT);
+
+        // Spawn the new monster
+        let z: Fixed = if MOBJINFO[mobj_type].flags & MF_SPAWNCEILING != 0 {
+            ONCEILINGZ
+        } else {
+            ONFLOORZ
+        };
+
+        // Inherit attributes from deceased one
+        let mo: MobjId = self.spawn_mobj(x, y, z, mobj_type);
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(mo);
+        mobj.spawn_point = spawn_point;
+        mobj.angle = Angle(ANG45.wrapping_mul((i32::from(spawn_point.angle) / 45) as u32));
+        if spawn_point.options & MTF_AMBUSH != 0 {
+            mobj.flags |= MF_AMBUSH;
+        }
+        mobj.reaction_time = 18;
+
+        // Remove the old monster
+        self.remove_mobj(id);
+    }
+
+    // P_RespawnSpecials
+    pub fn respawn_specials(&mut self) {
+        // Only respawn items in deathmatch
+        if self.settings.deathmatch != Deathmatch::AltDeath {
+            return;
+        }
+
+        // Nothing left to respawn?
+        let queue: &ItemRespawnQueue = &self.item_respawn_queue;
+        if queue.head == queue.tail {
+            return;
+        }
+
+        // Wait at least 30 seconds
+        if self.level_time - queue.times[queue.tail] < 30 * 35 {
+            return;
+        }
+
+        let map_thing: MapThing = queue.spawn_points[queue.tail];
+        let x: Fixed = Fixed(i32::from(map_thing.x) << FRACBITS);
+        let y: Fixed = Fixed(i32::from(map_thing.y) << FRACBITS);
+
+        // Spawn a teleport fog at the new spot
+        let subsector: usize = self.level.point_in_subsector(x, y);
+        let sector: usize = self.level.subsectors[subsector].sector;
+        let floor_height: Fixed = self.level.sectors[sector].floor_height;
+        let mo: MobjId = self.spawn_mobj(x, y, floor_height, MT_IFOG);
+        self.start_sound(Some(mo), SFX_ITMBK);
+
+        // Find which type to spawn
+        let mobj_type: MobjType = MOBJINFO
+            .iter()
+            .position(|info| info.doomed_num == i32::from(map_thing.thing_type))
+            .unwrap();
+
+        // Spawn it
+        let z: Fixed = if MOBJINFO[mobj_type].flags & MF_SPAWNCEILING != 0 {
+            ONCEILINGZ
+        } else {
+            ONFLOORZ
+        };
+
+        let mo: MobjId = self.spawn_mobj(x, y, z, mobj_type);
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(mo);
+        mobj.spawn_point = map_thing;
+        mobj.angle = Angle(ANG45.wrapping_mul((i32::from(map_thing.angle) / 45) as u32));
+
+        // Pull it from the que
+        let queue: &mut ItemRespawnQueue = &mut self.item_respawn_queue;
+        queue.tail = (queue.tail + 1) & (ITEMQUESIZE - 1);
+    }
+
+    // P_SetThingPosition. Links a mobj into the sector it's in and the
+    // blockmap
+    pub fn set_thing_position(&mut self, id: MobjId) {
+        let (x, y): (Fixed, Fixed) = {
+            let mobj: &Mobj = self.thinkers.mobj(id);
+            (mobj.x, mobj.y)
+        };
+        let subsector: usize = self.level.point_in_subsector(x, y);
+        let sector: usize = self.level.subsectors[subsector].sector;
+
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+        mobj.subsector = subsector;
+
+        if mobj.flags & MF_NOSECTOR == 0 {
+            // Invisible things don't go into the sector links
+            let thing_list: Option<MobjId> = self.level.sectors[sector].thing_list;
+            mobj.sprev = None;
+            mobj.snext = thing_list;
+
+            if let Some(head) = thing_list {
+                self.thinkers.mobj_mut(head).sprev = Some(id);
+            }
+
+            self.level.sectors[sector].thing_list = Some(id);
+        }
+
+        // Link into blockmap
+        let mobj: &Mobj = self.thinkers.mobj(id);
+        if mobj.flags & MF_NOBLOCKMAP == 0 {
+            // Inert things don't need to be in blockmap
+            let block_x: i32 = self.level.blockmap.block_x(x);
+            let block_y: i32 = self.level.blockmap.block_y(y);
+
+            match self.level.blockmap.block_index(block_x, block_y) {
+                Some(index) => {
+                    let link: Option<MobjId> = self.level.blockmap.links[index];
+                    let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+                    mobj.bprev = None;
+                    mobj.bnext = link;
+
+                    if let Some(head) = link {
+                        self.thinkers.mobj_mut(head).bprev = Some(id);
+                    }
+
+                    self.level.blockmap.links[index] = Some(id);
+                }
+                None => {
+                    // Thing is off the map
+                    let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+                    mobj.bnext = None;
+                    mobj.bprev = None;
+                }
+            }
+        }
+    }
+
+    // P_UnsetThingPosition. Unlinks a mobj from its sector and the blockmap,
+    // done before moving it
+    pub fn unset_thing_position(&mut self, id: MobjId) {
+        let mobj: &Mobj = self.thinkers.mobj(id);
+
+        if mobj.flags & MF_NOSECTOR == 0 {
+            let (snext, sprev, subsector): (Option<MobjId>, Option<MobjId>, usize) =
+                (mobj.snext, mobj.sprev, mobj.subsector);
+
+            if let Some(snext) = snext {
+                self.thinkers.mobj_mut(snext).sprev = sprev;
+            }
+
+            match sprev {
+                Some(sprev) => self.thinkers.mobj_mut(sprev).snext = snext,
+                None => {
+                    let sector: usize = self.level.subsectors[subsector].sector;
+                    self.level.sectors[sector].thing_list = snext;
+                }
+            }
+        }
+
+        // Unlink from block map
+        let mobj: &Mobj = self.thinkers.mobj(id);
+        if mobj.flags & MF_NOBLOCKMAP == 0 {
+            let (bnext, bprev, x, y): (Option<MobjId>, Option<MobjId>, Fixed, Fixed) =
+                (mobj.bnext, mobj.bprev, mobj.x, mobj.y);
+
+            if let Some(bnext) = bnext {
+                self.thinkers.mobj_mut(bnext).bprev = bprev;
+            }
+
+            match bprev {
+                Some(bprev) => self.thinkers.mobj_mut(bprev).bnext = bnext,
+                None => {
+                    let block_x: i32 = self.level.blockmap.block_x(x);
+                    let block_y: i32 = self.level.blockmap.block_y(y);
+
+                    if let Some(index) = self.level.blockmap.block_index(block_x, block_y) {
+                        self.level.blockmap.links[index] = bnext;
+                    }
+                }
+            }
+        }
+    }
+
+    // Mobjs linked into a sector's thing list, most recently linked first
+    pub fn sector_things(&self, sector: usize) -> Vec<MobjId> {
+        let mut things: Vec<MobjId> = Vec::new();
+        let mut next: Option<MobjId> = self.level.sectors[sector].thing_list;
+
+        while let Some(id) = next {
+            things.push(id);
+            next = self.thinkers.mobj(id).snext;
+        }
+
+        things
+    }
+
+    // P_SpawnPlayer. Called when a player is spawned on the level
+    pub fn spawn_player(&mut self, map_thing: &MapThing) {
+        let player_num: usize = usize::try_from(map_thing.thing_type - 1).unwrap();
+
+        // Not playing?
+        if !self.settings.players_in_game[player_num] {
+            return;
+        }
+
+        if self.players[player_num].player_state == PlayerState::Reborn {
+            self.players[player_num].reborn();
+        }
+
+        let x: Fixed = Fixed(i32::from(map_thing.x) << FRACBITS);
+        let y: Fixed = Fixed(i32::from(map_thing.y) << FRACBITS);
+        let id: MobjId = self.spawn_mobj(x, y, ONFLOORZ, MT_PLAYER);
+
+        let health: i32 = self.players[player_num].health;
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+
+        // Set color translations for player sprites
+        if map_thing.thing_type > 1 {
+            mobj.flags |= ((map_thing.thing_type - 1) as u32) << MF_TRANSSHIFT;
+        }
+
+        mobj.angle = Angle(ANG45.wrapping_mul((i32::from(map_thing.angle) / 45) as u32));
+        mobj.player = Some(player_num);
+        mobj.health = health;
+
+        let deathmatch: bool = self.settings.deathmatch != Deathmatch::Off;
+        let player: &mut Player = &mut self.players[player_num];
+        player.mo = Some(id);
+        player.player_state = PlayerState::Live;
+        player.refire = 0;
+        player.damage_count = 0;
+        player.bonus_count = 0;
+        player.extra_light = 0;
+        player.fixed_colormap = 0;
+        player.message = None;
+        player.view_height = VIEWHEIGHT;
+
+        // Setup gun psprite
+        self.setup_psprites(player_num);
+
+        // Give all cards in death match mode
+        if deathmatch {
+            self.players[player_num].cards = [true; 6];
+        }
+    }
+
+    // G_CheckSpot. Returns false if the player cannot be respawned at the
+    // given mapthing spot because something is occupying it
+    pub fn check_spot(&mut self, player_num: usize, map_thing: &MapThing) -> bool {
+        let x: Fixed = Fixed(i32::from(map_thing.x) << FRACBITS);
+        let y: Fixed = Fixed(i32::from(map_thing.y) << FRACBITS);
+
+        let Some(mo) = self.players[player_num].mo else {
+            // First spawn of level, before corpses
+            return (0..player_num).all(|other| {
+                self.players[other]
+                    .mo
+                    .map(|id| self.thinkers.mobj(id))
+                    .is_none_or(|mobj| mobj.x != x || mobj.y != y)
+            });
+        };
+
+        if !self.check_position(mo, x, y) {
+            return false;
+        }
+
+        // Flush an old corpse if needed
+        let slot: usize = self.body_queue_slot % BODYQUESIZE;
+        if self.body_queue_slot >= BODYQUESIZE {
+            if let Some(body) = self.body_queue[slot] {
+                self.remove_mobj(body);
+            }
+        }
+        self.body_queue[slot] = Some(mo);
+        self.body_queue_slot += 1;
+
+        // Spawn a teleport fog
+        let sector: usize = self.level.point_in_sector(x, y);
+        let an: Angle = Angle(ANG45.wrapping_mul((i32::from(map_thing.angle) / 45) as u32));
+        let floor_height: Fixed = self.level.sectors[sector].floor_height;
+        let fog: MobjId = self.spawn_mobj(
+            x + Fixed(20 * an.cos().0),
+            y + Fixed(20 * an.sin().0),
+            floor_height,
+            MT_TFOG,
+        );
+
+        // Don't start sound on first frame
+        if self.players[self.settings.console_player].view_z != Fixed(1) {
+            self.start_sound(Some(fog), SFX_TELEPT);
+        }
+
+        true
+    }
+
+    // G_DeathMatchSpawnPlayer. Spawns a player at one of the random death
+    // match spots
+    pub fn deathmatch_spawn_player(&mut self, player_num: usize) {
+        let selections: usize = self.deathmatch_starts.len();
+        if selections < 4 {
+            panic!("Only {} deathmatch spots, 4 required", selections);
+        }
+
+        for _ in 0..20 {
+            let i: usize = self.random.p_random() as usize % selections;
+            let mut map_thing: MapThing = self.deathmatch_starts[i];
+            if self.check_spot(player_num, &map_thing) {
+                map_thing.thing_type = player_num as i16 + 1;
+                self.deathmatch_starts[i] = map_thing;
+                self.spawn_player(&map_thing);
+                return;
+            }
+        }
+
+        // No good spot, so the player will probably get stuck
+        self.spawn_player(&self.own_player_start(player_num));
+    }
+
+    // The network game half of G_DoReborn, single player games reload the
+    // level instead
+    pub fn respawn_player(&mut self, player_num: usize) {
+        // First dissasociate the corpse
+        if let Some(mo) = self.players[player_num].mo {
+            self.thinkers.mobj_mut(mo).player = None;
+        }
+
+        // Spawn at random spot if in death match
+        if self.settings.deathmatch != Deathmatch::Off {
+            self.deathmatch_spawn_player(player_num);
+            return;
+        }
+
+        let own_start: MapThing = self.own_player_start(player_num);
+        if self.check_spot(player_num, &own_start) {
+            self.spawn_player(&own_start);
+            return;
+        }
+
+        // Try to spawn at one of the other players spots
+        for start in self.player_starts {
+            let Some(mut start) = start else {
+                continue;
+            };
+            if self.check_spot(player_num, &start) {
+                // Fake as other player
+                start.thing_type = player_num as i16 + 1;
+                self.spawn_player(&start);
+                return;
+            }
+            // He's going to be inside something. Too bad
+        }
+
+        self.spawn_player(&own_start);
+    }
+
+    fn own_player_start(&self, player_num: usize) -> MapThing {
+        match self.player_starts[player_num] {
+            Some(start) => start,
+            None => panic!("Player {} has no start", player_num + 1),
+        }
+    }
+
+    // P_SpawnMapThing. The fields of the mapthing should already be in host
+    // byte order
+    pub fn spawn_map_thing(&mut self, map_thing: &MapThing) {
+        // Count deathmatch start positions
+        if map_thing.thing_type == 11 {
+            if self.deathmatch_starts.len() < 10 {
+                self.deathmatch_starts.push(*map_thing);
+            }
+            return;
+        }
+
+        // Some maps have type 0 things that the original read outside of
+        // the player starts for, they're skipped like later ports do
+        if map_thing.thing_type == 0 {
+            return;
+        }
+
+        // Check for players specially
+        if (1..=4).contains(&map_thing.thing_type) {
+            // Save spots for respawning in network games
+            self.player_starts[usize::try_from(map_thing.thing_type - 1).unwrap()] =
+                Some(*map_thing);
+            if self.settings.deathmatch == Deathmatch::Off {
+                self.spawn_player(map_thing);
+            }
+            return;
+        }
+
+        // Check for appropriate skill level
+        if !self.settings.netgame && map_thing.options & MTF_NOTSINGLE != 0 {
+            return;
+        }
+
+        let bit: i16 = match self.settings.skill {
+            GameDifficulty::Baby | GameDifficulty::Easy => MTF_EASY,
+            GameDifficulty::Medium => MTF_NORMAL,
+            GameDifficulty::Hard | GameDifficulty::Nightmare => MTF_HARD,
+        };
+
+        if map_thing.options & bit == 0 {
+            return;
+        }
+
+        // Find which type to spawn
+        let mobj_type: MobjType = match MOBJINFO
+            .iter()
+            .position(|info| info.doomed_num == i32::from(map_thing.thing_type))
+        {
+            Some(mobj_type) => mobj_type,
+            None => panic!(
+                "P_SpawnMapThing: Unknown type {} at ({}, {})",
+                map_thing.thing_type, map_thing.x, map_thing.y
+            ),
+        };
+        debug_assert!(mobj_type < NUMMOBJTYPES);
+        let info: &MobjInfo = &MOBJINFO[mobj_type];
+
+        // Don't spawn keycards and players in deathmatch
+        if self.settings.deathmatch != Deathmatch::Off && info.flags & MF_NOTDMATCH != 0 {
+            return;
+        }
+
+        // Don't spawn any monsters if -nomonsters
+        if self.settings.no_monsters && (mobj_type == MT_SKULL || info.flags & MF_COUNTKILL != 0) {
+            return;
+        }
+
+        // Spawn it
+        let x: Fixed = Fixed(i32::from(map_thing.x) << FRACBITS);
+        let y: Fixed = Fixed(i32::from(map_thing.y) << FRACBITS);
+        let z: Fixed = if info.flags & MF_SPAWNCEILING != 0 {
+            ONCEILINGZ
+        } else {
+            ONFLOORZ
+        };
+
+        let id: MobjId = self.spawn_mobj(x, y, z, mobj_type);
+        let tics: i32 = self.thinkers.mobj(id).tics;
+        let random_tics: Option<i32> = if tics > 0 {
+            Some(1 + self.random.p_random() % tics)
+        } else {
+            None
+        };
+
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+        mobj.spawn_point = *map_thing;
+        if let Some(random_tics) = random_tics {
+            mobj.tics = random_tics;
+        }
+        if mobj.flags & MF_COUNTKILL != 0 {
+            self.total_kills += 1;
+        }
+        if mobj.flags & MF_COUNTITEM != 0 {
+            self.total_items += 1;
+        }
+
+        mobj.angle = Angle(ANG45.wrapping_mul((i32::from(map_thing.angle) / 45) as u32));
+        if map_thing.options & MTF_AMBUSH != 0 {
+            mobj.flags |= MF_AMBUSH;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GameDifficulty;
+    use crate::fixed::{fixed_mul, Angle, Fixed, ANG45, ANG90, FRACUNIT};
+    use crate::info::mobjinfo::{
+        MT_BRUISERSHOT, MT_IFOG, MT_MISC10, MT_MISC51, MT_PLAYER, MT_POSSESSED, MT_SERGEANT,
+        MT_SHOTGUY, MT_SKULL, MT_TFOG, MT_TROOP, MT_TROOPSHOT,
+    };
+    use crate::info::states::*;
+    use crate::play::level::{MTF_AMBUSH, MTF_EASY, MTF_HARD, MTF_NORMAL, MTF_NOTSINGLE};
+    use crate::play::mobj::{
+        Mobj, MobjId, FRICTION, MF_AMBUSH, MF_MISSILE, MF_SKULLFLY, MF_TRANSSHIFT, ONFLOORZ,
+    };
+    use crate::play::player::PlayerState;
+    use crate::play::pspr::PS_WEAPON;
+    use crate::play::test_map::TestMap;
+    use crate::play::world::{Deathmatch, World, WorldSettings};
+    use crate::play::CompatibilityLevel;
+    use crate::sound::sfx::{SFX_FIRXPL, SFX_OOF};
+
+    const ALL_SKILLS: i16 = MTF_EASY | MTF_NORMAL | MTF_HARD;
+
+    fn compatibility(compatibility: CompatibilityLevel) -> WorldSettings {
+        WorldSettings {
+            compatibility,
+            ..Default::default()
+        }
+    }
+
+    fn mobj_types(world: &World) -> Vec<usize> {
+        world
+            .thinkers
+            .mobjs()
+            .map(|(_id, mobj)| mobj.mobj_type)
+            .collect()
+    }
+
+    #[test]
+    fn test_spawn_mobj_uses_sector_heights() {
+        let mut world: World = TestMap::new()
+            .room(128, 0, 128)
+            .room(128, 32, 96)
+            .to_world_with(WorldSettings::default());
+
+        let id: MobjId = world.spawn_mobj(
+            Fixed(200 * FRACUNIT),
+            Fixed(64 * FRACUNIT),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+        let mobj: &Mobj = world.thinkers.mobj(id);
+        assert_eq!(mobj.z, Fixed(32 * FRACUNIT));
+        assert_eq!(mobj.floor_z, Fixed(32 * FRACUNIT));
+        assert_eq!(mobj.ceiling_z, Fixed(96 * FRACUNIT));
+        assert_eq!(mobj.state, S_TROO_STND);
+        assert_eq!(mobj.health, 60);
+        assert_eq!(mobj.reaction_time, 8);
+        assert_eq!(world.sector_things(1), vec![id]);
+    }
+
+    #[test]
+    fn test_spawn_map_thing_hangs_ceiling_things() {
+        let world: World = TestMap::new()
+            .room(128, 0, 128)
+            .thing(64, 64, 0, 49, ALL_SKILLS)
+            .to_world_with(WorldSettings::default());
+
+        let (_id, mobj) = world.thinkers.mobjs().next().unwrap();
+        assert_eq!(mobj.mobj_type, MT_MISC51);
+        assert_eq!(mobj.z, Fixed((128 - 68) * FRACUNIT));
+    }
+
+    #[test]
+    fn test_spawn_map_thing_filters_by_skill() {
+        let test_map: TestMap = TestMap::new()
+            .room(256, 0, 128)
+            .thing(32, 32, 0, 3004, MTF_EASY)
+            .thing(64, 32, 0, 9, MTF_NORMAL)
+            .thing(96, 32, 0, 3001, MTF_HARD);
+
+        let skills: [(GameDifficulty, Vec<usize>); 5] = [
+            (GameDifficulty::Baby, vec![MT_POSSESSED]),
+            (GameDifficulty::Easy, vec![MT_POSSESSED]),
+            (GameDifficulty::Medium, vec![MT_SHOTGUY]),
+            (GameDifficulty::Hard, vec![MT_TROOP]),
+            (GameDifficulty::Nightmare, vec![MT_TROOP]),
+        ];
+
+        for (skill, expected) in skills {
+            let world: World = test_map.clone().to_world_with(WorldSettings {
+                skill,
+                ..Default::default()
+            });
+            assert_eq!(mobj_types(&world), expected);
+            assert_eq!(world.total_kills, 1);
+        }
+    }
+
+    #[test]
+    fn test_spawn_map_thing_skips_monsters_and_multiplayer_things() {
+        let test_map: TestMap = TestMap::new()
+            .room(256, 0, 128)
+            .thing(32, 32, 0, 3001, ALL_SKILLS)
+            .thing(64, 32, 0, 3006, ALL_SKILLS)
+            .thing(96, 32, 0, 2011, ALL_SKILLS)
+            .thing(128, 32, 0, 2012, ALL_SKILLS | MTF_NOTSINGLE);
+
+        let world: World = test_map.clone().to_world_with(WorldSettings::default());
+        assert_eq!(mobj_types(&world).len(), 3);
+
+        let world: World = test_map.clone().to_world_with(WorldSettings {
+            no_monsters: true,
+            ..Default::default()
+        });
+        assert_eq!(mobj_types(&world).len(), 1);
+        assert_eq!(world.total_kills, 0);
+
+        let world: World = test_map.to_world_with(WorldSettings {
+            netgame: true,
+            ..Default::default()
+        });
+        assert_eq!(mobj_types(&world).len(), 4);
+    }
+
+    #[test]
+    fn test_spawn_map_thing_sets_angle_ambush_and_random_tics() {
+        let world: World = TestMap::new()
+            .room(256, 0, 128)
+            .thing(32, 32, 100, 3001, ALL_SKILLS | MTF_AMBUSH)
+            .to_world_with(WorldSettings::default());
+
+        let (_id, mobj) = world.thinkers.mobjs().next().unwrap();
+        assert_eq!(mobj.angle, Angle(ANG90));
+        assert_ne!(mobj.flags & MF_AMBUSH, 0);
+        // lastlook takes the first P_Random, the tics the second
+        assert_eq!(mobj.last_look, 8 % 4);
+        assert_eq!(mobj.tics, 1 + 109 % 10);
+        assert_eq!(world.random.prndindex(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "P_SpawnMapThing: Unknown type 1234")]
+    fn test_spawn_map_thing_panics_on_unknown_type() {
+        TestMap::new()
+            .room(256, 0, 128)
+            .thing(32, 32, 0, 1234, ALL_SKILLS)
+            .to_world_with(WorldSettings::default());
+    }
+
+    #[test]
+    fn test_spawn_map_thing_spawns_players_and_saves_starts() {
+        let world: World = TestMap::new()
+            .room(256, 0, 128)
+            .thing(32, 32, 90, 1, 7)
+            .thing(64, 32, 0, 2, 7)
+            .thing(96, 32, 0, 11, 7)
+            .to_world_with(WorldSettings {
+                players_in_game: [true, true, false, false],
+                netgame: true,
+                ..Default::default()
+            });
+
+        assert_eq!(world.deathmatch_starts.len(), 1);
+        assert!(world.player_starts[0].is_some());
+        assert!(world.player_starts[1].is_some());
+
+        let player_mobj: &Mobj = world.thinkers.mobj(world.players[0].mo.unwrap());
+        assert_eq!(player_mobj.player, Some(0));
+        assert_eq!(player_mobj.angle, Angle(ANG90));
+        assert_eq!(player_mobj.health, 100);
+        assert_eq!(world.players[0].player_state, PlayerState::Live);
+
+        let second_mobj: &Mobj = world.thinkers.mobj(world.players[1].mo.unwrap());
+        assert_eq!(second_mobj.flags >> MF_TRANSSHIFT, 1);
+
+        // The gun starts coming up straight away
+        assert_eq!(world.players[0].psprites[PS_WEAPON].state, Some(S_PISTOLUP));
+    }
+
+    #[test]
+    fn test_set_mobj_state_runs_through_zero_tic_states() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .to_world_with(WorldSettings::default());
+        let id: MobjId = world.spawn_mobj(
+            Fixed(32 * FRACUNIT),
+            Fixed(32 * FRACUNIT),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+
+        assert!(world.set_mobj_state(id, S_TROO_PAIN));
+        assert_eq!(world.thinkers.mobj(id).state, S_TROO_PAIN);
+        assert_eq!(world.thinkers.mobj(id).tics, 2);
+
+        assert!(!world.set_mobj_state(id, S_NULL));
+        assert!(world.thinkers.is_removed(id));
+        assert!(world.sector_things(0).is_empty());
+    }
+
+    #[test]
+    fn test_unset_thing_position_unlinks_from_middle_of_list() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .to_world_with(WorldSettings::default());
+        let spawn = |world: &mut World| {
+            world.spawn_mobj(
+                Fixed(32 * FRACUNIT),
+                Fixed(32 * FRACUNIT),
+                ONFLOORZ,
+                MT_TROOP,
+            )
+        };
+        let first: MobjId = spawn(&mut world);
+        let second: MobjId = spawn(&mut world);
+        let third: MobjId = spawn(&mut world);
+        assert_eq!(world.sector_things(0), vec![third, second, first]);
+
+        world.unset_thing_position(second);
+        assert_eq!(world.sector_things(0), vec![third, first]);
+        world.unset_thing_position(third);
+        assert_eq!(world.sector_things(0), vec![first]);
+    }
+
+    #[test]
+    fn test_set_thing_position_links_into_blockmap() {
+        let mut world: World = TestMap::new()
+            .room(512, 0, 128)
+            .to_world_with(WorldSettings::default());
+        let first: MobjId =
+            world.spawn_mobj(Fixed::from_int(32), Fixed::from_int(32), ONFLOORZ, MT_TROOP);
+        let second: MobjId =
+            world.spawn_mobj(Fixed::from_int(40), Fixed::from_int(40), ONFLOORZ, MT_TROOP);
+        assert_eq!(world.block_things(0, 0), vec![second, first]);
+
+        assert!(world.try_move(first, Fixed::from_int(300), Fixed::from_int(32)));
+        assert_eq!(world.block_things(0, 0), vec![second]);
+        assert_eq!(world.block_things(2, 0), vec![first]);
+
+        world.remove_mobj(second);
+        assert!(world.block_things(0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_xy_movement_applies_friction_and_stops() {
+        let mut world: World = TestMap::new()
+            .room(512, 0, 128)
+            .to_world_with(WorldSettings::default());
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(128),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+
+        world.thinkers.mobj_mut(troop).mom_x = Fixed::from_int(8);
+        world.xy_movement(troop);
+        let mobj: &Mobj = world.thinkers.mobj(troop);
+        assert_eq!(mobj.x, Fixed::from_int(136));
+        assert_eq!(mobj.mom_x, fixed_mul(Fixed::from_int(8), FRICTION));
+
+        // Slow enough to stop
+        world.thinkers.mobj_mut(troop).mom_x = Fixed(0x800);
+        world.xy_movement(troop);
+        assert_eq!(world.thinkers.mobj(troop).mom_x, Fixed::ZERO);
+
+        // Airborne things keep their momentum
+        let mobj: &mut Mobj = world.thinkers.mobj_mut(troop);
+        mobj.z = Fixed::from_int(32);
+        mobj.mom_y = Fixed::from_int(4);
+        world.xy_movement(troop);
+        assert_eq!(world.thinkers.mobj(troop).mom_y, Fixed::from_int(4));
+    }
+
+    #[test]
+    fn test_xy_movement_stops_monsters_and_explodes_missiles_at_walls() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .to_world_with(WorldSettings::default());
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(230),
+            Fixed::from_int(64),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+        world.thinkers.mobj_mut(troop).mom_x = Fixed::from_int(10);
+        world.xy_movement(troop);
+        let mobj: &Mobj = world.thinkers.mobj(troop);
+        assert_eq!((mobj.x, mobj.mom_x), (Fixed::from_int(230), Fixed::ZERO));
+
+        let shot: MobjId = world.spawn_mobj(
+            Fixed::from_int(245),
+            Fixed::from_int(192),
+            Fixed::from_int(32),
+            MT_TROOPSHOT,
+        );
+        world.thinkers.mobj_mut(shot).mom_x = Fixed::from_int(10);
+        world.xy_movement(shot);
+        let mobj: &Mobj = world.thinkers.mobj(shot);
+        assert_eq!(mobj.state, S_TBALLX1);
+        assert_eq!(mobj.flags & MF_MISSILE, 0);
+        assert_eq!(mobj.mom_x, Fixed::ZERO);
+        assert_eq!(world.sound_events.last().unwrap().sfx, SFX_FIRXPL);
+    }
+
+    #[test]
+    fn test_xy_movement_wallruns_in_vanilla() {
+        // Running forward and strafing right at 40 degrees, into the top wall
+        let wallrun = |level: CompatibilityLevel| -> Fixed {
+            let mut world: World = TestMap::new()
+                .room(4000, 0, 128)
+                .to_world_with(compatibility(level));
+            let player: MobjId = world.spawn_mobj(
+                Fixed::from_int(64),
+                Fixed::from_int(230),
+                ONFLOORZ,
+                MT_PLAYER,
+            );
+            world.thinkers.mobj_mut(player).player = Some(0);
+            world.players[0].mo = Some(player);
+
+            let angle: Angle = Angle(ANG45 / 45 * 40);
+            for _ in 0..100 {
+                let mobj: &mut Mobj = world.thinkers.mobj_mut(player);
+                mobj.mom_x += fixed_mul(Fixed(50 * 2048), angle.cos());
+                mobj.mom_y += fixed_mul(Fixed(50 * 2048), angle.sin());
+                mobj.mom_x += fixed_mul(Fixed(40 * 2048), (angle - Angle::ANG90).cos());
+                mobj.mom_y += fixed_mul(Fixed(40 * 2048), (angle - Angle::ANG90).sin());
+                world.xy_movement(player);
+            }
+            world.thinkers.mobj(player).x
+        };
+
+        let vanilla: Fixed = wallrun(CompatibilityLevel::Vanilla);
+        let fixed_level: Fixed = wallrun(CompatibilityLevel::Fixed);
+        assert!(vanilla > fixed_level + Fixed::from_int(500));
+    }
+
+    #[test]
+    fn test_z_movement_applies_gravity_until_floor() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .to_world_with(WorldSettings::default());
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(128),
+            Fixed::from_int(128),
+            Fixed::from_int(20),
+            MT_TROOP,
+        );
+
+        world.z_movement(troop);
+        let mobj: &Mobj = world.thinkers.mobj(troop);
+        assert_eq!(
+            (mobj.z, mobj.mom_z),
+            (Fixed::from_int(20), Fixed::from_int(-2))
+        );
+        world.z_movement(troop);
+        let mobj: &Mobj = world.thinkers.mobj(troop);
+        assert_eq!(
+            (mobj.z, mobj.mom_z),
+            (Fixed::from_int(18), Fixed::from_int(-3))
+        );
+
+        for _ in 0..10 {
+            world.z_movement(troop);
+        }
+        let mobj: &Mobj = world.thinkers.mobj(troop);
+        assert_eq!((mobj.z, mobj.mom_z), (Fixed::ZERO, Fixed::ZERO));
+    }
+
+    #[test]
+    fn test_z_movement_squats_players_landing_hard() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .to_world_with(WorldSettings::default());
+        let player: MobjId = world.spawn_mobj(
+            Fixed::from_int(128),
+            Fixed::from_int(128),
+            Fixed::from_int(4),
+            MT_PLAYER,
+        );
+        world.thinkers.mobj_mut(player).player = Some(0);
+        world.players[0].mo = Some(player);
+        world.thinkers.mobj_mut(player).mom_z = Fixed::from_int(-16);
+
+        world.z_movement(player);
+
+        assert_eq!(world.thinkers.mobj(player).z, Fixed::ZERO);
+        assert_eq!(world.players[0].delta_view_height, Fixed::from_int(-2));
+        assert_eq!(world.sound_events.last().unwrap().sfx, SFX_OOF);
+    }
+
+    #[test]
+    fn test_z_movement_lost_soul_floor_bounce_depends_on_compatibility() {
+        let hit_floor = |level: CompatibilityLevel| -> Fixed {
+            let mut world: World = TestMap::new()
+                .room(256, 0, 128)
+                .to_world_with(compatibility(level));
+            let skull: MobjId = world.spawn_mobj(
+                Fixed::from_int(128),
+                Fixed::from_int(128),
+                Fixed::from_int(2),
+                MT_SKULL,
+            );
+            let mobj: &mut Mobj = world.thinkers.mobj_mut(skull);
+            mobj.flags |= MF_SKULLFLY;
+            mobj.mom_z = Fixed::from_int(-4);
+            world.z_movement(skull);
+            world.thinkers.mobj(skull).mom_z
+        };
+
+        assert_eq!(hit_floor(CompatibilityLevel::Vanilla), Fixed::ZERO);
+        assert_eq!(hit_floor(CompatibilityLevel::Fixed), Fixed::from_int(4));
+    }
+
+    #[test]
+    fn test_fast_monsters_speed_up_demons_and_missiles() {
+        let mut world: World = TestMap::new()
+            .room(512, 0, 128)
+            .to_world_with(WorldSettings {
+                fast_parm: true,
+                ..Default::default()
+            });
+        let demon: MobjId = world.spawn_mobj(
+            Fixed::from_int(64),
+            Fixed::from_int(64),
+            ONFLOORZ,
+            MT_SERGEANT,
+        );
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(400),
+            Fixed::from_int(64),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+
+        // 8 tics normally
+        world.set_mobj_state(demon, S_SARG_ATK1);
+        assert_eq!(world.thinkers.mobj(demon).tics, 4);
+
+        let missile: MobjId = world.spawn_missile(troop, demon, MT_TROOPSHOT);
+        assert_eq!(world.thinkers.mobj(missile).mom_x, Fixed(-1310700));
+        assert_eq!(world.missile_speed(MT_BRUISERSHOT), Fixed::from_int(20));
+    }
+
+    #[test]
+    fn test_mobj_thinker_respawns_monsters_with_respawn() {
+        let mut world: World = TestMap::new()
+            .room(512, 0, 128)
+            .thing(200, 128, 90, 3001, ALL_SKILLS)
+            .to_world_with(WorldSettings {
+                respawn_parm: true,
+                ..Default::default()
+            });
+        let troop: MobjId = world.thinkers.mobjs().next().unwrap().0;
+        world.damage_mobj(troop, None, None, 1000);
+        while world.thinkers.mobj(troop).tics != -1 {
+            world.mobj_thinker(troop);
+        }
+
+        world.thinkers.mobj_mut(troop).move_count = 12 * 35 - 1;
+        world.level_time = 32;
+        // Index 0 of the random table is 0
+        world.random.set_prndindex(255);
+        world.mobj_thinker(troop);
+
+        assert!(world.thinkers.is_removed(troop));
+        assert_eq!(mobj_types(&world), vec![MT_TFOG, MT_TFOG, MT_TROOP]);
+
+        let (_id, respawned): (MobjId, &Mobj) = world.thinkers.mobjs().last().unwrap();
+        assert_eq!(respawned.reaction_time, 18);
+        assert_eq!(respawned.angle, Angle(ANG90));
+        assert_eq!(respawned.spawn_point.x, 200);
+    }
+
+    #[test]
+    fn test_respawn_specials_brings_items_back_in_altdeath() {
+        let mut world: World = TestMap::new()
+            .room(512, 0, 128)
+            .thing(200, 128, 0, 2011, ALL_SKILLS)
+            .to_world_with(WorldSettings {
+                deathmatch: Deathmatch::AltDeath,
+                // Nobody to spawn at the deathmatch starts
+                players_in_game: [false; 4],
+                ..Default::default()
+            });
+        let stimpack: MobjId = world.thinkers.mobjs().next().unwrap().0;
+        world.remove_mobj(stimpack);
+
+        world.level_time = 30 * 35 - 1;
+        world.respawn_specials();
+        assert_eq!(world.thinkers.mobjs().count(), 0);
+
+        world.level_time += 1;
+        world.respawn_specials();
+        assert_eq!(mobj_types(&world), vec![MT_IFOG, MT_MISC10]);
+
+        // Only once
+        world.respawn_specials();
+        assert_eq!(world.thinkers.mobjs().count(), 2);
+    }
+}
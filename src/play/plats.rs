@@ -0,0 +1,392 @@
+// Port of p_plats.c. Lifts and moving platforms, which also keep a list of
+// active plats so they can be stopped and restarted by tag
+use crate::fixed::{Fixed, FRACUNIT};
+use crate::play::floor::{MoveResult, Plane};
+use crate::play::thinker::{Thinker, ThinkerId};
+use crate::play::world::World;
+use crate::play::TICRATE;
+use crate::sound::sfx::{SFX_PSTART, SFX_PSTOP, SFX_STNMOV};
+
+pub const MAXPLATS: usize = 30;
+pub const PLATWAIT: i32 = 3;
+pub const PLATSPEED: Fixed = Fixed(FRACUNIT);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlatStatus {
+    Up,
+    Down,
+    Waiting,
+    InStasis,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlatType {
+    PerpetualRaise,
+    DownWaitUpStay,
+    RaiseAndChange,
+    RaiseToNearestAndChange,
+    BlazeDwus,
+}
+
+// plat_t
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Plat {
+    pub sector: usize,
+    pub speed: Fixed,
+    pub low: Fixed,
+    pub high: Fixed,
+    pub wait: i32,
+    pub count: i32,
+    pub status: PlatStatus,
+    pub old_status: PlatStatus,
+    pub crush: bool,
+    pub tag: i32,
+    pub plat_type: PlatType,
+}
+
+impl World {
+    // T_PlatRaise. Move a plat up and down
+    pub fn plat_raise_thinker(&mut self, id: ThinkerId) {
+        let mut plat: Plat = match self.thinkers.get(id) {
+            Some(Thinker::Plat(plat)) => *plat,
+            _ => return,
+        };
+
+        match plat.status {
+            PlatStatus::Up => {
+                let res: MoveResult = self.move_plane(
+                    plat.sector,
+                    plat.speed,
+                    plat.high,
+                    plat.crush,
+                    Plane::Floor,
+                    1,
+                );
+
+                if (plat.plat_type == PlatType::RaiseAndChange
+                    || plat.plat_type == PlatType::RaiseToNearestAndChange)
+                    && self.level_time & 7 == 0
+                {
+                    self.start_sector_sound(plat.sector, SFX_STNMOV);
+                }
+
+                if res == MoveResult::Crushed && !plat.crush {
+                    plat.count = plat.wait;
+                    plat.status = PlatStatus::Down;
+                    self.start_sector_sound(plat.sector, SFX_PSTART);
+                } else if res == MoveResult::PastDest {
+                    plat.count = plat.wait;
+                    plat.status = PlatStatus::Waiting;
+                    self.start_sector_sound(plat.sector, SFX_PSTOP);
+
+                    match plat.plat_type {
+                        PlatType::BlazeDwus
+                        | PlatType::DownWaitUpStay
+                        | PlatType::RaiseAndChange
+                        | PlatType::RaiseToNearestAndChange => {
+                            self.remove_active_plat(id);
+                            return;
+                        }
+                        PlatType::PerpetualRaise => {}
+                    }
+                }
+            }
+            PlatStatus::Down => {
+                let res: MoveResult =
+                    self.move_plane(plat.sector, plat.speed, plat.low, false, Plane::Floor, -1);
+
+                if res == MoveResult::PastDest {
+                    plat.count = plat.wait;
+                    plat.status = PlatStatus::Waiting;
+                    self.start_sector_sound(plat.sector, SFX_PSTOP);
+                }
+            }
+            PlatStatus::Waiting => {
+                plat.count -= 1;
+                if plat.count == 0 {
+                    if self.level.sectors[plat.sector].floor_height == plat.low {
+                        plat.status = PlatStatus::Up;
+                    } else {
+                        plat.status = PlatStatus::Down;
+                    }
+                    self.start_sector_sound(plat.sector, SFX_PSTART);
+                }
+            }
+            PlatStatus::InStasis => {}
+        }
+
+        if let Some(Thinker::Plat(stored)) = self.thinkers.get_mut(id) {
+            *stored = plat;
+        }
+    }
+
+    // EV_DoPlat. Do Platforms, "amount" is only used for SOME platforms
+    pub fn do_plat(&mut self, line: usize, plat_type: PlatType, amount: i32) -> bool {
+        let tag: i32 = self.level.lines[line].tag;
+        let mut rtn: bool = false;
+
+        // Activate all <type> plats that are in_stasis
+        if plat_type == PlatType::PerpetualRaise {
+            self.activate_in_stasis(tag);
+        }
+
+        for sector in self.level.tagged_sectors(line) {
+            if self.level.sectors[sector].special_data.is_some() {
+                continue;
+            }
+
+            // Find lowest & highest floors around sector
+            rtn = true;
+            let floor_height: Fixed = self.level.sectors[sector].floor_height;
+            let mut plat: Plat = Plat {
+                sector,
+                speed: PLATSPEED,
+                low: Fixed::ZERO,
+                high: Fixed::ZERO,
+                wait: 0,
+                count: 0,
+                status: PlatStatus::Up,
+                old_status: PlatStatus::Up,
+                crush: false,
+                tag,
+                plat_type,
+            };
+
+            match plat_type {
+                PlatType::RaiseToNearestAndChange | PlatType::RaiseAndChange => {
+                    plat.speed = Fixed(PLATSPEED.0 / 2);
+                    let front: usize = self.level.lines[line].front_sector.unwrap();
+                    self.level.sectors[sector].floor_pic = self.level.sectors[front].floor_pic;
+                    if plat_type == PlatType::RaiseToNearestAndChange {
+                        plat.high = self.level.find_next_highest_floor(sector, floor_height);
+                        // NO MORE DAMAGE, IF APPLICABLE
+                        self.level.sectors[sector].special = 0;
+                    } else {
+                        plat.high = floor_height + Fixed(amount * FRACUNIT);
+                    }
+                    plat.wait = 0;
+                    plat.status = PlatStatus::Up;
+                    self.start_sector_sound(sector, SFX_STNMOV);
+                }
+                PlatType::DownWaitUpStay | PlatType::BlazeDwus => {
+                    plat.speed = if plat_type == PlatType::BlazeDwus {
+                        Fixed(PLATSPEED.0 * 8)
+                    } else {
+                        Fixed(PLATSPEED.0 * 4)
+                    };
+                    plat.low = self
+                        .level
+                        .find_lowest_floor_surrounding(sector)
+                        .min(floor_height);
+                    plat.high = floor_height;
+                    plat.wait = TICRATE * PLATWAIT;
+                    plat.status = PlatStatus::Down;
+                    self.start_sector_sound(sector, SFX_PSTART);
+                }
+                PlatType::PerpetualRaise => {
+                    plat.low = self
+                        .level
+                        .find_lowest_floor_surrounding(sector)
+                        .min(floor_height);
+                    plat.high = self
+                        .level
+                        .find_highest_floor_surrounding(sector)
+                        .max(floor_height);
+                    plat.wait = TICRATE * PLATWAIT;
+                    plat.status = if self.random.p_random() & 1 == 0 {
+                        PlatStatus::Up
+                    } else {
+                        PlatStatus::Down
+                    };
+                    self.start_sector_sound(sector, SFX_PSTART);
+                }
+            }
+
+            let id: ThinkerId = self.thinkers.add(Thinker::Plat(plat));
+            self.level.sectors[sector].special_data = Some(id);
+            self.add_active_plat(id);
+        }
+
+        rtn
+    }
+
+    // P_ActivateInStasis
+    pub fn activate_in_stasis(&mut self, tag: i32) {
+        for id in self.active_plats.iter().flatten() {
+            if let Some(Thinker::Plat(plat)) = self.thinkers.get_mut(*id) {
+                if plat.tag == tag && plat.status == PlatStatus::InStasis {
+                    plat.status = plat.old_status;
+                }
+            }
+        }
+    }
+
+    // EV_StopPlat
+    pub fn stop_plat(&mut self, line: usize) {
+        let tag: i32 = self.level.lines[line].tag;
+
+        for id in self.active_plats.iter().flatten() {
+            if let Some(Thinker::Plat(plat)) = self.thinkers.get_mut(*id) {
+                if plat.status != PlatStatus::InStasis && plat.tag == tag {
+                    plat.old_status = plat.status;
+                    plat.status = PlatStatus::InStasis;
+                }
+            }
+        }
+    }
+
+    // P_AddActivePlat
+    pub fn add_active_plat(&mut self, id: ThinkerId) {
+        match self.active_plats.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => *slot = Some(id),
+            None => panic!("P_AddActivePlat: no more plats!"),
+        }
+    }
+
+    // P_RemoveActivePlat
+    pub fn remove_active_plat(&mut self, id: ThinkerId) {
+        let slot: &mut Option<ThinkerId> =
+            match self.active_plats.iter_mut().find(|slot| **slot == Some(id)) {
+                Some(slot) => slot,
+                None => panic!("P_RemoveActivePlat: can't find plat!"),
+            };
+        *slot = None;
+
+        if let Some(Thinker::Plat(plat)) = self.thinkers.get(id) {
+            self.level.sectors[plat.sector].special_data = None;
+        }
+        self.thinkers.remove(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::Fixed;
+    use crate::info::mobjinfo::MT_TROOP;
+    use crate::play::mobj::{MobjId, ONFLOORZ};
+    use crate::play::plats::{PlatStatus, PlatType, MAXPLATS};
+    use crate::play::test_map::{tick, TestMap, TestRoom};
+    use crate::play::thinker::Thinker;
+    use crate::play::world::World;
+
+    fn tagged(floor_height: i16) -> TestRoom {
+        TestRoom {
+            width: 128,
+            floor_height,
+            ceiling_height: 128,
+            light_level: 160,
+            tag: 1,
+            ..Default::default()
+        }
+    }
+
+    fn tagged_line(world: &World) -> usize {
+        world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == 1)
+            .unwrap()
+    }
+
+    fn plat_status(world: &World, sector: usize) -> PlatStatus {
+        match world
+            .thinkers
+            .get(world.level.sectors[sector].special_data.unwrap())
+        {
+            Some(Thinker::Plat(plat)) => plat.status,
+            _ => panic!("no plat"),
+        }
+    }
+
+    #[test]
+    fn test_do_plat_down_wait_up_stay() {
+        let mut world: World = TestMap::new()
+            .room(128, 0, 128)
+            .room_with(tagged(64))
+            .room(128, 64, 128)
+            .line_special(0, 88, 1)
+            .to_world();
+        let line: usize = tagged_line(&world);
+
+        assert!(world.do_plat(line, PlatType::DownWaitUpStay, 0));
+        assert!(!world.do_plat(line, PlatType::DownWaitUpStay, 0));
+
+        // Down to the lowest surrounding floor at 4 units a tic
+        tick(&mut world, 16);
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(0));
+        tick(&mut world, 1);
+        assert_eq!(plat_status(&world, 1), PlatStatus::Waiting);
+
+        // Waits 3 seconds at the bottom
+        tick(&mut world, 105);
+        assert_eq!(plat_status(&world, 1), PlatStatus::Up);
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(0));
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(4));
+
+        tick(&mut world, 16);
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(64));
+        assert_eq!(world.level.sectors[1].special_data, None);
+        assert!(world.active_plats.iter().all(|plat| plat.is_none()));
+    }
+
+    #[test]
+    fn test_do_plat_raise_and_change_takes_front_flat() {
+        let mut world: World = TestMap::new()
+            .room_with(tagged(0))
+            .room(128, 64, 128)
+            .line_special(0, 66, 1)
+            .to_world();
+        let line: usize = tagged_line(&world);
+        world.level.sectors[1].floor_pic = *b"NUKAGE1\0";
+
+        assert!(world.do_plat(line, PlatType::RaiseAndChange, 24));
+        assert_eq!(&world.level.sectors[0].floor_pic, b"NUKAGE1\0");
+
+        // Half speed
+        tick(&mut world, 48);
+        assert_eq!(world.level.sectors[0].floor_height, Fixed::from_int(24));
+        assert!(world.level.sectors[0].special_data.is_some());
+        tick(&mut world, 1);
+        assert_eq!(world.level.sectors[0].special_data, None);
+    }
+
+    #[test]
+    fn test_stop_plat_and_restart_perpetual_raise() {
+        let mut world: World = TestMap::new()
+            .room(128, 0, 128)
+            .room_with(tagged(32))
+            .room(128, 64, 128)
+            .line_special(0, 87, 1)
+            .to_world();
+        let line: usize = tagged_line(&world);
+
+        assert!(world.do_plat(line, PlatType::PerpetualRaise, 0));
+        tick(&mut world, 5);
+        let height: Fixed = world.level.sectors[1].floor_height;
+        assert!(height == Fixed::from_int(27) || height == Fixed::from_int(37));
+
+        world.stop_plat(line);
+        assert_eq!(plat_status(&world, 1), PlatStatus::InStasis);
+        tick(&mut world, 10);
+        assert_eq!(world.level.sectors[1].floor_height, height);
+
+        // The sector is still busy, but the stopped plat starts again
+        assert!(!world.do_plat(line, PlatType::PerpetualRaise, 0));
+        tick(&mut world, 1);
+        assert_ne!(world.level.sectors[1].floor_height, height);
+    }
+
+    #[test]
+    #[should_panic(expected = "P_AddActivePlat: no more plats!")]
+    fn test_add_active_plat_panics_when_full() {
+        let mut world: World = TestMap::new().room(128, 0, 128).to_world();
+        let id: MobjId =
+            world.spawn_mobj(Fixed::from_int(64), Fixed::from_int(64), ONFLOORZ, MT_TROOP);
+
+        for _ in 0..=MAXPLATS {
+            world.add_active_plat(id);
+        }
+    }
+}
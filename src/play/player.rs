@@ -0,0 +1,200 @@
+// Port of d_player.h and the weapon and ammo types from doomdef.h
+use crate::fixed::{Fixed, FRACUNIT};
+use crate::play::inter::MAXAMMO;
+use crate::play::mobj::MobjId;
+use crate::play::pspr::{PspDef, NUMPSPRITES};
+use crate::play::ticcmd::TicCmd;
+use crate::play::MAXPLAYERS;
+
+pub const MAXHEALTH: i32 = 100;
+pub const VIEWHEIGHT: Fixed = Fixed(41 * FRACUNIT);
+
+// Indexes into Player::cards
+pub const IT_BLUECARD: usize = 0;
+pub const IT_YELLOWCARD: usize = 1;
+pub const IT_REDCARD: usize = 2;
+pub const IT_BLUESKULL: usize = 3;
+pub const IT_YELLOWSKULL: usize = 4;
+pub const IT_REDSKULL: usize = 5;
+pub const NUMCARDS: usize = 6;
+
+// Power up artifacts, indexes into Player::powers
+pub const PW_INVULNERABILITY: usize = 0;
+pub const PW_STRENGTH: usize = 1;
+pub const PW_INVISIBILITY: usize = 2;
+pub const PW_IRONFEET: usize = 3;
+pub const PW_ALLMAP: usize = 4;
+pub const PW_INFRARED: usize = 5;
+pub const NUMPOWERS: usize = 6;
+
+// The defined weapons, including a marker indicating user has not changed
+// weapon
+pub type WeaponType = usize;
+pub const WP_FIST: WeaponType = 0;
+pub const WP_PISTOL: WeaponType = 1;
+pub const WP_SHOTGUN: WeaponType = 2;
+pub const WP_CHAINGUN: WeaponType = 3;
+pub const WP_MISSILE: WeaponType = 4;
+pub const WP_PLASMA: WeaponType = 5;
+pub const WP_BFG: WeaponType = 6;
+pub const WP_CHAINSAW: WeaponType = 7;
+pub const WP_SUPERSHOTGUN: WeaponType = 8;
+pub const NUMWEAPONS: usize = 9;
+// No pending weapon change
+pub const WP_NOCHANGE: WeaponType = 10;
+
+// Ammunition types defined
+pub type AmmoType = usize;
+// Pistol / chaingun ammo
+pub const AM_CLIP: AmmoType = 0;
+// Shotgun / double barreled shotgun
+pub const AM_SHELL: AmmoType = 1;
+// Plasma rifle, BFG
+pub const AM_CELL: AmmoType = 2;
+// Missile launcher
+pub const AM_MISL: AmmoType = 3;
+pub const NUMAMMO: usize = 4;
+// Unlimited for chainsaw / fist
+pub const AM_NOAMMO: AmmoType = 5;
+
+// Player cheat flags
+pub const CF_NOCLIP: i32 = 1;
+pub const CF_GODMODE: i32 = 2;
+// Not really a cheat, just a debug aid
+pub const CF_NOMOMENTUM: i32 = 4;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlayerState {
+    // Playing or camping
+    Live,
+    // Dead on the ground, view follows killer
+    Dead,
+    // Ready to restart/respawn
+    #[default]
+    Reborn,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Player {
+    pub mo: Option<MobjId>,
+    pub player_state: PlayerState,
+    pub cmd: TicCmd,
+    // Only used between levels, the mobj's health is used during play
+    pub health: i32,
+    pub armor_points: i32,
+    // Armor type is 0-2
+    pub armor_type: i32,
+    // Power ups. Invinc and invis are tic counters
+    pub powers: [i32; NUMPOWERS],
+    pub cards: [bool; NUMCARDS],
+    pub backpack: bool,
+    pub ready_weapon: WeaponType,
+    // Is WP_NOCHANGE if not changing
+    pub pending_weapon: WeaponType,
+    pub weapon_owned: [bool; NUMWEAPONS],
+    pub ammo: [i32; NUMAMMO],
+    pub max_ammo: [i32; NUMAMMO],
+    // True if button down last tic
+    pub attack_down: bool,
+    pub use_down: bool,
+    // Refired shots are less accurate
+    pub refire: i32,
+    // For intermission stats
+    pub frags: [i32; MAXPLAYERS],
+    pub kill_count: i32,
+    pub item_count: i32,
+    pub secret_count: i32,
+    // For screen flashing(red or bright)
+    pub damage_count: i32,
+    pub bonus_count: i32,
+    // Who did damage (None for floors/ceilings)
+    pub attacker: Option<MobjId>,
+    // So gun flashes light up areas
+    pub extra_light: i32,
+    // Current PLAYPAL, can be set to REDCOLORMAP for pain, etc
+    pub fixed_colormap: i32,
+    // Focal origin above the mobj's z
+    pub view_z: Fixed,
+    // Base height above floor for view_z
+    pub view_height: Fixed,
+    // Bob/squat speed
+    pub delta_view_height: Fixed,
+    // Bounded/scaled total momentum
+    pub bob: Fixed,
+    pub cheats: i32,
+    // Hint messages
+    pub message: Option<&'static str>,
+    // Overlay view sprites (gun, etc)
+    pub psprites: [PspDef; NUMPSPRITES],
+}
+
+impl Player {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // G_PlayerReborn. Called after a player dies, almost everything is
+    // cleared and initialized apart from the intermission stats
+    pub fn reborn(&mut self) {
+        let frags: [i32; MAXPLAYERS] = self.frags;
+        let (kill_count, item_count, secret_count): (i32, i32, i32) =
+            (self.kill_count, self.item_count, self.secret_count);
+
+        *self = Player {
+            frags,
+            kill_count,
+            item_count,
+            secret_count,
+            // Don't do anything immediately
+            use_down: true,
+            attack_down: true,
+            player_state: PlayerState::Live,
+            health: MAXHEALTH,
+            ready_weapon: WP_PISTOL,
+            pending_weapon: WP_PISTOL,
+            weapon_owned: [true, true, false, false, false, false, false, false, false],
+            ammo: [50, 0, 0, 0],
+            max_ammo: MAXAMMO,
+            ..Default::default()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::play::inter::MAXAMMO;
+    use crate::play::player::{
+        Player, PlayerState, AM_CLIP, MAXHEALTH, WP_FIST, WP_PISTOL, WP_SHOTGUN,
+    };
+
+    #[test]
+    fn test_player_reborn_keeps_intermission_stats() {
+        let mut player: Player = Player {
+            kill_count: 3,
+            item_count: 2,
+            secret_count: 1,
+            frags: [0, 2, 0, 0],
+            cards: [true; 6],
+            damage_count: 20,
+            ..Default::default()
+        };
+
+        player.reborn();
+
+        assert_eq!(player.player_state, PlayerState::Live);
+        assert_eq!(player.health, MAXHEALTH);
+        assert_eq!(
+            (player.kill_count, player.item_count, player.secret_count),
+            (3, 2, 1)
+        );
+        assert_eq!(player.frags, [0, 2, 0, 0]);
+        assert_eq!(player.cards, [false; 6]);
+        assert_eq!(player.damage_count, 0);
+        assert_eq!(player.ready_weapon, WP_PISTOL);
+        assert!(player.weapon_owned[WP_FIST] && player.weapon_owned[WP_PISTOL]);
+        assert!(!player.weapon_owned[WP_SHOTGUN]);
+        assert_eq!(player.ammo[AM_CLIP], 50);
+        assert_eq!(player.max_ammo, MAXAMMO);
+        assert!(player.attack_down && player.use_down);
+    }
+}
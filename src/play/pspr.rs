@@ -0,0 +1,805 @@
+// Port of p_pspr.c. Weapon sprite animation, weapon objects, action
+// functions for weapons
+use crate::config::GameType;
+use crate::fixed::tables::{finecosine, finesine, FINEANGLES, FINEMASK};
+use crate::fixed::{fixed_mul, point_to_angle, Angle, Fixed, ANG180, ANG90, FRACBITS, FRACUNIT};
+use crate::info::mobjinfo::{MT_BFG, MT_EXTRABFG, MT_PLASMA, MT_ROCKET};
+use crate::info::states::{STATES, S_CHAIN1, S_NULL, S_PLAY, S_PLAY_ATK1, S_PLAY_ATK2, S_SAW};
+use crate::info::weaponinfo::WEAPONINFO;
+use crate::info::{Action, State, StateNum};
+use crate::play::map::{MELEERANGE, MISSILERANGE};
+use crate::play::mobj::{Mobj, MobjId, MF_JUSTATTACKED};
+use crate::play::player::{
+    AmmoType, Player, PlayerState, AM_CELL, AM_CLIP, AM_MISL, AM_NOAMMO, AM_SHELL, PW_STRENGTH,
+    WP_BFG, WP_CHAINGUN, WP_CHAINSAW, WP_FIST, WP_MISSILE, WP_NOCHANGE, WP_PISTOL, WP_PLASMA,
+    WP_SHOTGUN, WP_SUPERSHOTGUN,
+};
+use crate::play::ticcmd::BT_ATTACK;
+use crate::play::world::World;
+use crate::sound::sfx::{
+    SfxNum, SFX_BFG, SFX_DBCLS, SFX_DBLOAD, SFX_DBOPN, SFX_DSHTGN, SFX_PISTOL, SFX_PUNCH,
+    SFX_SAWFUL, SFX_SAWHIT, SFX_SAWIDL, SFX_SAWUP, SFX_SHOTGN,
+};
+
+pub const LOWERSPEED: Fixed = Fixed(FRACUNIT * 6);
+pub const RAISESPEED: Fixed = Fixed(FRACUNIT * 6);
+
+pub const WEAPONBOTTOM: Fixed = Fixed(128 * FRACUNIT);
+pub const WEAPONTOP: Fixed = Fixed(32 * FRACUNIT);
+
+// Plasma cells for a bfg attack
+pub const BFGCELLS: i32 = 40;
+
+// Overlay psprites are scaled shapes drawn directly on the view screen,
+// coordinates are given for a 320*200 view screen
+pub const PS_WEAPON: usize = 0;
+pub const PS_FLASH: usize = 1;
+pub const NUMPSPRITES: usize = 2;
+
+// pspdef_t
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PspDef {
+    // None state means not active
+    pub state: Option<StateNum>,
+    pub tics: i32,
+    pub sx: Fixed,
+    pub sy: Fixed,
+}
+
+impl World {
+    // P_SetPsprite
+    pub fn set_psprite(&mut self, player: usize, position: usize, state: StateNum) {
+        let mut state: StateNum = state;
+
+        loop {
+            if state == S_NULL {
+                // Object removed itself
+                self.players[player].psprites[position].state = None;
+                return;
+            }
+
+            let st: &State = &STATES[state];
+            let psp: &mut PspDef = &mut self.players[player].psprites[position];
+            psp.state = Some(state);
+            psp.tics = st.tics;
+
+            if st.misc1 != 0 {
+                // Coordinate set
+                psp.sx = Fixed(st.misc1 << FRACBITS);
+                psp.sy = Fixed(st.misc2 << FRACBITS);
+            }
+
+            // Call action routine. Modified handling
+            self.call_psprite_action(player, position, st.action);
+
+            let psp: &PspDef = &self.players[player].psprites[position];
+            state = match psp.state {
+                Some(current) => STATES[current].next_state,
+                None => return,
+            };
+
+            if psp.tics != 0 {
+                return;
+            }
+        }
+    }
+
+    fn call_psprite_action(&mut self, player: usize, position: usize, action: Action) {
+        match action {
+            Action::Light0 => self.a_light0(player),
+            Action::WeaponReady => self.a_weapon_ready(player, position),
+            Action::Lower => self.a_lower(player, position),
+            Action::Raise => self.a_raise(player, position),
+            Action::Punch => self.a_punch(player),
+            Action::ReFire => self.a_refire(player),
+            Action::FirePistol => self.a_fire_pistol(player),
+            Action::Light1 => self.a_light1(player),
+            Action::FireShotgun => self.a_fire_shotgun(player),
+            Action::Light2 => self.a_light2(player),
+            Action::FireShotgun2 => self.a_fire_shotgun2(player),
+            Action::CheckReload => self.a_check_reload(player),
+            Action::OpenShotgun2 => self.a_open_shotgun2(player),
+            Action::LoadShotgun2 => self.a_load_shotgun2(player),
+            Action::CloseShotgun2 => self.a_close_shotgun2(player),
+            Action::FireCGun => self.a_fire_cgun(player, position),
+            Action::GunFlash => self.a_gun_flash(player),
+            Action::FireMissile => self.a_fire_missile(player),
+            Action::Saw => self.a_saw(player),
+            Action::FirePlasma => self.a_fire_plasma(player),
+            Action::BfgSound => self.a_bfg_sound(player),
+            Action::FireBfg => self.a_fire_bfg(player),
+            // The rest are monster actions called on mobjs
+            _ => {}
+        }
+    }
+
+    fn player_mo(&self, player: usize) -> MobjId {
+        self.players[player].mo.unwrap()
+    }
+
+    // P_BringUpWeapon. Starts bringing the pending weapon up from the
+    // bottom of the screen. Uses player
+    pub fn bring_up_weapon(&mut self, player: usize) {
+        let p: &mut Player = &mut self.players[player];
+
+        if p.pending_weapon == WP_NOCHANGE {
+            p.pending_weapon = p.ready_weapon;
+        }
+
+        if p.pending_weapon == WP_CHAINSAW {
+            let mo: MobjId = self.player_mo(player);
+            self.start_sound(Some(mo), SFX_SAWUP);
+        }
+
+        let p: &mut Player = &mut self.players[player];
+        let new_state: StateNum = WEAPONINFO[p.pending_weapon].up_state;
+
+        p.pending_weapon = WP_NOCHANGE;
+        p.psprites[PS_WEAPON].sy = WEAPONBOTTOM;
+
+        self.set_psprite(player, PS_WEAPON, new_state);
+    }
+
+    // P_CheckAmmo. Returns true if there is enough ammo to shoot. If not,
+    // selects the next weapon to use
+    pub fn check_ammo(&mut self, player: usize) -> bool {
+        let game_type: GameType = self.settings.game_type;
+        let p: &mut Player = &mut self.players[player];
+        let ammo: AmmoType = WEAPONINFO[p.ready_weapon].ammo;
+
+        // Minimal amount for one shot varies
+        let count: i32 = match p.ready_weapon {
+            WP_BFG => BFGCELLS,
+            // Double barrel
+            WP_SUPERSHOTGUN => 2,
+            // Regular
+            _ => 1,
+        };
+
+        // Some do not need ammunition anyway. Return if current ammunition
+        // sufficient
+        if ammo == AM_NOAMMO || p.ammo[ammo] >= count {
+            return true;
+        }
+
+        // Out of ammo, pick a weapon to change to. Preferences are set here
+        let owned: [bool; 9] = p.weapon_owned;
+        p.pending_weapon = if owned[WP_PLASMA]
+            && p.ammo[AM_CELL] != 0
+            && game_type != GameType::DoomIShareware
+        {
+            WP_PLASMA
+        } else if owned[WP_SUPERSHOTGUN] && p.ammo[AM_SHELL] > 2 && game_type == GameType::DoomII {
+            WP_SUPERSHOTGUN
+        } else if owned[WP_CHAINGUN] && p.ammo[AM_CLIP] != 0 {
+            WP_CHAINGUN
+        } else if owned[WP_SHOTGUN] && p.ammo[AM_SHELL] != 0 {
+            WP_SHOTGUN
+        } else if p.ammo[AM_CLIP] != 0 {
+            WP_PISTOL
+        } else if owned[WP_CHAINSAW] {
+            WP_CHAINSAW
+        } else if owned[WP_MISSILE] && p.ammo[AM_MISL] != 0 {
+            WP_MISSILE
+        } else if owned[WP_BFG] && p.ammo[AM_CELL] > 40 && game_type != GameType::DoomIShareware {
+            WP_BFG
+        } else {
+            // If everything fails
+            WP_FIST
+        };
+
+        // Now set appropriate weapon overlay
+        let down_state: StateNum = WEAPONINFO[p.ready_weapon].down_state;
+        self.set_psprite(player, PS_WEAPON, down_state);
+
+        false
+    }
+
+    // P_FireWeapon
+    pub fn fire_weapon(&mut self, player: usize) {
+        if !self.check_ammo(player) {
+            return;
+        }
+
+        let mo: MobjId = self.player_mo(player);
+        self.set_mobj_state(mo, S_PLAY_ATK1);
+        let new_state: StateNum = WEAPONINFO[self.players[player].ready_weapon].attack_state;
+        self.set_psprite(player, PS_WEAPON, new_state);
+        self.noise_alert(mo, mo);
+    }
+
+    // P_DropWeapon. Player died, so put the weapon away
+    pub fn drop_weapon(&mut self, player: usize) {
+        let down_state: StateNum = WEAPONINFO[self.players[player].ready_weapon].down_state;
+        self.set_psprite(player, PS_WEAPON, down_state);
+    }
+
+    // A_WeaponReady. The player can fire the weapon or change to another
+    // weapon at this time. Follows after getting weapon up, or after
+    // previous attack/fire sequence
+    pub fn a_weapon_ready(&mut self, player: usize, position: usize) {
+        let mo: MobjId = self.player_mo(player);
+
+        // Get out of attack state
+        let mo_state: StateNum = self.thinkers.mobj(mo).state;
+        if mo_state == S_PLAY_ATK1 || mo_state == S_PLAY_ATK2 {
+            self.set_mobj_state(mo, S_PLAY);
+        }
+
+        let p: &Player = &self.players[player];
+        if p.ready_weapon == WP_CHAINSAW && p.psprites[position].state == Some(S_SAW) {
+            self.start_sound(Some(mo), SFX_SAWIDL);
+        }
+
+        // Check for change. If player is dead, put the weapon away
+        let p: &mut Player = &mut self.players[player];
+        if p.pending_weapon != WP_NOCHANGE || p.health == 0 {
+            // Change weapon (pending weapon should already be validated)
+            let down_state: StateNum = WEAPONINFO[p.ready_weapon].down_state;
+            self.set_psprite(player, PS_WEAPON, down_state);
+            return;
+        }
+
+        // Check for fire. The missile launcher and bfg do not auto fire
+        if p.cmd.buttons & BT_ATTACK != 0 {
+            if !p.attack_down || (p.ready_weapon != WP_MISSILE && p.ready_weapon != WP_BFG) {
+                p.attack_down = true;
+                self.fire_weapon(player);
+                return;
+            }
+        } else {
+            p.attack_down = false;
+        }
+
+        // Bob the weapon based on movement speed
+        let angle: usize = (128 * self.level_time) as usize & FINEMASK;
+        let bob: Fixed = p.bob;
+        let psp: &mut PspDef = &mut p.psprites[position];
+        psp.sx = Fixed::ONE + fixed_mul(bob, finecosine(angle));
+        let angle: usize = angle & (FINEANGLES / 2 - 1);
+        psp.sy = WEAPONTOP + fixed_mul(bob, finesine(angle));
+    }
+
+    // A_ReFire. The player can re-fire the weapon without lowering it
+    // entirely
+    pub fn a_refire(&mut self, player: usize) {
+        let p: &mut Player = &mut self.players[player];
+
+        // Check for fire (if a weaponchange is pending, let it go through
+        // instead)
+        if p.cmd.buttons & BT_ATTACK != 0 && p.pending_weapon == WP_NOCHANGE && p.health != 0 {
+            p.refire += 1;
+            self.fire_weapon(player);
+        } else {
+            p.refire = 0;
+            self.check_ammo(player);
+        }
+    }
+
+    // A_CheckReload
+    pub fn a_check_reload(&mut self, player: usize) {
+        self.check_ammo(player);
+    }
+
+    // A_Lower. Lowers current weapon, and changes weapon at bottom
+    pub fn a_lower(&mut self, player: usize, position: usize) {
+        let p: &mut Player = &mut self.players[player];
+        p.psprites[position].sy += LOWERSPEED;
+
+        // Is already down
+        if p.psprites[position].sy < WEAPONBOTTOM {
+            return;
+        }
+
+        // Player is dead
+        if p.player_state == PlayerState::Dead {
+            p.psprites[position].sy = WEAPONBOTTOM;
+
+            // Don't bring weapon back up
+            return;
+        }
+
+        // The old weapon has been lowered off the screen, so change the
+        // weapon and start raising it
+        if p.health == 0 {
+            // Player is dead, so keep the weapon off screen
+            self.set_psprite(player, PS_WEAPON, S_NULL);
+            return;
+        }
+
+        p.ready_weapon = p.pending_weapon;
+
+        self.bring_up_weapon(player);
+    }
+
+    // A_Raise
+    pub fn a_raise(&mut self, player: usize, position: usize) {
+        let p: &mut Player = &mut self.players[player];
+        p.psprites[position].sy -= RAISESPEED;
+
+        if p.psprites[position].sy > WEAPONTOP {
+            return;
+        }
+
+        p.psprites[position].sy = WEAPONTOP;
+
+        // The weapon has been raised all the way, so change to the ready
+        // state
+        let new_state: StateNum = WEAPONINFO[p.ready_weapon].ready_state;
+        self.set_psprite(player, PS_WEAPON, new_state);
+    }
+
+    // A_GunFlash
+    pub fn a_gun_flash(&mut self, player: usize) {
+        let mo: MobjId = self.player_mo(player);
+        self.set_mobj_state(mo, S_PLAY_ATK2);
+        let flash_state: StateNum = WEAPONINFO[self.players[player].ready_weapon].flash_state;
+        self.set_psprite(player, PS_FLASH, flash_state);
+    }
+
+    // (P_Random() - P_Random()) << shift, used to spread out shots
+    fn weapon_spread(&mut self, shift: u32) -> Angle {
+        Angle((self.random.p_sub_random() << shift) as u32)
+    }
+
+    // A_Punch
+    pub fn a_punch(&mut self, player: usize) {
+        let mut damage: i32 = (self.random.p_random() % 10 + 1) << 1;
+
+        if self.players[player].powers[PW_STRENGTH] != 0 {
+            damage *= 10;
+        }
+
+        let mo: MobjId = self.player_mo(player);
+        let angle: Angle = self.thinkers.mobj(mo).angle + self.weapon_spread(18);
+        let slope: Fixed = self.aim_line_attack(mo, angle, MELEERANGE);
+        self.line_attack(mo, angle, MELEERANGE, slope, damage);
+
+        // Turn to face target
+        if let Some(line_target) = self.line_attack.line_target {
+            self.start_sound(Some(mo), SFX_PUNCH);
+            let target: &Mobj = self.thinkers.mobj(line_target);
+            let (target_x, target_y): (Fixed, Fixed) = (target.x, target.y);
+            let mobj: &mut Mobj = self.thinkers.mobj_mut(mo);
+            mobj.angle = point_to_angle(mobj.x, mobj.y, target_x, target_y);
+        }
+    }
+
+    // A_Saw
+    pub fn a_saw(&mut self, player: usize) {
+        let damage: i32 = 2 * (self.random.p_random() % 10 + 1);
+        let mo: MobjId = self.player_mo(player);
+        let angle: Angle = self.thinkers.mobj(mo).angle + self.weapon_spread(18);
+
+        // Use meleerange + 1 se the puff doesn't skip the flash
+        let range: Fixed = MELEERANGE + Fixed(1);
+        let slope: Fixed = self.aim_line_attack(mo, angle, range);
+        self.line_attack(mo, angle, range, slope, damage);
+
+        let line_target: MobjId = match self.line_attack.line_target {
+            Some(line_target) => line_target,
+            None => {
+                self.start_sound(Some(mo), SFX_SAWFUL);
+                return;
+            }
+        };
+        self.start_sound(Some(mo), SFX_SAWHIT);
+
+        // Turn to face target
+        let target: &Mobj = self.thinkers.mobj(line_target);
+        let (target_x, target_y): (Fixed, Fixed) = (target.x, target.y);
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(mo);
+        let angle: Angle = point_to_angle(mobj.x, mobj.y, target_x, target_y);
+        let delta: u32 = (angle - mobj.angle).0;
+
+        if delta > ANG180 {
+            // The original compared against -ANG90/20 as an unsigned angle
+            // so this is never true and it always turns by ANG90/20
+            if delta < 0u32.wrapping_sub(ANG90) / 20 {
+                mobj.angle = angle + Angle(ANG90 / 21);
+            } else {
+                mobj.angle -= Angle(ANG90 / 20);
+            }
+        } else if delta > ANG90 / 20 {
+            mobj.angle = angle - Angle(ANG90 / 21);
+        } else {
+            mobj.angle += Angle(ANG90 / 20);
+        }
+
+        mobj.flags |= MF_JUSTATTACKED;
+    }
+
+    // Uses up ammo for the ready weapon
+    fn use_ammo(&mut self, player: usize, amount: i32) {
+        let p: &mut Player = &mut self.players[player];
+        p.ammo[WEAPONINFO[p.ready_weapon].ammo] -= amount;
+    }
+
+    // A_FireMissile
+    pub fn a_fire_missile(&mut self, player: usize) {
+        self.use_ammo(player, 1);
+        let mo: MobjId = self.player_mo(player);
+        self.spawn_player_missile(mo, MT_ROCKET);
+    }
+
+    // A_FireBFG
+    pub fn a_fire_bfg(&mut self, player: usize) {
+        self.use_ammo(player, BFGCELLS);
+        let mo: MobjId = self.player_mo(player);
+        self.spawn_player_missile(mo, MT_BFG);
+    }
+
+    // A_FirePlasma
+    pub fn a_fire_plasma(&mut self, player: usize) {
+        self.use_ammo(player, 1);
+
+        let flash_state: StateNum = WEAPONINFO[self.players[player].ready_weapon].flash_state;
+        let random: usize = (self.random.p_random() & 1) as usize;
+        self.set_psprite(player, PS_FLASH, flash_state + random);
+
+        let mo: MobjId = self.player_mo(player);
+        self.spawn_player_missile(mo, MT_PLASMA);
+    }
+
+    // P_BulletSlope. Sets a slope so a near miss is at aproximately the
+    // height of the intended target
+    fn bullet_slope(&mut self, mo: MobjId) -> Fixed {
+        let range: Fixed = Fixed(16 * 64 * FRACUNIT);
+
+        // See which target is to be aimed at
+        let mut an: Angle = self.thinkers.mobj(mo).angle;
+        let mut slope: Fixed = self.aim_line_attack(mo, an, range);
+
+        if self.line_attack.line_target.is_none() {
+            an += Angle(1 << 26);
+            slope = self.aim_line_attack(mo, an, range);
+
+            if self.line_attack.line_target.is_none() {
+                an -= Angle(2 << 26);
+                slope = self.aim_line_attack(mo, an, range);
+            }
+        }
+
+        slope
+    }
+
+    // P_GunShot
+    fn gun_shot(&mut self, mo: MobjId, accurate: bool, bullet_slope: Fixed) {
+        let damage: i32 = 5 * (self.random.p_random() % 3 + 1);
+        let mut angle: Angle = self.thinkers.mobj(mo).angle;
+
+        if !accurate {
+            angle += self.weapon_spread(18);
+        }
+
+        self.line_attack(mo, angle, MISSILERANGE, bullet_slope, damage);
+    }
+
+    // Plays the sound, goes to the attack frame, uses the ammo and shows
+    // the flash like every gun does before it shoots
+    fn start_gun_shot(&mut self, player: usize, sfx: SfxNum, ammo: i32, flash_offset: usize) {
+        let mo: MobjId = self.player_mo(player);
+        self.start_sound(Some(mo), sfx);
+        self.set_mobj_state(mo, S_PLAY_ATK2);
+        self.use_ammo(player, ammo);
+
+        let flash_state: StateNum = WEAPONINFO[self.players[player].ready_weapon].flash_state;
+        self.set_psprite(player, PS_FLASH, flash_state + flash_offset);
+    }
+
+    // A_FirePistol
+    pub fn a_fire_pistol(&mut self, player: usize) {
+        self.start_gun_shot(player, SFX_PISTOL, 1, 0);
+
+        let mo: MobjId = self.player_mo(player);
+        let bullet_slope: Fixed = self.bullet_slope(mo);
+        let accurate: bool = self.players[player].refire == 0;
+        self.gun_shot(mo, accurate, bullet_slope);
+    }
+
+    // A_FireShotgun
+    pub fn a_fire_shotgun(&mut self, player: usize) {
+        self.start_gun_shot(player, SFX_SHOTGN, 1, 0);
+
+        let mo: MobjId = self.player_mo(player);
+        let bullet_slope: Fixed = self.bullet_slope(mo);
+
+        for _ in 0..7 {
+            self.gun_shot(mo, false, bullet_slope);
+        }
+    }
+
+    // A_FireShotgun2
+    pub fn a_fire_shotgun2(&mut self, player: usize) {
+        self.start_gun_shot(player, SFX_DSHTGN, 2, 0);
+
+        let mo: MobjId = self.player_mo(player);
+        let bullet_slope: Fixed = self.bullet_slope(mo);
+
+        for _ in 0..20 {
+            let damage: i32 = 5 * (self.random.p_random() % 3 + 1);
+            let angle: Angle = self.thinkers.mobj(mo).angle + self.weapon_spread(19);
+            let slope: Fixed = bullet_slope + Fixed(self.random.p_sub_random() << 5);
+            self.line_attack(mo, angle, MISSILERANGE, slope, damage);
+        }
+    }
+
+    // A_FireCGun
+    pub fn a_fire_cgun(&mut self, player: usize, position: usize) {
+        let mo: MobjId = self.player_mo(player);
+        self.start_sound(Some(mo), SFX_PISTOL);
+
+        let p: &Player = &self.players[player];
+        let ammo: AmmoType = WEAPONINFO[p.ready_weapon].ammo;
+        if p.ammo[ammo] == 0 {
+            return;
+        }
+
+        // The flash matches whichever of the two firing frames this is
+        let flash_offset: usize = p.psprites[position].state.unwrap() - S_CHAIN1;
+        self.set_mobj_state(mo, S_PLAY_ATK2);
+        self.use_ammo(player, 1);
+
+        let flash_state: StateNum = WEAPONINFO[self.players[player].ready_weapon].flash_state;
+        self.set_psprite(player, PS_FLASH, flash_state + flash_offset);
+
+        let bullet_slope: Fixed = self.bullet_slope(mo);
+        let accurate: bool = self.players[player].refire == 0;
+        self.gun_shot(mo, accurate, bullet_slope);
+    }
+
+    // A_Light0
+    pub fn a_light0(&mut self, player: usize) {
+        self.players[player].extra_light = 0;
+    }
+
+    // A_Light1
+    pub fn a_light1(&mut self, player: usize) {
+        self.players[player].extra_light = 1;
+    }
+
+    // A_Light2
+    pub fn a_light2(&mut self, player: usize) {
+        self.players[player].extra_light = 2;
+    }
+
+    // A_OpenShotgun2
+    pub fn a_open_shotgun2(&mut self, player: usize) {
+        let mo: MobjId = self.player_mo(player);
+        self.start_sound(Some(mo), SFX_DBOPN);
+    }
+
+    // A_LoadShotgun2
+    pub fn a_load_shotgun2(&mut self, player: usize) {
+        let mo: MobjId = self.player_mo(player);
+        self.start_sound(Some(mo), SFX_DBLOAD);
+    }
+
+    // A_CloseShotgun2
+    pub fn a_close_shotgun2(&mut self, player: usize) {
+        let mo: MobjId = self.player_mo(player);
+        self.start_sound(Some(mo), SFX_DBCLS);
+        self.a_refire(player);
+    }
+
+    // A_BFGSpray. Spawn a BFG explosion on every monster in view
+    pub fn a_bfg_spray(&mut self, id: MobjId) {
+        let mo: &Mobj = self.thinkers.mobj(id);
+        let angle: Angle = mo.angle;
+
+        // Mo target is the originator (player) of the missile
+        let source: MobjId = match mo
+            .target
+            .filter(|source| self.thinkers.get_mobj(*source).is_some())
+        {
+            Some(source) => source,
+            None => return,
+        };
+
+        // Offset angles from its attack angle
+        for i in 0..40 {
+            let an: Angle = angle - Angle(ANG90 / 2) + Angle(ANG90 / 40 * i);
+
+            self.aim_line_attack(source, an, Fixed(16 * 64 * FRACUNIT));
+
+            let line_target: MobjId = match self.line_attack.line_target {
+                Some(line_target) => line_target,
+                None => continue,
+            };
+
+            let target: &Mobj = self.thinkers.mobj(line_target);
+            let (x, y, z): (Fixed, Fixed, Fixed) =
+                (target.x, target.y, target.z + Fixed(target.height.0 >> 2));
+            self.spawn_mobj(x, y, z, MT_EXTRABFG);
+
+            let mut damage: i32 = 0;
+            for _ in 0..15 {
+                damage += (self.random.p_random() & 7) + 1;
+            }
+
+            self.damage_mobj(line_target, Some(source), Some(source), damage);
+        }
+    }
+
+    // A_BFGsound
+    pub fn a_bfg_sound(&mut self, player: usize) {
+        let mo: MobjId = self.player_mo(player);
+        self.start_sound(Some(mo), SFX_BFG);
+    }
+
+    // P_SetupPsprites. Called at start of level for each player
+    pub fn setup_psprites(&mut self, player: usize) {
+        let p: &mut Player = &mut self.players[player];
+
+        // Remove all psprites
+        for psp in p.psprites.iter_mut() {
+            psp.state = None;
+        }
+
+        // Spawn the gun
+        p.pending_weapon = p.ready_weapon;
+        self.bring_up_weapon(player);
+    }
+
+    // P_MovePsprites. Called every tic by player thinking routine
+    pub fn move_psprites(&mut self, player: usize) {
+        for position in 0..NUMPSPRITES {
+            let psp: &mut PspDef = &mut self.players[player].psprites[position];
+
+            // A None state means not active
+            let state: StateNum = match psp.state {
+                Some(state) => state,
+                None => continue,
+            };
+
+            // Drop tic count and possibly change state. A -1 tic count
+            // never changes
+            if psp.tics != -1 {
+                psp.tics -= 1;
+                if psp.tics == 0 {
+                    self.set_psprite(player, position, STATES[state].next_state);
+                }
+            }
+        }
+
+        let p: &mut Player = &mut self.players[player];
+        p.psprites[PS_FLASH].sx = p.psprites[PS_WEAPON].sx;
+        p.psprites[PS_FLASH].sy = p.psprites[PS_WEAPON].sy;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::info::mobjinfo::{MT_PLASMA, MT_TROOP};
+    use crate::info::states::{
+        S_PISTOL, S_PISTOLDOWN, S_PISTOLFLASH, S_PISTOLUP, S_PLAY_ATK2, S_SGUNUP,
+    };
+    use crate::info::MobjType;
+    use crate::play::mobj::{MobjId, ONFLOORZ};
+    use crate::play::player::{
+        AM_CELL, AM_CLIP, AM_SHELL, WP_NOCHANGE, WP_PISTOL, WP_PLASMA, WP_SHOTGUN,
+    };
+    use crate::play::pspr::{PS_FLASH, PS_WEAPON, RAISESPEED, WEAPONBOTTOM, WEAPONTOP};
+    use crate::play::test_map::TestMap;
+    use crate::play::ticcmd::BT_ATTACK;
+    use crate::play::world::{World, WorldSettings};
+
+    fn create_world() -> World {
+        World::new(
+            WorldSettings::default(),
+            &TestMap::new()
+                .room(512, 0, 128)
+                .thing(64, 128, 0, 1, 7)
+                .to_map_lumps(),
+        )
+        .unwrap()
+    }
+
+    fn spawn(world: &mut World, x: i32, mobj_type: MobjType) -> MobjId {
+        world.spawn_mobj(
+            Fixed(x * FRACUNIT),
+            Fixed(128 * FRACUNIT),
+            ONFLOORZ,
+            mobj_type,
+        )
+    }
+
+    // Runs the psprites until the weapon is up and ready
+    fn raise_weapon(world: &mut World) {
+        for _ in 0..32 {
+            world.move_psprites(0);
+        }
+    }
+
+    #[test]
+    fn test_setup_psprites_brings_up_the_pistol() {
+        let mut world: World = create_world();
+
+        let weapon = world.players[0].psprites[PS_WEAPON];
+        // A_Raise already ran once when the state was set
+        assert_eq!(weapon.state, Some(S_PISTOLUP));
+        assert_eq!(weapon.sy, WEAPONBOTTOM - RAISESPEED);
+        assert_eq!(world.players[0].pending_weapon, WP_NOCHANGE);
+
+        raise_weapon(&mut world);
+
+        let weapon = world.players[0].psprites[PS_WEAPON];
+        assert_eq!(weapon.state, Some(S_PISTOL));
+        assert_eq!(weapon.sy, WEAPONTOP);
+    }
+
+    #[test]
+    fn test_fire_pistol_uses_ammo_and_hits_target() {
+        let mut world: World = create_world();
+        let troop: MobjId = spawn(&mut world, 300, MT_TROOP);
+        raise_weapon(&mut world);
+
+        world.players[0].cmd.buttons = BT_ATTACK;
+        world.players[0].attack_down = false;
+        world.move_psprites(0);
+        for _ in 0..6 {
+            world.move_psprites(0);
+        }
+
+        assert_eq!(world.players[0].ammo[AM_CLIP], 49);
+        assert_eq!(
+            world.players[0].psprites[PS_FLASH].state,
+            Some(S_PISTOLFLASH)
+        );
+        let mo: MobjId = world.players[0].mo.unwrap();
+        assert_eq!(world.thinkers.mobj(mo).state, S_PLAY_ATK2);
+        assert!(world.thinkers.mobj(troop).health < 60);
+    }
+
+    #[test]
+    fn test_weapon_change_lowers_and_raises() {
+        let mut world: World = create_world();
+        raise_weapon(&mut world);
+
+        world.players[0].weapon_owned[WP_SHOTGUN] = true;
+        world.players[0].pending_weapon = WP_SHOTGUN;
+        world.move_psprites(0);
+        assert_eq!(
+            world.players[0].psprites[PS_WEAPON].state,
+            Some(S_PISTOLDOWN)
+        );
+
+        for _ in 0..16 {
+            world.move_psprites(0);
+        }
+        assert_eq!(world.players[0].ready_weapon, WP_SHOTGUN);
+        assert_eq!(world.players[0].psprites[PS_WEAPON].state, Some(S_SGUNUP));
+    }
+
+    #[test]
+    fn test_check_ammo_switches_weapon_when_out() {
+        let mut world: World = create_world();
+        raise_weapon(&mut world);
+        world.players[0].weapon_owned[WP_PLASMA] = true;
+        world.players[0].ammo[AM_CELL] = 20;
+        world.players[0].ammo[AM_CLIP] = 0;
+        world.players[0].ammo[AM_SHELL] = 0;
+
+        assert!(!world.check_ammo(0));
+        assert_eq!(world.players[0].pending_weapon, WP_PLASMA);
+        assert_eq!(world.players[0].ready_weapon, WP_PISTOL);
+    }
+
+    #[test]
+    fn test_fire_plasma_spawns_missile() {
+        let mut world: World = create_world();
+        world.players[0].ready_weapon = WP_PLASMA;
+        world.players[0].ammo[AM_CELL] = 10;
+
+        world.a_fire_plasma(0);
+
+        assert_eq!(world.players[0].ammo[AM_CELL], 9);
+        let missiles: usize = world
+            .thinkers
+            .mobjs()
+            .filter(|(_id, mobj)| mobj.mobj_type == MT_PLASMA)
+            .count();
+        assert_eq!(missiles, 1);
+    }
+}
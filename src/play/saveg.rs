@@ -0,0 +1,1213 @@
+// Port of p_saveg.c. The layout is the original's so savegames can be
+// swapped with other ports: players, mobjs and specials are copies of the
+// 32 bit structs with pointers turned into numbers, and the world is a list
+// of shorts. Flats and textures are saved by their number in the wads
+use std::fmt::{self, Display};
+
+use crate::fixed::{Angle, Fixed, FRACBITS};
+use crate::info::mobjinfo::NUMMOBJTYPES;
+use crate::info::sprites::NUMSPRITES;
+use crate::info::states::NUMSTATES;
+use crate::play::ceiling::{Ceiling, CeilingType};
+use crate::play::doors::{DoorType, VerticalDoor};
+use crate::play::floor::{FloorMove, FloorType};
+use crate::play::level::{names_equal, MapThing, Sector, Side};
+use crate::play::lights::{Glow, LightFlash, Strobe};
+use crate::play::mobj::{Mobj, MobjId};
+use crate::play::plats::{Plat, PlatStatus, PlatType};
+use crate::play::player::{
+    Player, PlayerState, WeaponType, NUMAMMO, NUMCARDS, NUMPOWERS, NUMWEAPONS,
+};
+use crate::play::pspr::NUMPSPRITES;
+use crate::play::thinker::{Thinker, ThinkerId};
+use crate::play::world::World;
+use crate::play::MAXPLAYERS;
+use crate::wad::{self, DoomFile};
+
+// Thinker classes
+const TC_END: u8 = 0;
+const TC_MOBJ: u8 = 1;
+
+// Special thinker classes
+const TC_CEILING: u8 = 0;
+const TC_DOOR: u8 = 1;
+const TC_FLOOR: u8 = 2;
+const TC_PLAT: u8 = 3;
+const TC_FLASH: u8 = 4;
+const TC_STROBE: u8 = 5;
+const TC_GLOW: u8 = 6;
+const TC_ENDSPECIALS: u8 = 7;
+
+// sizeof the original's structs
+const PLAYER_SIZE: usize = 280;
+const MOBJ_SIZE: usize = 156;
+const CEILING_SIZE: usize = 48;
+const DOOR_SIZE: usize = 40;
+const FLOOR_SIZE: usize = 44;
+const PLAT_SIZE: usize = 56;
+const FLASH_SIZE: usize = 36;
+const STROBE_SIZE: usize = 36;
+const GLOW_SIZE: usize = 28;
+
+// Where the thinker_t's function pointer is in every thinker. Only checked
+// for being NULL, which means in stasis
+const THINKER_FUNCTION: usize = 8;
+
+// The enums in the original's order
+const PLAYER_STATES: [PlayerState; 3] = [PlayerState::Live, PlayerState::Dead, PlayerState::Reborn];
+const CEILING_TYPES: [CeilingType; 6] = [
+    CeilingType::LowerToFloor,
+    CeilingType::RaiseToHighest,
+    CeilingType::LowerAndCrush,
+    CeilingType::CrushAndRaise,
+    CeilingType::FastCrushAndRaise,
+    CeilingType::SilentCrushAndRaise,
+];
+const DOOR_TYPES: [DoorType; 8] = [
+    DoorType::Normal,
+    DoorType::Close30ThenOpen,
+    DoorType::Close,
+    DoorType::Open,
+    DoorType::RaiseIn5Mins,
+    DoorType::BlazeRaise,
+    DoorType::BlazeOpen,
+    DoorType::BlazeClose,
+];
+const FLOOR_TYPES: [FloorType; 13] = [
+    FloorType::LowerFloor,
+    FloorType::LowerFloorToLowest,
+    FloorType::TurboLower,
+    FloorType::RaiseFloor,
+    FloorType::RaiseFloorToNearest,
+    FloorType::RaiseToTexture,
+    FloorType::LowerAndChange,
+    FloorType::RaiseFloor24,
+    FloorType::RaiseFloor24AndChange,
+    FloorType::RaiseFloorCrush,
+    FloorType::RaiseFloorTurbo,
+    FloorType::DonutRaise,
+    FloorType::RaiseFloor512,
+];
+const PLAT_STATUSES: [PlatStatus; 4] = [
+    PlatStatus::Up,
+    PlatStatus::Down,
+    PlatStatus::Waiting,
+    PlatStatus::InStasis,
+];
+const PLAT_TYPES: [PlatType; 5] = [
+    PlatType::PerpetualRaise,
+    PlatType::DownWaitUpStay,
+    PlatType::RaiseAndChange,
+    PlatType::RaiseToNearestAndChange,
+    PlatType::BlazeDwus,
+];
+
+// Texture 0 is never drawn, it's what "-" sides get
+const NO_TEXTURE: [u8; 8] = *b"-\0\0\0\0\0\0\0";
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum SaveGameError {
+    DifferentVersion,
+    Truncated,
+    BadSaveGame,
+    UnknownThinkerClass(u8),
+    UnknownSpecialClass(u8),
+    LevelError(String),
+    IoError(String),
+}
+
+impl Display for SaveGameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::DifferentVersion => write!(f, "Savegame is from a different version"),
+            Self::Truncated => write!(f, "Savegame is cut short"),
+            Self::BadSaveGame => write!(f, "Bad savegame"),
+            Self::UnknownThinkerClass(class) => write!(
+                f,
+                "P_UnArchiveThinkers: Unknown tclass {} in savegame",
+                class
+            ),
+            Self::UnknownSpecialClass(class) => write!(
+                f,
+                "P_UnarchiveSpecials:Unknown tclass {} in savegame",
+                class
+            ),
+            Self::LevelError(message) => write!(f, "{}", message),
+            Self::IoError(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// The save buffer. Everything is little endian like the x86 original
+#[derive(Default)]
+pub struct SaveWriter {
+    data: Vec<u8>,
+}
+
+impl SaveWriter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn write_i16(&mut self, value: i16) {
+        self.data.extend(value.to_le_bytes());
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.data.extend(bytes);
+    }
+
+    // PADSAVEP. The structs start on 4 byte boundaries
+    pub fn pad(&mut self) {
+        self.data.resize(self.data.len().next_multiple_of(4), 0);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+pub struct SaveReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> SaveReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        SaveReader { data, position: 0 }
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, SaveGameError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_i16(&mut self) -> Result<i16, SaveGameError> {
+        let bytes: &[u8] = self.read_bytes(2)?;
+        Ok(i16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], SaveGameError> {
+        let bytes: &[u8] = self
+            .data
+            .get(self.position..self.position + len)
+            .ok_or(SaveGameError::Truncated)?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    // PADSAVEP
+    pub fn pad(&mut self) {
+        self.position = self.position.next_multiple_of(4);
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.position >= self.data.len()
+    }
+}
+
+// R_FlatNumForName and R_TextureNumForName. Flats are numbered from the
+// lump after the last F_START and textures in TEXTURE1 then TEXTURE2 order
+#[derive(Clone, Debug, Default)]
+pub struct PicNumbers {
+    flats: Vec<[u8; 8]>,
+    textures: Vec<[u8; 8]>,
+}
+
+impl PicNumbers {
+    pub fn new(flats: Vec<[u8; 8]>, textures: Vec<[u8; 8]>) -> Self {
+        PicNumbers { flats, textures }
+    }
+
+    pub fn from_wad(doom_files: &mut [DoomFile]) -> Self {
+        let lump_names: Vec<[u8; 8]> = doom_files
+            .iter()
+            .flat_map(|doom_file| doom_file.lumps.iter())
+            .map(|lump| name_bytes(&lump.name))
+            .collect();
+        let first_flat: usize = lump_names
+            .iter()
+            .rposition(|name| names_equal(name, &name_bytes("F_START")))
+            .map_or(lump_names.len(), |index| index + 1);
+        let flats: Vec<[u8; 8]> = lump_names[first_flat..]
+            .iter()
+            .take_while(|name| !names_equal(name, &name_bytes("F_END")))
+            .copied()
+            .collect();
+
+        let mut textures: Vec<[u8; 8]> = Vec::new();
+        for lump_name in ["TEXTURE1", "TEXTURE2"] {
+            if let Some(data) = wad::find_lump_data(doom_files, lump_name) {
+                textures.extend(texture_names(&data));
+            }
+        }
+
+        PicNumbers { flats, textures }
+    }
+
+    // Flats missing from the wads are saved as 0
+    pub fn flat_num(&self, name: &[u8; 8]) -> i16 {
+        self.flats
+            .iter()
+            .rposition(|flat| names_equal(flat, name))
+            .map_or(0, |index| index as i16)
+    }
+
+    pub fn flat_name(&self, num: i16) -> Option<[u8; 8]> {
+        usize::try_from(num)
+            .ok()
+            .and_then(|num| self.flats.get(num))
+            .copied()
+    }
+
+    pub fn texture_num(&self, name: &[u8; 8]) -> i16 {
+        // "NoTexture" marker
+        if name[0] == b'-' {
+            return 0;
+        }
+
+        self.textures
+            .iter()
+            .position(|texture| names_equal(texture, name))
+            .map_or(0, |index| index as i16)
+    }
+
+    pub fn texture_name(&self, num: i16) -> Option<[u8; 8]> {
+        if num == 0 {
+            return Some(NO_TEXTURE);
+        }
+
+        usize::try_from(num)
+            .ok()
+            .and_then(|num| self.textures.get(num))
+            .copied()
+    }
+
+    // Only pics that changed since the level was loaded are looked up, so
+    // levels using pics the wads don't number keep their names
+    fn restore_flat(&self, pic: &mut [u8; 8], num: i16) {
+        if self.flat_num(pic) != num {
+            if let Some(name) = self.flat_name(num) {
+                *pic = name;
+            }
+        }
+    }
+
+    fn restore_texture(&self, texture: &mut [u8; 8], num: i16) {
+        if self.texture_num(texture) != num {
+            if let Some(name) = self.texture_name(num) {
+                *texture = name;
+            }
+        }
+    }
+}
+
+impl World {
+    // P_ArchivePlayers
+    pub fn archive_players(&self, writer: &mut SaveWriter) {
+        for (player_num, player) in self.players.iter().enumerate() {
+            if !self.settings.players_in_game[player_num] {
+                continue;
+            }
+
+            writer.pad();
+            writer.write_bytes(&player_record(player));
+        }
+    }
+
+    // P_UnArchivePlayers
+    pub fn unarchive_players(&mut self, reader: &mut SaveReader) -> Result<(), SaveGameError> {
+        for player_num in 0..MAXPLAYERS {
+            if !self.settings.players_in_game[player_num] {
+                continue;
+            }
+
+            reader.pad();
+            let record: &[u8] = reader.read_bytes(PLAYER_SIZE)?;
+            // The mobj and attacker will be set when unarc thinker
+            self.players[player_num] = player_from_record(record)?;
+        }
+        Ok(())
+    }
+
+    // P_ArchiveWorld
+    pub fn archive_world(&self, writer: &mut SaveWriter, pic_numbers: &PicNumbers) {
+        // Do sectors
+        for sector in self.level.sectors.iter() {
+            writer.write_i16((sector.floor_height.0 >> FRACBITS) as i16);
+            writer.write_i16((sector.ceiling_height.0 >> FRACBITS) as i16);
+            writer.write_i16(pic_numbers.flat_num(&sector.floor_pic));
+            writer.write_i16(pic_numbers.flat_num(&sector.ceiling_pic));
+            writer.write_i16(sector.light_level as i16);
+            // Needed?
+            writer.write_i16(sector.special as i16);
+            writer.write_i16(sector.tag as i16);
+        }
+
+        // Do lines
+        for line in self.level.lines.iter() {
+            writer.write_i16(line.flags as i16);
+            writer.write_i16(line.special as i16);
+            writer.write_i16(line.tag as i16);
+
+            for side_num in line.side_num.iter().flatten() {
+                let side: &Side = &self.level.sides[*side_num];
+                writer.write_i16((side.texture_offset.0 >> FRACBITS) as i16);
+                writer.write_i16((side.row_offset.0 >> FRACBITS) as i16);
+                writer.write_i16(pic_numbers.texture_num(&side.top_texture));
+                writer.write_i16(pic_numbers.texture_num(&side.bottom_texture));
+                writer.write_i16(pic_numbers.texture_num(&side.mid_texture));
+            }
+        }
+    }
+
+    // P_UnArchiveWorld
+    pub fn unarchive_world(
+        &mut self,
+        reader: &mut SaveReader,
+        pic_numbers: &PicNumbers,
+    ) -> Result<(), SaveGameError> {
+        // Do sectors
+        for sector in self.level.sectors.iter_mut() {
+            sector.floor_height = Fixed(i32::from(reader.read_i16()?) << FRACBITS);
+            sector.ceiling_height = Fixed(i32::from(reader.read_i16()?) << FRACBITS);
+            pic_numbers.restore_flat(&mut sector.floor_pic, reader.read_i16()?);
+            pic_numbers.restore_flat(&mut sector.ceiling_pic, reader.read_i16()?);
+            sector.light_level = i32::from(reader.read_i16()?);
+            // Needed?
+            sector.special = i32::from(reader.read_i16()?);
+            sector.tag = i32::from(reader.read_i16()?);
+            sector.special_data = None;
+            sector.sound_target = None;
+        }
+
+        // Do lines
+        for line_num in 0..self.level.lines.len() {
+            let side_nums: [Option<usize>; 2] = {
+                let line = &mut self.level.lines[line_num];
+                line.flags = i32::from(reader.read_i16()?);
+                line.special = i32::from(reader.read_i16()?);
+                line.tag = i32::from(reader.read_i16()?);
+                line.side_num
+            };
+
+            for side_num in side_nums.iter().flatten() {
+                let side: &mut Side = &mut self.level.sides[*side_num];
+                side.texture_offset = Fixed(i32::from(reader.read_i16()?) << FRACBITS);
+                side.row_offset = Fixed(i32::from(reader.read_i16()?) << FRACBITS);
+                pic_numbers.restore_texture(&mut side.top_texture, reader.read_i16()?);
+                pic_numbers.restore_texture(&mut side.bottom_texture, reader.read_i16()?);
+                pic_numbers.restore_texture(&mut side.mid_texture, reader.read_i16()?);
+            }
+        }
+        Ok(())
+    }
+
+    // P_ArchiveThinkers. Only mobjs, the rest are specials
+    pub fn archive_thinkers(&self, writer: &mut SaveWriter) {
+        // Save off the current thinkers
+        for (_id, mobj) in self.thinkers.mobjs() {
+            writer.write_u8(TC_MOBJ);
+            writer.pad();
+            writer.write_bytes(&mobj_record(mobj));
+        }
+
+        // Add a terminating marker
+        writer.write_u8(TC_END);
+    }
+
+    // P_UnArchiveThinkers
+    pub fn unarchive_thinkers(&mut self, reader: &mut SaveReader) -> Result<(), SaveGameError> {
+        // Remove all the current thinkers
+        let mobjs: Vec<MobjId> = self.thinkers.mobjs().map(|(id, _mobj)| id).collect();
+        for id in mobjs {
+            self.remove_mobj(id);
+        }
+        self.thinkers.clear();
+        self.active_ceilings = Default::default();
+        self.active_plats = Default::default();
+        self.body_queue = Default::default();
+
+        // Read in saved thinkers
+        loop {
+            match reader.read_u8()? {
+                // End of list
+                TC_END => return Ok(()),
+                TC_MOBJ => {
+                    reader.pad();
+                    let mobj: Mobj = mobj_from_record(reader.read_bytes(MOBJ_SIZE)?)?;
+                    let player: Option<usize> = mobj.player;
+                    let id: MobjId = self.thinkers.add(Thinker::Mobj(mobj));
+                    if let Some(player) = player {
+                        self.players[player].mo = Some(id);
+                    }
+
+                    self.set_thing_position(id);
+                    let sector: &Sector = &self.level.sectors
+                        [self.level.subsectors[self.thinkers.mobj(id).subsector].sector];
+                    let (floor_z, ceiling_z): (Fixed, Fixed) =
+                        (sector.floor_height, sector.ceiling_height);
+                    let mobj: &mut Mobj = self.thinkers.mobj_mut(id);
+                    mobj.floor_z = floor_z;
+                    mobj.ceiling_z = ceiling_z;
+                }
+                class => return Err(SaveGameError::UnknownThinkerClass(class)),
+            }
+        }
+    }
+
+    // P_ArchiveSpecials. Fire flickers have no class so they're lost, the
+    // same as the original. Plats in stasis are kept, the original only
+    // looked for stopped ceilings
+    pub fn archive_specials(&self, writer: &mut SaveWriter, pic_numbers: &PicNumbers) {
+        for (_id, thinker) in self.thinkers.iter() {
+            let (class, record): (u8, Vec<u8>) = match thinker {
+                Thinker::Ceiling(ceiling) => (TC_CEILING, ceiling_record(ceiling)),
+                Thinker::Door(door) => (TC_DOOR, door_record(door)),
+                Thinker::Floor(floor) => (TC_FLOOR, floor_record(floor, pic_numbers)),
+                Thinker::Plat(plat) => (TC_PLAT, plat_record(plat)),
+                Thinker::LightFlash(flash) => (TC_FLASH, flash_record(flash)),
+                Thinker::Strobe(strobe) => (TC_STROBE, strobe_record(strobe)),
+                Thinker::Glow(glow) => (TC_GLOW, glow_record(glow)),
+                Thinker::Mobj(_) | Thinker::FireFlicker(_) => continue,
+            };
+
+            writer.write_u8(class);
+            writer.pad();
+            writer.write_bytes(&record);
+        }
+
+        // Add a terminating marker
+        writer.write_u8(TC_ENDSPECIALS);
+    }
+
+    // P_UnArchiveSpecials
+    pub fn unarchive_specials(
+        &mut self,
+        reader: &mut SaveReader,
+        pic_numbers: &PicNumbers,
+    ) -> Result<(), SaveGameError> {
+        let num_sectors: usize = self.level.sectors.len();
+
+        // Read in saved thinkers
+        loop {
+            let class: u8 = reader.read_u8()?;
+            if class == TC_ENDSPECIALS {
+                // End of list
+                return Ok(());
+            }
+            reader.pad();
+
+            let thinker: Thinker = match class {
+                TC_CEILING => {
+                    Thinker::Ceiling(ceiling_from_record(reader.read_bytes(CEILING_SIZE)?)?)
+                }
+                TC_DOOR => Thinker::Door(door_from_record(reader.read_bytes(DOOR_SIZE)?)?),
+                TC_FLOOR => {
+                    let record: &[u8] = reader.read_bytes(FLOOR_SIZE)?;
+                    let sector: usize = get_index(record, 20, num_sectors)?;
+                    Thinker::Floor(floor_from_record(
+                        record,
+                        pic_numbers,
+                        &self.level.sectors[sector],
+                    )?)
+                }
+                TC_PLAT => Thinker::Plat(plat_from_record(reader.read_bytes(PLAT_SIZE)?)?),
+                TC_FLASH => Thinker::LightFlash(flash_from_record(reader.read_bytes(FLASH_SIZE)?)),
+                TC_STROBE => Thinker::Strobe(strobe_from_record(reader.read_bytes(STROBE_SIZE)?)),
+                TC_GLOW => Thinker::Glow(glow_from_record(reader.read_bytes(GLOW_SIZE)?)),
+                class => return Err(SaveGameError::UnknownSpecialClass(class)),
+            };
+
+            let sector: usize = match &thinker {
+                Thinker::Ceiling(ceiling) => ceiling.sector,
+                Thinker::Door(door) => door.sector,
+                Thinker::Floor(floor) => floor.sector,
+                Thinker::Plat(plat) => plat.sector,
+                Thinker::LightFlash(flash) => flash.sector,
+                Thinker::Strobe(strobe) => strobe.sector,
+                Thinker::Glow(glow) => glow.sector,
+                Thinker::Mobj(_) | Thinker::FireFlicker(_) => unreachable!(),
+            };
+            if sector >= num_sectors {
+                return Err(SaveGameError::BadSaveGame);
+            }
+
+            let is_mover: bool = matches!(
+                thinker,
+                Thinker::Ceiling(_) | Thinker::Door(_) | Thinker::Floor(_) | Thinker::Plat(_)
+            );
+            let is_ceiling: bool = matches!(thinker, Thinker::Ceiling(_));
+            let is_plat: bool = matches!(thinker, Thinker::Plat(_));
+            let id: ThinkerId = self.thinkers.add(thinker);
+
+            if is_mover {
+                self.level.sectors[sector].special_data = Some(id);
+            }
+            if is_ceiling {
+                self.add_active_ceiling(id);
+            }
+            if is_plat {
+                self.add_active_plat(id);
+            }
+        }
+    }
+}
+
+// The name of a lump as it's padded in the directory
+fn name_bytes(name: &str) -> [u8; 8] {
+    let mut bytes: [u8; 8] = [0; 8];
+    for (byte, c) in bytes.iter_mut().zip(name.bytes()) {
+        *byte = c;
+    }
+    bytes
+}
+
+// The names of a TEXTURE1/TEXTURE2 lump's maptexture_ts. A lump cut short
+// stops at the last whole name
+fn texture_names(data: &[u8]) -> Vec<[u8; 8]> {
+    let num_textures: usize = data
+        .get(0..4)
+        .map_or(0, |bytes| get_i32(bytes, 0).max(0) as usize);
+
+    (0..num_textures)
+        .map_while(|texture| {
+            let offset: usize = usize::try_from(get_i32(data.get(4 + texture * 4..)?, 0)).ok()?;
+            let name: &[u8] = data.get(offset..offset + 8)?;
+            Some(<[u8; 8]>::try_from(name).unwrap())
+        })
+        .collect()
+}
+
+fn put_i32(record: &mut [u8], offset: usize, value: i32) {
+    record[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn put_i16(record: &mut [u8], offset: usize, value: i16) {
+    record[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn get_i32(record: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes(record[offset..offset + 4].try_into().unwrap())
+}
+
+fn get_i16(record: &[u8], offset: usize) -> i16 {
+    i16::from_le_bytes(record[offset..offset + 2].try_into().unwrap())
+}
+
+fn get_fixed(record: &[u8], offset: usize) -> Fixed {
+    Fixed(get_i32(record, offset))
+}
+
+// An index saved in place of a pointer, which has to be in range
+fn get_index(record: &[u8], offset: usize, len: usize) -> Result<usize, SaveGameError> {
+    usize::try_from(get_i32(record, offset))
+        .ok()
+        .filter(|index| *index < len)
+        .ok_or(SaveGameError::BadSaveGame)
+}
+
+fn get_enum<T: Copy>(record: &[u8], offset: usize, values: &[T]) -> Result<T, SaveGameError> {
+    Ok(values[get_index(record, offset, values.len())?])
+}
+
+fn player_record(player: &Player) -> [u8; PLAYER_SIZE] {
+    let mut record: [u8; PLAYER_SIZE] = [0; PLAYER_SIZE];
+    put_i32(&mut record, 4, player.player_state as i32);
+    record[8] = player.cmd.forward_move as u8;
+    record[9] = player.cmd.side_move as u8;
+    put_i16(&mut record, 10, player.cmd.angle_turn);
+    put_i16(&mut record, 12, player.cmd.consistancy);
+    record[14] = player.cmd.chat_char;
+    record[15] = player.cmd.buttons;
+    put_i32(&mut record, 16, player.view_z.0);
+    put_i32(&mut record, 20, player.view_height.0);
+    put_i32(&mut record, 24, player.delta_view_height.0);
+    put_i32(&mut record, 28, player.bob.0);
+    put_i32(&mut record, 32, player.health);
+    put_i32(&mut record, 36, player.armor_points);
+    put_i32(&mut record, 40, player.armor_type);
+    for (index, power) in player.powers.iter().enumerate() {
+        put_i32(&mut record, 44 + index * 4, *power);
+    }
+    for (index, card) in player.cards.iter().enumerate() {
+        put_i32(&mut record, 68 + index * 4, i32::from(*card));
+    }
+    put_i32(&mut record, 92, i32::from(player.backpack));
+    for (index, frags) in player.frags.iter().enumerate() {
+        put_i32(&mut record, 96 + index * 4, *frags);
+    }
+    put_i32(&mut record, 112, player.ready_weapon as i32);
+    put_i32(&mut record, 116, player.pending_weapon as i32);
+    for (index, owned) in player.weapon_owned.iter().enumerate() {
+        put_i32(&mut record, 120 + index * 4, i32::from(*owned));
+    }
+    for (index, ammo) in player.ammo.iter().enumerate() {
+        put_i32(&mut record, 156 + index * 4, *ammo);
+    }
+    for (index, max_ammo) in player.max_ammo.iter().enumerate() {
+        put_i32(&mut record, 172 + index * 4, *max_ammo);
+    }
+    put_i32(&mut record, 188, i32::from(player.attack_down));
+    put_i32(&mut record, 192, i32::from(player.use_down));
+    put_i32(&mut record, 196, player.cheats);
+    put_i32(&mut record, 200, player.refire);
+    put_i32(&mut record, 204, player.kill_count);
+    put_i32(&mut record, 208, player.item_count);
+    put_i32(&mut record, 212, player.secret_count);
+    put_i32(&mut record, 220, player.damage_count);
+    put_i32(&mut record, 224, player.bonus_count);
+    put_i32(&mut record, 232, player.extra_light);
+    put_i32(&mut record, 236, player.fixed_colormap);
+    for (index, psp) in player.psprites.iter().enumerate() {
+        let offset: usize = 244 + index * 16;
+        put_i32(&mut record, offset, psp.state.unwrap_or(0) as i32);
+        put_i32(&mut record, offset + 4, psp.tics);
+        put_i32(&mut record, offset + 8, psp.sx.0);
+        put_i32(&mut record, offset + 12, psp.sy.0);
+    }
+    record
+}
+
+fn player_from_record(record: &[u8]) -> Result<Player, SaveGameError> {
+    let mut player: Player = Player {
+        player_state: get_enum(record, 4, &PLAYER_STATES)?,
+        view_z: get_fixed(record, 16),
+        view_height: get_fixed(record, 20),
+        delta_view_height: get_fixed(record, 24),
+        bob: get_fixed(record, 28),
+        health: get_i32(record, 32),
+        armor_points: get_i32(record, 36),
+        armor_type: get_i32(record, 40),
+        backpack: get_i32(record, 92) != 0,
+        ready_weapon: get_index(record, 112, NUMWEAPONS)?,
+        pending_weapon: get_i32(record, 116) as WeaponType,
+        attack_down: get_i32(record, 188) != 0,
+        use_down: get_i32(record, 192) != 0,
+        cheats: get_i32(record, 196),
+        refire: get_i32(record, 200),
+        kill_count: get_i32(record, 204),
+        item_count: get_i32(record, 208),
+        secret_count: get_i32(record, 212),
+        damage_count: get_i32(record, 220),
+        bonus_count: get_i32(record, 224),
+        extra_light: get_i32(record, 232),
+        fixed_colormap: get_i32(record, 236),
+        ..Default::default()
+    };
+    player.cmd.forward_move = record[8] as i8;
+    player.cmd.side_move = record[9] as i8;
+    player.cmd.angle_turn = get_i16(record, 10);
+    player.cmd.consistancy = get_i16(record, 12);
+    player.cmd.chat_char = record[14];
+    player.cmd.buttons = record[15];
+    for index in 0..NUMPOWERS {
+        player.powers[index] = get_i32(record, 44 + index * 4);
+    }
+    for index in 0..NUMCARDS {
+        player.cards[index] = get_i32(record, 68 + index * 4) != 0;
+    }
+    for index in 0..MAXPLAYERS {
+        player.frags[index] = get_i32(record, 96 + index * 4);
+    }
+    for index in 0..NUMWEAPONS {
+        player.weapon_owned[index] = get_i32(record, 120 + index * 4) != 0;
+    }
+    for index in 0..NUMAMMO {
+        player.ammo[index] = get_i32(record, 156 + index * 4);
+        player.max_ammo[index] = get_i32(record, 172 + index * 4);
+    }
+    for index in 0..NUMPSPRITES {
+        let offset: usize = 244 + index * 16;
+        let state: usize = get_index(record, offset, NUMSTATES)?;
+        player.psprites[index].state = (state != 0).then_some(state);
+        player.psprites[index].tics = get_i32(record, offset + 4);
+        player.psprites[index].sx = get_fixed(record, offset + 8);
+        player.psprites[index].sy = get_fixed(record, offset + 12);
+    }
+    Ok(player)
+}
+
+fn mobj_record(mobj: &Mobj) -> [u8; MOBJ_SIZE] {
+    let mut record: [u8; MOBJ_SIZE] = [0; MOBJ_SIZE];
+    put_i32(&mut record, 12, mobj.x.0);
+    put_i32(&mut record, 16, mobj.y.0);
+    put_i32(&mut record, 20, mobj.z.0);
+    put_i32(&mut record, 32, mobj.angle.0 as i32);
+    put_i32(&mut record, 36, mobj.sprite as i32);
+    put_i32(&mut record, 40, mobj.frame);
+    put_i32(&mut record, 56, mobj.floor_z.0);
+    put_i32(&mut record, 60, mobj.ceiling_z.0);
+    put_i32(&mut record, 64, mobj.radius.0);
+    put_i32(&mut record, 68, mobj.height.0);
+    put_i32(&mut record, 72, mobj.mom_x.0);
+    put_i32(&mut record, 76, mobj.mom_y.0);
+    put_i32(&mut record, 80, mobj.mom_z.0);
+    put_i32(&mut record, 84, mobj.valid_count);
+    put_i32(&mut record, 88, mobj.mobj_type as i32);
+    put_i32(&mut record, 96, mobj.tics);
+    put_i32(&mut record, 100, mobj.state as i32);
+    put_i32(&mut record, 104, mobj.flags as i32);
+    put_i32(&mut record, 108, mobj.health);
+    put_i32(&mut record, 112, mobj.move_dir);
+    put_i32(&mut record, 116, mobj.move_count);
+    put_i32(&mut record, 124, mobj.reaction_time);
+    put_i32(&mut record, 128, mobj.threshold);
+    put_i32(
+        &mut record,
+        132,
+        mobj.player.map_or(0, |player| player as i32 + 1),
+    );
+    put_i32(&mut record, 136, mobj.last_look);
+    let spawn_point: &MapThing = &mobj.spawn_point;
+    put_i16(&mut record, 140, spawn_point.x);
+    put_i16(&mut record, 142, spawn_point.y);
+    put_i16(&mut record, 144, spawn_point.angle);
+    put_i16(&mut record, 146, spawn_point.thing_type);
+    put_i16(&mut record, 148, spawn_point.options);
+    record
+}
+
+// The sector and blockmap links, the target and the tracer are left empty
+fn mobj_from_record(record: &[u8]) -> Result<Mobj, SaveGameError> {
+    let player: i32 = get_i32(record, 132);
+    if !(0..=MAXPLAYERS as i32).contains(&player) {
+        return Err(SaveGameError::BadSaveGame);
+    }
+
+    Ok(Mobj {
+        x: get_fixed(record, 12),
+        y: get_fixed(record, 16),
+        z: get_fixed(record, 20),
+        angle: Angle(get_i32(record, 32) as u32),
+        sprite: get_index(record, 36, NUMSPRITES)?,
+        frame: get_i32(record, 40),
+        floor_z: get_fixed(record, 56),
+        ceiling_z: get_fixed(record, 60),
+        radius: get_fixed(record, 64),
+        height: get_fixed(record, 68),
+        mom_x: get_fixed(record, 72),
+        mom_y: get_fixed(record, 76),
+        mom_z: get_fixed(record, 80),
+        valid_count: get_i32(record, 84),
+        mobj_type: get_index(record, 88, NUMMOBJTYPES)?,
+        tics: get_i32(record, 96),
+        state: get_index(record, 100, NUMSTATES)?,
+        flags: get_i32(record, 104) as u32,
+        health: get_i32(record, 108),
+        move_dir: get_i32(record, 112),
+        move_count: get_i32(record, 116),
+        reaction_time: get_i32(record, 124),
+        threshold: get_i32(record, 128),
+        player: (player != 0).then(|| player as usize - 1),
+        last_look: get_i32(record, 136),
+        spawn_point: MapThing {
+            x: get_i16(record, 140),
+            y: get_i16(record, 142),
+            angle: get_i16(record, 144),
+            thing_type: get_i16(record, 146),
+            options: get_i16(record, 148),
+        },
+        ..Default::default()
+    })
+}
+
+fn ceiling_record(ceiling: &Ceiling) -> Vec<u8> {
+    let mut record: Vec<u8> = vec![0; CEILING_SIZE];
+    // Ceilings in stasis had their function taken away
+    put_i32(
+        &mut record,
+        THINKER_FUNCTION,
+        i32::from(ceiling.direction != 0),
+    );
+    put_i32(&mut record, 12, ceiling.ceiling_type as i32);
+    put_i32(&mut record, 16, ceiling.sector as i32);
+    put_i32(&mut record, 20, ceiling.bottom_height.0);
+    put_i32(&mut record, 24, ceiling.top_height.0);
+    put_i32(&mut record, 28, ceiling.speed.0);
+    put_i32(&mut record, 32, i32::from(ceiling.crush));
+    put_i32(&mut record, 36, ceiling.direction);
+    put_i32(&mut record, 40, ceiling.tag);
+    put_i32(&mut record, 44, ceiling.old_direction);
+    record
+}
+
+fn ceiling_from_record(record: &[u8]) -> Result<Ceiling, SaveGameError> {
+    Ok(Ceiling {
+        ceiling_type: get_enum(record, 12, &CEILING_TYPES)?,
+        sector: get_i32(record, 16) as usize,
+        bottom_height: get_fixed(record, 20),
+        top_height: get_fixed(record, 24),
+        speed: get_fixed(record, 28),
+        crush: get_i32(record, 32) != 0,
+        direction: get_i32(record, 36),
+        tag: get_i32(record, 40),
+        old_direction: get_i32(record, 44),
+    })
+}
+
+fn door_record(door: &VerticalDoor) -> Vec<u8> {
+    let mut record: Vec<u8> = vec![0; DOOR_SIZE];
+    put_i32(&mut record, THINKER_FUNCTION, 1);
+    put_i32(&mut record, 12, door.door_type as i32);
+    put_i32(&mut record, 16, door.sector as i32);
+    put_i32(&mut record, 20, door.top_height.0);
+    put_i32(&mut record, 24, door.speed.0);
+    put_i32(&mut record, 28, door.direction);
+    put_i32(&mut record, 32, door.top_wait);
+    put_i32(&mut record, 36, door.top_countdown);
+    record
+}
+
+fn door_from_record(record: &[u8]) -> Result<VerticalDoor, SaveGameError> {
+    Ok(VerticalDoor {
+        door_type: get_enum(record, 12, &DOOR_TYPES)?,
+        sector: get_i32(record, 16) as usize,
+        top_height: get_fixed(record, 20),
+        speed: get_fixed(record, 24),
+        direction: get_i32(record, 28),
+        top_wait: get_i32(record, 32),
+        top_countdown: get_i32(record, 36),
+    })
+}
+
+fn floor_record(floor: &FloorMove, pic_numbers: &PicNumbers) -> Vec<u8> {
+    let mut record: Vec<u8> = vec![0; FLOOR_SIZE];
+    put_i32(&mut record, THINKER_FUNCTION, 1);
+    put_i32(&mut record, 12, floor.floor_type as i32);
+    put_i32(&mut record, 16, i32::from(floor.crush));
+    put_i32(&mut record, 20, floor.sector as i32);
+    put_i32(&mut record, 24, floor.direction);
+    put_i32(&mut record, 28, floor.new_special);
+    put_i16(&mut record, 32, pic_numbers.flat_num(&floor.texture));
+    put_i32(&mut record, 36, floor.floor_dest_height.0);
+    put_i32(&mut record, 40, floor.speed.0);
+    record
+}
+
+// A flat the wads don't number becomes the sector's floor
+fn floor_from_record(
+    record: &[u8],
+    pic_numbers: &PicNumbers,
+    sector: &Sector,
+) -> Result<FloorMove, SaveGameError> {
+    Ok(FloorMove {
+        floor_type: get_enum(record, 12, &FLOOR_TYPES)?,
+        crush: get_i32(record, 16) != 0,
+        sector: get_i32(record, 20) as usize,
+        direction: get_i32(record, 24),
+        new_special: get_i32(record, 28),
+        texture: pic_numbers
+            .flat_name(get_i16(record, 32))
+            .unwrap_or(sector.floor_pic),
+        floor_dest_height: get_fixed(record, 36),
+        speed: get_fixed(record, 40),
+    })
+}
+
+fn plat_record(plat: &Plat) -> Vec<u8> {
+    let mut record: Vec<u8> = vec![0; PLAT_SIZE];
+    put_i32(
+        &mut record,
+        THINKER_FUNCTION,
+        i32::from(plat.status != PlatStatus::InStasis),
+    );
+    put_i32(&mut record, 12, plat.sector as i32);
+    put_i32(&mut record, 16, plat.speed.0);
+    put_i32(&mut record, 20, plat.low.0);
+    put_i32(&mut record, 24, plat.high.0);
+    put_i32(&mut record, 28, plat.wait);
+    put_i32(&mut record, 32, plat.count);
+    put_i32(&mut record, 36, plat.status as i32);
+    put_i32(&mut record, 40, plat.old_status as i32);
+    put_i32(&mut record, 44, i32::from(plat.crush));
+    put_i32(&mut record, 48, plat.tag);
+    put_i32(&mut record, 52, plat.plat_type as i32);
+    record
+}
+
+fn plat_from_record(record: &[u8]) -> Result<Plat, SaveGameError> {
+    Ok(Plat {
+        sector: get_i32(record, 12) as usize,
+        speed: get_fixed(record, 16),
+        low: get_fixed(record, 20),
+        high: get_fixed(record, 24),
+        wait: get_i32(record, 28),
+        count: get_i32(record, 32),
+        status: get_enum(record, 36, &PLAT_STATUSES)?,
+        old_status: get_enum(record, 40, &PLAT_STATUSES)?,
+        crush: get_i32(record, 44) != 0,
+        tag: get_i32(record, 48),
+        plat_type: get_enum(record, 52, &PLAT_TYPES)?,
+    })
+}
+
+fn flash_record(flash: &LightFlash) -> Vec<u8> {
+    let mut record: Vec<u8> = vec![0; FLASH_SIZE];
+    put_i32(&mut record, THINKER_FUNCTION, 1);
+    put_i32(&mut record, 12, flash.sector as i32);
+    put_i32(&mut record, 16, flash.count);
+    put_i32(&mut record, 20, flash.max_light);
+    put_i32(&mut record, 24, flash.min_light);
+    put_i32(&mut record, 28, flash.max_time);
+    put_i32(&mut record, 32, flash.min_time);
+    record
+}
+
+fn flash_from_record(record: &[u8]) -> LightFlash {
+    LightFlash {
+        sector: get_i32(record, 12) as usize,
+        count: get_i32(record, 16),
+        max_light: get_i32(record, 20),
+        min_light: get_i32(record, 24),
+        max_time: get_i32(record, 28),
+        min_time: get_i32(record, 32),
+    }
+}
+
+fn strobe_record(strobe: &Strobe) -> Vec<u8> {
+    let mut record: Vec<u8> = vec![0; STROBE_SIZE];
+    put_i32(&mut record, THINKER_FUNCTION, 1);
+    put_i32(&mut record, 12, strobe.sector as i32);
+    put_i32(&mut record, 16, strobe.count);
+    put_i32(&mut record, 20, strobe.min_light);
+    put_i32(&mut record, 24, strobe.max_light);
+    put_i32(&mut record, 28, strobe.dark_time);
+    put_i32(&mut record, 32, strobe.bright_time);
+    record
+}
+
+fn strobe_from_record(record: &[u8]) -> Strobe {
+    Strobe {
+        sector: get_i32(record, 12) as usize,
+        count: get_i32(record, 16),
+        min_light: get_i32(record, 20),
+        max_light: get_i32(record, 24),
+        dark_time: get_i32(record, 28),
+        bright_time: get_i32(record, 32),
+    }
+}
+
+fn glow_record(glow: &Glow) -> Vec<u8> {
+    let mut record: Vec<u8> = vec![0; GLOW_SIZE];
+    put_i32(&mut record, THINKER_FUNCTION, 1);
+    put_i32(&mut record, 12, glow.sector as i32);
+    put_i32(&mut record, 16, glow.min_light);
+    put_i32(&mut record, 20, glow.max_light);
+    put_i32(&mut record, 24, glow.direction);
+    record
+}
+
+fn glow_from_record(record: &[u8]) -> Glow {
+    Glow {
+        sector: get_i32(record, 12) as usize,
+        min_light: get_i32(record, 16),
+        max_light: get_i32(record, 20),
+        direction: get_i32(record, 24),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::play::doors::DoorType;
+    use crate::play::hash::StateHashes;
+    use crate::play::plats::PlatType;
+    use crate::play::saveg::{
+        texture_names, PicNumbers, SaveGameError, SaveReader, SaveWriter, NO_TEXTURE,
+    };
+    use crate::play::test_map::{TestMap, TestRoom};
+    use crate::play::thinker::Thinker;
+    use crate::play::world::{World, WorldSettings};
+    use crate::test_util::{create_test_dir, write_test_wad};
+    use crate::wad::{self, DoomFile};
+
+    fn create_world() -> World {
+        let tagged = |floor_height: i16, special: i16, tag: i16| TestRoom {
+            width: 256,
+            floor_height,
+            ceiling_height: 128,
+            light_level: 160,
+            special,
+            tag,
+        };
+
+        World::new(
+            WorldSettings::default(),
+            &TestMap::new()
+                .room(256, 0, 128)
+                // Glowing lights
+                .room_with(tagged(0, 8, 1))
+                .room_with(tagged(64, 0, 2))
+                .thing(64, 128, 0, 1, 7)
+                .thing(192, 128, 0, 3004, 7)
+                .line_special(0, 0, 1)
+                .line_special(1, 0, 2)
+                .to_map_lumps(),
+        )
+        .unwrap()
+    }
+
+    fn tagged_line(world: &World, tag: i32) -> usize {
+        world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.tag == tag)
+            .unwrap()
+    }
+
+    fn pic_numbers() -> PicNumbers {
+        PicNumbers::new(
+            vec![*b"FLOOR4_8", *b"CEIL3_5\0", *b"NUKAGE1\0"],
+            vec![*b"AASHITTY", *b"STARTAN3", *b"BIGDOOR1"],
+        )
+    }
+
+    fn archive(world: &World, pic_numbers: &PicNumbers) -> Vec<u8> {
+        let mut writer: SaveWriter = SaveWriter::new();
+        world.archive_players(&mut writer);
+        world.archive_world(&mut writer, pic_numbers);
+        world.archive_thinkers(&mut writer);
+        world.archive_specials(&mut writer, pic_numbers);
+        writer.finish()
+    }
+
+    fn unarchive(world: &mut World, data: &[u8], pic_numbers: &PicNumbers) {
+        let mut reader: SaveReader = SaveReader::new(data);
+        world.unarchive_players(&mut reader).unwrap();
+        world.unarchive_world(&mut reader, pic_numbers).unwrap();
+        world.unarchive_thinkers(&mut reader).unwrap();
+        world.unarchive_specials(&mut reader, pic_numbers).unwrap();
+        assert!(reader.is_at_end());
+    }
+
+    fn specials(world: &World) -> Vec<Thinker> {
+        world
+            .thinkers
+            .iter()
+            .filter(|(_id, thinker)| !matches!(thinker, Thinker::Mobj(_)))
+            .map(|(_id, thinker)| thinker.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_save_writer_pads_to_4_bytes() {
+        let mut writer: SaveWriter = SaveWriter::new();
+        writer.write_u8(1);
+        writer.pad();
+        writer.write_i16(-2);
+        writer.pad();
+        writer.pad();
+        let data: Vec<u8> = writer.finish();
+
+        assert_eq!(data, vec![1, 0, 0, 0, 0xfe, 0xff, 0, 0]);
+
+        let mut reader: SaveReader = SaveReader::new(&data);
+        assert_eq!(reader.read_u8(), Ok(1));
+        reader.pad();
+        assert_eq!(reader.read_i16(), Ok(-2));
+        assert_eq!(reader.read_bytes(4), Err(SaveGameError::Truncated));
+    }
+
+    #[test]
+    fn test_pic_numbers_look_up_names_and_numbers() {
+        let pic_numbers: PicNumbers = pic_numbers();
+
+        assert_eq!(pic_numbers.flat_num(b"nukage1\0"), 2);
+        assert_eq!(pic_numbers.flat_num(b"UNKNOWN\0"), 0);
+        assert_eq!(pic_numbers.flat_name(1), Some(*b"CEIL3_5\0"));
+        assert_eq!(pic_numbers.flat_name(3), None);
+        assert_eq!(pic_numbers.flat_name(-1), None);
+
+        assert_eq!(pic_numbers.texture_num(&NO_TEXTURE), 0);
+        assert_eq!(pic_numbers.texture_num(b"BIGDOOR1"), 2);
+        assert_eq!(pic_numbers.texture_name(0), Some(NO_TEXTURE));
+        assert_eq!(pic_numbers.texture_name(1), Some(*b"STARTAN3"));
+        assert_eq!(pic_numbers.texture_name(3), None);
+    }
+
+    #[test]
+    fn test_pic_numbers_from_wad_numbers_flats_and_textures() {
+        let mut texture1: Vec<u8> = Vec::new();
+        texture1.extend(2i32.to_le_bytes());
+        texture1.extend(12i32.to_le_bytes());
+        texture1.extend(20i32.to_le_bytes());
+        texture1.extend(b"AASHITTY");
+        texture1.extend(b"STARTAN3");
+        assert_eq!(texture_names(&texture1), vec![*b"AASHITTY", *b"STARTAN3"]);
+
+        let test_dir: PathBuf = create_test_dir("saveg-pics");
+        let path: PathBuf = test_dir.join("test.wad");
+        write_test_wad(
+            &path,
+            &[
+                (String::from("F_START"), Vec::new()),
+                (String::from("FLOOR4_8"), vec![0; 4096]),
+                (String::from("NUKAGE1"), vec![0; 4096]),
+                (String::from("F_END"), Vec::new()),
+                (String::from("TEXTURE1"), texture1),
+            ],
+        );
+        let mut doom_files: Vec<DoomFile> = vec![wad::process_file(&path).unwrap()];
+        let pic_numbers: PicNumbers = PicNumbers::from_wad(&mut doom_files);
+        let _ = fs::remove_dir_all(&test_dir);
+
+        assert_eq!(pic_numbers.flat_num(b"NUKAGE1\0"), 1);
+        assert_eq!(pic_numbers.flat_name(2), None);
+        assert_eq!(pic_numbers.texture_num(b"STARTAN3"), 1);
+    }
+
+    #[test]
+    fn test_archives_restore_the_world() {
+        let pic_numbers: PicNumbers = pic_numbers();
+        let mut world: World = create_world();
+        assert!(world.do_door(tagged_line(&world, 1), DoorType::Close));
+        assert!(world.do_plat(tagged_line(&world, 2), PlatType::DownWaitUpStay, 0));
+        for _ in 0..5 {
+            world.tick();
+        }
+        world.level.sectors[0].floor_pic = *b"NUKAGE1\0";
+        world.level.sides[0].mid_texture = *b"BIGDOOR1";
+
+        let data: Vec<u8> = archive(&world, &pic_numbers);
+        let mut restored: World = create_world();
+        restored.level_time = world.level_time;
+        restored.random.set_prndindex(world.random.prndindex());
+        unarchive(&mut restored, &data, &pic_numbers);
+
+        let hashes: StateHashes = world.state_hashes();
+        assert_eq!(restored.state_hashes(), hashes);
+        assert_eq!(restored.players[0], world.players[0]);
+        assert_eq!(specials(&restored), specials(&world));
+        assert_eq!(&restored.level.sectors[0].floor_pic, b"NUKAGE1\0");
+        assert_eq!(&restored.level.sides[0].mid_texture, b"BIGDOOR1");
+        assert!(restored.level.sectors[1].special_data.is_some());
+
+        // Both carry on the same
+        for _ in 0..20 {
+            world.tick();
+            restored.tick();
+        }
+        assert_eq!(restored.state_hashes(), world.state_hashes());
+    }
+
+    #[test]
+    fn test_unarchive_thinkers_rejects_unknown_classes() {
+        let mut world: World = create_world();
+
+        assert_eq!(
+            world.unarchive_thinkers(&mut SaveReader::new(&[9])),
+            Err(SaveGameError::UnknownThinkerClass(9))
+        );
+        assert_eq!(
+            world.unarchive_thinkers(&mut SaveReader::new(&[1, 0, 0, 0])),
+            Err(SaveGameError::Truncated)
+        );
+    }
+}
@@ -0,0 +1,325 @@
+// Port of p_sight.c. Line of sight checks use the REJECT table to rule out
+// sector pairs quickly, then walk the BSP along the sight line looking for
+// anything in the way
+use crate::fixed::{fixed_div, Fixed, FRACBITS};
+use crate::play::level::{Level, Node, Seg, ML_TWOSIDED, NF_SUBSECTOR};
+use crate::play::maputl::{intercept_vector, DivLine};
+use crate::play::mobj::{Mobj, MobjId};
+use crate::play::world::World;
+
+// The sightzstart, topslope, etc globals from p_sight.c
+#[derive(Clone, Copy, Debug)]
+struct SightTrace {
+    // Eye z of the looker
+    z_start: Fixed,
+    // Slopes to top and bottom of target
+    top_slope: Fixed,
+    bottom_slope: Fixed,
+    trace: DivLine,
+    t2_x: Fixed,
+    t2_y: Fixed,
+}
+
+// P_DivlineSide. Returns side 0 (front), 1 (back), or 2 (on). The
+// horizontal line check compares x against the line's y like the original
+fn divline_side(x: Fixed, y: Fixed, node: &DivLine) -> usize {
+    if node.dx == Fixed::ZERO {
+        if x == node.x {
+            return 2;
+        }
+        if x <= node.x {
+            return usize::from(node.dy > Fixed::ZERO);
+        }
+        return usize::from(node.dy < Fixed::ZERO);
+    }
+
+    if node.dy == Fixed::ZERO {
+        if x == node.y {
+            return 2;
+        }
+        if y <= node.y {
+            return usize::from(node.dx < Fixed::ZERO);
+        }
+        return usize::from(node.dx > Fixed::ZERO);
+    }
+
+    let dx: i32 = (x - node.x).0;
+    let dy: i32 = (y - node.y).0;
+
+    let left: i32 = (node.dy.0 >> FRACBITS).wrapping_mul(dx >> FRACBITS);
+    let right: i32 = (dy >> FRACBITS).wrapping_mul(node.dx.0 >> FRACBITS);
+
+    if right < left {
+        // Front side
+        return 0;
+    }
+    if left == right {
+        return 2;
+    }
+    // Back side
+    1
+}
+
+impl Level {
+    // The REJECT check from P_CheckSight. Lumps that are too short are
+    // treated as not rejecting anything
+    pub fn is_rejected(&self, sector1: usize, sector2: usize) -> bool {
+        let pnum: usize = sector1 * self.sectors.len() + sector2;
+        self.reject
+            .get(pnum >> 3)
+            .is_some_and(|byte| byte & (1 << (pnum & 7)) != 0)
+    }
+}
+
+impl World {
+    // P_CheckSight. Returns true if a straight line between t1 and t2 is
+    // unobstructed. Uses REJECT
+    pub fn check_sight(&mut self, t1: MobjId, t2: MobjId) -> bool {
+        let t1: &Mobj = self.thinkers.mobj(t1);
+        let t2: &Mobj = self.thinkers.mobj(t2);
+
+        // First check for trivial rejection
+        let s1: usize = self.level.subsectors[t1.subsector].sector;
+        let s2: usize = self.level.subsectors[t2.subsector].sector;
+
+        // Check in REJECT table
+        if self.level.is_rejected(s1, s2) {
+            // Can't possibly be connected
+            return false;
+        }
+
+        // An unobstructed LOS is possible. Now look from eyes of t1 to any
+        // part of t2
+        let z_start: Fixed = t1.z + t1.height - Fixed(t1.height.0 >> 2);
+        let mut sight: SightTrace = SightTrace {
+            z_start,
+            top_slope: t2.z + t2.height - z_start,
+            bottom_slope: t2.z - z_start,
+            trace: DivLine {
+                x: t1.x,
+                y: t1.y,
+                dx: t2.x - t1.x,
+                dy: t2.y - t1.y,
+            },
+            t2_x: t2.x,
+            t2_y: t2.y,
+        };
+
+        self.valid_count += 1;
+
+        // The head node is the last node output
+        match self.level.nodes.len() {
+            0 => self.cross_subsector(0, &mut sight),
+            num_nodes => self.cross_bsp_node(num_nodes - 1, &mut sight),
+        }
+    }
+
+    // P_CrossBSPNode. Returns true if the sight line crosses the node's
+    // children without being blocked
+    fn cross_bsp_node(&mut self, node_num: usize, sight: &mut SightTrace) -> bool {
+        let node: Node = self.level.nodes[node_num];
+        let partition: DivLine = DivLine {
+            x: node.x,
+            y: node.y,
+            dx: node.dx,
+            dy: node.dy,
+        };
+
+        // Decide which side the start point is on
+        let mut side: usize = divline_side(sight.trace.x, sight.trace.y, &partition);
+        if side == 2 {
+            // An "on" should cross both sides
+            side = 0;
+        }
+
+        // Cross the starting side
+        if !self.cross_child(node.children[side], sight) {
+            return false;
+        }
+
+        // The partition plane is crossed here
+        if side == divline_side(sight.t2_x, sight.t2_y, &partition) {
+            // The line doesn't touch the other side
+            return true;
+        }
+
+        // Cross the ending side
+        self.cross_child(node.children[side ^ 1], sight)
+    }
+
+    fn cross_child(&mut self, child: u16, sight: &mut SightTrace) -> bool {
+        if child & NF_SUBSECTOR != 0 {
+            self.cross_subsector(usize::from(child & !NF_SUBSECTOR), sight)
+        } else {
+            self.cross_bsp_node(usize::from(child), sight)
+        }
+    }
+
+    // P_CrossSubsector. Returns true if strace crosses the given subsector
+    // successfully
+    fn cross_subsector(&mut self, num: usize, sight: &mut SightTrace) -> bool {
+        let first_line: usize = self.level.subsectors[num].first_line;
+        let num_lines: usize = self.level.subsectors[num].num_lines;
+
+        // Check lines
+        for seg_index in first_line..first_line + num_lines {
+            let seg: Seg = self.level.segs[seg_index];
+            let line_index: usize = seg.line_def;
+
+            // Already checked other side?
+            if self.level.lines[line_index].valid_count == self.valid_count {
+                continue;
+            }
+            self.level.lines[line_index].valid_count = self.valid_count;
+
+            let line = &self.level.lines[line_index];
+            let v1 = self.level.vertexes[line.v1];
+            let v2 = self.level.vertexes[line.v2];
+            let s1: usize = divline_side(v1.x, v1.y, &sight.trace);
+            let s2: usize = divline_side(v2.x, v2.y, &sight.trace);
+
+            // Line isn't crossed?
+            if s1 == s2 {
+                continue;
+            }
+
+            let divl: DivLine = DivLine {
+                x: v1.x,
+                y: v1.y,
+                dx: v2.x - v1.x,
+                dy: v2.y - v1.y,
+            };
+            let s1: usize = divline_side(sight.trace.x, sight.trace.y, &divl);
+            let s2: usize = divline_side(sight.t2_x, sight.t2_y, &divl);
+
+            // Line isn't crossed?
+            if s1 == s2 {
+                continue;
+            }
+
+            // Stop because it is not two sided anyway
+            let back_sector: usize = match seg.back_sector {
+                Some(back_sector) if line.flags & ML_TWOSIDED != 0 => back_sector,
+                _ => return false,
+            };
+
+            // Crosses a two sided line
+            let front = &self.level.sectors[seg.front_sector];
+            let back = &self.level.sectors[back_sector];
+
+            // No wall to block sight with?
+            if front.floor_height == back.floor_height
+                && front.ceiling_height == back.ceiling_height
+            {
+                continue;
+            }
+
+            // Possible occluder because of ceiling height differences
+            let open_top: Fixed = front.ceiling_height.min(back.ceiling_height);
+            // Because of floor height differences
+            let open_bottom: Fixed = front.floor_height.max(back.floor_height);
+
+            // Quick test for totally closed doors
+            if open_bottom >= open_top {
+                // Stop
+                return false;
+            }
+
+            let frac: Fixed = intercept_vector(&sight.trace, &divl);
+
+            if front.floor_height != back.floor_height {
+                let slope: Fixed = fixed_div(open_bottom - sight.z_start, frac);
+                if slope > sight.bottom_slope {
+                    sight.bottom_slope = slope;
+                }
+            }
+
+            if front.ceiling_height != back.ceiling_height {
+                let slope: Fixed = fixed_div(open_top - sight.z_start, frac);
+                if slope < sight.top_slope {
+                    sight.top_slope = slope;
+                }
+            }
+
+            if sight.top_slope <= sight.bottom_slope {
+                // Stop
+                return false;
+            }
+        }
+
+        // Passed the subsector ok
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::Fixed;
+    use crate::info::mobjinfo::MT_TROOP;
+    use crate::play::mobj::{MobjId, ONFLOORZ};
+    use crate::play::test_map::TestMap;
+    use crate::play::world::World;
+
+    fn spawn(world: &mut World, x: i32, y: i32) -> MobjId {
+        world.spawn_mobj(Fixed::from_int(x), Fixed::from_int(y), ONFLOORZ, MT_TROOP)
+    }
+
+    #[test]
+    fn test_check_sight_sees_through_open_lines() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .room(128, 16, 128)
+            .room(256, 0, 128)
+            .to_world();
+        let first: MobjId = spawn(&mut world, 64, 128);
+        let second: MobjId = spawn(&mut world, 100, 64);
+        let third: MobjId = spawn(&mut world, 600, 200);
+
+        assert!(world.check_sight(first, second));
+        assert!(world.check_sight(first, third));
+        assert!(world.check_sight(third, first));
+    }
+
+    #[test]
+    fn test_check_sight_is_blocked_by_closed_doors_and_high_floors() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .room(64, 0, 0)
+            .room(256, 0, 128)
+            .room(64, 100, 128)
+            .room(256, 0, 128)
+            .to_world();
+        let first: MobjId = spawn(&mut world, 128, 128);
+        let second: MobjId = spawn(&mut world, 448, 128);
+        let third: MobjId = spawn(&mut world, 768, 128);
+
+        // Closed door
+        assert!(!world.check_sight(first, second));
+        // Imps' eyes are at 42, under the middle floor
+        assert!(!world.check_sight(second, third));
+
+        // Opened door
+        world.level.sectors[1].ceiling_height = Fixed::from_int(72);
+        assert!(world.check_sight(first, second));
+    }
+
+    #[test]
+    fn test_check_sight_uses_reject_table() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .room(256, 0, 128)
+            .to_world();
+        let first: MobjId = spawn(&mut world, 128, 128);
+        let second: MobjId = spawn(&mut world, 384, 128);
+
+        // Sector 0 can't see sector 1 but it works the other way
+        world.level.reject = vec![0b10];
+        assert!(world.level.is_rejected(0, 1));
+        assert!(!world.check_sight(first, second));
+        assert!(world.check_sight(second, first));
+
+        // Too short to have the bits
+        world.level.reject = Vec::new();
+        assert!(world.check_sight(first, second));
+    }
+}
@@ -0,0 +1,1080 @@
+// Port of p_spec.c. Finding sectors around other sectors, the line specials
+// triggered by walking over and shooting lines, the damaging sectors and
+// the level effects that are updated every tic
+use crate::fixed::{Fixed, FRACUNIT};
+use crate::info::mobjinfo::{
+    MT_BFG, MT_BRUISERSHOT, MT_HEADSHOT, MT_PLASMA, MT_ROCKET, MT_TROOPSHOT,
+};
+use crate::play::ceiling::CeilingType;
+use crate::play::doors::DoorType;
+use crate::play::floor::{FloorMove, FloorType, StairType, FLOORSPEED};
+use crate::play::level::{Level, Line, Sector, Side, ML_TWOSIDED};
+use crate::play::lights::{FASTDARK, SLOWDARK};
+use crate::play::mobj::{Mobj, MobjId};
+use crate::play::plats::PlatType;
+use crate::play::player::{CF_GODMODE, PW_IRONFEET};
+use crate::play::switch::{Button, ButtonWhere};
+use crate::play::world::{LevelExit, World};
+use crate::sound::sfx::SFX_SWTCHN;
+
+// Sectors can have more neighbours than this, the original only looked at
+// the first 20 when finding the next highest floor
+pub const MAX_ADJOINING_SECTORS: usize = 20;
+
+impl Level {
+    // getNextSector. Return sector on the other side of a two sided line,
+    // None if the line is one sided
+    pub fn next_sector(&self, line: usize, sector: usize) -> Option<usize> {
+        let line: &Line = &self.lines[line];
+        if line.flags & ML_TWOSIDED == 0 {
+            return None;
+        }
+
+        if line.front_sector == Some(sector) {
+            return line.back_sector;
+        }
+
+        line.front_sector
+    }
+
+    // Sectors on the other side of a sector's two sided lines, in line
+    // order
+    pub(crate) fn neighbours(&self, sector: usize) -> impl Iterator<Item = &Sector> + '_ {
+        self.sectors[sector]
+            .lines
+            .iter()
+            .filter_map(move |line| self.next_sector(*line, sector))
+            .map(|other| &self.sectors[other])
+    }
+
+    // P_FindLowestFloorSurrounding
+    pub fn find_lowest_floor_surrounding(&self, sector: usize) -> Fixed {
+        self.neighbours(sector)
+            .map(|other| other.floor_height)
+            .fold(self.sectors[sector].floor_height, Fixed::min)
+    }
+
+    // P_FindHighestFloorSurrounding
+    pub fn find_highest_floor_surrounding(&self, sector: usize) -> Fixed {
+        self.neighbours(sector)
+            .map(|other| other.floor_height)
+            .fold(Fixed(-500 * FRACUNIT), Fixed::max)
+    }
+
+    // P_FindNextHighestFloor. Passing the current height lets it find the
+    // next height above it
+    pub fn find_next_highest_floor(&self, sector: usize, current_height: Fixed) -> Fixed {
+        let mut height_list: Vec<Fixed> = Vec::new();
+
+        for other in self.neighbours(sector) {
+            if other.floor_height > current_height {
+                height_list.push(other.floor_height);
+            }
+
+            // Check for overflow. Exit
+            if height_list.len() >= MAX_ADJOINING_SECTORS {
+                break;
+            }
+        }
+
+        // Find lowest height in list
+        height_list.into_iter().min().unwrap_or(current_height)
+    }
+
+    // P_FindLowestCeilingSurrounding
+    pub fn find_lowest_ceiling_surrounding(&self, sector: usize) -> Fixed {
+        self.neighbours(sector)
+            .map(|other| other.ceiling_height)
+            .fold(Fixed::MAX, Fixed::min)
+    }
+
+    // P_FindHighestCeilingSurrounding
+    pub fn find_highest_ceiling_surrounding(&self, sector: usize) -> Fixed {
+        self.neighbours(sector)
+            .map(|other| other.ceiling_height)
+            .fold(Fixed::ZERO, Fixed::max)
+    }
+
+    // P_FindSectorFromLineTag. Searches from the sector after start, so
+    // passing the last sector found gives the next one
+    pub fn find_sector_from_line_tag(&self, line: usize, start: Option<usize>) -> Option<usize> {
+        let tag: i32 = self.lines[line].tag;
+        let first: usize = start.map(|start| start + 1).unwrap_or(0);
+
+        (first..self.sectors.len()).find(|sector| self.sectors[*sector].tag == tag)
+    }
+
+    // Every sector with the line's tag, in order
+    pub fn tagged_sectors(&self, line: usize) -> Vec<usize> {
+        self.sectors_with_tag(self.lines[line].tag)
+    }
+
+    pub fn sectors_with_tag(&self, tag: i32) -> Vec<usize> {
+        (0..self.sectors.len())
+            .filter(|sector| self.sectors[*sector].tag == tag)
+            .collect()
+    }
+
+    // P_FindMinSurroundingLight. Find minimum light from an adjacent sector
+    pub fn find_min_surrounding_light(&self, sector: usize, max: i32) -> i32 {
+        self.neighbours(sector)
+            .map(|other| other.light_level)
+            .fold(max, i32::min)
+    }
+}
+
+impl World {
+    // P_CrossSpecialLine. Called every time a thing origin is about to
+    // cross a line with a non 0 special
+    pub fn cross_special_line(&mut self, line: usize, side: usize, id: MobjId) {
+        let thing: &Mobj = self.thinkers.mobj(id);
+        let is_player: bool = thing.player.is_some();
+
+        // Triggers that other things can activate
+        if !is_player {
+            // Things that should NOT trigger specials...
+            if [
+                MT_ROCKET,
+                MT_PLASMA,
+                MT_BFG,
+                MT_TROOPSHOT,
+                MT_HEADSHOT,
+                MT_BRUISERSHOT,
+            ]
+            .contains(&thing.mobj_type)
+            {
+                return;
+            }
+
+            match self.level.lines[line].special {
+                // TELEPORT TRIGGER
+                39 |
+                // TELEPORT RETRIGGER
+                97 |
+                // TELEPORT MONSTERONLY TRIGGER
+                125 |
+                // TELEPORT MONSTERONLY RETRIGGER
+                126 |
+                // RAISE DOOR
+                4 |
+                // PLAT DOWN-WAIT-UP-STAY TRIGGER
+                10 |
+                // PLAT DOWN-WAIT-UP-STAY RETRIGGER
+                88 => {}
+                _ => return,
+            }
+        }
+
+        // Triggers are used once and have their special cleared, the
+        // retriggers keep working
+        let special: i32 = self.level.lines[line].special;
+        let used: bool = match special {
+            // TRIGGERS. All from here to RETRIGGERS
+            2 => {
+                self.do_door(line, DoorType::Open);
+                true
+            }
+            3 => {
+                self.do_door(line, DoorType::Close);
+                true
+            }
+            4 => {
+                self.do_door(line, DoorType::Normal);
+                true
+            }
+            5 => {
+                self.do_floor(line, FloorType::RaiseFloor);
+                true
+            }
+            6 => {
+                self.do_ceiling(line, CeilingType::FastCrushAndRaise);
+                true
+            }
+            8 => {
+                self.build_stairs(line, StairType::Build8);
+                true
+            }
+            10 => {
+                self.do_plat(line, PlatType::DownWaitUpStay, 0);
+                true
+            }
+            12 => {
+                self.light_turn_on(line, 0);
+                true
+            }
+            13 => {
+                self.light_turn_on(line, 255);
+                true
+            }
+            16 => {
+                self.do_door(line, DoorType::Close30ThenOpen);
+                true
+            }
+            17 => {
+                self.start_light_strobing(line);
+                true
+            }
+            19 => {
+                self.do_floor(line, FloorType::LowerFloor);
+                true
+            }
+            22 => {
+                self.do_plat(line, PlatType::RaiseToNearestAndChange, 0);
+                true
+            }
+            25 => {
+                self.do_ceiling(line, CeilingType::CrushAndRaise);
+                true
+            }
+            30 => {
+                self.do_floor(line, FloorType::RaiseToTexture);
+                true
+            }
+            35 => {
+                self.light_turn_on(line, 35);
+                true
+            }
+            36 => {
+                self.do_floor(line, FloorType::TurboLower);
+                true
+            }
+            37 => {
+                self.do_floor(line, FloorType::LowerAndChange);
+                true
+            }
+            38 => {
+                self.do_floor(line, FloorType::LowerFloorToLowest);
+                true
+            }
+            39 => {
+                self.teleport(line, side, id);
+                true
+            }
+            40 => {
+                self.do_ceiling(line, CeilingType::RaiseToHighest);
+                self.do_floor(line, FloorType::LowerFloorToLowest);
+                true
+            }
+            44 => {
+                self.do_ceiling(line, CeilingType::LowerAndCrush);
+                true
+            }
+            52 => {
+                self.exit_level(LevelExit::Normal);
+                false
+            }
+            53 => {
+                self.do_plat(line, PlatType::PerpetualRaise, 0);
+                true
+            }
+            54 => {
+                self.stop_plat(line);
+                true
+            }
+            56 => {
+                self.do_floor(line, FloorType::RaiseFloorCrush);
+                true
+            }
+            57 => {
+                self.ceiling_crush_stop(line);
+                true
+            }
+            58 => {
+                self.do_floor(line, FloorType::RaiseFloor24);
+                true
+            }
+            59 => {
+                self.do_floor(line, FloorType::RaiseFloor24AndChange);
+                true
+            }
+            104 => {
+                self.turn_tag_lights_off(line);
+                true
+            }
+            108 => {
+                self.do_door(line, DoorType::BlazeRaise);
+                true
+            }
+            109 => {
+                self.do_door(line, DoorType::BlazeOpen);
+                true
+            }
+            100 => {
+                self.build_stairs(line, StairType::Turbo16);
+                true
+            }
+            110 => {
+                self.do_door(line, DoorType::BlazeClose);
+                true
+            }
+            119 => {
+                self.do_floor(line, FloorType::RaiseFloorToNearest);
+                true
+            }
+            121 => {
+                self.do_plat(line, PlatType::BlazeDwus, 0);
+                true
+            }
+            124 => {
+                self.exit_level(LevelExit::Secret);
+                false
+            }
+            125 => {
+                if !is_player {
+                    self.teleport(line, side, id);
+                }
+                !is_player
+            }
+            130 => {
+                self.do_floor(line, FloorType::RaiseFloorTurbo);
+                true
+            }
+            141 => {
+                self.do_ceiling(line, CeilingType::SilentCrushAndRaise);
+                true
+            }
+
+            // RETRIGGERS. All from here till end
+            72 => {
+                self.do_ceiling(line, CeilingType::LowerAndCrush);
+                false
+            }
+            73 => {
+                self.do_ceiling(line, CeilingType::CrushAndRaise);
+                false
+            }
+            74 => {
+                self.ceiling_crush_stop(line);
+                false
+            }
+            75 => {
+                self.do_door(line, DoorType::Close);
+                false
+            }
+            76 => {
+                self.do_door(line, DoorType::Close30ThenOpen);
+                false
+            }
+            77 => {
+                self.do_ceiling(line, CeilingType::FastCrushAndRaise);
+                false
+            }
+            79 => {
+                self.light_turn_on(line, 35);
+                false
+            }
+            80 => {
+                self.light_turn_on(line, 0);
+                false
+            }
+            81 => {
+                self.light_turn_on(line, 255);
+                false
+            }
+            82 => {
+                self.do_floor(line, FloorType::LowerFloorToLowest);
+                false
+            }
+            83 => {
+                self.do_floor(line, FloorType::LowerFloor);
+                false
+            }
+            84 => {
+                self.do_floor(line, FloorType::LowerAndChange);
+                false
+            }
+            86 => {
+                self.do_door(line, DoorType::Open);
+                false
+            }
+            87 => {
+                self.do_plat(line, PlatType::PerpetualRaise, 0);
+                false
+            }
+            88 => {
+                self.do_plat(line, PlatType::DownWaitUpStay, 0);
+                false
+            }
+            89 => {
+                self.stop_plat(line);
+                false
+            }
+            90 => {
+                self.do_door(line, DoorType::Normal);
+                false
+            }
+            91 => {
+                self.do_floor(line, FloorType::RaiseFloor);
+                false
+            }
+            92 => {
+                self.do_floor(line, FloorType::RaiseFloor24);
+                false
+            }
+            93 => {
+                self.do_floor(line, FloorType::RaiseFloor24AndChange);
+                false
+            }
+            94 => {
+                self.do_floor(line, FloorType::RaiseFloorCrush);
+                false
+            }
+            95 => {
+                self.do_plat(line, PlatType::RaiseToNearestAndChange, 0);
+                false
+            }
+            96 => {
+                self.do_floor(line, FloorType::RaiseToTexture);
+                false
+            }
+            97 => {
+                self.teleport(line, side, id);
+                false
+            }
+            98 => {
+                self.do_floor(line, FloorType::TurboLower);
+                false
+            }
+            105 => {
+                self.do_door(line, DoorType::BlazeRaise);
+                false
+            }
+            106 => {
+                self.do_door(line, DoorType::BlazeOpen);
+                false
+            }
+            107 => {
+                self.do_door(line, DoorType::BlazeClose);
+                false
+            }
+            120 => {
+                self.do_plat(line, PlatType::BlazeDwus, 0);
+                false
+            }
+            126 => {
+                if !is_player {
+                    self.teleport(line, side, id);
+                }
+                false
+            }
+            128 => {
+                self.do_floor(line, FloorType::RaiseFloorToNearest);
+                false
+            }
+            129 => {
+                self.do_floor(line, FloorType::RaiseFloorTurbo);
+                false
+            }
+            _ => false,
+        };
+
+        if used {
+            self.level.lines[line].special = 0;
+        }
+    }
+
+    // P_ShootSpecialLine. Called when a thing shoots a special line
+    pub fn shoot_special_line(&mut self, id: MobjId, line: usize) {
+        let special: i32 = self.level.lines[line].special;
+
+        // Impacts that other things can activate
+        if self.thinkers.mobj(id).player.is_none() && special != 46 {
+            return;
+        }
+
+        match special {
+            // RAISE FLOOR
+            24 => {
+                self.do_floor(line, FloorType::RaiseFloor);
+                self.change_switch_texture(line, false);
+            }
+            // OPEN DOOR
+            46 => {
+                self.do_door(line, DoorType::Open);
+                self.change_switch_texture(line, true);
+            }
+            // RAISE FLOOR NEAR AND CHANGE
+            47 => {
+                self.do_plat(line, PlatType::RaiseToNearestAndChange, 0);
+                self.change_switch_texture(line, false);
+            }
+            _ => {}
+        }
+    }
+
+    // P_PlayerInSpecialSector. Called every tic frame that the player
+    // origin is in a special sector
+    pub fn player_in_special_sector(&mut self, player: usize) {
+        let id: MobjId = self.players[player].mo.unwrap();
+        let mobj: &Mobj = self.thinkers.mobj(id);
+        let sector: usize = self.level.subsectors[mobj.subsector].sector;
+
+        // Falling, not all the way down yet?
+        if mobj.z != self.level.sectors[sector].floor_height {
+            return;
+        }
+
+        let damage_tic: bool = self.level_time & 0x1f == 0;
+        let iron_feet: bool = self.players[player].powers[PW_IRONFEET] != 0;
+
+        // Has hitten ground
+        match self.level.sectors[sector].special {
+            // HELLSLIME DAMAGE
+            5 => {
+                if !iron_feet && damage_tic {
+                    self.damage_mobj(id, None, None, 10);
+                }
+            }
+            // NUKAGE DAMAGE
+            7 => {
+                if !iron_feet && damage_tic {
+                    self.damage_mobj(id, None, None, 5);
+                }
+            }
+            // SUPER HELLSLIME DAMAGE and STROBE HURT
+            16 | 4 => {
+                if (!iron_feet || self.random.p_random() < 5) && damage_tic {
+                    self.damage_mobj(id, None, None, 20);
+                }
+            }
+            // SECRET SECTOR
+            9 => {
+                self.players[player].secret_count += 1;
+                self.level.sectors[sector].special = 0;
+            }
+            // EXIT SUPER DAMAGE! (for E1M8 finale)
+            11 => {
+                self.players[player].cheats &= !CF_GODMODE;
+
+                if damage_tic {
+                    self.damage_mobj(id, None, None, 20);
+                }
+
+                if self.players[player].health <= 10 {
+                    self.exit_level(LevelExit::Normal);
+                }
+            }
+            special => panic!("P_PlayerInSpecialSector: unknown special {}", special),
+        }
+    }
+
+    // G_ExitLevel and G_SecretExitLevel. The game notices on its next tic
+    pub fn exit_level(&mut self, exit: LevelExit) {
+        self.level_exit = Some(exit);
+    }
+
+    // P_UpdateSpecials. Animates scrolling lines and pops switches back out.
+    // Flats and textures are animated by the renderer
+    pub fn update_specials(&mut self) {
+        // ANIMATE LINE SPECIALS
+        for line in self.scrolling_lines.iter() {
+            let line: &Line = &self.level.lines[*line];
+            if line.special == 48 {
+                // EFFECT FIRSTCOL SCROLL +
+                let side: usize = line.side_num[0].unwrap();
+                self.level.sides[side].texture_offset += Fixed::ONE;
+            }
+        }
+
+        // DO BUTTONS
+        for index in 0..self.buttons.len() {
+            if self.buttons[index].timer == 0 {
+                continue;
+            }
+
+            self.buttons[index].timer -= 1;
+            if self.buttons[index].timer != 0 {
+                continue;
+            }
+
+            let button: Button = self.buttons[index];
+            let side: usize = self.level.lines[button.line].side_num[0].unwrap();
+            let side_def: &mut Side = &mut self.level.sides[side];
+            match button.where_ {
+                ButtonWhere::Top => side_def.top_texture = button.texture,
+                ButtonWhere::Middle => side_def.mid_texture = button.texture,
+                ButtonWhere::Bottom => side_def.bottom_texture = button.texture,
+            }
+
+            self.start_button_sound(index, SFX_SWTCHN);
+            self.buttons[index] = Default::default();
+        }
+    }
+
+    // P_SpawnSpecials. After the map has been loaded, scan for specials
+    // that spawn thinkers
+    pub fn spawn_specials(&mut self) {
+        // Init special SECTORs
+        for sector in 0..self.level.sectors.len() {
+            match self.level.sectors[sector].special {
+                // FLICKERING LIGHTS
+                1 => self.spawn_light_flash(sector),
+                // STROBE FAST
+                2 => self.spawn_strobe_flash(sector, FASTDARK, false),
+                // STROBE SLOW
+                3 => self.spawn_strobe_flash(sector, SLOWDARK, false),
+                // STROBE FAST/DEATH SLIME
+                4 => {
+                    self.spawn_strobe_flash(sector, FASTDARK, false);
+                    self.level.sectors[sector].special = 4;
+                }
+                // GLOWING LIGHT
+                8 => self.spawn_glowing_light(sector),
+                // SECRET SECTOR
+                9 => self.total_secrets += 1,
+                // DOOR CLOSE IN 30 SECONDS
+                10 => self.spawn_door_close_in_30(sector),
+                // SYNC STROBE SLOW
+                12 => self.spawn_strobe_flash(sector, SLOWDARK, true),
+                // SYNC STROBE FAST
+                13 => self.spawn_strobe_flash(sector, FASTDARK, true),
+                // DOOR RAISE IN 5 MINUTES
+                14 => self.spawn_door_raise_in_5_mins(sector),
+                17 => self.spawn_fire_flicker(sector),
+                _ => {}
+            }
+        }
+
+        // Init line EFFECTs
+        self.scrolling_lines = (0..self.level.lines.len())
+            .filter(|line| self.level.lines[*line].special == 48)
+            .collect();
+
+        // Init other misc stuff
+        self.active_ceilings = Default::default();
+        self.active_plats = Default::default();
+        self.buttons = Default::default();
+    }
+
+    // EV_DoDonut. Special Stuff that can not be categorized
+    pub fn do_donut(&mut self, line: usize) -> bool {
+        let mut rtn: bool = false;
+
+        for s1 in self.level.tagged_sectors(line) {
+            // ALREADY MOVING? IF SO, KEEP GOING...
+            if self.level.sectors[s1].special_data.is_some() {
+                continue;
+            }
+
+            rtn = true;
+            let s2: usize = match self.level.next_sector(self.level.sectors[s1].lines[0], s1) {
+                Some(s2) => s2,
+                None => continue,
+            };
+
+            for s2_line in self.level.sectors[s2].lines.clone() {
+                let line: &Line = &self.level.lines[s2_line];
+
+                // The original checked !flags & ML_TWOSIDED, which is never
+                // true, so lines where s2 is the back sector pick s2 itself
+                // as s3. One sided lines would have read a null sector
+                if line.back_sector == Some(s1) {
+                    continue;
+                }
+                let s3: usize = match line.back_sector {
+                    Some(s3) => s3,
+                    None => continue,
+                };
+                let (s3_floor_pic, s3_floor_height): ([u8; 8], Fixed) = (
+                    self.level.sectors[s3].floor_pic,
+                    self.level.sectors[s3].floor_height,
+                );
+
+                // Spawn rising slime
+                self.add_floor_move(FloorMove {
+                    floor_type: FloorType::DonutRaise,
+                    crush: false,
+                    sector: s2,
+                    direction: 1,
+                    new_special: 0,
+                    texture: s3_floor_pic,
+                    floor_dest_height: s3_floor_height,
+                    speed: Fixed(FLOORSPEED.0 / 2),
+                });
+
+                // Spawn lowering donut-hole
+                self.add_floor_move(FloorMove {
+                    floor_type: FloorType::LowerFloor,
+                    crush: false,
+                    sector: s1,
+                    direction: -1,
+                    new_special: 0,
+                    texture: [0; 8],
+                    floor_dest_height: s3_floor_height,
+                    speed: Fixed(FLOORSPEED.0 / 2),
+                });
+                break;
+            }
+        }
+
+        rtn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::Fixed;
+    use crate::info::mobjinfo::{MT_PLAYER, MT_ROCKET, MT_TROOP};
+    use crate::play::level::Level;
+    use crate::play::mobj::{MobjId, ONFLOORZ};
+    use crate::play::test_map::{TestMap, TestRoom};
+    use crate::play::world::{LevelExit, World};
+
+    fn room(width: i16, floor_height: i16, ceiling_height: i16, light_level: i16) -> TestRoom {
+        TestRoom {
+            width,
+            floor_height,
+            ceiling_height,
+            light_level,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_level_find_surrounding_heights_and_light() {
+        let level: Level = Level::from_lumps(
+            &TestMap::new()
+                .room_with(room(128, 16, 200, 100))
+                .room_with(room(128, 0, 128, 160))
+                .room_with(room(128, 64, 96, 200))
+                .to_map_lumps(),
+        )
+        .unwrap();
+
+        assert_eq!(level.next_sector(level.sectors[1].lines[0], 1), None);
+        assert_eq!(level.find_lowest_floor_surrounding(1), Fixed::from_int(0));
+        assert_eq!(level.find_lowest_floor_surrounding(2), Fixed::from_int(0));
+        assert_eq!(level.find_highest_floor_surrounding(1), Fixed::from_int(64));
+        assert_eq!(
+            level.find_next_highest_floor(1, Fixed::from_int(0)),
+            Fixed::from_int(16)
+        );
+        assert_eq!(
+            level.find_next_highest_floor(1, Fixed::from_int(16)),
+            Fixed::from_int(64)
+        );
+        assert_eq!(
+            level.find_next_highest_floor(1, Fixed::from_int(64)),
+            Fixed::from_int(64)
+        );
+        assert_eq!(
+            level.find_lowest_ceiling_surrounding(1),
+            Fixed::from_int(96)
+        );
+        assert_eq!(
+            level.find_highest_ceiling_surrounding(1),
+            Fixed::from_int(200)
+        );
+        assert_eq!(level.find_min_surrounding_light(1, 160), 100);
+        assert_eq!(level.find_min_surrounding_light(0, 100), 100);
+
+        // One sided sectors have nothing around them
+        let level: Level =
+            Level::from_lumps(&TestMap::new().room(128, 0, 128).to_map_lumps()).unwrap();
+        assert_eq!(
+            level.find_highest_floor_surrounding(0),
+            Fixed::from_int(-500)
+        );
+        assert_eq!(level.find_lowest_ceiling_surrounding(0), Fixed::MAX);
+    }
+
+    #[test]
+    fn test_level_find_sector_from_line_tag() {
+        let tagged = |tag: i16| TestRoom {
+            width: 64,
+            ceiling_height: 128,
+            tag,
+            ..Default::default()
+        };
+        let level: Level = Level::from_lumps(
+            &TestMap::new()
+                .room_with(tagged(3))
+                .room_with(tagged(0))
+                .room_with(tagged(3))
+                .line_special(0, 2, 3)
+                .to_map_lumps(),
+        )
+        .unwrap();
+        let line: usize = level.lines.iter().position(|line| line.tag == 3).unwrap();
+
+        assert_eq!(level.find_sector_from_line_tag(line, None), Some(0));
+        assert_eq!(level.find_sector_from_line_tag(line, Some(0)), Some(2));
+        assert_eq!(level.find_sector_from_line_tag(line, Some(2)), None);
+        assert_eq!(level.tagged_sectors(line), vec![0, 2]);
+    }
+
+    fn special_line(world: &World) -> usize {
+        world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.special != 0)
+            .unwrap()
+    }
+
+    fn walk_over_line(world: &mut World, id: MobjId) {
+        let (x, y): (Fixed, Fixed) = {
+            let mobj = world.thinkers.mobj(id);
+            (mobj.x, mobj.y)
+        };
+        assert!(world.try_move(id, x + Fixed::from_int(40), y));
+    }
+
+    #[test]
+    fn test_cross_special_line_triggers_once_and_retriggers() {
+        let test_map = |special: i16| {
+            TestMap::new()
+                .room(256, 0, 128)
+                .room_with(TestRoom {
+                    width: 256,
+                    floor_height: 0,
+                    ceiling_height: 0,
+                    light_level: 160,
+                    tag: 5,
+                    ..Default::default()
+                })
+                .room(256, 0, 128)
+                .line_special(0, special, 5)
+        };
+
+        // W1 door open, the ceiling goes up to 4 below the lowest
+        // surrounding ceiling
+        let mut world: World = test_map(2).to_world();
+        let player: MobjId = world.spawn_mobj(
+            Fixed::from_int(230),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_PLAYER,
+        );
+        world.thinkers.mobj_mut(player).player = Some(0);
+        let line: usize = special_line(&world);
+        world.cross_special_line(line, 1, player);
+        assert_eq!(world.level.lines[line].special, 0);
+        assert!(world.level.sectors[1].special_data.is_some());
+
+        for _ in 0..62 {
+            world.tick();
+        }
+        assert_eq!(world.level.sectors[1].ceiling_height, Fixed::from_int(124));
+        assert!(world.level.sectors[1].special_data.is_some());
+        world.tick();
+        assert_eq!(world.level.sectors[1].special_data, None);
+
+        // WR door open keeps its special
+        let mut world: World = test_map(86).to_world();
+        let player: MobjId = world.spawn_mobj(
+            Fixed::from_int(230),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_PLAYER,
+        );
+        world.thinkers.mobj_mut(player).player = Some(0);
+        let line: usize = special_line(&world);
+        world.cross_special_line(line, 1, player);
+        assert_eq!(world.level.lines[line].special, 86);
+    }
+
+    #[test]
+    fn test_cross_special_line_only_lets_monsters_use_some_specials() {
+        let test_map = |special: i16| {
+            TestMap::new()
+                .room(256, 0, 128)
+                .room_with(TestRoom {
+                    width: 256,
+                    ceiling_height: 128,
+                    floor_height: 64,
+                    light_level: 160,
+                    tag: 5,
+                    ..Default::default()
+                })
+                .line_special(0, special, 5)
+        };
+
+        // Floor lower, players only
+        let mut world: World = test_map(19).to_world();
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(200),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+        let line: usize = special_line(&world);
+        world.cross_special_line(line, 1, troop);
+        assert_eq!(world.level.lines[line].special, 19);
+        assert_eq!(world.level.sectors[1].special_data, None);
+
+        // Lifts are fine, rockets never trigger anything
+        let mut world: World = test_map(88).to_world();
+        let rocket: MobjId = world.spawn_mobj(
+            Fixed::from_int(200),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_ROCKET,
+        );
+        let line: usize = special_line(&world);
+        world.cross_special_line(line, 1, rocket);
+        assert_eq!(world.level.sectors[1].special_data, None);
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(200),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+        world.cross_special_line(line, 1, troop);
+        assert!(world.level.sectors[1].special_data.is_some());
+    }
+
+    #[test]
+    fn test_try_move_crosses_special_lines_and_exits() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .room(256, 0, 128)
+            .line_special(0, 52, 0)
+            .to_world();
+        let player: MobjId = world.spawn_mobj(
+            Fixed::from_int(230),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_PLAYER,
+        );
+        world.thinkers.mobj_mut(player).player = Some(0);
+
+        walk_over_line(&mut world, player);
+        assert_eq!(world.level_exit, Some(LevelExit::Normal));
+        // Exits keep their special
+        let line: usize = special_line(&world);
+        assert_eq!(world.level.lines[line].special, 52);
+    }
+
+    fn spawn_player(world: &mut World) -> MobjId {
+        let id: MobjId = world.spawn_mobj(
+            Fixed::from_int(64),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_PLAYER,
+        );
+        world.thinkers.mobj_mut(id).player = Some(0);
+        world.players[0].mo = Some(id);
+        world.players[0].health = 100;
+        id
+    }
+
+    #[test]
+    fn test_player_in_special_sector_damages_every_32_tics() {
+        let mut world: World = TestMap::new()
+            .room_with(TestRoom {
+                width: 256,
+                ceiling_height: 128,
+                special: 7,
+                ..Default::default()
+            })
+            .to_world();
+        let player: MobjId = spawn_player(&mut world);
+
+        for _ in 0..64 {
+            world.player_in_special_sector(0);
+            world.level_time += 1;
+        }
+        assert_eq!(world.thinkers.mobj(player).health, 90);
+
+        // Radiation suits stop nukage
+        world.players[0].powers[crate::play::player::PW_IRONFEET] = 100;
+        world.level_time = 0;
+        world.player_in_special_sector(0);
+        assert_eq!(world.thinkers.mobj(player).health, 90);
+
+        // Nothing happens in the air
+        world.players[0].powers[crate::play::player::PW_IRONFEET] = 0;
+        world.thinkers.mobj_mut(player).z = Fixed::from_int(10);
+        world.player_in_special_sector(0);
+        assert_eq!(world.thinkers.mobj(player).health, 90);
+    }
+
+    #[test]
+    fn test_player_in_special_sector_counts_secrets_once() {
+        let mut world: World = TestMap::new()
+            .room_with(TestRoom {
+                width: 256,
+                ceiling_height: 128,
+                special: 9,
+                ..Default::default()
+            })
+            .to_world();
+        assert_eq!(world.total_secrets, 1);
+        spawn_player(&mut world);
+
+        world.player_in_special_sector(0);
+        assert_eq!(world.players[0].secret_count, 1);
+        assert_eq!(world.level.sectors[0].special, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "P_PlayerInSpecialSector: unknown special 99")]
+    fn test_player_in_special_sector_panics_on_unknown_special() {
+        let mut world: World = TestMap::new()
+            .room_with(TestRoom {
+                width: 256,
+                ceiling_height: 128,
+                special: 99,
+                ..Default::default()
+            })
+            .to_world();
+        spawn_player(&mut world);
+        world.player_in_special_sector(0);
+    }
+
+    #[test]
+    fn test_update_specials_scrolls_lines() {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .room(256, 0, 128)
+            .line_special(1, 48, 0)
+            .to_world();
+        assert_eq!(world.scrolling_lines.len(), 1);
+        let line: usize = world.scrolling_lines[0];
+        let side: usize = world.level.lines[line].side_num[0].unwrap();
+
+        for _ in 0..10 {
+            world.tick();
+        }
+        assert_eq!(world.level.sides[side].texture_offset, Fixed::from_int(10));
+    }
+
+    #[test]
+    fn test_do_donut_raises_ring_and_lowers_hole() {
+        let mut world: World = TestMap::new()
+            .room(128, 16, 128)
+            .room(128, 0, 128)
+            .room_with(TestRoom {
+                width: 128,
+                floor_height: 32,
+                ceiling_height: 128,
+                tag: 2,
+                ..Default::default()
+            })
+            .line_special(2, 9, 2)
+            .to_world();
+        world.level.sectors[0].floor_pic = *b"NUKAGE1\0";
+        // The hole's first line has to be the one next to the ring
+        world.level.sectors[2].lines.rotate_right(1);
+        let line: usize = world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.special == 9)
+            .unwrap();
+
+        assert!(world.do_donut(line));
+        assert!(!world.do_donut(line));
+
+        // The ring rises to the pool around it and takes its flat, the hole
+        // lowers to the same height
+        for _ in 0..33 {
+            world.tick();
+        }
+        assert_eq!(world.level.sectors[1].floor_height, Fixed::from_int(16));
+        assert_eq!(world.level.sectors[1].floor_pic, *b"NUKAGE1\0");
+        assert_eq!(world.level.sectors[2].floor_height, Fixed::from_int(16));
+        assert_eq!(world.level.sectors[2].special_data, None);
+    }
+}
@@ -0,0 +1,482 @@
+// Port of p_switch.c. Switch textures, buttons that pop back out and the
+// table of specials activated by using a line
+use crate::play::ceiling::CeilingType;
+use crate::play::doors::DoorType;
+use crate::play::floor::{FloorType, StairType};
+use crate::play::level::{names_equal, ML_SECRET};
+use crate::play::mobj::MobjId;
+use crate::play::plats::PlatType;
+use crate::play::world::{LevelExit, World};
+use crate::sound::sfx::{SfxNum, SFX_SWTCHN, SFX_SWTCHX};
+
+pub const MAXBUTTONS: usize = 16;
+// 1 second, in ticks.
+pub const BUTTONTIME: i32 = 35;
+
+// The off and on texture of every switch, from every version of the game
+const SWITCH_LIST: [(&[u8], &[u8]); 40] = [
+    // Doom shareware episode 1 switches
+    (b"SW1BRCOM", b"SW2BRCOM"),
+    (b"SW1BRN1", b"SW2BRN1"),
+    (b"SW1BRN2", b"SW2BRN2"),
+    (b"SW1BRNGN", b"SW2BRNGN"),
+    (b"SW1BROWN", b"SW2BROWN"),
+    (b"SW1COMM", b"SW2COMM"),
+    (b"SW1COMP", b"SW2COMP"),
+    (b"SW1DIRT", b"SW2DIRT"),
+    (b"SW1EXIT", b"SW2EXIT"),
+    (b"SW1GRAY", b"SW2GRAY"),
+    (b"SW1GRAY1", b"SW2GRAY1"),
+    (b"SW1METAL", b"SW2METAL"),
+    (b"SW1PIPE", b"SW2PIPE"),
+    (b"SW1SLAD", b"SW2SLAD"),
+    (b"SW1STARG", b"SW2STARG"),
+    (b"SW1STON1", b"SW2STON1"),
+    (b"SW1STON2", b"SW2STON2"),
+    (b"SW1STONE", b"SW2STONE"),
+    (b"SW1STRTN", b"SW2STRTN"),
+    // Doom registered episodes 2&3 switches
+    (b"SW1BLUE", b"SW2BLUE"),
+    (b"SW1CMT", b"SW2CMT"),
+    (b"SW1GARG", b"SW2GARG"),
+    (b"SW1GSTON", b"SW2GSTON"),
+    (b"SW1HOT", b"SW2HOT"),
+    (b"SW1LION", b"SW2LION"),
+    (b"SW1SATYR", b"SW2SATYR"),
+    (b"SW1SKIN", b"SW2SKIN"),
+    (b"SW1VINE", b"SW2VINE"),
+    (b"SW1WOOD", b"SW2WOOD"),
+    // Doom II switches
+    (b"SW1PANEL", b"SW2PANEL"),
+    (b"SW1ROCK", b"SW2ROCK"),
+    (b"SW1MET2", b"SW2MET2"),
+    (b"SW1WDMET", b"SW2WDMET"),
+    (b"SW1BRIK", b"SW2BRIK"),
+    (b"SW1MOD1", b"SW2MOD1"),
+    (b"SW1ZIM", b"SW2ZIM"),
+    (b"SW1STON6", b"SW2STON6"),
+    (b"SW1TEK", b"SW2TEK"),
+    (b"SW1MARB", b"SW2MARB"),
+    (b"SW1SKULL", b"SW2SKULL"),
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ButtonWhere {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+// button_t
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Button {
+    pub line: usize,
+    pub where_: ButtonWhere,
+    pub texture: [u8; 8],
+    pub timer: i32,
+    pub sound_sector: Option<usize>,
+}
+
+fn switch_name(value: &[u8]) -> [u8; 8] {
+    let mut name: [u8; 8] = [0; 8];
+    name[..value.len()].copy_from_slice(value);
+    name
+}
+
+impl World {
+    // Plays a sound from where a button was pressed
+    pub fn start_button_sound(&mut self, index: usize, sfx: SfxNum) {
+        match self.buttons[index].sound_sector {
+            Some(sector) => self.start_sector_sound(sector, sfx),
+            None => self.start_sound(None, sfx),
+        }
+    }
+
+    // P_StartButton. Start a button counting down till it turns off.
+    fn start_button(&mut self, line: usize, where_: ButtonWhere, texture: [u8; 8], time: i32) {
+        // See if button is already pressed
+        if self
+            .buttons
+            .iter()
+            .any(|button| button.timer != 0 && button.line == line)
+        {
+            return;
+        }
+
+        let sound_sector: Option<usize> = self.level.lines[line].front_sector;
+        match self.buttons.iter_mut().find(|button| button.timer == 0) {
+            Some(button) => {
+                *button = Button {
+                    line,
+                    where_,
+                    texture,
+                    timer: time,
+                    sound_sector,
+                };
+            }
+            None => panic!("P_StartButton: no button slots left!"),
+        }
+    }
+
+    // P_ChangeSwitchTexture. Function that changes wall texture. Tell it
+    // if switch is ok to use again. The sound comes from the first button
+    // slot like the original
+    pub fn change_switch_texture(&mut self, line: usize, use_again: bool) {
+        if !use_again {
+            self.level.lines[line].special = 0;
+        }
+
+        let side: usize = self.level.lines[line].side_num[0].unwrap();
+        let tex_top: [u8; 8] = self.level.sides[side].top_texture;
+        let tex_mid: [u8; 8] = self.level.sides[side].mid_texture;
+        let tex_bot: [u8; 8] = self.level.sides[side].bottom_texture;
+
+        // EXIT SWITCH?
+        let sound: SfxNum = if self.level.lines[line].special == 11 {
+            SFX_SWTCHX
+        } else {
+            SFX_SWTCHN
+        };
+
+        for (off, on) in SWITCH_LIST.iter() {
+            for (texture, other) in [(off, on), (on, off)] {
+                let texture: [u8; 8] = switch_name(texture);
+                let where_: ButtonWhere = if names_equal(&texture, &tex_top) {
+                    ButtonWhere::Top
+                } else if names_equal(&texture, &tex_mid) {
+                    ButtonWhere::Middle
+                } else if names_equal(&texture, &tex_bot) {
+                    ButtonWhere::Bottom
+                } else {
+                    continue;
+                };
+
+                self.start_button_sound(0, sound);
+                let other: [u8; 8] = switch_name(other);
+                match where_ {
+                    ButtonWhere::Top => self.level.sides[side].top_texture = other,
+                    ButtonWhere::Middle => self.level.sides[side].mid_texture = other,
+                    ButtonWhere::Bottom => self.level.sides[side].bottom_texture = other,
+                }
+
+                if use_again {
+                    self.start_button(line, where_, texture, BUTTONTIME);
+                }
+                return;
+            }
+        }
+    }
+
+    // P_UseSpecialLine. Called when a thing uses a special line. Only the
+    // front sides of lines are usable.
+    pub fn use_special_line(&mut self, id: MobjId, line: usize, side: usize) -> bool {
+        let special: i32 = self.level.lines[line].special;
+
+        // Err...
+        // Use the back sides of VERY SPECIAL lines...
+        if side != 0 {
+            match special {
+                // Sliding door open&close
+                // UNUSED?
+                124 => {}
+                _ => return false,
+            }
+        }
+
+        // Switches that other things can activate.
+        let player: Option<usize> = self.thinkers.mobj(id).player;
+        if player.is_none() {
+            // never open secret doors
+            if self.level.lines[line].flags & ML_SECRET != 0 {
+                return false;
+            }
+
+            match special {
+                // MANUAL DOOR RAISE | MANUAL BLUE | MANUAL RED | MANUAL YELLOW
+                1 | 32 | 33 | 34 => {}
+                _ => return false,
+            }
+        }
+
+        // do something
+        let (activated, use_again): (bool, bool) = match special {
+            // MANUALS
+            1 | 26 | 27 | 28 | 31 | 32 | 33 | 34 | 117 | 118 => {
+                self.vertical_door(line, id);
+                (false, false)
+            }
+
+            // SWITCHES
+            // Build Stairs
+            7 => (self.build_stairs(line, StairType::Build8), false),
+            // Change Donut
+            9 => (self.do_donut(line), false),
+            // Exit level
+            11 => {
+                self.exit_level(LevelExit::Normal);
+                (true, false)
+            }
+            // Raise Floor 32 and change texture
+            14 => (self.do_plat(line, PlatType::RaiseAndChange, 32), false),
+            // Raise Floor 24 and change texture
+            15 => (self.do_plat(line, PlatType::RaiseAndChange, 24), false),
+            // Raise Floor to next highest floor
+            18 => (self.do_floor(line, FloorType::RaiseFloorToNearest), false),
+            // Raise Plat next highest floor and change texture
+            20 => (
+                self.do_plat(line, PlatType::RaiseToNearestAndChange, 0),
+                false,
+            ),
+            // PlatDownWaitUpStay
+            21 => (self.do_plat(line, PlatType::DownWaitUpStay, 0), false),
+            // Lower Floor to Lowest
+            23 => (self.do_floor(line, FloorType::LowerFloorToLowest), false),
+            // Raise Door
+            29 => (self.do_door(line, DoorType::Normal), false),
+            // Lower Ceiling to Floor
+            41 => (self.do_ceiling(line, CeilingType::LowerToFloor), false),
+            // Turbo Lower Floor
+            71 => (self.do_floor(line, FloorType::TurboLower), false),
+            // Ceiling Crush And Raise
+            49 => (self.do_ceiling(line, CeilingType::CrushAndRaise), false),
+            // Close Door
+            50 => (self.do_door(line, DoorType::Close), false),
+            // Secret EXIT
+            51 => {
+                self.exit_level(LevelExit::Secret);
+                (true, false)
+            }
+            // Raise Floor Crush
+            55 => (self.do_floor(line, FloorType::RaiseFloorCrush), false),
+            // Raise Floor
+            101 => (self.do_floor(line, FloorType::RaiseFloor), false),
+            // Lower Floor to Surrounding floor height
+            102 => (self.do_floor(line, FloorType::LowerFloor), false),
+            // Open Door
+            103 => (self.do_door(line, DoorType::Open), false),
+            // Blazing Door Raise (faster than TURBO!)
+            111 => (self.do_door(line, DoorType::BlazeRaise), false),
+            // Blazing Door Open (faster than TURBO!)
+            112 => (self.do_door(line, DoorType::BlazeOpen), false),
+            // Blazing Door Close (faster than TURBO!)
+            113 => (self.do_door(line, DoorType::BlazeClose), false),
+            // Blazing PlatDownWaitUpStay
+            122 => (self.do_plat(line, PlatType::BlazeDwus, 0), false),
+            // Build Stairs Turbo 16
+            127 => (self.build_stairs(line, StairType::Turbo16), false),
+            // Raise Floor Turbo
+            131 => (self.do_floor(line, FloorType::RaiseFloorTurbo), false),
+            // BlzOpenDoor BLUE | BlzOpenDoor RED | BlzOpenDoor YELLOW
+            133 | 135 | 137 => (
+                self.do_locked_door(line, DoorType::BlazeOpen, player),
+                false,
+            ),
+            // Raise Floor 512
+            140 => (self.do_floor(line, FloorType::RaiseFloor512), false),
+
+            // BUTTONS
+            // Close Door
+            42 => (self.do_door(line, DoorType::Close), true),
+            // Lower Ceiling to Floor
+            43 => (self.do_ceiling(line, CeilingType::LowerToFloor), true),
+            // Lower Floor to Surrounding floor height
+            45 => (self.do_floor(line, FloorType::LowerFloor), true),
+            // Lower Floor to Lowest
+            60 => (self.do_floor(line, FloorType::LowerFloorToLowest), true),
+            // Open Door
+            61 => (self.do_door(line, DoorType::Open), true),
+            // PlatDownWaitUpStay
+            62 => (self.do_plat(line, PlatType::DownWaitUpStay, 1), true),
+            // Raise Door
+            63 => (self.do_door(line, DoorType::Normal), true),
+            // Raise Floor to ceiling
+            64 => (self.do_floor(line, FloorType::RaiseFloor), true),
+            // Raise Floor 24 and change texture
+            66 => (self.do_plat(line, PlatType::RaiseAndChange, 24), true),
+            // Raise Floor 32 and change texture
+            67 => (self.do_plat(line, PlatType::RaiseAndChange, 32), true),
+            // Raise Floor Crush
+            65 => (self.do_floor(line, FloorType::RaiseFloorCrush), true),
+            // Raise Plat to next highest floor and change texture
+            68 => (
+                self.do_plat(line, PlatType::RaiseToNearestAndChange, 0),
+                true,
+            ),
+            // Raise Floor to next highest floor
+            69 => (self.do_floor(line, FloorType::RaiseFloorToNearest), true),
+            // Turbo Lower Floor
+            70 => (self.do_floor(line, FloorType::TurboLower), true),
+            // Blazing Door Raise (faster than TURBO!)
+            114 => (self.do_door(line, DoorType::BlazeRaise), true),
+            // Blazing Door Open (faster than TURBO!)
+            115 => (self.do_door(line, DoorType::BlazeOpen), true),
+            // Blazing Door Close (faster than TURBO!)
+            116 => (self.do_door(line, DoorType::BlazeClose), true),
+            // Blazing PlatDownWaitUpStay
+            123 => (self.do_plat(line, PlatType::BlazeDwus, 0), true),
+            // Raise Floor Turbo
+            132 => (self.do_floor(line, FloorType::RaiseFloorTurbo), true),
+            // BlzOpenDoor BLUE | BlzOpenDoor RED | BlzOpenDoor YELLOW
+            99 | 134 | 136 => (self.do_locked_door(line, DoorType::BlazeOpen, player), true),
+            // Light Turn On
+            138 => {
+                self.light_turn_on(line, 255);
+                (true, true)
+            }
+            // Light Turn Off
+            139 => {
+                self.light_turn_on(line, 35);
+                (true, true)
+            }
+            _ => (false, false),
+        };
+
+        if activated {
+            self.change_switch_texture(line, use_again);
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Angle, Fixed, ANG180};
+    use crate::info::mobjinfo::{MT_PLAYER, MT_TROOP};
+    use crate::play::mobj::{MobjId, ONFLOORZ};
+    use crate::play::switch::{Button, ButtonWhere, BUTTONTIME};
+    use crate::play::test_map::{tick, TestMap, TestRoom};
+    use crate::play::world::{SoundOrigin, World};
+    use crate::sound::sfx::{SFX_NOWAY, SFX_SWTCHN};
+
+    // A door tagged 1 between two rooms, with the given special on the
+    // right wall of the last room, which has a switch texture
+    fn switch_world(special: i16) -> (World, usize) {
+        let mut world: World = TestMap::new()
+            .room(256, 0, 128)
+            .room_with(TestRoom {
+                width: 64,
+                floor_height: 0,
+                ceiling_height: 0,
+                light_level: 160,
+                tag: 1,
+                ..Default::default()
+            })
+            .room(256, 0, 128)
+            .line_special(2, special, 1)
+            .to_world();
+        let line: usize = world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.special != 0)
+            .unwrap();
+        let side: usize = world.level.lines[line].side_num[0].unwrap();
+        world.level.sides[side].mid_texture = *b"SW1COMP\0";
+
+        (world, line)
+    }
+
+    fn spawn_player(world: &mut World, x: i32, angle: Angle) -> MobjId {
+        let id: MobjId = world.spawn_mobj(
+            Fixed::from_int(x),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_PLAYER,
+        );
+        world.thinkers.mobj_mut(id).player = Some(0);
+        world.thinkers.mobj_mut(id).angle = angle;
+        world.players[0].mo = Some(id);
+        id
+    }
+
+    fn mid_texture(world: &World, line: usize) -> [u8; 8] {
+        world.level.sides[world.level.lines[line].side_num[0].unwrap()].mid_texture
+    }
+
+    #[test]
+    fn test_change_switch_texture_once() {
+        let (mut world, line): (World, usize) = switch_world(103);
+
+        world.change_switch_texture(line, false);
+        assert_eq!(world.level.lines[line].special, 0);
+        assert_eq!(&mid_texture(&world, line), b"SW2COMP\0");
+        assert_eq!(world.sound_events.last().unwrap().sfx, SFX_SWTCHN);
+        assert!(world.buttons.iter().all(|button| button.timer == 0));
+
+        // Flipping it again turns it back on
+        world.change_switch_texture(line, false);
+        assert_eq!(&mid_texture(&world, line), b"SW1COMP\0");
+    }
+
+    #[test]
+    fn test_change_switch_texture_button_pops_back_out() {
+        let (mut world, line): (World, usize) = switch_world(61);
+
+        world.change_switch_texture(line, true);
+        assert_eq!(world.level.lines[line].special, 61);
+        assert_eq!(&mid_texture(&world, line), b"SW2COMP\0");
+        assert_eq!(world.buttons[0].where_, ButtonWhere::Middle);
+        assert_eq!(world.buttons[0].timer, BUTTONTIME);
+
+        tick(&mut world, BUTTONTIME - 1);
+        assert_eq!(&mid_texture(&world, line), b"SW2COMP\0");
+        tick(&mut world, 1);
+        assert_eq!(&mid_texture(&world, line), b"SW1COMP\0");
+        assert_eq!(world.buttons[0], Button::default());
+        assert_eq!(world.sound_events.last().unwrap().sfx, SFX_SWTCHN);
+        assert_eq!(
+            world.sound_events.last().unwrap().origin,
+            Some(SoundOrigin::Sector(2))
+        );
+    }
+
+    #[test]
+    fn test_use_special_line_switch_opens_door() {
+        let (mut world, line): (World, usize) = switch_world(103);
+        let player: MobjId = spawn_player(&mut world, 540, Angle(0));
+
+        // Back sides and monsters can't use switches
+        assert!(!world.use_special_line(player, line, 1));
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(500),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+        assert!(!world.use_special_line(troop, line, 0));
+        assert_eq!(world.level.sectors[1].special_data, None);
+
+        assert!(world.use_special_line(player, line, 0));
+        assert!(world.level.sectors[1].special_data.is_some());
+        assert_eq!(world.level.lines[line].special, 0);
+        assert_eq!(&mid_texture(&world, line), b"SW2COMP\0");
+    }
+
+    #[test]
+    fn test_use_lines_uses_special_lines_in_range() {
+        let (mut world, line): (World, usize) = switch_world(63);
+
+        // Out of range
+        spawn_player(&mut world, 500, Angle(0));
+        world.use_lines(0);
+        assert_eq!(world.level.sectors[1].special_data, None);
+
+        let (mut world, line_again): (World, usize) = switch_world(63);
+        assert_eq!(line, line_again);
+        spawn_player(&mut world, 540, Angle(0));
+        world.use_lines(0);
+        assert!(world.level.sectors[1].special_data.is_some());
+        assert_eq!(world.buttons[0].timer, BUTTONTIME);
+    }
+
+    #[test]
+    fn test_use_lines_grunts_at_walls() {
+        let (mut world, _line): (World, usize) = switch_world(63);
+
+        spawn_player(&mut world, 40, Angle(ANG180));
+        world.use_lines(0);
+        assert_eq!(world.sound_events.last().unwrap().sfx, SFX_NOWAY);
+    }
+}
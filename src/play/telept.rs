@@ -0,0 +1,253 @@
+// Port of p_telept.c
+use crate::fixed::{Angle, Fixed};
+use crate::info::mobjinfo::{MT_TELEPORTMAN, MT_TFOG};
+use crate::play::mobj::{Mobj, MobjId, MF_MISSILE};
+use crate::play::player::Player;
+use crate::play::world::World;
+use crate::sound::sfx::SFX_TELEPT;
+
+impl World {
+    // EV_Teleport. Moves the thing to the teleport destination in the
+    // first sector tagged by the line that has one
+    pub fn teleport(&mut self, line: usize, side: usize, id: MobjId) -> bool {
+        // Don't teleport missiles
+        if self.thinkers.mobj(id).flags & MF_MISSILE != 0 {
+            return false;
+        }
+
+        // Don't teleport if hit back of line, so you can get out of
+        // teleporter.
+        if side == 1 {
+            return false;
+        }
+
+        for sector in self.level.tagged_sectors(line) {
+            let destination: Option<MobjId> = self
+                .thinkers
+                .mobjs()
+                .find(|(_id, m)| {
+                    m.mobj_type == MT_TELEPORTMAN
+                        && self.level.subsectors[m.subsector].sector == sector
+                })
+                .map(|(id, _m)| id);
+            let destination: MobjId = match destination {
+                Some(destination) => destination,
+                None => continue,
+            };
+
+            let thing: &Mobj = self.thinkers.mobj(id);
+            let (old_x, old_y, old_z): (Fixed, Fixed, Fixed) = (thing.x, thing.y, thing.z);
+            let m: &Mobj = self.thinkers.mobj(destination);
+            let (m_x, m_y, m_angle): (Fixed, Fixed, Angle) = (m.x, m.y, m.angle);
+
+            if !self.teleport_move(id, m_x, m_y) {
+                return false;
+            }
+
+            let thing: &mut Mobj = self.thinkers.mobj_mut(id);
+            thing.z = thing.floor_z;
+            let (z, player): (Fixed, Option<usize>) = (thing.z, thing.player);
+            if let Some(player) = player {
+                let player: &mut Player = &mut self.players[player];
+                player.view_z = z + player.view_height;
+            }
+
+            // Spawn teleport fog at source and destination
+            let fog: MobjId = self.spawn_mobj(old_x, old_y, old_z, MT_TFOG);
+            self.start_sound(Some(fog), SFX_TELEPT);
+            let fog: MobjId = self.spawn_mobj(
+                m_x + Fixed(20 * m_angle.cos().0),
+                m_y + Fixed(20 * m_angle.sin().0),
+                z,
+                MT_TFOG,
+            );
+
+            // Emit sound, where?
+            self.start_sound(Some(fog), SFX_TELEPT);
+
+            let thing: &mut Mobj = self.thinkers.mobj_mut(id);
+
+            // Don't move for a bit
+            if player.is_some() {
+                thing.reaction_time = 18;
+            }
+
+            thing.angle = m_angle;
+            thing.mom_x = Fixed::ZERO;
+            thing.mom_y = Fixed::ZERO;
+            thing.mom_z = Fixed::ZERO;
+            return true;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Angle, Fixed, ANG90};
+    use crate::info::mobjinfo::{MT_PLAYER, MT_ROCKET, MT_TFOG, MT_TROOP};
+    use crate::play::mobj::{Mobj, MobjId, ONFLOORZ};
+    use crate::play::test_map::{TestMap, TestRoom};
+    use crate::play::world::{World, WorldSettings};
+    use crate::sound::sfx::SFX_TELEPT;
+
+    // Walking left over the line between the rooms teleports to the
+    // destination in the middle of the first room
+    fn create_world(settings: WorldSettings) -> World {
+        let test_map: TestMap = TestMap::new()
+            .room_with(TestRoom {
+                width: 256,
+                floor_height: 16,
+                ceiling_height: 128,
+                light_level: 160,
+                tag: 2,
+                ..Default::default()
+            })
+            .room(256, 0, 128)
+            .thing(128, 128, 90, 14, 7)
+            .line_special(0, 97, 2);
+        test_map.to_world_with(settings)
+    }
+
+    fn spawn_player(world: &mut World) -> MobjId {
+        let id: MobjId = world.spawn_mobj(
+            Fixed::from_int(270),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_PLAYER,
+        );
+        world.thinkers.mobj_mut(id).player = Some(0);
+        world.players[0].mo = Some(id);
+        world.players[0].view_height = Fixed::from_int(41);
+        id
+    }
+
+    fn teleport_line(world: &World) -> usize {
+        world
+            .level
+            .lines
+            .iter()
+            .position(|line| line.special == 97)
+            .unwrap()
+    }
+
+    fn count(world: &World, mobj_type: usize) -> usize {
+        world
+            .thinkers
+            .mobjs()
+            .filter(|(_id, mobj)| mobj.mobj_type == mobj_type)
+            .count()
+    }
+
+    #[test]
+    fn test_teleport_moves_player_to_destination() {
+        let mut world: World = create_world(WorldSettings::default());
+        let player: MobjId = spawn_player(&mut world);
+        world.thinkers.mobj_mut(player).mom_x = Fixed::from_int(-8);
+        let line: usize = teleport_line(&world);
+
+        assert!(world.teleport(line, 0, player));
+
+        let mo: &Mobj = world.thinkers.mobj(player);
+        assert_eq!(
+            (mo.x, mo.y, mo.z),
+            (
+                Fixed::from_int(128),
+                Fixed::from_int(128),
+                Fixed::from_int(16)
+            )
+        );
+        assert_eq!(mo.floor_z, Fixed::from_int(16));
+        assert_eq!(mo.angle, Angle(ANG90));
+        assert_eq!(mo.mom_x, Fixed::ZERO);
+        assert_eq!(mo.reaction_time, 18);
+        assert_eq!(world.players[0].view_z, Fixed::from_int(57));
+
+        // Fog at both ends
+        assert_eq!(count(&world, MT_TFOG), 2);
+        let sounds: usize = world
+            .sound_events
+            .iter()
+            .filter(|sound| sound.sfx == SFX_TELEPT)
+            .count();
+        assert_eq!(sounds, 2);
+    }
+
+    #[test]
+    fn test_teleport_skips_missiles_and_back_sides() {
+        let mut world: World = create_world(WorldSettings::default());
+        let line: usize = teleport_line(&world);
+        let player: MobjId = spawn_player(&mut world);
+        let rocket: MobjId = world.spawn_mobj(
+            Fixed::from_int(270),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_ROCKET,
+        );
+
+        assert!(!world.teleport(line, 1, player));
+        assert!(!world.teleport(line, 0, rocket));
+        assert_eq!(world.thinkers.mobj(player).x, Fixed::from_int(270));
+        assert_eq!(count(&world, MT_TFOG), 0);
+    }
+
+    #[test]
+    fn test_walking_over_teleport_line() {
+        let mut world: World = create_world(WorldSettings::default());
+        let player: MobjId = spawn_player(&mut world);
+
+        assert!(world.try_move(player, Fixed::from_int(250), Fixed::from_int(128)));
+        assert_eq!(world.thinkers.mobj(player).x, Fixed::from_int(128));
+
+        // Crossing it from the back side doesn't teleport
+        assert!(world.try_move(player, Fixed::from_int(262), Fixed::from_int(128)));
+        assert_eq!(world.thinkers.mobj(player).x, Fixed::from_int(262));
+    }
+
+    #[test]
+    fn test_teleport_telefrags_unless_monster() {
+        let mut world: World = create_world(WorldSettings::default());
+        let line: usize = teleport_line(&world);
+        let blocker: MobjId = world.spawn_mobj(
+            Fixed::from_int(128),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(300),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+
+        // Monsters only telefrag on MAP30
+        assert!(!world.teleport(line, 0, troop));
+        assert_eq!(world.thinkers.mobj(blocker).health, 60);
+
+        let player: MobjId = spawn_player(&mut world);
+        assert!(world.teleport(line, 0, player));
+        assert!(world.thinkers.mobj(blocker).health <= 0);
+
+        let mut world: World = create_world(WorldSettings {
+            map: 30,
+            ..Default::default()
+        });
+        let line: usize = teleport_line(&world);
+        let blocker: MobjId = world.spawn_mobj(
+            Fixed::from_int(128),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+        let troop: MobjId = world.spawn_mobj(
+            Fixed::from_int(300),
+            Fixed::from_int(128),
+            ONFLOORZ,
+            MT_TROOP,
+        );
+        assert!(world.teleport(line, 0, troop));
+        assert!(world.thinkers.mobj(blocker).health <= 0);
+    }
+}
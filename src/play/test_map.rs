@@ -0,0 +1,328 @@
+// Builds small maps for tests since there's no IWAD to load real ones from.
+// Rooms are rectangles placed left to right along the x axis, each one its
+// own sector and subsector, sharing a two sided line with its neighbours
+use crate::fixed::{point_to_angle, Angle, Fixed};
+use crate::play::level::{
+    MapLumps, MapThing, MAPTHING_SIZE, ML_BLOCKING, ML_TWOSIDED, NF_SUBSECTOR,
+};
+use crate::play::world::{World, WorldSettings};
+use crate::wad::format::MAP_LUMP_NAMES;
+
+pub const ROOM_HEIGHT: i16 = 256;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TestRoom {
+    pub width: i16,
+    pub floor_height: i16,
+    pub ceiling_height: i16,
+    pub light_level: i16,
+    pub special: i16,
+    pub tag: i16,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct TestLine {
+    v1: usize,
+    v2: usize,
+    flags: i16,
+    special: i16,
+    tag: i16,
+    sides: [Option<usize>; 2],
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TestMap {
+    pub rooms: Vec<TestRoom>,
+    pub things: Vec<MapThing>,
+    // (room, special, tag) for the line on the right side of a room
+    pub line_specials: Vec<(usize, i16, i16)>,
+    // Moves the top of the last room's right wall along x so the wall
+    // isn't vertical
+    pub right_wall_slope: i16,
+}
+
+impl TestMap {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn room(mut self, width: i16, floor_height: i16, ceiling_height: i16) -> Self {
+        self.rooms.push(TestRoom {
+            width,
+            floor_height,
+            ceiling_height,
+            light_level: 160,
+            ..Default::default()
+        });
+        self
+    }
+
+    pub fn room_with(mut self, room: TestRoom) -> Self {
+        self.rooms.push(room);
+        self
+    }
+
+    pub fn thing(mut self, x: i16, y: i16, angle: i16, thing_type: i16, options: i16) -> Self {
+        self.things.push(MapThing {
+            x,
+            y,
+            angle,
+            thing_type,
+            options,
+        });
+        self
+    }
+
+    // Sets the special and tag of the line between a room and the next
+    // one, or the room's right outside wall for the last room
+    pub fn line_special(mut self, room: usize, special: i16, tag: i16) -> Self {
+        self.line_specials.push((room, special, tag));
+        self
+    }
+
+    pub fn slope_right_wall(mut self, offset: i16) -> Self {
+        self.right_wall_slope = offset;
+        self
+    }
+
+    // X of the left edge of a room
+    pub fn room_x(&self, room: usize) -> i16 {
+        self.rooms[..room].iter().map(|room| room.width).sum()
+    }
+
+    pub fn to_world(&self) -> World {
+        self.to_world_with(WorldSettings::default())
+    }
+
+    pub fn to_world_with(&self, settings: WorldSettings) -> World {
+        World::new(settings, &self.to_map_lumps()).unwrap()
+    }
+
+    pub fn to_map_lumps(&self) -> MapLumps {
+        let num_rooms: usize = self.rooms.len();
+        let xs: Vec<i16> = (0..=num_rooms).map(|room| self.room_x(room)).collect();
+        let bottom = |index: usize| index * 2;
+        let top = |index: usize| index * 2 + 1;
+
+        let mut vertexes: Vec<(i16, i16)> = Vec::new();
+        for x in xs.iter() {
+            vertexes.push((*x, 0));
+            vertexes.push((*x, ROOM_HEIGHT));
+        }
+        vertexes[top(num_rooms)].0 += self.right_wall_slope;
+
+        // Side sectors, then the lines using them
+        let mut sides: Vec<(usize, bool)> = Vec::new();
+        let mut lines: Vec<TestLine> = Vec::new();
+        let mut add_wall = |v1: usize, v2: usize, room: usize| -> usize {
+            sides.push((room, true));
+            lines.push(TestLine {
+                v1,
+                v2,
+                flags: ML_BLOCKING as i16,
+                sides: [Some(sides.len() - 1), None],
+                ..Default::default()
+            });
+            lines.len() - 1
+        };
+
+        let mut room_walls: Vec<(usize, usize)> = Vec::new();
+        for room in 0..num_rooms {
+            let bottom_wall: usize = add_wall(bottom(room + 1), bottom(room), room);
+            let top_wall: usize = add_wall(top(room), top(room + 1), room);
+            room_walls.push((bottom_wall, top_wall));
+        }
+        let left_wall: usize = add_wall(bottom(0), top(0), 0);
+        let right_wall: usize = add_wall(top(num_rooms), bottom(num_rooms), num_rooms - 1);
+
+        // Line k is between room k and k + 1, the front is the right room
+        let mut shared_lines: Vec<usize> = Vec::new();
+        for room in 1..num_rooms {
+            sides.push((room, false));
+            sides.push((room - 1, false));
+            lines.push(TestLine {
+                v1: bottom(room),
+                v2: top(room),
+                flags: ML_TWOSIDED as i16,
+                sides: [Some(sides.len() - 2), Some(sides.len() - 1)],
+                ..Default::default()
+            });
+            shared_lines.push(lines.len() - 1);
+        }
+
+        for (room, special, tag) in self.line_specials.iter() {
+            let line: usize = shared_lines.get(*room).copied().unwrap_or(right_wall);
+            lines[line].special = *special;
+            lines[line].tag = *tag;
+        }
+
+        // (v1, v2, line, side)
+        let mut segs: Vec<(usize, usize, usize, i16)> = Vec::new();
+        for room in 0..num_rooms {
+            let (bottom_wall, top_wall) = room_walls[room];
+            segs.push((bottom(room + 1), bottom(room), bottom_wall, 0));
+            segs.push((top(room), top(room + 1), top_wall, 0));
+            if room == 0 {
+                segs.push((bottom(0), top(0), left_wall, 0));
+            } else {
+                segs.push((bottom(room), top(room), shared_lines[room - 1], 0));
+            }
+            if room == num_rooms - 1 {
+                segs.push((top(room + 1), bottom(room + 1), right_wall, 0));
+            } else {
+                segs.push((top(room + 1), bottom(room + 1), shared_lines[room], 1));
+            }
+        }
+
+        let mut map_lumps: MapLumps = MapLumps::default();
+
+        for thing in self.things.iter() {
+            map_lumps.things.extend_from_slice(&thing.to_lump_data());
+        }
+        debug_assert_eq!(map_lumps.things.len(), self.things.len() * MAPTHING_SIZE);
+
+        for line in lines.iter() {
+            let side = |side: Option<usize>| side.map(|side| side as i16).unwrap_or(-1);
+            push_shorts(
+                &mut map_lumps.linedefs,
+                &[
+                    line.v1 as i16,
+                    line.v2 as i16,
+                    line.flags,
+                    line.special,
+                    line.tag,
+                    side(line.sides[0]),
+                    side(line.sides[1]),
+                ],
+            );
+        }
+
+        for (sector, one_sided) in sides.iter() {
+            push_shorts(&mut map_lumps.sidedefs, &[0, 0]);
+            map_lumps.sidedefs.extend_from_slice(&name(b"-"));
+            map_lumps.sidedefs.extend_from_slice(&name(b"-"));
+            map_lumps.sidedefs.extend_from_slice(&name(if *one_sided {
+                b"STARTAN3"
+            } else {
+                b"-"
+            }));
+            push_shorts(&mut map_lumps.sidedefs, &[*sector as i16]);
+        }
+
+        for (x, y) in vertexes.iter() {
+            push_shorts(&mut map_lumps.vertexes, &[*x, *y]);
+        }
+
+        for (v1, v2, line, side) in segs.iter() {
+            let (x1, y1) = vertexes[*v1];
+            let (x2, y2) = vertexes[*v2];
+            let angle: Angle = point_to_angle(
+                Fixed::from_int(i32::from(x1)),
+                Fixed::from_int(i32::from(y1)),
+                Fixed::from_int(i32::from(x2)),
+                Fixed::from_int(i32::from(y2)),
+            );
+            push_shorts(
+                &mut map_lumps.segs,
+                &[
+                    *v1 as i16,
+                    *v2 as i16,
+                    (angle.0 >> 16) as i16,
+                    *line as i16,
+                    *side,
+                    0,
+                ],
+            );
+        }
+
+        for room in 0..num_rooms {
+            push_shorts(&mut map_lumps.ssectors, &[4, (room * 4) as i16]);
+        }
+
+        // Node j splits room j + 1 off from the rooms to its left, the last
+        // node is the root
+        for node in 0..num_rooms.saturating_sub(1) {
+            let left_child: u16 = if node == 0 {
+                NF_SUBSECTOR
+            } else {
+                (node - 1) as u16
+            };
+            push_shorts(
+                &mut map_lumps.nodes,
+                &[
+                    xs[node + 1],
+                    0,
+                    0,
+                    ROOM_HEIGHT,
+                    ROOM_HEIGHT,
+                    0,
+                    xs[node + 1],
+                    xs[node + 2],
+                    ROOM_HEIGHT,
+                    0,
+                    0,
+                    xs[node + 1],
+                    (NF_SUBSECTOR | (node + 1) as u16) as i16,
+                    left_child as i16,
+                ],
+            );
+        }
+
+        for room in self.rooms.iter() {
+            push_shorts(
+                &mut map_lumps.sectors,
+                &[room.floor_height, room.ceiling_height],
+            );
+            map_lumps.sectors.extend_from_slice(&name(b"FLOOR4_8"));
+            map_lumps.sectors.extend_from_slice(&name(b"CEIL3_5"));
+            push_shorts(
+                &mut map_lumps.sectors,
+                &[room.light_level, room.special, room.tag],
+            );
+        }
+
+        map_lumps.reject = vec![0; (num_rooms * num_rooms).div_ceil(8)];
+
+        map_lumps
+    }
+}
+
+// The map marker and the map's lumps, in the order a WAD stores them
+pub fn map_wad_lumps(map_name: &str, map_lumps: &MapLumps) -> Vec<(String, Vec<u8>)> {
+    let data: [&Vec<u8>; 10] = [
+        &map_lumps.things,
+        &map_lumps.linedefs,
+        &map_lumps.sidedefs,
+        &map_lumps.vertexes,
+        &map_lumps.segs,
+        &map_lumps.ssectors,
+        &map_lumps.nodes,
+        &map_lumps.sectors,
+        &map_lumps.reject,
+        &map_lumps.blockmap,
+    ];
+
+    let mut lumps: Vec<(String, Vec<u8>)> = vec![(map_name.to_owned(), Vec::new())];
+    for (lump_name, data) in MAP_LUMP_NAMES.iter().zip(data) {
+        lumps.push((lump_name.to_string(), data.clone()));
+    }
+    lumps
+}
+
+pub fn tick(world: &mut World, tics: i32) {
+    for _ in 0..tics {
+        world.tick();
+    }
+}
+
+fn push_shorts(data: &mut Vec<u8>, values: &[i16]) {
+    for value in values {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn name(value: &[u8]) -> [u8; 8] {
+    let mut name: [u8; 8] = [0; 8];
+    name[..value.len()].copy_from_slice(value);
+    name
+}
@@ -0,0 +1,277 @@
+// Port of the thinker list from p_tick.c. The original kept a doubly linked
+// list of structs that all started with a thinker_t. Here the thinkers live
+// in slots that get reused, and ids carry a generation so references kept
+// after a thinker is freed(mobj targets, etc) stop matching instead of
+// pointing at whatever took its place
+use crate::play::ceiling::Ceiling;
+use crate::play::doors::VerticalDoor;
+use crate::play::floor::FloorMove;
+use crate::play::lights::{FireFlicker, Glow, LightFlash, Strobe};
+use crate::play::mobj::Mobj;
+use crate::play::plats::Plat;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThinkerId {
+    index: u32,
+    generation: u32,
+}
+
+impl ThinkerId {
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Thinker {
+    Mobj(Mobj),
+    Door(VerticalDoor),
+    Plat(Plat),
+    Floor(FloorMove),
+    Ceiling(Ceiling),
+    FireFlicker(FireFlicker),
+    LightFlash(LightFlash),
+    Strobe(Strobe),
+    Glow(Glow),
+}
+
+#[derive(Clone, Debug)]
+struct ThinkerSlot {
+    generation: u32,
+    thinker: Option<Thinker>,
+    // P_RemoveThinker only marks the thinker, it's freed the next time
+    // P_RunThinkers gets to it
+    removed: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Thinkers {
+    slots: Vec<ThinkerSlot>,
+    free_slots: Vec<u32>,
+    // The order the thinkers run in, same as the original's list order
+    run_order: Vec<ThinkerId>,
+}
+
+impl Thinkers {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // P_InitThinkers
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.free_slots.clear();
+        self.run_order.clear();
+    }
+
+    // P_AddThinker. New thinkers go on the end so they still run this tic
+    // when added by another thinker
+    pub fn add(&mut self, thinker: Thinker) -> ThinkerId {
+        let id: ThinkerId = match self.free_slots.pop() {
+            Some(index) => {
+                let slot: &mut ThinkerSlot = &mut self.slots[index as usize];
+                slot.generation = slot.generation.wrapping_add(1);
+                slot.thinker = Some(thinker);
+                slot.removed = false;
+                ThinkerId {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(ThinkerSlot {
+                    generation: 0,
+                    thinker: Some(thinker),
+                    removed: false,
+                });
+                ThinkerId {
+                    index: u32::try_from(self.slots.len() - 1).unwrap(),
+                    generation: 0,
+                }
+            }
+        };
+
+        self.run_order.push(id);
+        id
+    }
+
+    // P_RemoveThinker. The thinker can still be looked at until it's freed
+    pub fn remove(&mut self, id: ThinkerId) {
+        if let Some(slot) = self.slot_mut(id) {
+            slot.removed = true;
+        }
+    }
+
+    pub fn is_removed(&self, id: ThinkerId) -> bool {
+        self.slot(id).map(|slot| slot.removed).unwrap_or(true)
+    }
+
+    pub fn contains(&self, id: ThinkerId) -> bool {
+        self.slot(id).is_some()
+    }
+
+    pub fn get(&self, id: ThinkerId) -> Option<&Thinker> {
+        self.slot(id).and_then(|slot| slot.thinker.as_ref())
+    }
+
+    pub fn get_mut(&mut self, id: ThinkerId) -> Option<&mut Thinker> {
+        self.slot_mut(id).and_then(|slot| slot.thinker.as_mut())
+    }
+
+    pub fn get_mobj(&self, id: ThinkerId) -> Option<&Mobj> {
+        match self.get(id) {
+            Some(Thinker::Mobj(mobj)) => Some(mobj),
+            _ => None,
+        }
+    }
+
+    pub fn get_mobj_mut(&mut self, id: ThinkerId) -> Option<&mut Mobj> {
+        match self.get_mut(id) {
+            Some(Thinker::Mobj(mobj)) => Some(mobj),
+            _ => None,
+        }
+    }
+
+    // For ids that have to be a mobj that hasn't been freed
+    pub fn mobj(&self, id: ThinkerId) -> &Mobj {
+        self.get_mobj(id)
+            .unwrap_or_else(|| panic!("Thinker {:?} is not a mobj", id))
+    }
+
+    pub fn mobj_mut(&mut self, id: ThinkerId) -> &mut Mobj {
+        self.get_mobj_mut(id)
+            .unwrap_or_else(|| panic!("Thinker {:?} is not a mobj", id))
+    }
+
+    // Thinkers that haven't been removed, in run order
+    pub fn iter(&self) -> impl Iterator<Item = (ThinkerId, &Thinker)> + '_ {
+        self.run_order.iter().filter_map(|id| {
+            let slot: &ThinkerSlot = self.slot(*id)?;
+            match (&slot.thinker, slot.removed) {
+                (Some(thinker), false) => Some((*id, thinker)),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn mobjs(&self) -> impl Iterator<Item = (ThinkerId, &Mobj)> + '_ {
+        self.iter().filter_map(|(id, thinker)| match thinker {
+            Thinker::Mobj(mobj) => Some((id, mobj)),
+            _ => None,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Used by P_RunThinkers, which walks the run order by index since
+    // thinkers can be added while it's running
+    pub(crate) fn run_order_id(&self, position: usize) -> Option<ThinkerId> {
+        self.run_order.get(position).copied()
+    }
+
+    pub(crate) fn set_run_order_id(&mut self, position: usize, id: ThinkerId) {
+        self.run_order[position] = id;
+    }
+
+    pub(crate) fn truncate_run_order(&mut self, len: usize) {
+        self.run_order.truncate(len);
+    }
+
+    pub(crate) fn free(&mut self, id: ThinkerId) {
+        if let Some(slot) = self.slot_mut(id) {
+            slot.thinker = None;
+            slot.removed = false;
+            self.free_slots.push(id.index);
+        }
+    }
+
+    fn slot(&self, id: ThinkerId) -> Option<&ThinkerSlot> {
+        self.slots
+            .get(id.index())
+            .filter(|slot| slot.generation == id.generation && slot.thinker.is_some())
+    }
+
+    fn slot_mut(&mut self, id: ThinkerId) -> Option<&mut ThinkerSlot> {
+        self.slots
+            .get_mut(id.index())
+            .filter(|slot| slot.generation == id.generation && slot.thinker.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::play::mobj::Mobj;
+    use crate::play::thinker::{Thinker, ThinkerId, Thinkers};
+
+    fn create_mobj(health: i32) -> Thinker {
+        Thinker::Mobj(Mobj {
+            health,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_thinkers_add_and_get() {
+        let mut thinkers: Thinkers = Thinkers::new();
+        let first: ThinkerId = thinkers.add(create_mobj(1));
+        let second: ThinkerId = thinkers.add(create_mobj(2));
+
+        assert_eq!(thinkers.mobj(first).health, 1);
+        assert_eq!(thinkers.mobj(second).health, 2);
+        assert_eq!(thinkers.len(), 2);
+        thinkers.mobj_mut(first).health = 10;
+        assert_eq!(
+            thinkers
+                .mobjs()
+                .map(|(_id, mobj)| mobj.health)
+                .collect::<Vec<i32>>(),
+            vec![10, 2]
+        );
+    }
+
+    #[test]
+    fn test_thinkers_remove_is_deferred_until_freed() {
+        let mut thinkers: Thinkers = Thinkers::new();
+        let id: ThinkerId = thinkers.add(create_mobj(1));
+
+        thinkers.remove(id);
+        assert!(thinkers.is_removed(id));
+        assert!(thinkers.contains(id));
+        assert_eq!(thinkers.mobj(id).health, 1);
+        assert!(thinkers.is_empty());
+
+        thinkers.free(id);
+        assert!(!thinkers.contains(id));
+        assert!(thinkers.get_mobj(id).is_none());
+    }
+
+    #[test]
+    fn test_thinkers_reused_slots_do_not_match_old_ids() {
+        let mut thinkers: Thinkers = Thinkers::new();
+        let old: ThinkerId = thinkers.add(create_mobj(1));
+        thinkers.remove(old);
+        thinkers.free(old);
+
+        let new: ThinkerId = thinkers.add(create_mobj(2));
+        assert_eq!(new.index(), old.index());
+        assert_ne!(new, old);
+        assert!(thinkers.get(old).is_none());
+        assert_eq!(thinkers.mobj(new).health, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a mobj")]
+    fn test_thinkers_mobj_panics_for_freed_ids() {
+        let mut thinkers: Thinkers = Thinkers::new();
+        let id: ThinkerId = thinkers.add(create_mobj(1));
+        thinkers.remove(id);
+        thinkers.free(id);
+        thinkers.mobj(id);
+    }
+}
@@ -0,0 +1,37 @@
+// Port of d_ticcmd.h. One player's input for one tic, what demos record and
+// network games send around
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TicCmd {
+    // *2048 for move
+    pub forward_move: i8,
+    // *2048 for move
+    pub side_move: i8,
+    // <<16 for angle delta
+    pub angle_turn: i16,
+    // Checks for net game
+    pub consistancy: i16,
+    pub chat_char: u8,
+    pub buttons: u8,
+}
+
+// Button/action code definitions from d_event.h
+// Press "Fire"
+pub const BT_ATTACK: u8 = 1;
+// Use button, to open doors, activate switches
+pub const BT_USE: u8 = 2;
+// Flag: game events, not really buttons
+pub const BT_SPECIAL: u8 = 128;
+pub const BT_SPECIALMASK: u8 = 3;
+// Flag, weapon change pending. If true, the next 3 bits hold weapon num
+pub const BT_CHANGE: u8 = 4;
+// The 3bit weapon mask and shift, convenience
+pub const BT_WEAPONMASK: u8 = 8 + 16 + 32;
+pub const BT_WEAPONSHIFT: u8 = 3;
+
+// Pause the game
+pub const BTS_PAUSE: u8 = 1;
+// Save the game at each console
+pub const BTS_SAVEGAME: u8 = 2;
+// Savegame slot numbers occupy the second byte of buttons
+pub const BTS_SAVEMASK: u8 = 4 + 8 + 16;
+pub const BTS_SAVESHIFT: u8 = 2;
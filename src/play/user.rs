@@ -0,0 +1,448 @@
+// Port of p_user.c. Player related stuff. Bobbing POV/weapon, movement.
+// Pending weapon
+use crate::config::GameType;
+use crate::fixed::tables::{finesine, FINEANGLES, FINEMASK};
+use crate::fixed::{fixed_mul, point_to_angle, Angle, Fixed, ANG180, ANG90, FRACUNIT};
+use crate::info::states::{S_PLAY, S_PLAY_RUN1};
+use crate::play::mobj::{Mobj, MobjId, MF_JUSTATTACKED, MF_NOCLIP, MF_SHADOW};
+use crate::play::player::{
+    Player, PlayerState, WeaponType, CF_NOCLIP, CF_NOMOMENTUM, PW_INFRARED, PW_INVISIBILITY,
+    PW_INVULNERABILITY, PW_IRONFEET, PW_STRENGTH, VIEWHEIGHT, WP_BFG, WP_CHAINSAW, WP_FIST,
+    WP_PLASMA, WP_SHOTGUN, WP_SUPERSHOTGUN,
+};
+use crate::play::ticcmd::{TicCmd, BT_CHANGE, BT_SPECIAL, BT_USE, BT_WEAPONMASK, BT_WEAPONSHIFT};
+use crate::play::world::World;
+
+// Index of the special effects (INVUL inverse) map
+pub const INVERSECOLORMAP: i32 = 32;
+
+// 16 pixels of bob
+pub const MAXBOB: Fixed = Fixed(0x100000);
+
+const ANG5: u32 = ANG90 / 18;
+
+impl World {
+    // P_Thrust. Moves the given origin along a given angle
+    pub fn thrust(&mut self, player: usize, angle: Angle, amount: Fixed) {
+        let mo: MobjId = self.players[player].mo.unwrap();
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(mo);
+        mobj.mom_x += fixed_mul(amount, angle.cos());
+        mobj.mom_y += fixed_mul(amount, angle.sin());
+    }
+
+    // P_CalcHeight. Calculate the walking / running height adjustment
+    pub fn calc_height(&mut self, player: usize) {
+        let level_time: i32 = self.level_time;
+        let on_ground: bool = self.on_ground;
+        let p: &mut Player = &mut self.players[player];
+        let mobj: &Mobj = self.thinkers.mobj(p.mo.unwrap());
+
+        // Regular movement bobbing (needs to be calculated for gun swing
+        // even if not on ground)
+        p.bob = fixed_mul(mobj.mom_x, mobj.mom_x) + fixed_mul(mobj.mom_y, mobj.mom_y);
+        p.bob = Fixed(p.bob.0 >> 2);
+        if p.bob > MAXBOB {
+            p.bob = MAXBOB;
+        }
+
+        let ceiling_limit: Fixed = mobj.ceiling_z - Fixed(4 * FRACUNIT);
+
+        if p.cheats & CF_NOMOMENTUM != 0 || !on_ground {
+            // The clamped value is thrown away straight after, the same as
+            // the original
+            p.view_z = mobj.z + VIEWHEIGHT;
+            if p.view_z > ceiling_limit {
+                p.view_z = ceiling_limit;
+            }
+
+            p.view_z = mobj.z + p.view_height;
+            return;
+        }
+
+        let angle: usize = (FINEANGLES / 20 * level_time as usize) & FINEMASK;
+        let bob: Fixed = fixed_mul(Fixed(p.bob.0 / 2), finesine(angle));
+
+        // Move viewheight
+        if p.player_state == PlayerState::Live {
+            p.view_height += p.delta_view_height;
+
+            if p.view_height > VIEWHEIGHT {
+                p.view_height = VIEWHEIGHT;
+                p.delta_view_height = Fixed::ZERO;
+            }
+
+            if p.view_height < Fixed(VIEWHEIGHT.0 / 2) {
+                p.view_height = Fixed(VIEWHEIGHT.0 / 2);
+                if p.delta_view_height <= Fixed::ZERO {
+                    p.delta_view_height = Fixed(1);
+                }
+            }
+
+            if p.delta_view_height != Fixed::ZERO {
+                p.delta_view_height += Fixed(FRACUNIT / 4);
+                if p.delta_view_height == Fixed::ZERO {
+                    p.delta_view_height = Fixed(1);
+                }
+            }
+        }
+
+        p.view_z = mobj.z + p.view_height + bob;
+
+        if p.view_z > ceiling_limit {
+            p.view_z = ceiling_limit;
+        }
+    }
+
+    // P_MovePlayer
+    fn move_player(&mut self, player: usize) {
+        let cmd: TicCmd = self.players[player].cmd;
+        let mo: MobjId = self.players[player].mo.unwrap();
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(mo);
+
+        mobj.angle += Angle(((cmd.angle_turn as i32) << 16) as u32);
+
+        // Do not let the player control movement if not onground
+        self.on_ground = mobj.z <= mobj.floor_z;
+        let angle: Angle = mobj.angle;
+
+        if cmd.forward_move != 0 && self.on_ground {
+            self.thrust(player, angle, Fixed(i32::from(cmd.forward_move) * 2048));
+        }
+
+        if cmd.side_move != 0 && self.on_ground {
+            self.thrust(
+                player,
+                angle - Angle::ANG90,
+                Fixed(i32::from(cmd.side_move) * 2048),
+            );
+        }
+
+        if (cmd.forward_move != 0 || cmd.side_move != 0) && self.thinkers.mobj(mo).state == S_PLAY {
+            self.set_mobj_state(mo, S_PLAY_RUN1);
+        }
+    }
+
+    // P_DeathThink. Fall on your face when dying. Decrease POV height to
+    // floor height
+    fn death_think(&mut self, player: usize) {
+        self.move_psprites(player);
+
+        let p: &mut Player = &mut self.players[player];
+
+        // Fall to the ground
+        if p.view_height > Fixed(6 * FRACUNIT) {
+            p.view_height -= Fixed::ONE;
+        }
+
+        if p.view_height < Fixed(6 * FRACUNIT) {
+            p.view_height = Fixed(6 * FRACUNIT);
+        }
+
+        p.delta_view_height = Fixed::ZERO;
+        let mo: MobjId = p.mo.unwrap();
+        let mobj: &Mobj = self.thinkers.mobj(mo);
+        self.on_ground = mobj.z <= mobj.floor_z;
+        self.calc_height(player);
+
+        let attacker: Option<MobjId> = self.players[player]
+            .attacker
+            .filter(|attacker| *attacker != mo && self.thinkers.get_mobj(*attacker).is_some());
+
+        if let Some(attacker) = attacker {
+            let attacker_mobj: &Mobj = self.thinkers.mobj(attacker);
+            let (attacker_x, attacker_y): (Fixed, Fixed) = (attacker_mobj.x, attacker_mobj.y);
+            let mobj: &mut Mobj = self.thinkers.mobj_mut(mo);
+            let angle: Angle = point_to_angle(mobj.x, mobj.y, attacker_x, attacker_y);
+            let delta: u32 = (angle - mobj.angle).0;
+
+            if delta < ANG5 || delta > 0u32.wrapping_sub(ANG5) {
+                // Looking at killer, so fade damage flash down
+                mobj.angle = angle;
+
+                let p: &mut Player = &mut self.players[player];
+                if p.damage_count != 0 {
+                    p.damage_count -= 1;
+                }
+            } else if delta < ANG180 {
+                mobj.angle += Angle(ANG5);
+            } else {
+                mobj.angle -= Angle(ANG5);
+            }
+        } else {
+            let p: &mut Player = &mut self.players[player];
+            if p.damage_count != 0 {
+                p.damage_count -= 1;
+            }
+        }
+
+        let p: &mut Player = &mut self.players[player];
+        if p.cmd.buttons & BT_USE != 0 {
+            p.player_state = PlayerState::Reborn;
+        }
+    }
+
+    // P_PlayerThink
+    pub fn player_think(&mut self, player: usize) {
+        let p: &mut Player = &mut self.players[player];
+        let mo: MobjId = p.mo.unwrap();
+        let mobj: &mut Mobj = self.thinkers.mobj_mut(mo);
+
+        // Fixme: do this in the cheat code
+        if p.cheats & CF_NOCLIP != 0 {
+            mobj.flags |= MF_NOCLIP;
+        } else {
+            mobj.flags &= !MF_NOCLIP;
+        }
+
+        // Chain saw run forward
+        if mobj.flags & MF_JUSTATTACKED != 0 {
+            p.cmd.angle_turn = 0;
+            p.cmd.forward_move = (0xc800 / 512) as i8;
+            p.cmd.side_move = 0;
+            mobj.flags &= !MF_JUSTATTACKED;
+        }
+
+        if p.player_state == PlayerState::Dead {
+            self.death_think(player);
+            return;
+        }
+
+        // Move around. Reactiontime is used to prevent movement for a bit
+        // after a teleport
+        if mobj.reaction_time != 0 {
+            mobj.reaction_time -= 1;
+        } else {
+            self.move_player(player);
+        }
+
+        self.calc_height(player);
+
+        let subsector: usize = self.thinkers.mobj(mo).subsector;
+        let sector: usize = self.level.subsectors[subsector].sector;
+        if self.level.sectors[sector].special != 0 {
+            self.player_in_special_sector(player);
+        }
+
+        // Check for weapon change
+        let game_type: GameType = self.settings.game_type;
+        let p: &mut Player = &mut self.players[player];
+
+        // A special event has no other buttons
+        if p.cmd.buttons & BT_SPECIAL != 0 {
+            p.cmd.buttons = 0;
+        }
+
+        if p.cmd.buttons & BT_CHANGE != 0 {
+            // The actual changing of the weapon is done when the weapon
+            // psprite can do it (read: not in the middle of an attack)
+            let mut new_weapon: WeaponType =
+                ((p.cmd.buttons & BT_WEAPONMASK) >> BT_WEAPONSHIFT) as WeaponType;
+
+            if new_weapon == WP_FIST
+                && p.weapon_owned[WP_CHAINSAW]
+                && !(p.ready_weapon == WP_CHAINSAW && p.powers[PW_STRENGTH] != 0)
+            {
+                new_weapon = WP_CHAINSAW;
+            }
+
+            if game_type == GameType::DoomII
+                && new_weapon == WP_SHOTGUN
+                && p.weapon_owned[WP_SUPERSHOTGUN]
+                && p.ready_weapon != WP_SUPERSHOTGUN
+            {
+                new_weapon = WP_SUPERSHOTGUN;
+            }
+
+            // Do not go to plasma or BFG in shareware, even if cheated
+            if p.weapon_owned[new_weapon]
+                && new_weapon != p.ready_weapon
+                && ((new_weapon != WP_PLASMA && new_weapon != WP_BFG)
+                    || game_type != GameType::DoomIShareware)
+            {
+                p.pending_weapon = new_weapon;
+            }
+        }
+
+        // Check for use
+        if p.cmd.buttons & BT_USE != 0 {
+            if !p.use_down {
+                self.use_lines(player);
+                self.players[player].use_down = true;
+            }
+        } else {
+            p.use_down = false;
+        }
+
+        // Cycle psprites
+        self.move_psprites(player);
+
+        // Counters, time dependend power ups
+        let p: &mut Player = &mut self.players[player];
+
+        // Strength counts up to diminish fade
+        if p.powers[PW_STRENGTH] != 0 {
+            p.powers[PW_STRENGTH] += 1;
+        }
+
+        if p.powers[PW_INVULNERABILITY] != 0 {
+            p.powers[PW_INVULNERABILITY] -= 1;
+        }
+
+        if p.powers[PW_INVISIBILITY] != 0 {
+            p.powers[PW_INVISIBILITY] -= 1;
+            if p.powers[PW_INVISIBILITY] == 0 {
+                self.thinkers.mobj_mut(mo).flags &= !MF_SHADOW;
+            }
+        }
+
+        if p.powers[PW_INFRARED] != 0 {
+            p.powers[PW_INFRARED] -= 1;
+        }
+
+        if p.powers[PW_IRONFEET] != 0 {
+            p.powers[PW_IRONFEET] -= 1;
+        }
+
+        if p.damage_count != 0 {
+            p.damage_count -= 1;
+        }
+
+        if p.bonus_count != 0 {
+            p.bonus_count -= 1;
+        }
+
+        // Handling colormaps
+        let invulnerability: i32 = p.powers[PW_INVULNERABILITY];
+        let infrared: i32 = p.powers[PW_INFRARED];
+        p.fixed_colormap = if invulnerability != 0 {
+            if invulnerability > 4 * 32 || invulnerability & 8 != 0 {
+                INVERSECOLORMAP
+            } else {
+                0
+            }
+        } else if infrared != 0 {
+            // Almost full bright
+            if infrared > 4 * 32 || infrared & 8 != 0 {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::info::states::{S_PLAY, S_PLAY_RUN1};
+    use crate::play::mobj::{Mobj, MobjId, MF_SHADOW};
+    use crate::play::player::{
+        PlayerState, PW_INVISIBILITY, PW_INVULNERABILITY, VIEWHEIGHT, WP_CHAINSAW, WP_FIST,
+        WP_SHOTGUN, WP_SUPERSHOTGUN,
+    };
+    use crate::play::test_map::TestMap;
+    use crate::play::ticcmd::{TicCmd, BT_CHANGE, BT_USE, BT_WEAPONSHIFT};
+    use crate::play::user::INVERSECOLORMAP;
+    use crate::play::world::{World, WorldSettings};
+
+    fn create_world() -> World {
+        World::new(
+            WorldSettings::default(),
+            &TestMap::new()
+                .room(512, 0, 128)
+                .thing(64, 128, 0, 1, 7)
+                .to_map_lumps(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_player_think_thrusts_forward() {
+        let mut world: World = create_world();
+        let mo: MobjId = world.players[0].mo.unwrap();
+        // Players can't move for 8 tics after spawning
+        world.thinkers.mobj_mut(mo).reaction_time = 0;
+
+        world.players[0].cmd = TicCmd {
+            forward_move: 50,
+            ..Default::default()
+        };
+        world.player_think(0);
+
+        let mobj: &Mobj = world.thinkers.mobj(mo);
+        // 50 * 2048 straight along x
+        assert_eq!(mobj.mom_x, Fixed(102398));
+        assert_eq!(mobj.state, S_PLAY_RUN1);
+        assert!(world.players[0].bob > Fixed::ZERO);
+        // The sine table has 25 for 0 so even the first tic bobs a little
+        assert_eq!(world.players[0].view_z, VIEWHEIGHT + Fixed(7));
+    }
+
+    #[test]
+    fn test_player_think_turns_and_waits_after_teleport() {
+        let mut world: World = create_world();
+        let mo: MobjId = world.players[0].mo.unwrap();
+        world.thinkers.mobj_mut(mo).reaction_time = 1;
+
+        world.players[0].cmd = TicCmd {
+            forward_move: 50,
+            angle_turn: 0x4000,
+            ..Default::default()
+        };
+        world.player_think(0);
+
+        let mobj: &Mobj = world.thinkers.mobj(mo);
+        assert_eq!(mobj.mom_x, Fixed::ZERO);
+        assert_eq!(mobj.state, S_PLAY);
+        assert_eq!(mobj.reaction_time, 0);
+
+        world.player_think(0);
+        assert_eq!(world.thinkers.mobj(mo).angle.0, 0x4000_0000);
+    }
+
+    #[test]
+    fn test_player_think_changes_weapon() {
+        let mut world: World = create_world();
+        world.players[0].weapon_owned[WP_SHOTGUN] = true;
+        world.players[0].weapon_owned[WP_SUPERSHOTGUN] = true;
+        world.players[0].weapon_owned[WP_CHAINSAW] = true;
+
+        world.players[0].cmd.buttons = BT_CHANGE | ((WP_SHOTGUN as u8) << BT_WEAPONSHIFT);
+        world.player_think(0);
+        assert_eq!(world.players[0].pending_weapon, WP_SUPERSHOTGUN);
+
+        world.players[0].cmd.buttons = BT_CHANGE | ((WP_FIST as u8) << BT_WEAPONSHIFT);
+        world.player_think(0);
+        assert_eq!(world.players[0].pending_weapon, WP_CHAINSAW);
+    }
+
+    #[test]
+    fn test_player_think_counts_down_powers() {
+        let mut world: World = create_world();
+        let mo: MobjId = world.players[0].mo.unwrap();
+        world.players[0].powers[PW_INVULNERABILITY] = 200;
+        world.players[0].powers[PW_INVISIBILITY] = 1;
+        world.thinkers.mobj_mut(mo).flags |= MF_SHADOW;
+
+        world.player_think(0);
+
+        assert_eq!(world.players[0].powers[PW_INVULNERABILITY], 199);
+        assert_eq!(world.players[0].fixed_colormap, INVERSECOLORMAP);
+        assert_eq!(world.thinkers.mobj(mo).flags & MF_SHADOW, 0);
+    }
+
+    #[test]
+    fn test_death_think_lowers_view_and_reborns_on_use() {
+        let mut world: World = create_world();
+        world.players[0].player_state = PlayerState::Dead;
+
+        world.player_think(0);
+        assert_eq!(world.players[0].view_height, VIEWHEIGHT - Fixed(FRACUNIT));
+
+        world.players[0].cmd.buttons = BT_USE;
+        world.player_think(0);
+        assert_eq!(world.players[0].player_state, PlayerState::Reborn);
+    }
+}
@@ -0,0 +1,509 @@
+use crate::config::{Config, GameDifficulty, GameType};
+use crate::fixed::Fixed;
+use crate::m_random::Random;
+use crate::option::DoomOptions;
+use crate::play::ceiling::MAXCEILINGS;
+use crate::play::enemy::BossBrain;
+use crate::play::level::{Level, LevelError, MapLumps, MapThing, Vertex};
+use crate::play::map::{LineAttack, PositionCheck};
+use crate::play::maputl::DivLine;
+use crate::play::mobj::{ItemRespawnQueue, MobjId, BODYQUESIZE};
+use crate::play::plats::MAXPLATS;
+use crate::play::player::Player;
+use crate::play::switch::{Button, MAXBUTTONS};
+use crate::play::thinker::{Thinker, ThinkerId, Thinkers};
+use crate::play::{CompatibilityLevel, MAXPLAYERS};
+use crate::sound::sfx::{SfxNum, SFX_NONE};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Deathmatch {
+    // Single player and cooperative
+    #[default]
+    Off,
+    // Weapons stay put when picked up
+    Classic,
+    // -altdeath, items respawn and weapons disappear when picked up
+    AltDeath,
+}
+
+// The game options the simulation needs, the globals the original set up
+// in d_main.c and G_InitNew
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorldSettings {
+    pub game_type: GameType,
+    pub skill: GameDifficulty,
+    pub deathmatch: Deathmatch,
+    pub netgame: bool,
+    pub no_monsters: bool,
+    // -respawn and -fast. Nightmare turns both on as well
+    pub respawn_parm: bool,
+    pub fast_parm: bool,
+    pub players_in_game: [bool; MAXPLAYERS],
+    pub console_player: usize,
+    pub episode: i32,
+    pub map: i32,
+    pub compatibility: CompatibilityLevel,
+}
+
+impl Default for WorldSettings {
+    fn default() -> Self {
+        WorldSettings {
+            game_type: GameType::DoomII,
+            skill: GameDifficulty::Medium,
+            deathmatch: Deathmatch::Off,
+            netgame: false,
+            no_monsters: false,
+            respawn_parm: false,
+            fast_parm: false,
+            players_in_game: [true, false, false, false],
+            console_player: 0,
+            episode: 1,
+            map: 1,
+            compatibility: CompatibilityLevel::Vanilla,
+        }
+    }
+}
+
+impl WorldSettings {
+    pub fn new(config: &Config, doom_options: &DoomOptions) -> Self {
+        let deathmatch: Deathmatch = if doom_options.is_option_enabled("-altdeath") {
+            Deathmatch::AltDeath
+        } else if doom_options.is_option_enabled("-deathmatch") {
+            Deathmatch::Classic
+        } else {
+            Deathmatch::Off
+        };
+
+        WorldSettings {
+            game_type: config.game_type,
+            skill: config.game_difficulty,
+            deathmatch,
+            no_monsters: doom_options.is_option_enabled("-nomonsters"),
+            respawn_parm: doom_options.is_option_enabled("-respawn"),
+            fast_parm: doom_options.is_option_enabled("-fast"),
+            episode: config.start_episode as i32,
+            compatibility: config.compatibility,
+            ..Default::default()
+        }
+    }
+
+    pub fn respawn_monsters(&self) -> bool {
+        self.respawn_parm || self.skill == GameDifficulty::Nightmare
+    }
+
+    pub fn fast_monsters(&self) -> bool {
+        self.fast_parm || self.skill == GameDifficulty::Nightmare
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundOrigin {
+    Mobj(MobjId),
+    // Moving floors, doors and switches play from the middle of the sector
+    Sector(usize),
+}
+
+// A sound started by the simulation, for whatever plays the sounds to pick
+// up. Sounds without an origin play at full volume
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SoundEvent {
+    pub sfx: SfxNum,
+    pub origin: Option<SoundOrigin>,
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+// Set when something ends the level, for the game to pick up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LevelExit {
+    Normal,
+    Secret,
+}
+
+// A loaded level and everything in it, stepped one tic at a time
+#[derive(Clone, Debug)]
+pub struct World {
+    pub settings: WorldSettings,
+    pub level: Level,
+    pub thinkers: Thinkers,
+    pub random: Random,
+    pub players: [Player; MAXPLAYERS],
+    pub player_starts: [Option<MapThing>; MAXPLAYERS],
+    pub deathmatch_starts: Vec<MapThing>,
+    // Tics since the level started
+    pub level_time: i32,
+    // Tics since the game started, A_Tracer only steers on every 4th
+    pub game_tic: i32,
+    pub total_kills: i32,
+    pub total_items: i32,
+    pub total_secrets: i32,
+    // Bumped so lines and mobjs are only checked once per search
+    pub valid_count: i32,
+    pub position_check: PositionCheck,
+    // The line being followed by P_PathTraverse
+    pub trace: DivLine,
+    pub line_attack: LineAttack,
+    // Plats and ceilings that can be stopped and started again by tag
+    pub active_plats: [Option<ThinkerId>; MAXPLATS],
+    pub active_ceilings: [Option<ThinkerId>; MAXCEILINGS],
+    // Switches waiting to pop back out
+    pub buttons: [Button; MAXBUTTONS],
+    // Lines with scrolling textures
+    pub scrolling_lines: Vec<usize>,
+    pub level_exit: Option<LevelExit>,
+    // Sounds started since whatever plays them last took them
+    pub sound_events: Vec<SoundEvent>,
+    pub brain: BossBrain,
+    pub item_respawn_queue: ItemRespawnQueue,
+    // Set by P_MovePlayer. Left over from the last player that moved when
+    // a player can't move, the same as the original's global
+    pub on_ground: bool,
+    // Corpses left by players respawning in network games, the oldest is
+    // removed once the queue is full
+    pub body_queue: [Option<MobjId>; BODYQUESIZE],
+    pub body_queue_slot: usize,
+}
+
+impl World {
+    // G_InitNew and P_SetupLevel. The random number index starts from 0
+    // like a new game
+    pub fn new(settings: WorldSettings, map_lumps: &MapLumps) -> Result<Self, LevelError> {
+        World::setup_level(settings, map_lumps, Default::default(), Random::new(), 0)
+    }
+
+    // P_SetupLevel. Players, the random number index and the game tic carry
+    // over from the last level
+    pub fn setup_level(
+        settings: WorldSettings,
+        map_lumps: &MapLumps,
+        mut players: [Player; MAXPLAYERS],
+        random: Random,
+        game_tic: i32,
+    ) -> Result<Self, LevelError> {
+        for player in players.iter_mut() {
+            player.kill_count = 0;
+            player.secret_count = 0;
+            player.item_count = 0;
+            // The mobjs were left behind with the last level
+            player.mo = None;
+            player.attacker = None;
+        }
+
+        // Initial height of PointOfView will be set by player think
+        players[settings.console_player].view_z = Fixed(1);
+
+        let mut world: World = World {
+            settings,
+            level: Level::from_lumps(map_lumps)?,
+            thinkers: Thinkers::new(),
+            random,
+            players,
+            player_starts: [None; MAXPLAYERS],
+            deathmatch_starts: Vec::new(),
+            level_time: 0,
+            game_tic,
+            total_kills: 0,
+            total_items: 0,
+            total_secrets: 0,
+            valid_count: 0,
+            position_check: PositionCheck::default(),
+            trace: DivLine::default(),
+            line_attack: LineAttack::default(),
+            active_plats: [None; MAXPLATS],
+            active_ceilings: [None; MAXCEILINGS],
+            buttons: [Button::default(); MAXBUTTONS],
+            scrolling_lines: Vec::new(),
+            level_exit: None,
+            sound_events: Vec::new(),
+            brain: BossBrain::default(),
+            item_respawn_queue: ItemRespawnQueue::default(),
+            on_ground: false,
+            body_queue: [None; BODYQUESIZE],
+            body_queue_slot: 0,
+        };
+
+        for map_thing in world.level.things.clone().iter() {
+            world.spawn_map_thing(map_thing);
+        }
+
+        // If deathmatch, randomly spawn the active players
+        if world.settings.deathmatch != Deathmatch::Off {
+            for player in 0..MAXPLAYERS {
+                if world.settings.players_in_game[player] {
+                    world.players[player].mo = None;
+                    world.deathmatch_spawn_player(player);
+                }
+            }
+        }
+
+        // Set up light thinkers and the like
+        world.spawn_specials();
+
+        Ok(world)
+    }
+
+    // P_Ticker
+    pub fn tick(&mut self) {
+        for player in 0..MAXPLAYERS {
+            if self.settings.players_in_game[player] && self.players[player].mo.is_some() {
+                self.player_think(player);
+            }
+        }
+
+        self.run_thinkers();
+        self.update_specials();
+        self.respawn_specials();
+
+        // For par times
+        self.level_time += 1;
+        self.game_tic += 1;
+    }
+
+    // S_StartSound
+    pub fn start_sound(&mut self, origin: Option<MobjId>, sfx: SfxNum) {
+        if sfx == SFX_NONE {
+            return;
+        }
+
+        let (x, y): (Fixed, Fixed) = origin
+            .and_then(|id| self.thinkers.get_mobj(id))
+            .map(|mobj| (mobj.x, mobj.y))
+            .unwrap_or_default();
+        self.sound_events.push(SoundEvent {
+            sfx,
+            origin: origin.map(SoundOrigin::Mobj),
+            x,
+            y,
+        });
+    }
+
+    // S_StartSound with a sector's sound origin
+    pub fn start_sector_sound(&mut self, sector: usize, sfx: SfxNum) {
+        let sound_org: Vertex = self.level.sectors[sector].sound_org;
+        self.sound_events.push(SoundEvent {
+            sfx,
+            origin: Some(SoundOrigin::Sector(sector)),
+            x: sound_org.x,
+            y: sound_org.y,
+        });
+    }
+
+    // P_RunThinkers. Removed thinkers are freed when they're reached
+    fn run_thinkers(&mut self) {
+        let mut position: usize = 0;
+        let mut kept: usize = 0;
+
+        while let Some(id) = self.thinkers.run_order_id(position) {
+            if self.thinkers.is_removed(id) {
+                // Time to remove it
+                self.thinkers.free(id);
+            } else {
+                self.run_thinker(id);
+                self.thinkers.set_run_order_id(kept, id);
+                kept += 1;
+            }
+            position += 1;
+        }
+
+        self.thinkers.truncate_run_order(kept);
+    }
+
+    fn run_thinker(&mut self, id: ThinkerId) {
+        match self.thinkers.get(id) {
+            Some(Thinker::Mobj(_)) => self.mobj_thinker(id),
+            Some(Thinker::Door(_)) => self.vertical_door_thinker(id),
+            Some(Thinker::Plat(_)) => self.plat_raise_thinker(id),
+            Some(Thinker::Floor(_)) => self.move_floor_thinker(id),
+            Some(Thinker::Ceiling(_)) => self.move_ceiling_thinker(id),
+            Some(Thinker::FireFlicker(_)) => self.fire_flicker_thinker(id),
+            Some(Thinker::LightFlash(_)) => self.light_flash_thinker(id),
+            Some(Thinker::Strobe(_)) => self.strobe_flash_thinker(id),
+            Some(Thinker::Glow(_)) => self.glow_thinker(id),
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use crate::config::{Config, GameDifficulty};
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::info::mobjinfo::{MT_BLOOD, MT_TROOP};
+    use crate::info::states::*;
+    use crate::option::DoomOptions;
+    use crate::play::mobj::{MobjId, ONFLOORZ};
+    use crate::play::test_map::TestMap;
+    use crate::play::world::{Deathmatch, SoundEvent, World, WorldSettings};
+    use crate::play::CompatibilityLevel;
+    use crate::sound::sfx::{SfxNum, SFX_DOROPN, SFX_PISTOL};
+
+    fn create_world() -> World {
+        World::new(
+            WorldSettings::default(),
+            &TestMap::new().room(256, 0, 128).to_map_lumps(),
+        )
+        .unwrap()
+    }
+
+    fn spawn(world: &mut World, mobj_type: usize) -> MobjId {
+        world.spawn_mobj(
+            Fixed(64 * FRACUNIT),
+            Fixed(64 * FRACUNIT),
+            ONFLOORZ,
+            mobj_type,
+        )
+    }
+
+    #[test]
+    fn test_world_settings_new_reads_options() {
+        let doom_options: DoomOptions = DoomOptions::new(
+            [
+                "-skill",
+                "5",
+                "-nomonsters",
+                "-fast",
+                "-deathmatch",
+                "-altdeath",
+                "-complevel",
+                "1",
+            ]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect(),
+        );
+        let settings: WorldSettings =
+            WorldSettings::new(&Config::new(&doom_options), &doom_options);
+
+        assert_eq!(settings.skill, GameDifficulty::Nightmare);
+        assert_eq!(settings.deathmatch, Deathmatch::AltDeath);
+        assert!(settings.no_monsters);
+        assert!(settings.fast_parm);
+        assert!(!settings.respawn_parm);
+        assert!(settings.respawn_monsters());
+        assert!(!settings.netgame);
+        assert_eq!(settings.compatibility, CompatibilityLevel::Fixed);
+    }
+
+    #[test]
+    fn test_world_settings_fast_and_respawn_only_from_options_below_nightmare() {
+        let settings: WorldSettings = WorldSettings {
+            respawn_parm: true,
+            ..Default::default()
+        };
+
+        assert!(settings.respawn_monsters());
+        assert!(!settings.fast_monsters());
+    }
+
+    #[test]
+    fn test_world_tick_advances_states() {
+        let mut world: World = create_world();
+        let id: MobjId = spawn(&mut world, MT_TROOP);
+
+        // S_TROO_STND lasts 10 tics
+        for _ in 0..9 {
+            world.tick();
+        }
+        assert_eq!(world.thinkers.mobj(id).state, S_TROO_STND);
+        assert_eq!(world.thinkers.mobj(id).tics, 1);
+
+        world.tick();
+        assert_eq!(world.thinkers.mobj(id).state, S_TROO_STND2);
+        assert_eq!(world.level_time, 10);
+    }
+
+    #[test]
+    fn test_world_tick_removes_mobjs_that_reach_s_null() {
+        let mut world: World = create_world();
+        let troop: MobjId = spawn(&mut world, MT_TROOP);
+        let blood: MobjId = spawn(&mut world, MT_BLOOD);
+
+        // Blood is 3 states of 8 tics then S_NULL
+        for _ in 0..24 {
+            world.tick();
+        }
+
+        assert!(world.thinkers.is_removed(blood));
+        assert!(world.thinkers.contains(blood));
+        assert_eq!(world.sector_things(0), vec![troop]);
+
+        // Freed when the thinker list gets to it on the next tic
+        world.tick();
+        assert!(!world.thinkers.contains(blood));
+        assert_eq!(world.thinkers.len(), 1);
+    }
+
+    #[test]
+    fn test_world_tick_is_deterministic() {
+        let test_map: TestMap = TestMap::new()
+            .room(256, 0, 128)
+            .room(256, 0, 128)
+            .thing(32, 32, 0, 3001, 7)
+            .thing(300, 32, 0, 3004, 7)
+            .thing(300, 100, 0, 2035, 7);
+        let mut first: World =
+            World::new(WorldSettings::default(), &test_map.to_map_lumps()).unwrap();
+        let mut second: World = first.clone();
+
+        for _ in 0..100 {
+            first.tick();
+            second.tick();
+        }
+
+        let states = |world: &World| {
+            world
+                .thinkers
+                .mobjs()
+                .map(|(_id, mobj)| (mobj.state, mobj.tics))
+                .collect::<Vec<(usize, i32)>>()
+        };
+        assert_eq!(states(&first), states(&second));
+        assert_eq!(first.level_time, 100);
+    }
+
+    #[test]
+    fn test_world_new_spawns_players_at_deathmatch_starts() {
+        let test_map: TestMap = TestMap::new()
+            .room(512, 0, 128)
+            .thing(32, 32, 0, 1, 7)
+            .thing(100, 64, 0, 11, 7)
+            .thing(200, 64, 0, 11, 7)
+            .thing(300, 64, 0, 11, 7)
+            .thing(400, 64, 0, 11, 7);
+        let world: World = World::new(
+            WorldSettings {
+                deathmatch: Deathmatch::Classic,
+                ..Default::default()
+            },
+            &test_map.to_map_lumps(),
+        )
+        .unwrap();
+
+        let mo: MobjId = world.players[0].mo.unwrap();
+        assert_eq!(world.thinkers.mobj(mo).y, Fixed(64 * FRACUNIT));
+        assert!(world.players[0].cards.iter().all(|card| *card));
+        assert_eq!(world.thinkers.mobjs().count(), 1);
+    }
+
+    #[test]
+    fn test_world_tick_keeps_sound_events_until_taken() {
+        let mut world: World = create_world();
+        let mo: MobjId = spawn(&mut world, MT_TROOP);
+        world.start_sound(Some(mo), SFX_PISTOL);
+        world.tick();
+        world.start_sector_sound(0, SFX_DOROPN);
+        world.tick();
+
+        let sounds: Vec<SoundEvent> = mem::take(&mut world.sound_events);
+        assert_eq!(
+            sounds
+                .iter()
+                .map(|sound| sound.sfx)
+                .collect::<Vec<SfxNum>>(),
+            vec![SFX_PISTOL, SFX_DOROPN]
+        );
+        assert!(world.sound_events.is_empty());
+    }
+}
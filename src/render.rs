@@ -0,0 +1,548 @@
+// The software renderer, the r_*.c parts of the original. Draws the
+// console player's view of a World into an 8 bit paletted framebuffer
+// without touching the game state
+use crate::fixed::tables::{
+    finetangent, tantoangle, ANGLETOFINESHIFT, DBITS, FINEANGLES, SLOPERANGE,
+};
+use crate::fixed::{fixed_div, fixed_mul, point_to_angle, Angle, Fixed, ANG90, FRACBITS, FRACUNIT};
+use crate::graphics::Palette;
+use crate::image::png::{PngColor, PngImage};
+use crate::play::level::{Level, ML_MAPPED};
+use crate::play::mobj::Mobj;
+use crate::play::player::Player;
+use crate::play::world::World;
+use crate::render::bsp::ClipRange;
+use crate::render::data::RenderData;
+use crate::render::plane::{sky_texture_name, Visplane};
+use crate::render::segs::DrawSeg;
+use crate::render::things::VisSprite;
+use crate::wad::DoomFile;
+
+pub mod bsp;
+pub mod data;
+pub mod draw;
+pub mod plane;
+pub mod segs;
+#[cfg(test)]
+pub(crate) mod test_wad;
+pub mod things;
+
+pub const SCREENWIDTH: usize = 320;
+pub const SCREENHEIGHT: usize = 200;
+
+// Fineangles in the SCREENWIDTH wide window
+const FIELDOFVIEW: usize = 2048;
+
+// Lighting constants. Now why not 32 levels here?
+pub const LIGHTLEVELS: usize = 16;
+pub const LIGHTSEGSHIFT: i32 = 4;
+pub const MAXLIGHTSCALE: usize = 48;
+pub const LIGHTSCALESHIFT: u32 = 12;
+pub const MAXLIGHTZ: usize = 128;
+pub const LIGHTZSHIFT: u32 = 20;
+// Number of diminishing brightness levels. There a 0-31, i.e. 32 LUT in
+// the COLORMAP lump
+pub const NUMCOLORMAPS: usize = 32;
+const DISTMAP: i32 = 2;
+
+// An indexed picture the size of the screen times the scale
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Framebuffer {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Framebuffer {
+    pub fn new(scale: usize) -> Self {
+        let width: usize = SCREENWIDTH * scale;
+        let height: usize = SCREENHEIGHT * scale;
+        Framebuffer {
+            width,
+            height,
+            pixels: vec![0; width * height],
+        }
+    }
+
+    pub fn to_png(&self, palette: &Palette) -> PngImage {
+        PngImage {
+            width: self.width as u32,
+            height: self.height as u32,
+            color: PngColor::Indexed {
+                palette: palette.colors.clone(),
+                indices: self.pixels.clone(),
+            },
+            grab_offset: None,
+        }
+    }
+}
+
+// The view globals R_SetupFrame fills in
+#[derive(Clone, Copy, Debug, Default)]
+struct View {
+    x: Fixed,
+    y: Fixed,
+    z: Fixed,
+    angle: Angle,
+    sin: Fixed,
+    cos: Fixed,
+    extra_light: i32,
+    // Offset of the colormap everything is drawn with, for the
+    // invulnerability and light amplification powers
+    fixed_colormap: Option<usize>,
+}
+
+pub struct Renderer {
+    pub data: RenderData,
+    view: View,
+    view_width: usize,
+    view_height: usize,
+    center_y: i32,
+    center_x_frac: Fixed,
+    center_y_frac: Fixed,
+    projection: Fixed,
+    // The view angle to screen column mapping for the fine angles in front
+    // of the view, and the angle through each column
+    view_angle_to_x: Vec<i32>,
+    x_to_view_angle: Vec<Angle>,
+    clip_angle: Angle,
+    y_slope: Vec<Fixed>,
+    dist_scale: Vec<Fixed>,
+    // Colormap offsets for each light level by wall scale and by flat
+    // distance
+    scale_light: [[usize; MAXLIGHTSCALE]; LIGHTLEVELS],
+    z_light: [[usize; MAXLIGHTZ]; LIGHTLEVELS],
+    psprite_scale: Fixed,
+    psprite_iscale: Fixed,
+    texture_translation: Vec<usize>,
+    flat_translation: Vec<usize>,
+    sky_texture: usize,
+    // Lines seen this frame that weren't on the automap yet
+    mapped_lines: Vec<usize>,
+    // r_bsp.c
+    solid_segs: Vec<ClipRange>,
+    cur_line: usize,
+    front_sector: usize,
+    back_sector: Option<usize>,
+    rw_angle1: Angle,
+    floor_plane: Option<usize>,
+    ceiling_plane: Option<usize>,
+    draw_segs: Vec<DrawSeg>,
+    // r_plane.c
+    floor_clip: Vec<i32>,
+    ceiling_clip: Vec<i32>,
+    visplanes: Vec<Visplane>,
+    openings: Vec<i32>,
+    span_start: Vec<i32>,
+    cached_height: Vec<Fixed>,
+    cached_distance: Vec<Fixed>,
+    cached_x_step: Vec<Fixed>,
+    cached_y_step: Vec<Fixed>,
+    base_x_scale: Fixed,
+    base_y_scale: Fixed,
+    // r_things.c
+    vissprites: Vec<VisSprite>,
+    sprite_sectors: Vec<bool>,
+    clip_bottom: Vec<i32>,
+    clip_top: Vec<i32>,
+    fuzz_pos: usize,
+}
+
+impl Renderer {
+    // R_Init
+    pub fn new(doom_files: &mut [DoomFile], scale: usize) -> Self {
+        Renderer::with_data(RenderData::from_wad(doom_files), scale)
+    }
+
+    // R_ExecuteSetViewSize for a full screen view
+    pub fn with_data(data: RenderData, scale: usize) -> Self {
+        let view_width: usize = SCREENWIDTH * scale;
+        let view_height: usize = SCREENHEIGHT * scale;
+        let center_x: i32 = view_width as i32 / 2;
+        let center_y: i32 = view_height as i32 / 2;
+        let center_x_frac: Fixed = Fixed::from_int(center_x);
+
+        let (view_angle_to_x, x_to_view_angle): (Vec<i32>, Vec<Angle>) =
+            init_texture_mapping(view_width, center_x_frac);
+        let clip_angle: Angle = x_to_view_angle[0];
+
+        let y_slope: Vec<Fixed> = (0..view_height as i32)
+            .map(|i| {
+                let dy: Fixed = Fixed(((i - center_y) << FRACBITS) + FRACUNIT / 2).abs();
+                fixed_div(Fixed(view_width as i32 / 2 * FRACUNIT), dy)
+            })
+            .collect();
+
+        let dist_scale: Vec<Fixed> = x_to_view_angle[..view_width]
+            .iter()
+            .map(|angle| fixed_div(Fixed::ONE, angle.cos().abs()))
+            .collect();
+
+        // Calculate the light levels to use for each level / scale
+        // combination
+        let mut scale_light: [[usize; MAXLIGHTSCALE]; LIGHTLEVELS] =
+            [[0; MAXLIGHTSCALE]; LIGHTLEVELS];
+        let mut z_light: [[usize; MAXLIGHTZ]; LIGHTLEVELS] = [[0; MAXLIGHTZ]; LIGHTLEVELS];
+        for i in 0..LIGHTLEVELS {
+            let start_map: i32 = ((LIGHTLEVELS - 1 - i) * 2 * NUMCOLORMAPS / LIGHTLEVELS) as i32;
+
+            for (j, colormap) in scale_light[i].iter_mut().enumerate() {
+                let level: i32 =
+                    start_map - j as i32 * SCREENWIDTH as i32 / view_width as i32 / DISTMAP;
+                *colormap = level.clamp(0, NUMCOLORMAPS as i32 - 1) as usize * 256;
+            }
+
+            // R_InitLightTables
+            for (j, colormap) in z_light[i].iter_mut().enumerate() {
+                let scale: Fixed = fixed_div(
+                    Fixed(SCREENWIDTH as i32 / 2 * FRACUNIT),
+                    Fixed((j as i32 + 1) << LIGHTZSHIFT),
+                );
+                let level: i32 = start_map - (scale.0 >> LIGHTSCALESHIFT) / DISTMAP;
+                *colormap = level.clamp(0, NUMCOLORMAPS as i32 - 1) as usize * 256;
+            }
+        }
+
+        Renderer {
+            data,
+            view: View::default(),
+            view_width,
+            view_height,
+            center_y,
+            center_x_frac,
+            center_y_frac: Fixed::from_int(center_y),
+            projection: center_x_frac,
+            view_angle_to_x,
+            x_to_view_angle,
+            clip_angle,
+            y_slope,
+            dist_scale,
+            scale_light,
+            z_light,
+            psprite_scale: Fixed(FRACUNIT * view_width as i32 / SCREENWIDTH as i32),
+            psprite_iscale: Fixed(FRACUNIT * SCREENWIDTH as i32 / view_width as i32),
+            texture_translation: Vec::new(),
+            flat_translation: Vec::new(),
+            sky_texture: 0,
+            mapped_lines: Vec::new(),
+            solid_segs: Vec::new(),
+            cur_line: 0,
+            front_sector: 0,
+            back_sector: None,
+            rw_angle1: Angle(0),
+            floor_plane: None,
+            ceiling_plane: None,
+            draw_segs: Vec::new(),
+            floor_clip: vec![0; view_width],
+            ceiling_clip: vec![0; view_width],
+            visplanes: Vec::new(),
+            openings: Vec::new(),
+            span_start: vec![0; view_height],
+            cached_height: vec![Fixed::ZERO; view_height],
+            cached_distance: vec![Fixed::ZERO; view_height],
+            cached_x_step: vec![Fixed::ZERO; view_height],
+            cached_y_step: vec![Fixed::ZERO; view_height],
+            base_x_scale: Fixed::ZERO,
+            base_y_scale: Fixed::ZERO,
+            vissprites: Vec::new(),
+            sprite_sectors: Vec::new(),
+            clip_bottom: vec![0; view_width],
+            clip_top: vec![0; view_width],
+            fuzz_pos: 0,
+        }
+    }
+
+    // R_RenderPlayerView. Draws the console player's view over the whole
+    // framebuffer
+    pub fn render_player_view(&mut self, world: &World, framebuffer: &mut Framebuffer) {
+        assert_eq!(
+            (framebuffer.width, framebuffer.height),
+            (self.view_width, self.view_height),
+            "R_RenderPlayerView: framebuffer is not the size of the view"
+        );
+
+        let player: &Player = &world.players[world.settings.console_player];
+        let Some(mo) = player.mo else {
+            return;
+        };
+        self.setup_frame(world, player, world.thinkers.mobj(mo));
+
+        // Clear buffers
+        self.mapped_lines.clear();
+        self.clear_clip_segs();
+        self.draw_segs.clear();
+        self.clear_planes();
+        self.clear_sprites(world);
+
+        // The head node is the last node output
+        let head_node: u16 = (world.level.nodes.len() as u16).wrapping_sub(1);
+        self.render_bsp_node(world, framebuffer, head_node);
+
+        self.draw_planes(framebuffer);
+        self.draw_masked(world, framebuffer);
+    }
+
+    // The automap flag the original set on lines as it drew them
+    pub fn mark_mapped_lines(&self, level: &mut Level) {
+        for line in self.mapped_lines.iter() {
+            level.lines[*line].flags |= ML_MAPPED;
+        }
+    }
+
+    // R_SetupFrame
+    fn setup_frame(&mut self, world: &World, player: &Player, mobj: &Mobj) {
+        self.view = View {
+            x: mobj.x,
+            y: mobj.y,
+            z: player.view_z,
+            angle: mobj.angle,
+            sin: mobj.angle.sin(),
+            cos: mobj.angle.cos(),
+            extra_light: player.extra_light,
+            fixed_colormap: (player.fixed_colormap != 0)
+                .then(|| player.fixed_colormap as usize * 256),
+        };
+
+        self.data.animate_pics(
+            world.level_time,
+            &mut self.texture_translation,
+            &mut self.flat_translation,
+        );
+        self.sky_texture = self.data.texture_num(
+            sky_texture_name(
+                world.settings.game_type,
+                world.settings.episode,
+                world.settings.map,
+            )
+            .as_bytes(),
+        );
+    }
+
+    // The light table row for a sector light level, adjust is the fake
+    // contrast for walls
+    fn light_row(&self, light_level: i32, adjust: i32) -> usize {
+        ((light_level >> LIGHTSEGSHIFT) + self.view.extra_light + adjust)
+            .clamp(0, LIGHTLEVELS as i32 - 1) as usize
+    }
+
+    // The colormap offset for something drawn at scale with a light row
+    fn scale_colormap(&self, light_row: usize, scale: Fixed) -> usize {
+        match self.view.fixed_colormap {
+            Some(colormap) => colormap,
+            None => {
+                let index: usize =
+                    ((scale.0 >> LIGHTSCALESHIFT) as u32 as usize).min(MAXLIGHTSCALE - 1);
+                self.scale_light[light_row][index]
+            }
+        }
+    }
+
+    // R_PointToDist
+    fn point_to_dist(&self, x: Fixed, y: Fixed) -> Fixed {
+        let mut dx: Fixed = (x - self.view.x).abs();
+        let mut dy: Fixed = (y - self.view.y).abs();
+
+        if dy > dx {
+            std::mem::swap(&mut dx, &mut dy);
+        }
+
+        let slope: u32 = ((fixed_div(dy, dx).0 >> DBITS) as u32).min(SLOPERANGE);
+        let angle: Angle = tantoangle(slope) + Angle(ANG90);
+
+        // Use as cosine
+        fixed_div(dx, angle.sin())
+    }
+
+    // R_ScaleFromGlobalAngle. Returns the texture mapping scale for the
+    // current line (horizontal span) at the given angle. rw_distance must
+    // be calculated first
+    fn scale_from_global_angle(
+        &self,
+        vis_angle: Angle,
+        normal_angle: Angle,
+        distance: Fixed,
+    ) -> Fixed {
+        let angle_a: Angle = Angle(ANG90) + (vis_angle - self.view.angle);
+        let angle_b: Angle = Angle(ANG90) + (vis_angle - normal_angle);
+
+        // Both sines are allways positive
+        let num: Fixed = fixed_mul(self.projection, angle_b.sin());
+        let den: Fixed = fixed_mul(distance, angle_a.sin());
+
+        if den.0 > num.0 >> FRACBITS {
+            fixed_div(num, den).clamp(Fixed(256), Fixed(64 * FRACUNIT))
+        } else {
+            Fixed(64 * FRACUNIT)
+        }
+    }
+
+    // R_PointToAngle
+    fn point_to_angle(&self, x: Fixed, y: Fixed) -> Angle {
+        point_to_angle(self.view.x, self.view.y, x, y)
+    }
+
+    // R_ViewAngleToX for an angle relative to the view
+    fn view_angle_to_x(&self, angle: Angle) -> i32 {
+        self.view_angle_to_x[((angle + Angle(ANG90)).0 >> ANGLETOFINESHIFT) as usize]
+    }
+}
+
+// R_InitTextureMapping
+fn init_texture_mapping(view_width: usize, center_x_frac: Fixed) -> (Vec<i32>, Vec<Angle>) {
+    // Use tangent table to generate viewangletox: viewangletox will give
+    // the next greatest x after the view angle. Calc focallength so
+    // FIELDOFVIEW angles covers SCREENWIDTH
+    let focal_length: Fixed =
+        fixed_div(center_x_frac, finetangent(FINEANGLES / 4 + FIELDOFVIEW / 2));
+    let width: i32 = view_width as i32;
+
+    let mut view_angle_to_x: Vec<i32> = (0..FINEANGLES / 2)
+        .map(|i| {
+            let tangent: Fixed = finetangent(i);
+            if tangent.0 > FRACUNIT * 2 {
+                -1
+            } else if tangent.0 < -FRACUNIT * 2 {
+                width + 1
+            } else {
+                let t: Fixed = fixed_mul(tangent, focal_length);
+                ((center_x_frac - t).0.wrapping_add(FRACUNIT - 1) >> FRACBITS).clamp(-1, width + 1)
+            }
+        })
+        .collect();
+
+    // Scan viewangletox[] to generate xtoviewangle[]: xtoviewangle will
+    // give the smallest view angle that maps to x
+    let x_to_view_angle: Vec<Angle> = (0..=width)
+        .map(|x| {
+            let i: usize = view_angle_to_x
+                .iter()
+                .position(|angle_x| *angle_x <= x)
+                .unwrap_or(FINEANGLES / 2);
+            Angle(((i as u32) << ANGLETOFINESHIFT).wrapping_sub(ANG90))
+        })
+        .collect();
+
+    // Take out the fencepost cases from viewangletox
+    for x in view_angle_to_x.iter_mut() {
+        if *x == -1 {
+            *x = 0;
+        } else if *x == width + 1 {
+            *x = width;
+        }
+    }
+
+    (view_angle_to_x, x_to_view_angle)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Angle, Fixed, ANG90, FRACUNIT};
+    use crate::game::Game;
+    use crate::graphics::Palette;
+    use crate::play::level::ML_MAPPED;
+    use crate::play::pspr::WEAPONTOP;
+    use crate::play::ticcmd::TicCmd;
+    use crate::render::test_wad::{check_golden, test_game, test_renderer, START};
+    use crate::render::{Framebuffer, Renderer, SCREENHEIGHT, SCREENWIDTH};
+    use crate::wad;
+
+    // Renders the scene from a player start with the pistol raised
+    fn render_scene(start: (i16, i16, i16), scale: usize) -> (Game, Renderer, Framebuffer) {
+        let mut game: Game = test_game(start);
+        // The view height is set by the first tic
+        game.tick(&[TicCmd::default()]);
+        game.world.players[0].psprites[0].sy = WEAPONTOP;
+        let mut renderer: Renderer = Renderer::new(&mut game.doom_files, scale);
+        let mut framebuffer: Framebuffer = Framebuffer::new(scale);
+
+        renderer.render_player_view(&game.world, &mut framebuffer);
+        (game, renderer, framebuffer)
+    }
+
+    fn palette(game: &mut Game) -> Palette {
+        Palette::from_playpal(&wad::get_lump_data(&mut game.doom_files, "PLAYPAL"), 0).unwrap()
+    }
+
+    #[test]
+    fn test_framebuffer_new_scales_the_screen() {
+        let framebuffer: Framebuffer = Framebuffer::new(2);
+        assert_eq!(framebuffer.width, SCREENWIDTH * 2);
+        assert_eq!(framebuffer.height, SCREENHEIGHT * 2);
+        assert_eq!(framebuffer.pixels.len(), SCREENWIDTH * SCREENHEIGHT * 4);
+    }
+
+    #[test]
+    fn test_with_data_covers_the_field_of_view() {
+        let renderer: Renderer = test_renderer(1);
+
+        // Just over 90 degrees across the screen, centered on the view
+        assert!(
+            renderer.clip_angle.0 > ANG90 / 2 && renderer.clip_angle.0 < ANG90 / 2 + ANG90 / 256
+        );
+        assert_eq!(renderer.view_angle_to_x(Angle(0)), 160);
+        assert_eq!(renderer.view_angle_to_x(renderer.clip_angle), 0);
+        assert_eq!(renderer.view_angle_to_x(-renderer.clip_angle), 320);
+        assert_eq!(renderer.x_to_view_angle.len(), SCREENWIDTH + 1);
+        assert!(renderer
+            .x_to_view_angle
+            .windows(2)
+            .all(|pair| (pair[0] - pair[1]).0 < ANG90));
+
+        // Sprites and the sky are drawn 1:1 at the original size
+        assert_eq!(renderer.psprite_scale, Fixed(FRACUNIT));
+        assert_eq!(test_renderer(2).psprite_scale, Fixed(2 * FRACUNIT));
+    }
+
+    #[test]
+    fn test_scale_light_gets_darker_with_distance() {
+        let renderer: Renderer = test_renderer(1);
+
+        // Full bright sectors stay full bright all the way back
+        assert_eq!(renderer.scale_light[15], [0; 48]);
+        assert!(renderer.scale_light[8][0] > renderer.scale_light[8][47]);
+        assert_eq!(renderer.scale_light[0][0], 31 * 256);
+        assert!(renderer.z_light[8][0] < renderer.z_light[8][127]);
+    }
+
+    #[test]
+    fn test_render_player_view_matches_golden_start() {
+        let (mut game, _, framebuffer) = render_scene(START, 1);
+        check_golden("render-start", &framebuffer.to_png(&palette(&mut game)));
+    }
+
+    #[test]
+    fn test_render_player_view_matches_golden_back_view() {
+        // From the sky lit room looking back through the grate
+        let (mut game, _, framebuffer) = render_scene((568, 168, 180), 1);
+        check_golden("render-back-view", &framebuffer.to_png(&palette(&mut game)));
+    }
+
+    #[test]
+    fn test_render_player_view_matches_golden_at_double_scale() {
+        let (mut game, _, framebuffer) = render_scene(START, 2);
+        check_golden("render-start-2x", &framebuffer.to_png(&palette(&mut game)));
+    }
+
+    #[test]
+    fn test_render_player_view_draws_every_pixel() {
+        let (_, _, mut framebuffer) = render_scene(START, 1);
+        let first: Vec<u8> = framebuffer.pixels.clone();
+
+        // Nothing is left over from the last frame
+        let (game, mut renderer, _) = render_scene(START, 1);
+        framebuffer.pixels.fill(0xff);
+        renderer.render_player_view(&game.world, &mut framebuffer);
+        assert_eq!(framebuffer.pixels, first);
+    }
+
+    #[test]
+    fn test_mark_mapped_lines_marks_the_lines_seen() {
+        let (mut game, renderer, _) = render_scene(START, 1);
+        renderer.mark_mapped_lines(&mut game.world.level);
+
+        // The far wall is seen through the rooms, the wall behind isn't
+        let lines = &game.world.level.lines;
+        assert_ne!(lines[7].flags & ML_MAPPED, 0);
+        assert_eq!(lines[6].flags & ML_MAPPED, 0);
+    }
+}
@@ -0,0 +1,460 @@
+// Port of the original r_bsp.c. Walks the BSP tree front to back from the
+// view point, clipping segs against the columns solid walls already cover
+use crate::fixed::{Angle, Fixed, ANG180};
+use crate::play::level::{
+    names_equal, Node, Sector, Seg, Subsector, BOXBOTTOM, BOXLEFT, BOXRIGHT, BOXTOP, NF_SUBSECTOR,
+};
+use crate::play::world::World;
+use crate::render::{Framebuffer, Renderer};
+
+// Which corners of a bounding box to check for each of the view point's 9
+// positions around it, as (x1, y1, x2, y2) indexes into the box
+const CHECKCOORD: [[usize; 4]; 12] = [
+    [3, 0, 2, 1],
+    [3, 0, 2, 0],
+    [3, 1, 2, 0],
+    [0, 0, 0, 0],
+    [2, 0, 2, 1],
+    [0, 0, 0, 0],
+    [3, 1, 3, 0],
+    [0, 0, 0, 0],
+    [2, 0, 3, 1],
+    [2, 1, 3, 1],
+    [2, 1, 3, 0],
+    [0, 0, 0, 0],
+];
+
+// A range of columns a solid wall covers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClipRange {
+    pub first: i32,
+    pub last: i32,
+}
+
+impl Renderer {
+    // R_ClearClipSegs
+    pub(super) fn clear_clip_segs(&mut self) {
+        self.solid_segs.clear();
+        self.solid_segs.push(ClipRange {
+            first: -0x7fffffff,
+            last: -1,
+        });
+        self.solid_segs.push(ClipRange {
+            first: self.view_width as i32,
+            last: 0x7fffffff,
+        });
+    }
+
+    // R_ClipSolidWallSegment. Does handle solid walls, e.g. single sided
+    // LineDefs (middle texture) that entirely block the view
+    fn clip_solid_wall_segment(
+        &mut self,
+        world: &World,
+        framebuffer: &mut Framebuffer,
+        first: i32,
+        last: i32,
+    ) {
+        // Find the first range that touches the range (adjacent pixels are
+        // touching)
+        let mut start: usize = 0;
+        while self.solid_segs[start].last < first - 1 {
+            start += 1;
+        }
+
+        if first < self.solid_segs[start].first {
+            if last < self.solid_segs[start].first - 1 {
+                // Post is entirely visible (above start), so insert a new
+                // clippost
+                self.store_wall_range(world, framebuffer, first, last);
+                self.solid_segs.insert(start, ClipRange { first, last });
+                return;
+            }
+
+            // There is a fragment above *start
+            let start_first: i32 = self.solid_segs[start].first;
+            self.store_wall_range(world, framebuffer, first, start_first - 1);
+            // Now adjust the clip size
+            self.solid_segs[start].first = first;
+        }
+
+        // Bottom contained in start?
+        if last <= self.solid_segs[start].last {
+            return;
+        }
+
+        let mut next: usize = start;
+        let mut crunched: bool = false;
+        while last >= self.solid_segs[next + 1].first - 1 {
+            // There is a fragment between two posts
+            let (from, to): (i32, i32) = (
+                self.solid_segs[next].last + 1,
+                self.solid_segs[next + 1].first - 1,
+            );
+            self.store_wall_range(world, framebuffer, from, to);
+            next += 1;
+
+            if last <= self.solid_segs[next].last {
+                // Bottom is contained in next. Adjust the clip size
+                self.solid_segs[start].last = self.solid_segs[next].last;
+                crunched = true;
+                break;
+            }
+        }
+
+        if !crunched {
+            // There is a fragment after *next
+            let from: i32 = self.solid_segs[next].last + 1;
+            self.store_wall_range(world, framebuffer, from, last);
+            // Adjust the clip size
+            self.solid_segs[start].last = last;
+        }
+
+        // Remove start+1 to next from the clip list, because start now
+        // covers their area
+        self.solid_segs.drain(start + 1..=next);
+    }
+
+    // R_ClipPassWallSegment. Clips the given range of columns, but does not
+    // includes it in the clip list. Does handle windows, e.g. LineDefs with
+    // upper and lower texture
+    fn clip_pass_wall_segment(
+        &mut self,
+        world: &World,
+        framebuffer: &mut Framebuffer,
+        first: i32,
+        last: i32,
+    ) {
+        // Find the first range that touches the range (adjacent pixels are
+        // touching)
+        let mut start: usize = 0;
+        while self.solid_segs[start].last < first - 1 {
+            start += 1;
+        }
+
+        if first < self.solid_segs[start].first {
+            if last < self.solid_segs[start].first - 1 {
+                // Post is entirely visible (above start)
+                self.store_wall_range(world, framebuffer, first, last);
+                return;
+            }
+
+            // There is a fragment above *start
+            let start_first: i32 = self.solid_segs[start].first;
+            self.store_wall_range(world, framebuffer, first, start_first - 1);
+        }
+
+        // Bottom contained in start?
+        if last <= self.solid_segs[start].last {
+            return;
+        }
+
+        while last >= self.solid_segs[start + 1].first - 1 {
+            // There is a fragment between two posts
+            let (from, to): (i32, i32) = (
+                self.solid_segs[start].last + 1,
+                self.solid_segs[start + 1].first - 1,
+            );
+            self.store_wall_range(world, framebuffer, from, to);
+            start += 1;
+
+            if last <= self.solid_segs[start].last {
+                return;
+            }
+        }
+
+        // There is a fragment after *next
+        let from: i32 = self.solid_segs[start].last + 1;
+        self.store_wall_range(world, framebuffer, from, last);
+    }
+
+    // R_AddLine. Clips the given segment and adds any visible pieces to the
+    // line list
+    fn add_line(&mut self, world: &World, framebuffer: &mut Framebuffer, line: usize) {
+        let level = &world.level;
+        let seg: &Seg = &level.segs[line];
+        self.cur_line = line;
+
+        // OPTIMIZE: quickly reject orthogonal back sides
+        let mut angle1: Angle =
+            self.point_to_angle(level.vertexes[seg.v1].x, level.vertexes[seg.v1].y);
+        let mut angle2: Angle =
+            self.point_to_angle(level.vertexes[seg.v2].x, level.vertexes[seg.v2].y);
+
+        // Clip to view edges
+        let span: Angle = angle1 - angle2;
+
+        // Back side? I.e. backface culling?
+        if span.0 >= ANG180 {
+            return;
+        }
+
+        // Global angle needed by segcalc
+        self.rw_angle1 = angle1;
+        angle1 -= self.view.angle;
+        angle2 -= self.view.angle;
+
+        let Some((angle1, angle2)) = self.clip_to_view(angle1, angle2, span) else {
+            return;
+        };
+
+        // The seg is in the view range, but not necessarily visible
+        let x1: i32 = self.view_angle_to_x(angle1);
+        let x2: i32 = self.view_angle_to_x(angle2);
+
+        // Does not cross a pixel?
+        if x1 == x2 {
+            return;
+        }
+
+        self.back_sector = seg.back_sector;
+        let front: &Sector = &level.sectors[self.front_sector];
+
+        let Some(back) = seg.back_sector.map(|back| &level.sectors[back]) else {
+            // Single sided line?
+            self.clip_solid_wall_segment(world, framebuffer, x1, x2 - 1);
+            return;
+        };
+
+        // Closed door
+        if back.ceiling_height <= front.floor_height || back.floor_height >= front.ceiling_height {
+            self.clip_solid_wall_segment(world, framebuffer, x1, x2 - 1);
+            return;
+        }
+
+        // Reject empty lines used for triggers and special events. Identical
+        // floor and ceiling on both sides, identical light levels on both
+        // sides, and no middle texture
+        if back.ceiling_height == front.ceiling_height
+            && back.floor_height == front.floor_height
+            && names_equal(&back.ceiling_pic, &front.ceiling_pic)
+            && names_equal(&back.floor_pic, &front.floor_pic)
+            && back.light_level == front.light_level
+            && self
+                .data
+                .texture_num(&level.sides[seg.side_def].mid_texture)
+                == 0
+        {
+            return;
+        }
+
+        // Window
+        self.clip_pass_wall_segment(world, framebuffer, x1, x2 - 1);
+    }
+
+    // Clips the angles of a line's ends relative to the view to the field
+    // of view, None if it's entirely outside of it
+    fn clip_to_view(
+        &self,
+        mut angle1: Angle,
+        mut angle2: Angle,
+        span: Angle,
+    ) -> Option<(Angle, Angle)> {
+        let double_clip: u32 = self.clip_angle.0.wrapping_mul(2);
+
+        let tspan: u32 = (angle1 + self.clip_angle).0;
+        if tspan > double_clip {
+            // Totally off the left edge?
+            if tspan - double_clip >= span.0 {
+                return None;
+            }
+            angle1 = self.clip_angle;
+        }
+
+        let tspan: u32 = (self.clip_angle - angle2).0;
+        if tspan > double_clip {
+            // Totally off the left edge?
+            if tspan - double_clip >= span.0 {
+                return None;
+            }
+            angle2 = -self.clip_angle;
+        }
+
+        Some((angle1, angle2))
+    }
+
+    // R_CheckBBox. Checks BSP node/subtree bounding box. Returns true if
+    // some part of the bbox might be visible
+    fn check_bbox(&self, bbox: &[Fixed; 4]) -> bool {
+        // Find the corners of the box that define the edges from current
+        // viewpoint
+        let box_x: usize = if self.view.x <= bbox[BOXLEFT] {
+            0
+        } else if self.view.x < bbox[BOXRIGHT] {
+            1
+        } else {
+            2
+        };
+
+        let box_y: usize = if self.view.y >= bbox[BOXTOP] {
+            0
+        } else if self.view.y > bbox[BOXBOTTOM] {
+            1
+        } else {
+            2
+        };
+
+        let box_pos: usize = (box_y << 2) + box_x;
+        if box_pos == 5 {
+            return true;
+        }
+
+        let [x1, y1, x2, y2]: [usize; 4] = CHECKCOORD[box_pos];
+
+        // Check clip list for an open space
+        let angle1: Angle = self.point_to_angle(bbox[x1], bbox[y1]) - self.view.angle;
+        let angle2: Angle = self.point_to_angle(bbox[x2], bbox[y2]) - self.view.angle;
+
+        let span: Angle = angle1 - angle2;
+
+        // Sitting on a line?
+        if span.0 >= ANG180 {
+            return true;
+        }
+
+        let Some((angle1, angle2)) = self.clip_to_view(angle1, angle2, span) else {
+            return false;
+        };
+
+        // Find the first clippost that touches the source post (adjacent
+        // pixels are touching)
+        let sx1: i32 = self.view_angle_to_x(angle1);
+        let sx2: i32 = self.view_angle_to_x(angle2);
+
+        // Does not cross a pixel
+        if sx1 == sx2 {
+            return false;
+        }
+        let sx2: i32 = sx2 - 1;
+
+        let start: &ClipRange = self
+            .solid_segs
+            .iter()
+            .find(|range| range.last >= sx2)
+            .expect("the last clip range covers every column");
+
+        // The clippost contains the new span
+        !(sx1 >= start.first && sx2 <= start.last)
+    }
+
+    // R_Subsector. Determine floor/ceiling planes. Add sprites of things in
+    // sector. Draw one or more line segments
+    fn subsector(&mut self, world: &World, framebuffer: &mut Framebuffer, num: usize) {
+        let level = &world.level;
+        let subsector: Subsector = level.subsectors[num];
+        let front: &Sector = &level.sectors[subsector.sector];
+        self.front_sector = subsector.sector;
+
+        self.floor_plane = if front.floor_height < self.view.z {
+            let pic: usize = self.data.flat_num(&front.floor_pic);
+            Some(self.find_plane(front.floor_height, pic, front.light_level))
+        } else {
+            None
+        };
+
+        let ceiling_pic: usize = self.data.flat_num(&front.ceiling_pic);
+        self.ceiling_plane =
+            if front.ceiling_height > self.view.z || ceiling_pic == self.data.sky_flat {
+                Some(self.find_plane(front.ceiling_height, ceiling_pic, front.light_level))
+            } else {
+                None
+            };
+
+        self.add_sprites(world, subsector.sector);
+
+        for line in subsector.first_line..subsector.first_line + subsector.num_lines {
+            self.add_line(world, framebuffer, line);
+        }
+    }
+
+    // R_RenderBSPNode. Renders all subsectors below a given node, traversing
+    // subtree recursively. Just call with BSP root
+    pub(super) fn render_bsp_node(
+        &mut self,
+        world: &World,
+        framebuffer: &mut Framebuffer,
+        bsp_num: u16,
+    ) {
+        // Found a subsector?
+        if bsp_num & NF_SUBSECTOR != 0 {
+            if bsp_num == u16::MAX {
+                self.subsector(world, framebuffer, 0);
+            } else {
+                self.subsector(world, framebuffer, usize::from(bsp_num & !NF_SUBSECTOR));
+            }
+            return;
+        }
+
+        let node: Node = world.level.nodes[usize::from(bsp_num)];
+
+        // Decide which side the view point is on
+        let side: usize = node.point_on_side(self.view.x, self.view.y);
+
+        // Recursively divide front space
+        self.render_bsp_node(world, framebuffer, node.children[side]);
+
+        // Possibly divide back space
+        if self.check_bbox(&node.bbox[side ^ 1]) {
+            self.render_bsp_node(world, framebuffer, node.children[side ^ 1]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::play::world::World;
+    use crate::render::bsp::ClipRange;
+    use crate::render::test_wad::{test_renderer, test_world};
+    use crate::render::{Framebuffer, Renderer};
+
+    #[test]
+    fn test_clip_solid_wall_segment_merges_ranges() {
+        let world: World = test_world();
+        let mut framebuffer: Framebuffer = Framebuffer::new(1);
+        let mut renderer: Renderer = test_renderer(1);
+        renderer.render_player_view(&world, &mut framebuffer);
+        renderer.clear_clip_segs();
+        renderer.clear_planes();
+        renderer.draw_segs.clear();
+        renderer.back_sector = None;
+        renderer.floor_plane = None;
+        renderer.ceiling_plane = None;
+
+        renderer.clip_solid_wall_segment(&world, &mut framebuffer, 10, 20);
+        renderer.clip_solid_wall_segment(&world, &mut framebuffer, 40, 50);
+        assert_eq!(renderer.solid_segs.len(), 4);
+        assert_eq!(renderer.draw_segs.len(), 2);
+
+        // Only the gap between them is drawn
+        renderer.clip_solid_wall_segment(&world, &mut framebuffer, 5, 60);
+        assert_eq!(renderer.solid_segs[1], ClipRange { first: 5, last: 60 });
+        assert_eq!(renderer.solid_segs.len(), 3);
+        let drawn: Vec<(i32, i32)> = renderer.draw_segs[2..]
+            .iter()
+            .map(|draw_seg| (draw_seg.x1, draw_seg.x2))
+            .collect();
+        assert_eq!(drawn, vec![(5, 9), (21, 39), (51, 60)]);
+    }
+
+    #[test]
+    fn test_clip_pass_wall_segment_leaves_the_clip_list() {
+        let world: World = test_world();
+        let mut framebuffer: Framebuffer = Framebuffer::new(1);
+        let mut renderer: Renderer = test_renderer(1);
+        renderer.render_player_view(&world, &mut framebuffer);
+        renderer.clear_clip_segs();
+        renderer.clear_planes();
+        renderer.draw_segs.clear();
+        renderer.back_sector = None;
+        renderer.floor_plane = None;
+        renderer.ceiling_plane = None;
+
+        renderer.clip_solid_wall_segment(&world, &mut framebuffer, 10, 99);
+        renderer.clip_pass_wall_segment(&world, &mut framebuffer, 50, 150);
+
+        assert_eq!(renderer.solid_segs.len(), 3);
+        assert_eq!(
+            (renderer.draw_segs[1].x1, renderer.draw_segs[1].x2),
+            (100, 150)
+        );
+    }
+}
@@ -0,0 +1,731 @@
+// Port of the original r_data.c, the sprite setup from r_things.c and the
+// animated pic setup from p_spec.c. Loads everything the renderer draws
+// with out of the wads up front
+use std::collections::HashMap;
+
+use crate::fixed::Fixed;
+use crate::graphics::{Picture, FLAT_HEIGHT, FLAT_WIDTH};
+use crate::info::sprites::SPRNAMES;
+use crate::play::level::SKYFLATNAME;
+use crate::render::NUMCOLORMAPS;
+use crate::wad::{self, read_lump_data, DoomFile};
+
+// The 32 light levels, the invulnerability map and an all black one
+pub const COLORMAP_SIZE: usize = (NUMCOLORMAPS + 2) * 256;
+const MAPTEXTURE_SIZE: usize = 22;
+const MAPPATCH_SIZE: usize = 10;
+// Frame letters run from A to ]
+const MAX_SPRITE_FRAMES: usize = 29;
+
+// (is texture, end name, start name, speed) of the flats and textures
+// that cycle through the pics between them
+const ANIMDEFS: [(bool, &str, &str, i32); 22] = [
+    (false, "NUKAGE3", "NUKAGE1", 8),
+    (false, "FWATER4", "FWATER1", 8),
+    (false, "SWATER4", "SWATER1", 8),
+    (false, "LAVA4", "LAVA1", 8),
+    (false, "BLOOD3", "BLOOD1", 8),
+    // DOOM II flat animations.
+    (false, "RROCK08", "RROCK05", 8),
+    (false, "SLIME04", "SLIME01", 8),
+    (false, "SLIME08", "SLIME05", 8),
+    (false, "SLIME12", "SLIME09", 8),
+    (true, "BLODGR4", "BLODGR1", 8),
+    (true, "SLADRIP3", "SLADRIP1", 8),
+    (true, "BLODRIP4", "BLODRIP1", 8),
+    (true, "FIREWALL", "FIREWALA", 8),
+    (true, "GSTFONT3", "GSTFONT1", 8),
+    (true, "FIRELAVA", "FIRELAV3", 8),
+    (true, "FIREMAG3", "FIREMAG1", 8),
+    (true, "FIREBLU2", "FIREBLU1", 8),
+    (true, "ROCKRED3", "ROCKRED1", 8),
+    (true, "BFALL4", "BFALL1", 8),
+    (true, "SFALL4", "SFALL1", 8),
+    (true, "WFALL4", "WFALL1", 8),
+    (true, "DBRAIN4", "DBRAIN1", 8),
+];
+
+// A patch_t split into columns for drawing. Transparent texels are 0 so
+// walls can draw any column as a solid strip
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Patch {
+    pub width: usize,
+    pub height: usize,
+    pub left_offset: i32,
+    pub top_offset: i32,
+    // Column by column
+    pub texels: Vec<u8>,
+    // (top, length) of the opaque runs in each column, the original's posts
+    pub posts: Vec<Vec<(usize, usize)>>,
+}
+
+impl Patch {
+    pub fn from_picture(picture: &Picture) -> Self {
+        let mut texels: Vec<u8> = vec![0; picture.width * picture.height];
+        let mut posts: Vec<Vec<(usize, usize)>> = vec![Vec::new(); picture.width];
+
+        for (x, column_posts) in posts.iter_mut().enumerate() {
+            let mut post_start: Option<usize> = None;
+            for y in 0..=picture.height {
+                let pixel: Option<u8> = if y < picture.height {
+                    picture.pixels[y * picture.width + x]
+                } else {
+                    None
+                };
+
+                match (pixel, post_start) {
+                    (Some(pixel), None) => {
+                        texels[x * picture.height + y] = pixel;
+                        post_start = Some(y);
+                    }
+                    (Some(pixel), Some(_)) => texels[x * picture.height + y] = pixel,
+                    (None, Some(start)) => {
+                        column_posts.push((start, y - start));
+                        post_start = None;
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+
+        Patch {
+            width: picture.width,
+            height: picture.height,
+            left_offset: i32::from(picture.left_offset),
+            top_offset: i32::from(picture.top_offset),
+            texels,
+            posts,
+        }
+    }
+
+    pub fn column(&self, x: usize) -> &[u8] {
+        &self.texels[x * self.height..(x + 1) * self.height]
+    }
+
+    // R_GetColumn. Columns repeat across the width, the original masked
+    // with the next power of two and read past the end of other widths
+    pub fn wrapped_column(&self, x: i32) -> usize {
+        x.rem_euclid(self.width as i32) as usize
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Texture {
+    pub name: [u8; 8],
+    pub patch: Patch,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SpriteFrame {
+    // If false use 0 for any position
+    pub rotate: bool,
+    // Lump to use for view angles 0-7, indexes into sprite_patches
+    pub lumps: [usize; 8],
+    // Flip bit (1 = flip) to use for view angles 0-7
+    pub flip: [bool; 8],
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpriteDef {
+    pub frames: Vec<SpriteFrame>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Anim {
+    pub is_texture: bool,
+    pub base_pic: usize,
+    pub num_pics: usize,
+    pub speed: i32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderData {
+    pub colormaps: Vec<u8>,
+    // In TEXTURE1 then TEXTURE2 order, texture 0 is never drawn
+    pub textures: Vec<Texture>,
+    pub flats: Vec<Vec<u8>>,
+    pub sprite_patches: Vec<Patch>,
+    // Indexed by SpriteNum
+    pub sprites: Vec<SpriteDef>,
+    // Gray, brown and red remappings of the green player colours
+    pub translation_tables: Vec<u8>,
+    pub anims: Vec<Anim>,
+    pub sky_flat: usize,
+    texture_numbers: HashMap<[u8; 8], usize>,
+    flat_numbers: HashMap<[u8; 8], usize>,
+}
+
+// Where every lump in the loaded files is, later files come after the
+// earlier ones like the original's single lump directory
+struct LumpDirectory {
+    lumps: Vec<(usize, usize, [u8; 8])>,
+}
+
+impl LumpDirectory {
+    fn new(doom_files: &[DoomFile]) -> Self {
+        let lumps: Vec<(usize, usize, [u8; 8])> = doom_files
+            .iter()
+            .enumerate()
+            .flat_map(|(file, doom_file)| {
+                doom_file
+                    .lumps
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, lump)| (file, index, name_key(lump.name.as_bytes())))
+            })
+            .collect();
+
+        LumpDirectory { lumps }
+    }
+
+    // W_CheckNumForName, the last lump with the name wins
+    fn check_num_for_name(&self, name: &[u8]) -> Option<usize> {
+        let key: [u8; 8] = name_key(name);
+        self.lumps.iter().rposition(|(_, _, name)| *name == key)
+    }
+
+    fn get_num_for_name(&self, name: &str) -> usize {
+        self.check_num_for_name(name.as_bytes())
+            .unwrap_or_else(|| panic!("W_GetNumForName: {} not found!", name))
+    }
+
+    fn read(&self, doom_files: &mut [DoomFile], lump: usize) -> Vec<u8> {
+        let (file, index, _) = self.lumps[lump];
+        read_lump_data(&mut doom_files[file], index)
+    }
+}
+
+impl RenderData {
+    // R_InitData, R_InitSprites and P_InitPicAnims
+    pub fn from_wad(doom_files: &mut [DoomFile]) -> Self {
+        let directory: LumpDirectory = LumpDirectory::new(doom_files);
+
+        let mut colormaps: Vec<u8> = wad::get_lump_data(doom_files, "COLORMAP");
+        colormaps.resize(COLORMAP_SIZE, 0);
+
+        let textures: Vec<Texture> = init_textures(doom_files, &directory);
+
+        // R_InitFlats
+        let first_flat: usize = directory.get_num_for_name("F_START") + 1;
+        let last_flat: usize = directory.get_num_for_name("F_END");
+        let mut flat_names: Vec<[u8; 8]> = Vec::new();
+        let mut flats: Vec<Vec<u8>> = Vec::new();
+        for lump in first_flat..last_flat {
+            let mut flat: Vec<u8> = directory.read(doom_files, lump);
+            flat.resize(FLAT_WIDTH * FLAT_HEIGHT, 0);
+            flat_names.push(directory.lumps[lump].2);
+            flats.push(flat);
+        }
+
+        // R_InitSpriteLumps
+        let first_sprite: usize = directory.get_num_for_name("S_START") + 1;
+        let last_sprite: usize = directory.get_num_for_name("S_END");
+        let sprite_names: Vec<[u8; 8]> = (first_sprite..last_sprite)
+            .map(|lump| directory.lumps[lump].2)
+            .collect();
+        let sprite_patches: Vec<Patch> = (first_sprite..last_sprite)
+            .map(|lump| {
+                let picture: Picture = Picture::from_lump(&directory.read(doom_files, lump))
+                    .unwrap_or_else(|e| {
+                        panic!(
+                            "R_InitSpriteLumps: {} {}",
+                            key_to_string(&directory.lumps[lump].2),
+                            e
+                        )
+                    });
+                Patch::from_picture(&picture)
+            })
+            .collect();
+
+        let mut texture_numbers: HashMap<[u8; 8], usize> = HashMap::new();
+        for (index, texture) in textures.iter().enumerate() {
+            // The first texture with a name is the one used
+            texture_numbers.entry(texture.name).or_insert(index);
+        }
+        let flat_numbers: HashMap<[u8; 8], usize> = flat_names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (*name, index))
+            .collect();
+
+        let mut render_data: RenderData = RenderData {
+            colormaps,
+            textures,
+            flats,
+            sprite_patches,
+            sprites: init_sprite_defs(&sprite_names),
+            translation_tables: init_translation_tables(),
+            anims: Vec::new(),
+            sky_flat: 0,
+            texture_numbers,
+            flat_numbers,
+        };
+        render_data.sky_flat = render_data.flat_num(SKYFLATNAME);
+        render_data.anims = render_data.init_pic_anims();
+        render_data
+    }
+
+    // R_CheckTextureNumForName. "-" is the no texture marker
+    pub fn check_texture_num(&self, name: &[u8]) -> Option<usize> {
+        if name.first() == Some(&b'-') {
+            return Some(0);
+        }
+        self.texture_numbers.get(&name_key(name)).copied()
+    }
+
+    // R_TextureNumForName
+    pub fn texture_num(&self, name: &[u8]) -> usize {
+        self.check_texture_num(name).unwrap_or_else(|| {
+            panic!(
+                "R_TextureNumForName: {} not found",
+                key_to_string(&name_key(name))
+            )
+        })
+    }
+
+    pub fn check_flat_num(&self, name: &[u8]) -> Option<usize> {
+        self.flat_numbers.get(&name_key(name)).copied()
+    }
+
+    // R_FlatNumForName
+    pub fn flat_num(&self, name: &[u8]) -> usize {
+        self.check_flat_num(name).unwrap_or_else(|| {
+            panic!(
+                "R_FlatNumForName: {} not found",
+                key_to_string(&name_key(name))
+            )
+        })
+    }
+
+    pub fn texture_height(&self, texture: usize) -> Fixed {
+        Fixed::from_int(self.textures[texture].patch.height as i32)
+    }
+
+    // The animation part of P_UpdateSpecials. Fills in the pic each flat
+    // and texture number is drawn with at the level time
+    pub fn animate_pics(
+        &self,
+        level_time: i32,
+        texture_translation: &mut Vec<usize>,
+        flat_translation: &mut Vec<usize>,
+    ) {
+        texture_translation.clear();
+        texture_translation.extend(0..self.textures.len());
+        flat_translation.clear();
+        flat_translation.extend(0..self.flats.len());
+
+        for anim in self.anims.iter() {
+            for i in anim.base_pic..anim.base_pic + anim.num_pics {
+                let pic: usize =
+                    anim.base_pic + ((level_time / anim.speed) as usize + i) % anim.num_pics;
+                if anim.is_texture {
+                    texture_translation[i] = pic;
+                } else {
+                    flat_translation[i] = pic;
+                }
+            }
+        }
+    }
+
+    // P_InitPicAnims. Animations missing their first pic are left out
+    fn init_pic_anims(&self) -> Vec<Anim> {
+        let mut anims: Vec<Anim> = Vec::new();
+
+        for (is_texture, end_name, start_name, speed) in ANIMDEFS {
+            let (base_pic, pic_num): (usize, usize) = if is_texture {
+                if self.check_texture_num(start_name.as_bytes()).is_none() {
+                    continue;
+                }
+                (
+                    self.texture_num(start_name.as_bytes()),
+                    self.texture_num(end_name.as_bytes()),
+                )
+            } else {
+                if self.check_flat_num(start_name.as_bytes()).is_none() {
+                    continue;
+                }
+                (
+                    self.flat_num(start_name.as_bytes()),
+                    self.flat_num(end_name.as_bytes()),
+                )
+            };
+
+            if pic_num < base_pic + 1 {
+                panic!(
+                    "P_InitPicAnims: bad cycle from {} to {}",
+                    start_name, end_name
+                );
+            }
+
+            anims.push(Anim {
+                is_texture,
+                base_pic,
+                num_pics: pic_num - base_pic + 1,
+                speed,
+            });
+        }
+
+        anims
+    }
+}
+
+// R_InitTextures. Every texture is composited from its patches up front.
+// Columns made of more than one patch are drawn solid, the original read
+// the composited texels as posts and drew garbage in the holes
+fn init_textures(doom_files: &mut [DoomFile], directory: &LumpDirectory) -> Vec<Texture> {
+    // Load the patch names from pnames.lmp
+    let names: Vec<u8> = wad::get_lump_data(doom_files, "PNAMES");
+    let num_patches: usize = read_i32(&names, 0, "PNAMES").max(0) as usize;
+    let patch_lumps: Vec<Option<usize>> = (0..num_patches)
+        .map(|patch| {
+            let name: &[u8] = names
+                .get(4 + patch * 8..12 + patch * 8)
+                .unwrap_or_else(|| panic!("R_InitTextures: PNAMES is truncated"));
+            directory.check_num_for_name(name)
+        })
+        .collect();
+    let mut patch_cache: HashMap<usize, Patch> = HashMap::new();
+
+    let mut texture_lumps: Vec<Vec<u8>> = vec![wad::get_lump_data(doom_files, "TEXTURE1")];
+    if let Some(data) = wad::find_lump_data(doom_files, "TEXTURE2") {
+        texture_lumps.push(data);
+    }
+
+    let mut textures: Vec<Texture> = Vec::new();
+    for data in texture_lumps.iter() {
+        let num_textures: usize = read_i32(data, 0, "TEXTURE").max(0) as usize;
+
+        for index in 0..num_textures {
+            let offset: usize = usize::try_from(read_i32(data, 4 + index * 4, "TEXTURE"))
+                .unwrap_or_else(|_| panic!("R_InitTextures: bad texture directory"));
+            let name: [u8; 8] = name_key(
+                data.get(offset..offset + 8)
+                    .unwrap_or_else(|| panic!("R_InitTextures: bad texture directory")),
+            );
+            let width: usize = read_i16(data, offset + 12, "TEXTURE").max(1) as usize;
+            let height: usize = read_i16(data, offset + 14, "TEXTURE").max(1) as usize;
+            let patch_count: usize = read_i16(data, offset + 20, "TEXTURE").max(0) as usize;
+
+            let mut texels: Vec<Option<u8>> = vec![None; width * height];
+            let mut column_patches: Vec<usize> = vec![0; width];
+            let mut single_posts: Vec<Vec<(usize, usize)>> = vec![Vec::new(); width];
+
+            for patch_index in 0..patch_count {
+                let record: usize = offset + MAPTEXTURE_SIZE + patch_index * MAPPATCH_SIZE;
+                let origin_x: i32 = i32::from(read_i16(data, record, "TEXTURE"));
+                let origin_y: i32 = i32::from(read_i16(data, record + 2, "TEXTURE"));
+                let patch_num: usize = read_i16(data, record + 4, "TEXTURE").max(0) as usize;
+
+                let lump: usize = patch_lumps
+                    .get(patch_num)
+                    .copied()
+                    .flatten()
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "R_InitTextures: Missing patch in texture {}",
+                            key_to_string(&name)
+                        )
+                    });
+                let patch: &Patch = patch_cache.entry(lump).or_insert_with(|| {
+                    let picture: Picture = Picture::from_lump(&directory.read(doom_files, lump))
+                        .unwrap_or_else(|e| {
+                            panic!("R_InitTextures: {} {}", key_to_string(&name), e)
+                        });
+                    Patch::from_picture(&picture)
+                });
+
+                for patch_x in 0..patch.width {
+                    let x: i32 = origin_x + patch_x as i32;
+                    if x < 0 || x >= width as i32 {
+                        continue;
+                    }
+                    let x: usize = x as usize;
+                    column_patches[x] += 1;
+                    single_posts[x].clear();
+
+                    for (top, length) in patch.posts[patch_x].iter() {
+                        let mut post_top: Option<usize> = None;
+                        let mut post_length: usize = 0;
+                        for row in *top..top + length {
+                            let y: i32 = origin_y + row as i32;
+                            if y < 0 || y >= height as i32 {
+                                continue;
+                            }
+                            texels[x * height + y as usize] =
+                                Some(patch.texels[patch_x * patch.height + row]);
+                            post_top.get_or_insert(y as usize);
+                            post_length += 1;
+                        }
+                        if let Some(post_top) = post_top {
+                            single_posts[x].push((post_top, post_length));
+                        }
+                    }
+                }
+            }
+
+            let posts: Vec<Vec<(usize, usize)>> = single_posts
+                .into_iter()
+                .zip(column_patches.iter())
+                .map(|(posts, count)| {
+                    if *count == 1 {
+                        posts
+                    } else {
+                        vec![(0, height)]
+                    }
+                })
+                .collect();
+
+            textures.push(Texture {
+                name,
+                patch: Patch {
+                    width,
+                    height,
+                    left_offset: 0,
+                    top_offset: 0,
+                    texels: texels.iter().map(|texel| texel.unwrap_or(0)).collect(),
+                    posts,
+                },
+            });
+        }
+    }
+
+    textures
+}
+
+// R_InitSpriteDefs. Sprite lumps are named with the sprite, a frame letter
+// and a rotation number, with an optional second frame and rotation that
+// uses the same lump flipped
+fn init_sprite_defs(sprite_names: &[[u8; 8]]) -> Vec<SpriteDef> {
+    SPRNAMES
+        .iter()
+        .map(|sprite_name| {
+            let mut frames: Vec<Option<SpriteFrame>> = vec![None; MAX_SPRITE_FRAMES];
+            let mut max_frame: Option<usize> = None;
+
+            for (lump, name) in sprite_names.iter().enumerate() {
+                if &name[..4] != sprite_name.as_bytes() {
+                    continue;
+                }
+
+                install_sprite_lump(&mut frames, &mut max_frame, name, lump, 4, false);
+                if name[6] != 0 {
+                    install_sprite_lump(&mut frames, &mut max_frame, name, lump, 6, true);
+                }
+            }
+
+            // No sprite for this thing
+            let Some(max_frame) = max_frame else {
+                return SpriteDef::default();
+            };
+
+            let frames: Vec<SpriteFrame> = frames[..=max_frame]
+                .iter()
+                .enumerate()
+                .map(|(frame, sprite_frame)| {
+                    let letter: char = char::from(b'A' + frame as u8);
+                    match sprite_frame {
+                        // No rotations were found for that frame at all
+                        None => panic!(
+                            "R_InitSprites: No patches found for {} frame {}",
+                            sprite_name, letter
+                        ),
+                        // Must have all 8 frames
+                        Some(sprite_frame) if sprite_frame.lumps.contains(&usize::MAX) => panic!(
+                            "R_InitSprites: Sprite {} frame {} is missing rotations",
+                            sprite_name, letter
+                        ),
+                        Some(sprite_frame) => *sprite_frame,
+                    }
+                })
+                .collect();
+
+            SpriteDef { frames }
+        })
+        .collect()
+}
+
+// R_InstallSpriteLump. The frame and rotation are at offset in the name
+fn install_sprite_lump(
+    frames: &mut [Option<SpriteFrame>],
+    max_frame: &mut Option<usize>,
+    name: &[u8; 8],
+    lump: usize,
+    offset: usize,
+    flipped: bool,
+) {
+    let frame: usize = usize::from(name[offset].wrapping_sub(b'A'));
+    let rotation: usize = usize::from(name[offset + 1].wrapping_sub(b'0'));
+    let sprite_name: String = key_to_string(name);
+
+    if frame >= MAX_SPRITE_FRAMES || rotation > 8 {
+        panic!(
+            "R_InstallSpriteLump: Bad frame characters in lump {}",
+            sprite_name
+        );
+    }
+
+    *max_frame = Some(max_frame.map_or(frame, |max_frame| max_frame.max(frame)));
+
+    // Rotations are filled in as they're found, None until then
+    let sprite_frame: &mut SpriteFrame = frames[frame].get_or_insert(SpriteFrame {
+        rotate: rotation != 0,
+        lumps: [usize::MAX; 8],
+        flip: [false; 8],
+    });
+
+    if rotation == 0 {
+        // The lump should be used for all rotations
+        if !sprite_frame.rotate && sprite_frame.lumps[0] != usize::MAX {
+            panic!(
+                "R_InitSprites: Sprite {} frame {} has multip rot=0 lump",
+                sprite_name,
+                char::from(name[offset])
+            );
+        }
+        if sprite_frame.rotate {
+            panic!(
+                "R_InitSprites: Sprite {} frame {} has rotations and a rot=0 lump",
+                sprite_name,
+                char::from(name[offset])
+            );
+        }
+
+        sprite_frame.lumps = [lump; 8];
+        sprite_frame.flip = [flipped; 8];
+        return;
+    }
+
+    // The lump is only used for one rotation
+    if !sprite_frame.rotate {
+        panic!(
+            "R_InitSprites: Sprite {} frame {} has rotations and a rot=0 lump",
+            sprite_name,
+            char::from(name[offset])
+        );
+    }
+
+    let rotation: usize = rotation - 1;
+    if sprite_frame.lumps[rotation] != usize::MAX {
+        panic!(
+            "R_InitSprites: Sprite {} : {} : {} has two lumps mapped to it",
+            sprite_name,
+            char::from(name[offset]),
+            char::from(name[offset + 1])
+        );
+    }
+
+    sprite_frame.lumps[rotation] = lump;
+    sprite_frame.flip[rotation] = flipped;
+}
+
+// R_InitTranslationTables. Translate just the 16 green colors
+fn init_translation_tables() -> Vec<u8> {
+    let mut tables: Vec<u8> = vec![0; 256 * 3];
+
+    for i in 0..256 {
+        let index: u8 = i as u8;
+        if (0x70..=0x7f).contains(&index) {
+            // map green ramp to gray, brown, red
+            tables[i] = 0x60 + (index & 0xf);
+            tables[i + 256] = 0x40 + (index & 0xf);
+            tables[i + 512] = 0x20 + (index & 0xf);
+        } else {
+            // Keep all other colors as is
+            tables[i] = index;
+            tables[i + 256] = index;
+            tables[i + 512] = index;
+        }
+    }
+
+    tables
+}
+
+// Upper cased with everything after a terminating NUL cleared so names can
+// be looked up the way the original compared them
+pub fn name_key(name: &[u8]) -> [u8; 8] {
+    let mut key: [u8; 8] = [0; 8];
+    for (key, c) in key.iter_mut().zip(name.iter().take_while(|c| **c != 0)) {
+        *key = c.to_ascii_uppercase();
+    }
+    key
+}
+
+fn key_to_string(key: &[u8; 8]) -> String {
+    String::from_utf8_lossy(key)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+fn read_i16(data: &[u8], offset: usize, lump_name: &str) -> i16 {
+    match data.get(offset..offset + 2) {
+        Some(bytes) => i16::from_le_bytes([bytes[0], bytes[1]]),
+        None => panic!("R_InitTextures: {} is truncated", lump_name),
+    }
+}
+
+fn read_i32(data: &[u8], offset: usize, lump_name: &str) -> i32 {
+    match data.get(offset..offset + 4) {
+        Some(bytes) => i32::from_le_bytes(bytes.try_into().unwrap()),
+        None => panic!("R_InitTextures: {} is truncated", lump_name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::info::sprites::{SPR_BAR1, SPR_POSS, SPR_TROO};
+    use crate::render::data::{name_key, Anim, Patch, RenderData};
+    use crate::render::test_wad::test_renderer;
+
+    #[test]
+    fn test_from_wad_composites_textures() {
+        let data: RenderData = test_renderer(1).data;
+
+        // "-" is no texture and lookups ignore case
+        assert_eq!(data.check_texture_num(b"-"), Some(0));
+        assert_eq!(data.check_texture_num(b"startan3"), Some(1));
+        assert_eq!(data.check_texture_num(b"NOTHERE"), None);
+
+        // Columns with the dot over the stripes are solid
+        let two_patch: &Patch = &data.textures[data.texture_num(b"TWOPATCH")].patch;
+        assert_eq!(two_patch.posts[0], vec![(0, 64)]);
+        assert_eq!(two_patch.posts[30], vec![(0, 64)]);
+        assert_ne!(two_patch.column(30)[30], two_patch.column(30)[0]);
+
+        // A single patch keeps its holes
+        let grate: &Patch = &data.textures[data.texture_num(b"MIDGRATE")].patch;
+        assert_eq!(grate.posts[8], vec![(0, 4), (32, 4), (64, 4), (96, 4)]);
+        assert_eq!(grate.wrapped_column(-1), 63);
+    }
+
+    #[test]
+    fn test_from_wad_installs_sprite_frames() {
+        let data: RenderData = test_renderer(1).data;
+
+        assert_eq!(data.sprites[SPR_POSS].frames.len(), 2);
+        assert!(!data.sprites[SPR_POSS].frames[1].rotate);
+        assert_eq!(data.sprites[SPR_BAR1].frames[0].lumps, [2; 8]);
+        assert!(data.sprites[SPR_TROO].frames.is_empty());
+        assert_eq!(data.sky_flat, data.flat_num(b"F_SKY1"));
+    }
+
+    #[test]
+    fn test_animate_pics_cycles_through_the_range() {
+        let mut data: RenderData = test_renderer(1).data;
+        data.anims = vec![Anim {
+            is_texture: true,
+            base_pic: 1,
+            num_pics: 3,
+            speed: 8,
+        }];
+        let mut textures: Vec<usize> = Vec::new();
+        let mut flats: Vec<usize> = Vec::new();
+
+        data.animate_pics(8, &mut textures, &mut flats);
+
+        // The original offset by the pic number rather than its place in the
+        // cycle
+        assert_eq!(textures[..5], [0, 3, 1, 2, 4]);
+        assert_eq!(flats, (0..data.flats.len()).collect::<Vec<usize>>());
+        assert_eq!(name_key(b"sky1\0junk"), *b"SKY1\0\0\0\0");
+    }
+}
@@ -0,0 +1,264 @@
+// Port of the column and span drawers from the original r_draw.c
+use crate::fixed::{Fixed, FRACBITS};
+use crate::render::Framebuffer;
+
+// Shadows draw the pixel a row above or below darkened, in this order
+const FUZZTABLE: usize = 50;
+const FUZZ_OFFSETS: [i32; FUZZTABLE] = [
+    1, -1, 1, -1, 1, 1, -1, 1, 1, -1, 1, 1, 1, -1, 1, 1, 1, -1, -1, -1, -1, 1, -1, -1, 1, 1, 1, 1,
+    -1, 1, -1, 1, 1, -1, -1, 1, 1, -1, -1, -1, -1, 1, 1, 1, 1, -1, 1, 1, -1, 1,
+];
+
+// Flats are always 64x64
+const FLAT_SHIFT: u32 = 6;
+const FLAT_MASK: i32 = 63;
+
+// The dc_ globals the column drawers read. The source is one column of a
+// texture or a single post of a patch
+#[derive(Clone, Copy, Debug)]
+pub struct Column<'a> {
+    pub x: i32,
+    pub yl: i32,
+    pub yh: i32,
+    pub iscale: Fixed,
+    pub texture_mid: Fixed,
+    pub source: &'a [u8],
+}
+
+// How a column's texels are turned into screen pixels
+#[derive(Clone, Copy, Debug)]
+pub enum ColumnStyle<'a> {
+    Lit(&'a [u8]),
+    // Colormap and the player colour translation applied before it
+    Translated(&'a [u8], &'a [u8]),
+    // Shadows ignore the source and darken the screen with this colormap
+    Fuzz(&'a [u8]),
+}
+
+// The ds_ globals the span drawer reads
+#[derive(Clone, Copy, Debug)]
+pub struct Span<'a> {
+    pub y: i32,
+    pub x1: i32,
+    pub x2: i32,
+    pub x_frac: Fixed,
+    pub y_frac: Fixed,
+    pub x_step: Fixed,
+    pub y_step: Fixed,
+    pub source: &'a [u8],
+    pub colormap: &'a [u8],
+}
+
+// R_DrawColumn. The source repeats every source length rows, the original
+// masked with 127 and read past the end of shorter columns
+pub fn draw_column(framebuffer: &mut Framebuffer, center_y: i32, column: &Column, colormap: &[u8]) {
+    draw_shaded_column(framebuffer, center_y, column, |texel| {
+        colormap[usize::from(texel)]
+    });
+}
+
+// R_DrawTranslatedColumn. Used to draw player sprites with the green
+// ramp remapped to gray, brown or red
+pub fn draw_translated_column(
+    framebuffer: &mut Framebuffer,
+    center_y: i32,
+    column: &Column,
+    colormap: &[u8],
+    translation: &[u8],
+) {
+    draw_shaded_column(framebuffer, center_y, column, |texel| {
+        colormap[usize::from(translation[usize::from(texel)])]
+    });
+}
+
+// R_DrawFuzzColumn. The top and bottom rows of the view are skipped so the
+// offsets never read outside of it
+pub fn draw_fuzz_column(
+    framebuffer: &mut Framebuffer,
+    column: &Column,
+    colormap: &[u8],
+    fuzz_pos: &mut usize,
+) {
+    let yl: i32 = column.yl.max(1);
+    let yh: i32 = column.yh.min(framebuffer.height as i32 - 2);
+    if yh < yl {
+        return;
+    }
+
+    let width: i32 = framebuffer.width as i32;
+    for y in yl..=yh {
+        let dest: i32 = y * width + column.x;
+        let fuzzed: u8 = framebuffer.pixels[(dest + FUZZ_OFFSETS[*fuzz_pos] * width) as usize];
+        framebuffer.pixels[dest as usize] = colormap[usize::from(fuzzed)];
+
+        // Clamp table lookup index
+        *fuzz_pos = (*fuzz_pos + 1) % FUZZTABLE;
+    }
+}
+
+pub fn draw_styled_column(
+    framebuffer: &mut Framebuffer,
+    center_y: i32,
+    column: &Column,
+    style: ColumnStyle,
+    fuzz_pos: &mut usize,
+) {
+    match style {
+        ColumnStyle::Lit(colormap) => draw_column(framebuffer, center_y, column, colormap),
+        ColumnStyle::Translated(colormap, translation) => {
+            draw_translated_column(framebuffer, center_y, column, colormap, translation)
+        }
+        ColumnStyle::Fuzz(colormap) => draw_fuzz_column(framebuffer, column, colormap, fuzz_pos),
+    }
+}
+
+// R_DrawSpan
+pub fn draw_span(framebuffer: &mut Framebuffer, span: &Span) {
+    let mut x_frac: i32 = span.x_frac.0;
+    let mut y_frac: i32 = span.y_frac.0;
+    let row: usize = span.y as usize * framebuffer.width;
+
+    for x in span.x1..=span.x2 {
+        // Current texture index in u,v
+        let spot: i32 = ((y_frac >> (FRACBITS - FLAT_SHIFT)) & (FLAT_MASK << FLAT_SHIFT))
+            + ((x_frac >> FRACBITS) & FLAT_MASK);
+
+        // Lookup pixel from flat texture tile, re-index using light/colormap
+        framebuffer.pixels[row + x as usize] =
+            span.colormap[usize::from(span.source[spot as usize])];
+
+        x_frac = x_frac.wrapping_add(span.x_step.0);
+        y_frac = y_frac.wrapping_add(span.y_step.0);
+    }
+}
+
+fn draw_shaded_column(
+    framebuffer: &mut Framebuffer,
+    center_y: i32,
+    column: &Column,
+    shade: impl Fn(u8) -> u8,
+) {
+    // Zero length, column does not exceed a pixel
+    if column.yh < column.yl || column.source.is_empty() {
+        return;
+    }
+
+    // Determine scaling, which is the only mapping to be done
+    let frac_step: i32 = column.iscale.0;
+    let mut frac: i32 = column
+        .texture_mid
+        .0
+        .wrapping_add((column.yl - center_y).wrapping_mul(frac_step));
+    let source_height: i32 = column.source.len() as i32;
+
+    for y in column.yl..=column.yh {
+        let texel: u8 = column.source[(frac >> FRACBITS).rem_euclid(source_height) as usize];
+        framebuffer.pixels[y as usize * framebuffer.width + column.x as usize] = shade(texel);
+        frac = frac.wrapping_add(frac_step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::{Fixed, FRACUNIT};
+    use crate::render::draw::{
+        draw_column, draw_fuzz_column, draw_span, draw_translated_column, Column, Span,
+    };
+    use crate::render::Framebuffer;
+
+    fn identity() -> Vec<u8> {
+        (0..=255).collect()
+    }
+
+    #[test]
+    fn test_draw_column_scales_and_repeats_the_source() {
+        let mut framebuffer: Framebuffer = Framebuffer::new(1);
+        let colormap: Vec<u8> = identity();
+        let column: Column = Column {
+            x: 10,
+            yl: 100,
+            yh: 105,
+            iscale: Fixed(FRACUNIT / 2),
+            texture_mid: Fixed::ZERO,
+            source: &[1, 2, 3],
+        };
+
+        draw_column(&mut framebuffer, 100, &column, &colormap);
+
+        let drawn: Vec<u8> = (100..=105)
+            .map(|y| framebuffer.pixels[y * framebuffer.width + 10])
+            .collect();
+        assert_eq!(drawn, vec![1, 1, 2, 2, 3, 3]);
+        assert_eq!(framebuffer.pixels[99 * framebuffer.width + 10], 0);
+        assert_eq!(framebuffer.pixels[106 * framebuffer.width + 10], 0);
+    }
+
+    #[test]
+    fn test_draw_translated_column_translates_before_lighting() {
+        let mut framebuffer: Framebuffer = Framebuffer::new(1);
+        let colormap: Vec<u8> = identity().iter().map(|index| index / 2).collect();
+        let mut translation: Vec<u8> = identity();
+        translation[0x70] = 0x60;
+        let column: Column = Column {
+            x: 0,
+            yl: 0,
+            yh: 0,
+            iscale: Fixed::ONE,
+            texture_mid: Fixed::ZERO,
+            source: &[0x70],
+        };
+
+        draw_translated_column(&mut framebuffer, 0, &column, &colormap, &translation);
+
+        assert_eq!(framebuffer.pixels[0], 0x30);
+    }
+
+    #[test]
+    fn test_draw_fuzz_column_skips_the_edge_rows() {
+        let mut framebuffer: Framebuffer = Framebuffer::new(1);
+        framebuffer.pixels.fill(10);
+        let colormap: Vec<u8> = identity()
+            .iter()
+            .map(|index| index.wrapping_add(1))
+            .collect();
+        let column: Column = Column {
+            x: 5,
+            yl: 0,
+            yh: 199,
+            iscale: Fixed::ONE,
+            texture_mid: Fixed::ZERO,
+            source: &[],
+        };
+        let mut fuzz_pos: usize = 0;
+
+        draw_fuzz_column(&mut framebuffer, &column, &colormap, &mut fuzz_pos);
+
+        assert_eq!(framebuffer.pixels[5], 10);
+        assert_eq!(framebuffer.pixels[199 * framebuffer.width + 5], 10);
+        assert_ne!(framebuffer.pixels[framebuffer.width + 5], 10);
+        assert_eq!(fuzz_pos, 198 % 50);
+    }
+
+    #[test]
+    fn test_draw_span_wraps_around_the_flat() {
+        let mut framebuffer: Framebuffer = Framebuffer::new(1);
+        let colormap: Vec<u8> = identity();
+        let source: Vec<u8> = (0..64 * 64).map(|index| (index % 64) as u8).collect();
+        let span: Span = Span {
+            y: 3,
+            x1: 0,
+            x2: 3,
+            x_frac: Fixed::from_int(62),
+            y_frac: Fixed::ZERO,
+            x_step: Fixed::ONE,
+            y_step: Fixed::ZERO,
+            source: &source,
+            colormap: &colormap,
+        };
+
+        draw_span(&mut framebuffer, &span);
+
+        let row: usize = 3 * framebuffer.width;
+        assert_eq!(framebuffer.pixels[row..row + 5], [62, 63, 0, 1, 0]);
+    }
+}
@@ -0,0 +1,348 @@
+// Port of the original r_plane.c and r_sky.c. Floors and ceilings are
+// collected into visplanes while the walls are drawn, then drawn as
+// horizontal spans once the whole view has been walked
+use crate::config::GameType;
+use crate::fixed::tables::{finecosine, finesine};
+use crate::fixed::{fixed_div, fixed_mul, Angle, Fixed, ANG90, FRACUNIT};
+use crate::render::data::Patch;
+use crate::render::draw::{draw_column, draw_span, Column, Span};
+use crate::render::{Framebuffer, Renderer, LIGHTZSHIFT, MAXLIGHTZ};
+
+// Columns of a visplane that nothing has marked
+pub const VISPLANE_EMPTY: u16 = u16::MAX;
+
+// The sky map is 256*128*4 maps
+const ANGLETOSKYSHIFT: u32 = 22;
+const SKY_TEXTURE_MID: Fixed = Fixed(100 * FRACUNIT);
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Visplane {
+    pub height: Fixed,
+    pub pic: usize,
+    pub light_level: i32,
+    pub min_x: i32,
+    pub max_x: i32,
+    // Rows covered in each column. There's an extra column on each side
+    // so R_MakeSpans can look one past the ends
+    pub top: Vec<u16>,
+    pub bottom: Vec<u16>,
+}
+
+impl Visplane {
+    fn new(height: Fixed, pic: usize, light_level: i32, view_width: usize) -> Self {
+        Visplane {
+            height,
+            pic,
+            light_level,
+            min_x: view_width as i32,
+            max_x: -1,
+            top: vec![VISPLANE_EMPTY; view_width + 2],
+            bottom: vec![0; view_width + 2],
+        }
+    }
+
+    pub fn top(&self, x: i32) -> i32 {
+        i32::from(self.top[(x + 1) as usize])
+    }
+
+    pub fn bottom(&self, x: i32) -> i32 {
+        i32::from(self.bottom[(x + 1) as usize])
+    }
+
+    pub fn set_rows(&mut self, x: i32, top: i32, bottom: i32) {
+        self.top[(x + 1) as usize] = top as u16;
+        self.bottom[(x + 1) as usize] = bottom as u16;
+    }
+}
+
+// R_InitSkyMap and the sky selection from G_InitNew/G_DoLoadLevel
+pub fn sky_texture_name(game_type: GameType, episode: i32, map: i32) -> &'static str {
+    if game_type == GameType::DoomII {
+        return if map < 12 {
+            "SKY1"
+        } else if map < 21 {
+            "SKY2"
+        } else {
+            "SKY3"
+        };
+    }
+
+    match episode {
+        2 => "SKY2",
+        3 => "SKY3",
+        4 => "SKY4",
+        _ => "SKY1",
+    }
+}
+
+impl Renderer {
+    // R_ClearPlanes. At begining of frame
+    pub(super) fn clear_planes(&mut self) {
+        // Opening / clipping determination
+        self.floor_clip.fill(self.view_height as i32);
+        self.ceiling_clip.fill(-1);
+
+        self.visplanes.clear();
+        self.openings.clear();
+
+        // Texture calculation
+        self.cached_height.fill(Fixed::ZERO);
+
+        // Left to right mapping
+        let angle: usize = (self.view.angle - Angle(ANG90)).fine_index();
+
+        // Scale will be unit scale at SCREENWIDTH/2 distance
+        self.base_x_scale = fixed_div(finecosine(angle), self.center_x_frac);
+        self.base_y_scale = -fixed_div(finesine(angle), self.center_x_frac);
+    }
+
+    // R_FindPlane
+    pub(super) fn find_plane(&mut self, height: Fixed, pic: usize, light_level: i32) -> usize {
+        // All skys map together
+        let (height, light_level): (Fixed, i32) = if pic == self.data.sky_flat {
+            (Fixed::ZERO, 0)
+        } else {
+            (height, light_level)
+        };
+
+        if let Some(index) = self.visplanes.iter().position(|plane| {
+            plane.height == height && plane.pic == pic && plane.light_level == light_level
+        }) {
+            return index;
+        }
+
+        self.visplanes
+            .push(Visplane::new(height, pic, light_level, self.view_width));
+        self.visplanes.len() - 1
+    }
+
+    // R_CheckPlane. Uses the plane for the columns if they're all unmarked,
+    // otherwise starts a new plane with the same height, pic and light
+    pub(super) fn check_plane(&mut self, index: usize, start: i32, stop: i32) -> usize {
+        let plane: &mut Visplane = &mut self.visplanes[index];
+
+        let (intrl, unionl): (i32, i32) = if start < plane.min_x {
+            (plane.min_x, start)
+        } else {
+            (start, plane.min_x)
+        };
+        let (intrh, unionh): (i32, i32) = if stop > plane.max_x {
+            (plane.max_x, stop)
+        } else {
+            (stop, plane.max_x)
+        };
+
+        if (intrl..=intrh).all(|x| plane.top(x) == i32::from(VISPLANE_EMPTY)) {
+            // Use the same one
+            plane.min_x = unionl;
+            plane.max_x = unionh;
+            return index;
+        }
+
+        // Make a new visplane
+        let mut new_plane: Visplane =
+            Visplane::new(plane.height, plane.pic, plane.light_level, self.view_width);
+        new_plane.min_x = start;
+        new_plane.max_x = stop;
+        self.visplanes.push(new_plane);
+        self.visplanes.len() - 1
+    }
+
+    // R_DrawPlanes. At the end of each frame
+    pub(super) fn draw_planes(&mut self, framebuffer: &mut Framebuffer) {
+        let mut visplanes: Vec<Visplane> = std::mem::take(&mut self.visplanes);
+
+        for plane in visplanes.iter_mut() {
+            if plane.min_x > plane.max_x {
+                continue;
+            }
+
+            // Sky flat
+            if plane.pic == self.data.sky_flat {
+                self.draw_sky(framebuffer, plane);
+                continue;
+            }
+
+            // Regular flat
+            let flat: usize = self.flat_translation[plane.pic];
+            let light: usize = self.light_row(plane.light_level, 0);
+            let plane_height: Fixed = (plane.height - self.view.z).abs();
+
+            plane.top[plane.max_x as usize + 2] = VISPLANE_EMPTY;
+            plane.top[plane.min_x as usize] = VISPLANE_EMPTY;
+
+            for x in plane.min_x..=plane.max_x + 1 {
+                self.make_spans(
+                    framebuffer,
+                    (plane_height, flat, light),
+                    x,
+                    (plane.top(x - 1), plane.bottom(x - 1)),
+                    (plane.top(x), plane.bottom(x)),
+                );
+            }
+        }
+
+        self.visplanes = visplanes;
+    }
+
+    // Sky is allways drawn full bright, i.e. colormaps[0] is used.
+    // Because of this hack, sky is not affected by INVUL inverse mapping
+    fn draw_sky(&self, framebuffer: &mut Framebuffer, plane: &Visplane) {
+        let sky: &Patch = &self.data.textures[self.sky_texture].patch;
+        let colormap: &[u8] = &self.data.colormaps[..256];
+
+        for x in plane.min_x..=plane.max_x {
+            let yl: i32 = plane.top(x);
+            let yh: i32 = plane.bottom(x);
+            if yl > yh {
+                continue;
+            }
+
+            let angle: Angle = self.view.angle + self.x_to_view_angle[x as usize];
+            let column: Column = Column {
+                x,
+                yl,
+                yh,
+                iscale: self.psprite_iscale,
+                texture_mid: SKY_TEXTURE_MID,
+                source: sky.column(sky.wrapped_column((angle.0 >> ANGLETOSKYSHIFT) as i32)),
+            };
+            draw_column(framebuffer, self.center_y, &column, colormap);
+        }
+    }
+
+    // R_MakeSpans. Finishes the spans of rows the previous column covered
+    // and this one doesn't, and starts the ones this column adds
+    fn make_spans(
+        &mut self,
+        framebuffer: &mut Framebuffer,
+        plane: (Fixed, usize, usize),
+        x: i32,
+        (mut t1, mut b1): (i32, i32),
+        (mut t2, mut b2): (i32, i32),
+    ) {
+        while t1 < t2 && t1 <= b1 {
+            self.map_plane(framebuffer, plane, t1, self.span_start[t1 as usize], x - 1);
+            t1 += 1;
+        }
+        while b1 > b2 && b1 >= t1 {
+            self.map_plane(framebuffer, plane, b1, self.span_start[b1 as usize], x - 1);
+            b1 -= 1;
+        }
+
+        while t2 < t1 && t2 <= b2 {
+            self.span_start[t2 as usize] = x;
+            t2 += 1;
+        }
+        while b2 > b1 && b2 >= t2 {
+            self.span_start[b2 as usize] = x;
+            b2 -= 1;
+        }
+    }
+
+    // R_MapPlane
+    fn map_plane(
+        &mut self,
+        framebuffer: &mut Framebuffer,
+        (plane_height, flat, light): (Fixed, usize, usize),
+        y: i32,
+        x1: i32,
+        x2: i32,
+    ) {
+        let row: usize = y as usize;
+        let (distance, x_step, y_step): (Fixed, Fixed, Fixed) =
+            if plane_height != self.cached_height[row] {
+                let distance: Fixed = fixed_mul(plane_height, self.y_slope[row]);
+                self.cached_height[row] = plane_height;
+                self.cached_distance[row] = distance;
+                self.cached_x_step[row] = fixed_mul(distance, self.base_x_scale);
+                self.cached_y_step[row] = fixed_mul(distance, self.base_y_scale);
+                (distance, self.cached_x_step[row], self.cached_y_step[row])
+            } else {
+                (
+                    self.cached_distance[row],
+                    self.cached_x_step[row],
+                    self.cached_y_step[row],
+                )
+            };
+
+        let length: Fixed = fixed_mul(distance, self.dist_scale[x1 as usize]);
+        let angle: Angle = self.view.angle + self.x_to_view_angle[x1 as usize];
+
+        let colormap: usize = match self.view.fixed_colormap {
+            Some(colormap) => colormap,
+            None => self.z_light[light][((distance.0 >> LIGHTZSHIFT) as usize).min(MAXLIGHTZ - 1)],
+        };
+
+        let span: Span = Span {
+            y,
+            x1,
+            x2,
+            x_frac: self.view.x + fixed_mul(angle.cos(), length),
+            y_frac: -self.view.y - fixed_mul(angle.sin(), length),
+            x_step,
+            y_step,
+            source: &self.data.flats[flat],
+            colormap: &self.data.colormaps[colormap..colormap + 256],
+        };
+        draw_span(framebuffer, &span);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GameType;
+    use crate::fixed::Fixed;
+    use crate::render::plane::{sky_texture_name, VISPLANE_EMPTY};
+    use crate::render::test_wad::test_renderer;
+    use crate::render::Renderer;
+
+    #[test]
+    fn test_sky_texture_name_by_episode_and_map() {
+        assert_eq!(sky_texture_name(GameType::DoomIRegistered, 1, 9), "SKY1");
+        assert_eq!(sky_texture_name(GameType::UltimateDoom, 4, 1), "SKY4");
+        assert_eq!(sky_texture_name(GameType::DoomII, 1, 11), "SKY1");
+        assert_eq!(sky_texture_name(GameType::DoomII, 1, 12), "SKY2");
+        assert_eq!(sky_texture_name(GameType::DoomII, 1, 21), "SKY3");
+    }
+
+    #[test]
+    fn test_find_plane_merges_skies_and_matching_planes() {
+        let mut renderer: Renderer = test_renderer(1);
+        renderer.clear_planes();
+        let sky: usize = renderer.data.sky_flat;
+
+        let floor: usize = renderer.find_plane(Fixed::ZERO, 0, 160);
+        assert_eq!(renderer.find_plane(Fixed::ZERO, 0, 160), floor);
+        assert_ne!(renderer.find_plane(Fixed::ZERO, 0, 128), floor);
+
+        let sky_plane: usize = renderer.find_plane(Fixed::from_int(128), sky, 160);
+        assert_eq!(
+            renderer.find_plane(Fixed::from_int(64), sky, 255),
+            sky_plane
+        );
+        assert_eq!(renderer.visplanes[sky_plane].height, Fixed::ZERO);
+    }
+
+    #[test]
+    fn test_check_plane_splits_overlapping_columns() {
+        let mut renderer: Renderer = test_renderer(1);
+        renderer.clear_planes();
+
+        let plane: usize = renderer.find_plane(Fixed::ZERO, 0, 160);
+        assert_eq!(renderer.check_plane(plane, 10, 20), plane);
+        renderer.visplanes[plane].set_rows(15, 100, 120);
+
+        // Unmarked columns extend the plane
+        assert_eq!(renderer.check_plane(plane, 21, 30), plane);
+        assert_eq!(renderer.visplanes[plane].min_x, 10);
+        assert_eq!(renderer.visplanes[plane].max_x, 30);
+
+        // Marked columns need a new one
+        let split: usize = renderer.check_plane(plane, 0, 15);
+        assert_ne!(split, plane);
+        assert_eq!(renderer.visplanes[split].min_x, 0);
+        assert_eq!(renderer.visplanes[split].max_x, 15);
+        assert_eq!(renderer.visplanes[split].top(15), i32::from(VISPLANE_EMPTY));
+    }
+}
@@ -0,0 +1,625 @@
+// Port of the original r_segs.c. Draws the visible parts of a seg's
+// walls, marks the floor and ceiling rows around them and saves what
+// sprites need to be clipped against
+use crate::fixed::tables::{finetangent, ANGLETOFINESHIFT, FINEANGLES};
+use crate::fixed::{fixed_mul, Angle, Fixed, ANG180, ANG90, FRACBITS};
+use crate::play::level::{
+    names_equal, Sector, Seg, Side, Vertex, ML_DONTPEGBOTTOM, ML_DONTPEGTOP, ML_MAPPED, SKYFLATNAME,
+};
+use crate::play::world::World;
+use crate::render::data::Patch;
+use crate::render::draw::{draw_column, Column, ColumnStyle};
+use crate::render::things::{draw_masked_column, MaskedColumn};
+use crate::render::{Framebuffer, Renderer};
+
+// Which sides of a drawseg sprites are clipped by
+pub const SIL_NONE: i32 = 0;
+pub const SIL_BOTTOM: i32 = 1;
+pub const SIL_TOP: i32 = 2;
+pub const SIL_BOTH: i32 = 3;
+
+// Masked columns already drawn are set to this
+pub const MAXSHORT: i32 = i16::MAX as i32;
+
+// Fixed point screen rows are worked out with 4 bits less precision
+const HEIGHTBITS: u32 = 12;
+const HEIGHTUNIT: i32 = 1 << HEIGHTBITS;
+
+// Where a drawseg's sprite clipping rows come from, the original pointed
+// into the openings or at the shared screen height and -1 arrays
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClipRows {
+    ScreenHeight,
+    NegativeOne,
+    // Start of the drawseg's columns in the openings
+    Openings(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrawSeg {
+    pub cur_line: usize,
+    pub x1: i32,
+    pub x2: i32,
+    pub scale1: Fixed,
+    pub scale2: Fixed,
+    pub scale_step: Fixed,
+    // 0=none, 1=bottom, 2=top, 3=both
+    pub silhouette: i32,
+    // Do not clip sprites above this
+    pub bsil_height: Fixed,
+    // Do not clip sprites below this
+    pub tsil_height: Fixed,
+    pub spr_top_clip: Option<ClipRows>,
+    pub spr_bottom_clip: Option<ClipRows>,
+    // Start of the masked texture columns in the openings
+    pub masked_texture_col: Option<usize>,
+}
+
+// The rw_ globals for the range of a seg being drawn
+#[derive(Clone, Copy, Debug, Default)]
+struct WallRange {
+    start: i32,
+    x: i32,
+    stop_x: i32,
+    scale: Fixed,
+    scale_step: Fixed,
+    distance: Fixed,
+    offset: Fixed,
+    center_angle: Angle,
+    light: usize,
+    mid_texture: usize,
+    top_texture: usize,
+    bottom_texture: usize,
+    masked_texture: bool,
+    seg_textured: bool,
+    mark_floor: bool,
+    mark_ceiling: bool,
+    mid_texture_mid: Fixed,
+    top_texture_mid: Fixed,
+    bottom_texture_mid: Fixed,
+    top_frac: Fixed,
+    top_step: Fixed,
+    bottom_frac: Fixed,
+    bottom_step: Fixed,
+    pix_high: Fixed,
+    pix_high_step: Fixed,
+    pix_low: Fixed,
+    pix_low_step: Fixed,
+    masked_texture_col: usize,
+}
+
+impl Renderer {
+    // R_StoreWallRange. A wall segment will be drawn between start and
+    // stop pixels (inclusive)
+    pub(super) fn store_wall_range(
+        &mut self,
+        world: &World,
+        framebuffer: &mut Framebuffer,
+        start: i32,
+        stop: i32,
+    ) {
+        let level = &world.level;
+        let seg: &Seg = &level.segs[self.cur_line];
+        let side: &Side = &level.sides[seg.side_def];
+        let front: &Sector = &level.sectors[self.front_sector];
+        let back: Option<&Sector> = self.back_sector.map(|back| &level.sectors[back]);
+        let v1: Vertex = level.vertexes[seg.v1];
+        let v2: Vertex = level.vertexes[seg.v2];
+        let line_flags: i32 = level.lines[seg.line_def].flags;
+
+        // Mark the segment as visible for auto map
+        if line_flags & ML_MAPPED == 0 {
+            self.mapped_lines.push(seg.line_def);
+        }
+
+        // Calculate rw_distance for scale calculation
+        let normal_angle: Angle = seg.angle + Angle(ANG90);
+        let offset_angle: u32 = ((normal_angle - self.rw_angle1).0 as i32).wrapping_abs() as u32;
+        let dist_angle: Angle = Angle(ANG90 - offset_angle.min(ANG90));
+        let hyp: Fixed = self.point_to_dist(v1.x, v1.y);
+        let distance: Fixed = fixed_mul(hyp, dist_angle.sin());
+
+        let mut range: WallRange = WallRange {
+            start,
+            x: start,
+            stop_x: stop + 1,
+            distance,
+            ..Default::default()
+        };
+
+        // Calculate scale at both ends and step
+        let scale1: Fixed = self.scale_from_global_angle(
+            self.view.angle + self.x_to_view_angle[start as usize],
+            normal_angle,
+            distance,
+        );
+        range.scale = scale1;
+        let scale2: Fixed = if stop > start {
+            let scale2: Fixed = self.scale_from_global_angle(
+                self.view.angle + self.x_to_view_angle[stop as usize],
+                normal_angle,
+                distance,
+            );
+            range.scale_step = Fixed((scale2.0 - scale1.0) / (stop - start));
+            scale2
+        } else {
+            scale1
+        };
+
+        let mut draw_seg: DrawSeg = DrawSeg {
+            cur_line: self.cur_line,
+            x1: start,
+            x2: stop,
+            scale1,
+            scale2,
+            scale_step: range.scale_step,
+            silhouette: SIL_NONE,
+            bsil_height: Fixed::ZERO,
+            tsil_height: Fixed::ZERO,
+            spr_top_clip: None,
+            spr_bottom_clip: None,
+            masked_texture_col: None,
+        };
+
+        // Calculate texture boundaries and decide if floor / ceiling marks
+        // are needed
+        let mut world_top: Fixed = front.ceiling_height - self.view.z;
+        let mut world_bottom: Fixed = front.floor_height - self.view.z;
+        let mut world_high: Fixed = Fixed::ZERO;
+        let mut world_low: Fixed = Fixed::ZERO;
+
+        match back {
+            None => {
+                // Single sided line
+                range.mid_texture =
+                    self.texture_translation[self.data.texture_num(&side.mid_texture)];
+                // A single sided line is terminal, so it must mark ends
+                range.mark_floor = true;
+                range.mark_ceiling = true;
+                range.mid_texture_mid = if line_flags & ML_DONTPEGBOTTOM != 0 {
+                    let vtop: Fixed = front.floor_height
+                        + self
+                            .data
+                            .texture_height(self.data.texture_num(&side.mid_texture));
+                    // Bottom of texture at bottom
+                    vtop - self.view.z
+                } else {
+                    // Top of texture at top
+                    world_top
+                };
+                range.mid_texture_mid += side.row_offset;
+
+                draw_seg.silhouette = SIL_BOTH;
+                draw_seg.spr_top_clip = Some(ClipRows::ScreenHeight);
+                draw_seg.spr_bottom_clip = Some(ClipRows::NegativeOne);
+                draw_seg.bsil_height = Fixed::MAX;
+                draw_seg.tsil_height = Fixed::MIN;
+            }
+            Some(back) => {
+                // Two sided line
+                if front.floor_height > back.floor_height {
+                    draw_seg.silhouette = SIL_BOTTOM;
+                    draw_seg.bsil_height = front.floor_height;
+                } else if back.floor_height > self.view.z {
+                    draw_seg.silhouette = SIL_BOTTOM;
+                    draw_seg.bsil_height = Fixed::MAX;
+                }
+
+                if front.ceiling_height < back.ceiling_height {
+                    draw_seg.silhouette |= SIL_TOP;
+                    draw_seg.tsil_height = front.ceiling_height;
+                } else if back.ceiling_height < self.view.z {
+                    draw_seg.silhouette |= SIL_TOP;
+                    draw_seg.tsil_height = Fixed::MIN;
+                }
+
+                if back.ceiling_height <= front.floor_height {
+                    draw_seg.spr_bottom_clip = Some(ClipRows::NegativeOne);
+                    draw_seg.bsil_height = Fixed::MAX;
+                    draw_seg.silhouette |= SIL_BOTTOM;
+                }
+
+                if back.floor_height >= front.ceiling_height {
+                    draw_seg.spr_top_clip = Some(ClipRows::ScreenHeight);
+                    draw_seg.tsil_height = Fixed::MIN;
+                    draw_seg.silhouette |= SIL_TOP;
+                }
+
+                world_high = back.ceiling_height - self.view.z;
+                world_low = back.floor_height - self.view.z;
+
+                // Hack to allow height changes in outdoor areas
+                if names_equal(&front.ceiling_pic, SKYFLATNAME)
+                    && names_equal(&back.ceiling_pic, SKYFLATNAME)
+                {
+                    world_top = world_high;
+                }
+
+                range.mark_floor = world_low != world_bottom
+                    || !names_equal(&back.floor_pic, &front.floor_pic)
+                    || back.light_level != front.light_level;
+
+                range.mark_ceiling = world_high != world_top
+                    || !names_equal(&back.ceiling_pic, &front.ceiling_pic)
+                    || back.light_level != front.light_level;
+
+                if back.ceiling_height <= front.floor_height
+                    || back.floor_height >= front.ceiling_height
+                {
+                    // Closed door
+                    range.mark_ceiling = true;
+                    range.mark_floor = true;
+                }
+
+                if world_high < world_top {
+                    // Top texture
+                    let top_texture: usize = self.data.texture_num(&side.top_texture);
+                    range.top_texture = self.texture_translation[top_texture];
+                    range.top_texture_mid = if line_flags & ML_DONTPEGTOP != 0 {
+                        // Top of texture at top
+                        world_top
+                    } else {
+                        let vtop: Fixed =
+                            back.ceiling_height + self.data.texture_height(top_texture);
+                        // Bottom of texture
+                        vtop - self.view.z
+                    };
+                }
+
+                if world_low > world_bottom {
+                    // Bottom texture
+                    range.bottom_texture =
+                        self.texture_translation[self.data.texture_num(&side.bottom_texture)];
+                    range.bottom_texture_mid = if line_flags & ML_DONTPEGBOTTOM != 0 {
+                        // Bottom of texture at bottom, top of texture at top
+                        world_top
+                    } else {
+                        // Top of texture at top
+                        world_low
+                    };
+                }
+                range.top_texture_mid += side.row_offset;
+                range.bottom_texture_mid += side.row_offset;
+
+                // Allocate space for masked texture tables
+                if self.data.texture_num(&side.mid_texture) != 0 {
+                    // Masked midtexture
+                    range.masked_texture = true;
+                    range.masked_texture_col = self.openings.len();
+                    draw_seg.masked_texture_col = Some(range.masked_texture_col);
+                    self.openings
+                        .resize(self.openings.len() + (range.stop_x - start) as usize, 0);
+                }
+            }
+        }
+
+        // Calculate rw_offset (only needed for textured lines)
+        range.seg_textured = range.mid_texture != 0
+            || range.top_texture != 0
+            || range.bottom_texture != 0
+            || range.masked_texture;
+
+        if range.seg_textured {
+            let angle_difference: Angle = normal_angle - self.rw_angle1;
+            let mut offset_angle: Angle = angle_difference;
+            if offset_angle.0 > ANG180 {
+                offset_angle = -offset_angle;
+            }
+            offset_angle = Angle(offset_angle.0.min(ANG90));
+
+            range.offset = fixed_mul(hyp, offset_angle.sin());
+            if angle_difference.0 < ANG180 {
+                range.offset = -range.offset;
+            }
+            range.offset += side.texture_offset + seg.offset;
+            range.center_angle = Angle(ANG90) + self.view.angle - normal_angle;
+
+            // Calculate light table. Use different light tables for
+            // horizontal / vertical / diagonal
+            range.light = self.light_row(front.light_level, fake_contrast(v1, v2));
+        }
+
+        // If a floor / ceiling plane is on the wrong side of the view
+        // plane, it is definitely invisible and doesn't need to be marked
+        if front.floor_height >= self.view.z {
+            // Above view plane
+            range.mark_floor = false;
+        }
+
+        if front.ceiling_height <= self.view.z && !names_equal(&front.ceiling_pic, SKYFLATNAME) {
+            // Below view plane
+            range.mark_ceiling = false;
+        }
+
+        // Calculate incremental stepping values for texture edges
+        world_top = Fixed(world_top.0 >> 4);
+        world_bottom = Fixed(world_bottom.0 >> 4);
+
+        let center_y_frac: Fixed = Fixed(self.center_y_frac.0 >> 4);
+        range.top_step = -fixed_mul(range.scale_step, world_top);
+        range.top_frac = center_y_frac - fixed_mul(world_top, range.scale);
+
+        range.bottom_step = -fixed_mul(range.scale_step, world_bottom);
+        range.bottom_frac = center_y_frac - fixed_mul(world_bottom, range.scale);
+
+        if back.is_some() {
+            world_high = Fixed(world_high.0 >> 4);
+            world_low = Fixed(world_low.0 >> 4);
+
+            if world_high < world_top {
+                range.pix_high = center_y_frac - fixed_mul(world_high, range.scale);
+                range.pix_high_step = -fixed_mul(range.scale_step, world_high);
+            }
+
+            if world_low > world_bottom {
+                range.pix_low = center_y_frac - fixed_mul(world_low, range.scale);
+                range.pix_low_step = -fixed_mul(range.scale_step, world_low);
+            }
+        }
+
+        // Render it
+        if range.mark_ceiling {
+            if let Some(plane) = self.ceiling_plane {
+                self.ceiling_plane = Some(self.check_plane(plane, start, range.stop_x - 1));
+            }
+        }
+
+        if range.mark_floor {
+            if let Some(plane) = self.floor_plane {
+                self.floor_plane = Some(self.check_plane(plane, start, range.stop_x - 1));
+            }
+        }
+
+        self.render_seg_loop(framebuffer, &mut range);
+
+        // Save sprite clipping info
+        let columns: usize = (range.stop_x - start) as usize;
+        if (draw_seg.silhouette & SIL_TOP != 0 || range.masked_texture)
+            && draw_seg.spr_top_clip.is_none()
+        {
+            draw_seg.spr_top_clip = Some(ClipRows::Openings(self.openings.len()));
+            self.openings
+                .extend_from_slice(&self.ceiling_clip[start as usize..start as usize + columns]);
+        }
+
+        if (draw_seg.silhouette & SIL_BOTTOM != 0 || range.masked_texture)
+            && draw_seg.spr_bottom_clip.is_none()
+        {
+            draw_seg.spr_bottom_clip = Some(ClipRows::Openings(self.openings.len()));
+            self.openings
+                .extend_from_slice(&self.floor_clip[start as usize..start as usize + columns]);
+        }
+
+        if range.masked_texture && draw_seg.silhouette & SIL_TOP == 0 {
+            draw_seg.silhouette |= SIL_TOP;
+            draw_seg.tsil_height = Fixed::MIN;
+        }
+        if range.masked_texture && draw_seg.silhouette & SIL_BOTTOM == 0 {
+            draw_seg.silhouette |= SIL_BOTTOM;
+            draw_seg.bsil_height = Fixed::MAX;
+        }
+
+        self.draw_segs.push(draw_seg);
+    }
+
+    // R_RenderSegLoop. Draws zero, one, or two textures (and possibly a
+    // masked texture) for walls. Can draw or mark the starting pixel of
+    // floor and ceiling textures
+    fn render_seg_loop(&mut self, framebuffer: &mut Framebuffer, range: &mut WallRange) {
+        let ceiling_plane: Option<usize> = self.ceiling_plane.filter(|_| range.mark_ceiling);
+        let floor_plane: Option<usize> = self.floor_plane.filter(|_| range.mark_floor);
+
+        while range.x < range.stop_x {
+            let x: i32 = range.x;
+            let column_x: usize = x as usize;
+
+            // Mark floor / ceiling areas
+            let mut yl: i32 = (range.top_frac.0 + HEIGHTUNIT - 1) >> HEIGHTBITS;
+
+            // No space above wall?
+            yl = yl.max(self.ceiling_clip[column_x] + 1);
+
+            if let Some(plane) = ceiling_plane {
+                let top: i32 = self.ceiling_clip[column_x] + 1;
+                let bottom: i32 = (yl - 1).min(self.floor_clip[column_x] - 1);
+
+                if top <= bottom {
+                    self.visplanes[plane].set_rows(x, top, bottom);
+                }
+            }
+
+            let mut yh: i32 = range.bottom_frac.0 >> HEIGHTBITS;
+            yh = yh.min(self.floor_clip[column_x] - 1);
+
+            if let Some(plane) = floor_plane {
+                let top: i32 = (yh + 1).max(self.ceiling_clip[column_x] + 1);
+                let bottom: i32 = self.floor_clip[column_x] - 1;
+                if top <= bottom {
+                    self.visplanes[plane].set_rows(x, top, bottom);
+                }
+            }
+
+            // Texture column and lighting are independent of wall tiers
+            let mut texture_column: i32 = 0;
+            let mut colormap: usize = 0;
+            let mut iscale: Fixed = Fixed::ZERO;
+            if range.seg_textured {
+                // Calculate texture offset
+                let angle: usize = ((range.center_angle + self.x_to_view_angle[column_x]).0
+                    >> ANGLETOFINESHIFT) as usize
+                    & (FINEANGLES / 2 - 1);
+                texture_column =
+                    (range.offset - fixed_mul(finetangent(angle), range.distance)).0 >> FRACBITS;
+
+                // Calculate lighting
+                colormap = self.scale_colormap(range.light, range.scale);
+                iscale = Fixed((u32::MAX / range.scale.0 as u32) as i32);
+            }
+
+            let wall_column = |texture: usize, yl: i32, yh: i32, texture_mid: Fixed| -> Column {
+                let patch: &Patch = &self.data.textures[texture].patch;
+                Column {
+                    x,
+                    yl,
+                    yh,
+                    iscale,
+                    texture_mid,
+                    source: patch.column(patch.wrapped_column(texture_column)),
+                }
+            };
+            let colormap: &[u8] = &self.data.colormaps[colormap..colormap + 256];
+
+            // Draw the wall tiers
+            if range.mid_texture != 0 {
+                // Single sided line
+                let column: Column = wall_column(range.mid_texture, yl, yh, range.mid_texture_mid);
+                draw_column(framebuffer, self.center_y, &column, colormap);
+                self.ceiling_clip[column_x] = self.view_height as i32;
+                self.floor_clip[column_x] = -1;
+            } else {
+                // Two sided line
+                if range.top_texture != 0 {
+                    // Top wall
+                    let mid: i32 =
+                        (range.pix_high.0 >> HEIGHTBITS).min(self.floor_clip[column_x] - 1);
+                    range.pix_high += range.pix_high_step;
+
+                    if mid >= yl {
+                        let column: Column =
+                            wall_column(range.top_texture, yl, mid, range.top_texture_mid);
+                        draw_column(framebuffer, self.center_y, &column, colormap);
+                        self.ceiling_clip[column_x] = mid;
+                    } else {
+                        self.ceiling_clip[column_x] = yl - 1;
+                    }
+                } else if range.mark_ceiling {
+                    // No top wall
+                    self.ceiling_clip[column_x] = yl - 1;
+                }
+
+                if range.bottom_texture != 0 {
+                    // Bottom wall
+                    let mid: i32 = ((range.pix_low.0 + HEIGHTUNIT - 1) >> HEIGHTBITS)
+                        .max(self.ceiling_clip[column_x] + 1);
+                    range.pix_low += range.pix_low_step;
+
+                    if mid <= yh {
+                        let column: Column =
+                            wall_column(range.bottom_texture, mid, yh, range.bottom_texture_mid);
+                        draw_column(framebuffer, self.center_y, &column, colormap);
+                        self.floor_clip[column_x] = mid;
+                    } else {
+                        self.floor_clip[column_x] = yh + 1;
+                    }
+                } else if range.mark_floor {
+                    // No bottom wall
+                    self.floor_clip[column_x] = yh + 1;
+                }
+
+                if range.masked_texture {
+                    // Save texturecol for backdrawing of masked mid texture
+                    self.openings[range.masked_texture_col + (x - range.start) as usize] =
+                        texture_column;
+                }
+            }
+
+            range.scale += range.scale_step;
+            range.top_frac += range.top_step;
+            range.bottom_frac += range.bottom_step;
+            range.x += 1;
+        }
+    }
+
+    // R_RenderMaskedSegRange. Draws the masked mid texture of a two sided
+    // line between x1 and x2
+    pub(super) fn render_masked_seg_range(
+        &mut self,
+        world: &World,
+        framebuffer: &mut Framebuffer,
+        draw_seg: &DrawSeg,
+        x1: i32,
+        x2: i32,
+    ) {
+        let Some(masked_texture_col) = draw_seg.masked_texture_col else {
+            return;
+        };
+
+        // Calculate light table. Use different light tables for horizontal
+        // / vertical / diagonal
+        let level = &world.level;
+        let seg: &Seg = &level.segs[draw_seg.cur_line];
+        let front: &Sector = &level.sectors[seg.front_sector];
+        let back: &Sector = &level.sectors[seg.back_sector.unwrap_or(seg.front_sector)];
+        let side: &Side = &level.sides[seg.side_def];
+        let texture: usize = self.texture_translation[self.data.texture_num(&side.mid_texture)];
+        let light: usize = self.light_row(
+            front.light_level,
+            fake_contrast(level.vertexes[seg.v1], level.vertexes[seg.v2]),
+        );
+
+        let mut scale: Fixed =
+            draw_seg.scale1 + Fixed((x1 - draw_seg.x1).wrapping_mul(draw_seg.scale_step.0));
+
+        // Find positioning
+        let mut texture_mid: Fixed = if level.lines[seg.line_def].flags & ML_DONTPEGBOTTOM != 0 {
+            front.floor_height.max(back.floor_height) + self.data.texture_height(texture)
+                - self.view.z
+        } else {
+            front.ceiling_height.min(back.ceiling_height) - self.view.z
+        };
+        texture_mid += side.row_offset;
+
+        for x in x1..=x2 {
+            let column_index: usize = masked_texture_col + (x - draw_seg.x1) as usize;
+
+            // Calculate lighting
+            if self.openings[column_index] != MAXSHORT {
+                let colormap: usize = self.scale_colormap(light, scale);
+                let masked: MaskedColumn = MaskedColumn {
+                    x,
+                    iscale: Fixed((u32::MAX / scale.0 as u32) as i32),
+                    texture_mid,
+                    top_screen: self.center_y_frac - fixed_mul(texture_mid, scale),
+                    scale,
+                    floor_clip: self.clip_row(draw_seg.spr_bottom_clip, draw_seg.x1, x),
+                    ceiling_clip: self.clip_row(draw_seg.spr_top_clip, draw_seg.x1, x),
+                    style: ColumnStyle::Lit(&self.data.colormaps[colormap..colormap + 256]),
+                };
+
+                // Draw the texture
+                let patch: &Patch = &self.data.textures[texture].patch;
+                draw_masked_column(
+                    framebuffer,
+                    self.center_y,
+                    &mut self.fuzz_pos,
+                    &masked,
+                    patch,
+                    patch.wrapped_column(self.openings[column_index]),
+                );
+                self.openings[column_index] = MAXSHORT;
+            }
+            scale += draw_seg.scale_step;
+        }
+    }
+
+    // The row a drawseg's sprite clip gives for a column
+    pub(super) fn clip_row(&self, rows: Option<ClipRows>, x1: i32, x: i32) -> i32 {
+        match rows {
+            Some(ClipRows::ScreenHeight) => self.view_height as i32,
+            Some(ClipRows::Openings(start)) => self.openings[start + (x - x1) as usize],
+            Some(ClipRows::NegativeOne) | None => -1,
+        }
+    }
+}
+
+// Horizontal walls are drawn a light level darker and vertical ones a level
+// brighter to make the corners stand out
+fn fake_contrast(v1: Vertex, v2: Vertex) -> i32 {
+    if v1.y == v2.y {
+        -1
+    } else if v1.x == v2.x {
+        1
+    } else {
+        0
+    }
+}
@@ -0,0 +1,376 @@
+// A small wad with everything the renderer needs to draw the test maps,
+// since there's no IWAD to take textures, flats and sprites from
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::game::Game;
+use crate::graphics::Picture;
+use crate::image::png::{PngColor, PngImage};
+use crate::play::level::{MAPSECTOR_SIZE, MAPSIDEDEF_SIZE};
+use crate::play::test_map::{map_wad_lumps, TestMap, TestRoom};
+use crate::play::world::{World, WorldSettings};
+use crate::render::data::COLORMAP_SIZE;
+use crate::render::Renderer;
+use crate::test_util::{create_test_dir, write_test_wad};
+use crate::wad::{self, DoomFile};
+
+// Each test gets its own wad file
+static WAD_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// Base colours of the palette's 16 ramps, each ramp is 16 shades from the
+// colour down to nearly black. Ramp 7 is the green the player colours
+// translate
+const RAMPS: [[u8; 3]; 16] = [
+    [255, 255, 255],
+    [255, 200, 150],
+    [220, 60, 40],
+    [200, 160, 60],
+    [60, 90, 220],
+    [180, 120, 60],
+    [255, 220, 0],
+    [40, 200, 60],
+    [200, 60, 200],
+    [120, 120, 140],
+    [80, 200, 200],
+    [150, 100, 60],
+    [100, 160, 255],
+    [255, 140, 0],
+    [60, 120, 60],
+    [140, 60, 60],
+];
+
+// The player's start in the scene, facing down the rooms
+pub const START: (i16, i16, i16) = (64, 128, 0);
+
+fn color(ramp: u8, shade: u8) -> u8 {
+    ramp * 16 + shade.min(15)
+}
+
+fn picture(
+    width: usize,
+    height: usize,
+    offsets: (i16, i16),
+    texel: impl Fn(usize, usize) -> Option<u8>,
+) -> Vec<u8> {
+    let picture: Picture = Picture {
+        width,
+        height,
+        left_offset: offsets.0,
+        top_offset: offsets.1,
+        pixels: (0..width * height)
+            .map(|index| texel(index % width, index / width))
+            .collect(),
+    };
+    picture.to_lump().unwrap()
+}
+
+fn playpal() -> Vec<u8> {
+    (0..256)
+        .flat_map(|index| {
+            let shade: u32 = 16 - index as u32 % 16;
+            RAMPS[index / 16].map(|channel| (u32::from(channel) * shade / 16) as u8)
+        })
+        .collect()
+}
+
+// 32 maps darkening by a shade every other map, the invulnerability map
+// turning everything gray and the all black map
+fn colormap() -> Vec<u8> {
+    let mut colormap: Vec<u8> = Vec::with_capacity(COLORMAP_SIZE);
+    for map in 0..32 {
+        colormap.extend((0..=255).map(|index: u8| color(index / 16, index % 16 + map / 2)));
+    }
+    colormap.extend((0..=255).map(|index: u8| color(0, index % 16)));
+    colormap.extend([color(0, 15); 256]);
+    colormap
+}
+
+fn name8(name: &str) -> [u8; 8] {
+    let mut bytes: [u8; 8] = [0; 8];
+    bytes[..name.len()].copy_from_slice(name.as_bytes());
+    bytes
+}
+
+// (name, width, height, (x, y, patch) of each patch)
+type TestTexture = (&'static str, i16, i16, Vec<(i16, i16, i16)>);
+
+fn texture_lump(textures: &[TestTexture]) -> Vec<u8> {
+    let mut directory: Vec<u8> = (textures.len() as i32).to_le_bytes().to_vec();
+    let mut records: Vec<u8> = Vec::new();
+    let records_start: usize = 4 + textures.len() * 4;
+
+    for (name, width, height, patches) in textures.iter() {
+        directory.extend(((records_start + records.len()) as i32).to_le_bytes());
+        records.extend(name8(name));
+        records.extend(0i32.to_le_bytes());
+        records.extend(width.to_le_bytes());
+        records.extend(height.to_le_bytes());
+        records.extend(0i32.to_le_bytes());
+        records.extend((patches.len() as i16).to_le_bytes());
+        for (x, y, patch) in patches.iter() {
+            for value in [*x, *y, *patch, 1, 0] {
+                records.extend(value.to_le_bytes());
+            }
+        }
+    }
+
+    directory.extend(records);
+    directory
+}
+
+// Everything but the map, in the order an IWAD has it
+pub fn render_lumps() -> Vec<(String, Vec<u8>)> {
+    let patch_names: [&str; 6] = ["WALL1", "WALL2", "DOT", "GRATE", "SKYPAT", "WALL3"];
+    let mut pnames: Vec<u8> = (patch_names.len() as i32).to_le_bytes().to_vec();
+    for patch_name in patch_names {
+        pnames.extend(name8(patch_name));
+    }
+
+    let textures: Vec<TestTexture> = vec![
+        ("AASHITTY", 64, 128, vec![(0, 0, 0)]),
+        ("STARTAN3", 64, 128, vec![(0, 0, 0)]),
+        ("TWOPATCH", 64, 64, vec![(0, 0, 1), (24, 24, 2)]),
+        ("MIDGRATE", 64, 128, vec![(0, 0, 3)]),
+        ("SKY1", 256, 128, vec![(0, 0, 4)]),
+        ("WALL3", 32, 72, vec![(0, 0, 5)]),
+    ];
+
+    let mut lumps: Vec<(String, Vec<u8>)> = vec![
+        ("PLAYPAL".to_owned(), playpal()),
+        ("COLORMAP".to_owned(), colormap()),
+        ("TEXTURE1".to_owned(), texture_lump(&textures)),
+        ("PNAMES".to_owned(), pnames),
+    ];
+
+    // Bricks, stripes, a dot over the stripes, a grate with holes, a sky
+    // with a marker every 32 columns and a plain panel
+    lumps.push((
+        "WALL1".to_owned(),
+        picture(64, 128, (0, 0), |x, y| {
+            let row: usize = y / 16;
+            let brick: usize = (x + row % 2 * 16) / 32;
+            Some(color(
+                2,
+                if y % 16 == 0 || (x + row % 2 * 16).is_multiple_of(32) {
+                    8
+                } else {
+                    (brick % 2) as u8 * 2
+                },
+            ))
+        }),
+    ));
+    lumps.push((
+        "WALL2".to_owned(),
+        picture(64, 64, (0, 0), |x, _| Some(color(4, (x / 8 % 2) as u8 * 4))),
+    ));
+    lumps.push((
+        "DOT".to_owned(),
+        picture(16, 16, (0, 0), |_, _| Some(color(6, 0))),
+    ));
+    lumps.push((
+        "GRATE".to_owned(),
+        picture(64, 128, (0, 0), |x, y| {
+            (x % 16 < 4 || y % 32 < 4).then(|| color(10, (y / 32) as u8))
+        }),
+    ));
+    lumps.push((
+        "SKYPAT".to_owned(),
+        picture(256, 128, (0, 0), |x, y| {
+            Some(if x % 32 == 0 {
+                color(0, 0)
+            } else {
+                color(12, (y / 10) as u8)
+            })
+        }),
+    ));
+    lumps.push((
+        "WALL3".to_owned(),
+        picture(32, 72, (0, 0), |x, y| {
+            Some(color(11, ((x / 4 + y / 4) % 4) as u8))
+        }),
+    ));
+
+    // Automap mark numbers, a white box with the digit's count of dots
+    for digit in 0..10 {
+        lumps.push((
+            format!("AMMNUM{}", digit),
+            picture(5, 6, (0, 0), move |x, y| {
+                (x == 0 || y == 0 || x == 4 || y == 5 || (y - 1) * 3 + x - 1 < digit)
+                    .then(|| color(0, 0))
+            }),
+        ));
+    }
+
+    lumps.push(("F_START".to_owned(), Vec::new()));
+    lumps.push((
+        "FLOOR4_8".to_owned(),
+        (0..64 * 64)
+            .map(|index| color(3, ((index % 64 / 8 + index / 64 / 8) % 2) as u8 * 4))
+            .collect(),
+    ));
+    lumps.push((
+        "CEIL3_5".to_owned(),
+        (0..64 * 64)
+            .map(|index| {
+                if index % 16 == 0 || index / 64 % 16 == 0 {
+                    color(9, 6)
+                } else {
+                    color(9, 1)
+                }
+            })
+            .collect(),
+    ));
+    lumps.push(("F_SKY1".to_owned(), vec![0; 64 * 64]));
+    lumps.push(("F_END".to_owned(), Vec::new()));
+
+    // A trooper shaped blob with two frames, a barrel and a pistol
+    let trooper = |shade: u8| {
+        picture(32, 56, (16, 52), move |x, y| {
+            let dx: i32 = x as i32 - 16;
+            let head: bool = y < 12 && dx.abs() < 5;
+            let body: bool = y >= 12 && dx.abs() < 12 - (y as i32 - 12) / 8;
+            (head || body).then(|| color(7, shade + (y / 14) as u8))
+        })
+    };
+    let barrel = |shade: u8| {
+        picture(24, 32, (12, 30), move |x, _| {
+            Some(color(5, shade + (x as i32 - 12).unsigned_abs() as u8 / 3))
+        })
+    };
+    lumps.push(("S_START".to_owned(), Vec::new()));
+    lumps.push(("POSSA0".to_owned(), trooper(0)));
+    lumps.push(("POSSB0".to_owned(), trooper(2)));
+    lumps.push(("BAR1A0".to_owned(), barrel(0)));
+    lumps.push(("BAR1B0".to_owned(), barrel(1)));
+    lumps.push((
+        "PISGA0".to_owned(),
+        picture(32, 40, (-144, -128), |x, y| {
+            (x > 8 && x < 24 || y > 24).then(|| color(9, (x / 6) as u8))
+        }),
+    ));
+    lumps.push(("S_END".to_owned(), Vec::new()));
+
+    lumps
+}
+
+// Three rooms down the x axis. The first has a step up to a lower ceiling
+// in the second, seen past two patch upper and lower textures, with a grate
+// into the sky lit third room where a trooper stands. A barrel is in the
+// first room
+pub fn scene_map(start: (i16, i16, i16)) -> Vec<(String, Vec<u8>)> {
+    let test_map: TestMap = TestMap::new()
+        .room_with(TestRoom {
+            width: 256,
+            floor_height: 0,
+            ceiling_height: 128,
+            light_level: 192,
+            ..Default::default()
+        })
+        .room_with(TestRoom {
+            width: 128,
+            floor_height: 24,
+            ceiling_height: 96,
+            light_level: 144,
+            ..Default::default()
+        })
+        .room_with(TestRoom {
+            width: 192,
+            floor_height: 0,
+            ceiling_height: 112,
+            light_level: 255,
+            ..Default::default()
+        })
+        .thing(start.0, start.1, start.2, 1, 7)
+        .thing(470, 120, 180, 3004, 7)
+        .thing(200, 48, 0, 2035, 7);
+    let mut map_lumps = test_map.to_map_lumps();
+
+    // The first room's side of the step, both sides of the grate and the
+    // third room's side of the grate's upper and lower walls
+    let step: usize = 9 * MAPSIDEDEF_SIZE;
+    map_lumps.sidedefs[step + 4..step + 12].copy_from_slice(&name8("TWOPATCH"));
+    map_lumps.sidedefs[step + 12..step + 20].copy_from_slice(&name8("TWOPATCH"));
+    for side in [10, 11] {
+        let grate: usize = side * MAPSIDEDEF_SIZE;
+        map_lumps.sidedefs[grate + 20..grate + 28].copy_from_slice(&name8("MIDGRATE"));
+    }
+    let grate_walls: usize = 10 * MAPSIDEDEF_SIZE;
+    map_lumps.sidedefs[grate_walls + 4..grate_walls + 12].copy_from_slice(&name8("STARTAN3"));
+    map_lumps.sidedefs[grate_walls + 12..grate_walls + 20].copy_from_slice(&name8("TWOPATCH"));
+
+    // The far wall of the third room
+    let far_wall: usize = 7 * MAPSIDEDEF_SIZE;
+    map_lumps.sidedefs[far_wall + 20..far_wall + 28].copy_from_slice(&name8("WALL3"));
+
+    let sky: usize = 2 * MAPSECTOR_SIZE;
+    map_lumps.sectors[sky + 12..sky + 20].copy_from_slice(&name8("F_SKY1"));
+
+    map_wad_lumps("MAP01", &map_lumps)
+}
+
+pub fn test_wad(name: &str, lumps: &[(String, Vec<u8>)]) -> DoomFile {
+    let mut dir: PathBuf = create_test_dir(&format!(
+        "render-{}-{}",
+        name,
+        WAD_COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    dir.push("test.wad");
+    write_test_wad(&dir, lumps);
+    wad::process_file(&dir).unwrap()
+}
+
+// The scene with the player starting at start
+pub fn test_game(start: (i16, i16, i16)) -> Game {
+    let mut lumps: Vec<(String, Vec<u8>)> = render_lumps();
+    lumps.extend(scene_map(start));
+    Game::with_settings(WorldSettings::default(), vec![test_wad("game", &lumps)]).unwrap()
+}
+
+pub fn test_world() -> World {
+    test_game(START).world
+}
+
+pub fn test_renderer(scale: usize) -> Renderer {
+    let mut doom_files: Vec<DoomFile> = vec![test_wad("renderer", &render_lumps())];
+    Renderer::new(&mut doom_files, scale)
+}
+
+// Compares a frame with the PNG under tests/resource/golden, or writes it
+// there when ZZ_DOOM_UPDATE_GOLDEN is set
+pub fn check_golden(name: &str, image: &PngImage) {
+    let mut path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resource/golden");
+    path.push(format!("{}.png", name));
+
+    if std::env::var_os("ZZ_DOOM_UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, image.encode()).unwrap();
+        return;
+    }
+
+    let golden: PngImage = PngImage::decode(&fs::read(&path).unwrap()).unwrap();
+    assert_eq!(
+        (golden.width, golden.height),
+        (image.width, image.height),
+        "{} is a different size",
+        name
+    );
+    let (
+        PngColor::Indexed { indices, .. },
+        PngColor::Indexed {
+            indices: expected, ..
+        },
+    ) = (&image.color, &golden.color)
+    else {
+        panic!("{} is not indexed", name);
+    };
+    let differences: usize = indices
+        .iter()
+        .zip(expected.iter())
+        .filter(|(index, expected)| index != expected)
+        .count();
+    assert_eq!(differences, 0, "{} differs in {} pixels", name, differences);
+    assert_eq!(image, &golden);
+}
@@ -0,0 +1,528 @@
+// Port of the original r_things.c. Sprites are projected as the BSP walk
+// reaches their sectors, then drawn back to front clipped against the
+// walls in front of them along with the masked mid textures and the
+// player's weapon
+use crate::fixed::{fixed_div, fixed_mul, point_to_angle, Angle, Fixed, ANG45, FRACBITS, FRACUNIT};
+use crate::info::states::STATES;
+use crate::info::{FF_FRAMEMASK, FF_FULLBRIGHT};
+use crate::play::level::{Node, Seg, Vertex};
+use crate::play::mobj::{Mobj, MF_SHADOW, MF_TRANSLATION, MF_TRANSSHIFT};
+use crate::play::player::{Player, PW_INVISIBILITY};
+use crate::play::pspr::PspDef;
+use crate::play::world::World;
+use crate::render::data::{Patch, SpriteFrame};
+use crate::render::draw::{draw_styled_column, Column, ColumnStyle};
+use crate::render::segs::{DrawSeg, SIL_BOTTOM, SIL_TOP};
+use crate::render::{Framebuffer, Renderer, LIGHTSCALESHIFT, MAXLIGHTSCALE};
+
+const MINZ: Fixed = Fixed(FRACUNIT * 4);
+const BASEYCENTER: i32 = 100;
+// The colormap shadows darken the screen with
+const FUZZ_COLORMAP: usize = 6;
+// Not drawn over yet in the sprite clip rows
+const CLIP_UNSET: i32 = -2;
+
+// A vissprite_t is a thing that will be drawn during a refresh, i.e. a
+// sprite object that is partly visible
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VisSprite {
+    pub x1: i32,
+    pub x2: i32,
+    // For line side calculation
+    pub gx: Fixed,
+    pub gy: Fixed,
+    // Global bottom / top for silhouette clipping
+    pub gz: Fixed,
+    pub gzt: Fixed,
+    // Horizontal position of x1
+    pub start_frac: Fixed,
+    pub scale: Fixed,
+    // Negative if flipped
+    pub x_iscale: Fixed,
+    pub texture_mid: Fixed,
+    pub patch: usize,
+    // For color translation and shadow draw, maxbright frames as well.
+    // None is a shadow
+    pub colormap: Option<usize>,
+    pub mobj_flags: u32,
+}
+
+// The dc_ and sprite globals R_DrawMaskedColumn drew a patch column with
+#[derive(Clone, Copy, Debug)]
+pub struct MaskedColumn<'a> {
+    pub x: i32,
+    pub iscale: Fixed,
+    pub texture_mid: Fixed,
+    // Screen row of the top of the patch
+    pub top_screen: Fixed,
+    pub scale: Fixed,
+    // Rows the column is clipped between
+    pub floor_clip: i32,
+    pub ceiling_clip: i32,
+    pub style: ColumnStyle<'a>,
+}
+
+// R_DrawMaskedColumn. Used for sprites and masked mid textures
+pub fn draw_masked_column(
+    framebuffer: &mut Framebuffer,
+    center_y: i32,
+    fuzz_pos: &mut usize,
+    masked: &MaskedColumn,
+    patch: &Patch,
+    column: usize,
+) {
+    let texels: &[u8] = patch.column(column);
+
+    for (top, length) in patch.posts[column].iter() {
+        // Calculate unclipped screen coordinates for post
+        let top_screen: Fixed = masked.top_screen + Fixed(masked.scale.0.wrapping_mul(*top as i32));
+        let bottom_screen: Fixed = top_screen + Fixed(masked.scale.0.wrapping_mul(*length as i32));
+
+        let yl: i32 =
+            ((top_screen.0.wrapping_add(FRACUNIT - 1)) >> FRACBITS).max(masked.ceiling_clip + 1);
+        let yh: i32 = ((bottom_screen.0.wrapping_sub(1)) >> FRACBITS).min(masked.floor_clip - 1);
+
+        if yl <= yh {
+            let column: Column = Column {
+                x: masked.x,
+                yl,
+                yh,
+                iscale: masked.iscale,
+                texture_mid: masked.texture_mid - Fixed::from_int(*top as i32),
+                source: &texels[*top..top + length],
+            };
+
+            // Drawn by either R_DrawColumn or (SHADOW) R_DrawFuzzColumn
+            draw_styled_column(framebuffer, center_y, &column, masked.style, fuzz_pos);
+        }
+    }
+}
+
+impl Renderer {
+    // R_ClearSprites. Called at frame start
+    pub(super) fn clear_sprites(&mut self, world: &World) {
+        self.vissprites.clear();
+        self.sprite_sectors.clear();
+        self.sprite_sectors.resize(world.level.sectors.len(), false);
+    }
+
+    // R_AddSprites. During BSP traversal, this adds sprites by sector
+    pub(super) fn add_sprites(&mut self, world: &World, sector: usize) {
+        // A sector might have been split into several subsectors during
+        // BSP building. Thus we check whether its already added
+        if self.sprite_sectors[sector] {
+            return;
+        }
+
+        // Well, now it will be done
+        self.sprite_sectors[sector] = true;
+
+        let light: usize = self.light_row(world.level.sectors[sector].light_level, 0);
+
+        // Handle all things in sector
+        let mut thing = world.level.sectors[sector].thing_list;
+        while let Some(id) = thing {
+            let mobj: &Mobj = world.thinkers.mobj(id);
+            self.project_sprite(mobj, light);
+            thing = mobj.snext;
+        }
+    }
+
+    // R_ProjectSprite. Generates a vissprite for a thing if it might be
+    // visible
+    fn project_sprite(&mut self, thing: &Mobj, light: usize) {
+        // Transform the origin point
+        let tr_x: Fixed = thing.x - self.view.x;
+        let tr_y: Fixed = thing.y - self.view.y;
+
+        let gxt: Fixed = fixed_mul(tr_x, self.view.cos);
+        let gyt: Fixed = -fixed_mul(tr_y, self.view.sin);
+
+        let tz: Fixed = gxt - gyt;
+
+        // Thing is behind view plane?
+        if tz < MINZ {
+            return;
+        }
+
+        let x_scale: Fixed = fixed_div(self.projection, tz);
+
+        let gxt: Fixed = -fixed_mul(tr_x, self.view.sin);
+        let gyt: Fixed = fixed_mul(tr_y, self.view.cos);
+        let mut tx: Fixed = -(gyt + gxt);
+
+        // Too far off the side?
+        if tx.abs().0 > (tz.0 << 2) {
+            return;
+        }
+
+        // Decide which patch to use for sprite relative to player
+        let sprite_frame: SpriteFrame = self.sprite_frame(thing.sprite, thing.frame);
+        let (lump, flip): (usize, bool) = if sprite_frame.rotate {
+            // Choose a different rotation based on player view
+            let angle: Angle = point_to_angle(self.view.x, self.view.y, thing.x, thing.y);
+            let rotation: usize = ((angle - thing.angle + Angle((ANG45 / 2) * 9)).0 >> 29) as usize;
+            (sprite_frame.lumps[rotation], sprite_frame.flip[rotation])
+        } else {
+            // Use single rotation for all views
+            (sprite_frame.lumps[0], sprite_frame.flip[0])
+        };
+        let patch: &Patch = &self.data.sprite_patches[lump];
+
+        // Calculate edges of the shape
+        tx -= Fixed::from_int(patch.left_offset);
+        let x1: i32 = (self.center_x_frac + fixed_mul(tx, x_scale)).0 >> FRACBITS;
+
+        // Off the right side?
+        if x1 > self.view_width as i32 {
+            return;
+        }
+
+        tx += Fixed::from_int(patch.width as i32);
+        let x2: i32 = ((self.center_x_frac + fixed_mul(tx, x_scale)).0 >> FRACBITS) - 1;
+
+        // Off the left side
+        if x2 < 0 {
+            return;
+        }
+
+        // Store information in a vissprite
+        let gzt: Fixed = thing.z + Fixed::from_int(patch.top_offset);
+        let iscale: Fixed = fixed_div(Fixed::ONE, x_scale);
+        let mut vis: VisSprite = VisSprite {
+            x1: x1.max(0),
+            x2: x2.min(self.view_width as i32 - 1),
+            gx: thing.x,
+            gy: thing.y,
+            gz: thing.z,
+            gzt,
+            start_frac: Fixed::ZERO,
+            scale: x_scale,
+            x_iscale: iscale,
+            texture_mid: gzt - self.view.z,
+            patch: lump,
+            colormap: None,
+            mobj_flags: thing.flags,
+        };
+
+        if flip {
+            vis.start_frac = Fixed::from_int(patch.width as i32 - 1);
+            vis.x_iscale = -iscale;
+        }
+
+        if vis.x1 > x1 {
+            vis.start_frac += Fixed(vis.x_iscale.0.wrapping_mul(vis.x1 - x1));
+        }
+
+        // Get light level
+        vis.colormap = if thing.flags & MF_SHADOW != 0 {
+            // Shadow draw
+            None
+        } else if let Some(colormap) = self.view.fixed_colormap {
+            // Fixed map
+            Some(colormap)
+        } else if thing.frame & FF_FULLBRIGHT != 0 {
+            // Full bright
+            Some(0)
+        } else {
+            // Diminished light
+            let index: usize = ((x_scale.0 >> LIGHTSCALESHIFT) as usize).min(MAXLIGHTSCALE - 1);
+            Some(self.scale_light[light][index])
+        };
+
+        self.vissprites.push(vis);
+    }
+
+    // R_DrawMasked. Sprites back to front, then the masked mid textures
+    // no sprite drew, then the player's weapon over everything
+    pub(super) fn draw_masked(&mut self, world: &World, framebuffer: &mut Framebuffer) {
+        // R_SortVisSprites, the sort keeps things of the same scale in the
+        // order they were added like the original
+        self.vissprites.sort_by_key(|vis| vis.scale);
+
+        // Draw all vissprites back to front
+        for index in 0..self.vissprites.len() {
+            let vis: VisSprite = self.vissprites[index];
+            self.draw_sprite(world, framebuffer, &vis);
+        }
+
+        // Render any remaining masked mid textures
+        for index in (0..self.draw_segs.len()).rev() {
+            let draw_seg: DrawSeg = self.draw_segs[index];
+            if draw_seg.masked_texture_col.is_some() {
+                self.render_masked_seg_range(
+                    world,
+                    framebuffer,
+                    &draw_seg,
+                    draw_seg.x1,
+                    draw_seg.x2,
+                );
+            }
+        }
+
+        // Draw the psprites on top of everything
+        self.draw_player_sprites(world, framebuffer);
+    }
+
+    // R_DrawSprite. Clips the sprite against the drawsegs in front of it,
+    // drawing the masked mid textures behind it first
+    fn draw_sprite(&mut self, world: &World, framebuffer: &mut Framebuffer, vis: &VisSprite) {
+        for x in vis.x1..=vis.x2 {
+            self.clip_bottom[x as usize] = CLIP_UNSET;
+            self.clip_top[x as usize] = CLIP_UNSET;
+        }
+
+        // Scan drawsegs from end to start for obscuring segs. The first
+        // drawseg that has a greater scale is the clip seg
+        for index in (0..self.draw_segs.len()).rev() {
+            let draw_seg: DrawSeg = self.draw_segs[index];
+
+            // Determine if the drawseg obscures the sprite
+            if draw_seg.x1 > vis.x2
+                || draw_seg.x2 < vis.x1
+                || (draw_seg.silhouette == 0 && draw_seg.masked_texture_col.is_none())
+            {
+                // Does not cover sprite
+                continue;
+            }
+
+            let r1: i32 = draw_seg.x1.max(vis.x1);
+            let r2: i32 = draw_seg.x2.min(vis.x2);
+
+            let (low_scale, scale): (Fixed, Fixed) = if draw_seg.scale1 > draw_seg.scale2 {
+                (draw_seg.scale2, draw_seg.scale1)
+            } else {
+                (draw_seg.scale1, draw_seg.scale2)
+            };
+
+            if scale < vis.scale
+                || (low_scale < vis.scale
+                    && !point_on_seg_side(
+                        world,
+                        vis.gx,
+                        vis.gy,
+                        &world.level.segs[draw_seg.cur_line],
+                    ))
+            {
+                // Masked mid texture?
+                if draw_seg.masked_texture_col.is_some() {
+                    self.render_masked_seg_range(world, framebuffer, &draw_seg, r1, r2);
+                }
+                // Seg is behind sprite
+                continue;
+            }
+
+            // Clip this piece of the sprite
+            let mut silhouette: i32 = draw_seg.silhouette;
+
+            if vis.gz >= draw_seg.bsil_height {
+                silhouette &= !SIL_BOTTOM;
+            }
+
+            if vis.gzt <= draw_seg.tsil_height {
+                silhouette &= !SIL_TOP;
+            }
+
+            for x in r1..=r2 {
+                let column: usize = x as usize;
+                if silhouette & SIL_BOTTOM != 0 && self.clip_bottom[column] == CLIP_UNSET {
+                    self.clip_bottom[column] =
+                        self.clip_row(draw_seg.spr_bottom_clip, draw_seg.x1, x);
+                }
+                if silhouette & SIL_TOP != 0 && self.clip_top[column] == CLIP_UNSET {
+                    self.clip_top[column] = self.clip_row(draw_seg.spr_top_clip, draw_seg.x1, x);
+                }
+            }
+        }
+
+        // All clipping has been performed, so draw the sprite. Check for
+        // unclipped columns
+        for x in vis.x1..=vis.x2 {
+            let column: usize = x as usize;
+            if self.clip_bottom[column] == CLIP_UNSET {
+                self.clip_bottom[column] = self.view_height as i32;
+            }
+            if self.clip_top[column] == CLIP_UNSET {
+                self.clip_top[column] = -1;
+            }
+        }
+
+        self.draw_vis_sprite(framebuffer, vis);
+    }
+
+    // R_DrawVisSprite. Draws the columns between the clip rows
+    fn draw_vis_sprite(&mut self, framebuffer: &mut Framebuffer, vis: &VisSprite) {
+        let colormaps: &[u8] = &self.data.colormaps;
+        let style: ColumnStyle = match vis.colormap {
+            // NULL colormap = shadow draw
+            None => ColumnStyle::Fuzz(&colormaps[FUZZ_COLORMAP * 256..(FUZZ_COLORMAP + 1) * 256]),
+            Some(colormap) if vis.mobj_flags & MF_TRANSLATION != 0 => {
+                let table: usize =
+                    ((vis.mobj_flags & MF_TRANSLATION) >> MF_TRANSSHIFT) as usize - 1;
+                ColumnStyle::Translated(
+                    &colormaps[colormap..colormap + 256],
+                    &self.data.translation_tables[table * 256..(table + 1) * 256],
+                )
+            }
+            Some(colormap) => ColumnStyle::Lit(&colormaps[colormap..colormap + 256]),
+        };
+
+        let patch: &Patch = &self.data.sprite_patches[vis.patch];
+        let mut frac: Fixed = vis.start_frac;
+
+        for x in vis.x1..=vis.x2 {
+            let texture_column: i32 = frac.0 >> FRACBITS;
+            frac += vis.x_iscale;
+
+            // The original only checked this with RANGECHECK
+            if texture_column < 0 || texture_column >= patch.width as i32 {
+                continue;
+            }
+
+            let masked: MaskedColumn = MaskedColumn {
+                x,
+                iscale: vis.x_iscale.abs(),
+                texture_mid: vis.texture_mid,
+                top_screen: self.center_y_frac - fixed_mul(vis.texture_mid, vis.scale),
+                scale: vis.scale,
+                floor_clip: self.clip_bottom[x as usize],
+                ceiling_clip: self.clip_top[x as usize],
+                style,
+            };
+            draw_masked_column(
+                framebuffer,
+                self.center_y,
+                &mut self.fuzz_pos,
+                &masked,
+                patch,
+                texture_column as usize,
+            );
+        }
+    }
+
+    // R_DrawPlayerSprites
+    fn draw_player_sprites(&mut self, world: &World, framebuffer: &mut Framebuffer) {
+        let player: &Player = &world.players[world.settings.console_player];
+        let Some(mo) = player.mo else {
+            return;
+        };
+
+        // Get light level
+        let mobj: &Mobj = world.thinkers.mobj(mo);
+        let sector: usize = world.level.subsectors[mobj.subsector].sector;
+        let light: usize = self.light_row(world.level.sectors[sector].light_level, 0);
+
+        // Clip to screen bounds
+        self.clip_bottom.fill(self.view_height as i32);
+        self.clip_top.fill(-1);
+
+        // Add all active psprites
+        for psp in player.psprites.iter() {
+            if psp.state.is_some() {
+                self.draw_psprite(framebuffer, player, psp, light);
+            }
+        }
+    }
+
+    // R_DrawPSprite
+    fn draw_psprite(
+        &mut self,
+        framebuffer: &mut Framebuffer,
+        player: &Player,
+        psp: &PspDef,
+        light: usize,
+    ) {
+        let Some(state) = psp.state else {
+            return;
+        };
+        let frame: i32 = STATES[state].frame;
+
+        // Decide which patch to use
+        let sprite_frame: SpriteFrame = self.sprite_frame(STATES[state].sprite, frame);
+        let lump: usize = sprite_frame.lumps[0];
+        let flip: bool = sprite_frame.flip[0];
+        let patch: &Patch = &self.data.sprite_patches[lump];
+
+        // Calculate edges of the shape
+        let mut tx: Fixed = psp.sx - Fixed::from_int(160);
+
+        tx -= Fixed::from_int(patch.left_offset);
+        let x1: i32 = (self.center_x_frac + fixed_mul(tx, self.psprite_scale)).0 >> FRACBITS;
+
+        // Off the right side
+        if x1 > self.view_width as i32 {
+            return;
+        }
+
+        tx += Fixed::from_int(patch.width as i32);
+        let x2: i32 = ((self.center_x_frac + fixed_mul(tx, self.psprite_scale)).0 >> FRACBITS) - 1;
+
+        // Off the left side
+        if x2 < 0 {
+            return;
+        }
+
+        // Store information in a vissprite
+        let mut vis: VisSprite = VisSprite {
+            x1: x1.max(0),
+            x2: x2.min(self.view_width as i32 - 1),
+            texture_mid: Fixed::from_int(BASEYCENTER) + Fixed(FRACUNIT / 2)
+                - (psp.sy - Fixed::from_int(patch.top_offset)),
+            scale: self.psprite_scale,
+            x_iscale: self.psprite_iscale,
+            patch: lump,
+            ..Default::default()
+        };
+
+        if flip {
+            vis.x_iscale = -self.psprite_iscale;
+            vis.start_frac = Fixed::from_int(patch.width as i32 - 1);
+        }
+
+        if vis.x1 > x1 {
+            vis.start_frac += Fixed(vis.x_iscale.0.wrapping_mul(vis.x1 - x1));
+        }
+
+        let invisibility: i32 = player.powers[PW_INVISIBILITY];
+        vis.colormap = if invisibility > 4 * 32 || invisibility & 8 != 0 {
+            // Shadow draw
+            None
+        } else if let Some(colormap) = self.view.fixed_colormap {
+            // Fixed color
+            Some(colormap)
+        } else if frame & FF_FULLBRIGHT != 0 {
+            // Full bright
+            Some(0)
+        } else {
+            // Local light
+            Some(self.scale_light[light][MAXLIGHTSCALE - 1])
+        };
+
+        self.draw_vis_sprite(framebuffer, &vis);
+    }
+
+    fn sprite_frame(&self, sprite: usize, frame: i32) -> SpriteFrame {
+        let frame: usize = (frame & FF_FRAMEMASK) as usize;
+        match self.data.sprites[sprite].frames.get(frame) {
+            Some(sprite_frame) => *sprite_frame,
+            None => panic!(
+                "R_ProjectSprite: invalid sprite frame {} : {}",
+                sprite, frame
+            ),
+        }
+    }
+}
+
+// R_PointOnSegSide. 0 is the front and 1 the back, same as the nodes
+fn point_on_seg_side(world: &World, x: Fixed, y: Fixed, seg: &Seg) -> bool {
+    let v1: Vertex = world.level.vertexes[seg.v1];
+    let v2: Vertex = world.level.vertexes[seg.v2];
+    let partition: Node = Node {
+        x: v1.x,
+        y: v1.y,
+        dx: v2.x - v1.x,
+        dy: v2.y - v1.y,
+        ..Default::default()
+    };
+    partition.point_on_side(x, y) != 0
+}
@@ -0,0 +1,182 @@
+use std::fmt::{self, Display};
+
+use crate::sound::dmx::DmxSound;
+use crate::sound::pcspeaker::PcSpeakerSound;
+use crate::sound::sfx::{SfxNum, S_SFX};
+use crate::wad::{self, DoomFile};
+
+pub mod dmx;
+pub mod mixer;
+pub mod pcspeaker;
+pub mod sfx;
+pub mod wav;
+
+// snd_sfxdevice from the config file, the numbers are the ones setup.exe wrote
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SfxDevice {
+    None,
+    PcSpeaker,
+    // Sound Blaster, Pro Audio Spectrum, Gravis Ultrasound, etc
+    Digital,
+}
+
+impl SfxDevice {
+    pub fn from_config_value(value: i32) -> Self {
+        match value {
+            1 => SfxDevice::PcSpeaker,
+            3..=7 | 9 => SfxDevice::Digital,
+            // Adlib and General MIDI can only play music
+            _ => SfxDevice::None,
+        }
+    }
+
+    // Which sound lumps the device plays, DSPISTOL or DPPISTOL for example
+    pub fn lump_prefix(&self) -> Option<&str> {
+        match self {
+            SfxDevice::None => None,
+            SfxDevice::PcSpeaker => Some("DP"),
+            SfxDevice::Digital => Some("DS"),
+        }
+    }
+}
+
+// A sound effect lump in the format of the device that plays it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SfxLump {
+    Digital(DmxSound),
+    PcSpeaker(PcSpeakerSound),
+}
+
+impl SfxLump {
+    // I_GetSfxLumpNum. Linked sounds play the lump of the sound they link
+    // to. None when the device doesn't play sound effects or the lump is
+    // missing or broken
+    pub fn load(doom_files: &mut [DoomFile], sfx_device: SfxDevice, sfx: SfxNum) -> Option<Self> {
+        let prefix: &str = sfx_device.lump_prefix()?;
+        let sfx: SfxNum = S_SFX[sfx].link.map_or(sfx, |(link, _, _)| link);
+        let data: Vec<u8> = wad::find_lump_data(doom_files, &S_SFX[sfx].lump_name(prefix))?;
+
+        match sfx_device {
+            SfxDevice::None => None,
+            SfxDevice::PcSpeaker => PcSpeakerSound::from_lump(&data)
+                .ok()
+                .map(SfxLump::PcSpeaker),
+            SfxDevice::Digital => DmxSound::from_lump(&data).ok().map(SfxLump::Digital),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum SoundError {
+    InvalidDmxSound(String),
+    InvalidPcSpeakerSound(String),
+    InvalidWav(String),
+}
+
+impl Display for SoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidDmxSound(reason) => write!(f, "Invalid DMX sound data. {}", reason),
+            Self::InvalidPcSpeakerSound(reason) => {
+                write!(f, "Invalid PC speaker sound data. {}", reason)
+            }
+            Self::InvalidWav(reason) => write!(f, "Invalid WAV data. {}", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::config::Config;
+    use crate::sound::dmx::DmxSound;
+    use crate::sound::pcspeaker::{PcSpeakerSound, DEFAULT_PC_SPEAKER_VOLUME};
+    use crate::sound::sfx::{SFX_CHGUN, SFX_PISTOL, SFX_SHOTGN};
+    use crate::sound::{SfxDevice, SfxLump, SoundError};
+    use crate::test_util::{create_test_dir, write_test_wad};
+    use crate::wad::{self, DoomFile};
+
+    fn create_sfx_files() -> Vec<DoomFile> {
+        let wad_path: PathBuf = create_test_dir("sfx-lump").join("test.wad");
+        let dmx_sound: DmxSound = DmxSound {
+            sample_rate: 11025,
+            samples: vec![128; 64],
+        };
+        let pc_speaker_sound: PcSpeakerSound = PcSpeakerSound {
+            tones: vec![0, 20, 40, 60],
+        };
+        write_test_wad(
+            &wad_path,
+            &[
+                (String::from("DSPISTOL"), dmx_sound.to_lump()),
+                (String::from("DPPISTOL"), pc_speaker_sound.to_lump()),
+            ],
+        );
+        vec![wad::process_file(&wad_path).unwrap()]
+    }
+
+    #[test]
+    fn test_sfx_device_from_config_value() {
+        assert_eq!(SfxDevice::from_config_value(0), SfxDevice::None);
+        assert_eq!(SfxDevice::from_config_value(1), SfxDevice::PcSpeaker);
+        assert_eq!(SfxDevice::from_config_value(2), SfxDevice::None);
+        assert_eq!(SfxDevice::from_config_value(3), SfxDevice::Digital);
+        assert_eq!(SfxDevice::from_config_value(8), SfxDevice::None);
+        assert_eq!(SfxDevice::from_config_value(9), SfxDevice::Digital);
+        assert_eq!(SfxDevice::from_config_value(-1), SfxDevice::None);
+
+        assert_eq!(SfxDevice::PcSpeaker.lump_prefix(), Some("DP"));
+        assert_eq!(SfxDevice::Digital.lump_prefix(), Some("DS"));
+        assert_eq!(SfxDevice::None.lump_prefix(), None);
+    }
+
+    #[test]
+    fn test_sfx_lump_load_uses_the_configured_device() {
+        let mut doom_files: Vec<DoomFile> = create_sfx_files();
+        let mut config: Config = Default::default();
+        config.apply_config_text("snd_sfxdevice 1");
+
+        let Some(SfxLump::PcSpeaker(sound)) =
+            SfxLump::load(&mut doom_files, config.sfx_device, SFX_PISTOL)
+        else {
+            panic!("DPPISTOL wasn't loaded");
+        };
+        assert_eq!(sound.tones, vec![0, 20, 40, 60]);
+        let samples: Vec<i16> = sound.render(11025, DEFAULT_PC_SPEAKER_VOLUME);
+        assert_eq!(samples.len(), 315);
+        assert!(samples.iter().any(|sample| *sample != 0));
+
+        // The chaingun plays the pistol sound
+        assert_eq!(
+            SfxLump::load(&mut doom_files, config.sfx_device, SFX_CHGUN),
+            Some(SfxLump::PcSpeaker(sound))
+        );
+        assert_eq!(
+            SfxLump::load(&mut doom_files, config.sfx_device, SFX_SHOTGN),
+            None
+        );
+
+        assert!(matches!(
+            SfxLump::load(&mut doom_files, SfxDevice::Digital, SFX_PISTOL),
+            Some(SfxLump::Digital(_))
+        ));
+        assert_eq!(
+            SfxLump::load(&mut doom_files, SfxDevice::None, SFX_PISTOL),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sound_error_implements_display_trait() {
+        let sound_errors: Vec<SoundError> = vec![
+            SoundError::InvalidDmxSound(String::from("test")),
+            SoundError::InvalidPcSpeakerSound(String::from("test")),
+            SoundError::InvalidWav(String::from("test")),
+        ];
+
+        for sound_error in sound_errors {
+            println!("{}", sound_error);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::sound::SoundError;
+
+const DMX_HEADER_SIZE: usize = 8;
+const DMX_FORMAT_DIGITAL: u16 = 3;
+// DMX skips the first and last 16 bytes of every sound.
+// Nobody seems to know why
+const DMX_PADDING_SIZE: usize = 16;
+// DMX refuses to play sounds at or below this many samples
+const DMX_MIN_SAMPLE_COUNT: usize = 48;
+
+// Digital sound effect lumps(DS*). Samples are 8 bit unsigned mono
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DmxSound {
+    pub sample_rate: u16,
+    pub samples: Vec<u8>,
+}
+
+impl DmxSound {
+    pub fn from_lump(data: &[u8]) -> Result<Self, SoundError> {
+        if data.len() < DMX_HEADER_SIZE {
+            return Err(SoundError::InvalidDmxSound(String::from(
+                "Lump is smaller than the sound header",
+            )));
+        }
+
+        let format: u16 = u16::from_le_bytes([data[0], data[1]]);
+        let sample_rate: u16 = u16::from_le_bytes([data[2], data[3]]);
+        let sample_count: usize = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+
+        if format != DMX_FORMAT_DIGITAL {
+            return Err(SoundError::InvalidDmxSound(format!(
+                "Unsupported format {}",
+                format
+            )));
+        }
+
+        if sample_count > data.len() - DMX_HEADER_SIZE {
+            return Err(SoundError::InvalidDmxSound(format!(
+                "Sample count {} is larger than the lump",
+                sample_count
+            )));
+        }
+
+        if sample_count <= DMX_MIN_SAMPLE_COUNT {
+            return Err(SoundError::InvalidDmxSound(format!(
+                "Sample count {} is too small to be played",
+                sample_count
+            )));
+        }
+
+        let samples_start: usize = DMX_HEADER_SIZE + DMX_PADDING_SIZE;
+        let samples_end: usize = DMX_HEADER_SIZE + sample_count - DMX_PADDING_SIZE;
+
+        Ok(DmxSound {
+            sample_rate,
+            samples: data[samples_start..samples_end].to_vec(),
+        })
+    }
+
+    pub fn to_lump(&self) -> Vec<u8> {
+        let sample_count: u32 = (self.samples.len() + DMX_PADDING_SIZE * 2) as u32;
+        let first_sample: u8 = self.samples.first().copied().unwrap_or(128);
+        let last_sample: u8 = self.samples.last().copied().unwrap_or(128);
+
+        let mut lump: Vec<u8> = Vec::with_capacity(DMX_HEADER_SIZE + sample_count as usize);
+        lump.extend_from_slice(&DMX_FORMAT_DIGITAL.to_le_bytes());
+        lump.extend_from_slice(&self.sample_rate.to_le_bytes());
+        lump.extend_from_slice(&sample_count.to_le_bytes());
+        // The id tools filled the padding with the first and last sample
+        lump.extend_from_slice(&[first_sample; DMX_PADDING_SIZE]);
+        lump.extend_from_slice(&self.samples);
+        lump.extend_from_slice(&[last_sample; DMX_PADDING_SIZE]);
+
+        lump
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sound::dmx::DmxSound;
+    use crate::sound::SoundError;
+
+    fn create_dmx_lump(sample_rate: u16, samples: &[u8]) -> Vec<u8> {
+        let mut lump: Vec<u8> = vec![3, 0];
+        lump.extend_from_slice(&sample_rate.to_le_bytes());
+        lump.extend_from_slice(&((samples.len() + 32) as u32).to_le_bytes());
+        lump.extend_from_slice(&[0; 16]);
+        lump.extend_from_slice(samples);
+        lump.extend_from_slice(&[0; 16]);
+        lump
+    }
+
+    #[test]
+    fn test_dmx_sound_from_lump_strips_padding() {
+        let samples: Vec<u8> = (0..64).collect();
+        let lump: Vec<u8> = create_dmx_lump(11025, &samples);
+
+        let dmx_sound: DmxSound = DmxSound::from_lump(&lump).unwrap();
+
+        assert_eq!(dmx_sound.sample_rate, 11025);
+        assert_eq!(dmx_sound.samples, samples);
+    }
+
+    #[test]
+    fn test_dmx_sound_from_lump_returns_error_for_invalid_lumps() {
+        assert!(DmxSound::from_lump(&[3, 0, 0x11, 0x2B]).is_err());
+
+        let mut wrong_format: Vec<u8> = create_dmx_lump(11025, &[128; 64]);
+        wrong_format[0] = 0;
+        assert_eq!(
+            DmxSound::from_lump(&wrong_format),
+            Err(SoundError::InvalidDmxSound(String::from(
+                "Unsupported format 0"
+            )))
+        );
+
+        let mut truncated: Vec<u8> = create_dmx_lump(11025, &[128; 64]);
+        truncated.truncate(50);
+        assert!(DmxSound::from_lump(&truncated).is_err());
+
+        let too_short: Vec<u8> = create_dmx_lump(11025, &[128; 16]);
+        assert!(DmxSound::from_lump(&too_short).is_err());
+    }
+
+    #[test]
+    fn test_dmx_sound_lump_round_trip() {
+        let dmx_sound: DmxSound = DmxSound {
+            sample_rate: 22050,
+            samples: (0..200).map(|x| x as u8).collect(),
+        };
+
+        let lump: Vec<u8> = dmx_sound.to_lump();
+
+        assert_eq!(lump.len(), 8 + 200 + 32);
+        assert_eq!(DmxSound::from_lump(&lump).unwrap(), dmx_sound);
+    }
+}
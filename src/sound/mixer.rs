@@ -0,0 +1,453 @@
+use crate::fixed::{fixed_mul, point_to_angle, Angle, Fixed, FRACBITS, FRACUNIT};
+use crate::sound::dmx::DmxSound;
+use crate::sound::wav::Wav;
+
+// Same number of channels vanilla defaults to(snd_channels)
+pub const DEFAULT_MIXER_CHANNELS: usize = 8;
+pub const DEFAULT_OUTPUT_SAMPLE_RATE: u32 = 11025;
+pub const MAX_SOUND_VOLUME: i32 = 127;
+pub const NORMAL_SEPARATION: i32 = 128;
+
+// Sounds further away than this can't be heard
+const S_CLIPPING_DIST: i32 = 1200 * FRACUNIT;
+// Sounds closer than this are played at full volume
+const S_CLOSE_DIST: i32 = 200 * FRACUNIT;
+const S_ATTENUATOR: i32 = (S_CLIPPING_DIST - S_CLOSE_DIST) >> FRACBITS;
+const S_STEREO_SWING: i32 = 96 * FRACUNIT;
+
+// Where the sound is being heard from. x/y are fixed point map coordinates
+// and angle is a binary angle(0x40000000 is 90 degrees)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SoundListener {
+    pub x: i32,
+    pub y: i32,
+    pub angle: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SoundParams {
+    // 0 to 127
+    pub volume: i32,
+    // 0 is all the way left, 255 all the way right
+    pub separation: i32,
+}
+
+impl SoundParams {
+    // Sounds made by the listener themselves aren't positioned
+    pub fn centered(volume: i32) -> Self {
+        SoundParams {
+            volume,
+            separation: NORMAL_SEPARATION,
+        }
+    }
+}
+
+// Port of S_AdjustSoundParams. Works out how loud a sound is and how far
+// left/right it is from where the listener is standing and facing.
+// Returns None if the sound is too far away to be heard.
+// On boss maps(E1M8, etc) sounds can be heard from anywhere
+pub fn adjust_sound_params(
+    listener: &SoundListener,
+    source_x: i32,
+    source_y: i32,
+    sfx_volume: i32,
+    is_boss_map: bool,
+) -> Option<SoundParams> {
+    let adx: i32 = (i64::from(listener.x) - i64::from(source_x))
+        .unsigned_abs()
+        .min(i32::MAX as u64) as i32;
+    let ady: i32 = (i64::from(listener.y) - i64::from(source_y))
+        .unsigned_abs()
+        .min(i32::MAX as u64) as i32;
+
+    // From _GG1_ p.428. Appox. eucledian distance fast.
+    let mut approx_dist: i32 = adx.saturating_add(ady) - (adx.min(ady) >> 1);
+
+    if !is_boss_map && approx_dist > S_CLIPPING_DIST {
+        return None;
+    }
+
+    let mut angle: u32 = point_to_angle(
+        Fixed(listener.x),
+        Fixed(listener.y),
+        Fixed(source_x),
+        Fixed(source_y),
+    )
+    .0;
+
+    // The original is off by one here, keep it that way
+    if angle > listener.angle {
+        angle -= listener.angle;
+    } else {
+        angle = angle.wrapping_add(0xffffffff - listener.angle);
+    }
+
+    let separation: i32 =
+        NORMAL_SEPARATION - (fixed_mul(Fixed(S_STEREO_SWING), Angle(angle).sin()).0 >> FRACBITS);
+
+    let volume: i32 = if approx_dist < S_CLOSE_DIST {
+        sfx_volume
+    } else if is_boss_map {
+        approx_dist = approx_dist.min(S_CLIPPING_DIST);
+        15 + ((sfx_volume - 15) * ((S_CLIPPING_DIST - approx_dist) >> FRACBITS)) / S_ATTENUATOR
+    } else {
+        (sfx_volume * ((S_CLIPPING_DIST - approx_dist) >> FRACBITS)) / S_ATTENUATOR
+    };
+
+    if volume > 0 {
+        Some(SoundParams { volume, separation })
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Debug)]
+struct MixerChannel {
+    samples: Vec<u8>,
+    // 16.16 fixed point position and step through the samples
+    position: u64,
+    step: u64,
+    left_volume: i32,
+    right_volume: i32,
+    origin: Option<usize>,
+    priority: i32,
+}
+
+impl MixerChannel {
+    fn set_params(&mut self, params: SoundParams) {
+        let volume: i32 = params.volume.clamp(0, MAX_SOUND_VOLUME);
+        let separation: i32 = params.separation.clamp(0, 255) + 1;
+
+        // Same panning curve as linuxdoom's addsfx
+        self.left_volume = volume - ((volume * separation * separation) >> 16);
+        let separation: i32 = separation - 257;
+        self.right_volume = volume - ((volume * separation * separation) >> 16);
+    }
+
+    fn is_finished(&self) -> bool {
+        (self.position >> FRACBITS) as usize >= self.samples.len()
+    }
+}
+
+// Fixed channel software mixer that renders 16 bit stereo PCM.
+// Channel allocation follows S_getChannel, lower priority numbers are more important
+pub struct Mixer {
+    pub output_sample_rate: u32,
+    channels: Vec<Option<MixerChannel>>,
+}
+
+impl Mixer {
+    pub fn new(num_channels: usize, output_sample_rate: u32) -> Self {
+        Mixer {
+            output_sample_rate,
+            channels: vec![None; num_channels],
+        }
+    }
+
+    // Returns the channel the sound is playing on or None if every
+    // channel is busy with something more important. Sounds without samples
+    // or too slow to ever move through them aren't played
+    pub fn start_sound(
+        &mut self,
+        sound: &DmxSound,
+        origin: Option<usize>,
+        priority: i32,
+        params: SoundParams,
+    ) -> Option<usize> {
+        let step: u64 =
+            (u64::from(sound.sample_rate) << FRACBITS) / u64::from(self.output_sample_rate.max(1));
+        if sound.samples.is_empty() || step == 0 {
+            return None;
+        }
+
+        // A thing can only make one sound at a time
+        if origin.is_some() {
+            for channel in self.channels.iter_mut() {
+                if channel
+                    .as_ref()
+                    .is_some_and(|channel| channel.origin == origin)
+                {
+                    *channel = None;
+                }
+            }
+        }
+
+        let channel_index: usize = match self.channels.iter().position(Option::is_none) {
+            Some(channel_index) => channel_index,
+            None => self.channels.iter().position(|channel| {
+                channel
+                    .as_ref()
+                    .is_some_and(|channel| channel.priority >= priority)
+            })?,
+        };
+
+        let mut channel: MixerChannel = MixerChannel {
+            samples: sound.samples.clone(),
+            position: 0,
+            step,
+            left_volume: 0,
+            right_volume: 0,
+            origin,
+            priority,
+        };
+        channel.set_params(params);
+        self.channels[channel_index] = Some(channel);
+
+        Some(channel_index)
+    }
+
+    pub fn stop_sound(&mut self, channel_index: usize) {
+        if let Some(channel) = self.channels.get_mut(channel_index) {
+            *channel = None;
+        }
+    }
+
+    pub fn stop_all_sounds(&mut self) {
+        self.channels.iter_mut().for_each(|channel| *channel = None);
+    }
+
+    // For moving sources, like S_UpdateSounds does every tic
+    pub fn update_sound_params(&mut self, channel_index: usize, params: SoundParams) {
+        if let Some(Some(channel)) = self.channels.get_mut(channel_index) {
+            channel.set_params(params);
+        }
+    }
+
+    pub fn is_playing(&self, channel_index: usize) -> bool {
+        self.channels
+            .get(channel_index)
+            .is_some_and(|channel| channel.is_some())
+    }
+
+    pub fn num_channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    // Mixes the next frame_count frames as interleaved left/right samples
+    pub fn render(&mut self, frame_count: usize) -> Vec<i16> {
+        let mut output: Vec<i16> = Vec::with_capacity(frame_count * 2);
+
+        for _ in 0..frame_count {
+            let mut left: i32 = 0;
+            let mut right: i32 = 0;
+
+            for slot in self.channels.iter_mut() {
+                if let Some(channel) = slot {
+                    let sample: i32 =
+                        i32::from(channel.samples[(channel.position >> FRACBITS) as usize]) - 128;
+
+                    left += (channel.left_volume * sample * 256) / MAX_SOUND_VOLUME;
+                    right += (channel.right_volume * sample * 256) / MAX_SOUND_VOLUME;
+
+                    channel.position += channel.step;
+                    if channel.is_finished() {
+                        *slot = None;
+                    }
+                }
+            }
+
+            output.push(left.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16);
+            output.push(right.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16);
+        }
+
+        output
+    }
+
+    pub fn render_wav(&mut self, frame_count: usize) -> Wav {
+        Wav {
+            sample_rate: self.output_sample_rate,
+            channels: 2,
+            bits_per_sample: 16,
+            data: self
+                .render(frame_count)
+                .iter()
+                .flat_map(|sample| sample.to_le_bytes())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::FRACUNIT;
+    use crate::sound::dmx::DmxSound;
+    use crate::sound::mixer::{
+        adjust_sound_params, Mixer, SoundListener, SoundParams, MAX_SOUND_VOLUME, NORMAL_SEPARATION,
+    };
+    use crate::sound::wav::Wav;
+
+    fn create_sound(sample_rate: u16, sample: u8, sample_count: usize) -> DmxSound {
+        DmxSound {
+            sample_rate,
+            samples: vec![sample; sample_count],
+        }
+    }
+
+    fn create_listener() -> SoundListener {
+        // Facing east
+        SoundListener {
+            x: 0,
+            y: 0,
+            angle: 0,
+        }
+    }
+
+    #[test]
+    fn test_adjust_sound_params_close_sounds_are_full_volume() {
+        let params: SoundParams =
+            adjust_sound_params(&create_listener(), 100 * FRACUNIT, 0, 120, false).unwrap();
+
+        assert_eq!(params.volume, 120);
+        // Straight ahead wraps round to just right of center because of the off by one
+        assert_eq!(params.separation, NORMAL_SEPARATION + 1);
+    }
+
+    #[test]
+    fn test_adjust_sound_params_attenuates_with_distance() {
+        let near: SoundParams =
+            adjust_sound_params(&create_listener(), 400 * FRACUNIT, 0, 120, false).unwrap();
+        let far: SoundParams =
+            adjust_sound_params(&create_listener(), 1000 * FRACUNIT, 0, 120, false).unwrap();
+
+        assert_eq!(near.volume, 120 * 800 / 1000);
+        assert_eq!(far.volume, 120 * 200 / 1000);
+
+        assert_eq!(
+            adjust_sound_params(&create_listener(), 1201 * FRACUNIT, 0, 120, false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_adjust_sound_params_boss_maps_can_be_heard_everywhere() {
+        let params: SoundParams =
+            adjust_sound_params(&create_listener(), 5000 * FRACUNIT, 0, 120, true).unwrap();
+
+        assert_eq!(params.volume, 15);
+    }
+
+    #[test]
+    fn test_adjust_sound_params_separation_follows_listener_angle() {
+        // North is to the left when facing east
+        let left: SoundParams =
+            adjust_sound_params(&create_listener(), 0, 300 * FRACUNIT, 120, false).unwrap();
+        let right: SoundParams =
+            adjust_sound_params(&create_listener(), 0, -300 * FRACUNIT, 120, false).unwrap();
+
+        assert_eq!(left.separation, NORMAL_SEPARATION - 95);
+        assert_eq!(right.separation, NORMAL_SEPARATION + 96);
+
+        // Turning to face north puts the sound in front
+        let facing_north: SoundListener = SoundListener {
+            angle: 0x40000000,
+            ..create_listener()
+        };
+        let ahead: SoundParams =
+            adjust_sound_params(&facing_north, 0, 300 * FRACUNIT, 120, false).unwrap();
+
+        assert!((ahead.separation - NORMAL_SEPARATION).abs() <= 1);
+    }
+
+    #[test]
+    fn test_mixer_render_pans_sounds() {
+        let mut mixer: Mixer = Mixer::new(8, 11025);
+        mixer.start_sound(
+            &create_sound(11025, 228, 100),
+            None,
+            64,
+            SoundParams {
+                volume: MAX_SOUND_VOLUME,
+                separation: 0,
+            },
+        );
+
+        let output: Vec<i16> = mixer.render(1);
+
+        assert_eq!(output[0], 100 * 256);
+        assert_eq!(output[1], 0);
+    }
+
+    #[test]
+    fn test_mixer_render_resamples_and_finishes_sounds() {
+        let mut mixer: Mixer = Mixer::new(8, 22050);
+        let channel: usize = mixer
+            .start_sound(
+                &create_sound(11025, 255, 50),
+                None,
+                64,
+                SoundParams::centered(MAX_SOUND_VOLUME),
+            )
+            .unwrap();
+
+        let output: Vec<i16> = mixer.render(100);
+        assert!(output.iter().all(|sample| *sample > 0));
+        assert!(!mixer.is_playing(channel));
+
+        assert_eq!(mixer.render(1), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_mixer_render_clamps_mixed_samples() {
+        let mut mixer: Mixer = Mixer::new(8, 11025);
+        for origin in 0..4 {
+            mixer.start_sound(
+                &create_sound(11025, 0, 100),
+                Some(origin),
+                64,
+                SoundParams::centered(MAX_SOUND_VOLUME),
+            );
+        }
+
+        assert_eq!(mixer.render(1), vec![i16::MIN, i16::MIN]);
+    }
+
+    #[test]
+    fn test_mixer_start_sound_replaces_origin_and_lower_priority_sounds() {
+        let mut mixer: Mixer = Mixer::new(2, 11025);
+        let sound: DmxSound = create_sound(11025, 128, 100);
+        let params: SoundParams = SoundParams::centered(MAX_SOUND_VOLUME);
+
+        assert_eq!(mixer.start_sound(&sound, Some(1), 64, params), Some(0));
+        // Same thing making a new sound stops the old one
+        assert_eq!(mixer.start_sound(&sound, Some(1), 64, params), Some(0));
+        assert_eq!(mixer.start_sound(&sound, Some(2), 32, params), Some(1));
+
+        // Everything is busy with more important sounds
+        assert_eq!(mixer.start_sound(&sound, Some(3), 16, params), Some(0));
+        assert_eq!(mixer.start_sound(&sound, Some(4), 100, params), None);
+
+        mixer.stop_sound(1);
+        assert!(!mixer.is_playing(1));
+        assert_eq!(mixer.start_sound(&sound, Some(4), 100, params), Some(1));
+
+        mixer.stop_all_sounds();
+        assert!(!mixer.is_playing(0));
+    }
+
+    #[test]
+    fn test_mixer_start_sound_skips_empty_and_zero_rate_sounds() {
+        let mut mixer: Mixer = Mixer::new(2, 11025);
+        let params: SoundParams = SoundParams::centered(MAX_SOUND_VOLUME);
+
+        assert_eq!(
+            mixer.start_sound(&create_sound(11025, 200, 0), None, 64, params),
+            None
+        );
+        assert_eq!(
+            mixer.start_sound(&create_sound(0, 200, 10), None, 64, params),
+            None
+        );
+        assert!(!mixer.is_playing(0));
+        assert_eq!(mixer.render(4), vec![0; 8]);
+    }
+
+    #[test]
+    fn test_mixer_render_wav_writes_16_bit_stereo() {
+        let mut mixer: Mixer = Mixer::new(8, 11025);
+        let wav: Wav = mixer.render_wav(10);
+
+        assert_eq!(wav.channels, 2);
+        assert_eq!(wav.bits_per_sample, 16);
+        assert_eq!(wav.data.len(), 10 * 4);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::sound::wav::Wav;
+use crate::sound::SoundError;
+
+// Tones change 140 times a second, same as MUS music
+pub const PC_SPEAKER_TONE_RATE: u32 = 140;
+// The PIT timer that drives the speaker runs at 1.193182MHz
+pub const PIT_FREQUENCY: u32 = 1193181;
+pub const DEFAULT_PC_SPEAKER_VOLUME: i16 = 8192;
+
+const PC_SPEAKER_HEADER_SIZE: usize = 4;
+const PC_SPEAKER_FORMAT: u16 = 0;
+
+// PIT divisors for each tone value DMX used, roughly quarter tones
+// from 175Hz up. Tone 0 is silence
+const TONE_DIVISORS: [u16; 128] = [
+    0, 6818, 6628, 6449, 6279, 6087, 5906, 5736, 5575, 5423, 5279, 5120, 4971, 4830, 4697, 4554,
+    4435, 4307, 4186, 4058, 3950, 3836, 3728, 3615, 3519, 3418, 3323, 3224, 3131, 3043, 2960, 2875,
+    2794, 2711, 2633, 2560, 2485, 2415, 2348, 2281, 2213, 2153, 2089, 2032, 1975, 1918, 1864, 1810,
+    1757, 1709, 1659, 1612, 1565, 1521, 1478, 1435, 1395, 1355, 1316, 1280, 1242, 1207, 1173, 1140,
+    1107, 1075, 1045, 1015, 986, 959, 931, 905, 879, 854, 829, 806, 783, 760, 739, 718, 697, 677,
+    658, 640, 621, 604, 586, 570, 553, 538, 522, 507, 493, 479, 465, 452, 439, 427, 415, 403, 391,
+    380, 369, 359, 348, 339, 329, 319, 310, 302, 293, 285, 276, 269, 261, 253, 246, 239, 232, 226,
+    219, 213, 207, 201, 195, 190, 184, 179,
+];
+
+// PC speaker sound effect lumps(DP*). A list of tones played one after the other
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PcSpeakerSound {
+    pub tones: Vec<u8>,
+}
+
+impl PcSpeakerSound {
+    pub fn from_lump(data: &[u8]) -> Result<Self, SoundError> {
+        if data.len() < PC_SPEAKER_HEADER_SIZE {
+            return Err(SoundError::InvalidPcSpeakerSound(String::from(
+                "Lump is smaller than the sound header",
+            )));
+        }
+
+        let format: u16 = u16::from_le_bytes([data[0], data[1]]);
+        let tone_count: usize = usize::from(u16::from_le_bytes([data[2], data[3]]));
+
+        if format != PC_SPEAKER_FORMAT {
+            return Err(SoundError::InvalidPcSpeakerSound(format!(
+                "Unsupported format {}",
+                format
+            )));
+        }
+
+        if tone_count > data.len() - PC_SPEAKER_HEADER_SIZE {
+            return Err(SoundError::InvalidPcSpeakerSound(format!(
+                "Tone count {} is larger than the lump",
+                tone_count
+            )));
+        }
+
+        Ok(PcSpeakerSound {
+            tones: data[PC_SPEAKER_HEADER_SIZE..PC_SPEAKER_HEADER_SIZE + tone_count].to_vec(),
+        })
+    }
+
+    pub fn to_lump(&self) -> Vec<u8> {
+        let mut lump: Vec<u8> = Vec::with_capacity(PC_SPEAKER_HEADER_SIZE + self.tones.len());
+        lump.extend_from_slice(&PC_SPEAKER_FORMAT.to_le_bytes());
+        lump.extend_from_slice(&(self.tones.len() as u16).to_le_bytes());
+        lump.extend_from_slice(&self.tones);
+        lump
+    }
+
+    // Renders the whole sound as 16 bit mono
+    pub fn render(&self, sample_rate: u32, volume: i16) -> Vec<i16> {
+        let mut pc_speaker: PcSpeaker = PcSpeaker::new(sample_rate, volume);
+        pc_speaker.start_sound(self);
+
+        let frame_count: u64 = (self.tones.len() as u64 * u64::from(sample_rate))
+            .div_ceil(u64::from(PC_SPEAKER_TONE_RATE));
+
+        pc_speaker
+            .render(frame_count as usize)
+            .into_iter()
+            .step_by(2)
+            .collect()
+    }
+
+    pub fn to_wav(&self, sample_rate: u32) -> Wav {
+        Wav {
+            sample_rate,
+            channels: 1,
+            bits_per_sample: 16,
+            data: self
+                .render(sample_rate, DEFAULT_PC_SPEAKER_VOLUME)
+                .iter()
+                .flat_map(|sample| sample.to_le_bytes())
+                .collect(),
+        }
+    }
+}
+
+// None for silence and tones past the end of the table
+pub fn tone_divisor(tone: u8) -> Option<u16> {
+    TONE_DIVISORS
+        .get(usize::from(tone))
+        .copied()
+        .filter(|divisor| *divisor != 0)
+}
+
+// The speaker can only play one thing at a time, so a new sound
+// cuts off whatever was playing. Renders a square wave as stereo
+// to match the digital mixer
+pub struct PcSpeaker {
+    pub sample_rate: u32,
+    pub volume: i16,
+    tones: Vec<u8>,
+    frames_rendered: u64,
+    // Time since the sound started in PIT ticks multiplied by sample_rate
+    phase: u64,
+    current_tone: usize,
+}
+
+impl PcSpeaker {
+    pub fn new(sample_rate: u32, volume: i16) -> Self {
+        PcSpeaker {
+            sample_rate: sample_rate.max(1),
+            volume,
+            tones: Vec::new(),
+            frames_rendered: 0,
+            phase: 0,
+            current_tone: 0,
+        }
+    }
+
+    pub fn start_sound(&mut self, sound: &PcSpeakerSound) {
+        self.tones = sound.tones.clone();
+        self.frames_rendered = 0;
+        self.phase = 0;
+        self.current_tone = 0;
+    }
+
+    pub fn stop_sound(&mut self) {
+        self.tones.clear();
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.current_tone < self.tones.len()
+    }
+
+    // Interleaved left/right samples
+    pub fn render(&mut self, frame_count: usize) -> Vec<i16> {
+        let mut output: Vec<i16> = Vec::with_capacity(frame_count * 2);
+
+        for _ in 0..frame_count {
+            let sample: i16 = match self
+                .tones
+                .get(self.current_tone)
+                .copied()
+                .and_then(tone_divisor)
+            {
+                Some(divisor) => {
+                    let half_period: u64 = u64::from(divisor) * u64::from(self.sample_rate) / 2;
+                    let high: bool = (self.phase / half_period).is_multiple_of(2);
+
+                    if high {
+                        self.volume
+                    } else {
+                        -self.volume
+                    }
+                }
+                None => 0,
+            };
+
+            output.push(sample);
+            output.push(sample);
+
+            if self.is_playing() {
+                self.phase += u64::from(PIT_FREQUENCY);
+                self.frames_rendered += 1;
+                self.current_tone = (self.frames_rendered * u64::from(PC_SPEAKER_TONE_RATE)
+                    / u64::from(self.sample_rate)) as usize;
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sound::pcspeaker::{tone_divisor, PcSpeaker, PcSpeakerSound, PIT_FREQUENCY};
+    use crate::sound::wav::Wav;
+    use crate::sound::SoundError;
+
+    #[test]
+    fn test_pc_speaker_sound_lump_round_trip() {
+        let sound: PcSpeakerSound = PcSpeakerSound {
+            tones: vec![0, 10, 20, 127],
+        };
+        let lump: Vec<u8> = sound.to_lump();
+
+        assert_eq!(lump, vec![0, 0, 4, 0, 0, 10, 20, 127]);
+        assert_eq!(PcSpeakerSound::from_lump(&lump).unwrap(), sound);
+    }
+
+    #[test]
+    fn test_pc_speaker_sound_from_lump_returns_error_for_invalid_lumps() {
+        assert!(PcSpeakerSound::from_lump(&[0, 0, 1]).is_err());
+        assert_eq!(
+            PcSpeakerSound::from_lump(&[3, 0, 1, 0, 5]),
+            Err(SoundError::InvalidPcSpeakerSound(String::from(
+                "Unsupported format 3"
+            )))
+        );
+        assert!(PcSpeakerSound::from_lump(&[0, 0, 5, 0, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_tone_divisor_lookup() {
+        assert_eq!(tone_divisor(0), None);
+        assert_eq!(tone_divisor(1), Some(6818));
+        assert_eq!(tone_divisor(127), Some(179));
+        assert_eq!(tone_divisor(128), None);
+    }
+
+    #[test]
+    fn test_pc_speaker_sound_render_plays_square_wave_at_tone_frequency() {
+        // 140 tones of tone 1 is one second of 175Hz
+        let sound: PcSpeakerSound = PcSpeakerSound {
+            tones: vec![1; 140],
+        };
+
+        let samples: Vec<i16> = sound.render(44100, 1000);
+
+        assert_eq!(samples.len(), 44100);
+        assert!(samples
+            .iter()
+            .all(|sample| *sample == 1000 || *sample == -1000));
+
+        let rising_edges: usize = samples
+            .windows(2)
+            .filter(|pair| pair[0] < 0 && pair[1] > 0)
+            .count();
+        let frequency: u32 = PIT_FREQUENCY / 6818;
+        assert!(rising_edges.abs_diff(frequency as usize) <= 1);
+    }
+
+    #[test]
+    fn test_pc_speaker_sound_render_silent_tones() {
+        let sound: PcSpeakerSound = PcSpeakerSound { tones: vec![0, 0] };
+
+        let samples: Vec<i16> = sound.render(14000, 1000);
+
+        assert_eq!(samples.len(), 200);
+        assert!(samples.iter().all(|sample| *sample == 0));
+    }
+
+    #[test]
+    fn test_pc_speaker_new_sound_replaces_current_sound() {
+        let mut pc_speaker: PcSpeaker = PcSpeaker::new(14000, 1000);
+        pc_speaker.start_sound(&PcSpeakerSound { tones: vec![1; 10] });
+        pc_speaker.render(250);
+
+        pc_speaker.start_sound(&PcSpeakerSound { tones: vec![50; 2] });
+        assert!(pc_speaker.is_playing());
+        pc_speaker.render(200);
+        assert!(!pc_speaker.is_playing());
+        assert!(pc_speaker.render(10).iter().all(|sample| *sample == 0));
+
+        pc_speaker.start_sound(&PcSpeakerSound { tones: vec![50; 2] });
+        pc_speaker.stop_sound();
+        assert!(!pc_speaker.is_playing());
+    }
+
+    #[test]
+    fn test_pc_speaker_sound_to_wav_writes_16_bit_mono() {
+        let wav: Wav = PcSpeakerSound { tones: vec![5; 14] }.to_wav(14000);
+
+        assert_eq!(wav.channels, 1);
+        assert_eq!(wav.bits_per_sample, 16);
+        assert_eq!(wav.data.len(), 1400 * 2);
+    }
+}
@@ -0,0 +1,271 @@
+// Sound effect numbers and info from the original sounds.c
+pub type SfxNum = usize;
+
+pub const SFX_NONE: SfxNum = 0;
+pub const SFX_PISTOL: SfxNum = 1;
+pub const SFX_SHOTGN: SfxNum = 2;
+pub const SFX_SGCOCK: SfxNum = 3;
+pub const SFX_DSHTGN: SfxNum = 4;
+pub const SFX_DBOPN: SfxNum = 5;
+pub const SFX_DBCLS: SfxNum = 6;
+pub const SFX_DBLOAD: SfxNum = 7;
+pub const SFX_PLASMA: SfxNum = 8;
+pub const SFX_BFG: SfxNum = 9;
+pub const SFX_SAWUP: SfxNum = 10;
+pub const SFX_SAWIDL: SfxNum = 11;
+pub const SFX_SAWFUL: SfxNum = 12;
+pub const SFX_SAWHIT: SfxNum = 13;
+pub const SFX_RLAUNC: SfxNum = 14;
+pub const SFX_RXPLOD: SfxNum = 15;
+pub const SFX_FIRSHT: SfxNum = 16;
+pub const SFX_FIRXPL: SfxNum = 17;
+pub const SFX_PSTART: SfxNum = 18;
+pub const SFX_PSTOP: SfxNum = 19;
+pub const SFX_DOROPN: SfxNum = 20;
+pub const SFX_DORCLS: SfxNum = 21;
+pub const SFX_STNMOV: SfxNum = 22;
+pub const SFX_SWTCHN: SfxNum = 23;
+pub const SFX_SWTCHX: SfxNum = 24;
+pub const SFX_PLPAIN: SfxNum = 25;
+pub const SFX_DMPAIN: SfxNum = 26;
+pub const SFX_POPAIN: SfxNum = 27;
+pub const SFX_VIPAIN: SfxNum = 28;
+pub const SFX_MNPAIN: SfxNum = 29;
+pub const SFX_PEPAIN: SfxNum = 30;
+pub const SFX_SLOP: SfxNum = 31;
+pub const SFX_ITEMUP: SfxNum = 32;
+pub const SFX_WPNUP: SfxNum = 33;
+pub const SFX_OOF: SfxNum = 34;
+pub const SFX_TELEPT: SfxNum = 35;
+pub const SFX_POSIT1: SfxNum = 36;
+pub const SFX_POSIT2: SfxNum = 37;
+pub const SFX_POSIT3: SfxNum = 38;
+pub const SFX_BGSIT1: SfxNum = 39;
+pub const SFX_BGSIT2: SfxNum = 40;
+pub const SFX_SGTSIT: SfxNum = 41;
+pub const SFX_CACSIT: SfxNum = 42;
+pub const SFX_BRSSIT: SfxNum = 43;
+pub const SFX_CYBSIT: SfxNum = 44;
+pub const SFX_SPISIT: SfxNum = 45;
+pub const SFX_BSPSIT: SfxNum = 46;
+pub const SFX_KNTSIT: SfxNum = 47;
+pub const SFX_VILSIT: SfxNum = 48;
+pub const SFX_MANSIT: SfxNum = 49;
+pub const SFX_PESIT: SfxNum = 50;
+pub const SFX_SKLATK: SfxNum = 51;
+pub const SFX_SGTATK: SfxNum = 52;
+pub const SFX_SKEPCH: SfxNum = 53;
+pub const SFX_VILATK: SfxNum = 54;
+pub const SFX_CLAW: SfxNum = 55;
+pub const SFX_SKESWG: SfxNum = 56;
+pub const SFX_PLDETH: SfxNum = 57;
+pub const SFX_PDIEHI: SfxNum = 58;
+pub const SFX_PODTH1: SfxNum = 59;
+pub const SFX_PODTH2: SfxNum = 60;
+pub const SFX_PODTH3: SfxNum = 61;
+pub const SFX_BGDTH1: SfxNum = 62;
+pub const SFX_BGDTH2: SfxNum = 63;
+pub const SFX_SGTDTH: SfxNum = 64;
+pub const SFX_CACDTH: SfxNum = 65;
+pub const SFX_SKLDTH: SfxNum = 66;
+pub const SFX_BRSDTH: SfxNum = 67;
+pub const SFX_CYBDTH: SfxNum = 68;
+pub const SFX_SPIDTH: SfxNum = 69;
+pub const SFX_BSPDTH: SfxNum = 70;
+pub const SFX_VILDTH: SfxNum = 71;
+pub const SFX_KNTDTH: SfxNum = 72;
+pub const SFX_PEDTH: SfxNum = 73;
+pub const SFX_SKEDTH: SfxNum = 74;
+pub const SFX_POSACT: SfxNum = 75;
+pub const SFX_BGACT: SfxNum = 76;
+pub const SFX_DMACT: SfxNum = 77;
+pub const SFX_BSPACT: SfxNum = 78;
+pub const SFX_BSPWLK: SfxNum = 79;
+pub const SFX_VILACT: SfxNum = 80;
+pub const SFX_NOWAY: SfxNum = 81;
+pub const SFX_BAREXP: SfxNum = 82;
+pub const SFX_PUNCH: SfxNum = 83;
+pub const SFX_HOOF: SfxNum = 84;
+pub const SFX_METAL: SfxNum = 85;
+pub const SFX_CHGUN: SfxNum = 86;
+pub const SFX_TINK: SfxNum = 87;
+pub const SFX_BDOPN: SfxNum = 88;
+pub const SFX_BDCLS: SfxNum = 89;
+pub const SFX_ITMBK: SfxNum = 90;
+pub const SFX_FLAME: SfxNum = 91;
+pub const SFX_FLAMST: SfxNum = 92;
+pub const SFX_GETPOW: SfxNum = 93;
+pub const SFX_BOSPIT: SfxNum = 94;
+pub const SFX_BOSCUB: SfxNum = 95;
+pub const SFX_BOSSIT: SfxNum = 96;
+pub const SFX_BOSPN: SfxNum = 97;
+pub const SFX_BOSDTH: SfxNum = 98;
+pub const SFX_MANATK: SfxNum = 99;
+pub const SFX_MANDTH: SfxNum = 100;
+pub const SFX_SSSIT: SfxNum = 101;
+pub const SFX_SSDTH: SfxNum = 102;
+pub const SFX_KEENPN: SfxNum = 103;
+pub const SFX_KEENDT: SfxNum = 104;
+pub const SFX_SKEACT: SfxNum = 105;
+pub const SFX_SKESIT: SfxNum = 106;
+pub const SFX_SKEATK: SfxNum = 107;
+pub const SFX_RADIO: SfxNum = 108;
+
+pub const NUMSFX: usize = 109;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SfxInfo {
+    // Lump name without the DS/DP prefix
+    pub name: &'static str,
+    // Higher numbers get a channel over lower ones when they're all busy
+    pub priority: i32,
+    // Sound to play instead, with a pitch and volume change. Only the
+    // chaingun uses it, it's the pistol sound
+    pub link: Option<(SfxNum, i32, i32)>,
+}
+
+impl SfxInfo {
+    const fn new(name: &'static str, priority: i32) -> Self {
+        SfxInfo {
+            name,
+            priority,
+            link: None,
+        }
+    }
+
+    pub fn lump_name(&self, prefix: &str) -> String {
+        format!("{}{}", prefix, self.name.to_uppercase())
+    }
+}
+
+#[rustfmt::skip]
+pub static S_SFX: [SfxInfo; NUMSFX] = [
+    SfxInfo::new("", 0),
+    SfxInfo::new("pistol", 64),
+    SfxInfo::new("shotgn", 64),
+    SfxInfo::new("sgcock", 64),
+    SfxInfo::new("dshtgn", 64),
+    SfxInfo::new("dbopn", 64),
+    SfxInfo::new("dbcls", 64),
+    SfxInfo::new("dbload", 64),
+    SfxInfo::new("plasma", 64),
+    SfxInfo::new("bfg", 64),
+    SfxInfo::new("sawup", 64),
+    SfxInfo::new("sawidl", 118),
+    SfxInfo::new("sawful", 64),
+    SfxInfo::new("sawhit", 64),
+    SfxInfo::new("rlaunc", 64),
+    SfxInfo::new("rxplod", 70),
+    SfxInfo::new("firsht", 70),
+    SfxInfo::new("firxpl", 70),
+    SfxInfo::new("pstart", 100),
+    SfxInfo::new("pstop", 100),
+    SfxInfo::new("doropn", 100),
+    SfxInfo::new("dorcls", 100),
+    SfxInfo::new("stnmov", 119),
+    SfxInfo::new("swtchn", 78),
+    SfxInfo::new("swtchx", 78),
+    SfxInfo::new("plpain", 96),
+    SfxInfo::new("dmpain", 96),
+    SfxInfo::new("popain", 96),
+    SfxInfo::new("vipain", 96),
+    SfxInfo::new("mnpain", 96),
+    SfxInfo::new("pepain", 96),
+    SfxInfo::new("slop", 78),
+    SfxInfo::new("itemup", 78),
+    SfxInfo::new("wpnup", 78),
+    SfxInfo::new("oof", 96),
+    SfxInfo::new("telept", 32),
+    SfxInfo::new("posit1", 98),
+    SfxInfo::new("posit2", 98),
+    SfxInfo::new("posit3", 98),
+    SfxInfo::new("bgsit1", 98),
+    SfxInfo::new("bgsit2", 98),
+    SfxInfo::new("sgtsit", 98),
+    SfxInfo::new("cacsit", 98),
+    SfxInfo::new("brssit", 94),
+    SfxInfo::new("cybsit", 92),
+    SfxInfo::new("spisit", 90),
+    SfxInfo::new("bspsit", 90),
+    SfxInfo::new("kntsit", 90),
+    SfxInfo::new("vilsit", 90),
+    SfxInfo::new("mansit", 90),
+    SfxInfo::new("pesit", 90),
+    SfxInfo::new("sklatk", 70),
+    SfxInfo::new("sgtatk", 70),
+    SfxInfo::new("skepch", 70),
+    SfxInfo::new("vilatk", 70),
+    SfxInfo::new("claw", 70),
+    SfxInfo::new("skeswg", 70),
+    SfxInfo::new("pldeth", 32),
+    SfxInfo::new("pdiehi", 32),
+    SfxInfo::new("podth1", 70),
+    SfxInfo::new("podth2", 70),
+    SfxInfo::new("podth3", 70),
+    SfxInfo::new("bgdth1", 70),
+    SfxInfo::new("bgdth2", 70),
+    SfxInfo::new("sgtdth", 70),
+    SfxInfo::new("cacdth", 70),
+    SfxInfo::new("skldth", 70),
+    SfxInfo::new("brsdth", 32),
+    SfxInfo::new("cybdth", 32),
+    SfxInfo::new("spidth", 32),
+    SfxInfo::new("bspdth", 32),
+    SfxInfo::new("vildth", 32),
+    SfxInfo::new("kntdth", 32),
+    SfxInfo::new("pedth", 32),
+    SfxInfo::new("skedth", 32),
+    SfxInfo::new("posact", 120),
+    SfxInfo::new("bgact", 120),
+    SfxInfo::new("dmact", 120),
+    SfxInfo::new("bspact", 100),
+    SfxInfo::new("bspwlk", 100),
+    SfxInfo::new("vilact", 100),
+    SfxInfo::new("noway", 78),
+    SfxInfo::new("barexp", 60),
+    SfxInfo::new("punch", 64),
+    SfxInfo::new("hoof", 70),
+    SfxInfo::new("metal", 70),
+    SfxInfo { name: "chgun", priority: 64, link: Some((SFX_PISTOL, 150, 0)) },
+    SfxInfo::new("tink", 60),
+    SfxInfo::new("bdopn", 100),
+    SfxInfo::new("bdcls", 100),
+    SfxInfo::new("itmbk", 100),
+    SfxInfo::new("flame", 32),
+    SfxInfo::new("flamst", 32),
+    SfxInfo::new("getpow", 60),
+    SfxInfo::new("bospit", 70),
+    SfxInfo::new("boscub", 70),
+    SfxInfo::new("bossit", 70),
+    SfxInfo::new("bospn", 70),
+    SfxInfo::new("bosdth", 70),
+    SfxInfo::new("manatk", 70),
+    SfxInfo::new("mandth", 70),
+    SfxInfo::new("sssit", 70),
+    SfxInfo::new("ssdth", 70),
+    SfxInfo::new("keenpn", 70),
+    SfxInfo::new("keendt", 70),
+    SfxInfo::new("skeact", 70),
+    SfxInfo::new("skesit", 70),
+    SfxInfo::new("skeatk", 70),
+    SfxInfo::new("radio", 60),
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::sound::sfx::{SfxInfo, NUMSFX, SFX_CHGUN, SFX_PISTOL, SFX_RADIO, S_SFX};
+
+    #[test]
+    fn test_s_sfx_matches_original() {
+        assert_eq!(NUMSFX, 109);
+        assert_eq!(S_SFX[SFX_RADIO].name, "radio");
+        assert_eq!(S_SFX[SFX_CHGUN].link, Some((SFX_PISTOL, 150, 0)));
+    }
+
+    #[test]
+    fn test_sfx_info_lump_name_adds_device_prefix() {
+        let pistol: &SfxInfo = &S_SFX[SFX_PISTOL];
+        assert_eq!(pistol.lump_name("DS"), "DSPISTOL");
+        assert_eq!(pistol.lump_name("DP"), "DPPISTOL");
+    }
+}
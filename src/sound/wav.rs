@@ -0,0 +1,225 @@
+use crate::sound::SoundError;
+
+const WAV_HEADER_SIZE: usize = 44;
+const WAV_FORMAT_PCM: u16 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Wav {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub bits_per_sample: u16,
+    pub data: Vec<u8>,
+}
+
+impl Wav {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let block_align: u16 = self.channels * (self.bits_per_sample / 8);
+        let byte_rate: u32 = self.sample_rate * u32::from(block_align);
+        let data_size: u32 = self.data.len() as u32;
+
+        let mut wav: Vec<u8> = Vec::with_capacity(WAV_HEADER_SIZE + self.data.len());
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&WAV_FORMAT_PCM.to_le_bytes());
+        wav.extend_from_slice(&self.channels.to_le_bytes());
+        wav.extend_from_slice(&self.sample_rate.to_le_bytes());
+        wav.extend_from_slice(&byte_rate.to_le_bytes());
+        wav.extend_from_slice(&block_align.to_le_bytes());
+        wav.extend_from_slice(&self.bits_per_sample.to_le_bytes());
+
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_size.to_le_bytes());
+        wav.extend_from_slice(&self.data);
+
+        // Chunks have to be an even size
+        if self.data.len() % 2 == 1 {
+            wav.push(0);
+        }
+
+        wav
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, SoundError> {
+        if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+            return Err(SoundError::InvalidWav(String::from(
+                "Missing RIFF/WAVE header",
+            )));
+        }
+
+        let mut position: usize = 12;
+        let mut format: Option<(u16, u16, u32, u16)> = None;
+        let mut samples: Option<&[u8]> = None;
+
+        while position + 8 <= data.len() {
+            let chunk_id: &[u8] = &data[position..position + 4];
+            let chunk_size: usize =
+                u32::from_le_bytes(data[position + 4..position + 8].try_into().unwrap()) as usize;
+            let chunk_start: usize = position + 8;
+            // Some writers put the wrong size on the data chunk, so don't read past the end
+            let chunk_end: usize = (chunk_start + chunk_size).min(data.len());
+            let chunk: &[u8] = &data[chunk_start..chunk_end];
+
+            match chunk_id {
+                b"fmt " if chunk.len() >= 16 => {
+                    format = Some((
+                        u16::from_le_bytes([chunk[0], chunk[1]]),
+                        u16::from_le_bytes([chunk[2], chunk[3]]),
+                        u32::from_le_bytes(chunk[4..8].try_into().unwrap()),
+                        u16::from_le_bytes([chunk[14], chunk[15]]),
+                    ))
+                }
+                b"data" => samples = Some(chunk),
+                _ => (),
+            }
+
+            // Chunks are padded to an even size
+            position = chunk_start + chunk_size + chunk_size % 2;
+        }
+
+        let (audio_format, channels, sample_rate, bits_per_sample): (u16, u16, u32, u16) =
+            format.ok_or_else(|| SoundError::InvalidWav(String::from("Missing fmt chunk")))?;
+        let samples: &[u8] =
+            samples.ok_or_else(|| SoundError::InvalidWav(String::from("Missing data chunk")))?;
+
+        if audio_format != WAV_FORMAT_PCM {
+            return Err(SoundError::InvalidWav(format!(
+                "Unsupported format {}, only PCM is supported",
+                audio_format
+            )));
+        }
+
+        if channels == 0 || (bits_per_sample != 8 && bits_per_sample != 16) {
+            return Err(SoundError::InvalidWav(format!(
+                "Unsupported {} channel {} bit audio",
+                channels, bits_per_sample
+            )));
+        }
+
+        Ok(Wav {
+            sample_rate,
+            channels,
+            bits_per_sample,
+            data: samples.to_vec(),
+        })
+    }
+
+    // The format DMX sounds use. Channels are mixed down by averaging them
+    pub fn to_unsigned_8bit_mono(&self) -> Vec<u8> {
+        let bytes_per_sample: usize = usize::from(self.bits_per_sample / 8);
+        let frame_size: usize = bytes_per_sample * usize::from(self.channels);
+
+        self.data
+            .chunks_exact(frame_size)
+            .map(|frame| {
+                let sum: i32 = frame
+                    .chunks_exact(bytes_per_sample)
+                    .map(|sample| {
+                        if bytes_per_sample == 1 {
+                            i32::from(sample[0]) - 128
+                        } else {
+                            i32::from(i16::from_le_bytes([sample[0], sample[1]])) >> 8
+                        }
+                    })
+                    .sum();
+
+                (sum / i32::from(self.channels) + 128) as u8
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sound::wav::Wav;
+    use crate::sound::SoundError;
+
+    #[test]
+    fn test_wav_to_bytes_writes_pcm_header() {
+        let wav: Wav = Wav {
+            sample_rate: 11025,
+            channels: 1,
+            bits_per_sample: 8,
+            data: vec![128, 129, 130],
+        };
+
+        let bytes: Vec<u8> = wav.to_bytes();
+
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(&bytes[8..12], b"WAVE");
+        assert_eq!(u16::from_le_bytes([bytes[20], bytes[21]]), 1);
+        assert_eq!(u16::from_le_bytes([bytes[22], bytes[23]]), 1);
+        assert_eq!(u32::from_le_bytes(bytes[24..28].try_into().unwrap()), 11025);
+        assert_eq!(&bytes[36..40], b"data");
+        assert_eq!(u32::from_le_bytes(bytes[40..44].try_into().unwrap()), 3);
+        assert_eq!(&bytes[44..47], &[128, 129, 130]);
+        // Padding byte for odd sized data
+        assert_eq!(bytes.len(), 48);
+    }
+
+    #[test]
+    fn test_wav_from_bytes_round_trip() {
+        let wav: Wav = Wav {
+            sample_rate: 22050,
+            channels: 2,
+            bits_per_sample: 16,
+            data: vec![0, 1, 2, 3, 4, 5, 6, 7],
+        };
+
+        assert_eq!(Wav::from_bytes(&wav.to_bytes()).unwrap(), wav);
+    }
+
+    #[test]
+    fn test_wav_from_bytes_returns_error_for_invalid_data() {
+        assert_eq!(
+            Wav::from_bytes(b"RIFX"),
+            Err(SoundError::InvalidWav(String::from(
+                "Missing RIFF/WAVE header"
+            )))
+        );
+
+        let mut float_wav: Vec<u8> = Wav {
+            sample_rate: 11025,
+            channels: 1,
+            bits_per_sample: 8,
+            data: vec![128],
+        }
+        .to_bytes();
+        float_wav[20] = 3;
+        assert!(Wav::from_bytes(&float_wav).is_err());
+
+        let header_only: Vec<u8> = Wav {
+            sample_rate: 11025,
+            channels: 1,
+            bits_per_sample: 8,
+            data: Vec::new(),
+        }
+        .to_bytes()[0..36]
+            .to_vec();
+        assert_eq!(
+            Wav::from_bytes(&header_only),
+            Err(SoundError::InvalidWav(String::from("Missing data chunk")))
+        );
+    }
+
+    #[test]
+    fn test_wav_to_unsigned_8bit_mono_mixes_channels() {
+        let wav: Wav = Wav {
+            sample_rate: 11025,
+            channels: 2,
+            bits_per_sample: 16,
+            data: [
+                i16::MAX.to_le_bytes(),
+                i16::MAX.to_le_bytes(),
+                0i16.to_le_bytes(),
+                (-256i16).to_le_bytes(),
+            ]
+            .concat(),
+        };
+
+        assert_eq!(wav.to_unsigned_8bit_mono(), vec![255, 128]);
+    }
+}
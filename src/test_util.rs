@@ -0,0 +1,87 @@
+// Helpers shared by the tests that go through the filesystem
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::option::DoomOptions;
+use crate::wad::iwad;
+use crate::wad::{
+    self, DoomFile, Lump, WadHeader, WadID, LUMP_DIRECTORY_ENTRY_SIZE, WAD_HEADER_SIZE,
+};
+
+// An empty directory of its own for the test, the process id keeps test
+// runs from stepping on each other
+pub fn create_test_dir(name: &str) -> PathBuf {
+    let mut dir: PathBuf = std::env::temp_dir();
+    dir.push(format!("zz-doom-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Writes the lumps out as a PWAD for tests that load through the lump
+// directory like the game does
+pub fn write_test_wad(path: &Path, lumps: &[(String, Vec<u8>)]) {
+    let mut wad_data: Vec<u8> = vec![0; WAD_HEADER_SIZE];
+    let mut directory: Vec<u8> = Vec::new();
+
+    for (lump_name, data) in lumps {
+        let lump: Lump = Lump {
+            name: lump_name.clone(),
+            file_path: path.to_owned(),
+            file_position: wad_data.len() as u32,
+            size: data.len() as u32,
+            should_reload: false,
+        };
+        directory.extend_from_slice(&lump.to_directory_entry());
+        wad_data.extend_from_slice(data);
+    }
+
+    let wad_header: WadHeader = WadHeader {
+        id: WadID::Pwad,
+        num_lumps: (directory.len() / LUMP_DIRECTORY_ENTRY_SIZE) as u32,
+        lump_location_offset: wad_data.len() as u32,
+    };
+    wad_data[..WAD_HEADER_SIZE].copy_from_slice(&wad_header.to_bytes());
+    wad_data.extend_from_slice(&directory);
+
+    fs::write(path, wad_data).unwrap();
+}
+
+// The IWAD in DOOMWADDIR for tests that check against the real data. The
+// IWADs can't be shipped with the tests so those tests are skipped when
+// there isn't one
+pub fn find_test_iwad() -> Option<DoomFile> {
+    let wad_files_dir: PathBuf = PathBuf::from(std::env::var_os("DOOMWADDIR")?);
+    let iwad_path: PathBuf =
+        iwad::find_valid_iwad_file_paths(&wad_files_dir, &DoomOptions::new(Vec::new())).pop()?;
+    wad::process_file(&iwad_path).ok()
+}
+
+// Checks a hash against the one stored in tests/resource/golden, or stores
+// it there when ZZ_DOOM_UPDATE_GOLDEN is set
+pub fn check_golden_hash(name: &str, hash: u64) {
+    let mut path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resource/golden");
+    path.push(format!("{}.hash", name));
+
+    if std::env::var_os("ZZ_DOOM_UPDATE_GOLDEN").is_some() {
+        fs::write(&path, format!("{}\n", hash)).unwrap();
+        return;
+    }
+
+    let golden: String = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "{} has no stored hash, run with ZZ_DOOM_UPDATE_GOLDEN set to store it",
+            name
+        )
+    });
+    assert_eq!(hash.to_string(), golden.trim_end(), "{} has changed", name);
+}
+
+// Files from tests/resource
+pub fn read_test_resource(name: &str) -> Vec<u8> {
+    let mut path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/resource");
+    path.push(name);
+    fs::read(path).unwrap()
+}
@@ -0,0 +1,118 @@
+// The i_video.c side of the original. The game draws into a Framebuffer and
+// a video backend gets each finished frame to show or store however it can
+use std::io;
+
+use crate::automap::Automap;
+use crate::game::event::EventQueue;
+use crate::game::{Game, GameState};
+use crate::graphics::{GraphicsError, Palette};
+use crate::render::{Framebuffer, Renderer};
+use crate::wad::{self, DoomFile};
+
+pub mod headless;
+pub mod terminal;
+pub mod y4m;
+
+pub trait VideoBackend {
+    // I_FinishUpdate. The framebuffer is indices into the palette
+    fn finish_update(&mut self, framebuffer: &Framebuffer, palette: &Palette) -> io::Result<()>;
+
+    // I_StartTic. Posts events for the backend's input since the last call
+    fn start_tic(&mut self, _events: &mut EventQueue) {}
+
+    // G_ScreenShot. The next finished frame is saved, if the backend can
+    fn request_screenshot(&mut self) {}
+
+    // The display was closed, or the tty for a terminal
+    fn quit_requested(&self) -> bool {
+        false
+    }
+}
+
+// D_Display. Owns what's needed to draw a frame of the game and hands it
+// to a backend
+pub struct Screen {
+    pub renderer: Renderer,
+    pub automap: Automap,
+    pub framebuffer: Framebuffer,
+    // I_SetPalette, the first PLAYPAL palette until the status bar can
+    // flash the others
+    pub palette: Palette,
+    pub video: Box<dyn VideoBackend>,
+}
+
+impl Screen {
+    pub fn new(
+        doom_files: &mut [DoomFile],
+        scale: usize,
+        video: Box<dyn VideoBackend>,
+    ) -> Result<Self, GraphicsError> {
+        let playpal: Vec<u8> = wad::get_lump_data(doom_files, "PLAYPAL");
+        Ok(Screen {
+            renderer: Renderer::new(doom_files, scale),
+            automap: Automap::new(doom_files, scale),
+            framebuffer: Framebuffer::new(scale),
+            palette: Palette::from_playpal(&playpal, 0)?,
+            video,
+        })
+    }
+
+    // Only the level has anything to draw yet, the finale shows the
+    // last frame of it. The automap replaces the player's view
+    pub fn draw(&mut self, game: &Game) -> io::Result<()> {
+        if game.game_state == GameState::Level {
+            if self.automap.active {
+                self.automap.drawer(&game.world, &mut self.framebuffer);
+            } else {
+                self.renderer
+                    .render_player_view(&game.world, &mut self.framebuffer);
+            }
+        }
+        self.video.finish_update(&self.framebuffer, &self.palette)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io;
+    use std::rc::Rc;
+
+    use crate::game::Game;
+    use crate::graphics::Palette;
+    use crate::render::test_wad::{test_game, START};
+    use crate::render::Framebuffer;
+    use crate::video::{Screen, VideoBackend};
+
+    struct RecordingVideo {
+        frames: Rc<RefCell<Vec<Vec<u8>>>>,
+    }
+
+    impl VideoBackend for RecordingVideo {
+        fn finish_update(
+            &mut self,
+            framebuffer: &Framebuffer,
+            _palette: &Palette,
+        ) -> io::Result<()> {
+            self.frames.borrow_mut().push(framebuffer.pixels.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_screen_draw_renders_and_finishes_the_frame() {
+        let mut game: Game = test_game(START);
+        let frames: Rc<RefCell<Vec<Vec<u8>>>> = Rc::new(RefCell::new(Vec::new()));
+        let video: RecordingVideo = RecordingVideo {
+            frames: Rc::clone(&frames),
+        };
+        let mut screen: Screen = Screen::new(&mut game.doom_files, 1, Box::new(video)).unwrap();
+        game.tick(&[Default::default()]);
+
+        screen.draw(&game).unwrap();
+
+        assert_eq!(frames.borrow().len(), 1);
+        assert_eq!(frames.borrow()[0], screen.framebuffer.pixels);
+        assert!(screen.framebuffer.pixels.iter().any(|&pixel| pixel != 0));
+    }
+}
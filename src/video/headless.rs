@@ -0,0 +1,247 @@
+// A video backend for machines without a display. Frames only go to disk:
+// screenshots when asked for, like M_ScreenShot, and optionally every frame
+// with -framedump
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::graphics::Palette;
+use crate::image::pcx::PcxImage;
+use crate::render::Framebuffer;
+use crate::video::y4m::Y4mWriter;
+use crate::video::VideoBackend;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    Pcx,
+    Png,
+}
+
+impl ScreenshotFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ScreenshotFormat::Pcx => "PCX",
+            ScreenshotFormat::Png => "PNG",
+        }
+    }
+}
+
+pub enum FrameDump {
+    // Numbered PNG files in a directory
+    Png { dir: PathBuf, frame: usize },
+    Y4m(Y4mWriter<Box<dyn Write>>),
+}
+
+impl FrameDump {
+    // -framedump. A .y4m path, which can be a named pipe an encoder is
+    // reading, gets a Y4M stream and anything else is a directory for PNGs.
+    // Not stdout since the startup messages go there
+    pub fn from_path(path: &str) -> io::Result<Self> {
+        if Path::new(path)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("y4m"))
+        {
+            let file: Box<dyn Write> = Box::new(BufWriter::new(File::create(path)?));
+            Ok(FrameDump::Y4m(Y4mWriter::new(file)))
+        } else {
+            fs::create_dir_all(path)?;
+            Ok(FrameDump::Png {
+                dir: PathBuf::from(path),
+                frame: 0,
+            })
+        }
+    }
+
+    fn write_frame(&mut self, framebuffer: &Framebuffer, palette: &Palette) -> io::Result<()> {
+        match self {
+            FrameDump::Png { dir, frame } => {
+                let path: PathBuf = dir.join(format!("frame{:06}.png", frame));
+                *frame += 1;
+                fs::write(path, framebuffer.to_png(palette).encode())
+            }
+            FrameDump::Y4m(writer) => writer.write_frame(framebuffer, palette),
+        }
+    }
+}
+
+pub struct HeadlessVideo {
+    pub screenshot_dir: PathBuf,
+    pub screenshot_format: ScreenshotFormat,
+    frame_dump: Option<FrameDump>,
+    // Set by G_ScreenShot, the next finished frame is saved
+    screenshot_requested: bool,
+    // Where the last screenshot went, so the player can be told
+    pub last_screenshot: Option<PathBuf>,
+}
+
+impl HeadlessVideo {
+    pub fn new(screenshot_dir: PathBuf, screenshot_format: ScreenshotFormat) -> Self {
+        HeadlessVideo {
+            screenshot_dir,
+            screenshot_format,
+            frame_dump: None,
+            screenshot_requested: false,
+            last_screenshot: None,
+        }
+    }
+
+    pub fn with_frame_dump(mut self, frame_dump: FrameDump) -> Self {
+        self.frame_dump = Some(frame_dump);
+        self
+    }
+
+    // M_ScreenShot. Saves to the first free DOOM00 to DOOM99 name
+    pub fn take_screenshot(
+        &mut self,
+        framebuffer: &Framebuffer,
+        palette: &Palette,
+    ) -> io::Result<PathBuf> {
+        let path: PathBuf = (0..=99)
+            .map(|index| {
+                self.screenshot_dir.join(format!(
+                    "DOOM{:02}.{}",
+                    index,
+                    self.screenshot_format.extension()
+                ))
+            })
+            .find(|path| !path.exists())
+            .unwrap_or_else(|| panic!("M_ScreenShot: Couldn't create a PCX"));
+
+        let data: Vec<u8> = match self.screenshot_format {
+            ScreenshotFormat::Pcx => PcxImage {
+                width: framebuffer.width as u32,
+                height: framebuffer.height as u32,
+                palette: palette.colors.clone(),
+                indices: framebuffer.pixels.clone(),
+            }
+            .encode(),
+            ScreenshotFormat::Png => framebuffer.to_png(palette).encode(),
+        };
+        fs::write(&path, data)?;
+
+        self.last_screenshot = Some(path.clone());
+        Ok(path)
+    }
+}
+
+impl VideoBackend for HeadlessVideo {
+    fn finish_update(&mut self, framebuffer: &Framebuffer, palette: &Palette) -> io::Result<()> {
+        if let Some(frame_dump) = self.frame_dump.as_mut() {
+            frame_dump.write_frame(framebuffer, palette)?;
+        }
+
+        if self.screenshot_requested {
+            self.screenshot_requested = false;
+            self.take_screenshot(framebuffer, palette)?;
+        }
+
+        Ok(())
+    }
+
+    fn request_screenshot(&mut self) {
+        self.screenshot_requested = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::graphics::Palette;
+    use crate::image::pcx::PcxImage;
+    use crate::image::png::PngImage;
+    use crate::render::Framebuffer;
+    use crate::test_util::create_test_dir;
+    use crate::video::headless::{FrameDump, HeadlessVideo, ScreenshotFormat};
+    use crate::video::VideoBackend;
+
+    fn test_frame() -> (Framebuffer, Palette) {
+        let mut framebuffer: Framebuffer = Framebuffer::new(1);
+        for (index, pixel) in framebuffer.pixels.iter_mut().enumerate() {
+            *pixel = index as u8;
+        }
+        let playpal: Vec<u8> = (0..=255u8)
+            .flat_map(|index| [index, 0, 255 - index])
+            .collect();
+        (framebuffer, Palette::from_playpal(&playpal, 0).unwrap())
+    }
+
+    #[test]
+    fn test_headless_video_take_screenshot_uses_first_free_name() {
+        let dir: PathBuf = create_test_dir("headless-screenshot");
+        fs::write(dir.join("DOOM00.PCX"), []).unwrap();
+        let (framebuffer, palette) = test_frame();
+        let mut video: HeadlessVideo = HeadlessVideo::new(dir.clone(), ScreenshotFormat::Pcx);
+
+        let path: PathBuf = video.take_screenshot(&framebuffer, &palette).unwrap();
+
+        assert_eq!(path, dir.join("DOOM01.PCX"));
+        let image: PcxImage = PcxImage::decode(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!((image.width, image.height), (320, 200));
+        assert_eq!(image.indices, framebuffer.pixels);
+        assert_eq!(image.palette, palette.colors);
+    }
+
+    #[test]
+    #[should_panic(expected = "M_ScreenShot: Couldn't create a PCX")]
+    fn test_headless_video_take_screenshot_panics_when_names_run_out() {
+        let dir: PathBuf = create_test_dir("headless-screenshot-full");
+        for index in 0..=99 {
+            fs::write(dir.join(format!("DOOM{:02}.PNG", index)), []).unwrap();
+        }
+        let (framebuffer, palette) = test_frame();
+        let mut video: HeadlessVideo = HeadlessVideo::new(dir, ScreenshotFormat::Png);
+
+        let _ = video.take_screenshot(&framebuffer, &palette);
+    }
+
+    #[test]
+    fn test_headless_video_finish_update_takes_requested_screenshot_once() {
+        let dir: PathBuf = create_test_dir("headless-requested");
+        let (framebuffer, palette) = test_frame();
+        let mut video: HeadlessVideo = HeadlessVideo::new(dir.clone(), ScreenshotFormat::Png);
+
+        video.finish_update(&framebuffer, &palette).unwrap();
+        assert_eq!(video.last_screenshot, None);
+
+        video.request_screenshot();
+        video.finish_update(&framebuffer, &palette).unwrap();
+        video.finish_update(&framebuffer, &palette).unwrap();
+
+        assert_eq!(video.last_screenshot, Some(dir.join("DOOM00.PNG")));
+        assert!(!dir.join("DOOM01.PNG").exists());
+        let image: PngImage = PngImage::decode(&fs::read(dir.join("DOOM00.PNG")).unwrap()).unwrap();
+        assert_eq!(image, framebuffer.to_png(&palette));
+    }
+
+    #[test]
+    fn test_headless_video_frame_dump_writes_numbered_pngs() {
+        let dir: PathBuf = create_test_dir("headless-framedump").join("frames");
+        let (framebuffer, palette) = test_frame();
+        let frame_dump: FrameDump = FrameDump::from_path(dir.to_str().unwrap()).unwrap();
+        let mut video: HeadlessVideo =
+            HeadlessVideo::new(dir.clone(), ScreenshotFormat::Pcx).with_frame_dump(frame_dump);
+
+        video.finish_update(&framebuffer, &palette).unwrap();
+        video.finish_update(&framebuffer, &palette).unwrap();
+
+        assert!(dir.join("frame000000.png").exists());
+        assert!(dir.join("frame000001.png").exists());
+        assert!(!dir.join("frame000002.png").exists());
+    }
+
+    #[test]
+    fn test_frame_dump_from_path_streams_y4m_files() {
+        let path: PathBuf = create_test_dir("headless-y4m").join("demo.Y4M");
+        let (framebuffer, palette) = test_frame();
+        let mut frame_dump: FrameDump = FrameDump::from_path(path.to_str().unwrap()).unwrap();
+        assert!(matches!(frame_dump, FrameDump::Y4m(_)));
+
+        frame_dump.write_frame(&framebuffer, &palette).unwrap();
+        drop(frame_dump);
+
+        let data: Vec<u8> = fs::read(&path).unwrap();
+        assert!(data.starts_with(b"YUV4MPEG2 W320 H200"));
+    }
+}
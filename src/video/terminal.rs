@@ -0,0 +1,496 @@
+// A video backend for terminals, for playing over SSH. Every character cell
+// shows two pixels with an upper half block, the top one in the 24 bit
+// foreground colour and the bottom one in the background colour. Keys are
+// read from the tty in raw mode, which needs stty like any Unix terminal
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::KeyBindings;
+use crate::game::event::{
+    Event, EventQueue, KEY_BACKSPACE, KEY_DOWNARROW, KEY_ENTER, KEY_ESCAPE, KEY_F1, KEY_F10,
+    KEY_F11, KEY_F12, KEY_F2, KEY_F3, KEY_F4, KEY_F5, KEY_F6, KEY_F7, KEY_F8, KEY_F9,
+    KEY_LEFTARROW, KEY_RALT, KEY_RCTRL, KEY_RIGHTARROW, KEY_RSHIFT, KEY_TAB, KEY_UPARROW,
+};
+use crate::graphics::Palette;
+use crate::render::Framebuffer;
+use crate::video::headless::HeadlessVideo;
+use crate::video::VideoBackend;
+
+const TTY_PATH: &str = "/dev/tty";
+// Ctrl-C doesn't interrupt in raw mode so it's the way out
+const TTY_QUIT: u8 = 0x03;
+const ESC: u8 = 0x1b;
+
+// Terminals only send key presses and repeat them while the key is held, so
+// a key is let go once it stops repeating. The first repeat takes longer
+const KEY_FIRST_REPEAT: Duration = Duration::from_millis(600);
+const KEY_REPEAT: Duration = Duration::from_millis(150);
+
+// How many frames go by before the terminal size is checked again
+const RESIZE_CHECK_FRAMES: usize = 35;
+
+// Ctrl, Alt and Shift on their own never reach a terminal program, so fire
+// moves to Z when it's left on Ctrl. Strafe and run still work on Alt and
+// Shift with the arrow keys, which send them along
+pub fn terminal_key_bindings(mut key_bindings: KeyBindings) -> KeyBindings {
+    if key_bindings.key_fire == KEY_RCTRL {
+        key_bindings.key_fire = i32::from(b'z');
+    }
+    key_bindings
+}
+
+// Turns what a terminal sends for key presses into key codes. Escape
+// sequences for the arrow and function keys have to arrive in one read
+pub fn decode_keys(bytes: &[u8]) -> Vec<i32> {
+    let mut keys: Vec<i32> = Vec::new();
+    let mut index: usize = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            ESC if matches!(bytes.get(index + 1), Some(b'[' | b'O')) => {
+                let sequence_start: usize = index + 2;
+                // Parameters run up to the final byte
+                let sequence_end: usize = bytes[sequence_start..]
+                    .iter()
+                    .position(|byte| (0x40..=0x7e).contains(byte))
+                    .map_or(bytes.len(), |offset| sequence_start + offset + 1);
+                let sequence: &[u8] = &bytes[sequence_start..sequence_end];
+                if let Some(key) = escape_sequence_key(sequence) {
+                    keys.extend(modifier_keys(sequence));
+                    keys.push(key);
+                }
+                index = sequence_end;
+                continue;
+            }
+            ESC => keys.push(KEY_ESCAPE),
+            b'\r' | b'\n' => keys.push(KEY_ENTER),
+            b'\t' => keys.push(KEY_TAB),
+            0x08 | 0x7f => keys.push(KEY_BACKSPACE),
+            byte @ 0x20..=0x7e => keys.push(i32::from(byte.to_ascii_lowercase())),
+            _ => {}
+        }
+        index += 1;
+    }
+
+    keys
+}
+
+// xterm's second parameter is 1 plus a bit each for Shift, Alt and Ctrl.
+// They're pressed along with the key so they repeat with it
+fn modifier_keys(sequence: &[u8]) -> Vec<i32> {
+    let modifiers: u8 = sequence[..sequence.len().saturating_sub(1)]
+        .split(|&byte| byte == b';')
+        .nth(1)
+        .and_then(|parameter| std::str::from_utf8(parameter).ok()?.parse::<u8>().ok())
+        .map_or(0, |parameter| parameter.saturating_sub(1));
+
+    [(1, KEY_RSHIFT), (2, KEY_RALT), (4, KEY_RCTRL)]
+        .into_iter()
+        .filter(|(bit, _)| modifiers & bit != 0)
+        .map(|(_, key)| key)
+        .collect()
+}
+
+// The xterm sequences after ESC [ or ESC O
+fn escape_sequence_key(sequence: &[u8]) -> Option<i32> {
+    match sequence.last()? {
+        b'A' => Some(KEY_UPARROW),
+        b'B' => Some(KEY_DOWNARROW),
+        b'C' => Some(KEY_RIGHTARROW),
+        b'D' => Some(KEY_LEFTARROW),
+        b'P' => Some(KEY_F1),
+        b'Q' => Some(KEY_F2),
+        b'R' => Some(KEY_F3),
+        b'S' => Some(KEY_F4),
+        b'~' => {
+            let code: &[u8] = sequence[..sequence.len() - 1]
+                .split(|&byte| byte == b';')
+                .next()?;
+            match code {
+                b"11" => Some(KEY_F1),
+                b"12" => Some(KEY_F2),
+                b"13" => Some(KEY_F3),
+                b"14" => Some(KEY_F4),
+                b"15" => Some(KEY_F5),
+                b"17" => Some(KEY_F6),
+                b"18" => Some(KEY_F7),
+                b"19" => Some(KEY_F8),
+                b"20" => Some(KEY_F9),
+                b"21" => Some(KEY_F10),
+                b"23" => Some(KEY_F11),
+                b"24" => Some(KEY_F12),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+struct HeldKey {
+    key: i32,
+    release_time: Instant,
+}
+
+// Makes key down and key up events out of key presses
+#[derive(Default)]
+pub struct HeldKeys {
+    keys: Vec<HeldKey>,
+}
+
+impl HeldKeys {
+    pub fn press(&mut self, key: i32, now: Instant, events: &mut Vec<Event>) {
+        match self.keys.iter_mut().find(|held_key| held_key.key == key) {
+            Some(held_key) => held_key.release_time = now + KEY_REPEAT,
+            None => {
+                events.push(Event::KeyDown(key));
+                self.keys.push(HeldKey {
+                    key,
+                    release_time: now + KEY_FIRST_REPEAT,
+                });
+            }
+        }
+    }
+
+    pub fn release_stale(&mut self, now: Instant, events: &mut Vec<Event>) {
+        self.keys.retain(|held_key| {
+            let held: bool = held_key.release_time > now;
+            if !held {
+                events.push(Event::KeyUp(held_key.key));
+            }
+            held
+        });
+    }
+}
+
+// Columns and pixel rows that fit the terminal while keeping the 4:3 the
+// original was shown at. A cell is about twice as tall as it is wide so
+// half blocks are close to square
+pub fn fit_to_terminal(columns: usize, rows: usize) -> (usize, usize) {
+    let width: usize = columns.min(rows * 2 * 4 / 3).max(1);
+    let height: usize = (width * 3 / 4).max(2);
+    (width, height)
+}
+
+// Escape codes drawing the framebuffer scaled to width by height pixels
+// from the top left of the terminal. Colours are only sent when they change
+pub fn render_half_blocks(
+    framebuffer: &Framebuffer,
+    palette: &Palette,
+    width: usize,
+    height: usize,
+) -> String {
+    let mut output: String = String::new();
+    let mut colors: Option<(u8, u8)> = None;
+
+    for row in 0..height.div_ceil(2) {
+        let _ = write!(output, "\x1b[{};1H", row + 1);
+        let top_y: usize = row * 2 * framebuffer.height / height;
+        let bottom_y: usize =
+            ((row * 2 + 1) * framebuffer.height / height).min(framebuffer.height - 1);
+
+        for column in 0..width {
+            let x: usize = column * framebuffer.width / width;
+            let top: u8 = framebuffer.pixels[top_y * framebuffer.width + x];
+            let bottom: u8 = framebuffer.pixels[bottom_y * framebuffer.width + x];
+
+            if colors.is_none_or(|(last_top, _)| last_top != top) {
+                let [r, g, b]: [u8; 3] = palette.color(top);
+                let _ = write!(output, "\x1b[38;2;{};{};{}m", r, g, b);
+            }
+            if colors.is_none_or(|(_, last_bottom)| last_bottom != bottom) {
+                let [r, g, b]: [u8; 3] = palette.color(bottom);
+                let _ = write!(output, "\x1b[48;2;{};{};{}m", r, g, b);
+            }
+            colors = Some((top, bottom));
+            output.push('\u{2580}');
+        }
+    }
+    output.push_str("\x1b[0m");
+
+    output
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(File::open(TTY_PATH)?)
+        .stderr(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("stty {} failed", args.join(" "))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Rows and columns, 80 by 24 when stty can't tell
+fn terminal_size() -> (usize, usize) {
+    stty(&["size"])
+        .ok()
+        .and_then(|size| {
+            let mut fields = size.split_whitespace().map(str::parse::<usize>);
+            match (fields.next(), fields.next()) {
+                (Some(Ok(rows)), Some(Ok(columns))) if rows > 0 && columns > 0 => {
+                    Some((rows, columns))
+                }
+                _ => None,
+            }
+        })
+        .unwrap_or((24, 80))
+}
+
+pub struct TerminalVideo {
+    // stty settings to put back when done
+    saved_settings: String,
+    // Frames are saved with the headless backend when asked
+    screenshots: HeadlessVideo,
+    input: Receiver<Vec<u8>>,
+    held_keys: HeldKeys,
+    quit: bool,
+    // Rows and columns
+    size: (usize, usize),
+    frames_until_resize_check: usize,
+}
+
+impl TerminalVideo {
+    // I_InitGraphics. Puts the tty in raw mode and switches to the
+    // alternate screen until dropped
+    pub fn new(screenshots: HeadlessVideo) -> io::Result<Self> {
+        let saved_settings: String = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+
+        let mut tty: File = File::open(TTY_PATH)?;
+        let (sender, input) = mpsc::channel::<Vec<u8>>();
+        thread::spawn(move || {
+            let mut buffer: [u8; 64] = [0; 64];
+            while let Ok(count) = tty.read(&mut buffer) {
+                if count == 0 || sender.send(buffer[..count].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(b"\x1b[?1049h\x1b[?25l\x1b[2J")?;
+        stdout.flush()?;
+
+        Ok(TerminalVideo {
+            saved_settings,
+            screenshots,
+            input,
+            held_keys: Default::default(),
+            quit: false,
+            size: terminal_size(),
+            frames_until_resize_check: RESIZE_CHECK_FRAMES,
+        })
+    }
+}
+
+impl VideoBackend for TerminalVideo {
+    fn finish_update(&mut self, framebuffer: &Framebuffer, palette: &Palette) -> io::Result<()> {
+        self.frames_until_resize_check -= 1;
+        if self.frames_until_resize_check == 0 {
+            self.frames_until_resize_check = RESIZE_CHECK_FRAMES;
+            let size: (usize, usize) = terminal_size();
+            if size != self.size {
+                self.size = size;
+                io::stdout().write_all(b"\x1b[2J")?;
+            }
+        }
+
+        let (rows, columns) = self.size;
+        let (width, height) = fit_to_terminal(columns, rows);
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(render_half_blocks(framebuffer, palette, width, height).as_bytes())?;
+        stdout.flush()?;
+
+        self.screenshots.finish_update(framebuffer, palette)
+    }
+
+    fn start_tic(&mut self, event_queue: &mut EventQueue) {
+        let mut events: Vec<Event> = Vec::new();
+        let now: Instant = Instant::now();
+
+        loop {
+            match self.input.try_recv() {
+                Ok(bytes) => {
+                    if bytes.contains(&TTY_QUIT) {
+                        self.quit = true;
+                    }
+                    for key in decode_keys(&bytes) {
+                        self.held_keys.press(key, now, &mut events);
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                // The tty went away
+                Err(TryRecvError::Disconnected) => {
+                    self.quit = true;
+                    break;
+                }
+            }
+        }
+        self.held_keys.release_stale(now, &mut events);
+
+        for event in events {
+            event_queue.post(event);
+        }
+    }
+
+    fn request_screenshot(&mut self) {
+        self.screenshots.request_screenshot();
+    }
+
+    fn quit_requested(&self) -> bool {
+        self.quit
+    }
+}
+
+impl Drop for TerminalVideo {
+    // I_ShutdownGraphics
+    fn drop(&mut self) {
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_all(b"\x1b[0m\x1b[?25h\x1b[?1049l");
+        let _ = stdout.flush();
+        let _ = stty(&[self.saved_settings.as_str()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::config::KeyBindings;
+    use crate::game::event::{
+        Event, KEY_BACKSPACE, KEY_DOWNARROW, KEY_ENTER, KEY_ESCAPE, KEY_F1, KEY_F12, KEY_F5,
+        KEY_LEFTARROW, KEY_RALT, KEY_RCTRL, KEY_RIGHTARROW, KEY_RSHIFT, KEY_UPARROW,
+    };
+    use crate::graphics::Palette;
+    use crate::render::Framebuffer;
+    use crate::video::terminal::{
+        decode_keys, fit_to_terminal, render_half_blocks, terminal_key_bindings, HeldKeys,
+    };
+
+    #[test]
+    fn test_decode_keys_maps_characters_to_key_codes() {
+        assert_eq!(
+            decode_keys(b"wA \r\x7f"),
+            vec![
+                i32::from(b'w'),
+                i32::from(b'a'),
+                i32::from(b' '),
+                KEY_ENTER,
+                KEY_BACKSPACE
+            ]
+        );
+        // Other control characters are dropped
+        assert_eq!(decode_keys(b"\x01\x03"), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_decode_keys_reads_escape_sequences() {
+        assert_eq!(
+            decode_keys(b"\x1b[A\x1b[B\x1bOC\x1b[D"),
+            vec![KEY_UPARROW, KEY_DOWNARROW, KEY_RIGHTARROW, KEY_LEFTARROW]
+        );
+        assert_eq!(
+            decode_keys(b"\x1bOP\x1b[15~\x1b[24~\x1b[99~"),
+            vec![KEY_F1, KEY_F5, KEY_F12]
+        );
+        // Escape on its own, or followed by something that isn't a sequence
+        assert_eq!(decode_keys(b"\x1b"), vec![KEY_ESCAPE]);
+        assert_eq!(decode_keys(b"\x1bx"), vec![KEY_ESCAPE, i32::from(b'x')]);
+    }
+
+    #[test]
+    fn test_decode_keys_presses_modifiers_with_the_key() {
+        // Shift runs, Alt strafes and Ctrl fires with the default bindings
+        assert_eq!(decode_keys(b"\x1b[1;2A"), vec![KEY_RSHIFT, KEY_UPARROW]);
+        assert_eq!(decode_keys(b"\x1b[1;3D"), vec![KEY_RALT, KEY_LEFTARROW]);
+        assert_eq!(decode_keys(b"\x1b[1;5C"), vec![KEY_RCTRL, KEY_RIGHTARROW]);
+        assert_eq!(
+            decode_keys(b"\x1b[1;8B\x1b[24;2~"),
+            vec![
+                KEY_RSHIFT,
+                KEY_RALT,
+                KEY_RCTRL,
+                KEY_DOWNARROW,
+                KEY_RSHIFT,
+                KEY_F12
+            ]
+        );
+        assert_eq!(decode_keys(b"\x1b[1;A"), vec![KEY_UPARROW]);
+    }
+
+    #[test]
+    fn test_terminal_key_bindings_moves_fire_off_ctrl() {
+        let key_bindings: KeyBindings = terminal_key_bindings(KeyBindings::default());
+        assert_eq!(key_bindings.key_fire, i32::from(b'z'));
+        assert_eq!(key_bindings.key_strafe, KEY_RALT);
+        assert_eq!(key_bindings.key_speed, KEY_RSHIFT);
+
+        // A fire key that was picked on purpose stays
+        let custom: KeyBindings = KeyBindings {
+            key_fire: i32::from(b'f'),
+            ..Default::default()
+        };
+        assert_eq!(terminal_key_bindings(custom), custom);
+    }
+
+    #[test]
+    fn test_held_keys_release_keys_that_stop_repeating() {
+        let start: Instant = Instant::now();
+        let mut held_keys: HeldKeys = Default::default();
+        let mut events: Vec<Event> = Vec::new();
+
+        held_keys.press(KEY_UPARROW, start, &mut events);
+        held_keys.release_stale(start + Duration::from_millis(500), &mut events);
+        assert_eq!(events, vec![Event::KeyDown(KEY_UPARROW)]);
+
+        // Repeats keep it held without another key down
+        held_keys.press(KEY_UPARROW, start + Duration::from_millis(550), &mut events);
+        held_keys.release_stale(start + Duration::from_millis(650), &mut events);
+        assert_eq!(events, vec![Event::KeyDown(KEY_UPARROW)]);
+
+        held_keys.release_stale(start + Duration::from_millis(700), &mut events);
+        assert_eq!(
+            events,
+            vec![Event::KeyDown(KEY_UPARROW), Event::KeyUp(KEY_UPARROW)]
+        );
+    }
+
+    #[test]
+    fn test_fit_to_terminal_keeps_aspect_ratio() {
+        assert_eq!(fit_to_terminal(80, 24), (64, 48));
+        assert_eq!(fit_to_terminal(320, 200), (320, 240));
+        assert_eq!(fit_to_terminal(40, 100), (40, 30));
+    }
+
+    #[test]
+    fn test_render_half_blocks_sends_colours_when_they_change() {
+        let playpal: Vec<u8> = (0..=255u8).flat_map(|index| [index, 0, 0]).collect();
+        let palette: Palette = Palette::from_playpal(&playpal, 0).unwrap();
+        let mut framebuffer: Framebuffer = Framebuffer::new(1);
+        // Top half 1 and bottom half 2, apart from the middle of the top row
+        for (index, pixel) in framebuffer.pixels.iter_mut().enumerate() {
+            *pixel = if index < 320 * 100 { 1 } else { 2 };
+        }
+        framebuffer.pixels[160] = 3;
+
+        let output: String = render_half_blocks(&framebuffer, &palette, 2, 2);
+
+        assert_eq!(
+            output,
+            "\x1b[1;1H\x1b[38;2;1;0;0m\x1b[48;2;2;0;0m\u{2580}\x1b[38;2;3;0;0m\u{2580}\x1b[0m"
+        );
+        assert_eq!(
+            render_half_blocks(&framebuffer, &palette, 4, 4)
+                .matches('\u{2580}')
+                .count(),
+            8
+        );
+    }
+}
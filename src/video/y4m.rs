@@ -0,0 +1,134 @@
+// A raw YUV4MPEG2 stream of frames that ffmpeg and most encoders read
+// straight from a pipe. Full resolution 4:4:4 so no colour is lost to
+// subsampling
+use std::io::{self, Write};
+
+use crate::graphics::Palette;
+use crate::play::TICRATE;
+use crate::render::Framebuffer;
+
+pub struct Y4mWriter<W: Write> {
+    output: W,
+    // Set by the first frame, every frame after has to match
+    frame_size: Option<(usize, usize)>,
+}
+
+impl<W: Write> Y4mWriter<W> {
+    pub fn new(output: W) -> Self {
+        Y4mWriter {
+            output,
+            frame_size: None,
+        }
+    }
+
+    pub fn write_frame(&mut self, framebuffer: &Framebuffer, palette: &Palette) -> io::Result<()> {
+        match self.frame_size {
+            None => {
+                writeln!(
+                    self.output,
+                    "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C444",
+                    framebuffer.width, framebuffer.height, TICRATE
+                )?;
+                self.frame_size = Some((framebuffer.width, framebuffer.height));
+            }
+            Some(frame_size) if frame_size != (framebuffer.width, framebuffer.height) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Y4M frames can't change size",
+                ));
+            }
+            Some(_) => {}
+        }
+
+        let colors: Vec<[u8; 3]> = palette
+            .colors
+            .iter()
+            .map(|&rgb| rgb_to_ycbcr(rgb))
+            .collect();
+        let mut frame: Vec<u8> = Vec::with_capacity(6 + framebuffer.pixels.len() * 3);
+        frame.extend_from_slice(b"FRAME\n");
+        for plane in 0..3 {
+            frame.extend(
+                framebuffer
+                    .pixels
+                    .iter()
+                    .map(|&pixel| colors.get(pixel as usize).map_or(0, |color| color[plane])),
+            );
+        }
+        self.output.write_all(&frame)?;
+        self.output.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+}
+
+// BT.601 studio range, what Y4M readers assume without a colour range tag
+pub fn rgb_to_ycbcr(rgb: [u8; 3]) -> [u8; 3] {
+    let [r, g, b]: [i32; 3] = rgb.map(i32::from);
+    [
+        (((66 * r + 129 * g + 25 * b + 128) >> 8) + 16) as u8,
+        (((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128) as u8,
+        (((112 * r - 94 * g - 18 * b + 128) >> 8) + 128) as u8,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use crate::graphics::Palette;
+    use crate::render::Framebuffer;
+    use crate::video::y4m::{rgb_to_ycbcr, Y4mWriter};
+
+    fn test_palette() -> Palette {
+        let playpal: Vec<u8> = (0..=255u8)
+            .flat_map(|index| [index, index, index])
+            .collect();
+        Palette::from_playpal(&playpal, 0).unwrap()
+    }
+
+    #[test]
+    fn test_rgb_to_ycbcr_uses_studio_range() {
+        assert_eq!(rgb_to_ycbcr([0, 0, 0]), [16, 128, 128]);
+        assert_eq!(rgb_to_ycbcr([255, 255, 255]), [235, 128, 128]);
+        assert_eq!(rgb_to_ycbcr([255, 0, 0]), [82, 90, 240]);
+    }
+
+    #[test]
+    fn test_y4m_writer_writes_header_once_and_planar_frames() {
+        let mut framebuffer: Framebuffer = Framebuffer::new(1);
+        framebuffer.pixels[0] = 255;
+        let mut writer: Y4mWriter<Vec<u8>> = Y4mWriter::new(Vec::new());
+
+        writer.write_frame(&framebuffer, &test_palette()).unwrap();
+        writer.write_frame(&framebuffer, &test_palette()).unwrap();
+
+        let output: Vec<u8> = writer.into_inner();
+        let header: &[u8] = b"YUV4MPEG2 W320 H200 F35:1 Ip A1:1 C444\n";
+        let frame_size: usize = 6 + 320 * 200 * 3;
+        assert!(output.starts_with(header));
+        assert_eq!(output.len(), header.len() + frame_size * 2);
+
+        let frame: &[u8] = &output[header.len()..header.len() + frame_size];
+        assert!(frame.starts_with(b"FRAME\n"));
+        // Y, Cb and Cr planes of the first pixel then the second
+        assert_eq!(frame[6], 235);
+        assert_eq!(frame[7], 16);
+        assert_eq!(frame[6 + 320 * 200], 128);
+        assert_eq!(frame[6 + 320 * 200 * 2], 128);
+    }
+
+    #[test]
+    fn test_y4m_writer_rejects_frames_that_change_size() {
+        let mut writer: Y4mWriter<io::Sink> = Y4mWriter::new(io::sink());
+        writer
+            .write_frame(&Framebuffer::new(1), &test_palette())
+            .unwrap();
+
+        let result: io::Result<()> = writer.write_frame(&Framebuffer::new(2), &test_palette());
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
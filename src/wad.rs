@@ -7,8 +7,15 @@ use std::{
     path::{Path, PathBuf},
 };
 
+pub mod build;
+pub mod extract;
+pub mod format;
 pub mod iwad;
+pub mod manifest;
+pub mod namespace;
 
+pub const WAD_HEADER_SIZE: usize = 12;
+pub const LUMP_DIRECTORY_ENTRY_SIZE: usize = 16;
 const LUMP_FILE_MAX_NAME_LENGTH: usize = 8;
 const RELOAD_FILE_PREFIX: &str = "~";
 
@@ -78,11 +85,46 @@ pub struct Lump {
     pub should_reload: bool,
 }
 
+impl Lump {
+    pub fn from_directory_entry(
+        entry: &[u8; LUMP_DIRECTORY_ENTRY_SIZE],
+        file_path: &Path,
+        should_reload: bool,
+    ) -> Self {
+        let file_position: [u8; 4] = entry[0..=3].try_into().unwrap();
+        let size: [u8; 4] = entry[4..=7].try_into().unwrap();
+        let name: [u8; 8] = entry[8..=15].try_into().unwrap();
+
+        Lump {
+            name: String::from_utf8_lossy(&name).to_string(),
+            file_path: file_path.to_owned(),
+            file_position: u32::from_le_bytes(file_position),
+            size: u32::from_le_bytes(size),
+            should_reload,
+        }
+    }
+
+    // Names shorter than 8 characters are padded out with nulls
+    pub fn to_directory_entry(&self) -> [u8; LUMP_DIRECTORY_ENTRY_SIZE] {
+        let mut entry: [u8; LUMP_DIRECTORY_ENTRY_SIZE] = [0; LUMP_DIRECTORY_ENTRY_SIZE];
+        let name: &[u8] = self.name.as_bytes();
+        let name_length: usize = name.len().min(LUMP_FILE_MAX_NAME_LENGTH);
+
+        entry[0..=3].copy_from_slice(&self.file_position.to_le_bytes());
+        entry[4..=7].copy_from_slice(&self.size.to_le_bytes());
+        entry[8..8 + name_length].copy_from_slice(&name[..name_length]);
+
+        entry
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum WadError {
     IoError(String),
     NoFileExtension(PathBuf),
     InvalidFileExtension(PathBuf),
+    InvalidManifest(String),
+    InvalidLumpFile(PathBuf, String),
 }
 
 impl Display for WadError {
@@ -99,6 +141,13 @@ impl Display for WadError {
                 path.display()
             ),
             Self::IoError(io_error_reason) => write!(f, "{}", io_error_reason),
+            Self::InvalidManifest(reason) => write!(f, "Invalid manifest. {}", reason),
+            Self::InvalidLumpFile(path, reason) => write!(
+                f,
+                "Unable to convert {} into a lump. {}",
+                path.display(),
+                reason
+            ),
         }
     }
 }
@@ -116,36 +165,48 @@ pub struct WadHeader {
 }
 
 impl WadHeader {
-    fn from(file: &mut File) -> Result<Self, WadError> {
+    pub fn from(file: &mut File) -> Result<Self, WadError> {
         println!("Processing Wad Header...");
 
-        let mut wad_header_data: [u8; 12] = [0; 12];
+        let mut wad_header_data: [u8; WAD_HEADER_SIZE] = [0; WAD_HEADER_SIZE];
         file.read_exact(&mut wad_header_data)?;
 
+        let wad_header: WadHeader = WadHeader::from_bytes(&wad_header_data);
+
+        println!(
+            "Creating Wad Header - ID: {}, Number of Lumps: {}, Lump Location Offset: {}",
+            wad_header.id.to_str(),
+            wad_header.num_lumps,
+            wad_header.lump_location_offset
+        );
+
+        Ok(wad_header)
+    }
+
+    pub fn from_bytes(wad_header_data: &[u8; WAD_HEADER_SIZE]) -> Self {
         let id: [u8; 4] = wad_header_data[0..=3].try_into().unwrap();
         let num_lumps: [u8; 4] = wad_header_data[4..=7].try_into().unwrap();
         let lump_location_offset: [u8; 4] = wad_header_data[8..=11].try_into().unwrap();
 
-        let id: WadID = WadID::from(String::from_utf8_lossy(&id).to_string());
-        let num_lumps: u32 = u32::from_le_bytes(num_lumps);
-        let lump_location_offset: u32 = u32::from_le_bytes(lump_location_offset);
+        WadHeader {
+            id: WadID::from(String::from_utf8_lossy(&id).to_string()),
+            num_lumps: u32::from_le_bytes(num_lumps),
+            lump_location_offset: u32::from_le_bytes(lump_location_offset),
+        }
+    }
 
-        println!(
-            "Creating Wad Header - ID: {}, Number of Lumps: {}, Lump Location Offset: {}",
-            id.to_str(),
-            num_lumps,
-            lump_location_offset
-        );
+    pub fn to_bytes(&self) -> [u8; WAD_HEADER_SIZE] {
+        let mut wad_header_data: [u8; WAD_HEADER_SIZE] = [0; WAD_HEADER_SIZE];
 
-        Ok(WadHeader {
-            id,
-            num_lumps,
-            lump_location_offset,
-        })
+        wad_header_data[0..=3].copy_from_slice(self.id.to_str().as_bytes());
+        wad_header_data[4..=7].copy_from_slice(&self.num_lumps.to_le_bytes());
+        wad_header_data[8..=11].copy_from_slice(&self.lump_location_offset.to_le_bytes());
+
+        wad_header_data
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum WadID {
     Iwad,
     Pwad,
@@ -155,14 +216,14 @@ impl WadID {
     // TODO This was a panic in the source code of the original,
     // So we will keep it the same for now.
     // Once we port it over one for one we can decide if we want to keep it the same
-    fn from(value: String) -> Self {
+    pub fn from(value: String) -> Self {
         match value.as_ref() {
             "IWAD" => WadID::Iwad,
             "PWAD" => WadID::Pwad,
             _ => panic!("Invalid value for Wad ID: {}. Can only be IWAD/PWAD", value),
         }
     }
-    fn to_str(&self) -> &str {
+    pub fn to_str(&self) -> &str {
         match self {
             WadID::Iwad => "IWAD",
             WadID::Pwad => "PWAD",
@@ -208,26 +269,14 @@ fn process_wad_file(file_info: FileInfo) -> Result<DoomFile, WadError> {
     let mut lumps: Vec<Lump> = Vec::new();
 
     for _ in 0..wad_header.num_lumps {
-        let mut lump_data: [u8; 16] = [0; 16];
+        let mut lump_data: [u8; LUMP_DIRECTORY_ENTRY_SIZE] = [0; LUMP_DIRECTORY_ENTRY_SIZE];
         file.read_exact(&mut lump_data)?;
 
-        let file_position: [u8; 4] = lump_data[0..=3].try_into().unwrap();
-        let size: [u8; 4] = lump_data[4..=7].try_into().unwrap();
-        let name: [u8; 8] = lump_data[8..=15].try_into().unwrap();
-
-        let file_position: u32 = u32::from_le_bytes(file_position);
-        let size: u32 = u32::from_le_bytes(size);
-        let name: String = String::from_utf8_lossy(&name).to_string();
-
-        let lump: Lump = Lump {
-            name,
-            file_path: file_info.path.to_owned(),
-            file_position,
-            size,
-            should_reload: file_info.should_reload,
-        };
-
-        lumps.push(lump);
+        lumps.push(Lump::from_directory_entry(
+            &lump_data,
+            &file_info.path,
+            file_info.should_reload,
+        ));
     }
 
     println!("Wad file processing done for {}", file_info.path.display());
@@ -259,19 +308,31 @@ fn process_lump_file(file_info: FileInfo) -> Result<DoomFile, WadError> {
     })
 }
 
-pub fn get_lump_data(doom_files: &mut Vec<DoomFile>, lump_name: &str) -> Vec<u8> {
+pub fn get_lump_data(doom_files: &mut [DoomFile], lump_name: &str) -> Vec<u8> {
+    match find_lump_data(doom_files, lump_name) {
+        Some(lump_data) => lump_data,
+        None => panic!("Unable to find data for lump {}", lump_name),
+    }
+}
+
+// Same as get_lump_data but for optional lumps like ENDOOM that
+// PWADs and some IWADs might not have
+pub fn find_lump_data(doom_files: &mut [DoomFile], lump_name: &str) -> Option<Vec<u8>> {
     let (doom_file, lump_index): (&mut DoomFile, usize) =
-        match doom_files.iter_mut().rev().find_map(|doom_file| {
+        doom_files.iter_mut().rev().find_map(|doom_file| {
             let lump_index = doom_file
                 .lumps
                 .iter()
                 .rposition(|lump| lump.name.trim_end_matches('\0') == lump_name);
             lump_index.map(|index| (doom_file, index))
-        }) {
-            Some((doom_file, lump_index)) => (doom_file, lump_index),
-            None => panic!("Unable to find data for lump {}", lump_name),
-        };
+        })?;
 
+    Some(read_lump_data(doom_file, lump_index))
+}
+
+// Reads a lump by its position in the files directory instead of by name.
+// Needed when a wad has multiple lumps with the same name(THINGS, LINEDEFS, etc for every map)
+pub fn read_lump_data(doom_file: &mut DoomFile, lump_index: usize) -> Vec<u8> {
     let lump: &Lump = doom_file.lumps.get(lump_index).unwrap();
     let mut lump_data: Vec<u8> = vec![0; usize::try_from(lump.size).unwrap()];
 
@@ -313,7 +374,8 @@ pub fn get_lump_data(doom_files: &mut Vec<DoomFile>, lump_name: &str) -> Vec<u8>
 #[cfg(test)]
 mod tests {
     use crate::wad::{
-        get_lump_data, process_file, process_wad_file, DoomFile, FileInfo, Lump, WadError, WadID,
+        find_lump_data, get_lump_data, process_file, process_wad_file, DoomFile, FileInfo, Lump,
+        WadError, WadHeader, WadID,
     };
     use std::collections::HashMap;
     use std::ffi::OsString;
@@ -327,6 +389,8 @@ mod tests {
             WadError::InvalidFileExtension(PathBuf::from("")),
             WadError::IoError(String::from("test")),
             WadError::NoFileExtension(PathBuf::from("")),
+            WadError::InvalidManifest(String::from("test")),
+            WadError::InvalidLumpFile(PathBuf::from(""), String::from("test")),
         ];
 
         for wad_error in wad_errors {
@@ -375,6 +439,61 @@ mod tests {
         assert_eq!(WadID::Pwad.to_str(), "PWAD");
     }
 
+    #[test]
+    fn test_wad_header_bytes_round_trip() {
+        let wad_header: WadHeader = WadHeader {
+            id: WadID::Pwad,
+            num_lumps: 3,
+            lump_location_offset: 1234,
+        };
+
+        let wad_header_data: [u8; 12] = wad_header.to_bytes();
+        let read_wad_header: WadHeader = WadHeader::from_bytes(&wad_header_data);
+
+        assert_eq!(&wad_header_data[0..4], b"PWAD");
+        assert_eq!(read_wad_header.id, wad_header.id);
+        assert_eq!(read_wad_header.num_lumps, wad_header.num_lumps);
+        assert_eq!(
+            read_wad_header.lump_location_offset,
+            wad_header.lump_location_offset
+        );
+    }
+
+    #[test]
+    fn test_lump_directory_entry_round_trip() {
+        let lump: Lump = Lump {
+            name: String::from("PLAYPAL\0"),
+            file_path: PathBuf::from("test.wad"),
+            file_position: 12,
+            size: 10752,
+            should_reload: false,
+        };
+
+        let entry: [u8; 16] = lump.to_directory_entry();
+        let read_lump: Lump = Lump::from_directory_entry(&entry, &lump.file_path, false);
+
+        assert_eq!(&entry[8..16], b"PLAYPAL\0");
+        assert_eq!(read_lump.name, lump.name);
+        assert_eq!(read_lump.file_position, lump.file_position);
+        assert_eq!(read_lump.size, lump.size);
+    }
+
+    #[test]
+    fn test_lump_to_directory_entry_pads_and_truncates_names() {
+        let mut lump: Lump = Lump {
+            name: String::from("DATA"),
+            file_path: PathBuf::from("test.wad"),
+            file_position: 0,
+            size: 0,
+            should_reload: false,
+        };
+
+        assert_eq!(&lump.to_directory_entry()[8..16], b"DATA\0\0\0\0");
+
+        lump.name = String::from("LONGERNAME");
+        assert_eq!(&lump.to_directory_entry()[8..16], b"LONGERNA");
+    }
+
     #[test]
     fn test_process_file_processes_wad_file() {
         let mut wad_path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -552,6 +671,30 @@ mod tests {
 
         get_lump_data(&mut doom_files, "DOESNOTEXIST");
     }
+
+    #[test]
+    fn test_find_lump_data_returns_none_when_lump_data_not_found() {
+        let mut file_path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        file_path.push("tests/resource/test.wad");
+
+        let file: File = File::open(&file_path).unwrap();
+
+        let doom_file: DoomFile = DoomFile {
+            file_handle: ManuallyDrop::new(file),
+            lumps: vec![Lump {
+                name: String::from("DATA\0\0\0\0"),
+                file_path,
+                file_position: 12,
+                size: 13,
+                should_reload: false,
+            }],
+        };
+
+        let mut doom_files: Vec<DoomFile> = vec![doom_file];
+
+        assert_eq!(find_lump_data(&mut doom_files, "DOESNOTEXIST"), None);
+        assert_eq!(find_lump_data(&mut doom_files, "DATA").unwrap().len(), 13);
+    }
     #[test]
     fn test_get_lump_data_returns_lump_data_when_lump_reloadable() {
         let mut file_path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
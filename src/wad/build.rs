@@ -0,0 +1,584 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::graphics::{Palette, Picture};
+use crate::image::png::{PngColor, PngImage};
+use crate::sound::{dmx::DmxSound, wav::Wav};
+use crate::wad::format::LumpFormat;
+use crate::wad::manifest::{ManifestEntry, WadManifest};
+use crate::wad::{Lump, WadError, WadHeader, LUMP_DIRECTORY_ENTRY_SIZE, WAD_HEADER_SIZE};
+
+// Pixels more transparent than this are left out of pictures
+const PICTURE_ALPHA_THRESHOLD: u8 = 128;
+
+// Builds a wad from a manifest written by extract_wad(or by hand).
+// When every lump is the same size as it was when extracted the original
+// offsets are reused so the wad is rebuilt identically, otherwise the
+// lumps are written one after the other in manifest order.
+// If no palette is supplied the manifests PLAYPAL lump is used to convert PNGs
+pub fn build_wad(
+    manifest_path: &Path,
+    output_path: &Path,
+    palette: Option<Palette>,
+) -> Result<(), WadError> {
+    let manifest: WadManifest = WadManifest::from_text(&fs::read_to_string(manifest_path)?)?;
+    let manifest_dir: &Path = manifest_path.parent().unwrap_or(Path::new(""));
+
+    let palette: Option<Palette> = match palette {
+        Some(palette) => Some(palette),
+        None => find_manifest_palette(&manifest, manifest_dir)?,
+    };
+
+    let mut lumps_data: Vec<Vec<u8>> = Vec::with_capacity(manifest.entries.len());
+    for entry in &manifest.entries {
+        lumps_data.push(read_lump_file(
+            entry,
+            &manifest_dir.join(&entry.file),
+            palette.as_ref(),
+        )?);
+    }
+
+    let wad_data: Vec<u8> = match layout_original(&manifest, &lumps_data) {
+        Some(wad_data) => wad_data,
+        None => {
+            println!("Lumps have changed size, writing them sequentially");
+            layout_sequential(&manifest, &lumps_data)
+        }
+    };
+
+    fs::write(output_path, wad_data)?;
+
+    println!(
+        "Built {} with {} lumps",
+        output_path.display(),
+        manifest.entries.len()
+    );
+
+    Ok(())
+}
+
+fn find_manifest_palette(
+    manifest: &WadManifest,
+    manifest_dir: &Path,
+) -> Result<Option<Palette>, WadError> {
+    match manifest
+        .entries
+        .iter()
+        .rev()
+        .find(|entry| entry.name == "PLAYPAL")
+    {
+        Some(entry) => {
+            let playpal_path: PathBuf = manifest_dir.join(&entry.file);
+            let playpal_data: Vec<u8> = fs::read(&playpal_path)?;
+
+            Palette::from_playpal(&playpal_data, 0)
+                .map(Some)
+                .map_err(|error| WadError::InvalidLumpFile(playpal_path, error.to_string()))
+        }
+        None => Ok(None),
+    }
+}
+
+// Converts PNG/WAV files back into Doom formats, anything else is used as is
+pub fn read_lump_file(
+    entry: &ManifestEntry,
+    file_path: &Path,
+    palette: Option<&Palette>,
+) -> Result<Vec<u8>, WadError> {
+    let file_data: Vec<u8> = fs::read(file_path)?;
+    let extension: String = file_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let invalid_lump_file =
+        |reason: String| -> WadError { WadError::InvalidLumpFile(file_path.to_path_buf(), reason) };
+
+    match extension.as_str() {
+        "png" => {
+            let palette: &Palette = palette.ok_or_else(|| {
+                invalid_lump_file(String::from("A palette is needed to convert PNG files"))
+            })?;
+            let png_image: PngImage = PngImage::decode(&file_data)
+                .map_err(|error| invalid_lump_file(error.to_string()))?;
+
+            if entry.format == LumpFormat::Flat {
+                Ok(png_to_palette_indices(&png_image, palette)
+                    .into_iter()
+                    .map(|index| index.unwrap_or(0))
+                    .collect())
+            } else {
+                let (left_offset, top_offset): (i32, i32) = png_image.grab_offset.unwrap_or((0, 0));
+
+                Picture {
+                    width: png_image.width as usize,
+                    height: png_image.height as usize,
+                    left_offset: left_offset as i16,
+                    top_offset: top_offset as i16,
+                    pixels: png_to_palette_indices(&png_image, palette),
+                }
+                .to_lump()
+                .map_err(|error| invalid_lump_file(error.to_string()))
+            }
+        }
+        "wav" if entry.format == LumpFormat::PcSpeaker => Err(invalid_lump_file(String::from(
+            "Converting PC speaker WAV files back into lumps is not supported, use the .lmp file",
+        ))),
+        "wav" => {
+            let wav: Wav = Wav::from_bytes(&file_data)
+                .map_err(|error| invalid_lump_file(error.to_string()))?;
+            let sample_rate: u16 = u16::try_from(wav.sample_rate).map_err(|_| {
+                invalid_lump_file(format!(
+                    "Sample rate {} is too high for a DMX sound",
+                    wav.sample_rate
+                ))
+            })?;
+
+            Ok(DmxSound {
+                sample_rate,
+                samples: wav.to_unsigned_8bit_mono(),
+            }
+            .to_lump())
+        }
+        "mid" | "ans" => Err(invalid_lump_file(format!(
+            "Converting .{} files back into lumps is not supported, use the .lmp file",
+            extension
+        ))),
+        _ => Ok(file_data),
+    }
+}
+
+// Indexed PNGs using the same palette keep their indices, everything
+// else is matched to the closest palette color
+fn png_to_palette_indices(png_image: &PngImage, palette: &Palette) -> Vec<Option<u8>> {
+    match &png_image.color {
+        PngColor::Indexed {
+            palette: png_palette,
+            indices,
+        } if *png_palette == palette.colors => indices.iter().map(|index| Some(*index)).collect(),
+        _ => png_image
+            .to_rgba()
+            .iter()
+            .map(|[red, green, blue, alpha]| {
+                if *alpha < PICTURE_ALPHA_THRESHOLD {
+                    None
+                } else {
+                    Some(palette.nearest_index([*red, *green, *blue]))
+                }
+            })
+            .collect(),
+    }
+}
+
+fn create_lump(entry: &ManifestEntry, file_position: u32, size: u32) -> Lump {
+    Lump {
+        name: entry.name.clone(),
+        file_path: PathBuf::from(&entry.file),
+        file_position,
+        size,
+        should_reload: false,
+    }
+}
+
+fn write_wad(
+    manifest: &WadManifest,
+    wad_data: &mut Vec<u8>,
+    lumps: &[Lump],
+    directory_offset: u32,
+) {
+    let wad_header: WadHeader = WadHeader {
+        id: manifest.id,
+        num_lumps: lumps.len() as u32,
+        lump_location_offset: directory_offset,
+    };
+
+    let directory_start: usize = directory_offset as usize;
+    let directory_end: usize = directory_start + lumps.len() * LUMP_DIRECTORY_ENTRY_SIZE;
+    if wad_data.len() < directory_end {
+        wad_data.resize(directory_end, 0);
+    }
+
+    wad_data[0..WAD_HEADER_SIZE].copy_from_slice(&wad_header.to_bytes());
+
+    for (lump_index, lump) in lumps.iter().enumerate() {
+        let entry_start: usize = directory_start + lump_index * LUMP_DIRECTORY_ENTRY_SIZE;
+        wad_data[entry_start..entry_start + LUMP_DIRECTORY_ENTRY_SIZE]
+            .copy_from_slice(&lump.to_directory_entry());
+    }
+}
+
+// Returns None if the lumps no longer fit at their original offsets
+fn layout_original(manifest: &WadManifest, lumps_data: &[Vec<u8>]) -> Option<Vec<u8>> {
+    let directory_start: usize = manifest.directory_offset as usize;
+    let directory_end: usize = directory_start + lumps_data.len() * LUMP_DIRECTORY_ENTRY_SIZE;
+
+    if directory_start < WAD_HEADER_SIZE {
+        return None;
+    }
+
+    let mut wad_data: Vec<u8> = vec![0; directory_end];
+    let mut lumps: Vec<Lump> = Vec::with_capacity(lumps_data.len());
+
+    for (entry, lump_data) in manifest.entries.iter().zip(lumps_data) {
+        let start: usize = entry.offset as usize;
+        let end: usize = start + lump_data.len();
+
+        if lump_data.len() != entry.size as usize {
+            return None;
+        }
+
+        if !lump_data.is_empty() {
+            let overlaps_header: bool = start < WAD_HEADER_SIZE;
+            let overlaps_directory: bool = start < directory_end && end > directory_start;
+
+            if overlaps_header || overlaps_directory {
+                return None;
+            }
+
+            if wad_data.len() < end {
+                wad_data.resize(end, 0);
+            }
+
+            wad_data[start..end].copy_from_slice(lump_data);
+        }
+
+        lumps.push(create_lump(entry, entry.offset, entry.size));
+    }
+
+    // Lumps can share data, but only if it's the same data
+    let lumps_intact: bool = manifest
+        .entries
+        .iter()
+        .zip(lumps_data)
+        .all(|(entry, lump_data)| {
+            let start: usize = entry.offset as usize;
+            lump_data.is_empty() || wad_data[start..start + lump_data.len()] == lump_data[..]
+        });
+
+    if !lumps_intact {
+        return None;
+    }
+
+    write_wad(manifest, &mut wad_data, &lumps, manifest.directory_offset);
+    Some(wad_data)
+}
+
+// Header, then the lumps in order, then the lump directory like the id tools did it
+fn layout_sequential(manifest: &WadManifest, lumps_data: &[Vec<u8>]) -> Vec<u8> {
+    let mut wad_data: Vec<u8> = vec![0; WAD_HEADER_SIZE];
+    let mut lumps: Vec<Lump> = Vec::with_capacity(lumps_data.len());
+
+    for (entry, lump_data) in manifest.entries.iter().zip(lumps_data) {
+        lumps.push(create_lump(
+            entry,
+            wad_data.len() as u32,
+            lump_data.len() as u32,
+        ));
+        wad_data.extend_from_slice(lump_data);
+    }
+
+    let directory_offset: u32 = wad_data.len() as u32;
+    write_wad(manifest, &mut wad_data, &lumps, directory_offset);
+
+    wad_data
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphics::{Palette, Picture};
+    use crate::image::png::{PngColor, PngImage};
+    use crate::sound::dmx::DmxSound;
+    use crate::sound::wav::Wav;
+    use crate::test_util::create_test_dir;
+    use crate::wad::build::{build_wad, read_lump_file};
+    use crate::wad::extract::extract_wad;
+    use crate::wad::format::LumpFormat;
+    use crate::wad::manifest::{ManifestEntry, WadManifest, MANIFEST_FILE_NAME};
+    use crate::wad::namespace::LumpNamespace;
+    use crate::wad::{WadError, WadID};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn create_test_palette() -> Palette {
+        Palette {
+            colors: (0..256).map(|x| [x as u8, 0, 255 - x as u8]).collect(),
+        }
+    }
+
+    fn create_entry(name: &str, format: LumpFormat, file: &str) -> ManifestEntry {
+        ManifestEntry {
+            name: String::from(name),
+            namespace: LumpNamespace::Global,
+            format,
+            offset: 0,
+            size: 0,
+            file: String::from(file),
+        }
+    }
+
+    fn write_gapped_wad(wad_path: &PathBuf) -> Vec<u8> {
+        let playpal: Vec<u8> = create_test_palette()
+            .colors
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+        let picture: Vec<u8> = Picture {
+            width: 2,
+            height: 1,
+            left_offset: 3,
+            top_offset: -4,
+            pixels: vec![Some(7), None],
+        }
+        .to_lump()
+        .unwrap();
+
+        let lumps: [(&str, Vec<u8>); 4] = [
+            ("PLAYPAL", playpal),
+            ("F_START", Vec::new()),
+            ("FLAT1", (0..4096).map(|x| (x % 256) as u8).collect()),
+            ("F_END", Vec::new()),
+        ];
+
+        // Gap in between lumps and the directory before the
+        // picture lump to make sure we keep the original layout
+        let mut wad_data: Vec<u8> = vec![0; 12];
+        let mut directory: Vec<u8> = Vec::new();
+        for (name, lump_data) in lumps.iter() {
+            let mut entry: [u8; 16] = [0; 16];
+            entry[0..4].copy_from_slice(&(wad_data.len() as u32).to_le_bytes());
+            entry[4..8].copy_from_slice(&(lump_data.len() as u32).to_le_bytes());
+            entry[8..8 + name.len()].copy_from_slice(name.as_bytes());
+            directory.extend_from_slice(&entry);
+            wad_data.extend_from_slice(lump_data);
+            wad_data.extend_from_slice(&[0; 4]);
+        }
+
+        let directory_offset: u32 = wad_data.len() as u32;
+        let mut entry: [u8; 16] = [0; 16];
+        entry[0..4].copy_from_slice(&(directory_offset + 5 * 16).to_le_bytes());
+        entry[4..8].copy_from_slice(&(picture.len() as u32).to_le_bytes());
+        entry[8..14].copy_from_slice(b"TITLE1");
+        directory.extend_from_slice(&entry);
+
+        wad_data.extend_from_slice(&directory);
+        wad_data.extend_from_slice(&picture);
+        wad_data[0..4].copy_from_slice(b"IWAD");
+        wad_data[4..8].copy_from_slice(&5u32.to_le_bytes());
+        wad_data[8..12].copy_from_slice(&directory_offset.to_le_bytes());
+
+        fs::write(wad_path, &wad_data).unwrap();
+        wad_data
+    }
+
+    #[test]
+    fn test_build_wad_rebuilds_extracted_wad_identically() {
+        let test_dir: PathBuf = create_test_dir("build-identical");
+        let wad_path: PathBuf = test_dir.join("original.wad");
+        let rebuilt_wad_path: PathBuf = test_dir.join("rebuilt.wad");
+
+        let wad_data: Vec<u8> = write_gapped_wad(&wad_path);
+        extract_wad(&wad_path, &test_dir.join("out"), None).unwrap();
+
+        build_wad(
+            &test_dir.join("out").join(MANIFEST_FILE_NAME),
+            &rebuilt_wad_path,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(fs::read(&rebuilt_wad_path).unwrap(), wad_data);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_wad_converts_png_files_and_writes_changed_lumps_sequentially() {
+        let test_dir: PathBuf = create_test_dir("build-converted");
+        let wad_path: PathBuf = test_dir.join("original.wad");
+        let out_dir: PathBuf = test_dir.join("out");
+        let rebuilt_wad_path: PathBuf = test_dir.join("rebuilt.wad");
+
+        write_gapped_wad(&wad_path);
+        extract_wad(&wad_path, &out_dir, None).unwrap();
+
+        // Swap the picture and flat over to their PNG versions
+        let manifest_path: PathBuf = out_dir.join(MANIFEST_FILE_NAME);
+        let mut manifest: WadManifest =
+            WadManifest::from_text(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest.entries[2].file = String::from("00002_FLAT1.png");
+        manifest.entries[4].file = String::from("00004_TITLE1.png");
+        manifest
+            .entries
+            .push(create_entry("EXTRA", LumpFormat::Raw, "extra.lmp"));
+        fs::write(out_dir.join("extra.lmp"), b"extra").unwrap();
+        fs::write(&manifest_path, manifest.to_text()).unwrap();
+
+        build_wad(&manifest_path, &rebuilt_wad_path, None).unwrap();
+
+        let rebuilt_wad: Vec<u8> = fs::read(&rebuilt_wad_path).unwrap();
+        let directory_offset: usize =
+            u32::from_le_bytes(rebuilt_wad[8..12].try_into().unwrap()) as usize;
+
+        assert_eq!(&rebuilt_wad[0..8], &[b'I', b'W', b'A', b'D', 6, 0, 0, 0]);
+        // Lumps are packed together with the directory at the end
+        assert_eq!(rebuilt_wad.len(), directory_offset + 6 * 16);
+
+        let read_lump = |index: usize| -> &[u8] {
+            let entry: &[u8] = &rebuilt_wad[directory_offset + index * 16..];
+            let position: usize = u32::from_le_bytes(entry[0..4].try_into().unwrap()) as usize;
+            let size: usize = u32::from_le_bytes(entry[4..8].try_into().unwrap()) as usize;
+            &rebuilt_wad[position..position + size]
+        };
+
+        assert_eq!(
+            read_lump(2),
+            (0..4096).map(|x| (x % 256) as u8).collect::<Vec<u8>>()
+        );
+        assert_eq!(
+            Picture::from_lump(read_lump(4)).unwrap(),
+            Picture {
+                width: 2,
+                height: 1,
+                left_offset: 3,
+                top_offset: -4,
+                pixels: vec![Some(7), None],
+            }
+        );
+        assert_eq!(read_lump(5), b"extra");
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_lump_file_converts_wav_to_dmx_sound() {
+        let test_dir: PathBuf = create_test_dir("build-wav");
+        let wav_path: PathBuf = test_dir.join("DSPISTOL.wav");
+
+        let wav: Wav = Wav {
+            sample_rate: 11025,
+            channels: 1,
+            bits_per_sample: 8,
+            data: vec![100; 64],
+        };
+        fs::write(&wav_path, wav.to_bytes()).unwrap();
+
+        let lump: Vec<u8> = read_lump_file(
+            &create_entry("DSPISTOL", LumpFormat::Sound, "DSPISTOL.wav"),
+            &wav_path,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            DmxSound::from_lump(&lump).unwrap(),
+            DmxSound {
+                sample_rate: 11025,
+                samples: vec![100; 64]
+            }
+        );
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_lump_file_matches_rgba_png_to_palette() {
+        let test_dir: PathBuf = create_test_dir("build-png");
+        let png_path: PathBuf = test_dir.join("TEST.png");
+
+        let png_image: PngImage = PngImage {
+            width: 2,
+            height: 1,
+            color: PngColor::Rgba(vec![[10, 1, 244, 255], [0, 0, 0, 0]]),
+            grab_offset: None,
+        };
+        fs::write(&png_path, png_image.encode()).unwrap();
+
+        let lump: Vec<u8> = read_lump_file(
+            &create_entry("TEST", LumpFormat::Picture, "TEST.png"),
+            &png_path,
+            Some(&create_test_palette()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Picture::from_lump(&lump).unwrap().pixels,
+            vec![Some(10), None]
+        );
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_lump_file_returns_error_for_unconvertible_files() {
+        let test_dir: PathBuf = create_test_dir("build-errors");
+        let png_path: PathBuf = test_dir.join("TEST.png");
+        let midi_path: PathBuf = test_dir.join("D_E1M1.mid");
+        fs::write(&png_path, b"not a png").unwrap();
+        fs::write(&midi_path, b"MThd").unwrap();
+
+        assert!(matches!(
+            read_lump_file(
+                &create_entry("TEST", LumpFormat::Picture, "TEST.png"),
+                &png_path,
+                None
+            ),
+            Err(WadError::InvalidLumpFile(_, _))
+        ));
+
+        assert!(matches!(
+            read_lump_file(
+                &create_entry("TEST", LumpFormat::Picture, "TEST.png"),
+                &png_path,
+                Some(&create_test_palette())
+            ),
+            Err(WadError::InvalidLumpFile(_, _))
+        ));
+
+        assert!(matches!(
+            read_lump_file(
+                &create_entry("D_E1M1", LumpFormat::Music, "D_E1M1.mid"),
+                &midi_path,
+                None
+            ),
+            Err(WadError::InvalidLumpFile(_, _))
+        ));
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_wad_returns_error_for_missing_manifest() {
+        let test_dir: PathBuf = create_test_dir("build-missing");
+
+        assert!(matches!(
+            build_wad(
+                &test_dir.join(MANIFEST_FILE_NAME),
+                &test_dir.join("out.wad"),
+                None
+            ),
+            Err(WadError::IoError(_))
+        ));
+
+        let manifest: WadManifest = WadManifest {
+            id: WadID::Pwad,
+            directory_offset: 12,
+            entries: Vec::new(),
+        };
+        fs::write(test_dir.join(MANIFEST_FILE_NAME), manifest.to_text()).unwrap();
+        build_wad(
+            &test_dir.join(MANIFEST_FILE_NAME),
+            &test_dir.join("out.wad"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read(test_dir.join("out.wad")).unwrap(),
+            vec![b'P', b'W', b'A', b'D', 0, 0, 0, 0, 12, 0, 0, 0]
+        );
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    fs::{self, File},
+    path::Path,
+};
+
+use crate::endoom::Endoom;
+use crate::graphics::{Palette, Picture};
+use crate::image::png::{PngColor, PngImage};
+use crate::music::{midi, mus::MusFile};
+use crate::sound::{dmx::DmxSound, pcspeaker::PcSpeakerSound, wav::Wav};
+use crate::wad::format::LumpFormat;
+use crate::wad::manifest::{ManifestEntry, WadManifest, MANIFEST_FILE_NAME};
+use crate::wad::namespace::{self, LumpNamespace};
+use crate::wad::{self, DoomFile, WadError, WadHeader};
+
+// Square waves alias badly at the 11025Hz the digital sounds use
+const PC_SPEAKER_WAV_SAMPLE_RATE: u32 = 44100;
+
+// Dumps every lump of a wad into output_dir as <index>_<name>.lmp along with a
+// converted copy(PNG/WAV/MIDI/ANSI) of the lumps we recognise and a manifest
+// describing the lump directory. If no palette is supplied the wads own PLAYPAL is used
+pub fn extract_wad(
+    wad_path: &Path,
+    output_dir: &Path,
+    palette: Option<Palette>,
+) -> Result<WadManifest, WadError> {
+    if wad_path
+        .extension()
+        .is_none_or(|extension| extension != "wad")
+    {
+        return Err(WadError::InvalidFileExtension(wad_path.to_path_buf()));
+    }
+
+    let wad_header: WadHeader = WadHeader::from(&mut File::open(wad_path)?)?;
+    let mut doom_file: DoomFile = wad::process_file(wad_path)?;
+
+    let palette: Option<Palette> = palette.or_else(|| {
+        doom_file
+            .lumps
+            .iter()
+            .position(|lump| lump.name.trim_end_matches('\0') == "PLAYPAL")
+            .and_then(|playpal_index| {
+                Palette::from_playpal(&wad::read_lump_data(&mut doom_file, playpal_index), 0).ok()
+            })
+    });
+
+    if palette.is_none() {
+        println!("No PLAYPAL found, pictures and flats will only be extracted raw");
+    }
+
+    fs::create_dir_all(output_dir)?;
+
+    let namespaces: Vec<LumpNamespace> = namespace::lump_namespaces(&doom_file.lumps);
+    let mut entries: Vec<ManifestEntry> = Vec::with_capacity(doom_file.lumps.len());
+
+    for (lump_index, namespace) in namespaces.into_iter().enumerate() {
+        let lump_data: Vec<u8> = wad::read_lump_data(&mut doom_file, lump_index);
+        let lump_name: String =
+            String::from(doom_file.lumps[lump_index].name.trim_end_matches('\0'));
+        let format: LumpFormat = LumpFormat::detect(&lump_name, namespace, &lump_data);
+
+        let file_stem: String = format!("{:05}_{}", lump_index, sanitize_file_name(&lump_name));
+        let raw_file_name: String = format!("{}.lmp", file_stem);
+        fs::write(output_dir.join(&raw_file_name), &lump_data)?;
+
+        if let (Some(converted_data), Some(extension)) = (
+            convert_lump(format, &lump_data, palette.as_ref()),
+            format.file_extension(),
+        ) {
+            fs::write(
+                output_dir.join(format!("{}.{}", file_stem, extension)),
+                converted_data,
+            )?;
+        }
+
+        entries.push(ManifestEntry {
+            name: lump_name,
+            namespace,
+            format,
+            offset: doom_file.lumps[lump_index].file_position,
+            size: doom_file.lumps[lump_index].size,
+            file: raw_file_name,
+        });
+    }
+
+    let manifest: WadManifest = WadManifest {
+        id: wad_header.id,
+        directory_offset: wad_header.lump_location_offset,
+        entries,
+    };
+
+    fs::write(output_dir.join(MANIFEST_FILE_NAME), manifest.to_text())?;
+
+    println!(
+        "Extracted {} lumps to {}",
+        manifest.entries.len(),
+        output_dir.display()
+    );
+
+    Ok(manifest)
+}
+
+// Returns None when the lump can't be converted, we still have the raw copy
+pub fn convert_lump(format: LumpFormat, data: &[u8], palette: Option<&Palette>) -> Option<Vec<u8>> {
+    match format {
+        LumpFormat::Raw => None,
+        LumpFormat::Picture => {
+            let palette: &Palette = palette?;
+            let picture: Picture = Picture::from_lump(data).ok()?;
+
+            let pixels: Vec<[u8; 4]> = picture
+                .pixels
+                .iter()
+                .map(|pixel| match pixel {
+                    Some(index) => {
+                        let [red, green, blue]: [u8; 3] = palette.color(*index);
+                        [red, green, blue, 255]
+                    }
+                    None => [0, 0, 0, 0],
+                })
+                .collect();
+
+            Some(
+                PngImage {
+                    width: picture.width as u32,
+                    height: picture.height as u32,
+                    color: PngColor::Rgba(pixels),
+                    grab_offset: Some((
+                        i32::from(picture.left_offset),
+                        i32::from(picture.top_offset),
+                    )),
+                }
+                .encode(),
+            )
+        }
+        LumpFormat::Flat => {
+            let palette: &Palette = palette?;
+            let width: usize = crate::graphics::FLAT_WIDTH;
+
+            Some(
+                PngImage {
+                    width: width as u32,
+                    height: (data.len() / width) as u32,
+                    color: PngColor::Indexed {
+                        palette: palette.colors.clone(),
+                        indices: data.to_vec(),
+                    },
+                    grab_offset: None,
+                }
+                .encode(),
+            )
+        }
+        LumpFormat::Sound => {
+            let dmx_sound: DmxSound = DmxSound::from_lump(data).ok()?;
+
+            Some(
+                Wav {
+                    sample_rate: u32::from(dmx_sound.sample_rate),
+                    channels: 1,
+                    bits_per_sample: 8,
+                    data: dmx_sound.samples,
+                }
+                .to_bytes(),
+            )
+        }
+        LumpFormat::PcSpeaker => Some(
+            PcSpeakerSound::from_lump(data)
+                .ok()?
+                .to_wav(PC_SPEAKER_WAV_SAMPLE_RATE)
+                .to_bytes(),
+        ),
+        LumpFormat::Music => Some(midi::mus_to_midi(&MusFile::from_lump(data).ok()?)),
+        LumpFormat::Endoom => Some(Endoom::from_lump(data).ok()?.to_ansi().into_bytes()),
+    }
+}
+
+// Lump names can contain characters that aren't valid in file names(VILE\1 in DOOM2.WAD)
+fn sanitize_file_name(lump_name: &str) -> String {
+    lump_name
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() || "_-[]".contains(character) {
+                character.to_string()
+            } else {
+                format!("%{:02X}", u32::from(character))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphics::Picture;
+    use crate::sound::dmx::DmxSound;
+    use crate::test_util::{create_test_dir, write_test_wad};
+    use crate::wad::extract::{extract_wad, sanitize_file_name};
+    use crate::wad::format::LumpFormat;
+    use crate::wad::manifest::{WadManifest, MANIFEST_FILE_NAME};
+    use crate::wad::namespace::LumpNamespace;
+    use crate::wad::{WadError, WadID};
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_sanitize_file_name_escapes_invalid_characters() {
+        assert_eq!(sanitize_file_name("VILE[1"), "VILE[1");
+        assert_eq!(sanitize_file_name("VILE\\1"), "VILE%5C1");
+        assert_eq!(sanitize_file_name("A/B.C"), "A%2FB%2EC");
+    }
+
+    #[test]
+    fn test_extract_wad_returns_error_for_non_wad_file() {
+        let mut lump_path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        lump_path.push("tests/resource/TEST.lmp");
+
+        assert_eq!(
+            extract_wad(&lump_path, &create_test_dir("extract-lmp"), None).err(),
+            Some(WadError::InvalidFileExtension(lump_path))
+        );
+    }
+
+    #[test]
+    fn test_extract_wad_writes_lumps_conversions_and_manifest() {
+        let test_dir: PathBuf = create_test_dir("extract");
+        let wad_path: PathBuf = test_dir.join("test.wad");
+        let output_dir: PathBuf = test_dir.join("out");
+
+        let playpal: Vec<u8> = (0..768).map(|x| (x % 256) as u8).collect();
+        let picture: Vec<u8> = Picture {
+            width: 1,
+            height: 2,
+            left_offset: 1,
+            top_offset: 2,
+            pixels: vec![Some(5), None],
+        }
+        .to_lump()
+        .unwrap();
+        let sound: Vec<u8> = DmxSound {
+            sample_rate: 11025,
+            samples: vec![128; 64],
+        }
+        .to_lump();
+
+        write_test_wad(
+            &wad_path,
+            &[
+                (String::from("PLAYPAL"), playpal.clone()),
+                (String::from("DSPISTOL"), sound),
+                (String::from("S_START"), Vec::new()),
+                (String::from("TROOA1"), picture),
+                (String::from("S_END"), Vec::new()),
+            ],
+        );
+
+        let manifest: WadManifest = extract_wad(&wad_path, &output_dir, None).unwrap();
+
+        assert_eq!(manifest.id, WadID::Pwad);
+        assert_eq!(manifest.entries.len(), 5);
+        assert_eq!(manifest.entries[3].name, "TROOA1");
+        assert_eq!(manifest.entries[3].namespace, LumpNamespace::Sprites);
+        assert_eq!(manifest.entries[3].format, LumpFormat::Picture);
+        assert_eq!(manifest.entries[1].format, LumpFormat::Sound);
+        assert_eq!(manifest.entries[0].offset, 12);
+
+        assert_eq!(
+            fs::read(output_dir.join("00000_PLAYPAL.lmp")).unwrap(),
+            playpal
+        );
+        assert!(output_dir.join("00001_DSPISTOL.wav").exists());
+        assert!(output_dir.join("00003_TROOA1.png").exists());
+        assert!(!output_dir.join("00002_S_START.png").exists());
+
+        let manifest_text: String =
+            fs::read_to_string(output_dir.join(MANIFEST_FILE_NAME)).unwrap();
+        assert_eq!(WadManifest::from_text(&manifest_text).unwrap(), manifest);
+
+        fs::remove_dir_all(test_dir).unwrap();
+    }
+}
@@ -0,0 +1,195 @@
+use crate::endoom::ENDOOM_SIZE;
+use crate::graphics::{Picture, FLAT_HEIGHT, FLAT_WIDTH};
+use crate::music::mus::MusFile;
+use crate::sound::dmx::DmxSound;
+use crate::sound::pcspeaker::PcSpeakerSound;
+use crate::wad::namespace::LumpNamespace;
+
+// Lumps that come after a map marker lump(E1M1, MAP01, etc)
+pub const MAP_LUMP_NAMES: [&str; 10] = [
+    "THINGS", "LINEDEFS", "SIDEDEFS", "VERTEXES", "SEGS", "SSECTORS", "NODES", "SECTORS", "REJECT",
+    "BLOCKMAP",
+];
+
+// Global lumps that can happen to pass as a picture but never are one
+const NON_PICTURE_LUMP_NAMES: [&str; 9] = [
+    "PLAYPAL", "COLORMAP", "TEXTURE1", "TEXTURE2", "PNAMES", "GENMIDI", "DMXGUS", "DMXGUSC",
+    "ENDOOM",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LumpFormat {
+    Raw,
+    Picture,
+    Flat,
+    Sound,
+    PcSpeaker,
+    Music,
+    Endoom,
+}
+
+impl LumpFormat {
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value {
+            "raw" => Some(LumpFormat::Raw),
+            "picture" => Some(LumpFormat::Picture),
+            "flat" => Some(LumpFormat::Flat),
+            "sound" => Some(LumpFormat::Sound),
+            "pcspeaker" => Some(LumpFormat::PcSpeaker),
+            "music" => Some(LumpFormat::Music),
+            "endoom" => Some(LumpFormat::Endoom),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            LumpFormat::Raw => "raw",
+            LumpFormat::Picture => "picture",
+            LumpFormat::Flat => "flat",
+            LumpFormat::Sound => "sound",
+            LumpFormat::PcSpeaker => "pcspeaker",
+            LumpFormat::Music => "music",
+            LumpFormat::Endoom => "endoom",
+        }
+    }
+
+    // Lumps have no type information, so like the original we go off
+    // of the name, the namespace it is in and whether the data is valid
+    pub fn detect(lump_name: &str, namespace: LumpNamespace, data: &[u8]) -> Self {
+        if data.is_empty() || MAP_LUMP_NAMES.contains(&lump_name) {
+            return LumpFormat::Raw;
+        }
+
+        match namespace {
+            LumpNamespace::Flats if data.len() == FLAT_WIDTH * FLAT_HEIGHT => LumpFormat::Flat,
+            LumpNamespace::Flats => LumpFormat::Raw,
+            LumpNamespace::Sprites | LumpNamespace::Patches => {
+                if Picture::from_lump(data).is_ok() {
+                    LumpFormat::Picture
+                } else {
+                    LumpFormat::Raw
+                }
+            }
+            LumpNamespace::Global => {
+                if lump_name == "ENDOOM" && data.len() == ENDOOM_SIZE {
+                    LumpFormat::Endoom
+                } else if lump_name.starts_with("DS") && DmxSound::from_lump(data).is_ok() {
+                    LumpFormat::Sound
+                } else if lump_name.starts_with("DP") && PcSpeakerSound::from_lump(data).is_ok() {
+                    LumpFormat::PcSpeaker
+                } else if MusFile::from_lump(data).is_ok() {
+                    LumpFormat::Music
+                } else if !NON_PICTURE_LUMP_NAMES.contains(&lump_name)
+                    && !lump_name.starts_with("DEMO")
+                    && Picture::from_lump(data).is_ok()
+                {
+                    LumpFormat::Picture
+                } else {
+                    LumpFormat::Raw
+                }
+            }
+        }
+    }
+
+    pub fn file_extension(&self) -> Option<&str> {
+        match self {
+            LumpFormat::Raw => None,
+            LumpFormat::Picture | LumpFormat::Flat => Some("png"),
+            LumpFormat::Sound | LumpFormat::PcSpeaker => Some("wav"),
+            LumpFormat::Music => Some("mid"),
+            LumpFormat::Endoom => Some("ans"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphics::Picture;
+    use crate::sound::dmx::DmxSound;
+    use crate::wad::format::LumpFormat;
+    use crate::wad::namespace::LumpNamespace;
+
+    fn create_picture_lump() -> Vec<u8> {
+        Picture {
+            width: 2,
+            height: 2,
+            left_offset: 0,
+            top_offset: 0,
+            pixels: vec![Some(1), Some(2), None, Some(3)],
+        }
+        .to_lump()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_lump_format_name_round_trip() {
+        for format in [
+            LumpFormat::Raw,
+            LumpFormat::Picture,
+            LumpFormat::Flat,
+            LumpFormat::Sound,
+            LumpFormat::PcSpeaker,
+            LumpFormat::Music,
+            LumpFormat::Endoom,
+        ] {
+            assert_eq!(LumpFormat::from_name(format.to_str()), Some(format));
+        }
+
+        assert_eq!(LumpFormat::from_name("unknown"), None);
+    }
+
+    #[test]
+    fn test_lump_format_detect_uses_namespace() {
+        assert_eq!(
+            LumpFormat::detect("FLOOR0_1", LumpNamespace::Flats, &[0; 4096]),
+            LumpFormat::Flat
+        );
+        assert_eq!(
+            LumpFormat::detect("F1_START", LumpNamespace::Flats, &[]),
+            LumpFormat::Raw
+        );
+        assert_eq!(
+            LumpFormat::detect("TROOA1", LumpNamespace::Sprites, &create_picture_lump()),
+            LumpFormat::Picture
+        );
+        assert_eq!(
+            LumpFormat::detect("TROOA1", LumpNamespace::Sprites, &[1, 2, 3]),
+            LumpFormat::Raw
+        );
+    }
+
+    #[test]
+    fn test_lump_format_detect_global_lumps() {
+        let sound: Vec<u8> = DmxSound {
+            sample_rate: 11025,
+            samples: vec![128; 64],
+        }
+        .to_lump();
+
+        assert_eq!(
+            LumpFormat::detect("DSPISTOL", LumpNamespace::Global, &sound),
+            LumpFormat::Sound
+        );
+        assert_eq!(
+            LumpFormat::detect("DPPISTOL", LumpNamespace::Global, &[0, 0, 2, 0, 10, 20]),
+            LumpFormat::PcSpeaker
+        );
+        assert_eq!(
+            LumpFormat::detect("ENDOOM", LumpNamespace::Global, &[0; 4000]),
+            LumpFormat::Endoom
+        );
+        assert_eq!(
+            LumpFormat::detect("TITLEPIC", LumpNamespace::Global, &create_picture_lump()),
+            LumpFormat::Picture
+        );
+        assert_eq!(
+            LumpFormat::detect("COLORMAP", LumpNamespace::Global, &create_picture_lump()),
+            LumpFormat::Raw
+        );
+        assert_eq!(
+            LumpFormat::detect("THINGS", LumpNamespace::Global, &create_picture_lump()),
+            LumpFormat::Raw
+        );
+    }
+}
@@ -113,7 +113,7 @@ pub fn get_iwad_name_from_iwad_paths(iwad_paths: &[PathBuf]) -> &str {
         .find_map(|path_buf| {
             if path_buf
                 .extension()
-                .map_or(false, |extension| extension.eq("wad"))
+                .is_some_and(|extension| extension.eq("wad"))
             {
                 path_buf.file_name().unwrap_or(OsStr::new("")).to_str()
             } else {
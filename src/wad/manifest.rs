@@ -0,0 +1,235 @@
+use crate::wad::format::LumpFormat;
+use crate::wad::namespace::LumpNamespace;
+use crate::wad::{WadError, WadID};
+
+pub const MANIFEST_FILE_NAME: &str = "manifest.txt";
+const MANIFEST_HEADER: &str = "# zz-doom wad manifest";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub name: String,
+    pub namespace: LumpNamespace,
+    pub format: LumpFormat,
+    // Where the lump was in the original file so it can be rebuilt identically
+    pub offset: u32,
+    pub size: u32,
+    // Relative to the manifest directory
+    pub file: String,
+}
+
+// Describes a wad that has been extracted into a directory.
+// Entries are in the same order as the wads lump directory
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WadManifest {
+    pub id: WadID,
+    pub directory_offset: u32,
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl WadManifest {
+    pub fn to_text(&self) -> String {
+        let mut text: String = format!(
+            "{}\nid {}\ndirectory_offset {}\n# lump <name> <namespace> <format> <offset> <size> <file>\n",
+            MANIFEST_HEADER,
+            self.id.to_str(),
+            self.directory_offset
+        );
+
+        for entry in &self.entries {
+            text.push_str(&format!(
+                "lump {} {} {} {} {} {}\n",
+                entry.name,
+                entry.namespace.to_str(),
+                entry.format.to_str(),
+                entry.offset,
+                entry.size,
+                entry.file
+            ));
+        }
+
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, WadError> {
+        let mut id: Option<WadID> = None;
+        let mut directory_offset: Option<u32> = None;
+        let mut entries: Vec<ManifestEntry> = Vec::new();
+
+        for (line_index, line) in text.lines().enumerate() {
+            let line: &str = line.trim();
+            let line_number: usize = line_index + 1;
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value): (&str, &str) = line.split_once(' ').unwrap_or((line, ""));
+
+            match key {
+                "id" => {
+                    id = match value {
+                        "IWAD" | "PWAD" => Some(WadID::from(String::from(value))),
+                        _ => {
+                            return Err(WadError::InvalidManifest(format!(
+                                "Invalid wad id {} on line {}",
+                                value, line_number
+                            )))
+                        }
+                    }
+                }
+                "directory_offset" => {
+                    directory_offset = Some(parse_number(value, line_number)?);
+                }
+                "lump" => entries.push(parse_entry(value, line_number)?),
+                _ => {
+                    return Err(WadError::InvalidManifest(format!(
+                        "Unknown key {} on line {}",
+                        key, line_number
+                    )))
+                }
+            }
+        }
+
+        Ok(WadManifest {
+            id: id.ok_or_else(|| WadError::InvalidManifest(String::from("Missing wad id")))?,
+            directory_offset: directory_offset.ok_or_else(|| {
+                WadError::InvalidManifest(String::from("Missing directory offset"))
+            })?,
+            entries,
+        })
+    }
+}
+
+fn parse_number(value: &str, line_number: usize) -> Result<u32, WadError> {
+    value.parse::<u32>().map_err(|_| {
+        WadError::InvalidManifest(format!("Invalid number {} on line {}", value, line_number))
+    })
+}
+
+fn parse_entry(value: &str, line_number: usize) -> Result<ManifestEntry, WadError> {
+    let fields: Vec<&str> = value.splitn(6, ' ').collect();
+
+    if fields.len() != 6 {
+        return Err(WadError::InvalidManifest(format!(
+            "Expected 6 lump fields on line {}, found {}",
+            line_number,
+            fields.len()
+        )));
+    }
+
+    let namespace: LumpNamespace = LumpNamespace::from_name(fields[1]).ok_or_else(|| {
+        WadError::InvalidManifest(format!(
+            "Invalid namespace {} on line {}",
+            fields[1], line_number
+        ))
+    })?;
+
+    let format: LumpFormat = LumpFormat::from_name(fields[2]).ok_or_else(|| {
+        WadError::InvalidManifest(format!(
+            "Invalid format {} on line {}",
+            fields[2], line_number
+        ))
+    })?;
+
+    Ok(ManifestEntry {
+        name: String::from(fields[0]),
+        namespace,
+        format,
+        offset: parse_number(fields[3], line_number)?,
+        size: parse_number(fields[4], line_number)?,
+        file: String::from(fields[5]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wad::format::LumpFormat;
+    use crate::wad::manifest::{ManifestEntry, WadManifest};
+    use crate::wad::namespace::LumpNamespace;
+    use crate::wad::{WadError, WadID};
+
+    fn create_manifest() -> WadManifest {
+        WadManifest {
+            id: WadID::Pwad,
+            directory_offset: 4108,
+            entries: vec![
+                ManifestEntry {
+                    name: String::from("F_START"),
+                    namespace: LumpNamespace::Global,
+                    format: LumpFormat::Raw,
+                    offset: 12,
+                    size: 0,
+                    file: String::from("00000_F_START.lmp"),
+                },
+                ManifestEntry {
+                    name: String::from("FLOOR0_1"),
+                    namespace: LumpNamespace::Flats,
+                    format: LumpFormat::Flat,
+                    offset: 12,
+                    size: 4096,
+                    file: String::from("flats/my floor.png"),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_wad_manifest_text_round_trip() {
+        let manifest: WadManifest = create_manifest();
+
+        assert_eq!(
+            WadManifest::from_text(&manifest.to_text()).unwrap(),
+            manifest
+        );
+    }
+
+    #[test]
+    fn test_wad_manifest_from_text_returns_error_for_invalid_text() {
+        assert_eq!(
+            WadManifest::from_text("directory_offset 12"),
+            Err(WadError::InvalidManifest(String::from("Missing wad id")))
+        );
+
+        assert_eq!(
+            WadManifest::from_text("id IWAD"),
+            Err(WadError::InvalidManifest(String::from(
+                "Missing directory offset"
+            )))
+        );
+
+        assert_eq!(
+            WadManifest::from_text("id XWAD"),
+            Err(WadError::InvalidManifest(String::from(
+                "Invalid wad id XWAD on line 1"
+            )))
+        );
+
+        assert_eq!(
+            WadManifest::from_text("id IWAD\nlump DATA global raw 12"),
+            Err(WadError::InvalidManifest(String::from(
+                "Expected 6 lump fields on line 2, found 4"
+            )))
+        );
+
+        assert_eq!(
+            WadManifest::from_text("id IWAD\nlump DATA nowhere raw 12 13 data.lmp"),
+            Err(WadError::InvalidManifest(String::from(
+                "Invalid namespace nowhere on line 2"
+            )))
+        );
+
+        assert_eq!(
+            WadManifest::from_text("id IWAD\nlump DATA global raw twelve 13 data.lmp"),
+            Err(WadError::InvalidManifest(String::from(
+                "Invalid number twelve on line 2"
+            )))
+        );
+
+        assert_eq!(
+            WadManifest::from_text("id IWAD\ncolor red"),
+            Err(WadError::InvalidManifest(String::from(
+                "Unknown key color on line 2"
+            )))
+        );
+    }
+}
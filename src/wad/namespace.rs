@@ -0,0 +1,165 @@
+use crate::wad::Lump;
+
+// Doom groups sprites, flats and wall patches between marker lumps
+// (S_START/S_END, F_START/F_END, P_START/P_END). PWADs commonly used the
+// doubled versions(SS_START, FF_END, etc) since the original engine
+// only recognised the single letter ones in the IWAD
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LumpNamespace {
+    Global,
+    Sprites,
+    Flats,
+    Patches,
+}
+
+impl LumpNamespace {
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value {
+            "global" => Some(LumpNamespace::Global),
+            "sprites" => Some(LumpNamespace::Sprites),
+            "flats" => Some(LumpNamespace::Flats),
+            "patches" => Some(LumpNamespace::Patches),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            LumpNamespace::Global => "global",
+            LumpNamespace::Sprites => "sprites",
+            LumpNamespace::Flats => "flats",
+            LumpNamespace::Patches => "patches",
+        }
+    }
+
+    fn from_start_marker(lump_name: &str) -> Option<Self> {
+        match lump_name {
+            "S_START" | "SS_START" => Some(LumpNamespace::Sprites),
+            "F_START" | "FF_START" => Some(LumpNamespace::Flats),
+            "P_START" | "PP_START" => Some(LumpNamespace::Patches),
+            _ => None,
+        }
+    }
+
+    fn from_end_marker(lump_name: &str) -> Option<Self> {
+        match lump_name {
+            "S_END" | "SS_END" => Some(LumpNamespace::Sprites),
+            "F_END" | "FF_END" => Some(LumpNamespace::Flats),
+            "P_END" | "PP_END" => Some(LumpNamespace::Patches),
+            _ => None,
+        }
+    }
+}
+
+// Returns the namespace of every lump in directory order.
+// The marker lumps themselves are considered global.
+pub fn lump_namespaces(lumps: &[Lump]) -> Vec<LumpNamespace> {
+    let mut current_namespace: LumpNamespace = LumpNamespace::Global;
+
+    lumps
+        .iter()
+        .map(|lump| {
+            let lump_name: &str = lump.name.trim_end_matches('\0');
+
+            if let Some(namespace) = LumpNamespace::from_start_marker(lump_name) {
+                current_namespace = namespace;
+                LumpNamespace::Global
+            } else if LumpNamespace::from_end_marker(lump_name) == Some(current_namespace) {
+                current_namespace = LumpNamespace::Global;
+                LumpNamespace::Global
+            } else {
+                current_namespace
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wad::namespace::{lump_namespaces, LumpNamespace};
+    use crate::wad::Lump;
+    use std::path::PathBuf;
+
+    fn create_lump(name: &str) -> Lump {
+        Lump {
+            name: String::from(name),
+            file_path: PathBuf::from("test.wad"),
+            file_position: 0,
+            size: 0,
+            should_reload: false,
+        }
+    }
+
+    #[test]
+    fn test_lump_namespaces_tracks_marker_lumps() {
+        let lumps: Vec<Lump> = [
+            "PLAYPAL",
+            "S_START",
+            "TROOA1",
+            "S_END",
+            "FF_START",
+            "F1_START",
+            "FLOOR0_1",
+            "FF_END\0\0",
+            "P_START",
+            "WALL00_1",
+            "P_END",
+            "ENDOOM",
+        ]
+        .iter()
+        .map(|name| create_lump(name))
+        .collect();
+
+        assert_eq!(
+            lump_namespaces(&lumps),
+            vec![
+                LumpNamespace::Global,
+                LumpNamespace::Global,
+                LumpNamespace::Sprites,
+                LumpNamespace::Global,
+                LumpNamespace::Global,
+                LumpNamespace::Flats,
+                LumpNamespace::Flats,
+                LumpNamespace::Global,
+                LumpNamespace::Global,
+                LumpNamespace::Patches,
+                LumpNamespace::Global,
+                LumpNamespace::Global,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lump_namespaces_ignores_mismatched_end_marker() {
+        let lumps: Vec<Lump> = ["S_START", "F_END", "TROOA1"]
+            .iter()
+            .map(|name| create_lump(name))
+            .collect();
+
+        assert_eq!(
+            lump_namespaces(&lumps),
+            vec![
+                LumpNamespace::Global,
+                LumpNamespace::Sprites,
+                LumpNamespace::Sprites,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lump_namespace_name_round_trip() {
+        for namespace in [
+            LumpNamespace::Global,
+            LumpNamespace::Sprites,
+            LumpNamespace::Flats,
+            LumpNamespace::Patches,
+        ] {
+            assert_eq!(
+                LumpNamespace::from_name(namespace.to_str()),
+                Some(namespace)
+            );
+        }
+
+        assert_eq!(LumpNamespace::from_name("unknown"), None);
+    }
+}